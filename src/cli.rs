@@ -1,33 +1,95 @@
 mod forge {
+    pub mod forgejo;
     pub mod gitea;
     pub mod github;
     pub mod gitlab;
 
     mod api_type;
+    mod auth_guard;
+    mod capability;
     mod http_client;
+    mod identity;
+    mod labels;
+    mod precheck;
+    mod server_version;
+    mod url_encoding;
 
     pub(crate) use api_type::function;
-    pub use api_type::{ApiType, guess_api_type_from_host};
-    pub use http_client::HttpClient;
+    pub use api_type::{
+        API_LONG_HELP, API_URL_LONG_HELP, ApiType, ApiUrl, detect_api_type, get_api_type,
+        parse_api_url,
+    };
+    #[cfg(feature = "self-update")]
+    pub use auth_guard::allow_remote as allow_auth_for_remote;
+    #[cfg(all(test, feature = "self-update"))]
+    pub use auth_guard::is_allowed_for_remote as is_auth_allowed_for_remote;
+    pub use auth_guard::set_forced as set_force_auth_host;
+    pub use capability::{Capability, Support, supports};
+    #[cfg(feature = "self-update")]
+    pub use http_client::WithHttpStatusOk;
+    pub use http_client::{HttpClient, PaginatedResponse};
+    pub use identity::set_refresh as set_refresh_identity;
+    pub use labels::{LabelFilter, MissingLabels, find_labels, resolve_labels};
+    pub use precheck::check_repo;
 }
 
+mod audit;
+mod branch;
 mod browse;
+mod cache;
+mod changelog;
 mod completions;
 mod config;
+mod context;
+mod doctor;
+mod hook;
+mod init;
 mod issue;
 mod pr;
+mod repo;
+#[cfg(feature = "self-update")]
+mod self_update;
+mod version;
 
+pub use audit::{AuditCommand, list_audit_log};
+pub use branch::{BranchCommand, delete_merged_branches, list_branches};
 pub use browse::browse_repository;
+pub use cache::{CacheCommand, cache_clear, cache_list, cache_path};
+pub use changelog::generate_changelog;
 pub use completions::generate_completions;
-pub use config::{ConfigCommand, config_edit, config_get, config_set, config_unset};
-pub use issue::{IssueCommand, create_issue, list_issues};
-pub use pr::{PrCommand, checkout_pr, create_pr, list_prs};
+pub use config::{
+    Config, ConfigCommand, config_edit, config_get, config_key, config_set, config_unset,
+    get_audit_log_setting, get_date_style_setting, get_mutation_interval_setting,
+    get_prompt_setting, get_show_rate_limit_setting,
+};
+pub use context::print_context;
+pub use doctor::doctor;
+pub use forge::{set_force_auth_host, set_refresh_identity};
+pub use hook::{HookCommand, disable_hook, enable_hook, list_hooks, ping_hook};
+pub use init::init;
+pub use issue::{
+    IssueCommand, IssueLabelCommand, add_issue_labels, assign_issue, create_issue, export_issues,
+    import_issues, list_issues, pin_issue, remove_issue_labels, set_issue_labels, snapshot_issues,
+    unassign_issue, unpin_issue, view_issue,
+};
+pub use pr::{PrCommand, checkout_pr, create_pr, list_prs, merge_pr, update_pr_reviewers, view_pr};
+pub use repo::{RepoCommand, edit_repo, list_repos, view_repo};
+#[cfg(feature = "self-update")]
+pub use self_update::self_update;
+pub use version::print_version;
 
 use clap::{Parser, Subcommand};
 
-use crate::cli::{
-    browse::BrowseCommandArgs, completions::CompletionsCommandArgs, config::ConfigCommandArgs,
-    issue::IssueCommandArgs, pr::PrCommandArgs,
+use crate::{
+    cli::{
+        audit::AuditCommandArgs, branch::BranchCommandArgs, browse::BrowseCommandArgs,
+        cache::CacheCommandArgs, changelog::ChangelogCommandArgs,
+        completions::CompletionsCommandArgs, config::ConfigCommandArgs,
+        context::ContextCommandArgs, doctor::DoctorCommandArgs, hook::HookCommandArgs,
+        init::InitCommandArgs, issue::IssueCommandArgs, pr::PrCommandArgs, repo::RepoCommandArgs,
+        version::VersionCommandArgs,
+    },
+    io::{ColorChoice, PromptMode},
 };
 
 #[derive(Parser)]
@@ -35,25 +97,118 @@ use crate::cli::{
 pub struct Cli {
     #[command(subcommand)]
     pub subcommand: GitForgeCommand,
+
+    /// Print more diagnostics. Can be repeated (e.g. -vv)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Stream the output of git commands (e.g. fetch progress) while they run.
+    /// Implied by --verbose
+    #[arg(long, global = true)]
+    pub verbose_git: bool,
+
+    /// Print the remaining API rate limit of the forge to stderr after the
+    /// command. The interactive TUI shows it in its info bar. Can also be
+    /// enabled with the `http/show-rate-limit=true` setting
+    #[arg(long, global = true)]
+    pub show_rate_limit: bool,
+
+    /// Single-quote the fields of TSV and CSV output for the shell, e.g. to
+    /// `eval` them in scripts
+    #[arg(long, global = true)]
+    pub escape_shell: bool,
+
+    /// Send the token even if the API URL's host isn't a host of the git
+    /// remote, its `api-base-url` or the `auth/allowed-hosts` setting
+    #[arg(long, global = true)]
+    pub force_auth_host: bool,
+
+    /// Fetch the authenticated user again instead of using the one cached for
+    /// a day, e.g. after switching accounts with the same token
+    #[arg(long, global = true)]
+    pub refresh_identity: bool,
+
+    /// When to style the output. The NO_COLOR and CLICOLOR_FORCE environment
+    /// variables are respected with `auto`
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Never style the output. Same as --color never
+    #[arg(long, global = true, conflicts_with = "color")]
+    pub no_color: bool,
+
+    /// When to ask for missing input. `never` fails with the option to pass
+    /// instead, e.g. in CI jobs. `always` also asks for the confirmations that
+    /// --yes skips. Can also be set with the `prompt` setting [default: auto]
+    #[arg(long, global = true, value_name = "WHEN")]
+    pub prompt: Option<PromptMode>,
+}
+
+impl Cli {
+    pub fn get_color_choice(&self) -> ColorChoice {
+        if self.no_color {
+            ColorChoice::Never
+        } else {
+            self.color
+        }
+    }
 }
 
 #[derive(Subcommand)]
 pub enum GitForgeCommand {
+    /// List the changes recorded in the audit log, see the `audit-log` setting.
+    Audit(AuditCommandArgs),
+
+    /// List the branches of the remote repository and clean up merged ones.
+    Branch(BranchCommandArgs),
+
     /// Open repository links in your browser or print them to stdout
     #[command(alias = "b")]
     Browse(BrowseCommandArgs),
 
+    /// Manage the data stored on disk, e.g. issue snapshots.
+    Cache(CacheCommandArgs),
+
+    /// Print the merged pull requests of a milestone or since a tag as
+    /// Markdown, grouped into sections by their labels.
+    Changelog(ChangelogCommandArgs),
+
     /// Generate shell completions.
     Completions(CompletionsCommandArgs),
 
     #[command(about = "Manage configuration settings. Currently supported settings:
 
+  - audit-log: Global setting to append every change to a forge (API requests besides GETs) and every push to the audit log in the state directory. See `git forge audit list`. E.g. `git forge config set audit-log true`
+  - audit-log/max-size: Global setting for the size at which the audit log is rotated, keeping the previous file. Defaults to 10MiB. E.g. `git forge config set audit-log/max-size 1MiB`
+  - auth/allowed-hosts: Hosts besides the git remote's and its API's that tokens may be sent to (comma-separated, optionally with a port). E.g. `git forge config set auth/allowed-hosts proxy.internal:8443`
+  - branch/protect-patterns: Globs of branches (comma-separated) that `branch delete-merged` never deletes. E.g. `git forge config set branch/protect-patterns 'release/*,staging'`
   - editor-command: This command will be called instead of the default text editor when using the --editor flag. E.g. for vscode use `code --wait`
+  - forge-type: Host scope setting for forges whose type can't be detected from the hostname. The --api option takes precedence. E.g. `git forge config set --scope host forge-type gitlab`
+  - http/mutation-interval-ms: Global setting for the minimum interval in milliseconds between API requests that change something, e.g. in bulk commands like `issue import`, to stay below the secondary rate limits of the forges. Defaults to 500, 0 turns it off. E.g. `git forge config set http/mutation-interval-ms 1000`
+  - http/show-rate-limit: Global setting to always print the remaining API rate limit after a command like the --show-rate-limit flag does. E.g. `git forge config set http/show-rate-limit true`
+  - prompt: Global setting for when to ask for missing input like the --prompt option. E.g. `git forge config set prompt never`
+  - locale/date-format: Global setting for the format of the timestamps in human-readable output like `pr view` as a strftime pattern in UTC. TSV, CSV and JSON output always uses RFC 3339. E.g. `git forge config set locale/date-format '%Y-%m-%d %H:%M'`
+  - locale/relative-dates: Global setting to show the timestamps in human-readable output as how long ago they were, e.g. `3d ago`. It takes precedence over locale/date-format. E.g. `git forge config set locale/relative-dates true`
+  - web-base-url, api-base-url: Host scope settings for self-hosted forges whose web interface or API isn't reachable via https on the host of the git remote URL. E.g. `git forge config set --scope host web-base-url http://git.internal:3000`
   - <CLI_OPTIONS>: Most CLI options can be configured with a scoped default setting. See the config subcommands' help for more details.
     ")]
     #[command(alias = "c")]
     Config(ConfigCommandArgs),
 
+    /// Print the remote, forge, default branch and PR of the current branch
+    /// without network requests, e.g. for shell prompts.
+    Context(ContextCommandArgs),
+
+    /// Print information for debugging problems, e.g. the log file location.
+    Doctor(DoctorCommandArgs),
+
+    /// List, test, enable and disable the webhooks of the repository.
+    Hook(HookCommandArgs),
+
+    /// Detect the forge of a git remote and save the settings for it to your
+    /// config file or to the repository's .git-forge.toml.
+    Init(InitCommandArgs),
+
     /// Interact with issues.
     #[command(alias = "i")]
     Issue(IssueCommandArgs),
@@ -61,4 +216,15 @@ pub enum GitForgeCommand {
     /// Interact with pull requests.
     #[command(alias = "p")]
     Pr(PrCommandArgs),
+
+    /// View and edit the repository's metadata.
+    #[command(alias = "r")]
+    Repo(RepoCommandArgs),
+
+    /// Replace git-forge with the latest release.
+    #[cfg(feature = "self-update")]
+    SelfUpdate(self_update::SelfUpdateCommandArgs),
+
+    /// Print the version and optionally check for updates.
+    Version(VersionCommandArgs),
 }
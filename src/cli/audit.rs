@@ -0,0 +1,91 @@
+//! The `audit` subcommand.
+
+use clap::{Args, Subcommand, ValueEnum};
+use time::OffsetDateTime;
+
+use crate::{
+    audit, date,
+    io::{self, OutputFormat, Truncation},
+};
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `audit` subcommand.
+#[derive(Args)]
+pub struct AuditCommandArgs {
+    #[command(subcommand)]
+    pub subcommand: AuditCommand,
+}
+
+/// Available subcommands for the audit log.
+#[derive(Subcommand)]
+pub enum AuditCommand {
+    /// List the recorded changes, oldest first.
+    #[command(alias = "ls")]
+    List(AuditListCommandArgs),
+}
+
+/// Command-line arguments for listing the audit log.
+#[derive(Args)]
+pub struct AuditListCommandArgs {
+    /// Output format
+    #[arg(short = 'o', long, default_value = "tsv")]
+    format: AuditFormat,
+
+    /// Don't truncate the cells of rows that are wider than the terminal
+    #[arg(long)]
+    no_truncate: bool,
+
+    /// Only list the changes since this date, e.g. `7d` or `2024-01-01`
+    #[arg(long, value_name = "DATE", value_parser = date::parse_date)]
+    since: Option<OffsetDateTime>,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+/// Output format of the audit log.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum AuditFormat {
+    /// Tab-separated values format.
+    Tsv,
+    /// JSON format, e.g. for log ingestion.
+    Json,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Prints the entries of the audit log.
+pub fn list_audit_log(args: AuditListCommandArgs) -> anyhow::Result<()> {
+    let entries = audit::read_entries(args.since)?;
+
+    if entries.is_empty() && matches!(args.format, AuditFormat::Tsv) {
+        if !audit::is_enabled() {
+            eprintln!(
+                "The audit log is disabled. Enable it with `git forge config set audit-log true`"
+            );
+        }
+
+        return Ok(());
+    }
+
+    let format = match args.format {
+        AuditFormat::Tsv => OutputFormat::Tsv,
+        AuditFormat::Json => OutputFormat::Json,
+    };
+
+    io::print_list(
+        &entries,
+        &[] as &[String],
+        &format,
+        Truncation {
+            fit_to_terminal: !args.no_truncate,
+            max_title_width: None,
+        },
+    )
+}
@@ -0,0 +1,849 @@
+//! The `branch` subcommand.
+
+use anyhow::Context;
+use clap::{Args, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{
+    cli::{
+        config::{self, Config},
+        forge::{self, ApiType, ApiUrl, HttpClient, forgejo, gitea, github, gitlab},
+        pr::Pr,
+    },
+    date::{self, DateRange, Timestamp},
+    git::GitRemoteData,
+    io::{self, OutputFormat, Truncation},
+    throttle,
+};
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+const DEFAULT_REMOTE: &str = "origin";
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+/// Comma-separated globs of branches that `branch delete-merged` never
+/// deletes, e.g. `release/*`.
+const PROTECT_PATTERNS_CONFIG_PATH: &str = "branch/protect-patterns";
+
+/// Command-line arguments for the `branch` subcommand.
+#[derive(Args)]
+pub struct BranchCommandArgs {
+    #[command(subcommand)]
+    pub subcommand: BranchCommand,
+}
+
+/// Available subcommands for branch operations.
+#[derive(Subcommand)]
+pub enum BranchCommand {
+    /// Delete the remote branches that are merged into the default branch or
+    /// whose pull request was merged.
+    DeleteMerged(BranchDeleteMergedCommandArgs),
+
+    /// List the branches of the remote repository with their protection and
+    /// open pull request.
+    #[command(alias = "ls")]
+    List(BranchListCommandArgs),
+}
+
+/// Command-line arguments for deleting merged branches.
+#[derive(Args)]
+pub struct BranchDeleteMergedCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Print which branches would be deleted or kept and why, sorted by name,
+    /// without deleting them
+    #[arg(long, conflicts_with = "yes")]
+    dry_run: bool,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Only delete the branches whose last commit is older than this, e.g.
+    /// `90d` or `2024-01-01`
+    #[arg(long, value_name = "DATE", value_parser = date::parse_date)]
+    stale: Option<OffsetDateTime>,
+
+    /// Delete the branches without asking for confirmation
+    #[arg(short, long)]
+    yes: bool,
+}
+
+/// Command-line arguments for listing branches.
+#[derive(Args)]
+pub struct BranchListCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
+
+    /// Fields to include in output (comma-separated). The columns and JSON keys
+    /// follow their order. Use `all` for all fields
+    #[arg(short, long, value_delimiter = ',')]
+    fields: Vec<BranchField>,
+
+    /// Output format
+    #[arg(short = 'o', long)]
+    format: Option<OutputFormat>,
+
+    /// Only list branches whose commits are all in the default branch. This
+    /// needs an extra API request per branch on GitHub and Gitea/Forgejo
+    #[arg(long, conflicts_with = "no_merged")]
+    merged: bool,
+
+    /// Only list branches with commits that aren't in the default branch. This
+    /// needs an extra API request per branch on GitHub and Gitea/Forgejo
+    #[arg(long)]
+    no_merged: bool,
+
+    /// Don't truncate the cells of rows that are wider than the terminal
+    #[arg(long)]
+    no_truncate: bool,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Mark the branches whose last commit is older than this, e.g. `90d` or
+    /// `2024-01-01`, as stale. Other branches are dimmed on a terminal
+    #[arg(long, value_name = "DATE", value_parser = date::parse_date)]
+    stale: Option<OffsetDateTime>,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum BranchField {
+    Name,
+    Sha,
+    LastCommitAt,
+    Age,
+    Protected,
+    Merged,
+    Pr,
+    Stale,
+    All,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Branch {
+    /// The branch name.
+    pub name: String,
+    /// The commit the branch points to.
+    pub sha: String,
+    /// Timestamp of the last commit. Not every forge returns it when listing
+    /// branches, otherwise it's only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_commit_at: Option<Timestamp>,
+    /// How long ago the last commit was, e.g. `3d`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<String>,
+    /// Whether the branch is protected, e.g. against force pushes.
+    pub protected: bool,
+    /// Whether the default branch contains all commits of the branch. Not
+    /// every forge returns it when listing branches, otherwise it's only
+    /// fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merged: Option<bool>,
+    /// The number of the open pull request from or into the branch. Only
+    /// fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr: Option<u32>,
+    /// Whether the last commit is older than --stale.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<bool>,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Lists the branches of the repository of the git remote.
+pub fn list_branches(mut args: BranchListCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("branch/list/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "branch/list",
+        [api, api_url, auth, format, no_truncate]
+    );
+
+    let mut default_fields = vec![
+        BranchField::Name,
+        BranchField::Age,
+        BranchField::Protected,
+        BranchField::Pr,
+    ];
+
+    if args.stale.is_some() {
+        default_fields.push(BranchField::Stale);
+    }
+
+    args.fields = io::resolve_fields(
+        args.fields,
+        config.get_string("branch/list/fields", Some(&remote)),
+        default_fields,
+    )?;
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let branches = get_branches_with_fields(&HttpClient::new(), &remote, &api_type, &args)?;
+
+    if !branches.is_empty() {
+        io::print_list_with_dimmed_rows(
+            &branches,
+            &args.fields,
+            &args.format.unwrap_or_default(),
+            Truncation {
+                fit_to_terminal: !args.no_truncate,
+                max_title_width: None,
+            },
+            |branch| branch.stale == Some(false),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Deletes the merged branches of the repository of the git remote. The
+/// default branch, protected branches, branches matching the
+/// `branch/protect-patterns` setting and branches of open PRs are kept.
+pub fn delete_merged_branches(mut args: BranchDeleteMergedCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("branch/delete-merged/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "branch/delete-merged",
+        [api, api_url]
+    );
+
+    let protect_patterns: Vec<String> = config
+        .get_string(PROTECT_PATTERNS_CONFIG_PATH, Some(&remote))
+        .map(|patterns| {
+            patterns
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+    let api_url = args.api_url.as_deref();
+    let get_branches = forge::function!(api_type, get_branches);
+    let get_open_prs = forge::function!(api_type, get_open_prs);
+    let branches =
+        get_branches(&http_client, &remote, api_url, true).context("Failed fetching branches")?;
+    let default_branch = get_default_branch(&http_client, &remote, &api_type, api_url, true)?;
+    let open_prs = get_open_prs(&http_client, &remote, api_url, true)
+        .context("Failed fetching the open pull requests")?;
+    let mut kept: Vec<(String, String)> = vec![];
+    let mut candidates: Vec<Branch> = vec![];
+
+    for branch in branches {
+        match get_keep_reason(&branch, &default_branch, &protect_patterns, &open_prs) {
+            Some(reason) => kept.push((branch.name, reason)),
+            None => candidates.push(branch),
+        }
+    }
+
+    if let Some(stale) = args.stale {
+        add_last_commit_dates(
+            &http_client,
+            &remote,
+            &api_type,
+            api_url,
+            &mut candidates,
+            true,
+        )?;
+
+        let stale_range = DateRange {
+            after: None,
+            before: Some(stale),
+        };
+        let (stale_branches, recent_branches): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .partition(|branch| stale_range.contains(branch.last_commit_at));
+
+        kept.extend(
+            recent_branches
+                .into_iter()
+                .map(|branch| (branch.name, String::from("not stale"))),
+        );
+        candidates = stale_branches;
+    }
+
+    let deleted = find_merged_branches(
+        &http_client,
+        &remote,
+        &api_type,
+        api_url,
+        candidates,
+        &default_branch,
+        &mut kept,
+    )?;
+
+    kept.sort();
+
+    if args.dry_run {
+        for (name, reason) in &kept {
+            println!("keep\t{name}\t{reason}");
+        }
+
+        for (name, reason) in &deleted {
+            println!("delete\t{name}\t{reason}");
+        }
+
+        eprintln!(
+            "Would delete {} branches and keep {}",
+            deleted.len(),
+            kept.len()
+        );
+
+        return Ok(());
+    }
+
+    if deleted.is_empty() {
+        eprintln!("No merged branches to delete.");
+
+        return Ok(());
+    }
+
+    eprintln!("These branches will be deleted:");
+
+    for (name, reason) in &deleted {
+        eprintln!("  {name} ({reason})");
+    }
+
+    if !io::skips_confirmation(args.yes)
+        && !io::prompt_confirm(
+            &format!("Delete {} branches?", deleted.len()),
+            false,
+            "--yes",
+        )?
+    {
+        anyhow::bail!("Aborted. Pass --yes to delete the branches without confirmation");
+    }
+
+    let delete_branch = forge::function!(api_type, delete_branch);
+
+    for (index, (name, reason)) in deleted.iter().enumerate() {
+        delete_branch(&http_client, &remote, api_url, name).with_context(|| {
+            format!(
+                "Failed to delete {name}. {index} of {} branches were deleted",
+                deleted.len()
+            )
+        })?;
+
+        println!("deleted\t{name}\t{reason}");
+        eprintln!(
+            "Deleted {}/{} branches{}",
+            index + 1,
+            deleted.len(),
+            throttle::take_wait_note()
+        );
+    }
+
+    eprintln!("Deleted {} branches and kept {}", deleted.len(), kept.len());
+
+    Ok(())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// Finds the candidates that are merged into the default branch or whose PR
+/// was merged and returns them with the reason, sorted by name. The others are
+/// added to `kept`.
+fn find_merged_branches(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    mut candidates: Vec<Branch>,
+    default_branch: &str,
+    kept: &mut Vec<(String, String)>,
+) -> anyhow::Result<Vec<(String, String)>> {
+    add_merged(
+        http_client,
+        remote,
+        api_type,
+        api_url,
+        &mut candidates,
+        default_branch,
+        true,
+    )?;
+
+    let get_merged_prs_for_branch = forge::function!(api_type, get_merged_prs_for_branch);
+    // Squash and rebase merges leave the branch's commits out of the default
+    // branch, so the merged PRs of the branch are checked as well
+    let merged_prs = fetch_for_each_branch(&candidates, |branch| {
+        if branch.merged == Some(true) {
+            return Ok(None);
+        }
+
+        get_merged_prs_for_branch(http_client, remote, api_url, &branch.name, true)
+            .map(|prs| find_merged_pr(&prs, &branch.sha))
+            .with_context(|| format!("Failed fetching the merged PRs of {}", branch.name))
+    })?;
+    let mut deleted = vec![];
+
+    for (branch, merged_pr) in candidates.into_iter().zip(merged_prs) {
+        match (branch.merged, merged_pr) {
+            (Some(true), _) => {
+                deleted.push((branch.name, format!("merged into {default_branch}")));
+            }
+            (_, Some(pr)) => deleted.push((branch.name, format!("PR #{pr} merged"))),
+            _ => kept.push((branch.name, String::from("not merged"))),
+        }
+    }
+
+    deleted.sort();
+
+    Ok(deleted)
+}
+
+/// Gets why a branch is kept regardless of whether it's merged.
+fn get_keep_reason(
+    branch: &Branch,
+    default_branch: &str,
+    protect_patterns: &[String],
+    open_prs: &[Pr],
+) -> Option<String> {
+    if branch.name == default_branch {
+        return Some(String::from("default branch"));
+    }
+
+    if branch.protected {
+        return Some(String::from("protected"));
+    }
+
+    if let Some(pattern) = protect_patterns
+        .iter()
+        .find(|pattern| matches_glob(pattern, &branch.name))
+    {
+        return Some(format!("matches {pattern}"));
+    }
+
+    find_pr_of_branch(open_prs, &branch.name).map(|pr| format!("open PR #{pr}"))
+}
+
+/// Finds the newest merged PR whose last commit is `sha`. If the branch got
+/// new commits after the merge, it isn't merged anymore.
+fn find_merged_pr(prs: &[Pr], sha: &str) -> Option<u32> {
+    prs.iter()
+        .filter(|pr| pr.merged_at.is_some())
+        .filter(|pr| {
+            pr.head
+                .as_ref()
+                .is_some_and(|head| head.sha.as_deref() == Some(sha))
+        })
+        .map(|pr| pr.id)
+        .max()
+}
+
+/// Matches a name against a glob of `branch/protect-patterns`. `*` matches
+/// any characters including slashes and `?` matches a single character.
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` and the part of the name it matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    backtrack = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Fetches the branches, applies the --merged and --no-merged filters and
+/// fills in the fields that the forge didn't return when listing them. The
+/// requests for fields that won't be printed are skipped.
+fn get_branches_with_fields(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: &BranchListCommandArgs,
+) -> anyhow::Result<Vec<Branch>> {
+    let api_url = args.api_url.as_deref();
+    let get_branches = forge::function!(api_type, get_branches);
+    let mut branches = get_branches(http_client, remote, api_url, args.auth)
+        .context("Failed fetching branches")?;
+
+    let needs_merged = args.merged || args.no_merged || args.fields.contains(&BranchField::Merged);
+
+    if needs_merged && branches.iter().any(|branch| branch.merged.is_none()) {
+        let default_branch = get_default_branch(http_client, remote, api_type, api_url, args.auth)?;
+
+        add_merged(
+            http_client,
+            remote,
+            api_type,
+            api_url,
+            &mut branches,
+            &default_branch,
+            args.auth,
+        )?;
+    }
+
+    if args.merged || args.no_merged {
+        branches.retain(|branch| branch.merged == Some(args.merged));
+    }
+
+    let needs_dates = args.stale.is_some()
+        || args.fields.iter().any(|field| {
+            matches!(
+                field,
+                BranchField::LastCommitAt | BranchField::Age | BranchField::Stale
+            )
+        });
+
+    if needs_dates {
+        add_last_commit_dates(
+            http_client,
+            remote,
+            api_type,
+            api_url,
+            &mut branches,
+            args.auth,
+        )?;
+    }
+
+    let stale_range = DateRange {
+        after: None,
+        before: args.stale,
+    };
+
+    for branch in &mut branches {
+        branch.age = branch.last_commit_at.map(|date| date.format_age());
+
+        if stale_range.is_set() {
+            branch.stale = Some(stale_range.contains(branch.last_commit_at));
+        }
+    }
+
+    if args.fields.contains(&BranchField::Pr) {
+        let get_open_prs = forge::function!(api_type, get_open_prs);
+        let prs = get_open_prs(http_client, remote, api_url, args.auth)
+            .context("Failed fetching the open pull requests")?;
+
+        for branch in &mut branches {
+            branch.pr = find_pr_of_branch(&prs, &branch.name);
+        }
+    }
+
+    Ok(branches)
+}
+
+fn get_default_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<String> {
+    let get_repo = forge::function!(api_type, get_repo);
+
+    get_repo(http_client, remote, api_url, use_auth)
+        .map(|repo| repo.default_branch)
+        .context("Failed fetching the default branch")
+}
+
+/// Compares the branches that the forge didn't return the merge state for
+/// with the default branch.
+fn add_merged(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    branches: &mut [Branch],
+    default_branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    let is_branch_merged = forge::function!(api_type, is_branch_merged);
+    let merged = fetch_for_each_branch(branches, |branch| match branch.merged {
+        Some(merged) => Ok(merged),
+        None => is_branch_merged(
+            http_client,
+            remote,
+            api_url,
+            &branch.sha,
+            default_branch,
+            use_auth,
+        )
+        .with_context(|| format!("Failed comparing {} with {default_branch}", branch.name)),
+    })?;
+
+    for (branch, merged) in branches.iter_mut().zip(merged) {
+        branch.merged = Some(merged);
+    }
+
+    Ok(())
+}
+
+fn add_last_commit_dates(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    branches: &mut [Branch],
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    if branches
+        .iter()
+        .all(|branch| branch.last_commit_at.is_some())
+    {
+        return Ok(());
+    }
+
+    let get_commit_date = forge::function!(api_type, get_commit_date);
+    let dates = fetch_for_each_branch(branches, |branch| match &branch.last_commit_at {
+        Some(date) => Ok(*date),
+        None => get_commit_date(http_client, remote, api_url, &branch.sha, use_auth)
+            .with_context(|| format!("Failed fetching the last commit of {}", branch.name)),
+    })?;
+
+    for (branch, date) in branches.iter_mut().zip(dates) {
+        branch.last_commit_at = Some(date);
+    }
+
+    Ok(())
+}
+
+/// Finds the open PR from the branch or, if there is none, the open PR into
+/// it. The newest PR wins if there are several.
+fn find_pr_of_branch(prs: &[Pr], branch: &str) -> Option<u32> {
+    let newest_pr = |is_match: fn(&Pr, &str) -> bool| {
+        prs.iter()
+            .filter(|pr| is_match(pr, branch))
+            .map(|pr| pr.id)
+            .max()
+    };
+
+    newest_pr(|pr, branch| pr.head.as_ref().is_some_and(|head| head.name == branch)).or_else(|| {
+        newest_pr(|pr, branch| pr.base.as_ref().is_some_and(|base| base.name == branch))
+    })
+}
+
+/// Runs `fetch` for every branch with a few requests in flight at once. The
+/// results are in the same order as the branches.
+fn fetch_for_each_branch<T: Send>(
+    branches: &[Branch],
+    fetch: impl Fn(&Branch) -> anyhow::Result<T> + Sync,
+) -> anyhow::Result<Vec<T>> {
+    let fetch = &fetch;
+    let mut results = Vec::with_capacity(branches.len());
+
+    for chunk in branches.chunks(MAX_CONCURRENT_REQUESTS) {
+        let chunk_results: Vec<anyhow::Result<T>> = std::thread::scope(|scope| {
+            chunk
+                .iter()
+                .map(|branch| scope.spawn(move || fetch(branch)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+
+        for result in chunk_results {
+            results.push(result?);
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::pr::PrBranch;
+
+    fn pr(id: u32, head: &str, base: &str) -> Pr {
+        let branch = |name: &str| {
+            Some(PrBranch {
+                name: name.to_string(),
+                sha: None,
+            })
+        };
+
+        Pr {
+            id,
+            title: format!("PR {id}"),
+            state: String::from("open"),
+            author: String::from("alice"),
+            url: format!("https://github.com/user/repo/pull/{id}"),
+            labels: vec![],
+            milestone: None,
+            created_at: Timestamp::parse("2024-01-01T00:00:00Z").unwrap(),
+            updated_at: Timestamp::parse("2024-01-01T00:00:00Z").unwrap(),
+            merged_at: None,
+            closed_at: None,
+            head: branch(head),
+            base: branch(base),
+            draft: false,
+            approvals_required: None,
+            approvals_left: None,
+            review_status: None,
+            mergeable: None,
+            ci: None,
+            comments: None,
+            commits: None,
+            changed_files: None,
+        }
+    }
+
+    fn branch(name: &str, protected: bool) -> Branch {
+        Branch {
+            name: name.to_string(),
+            sha: format!("{name}-sha"),
+            last_commit_at: None,
+            age: None,
+            protected,
+            merged: None,
+            pr: None,
+            stale: None,
+        }
+    }
+
+    #[test]
+    fn test_find_pr_of_branch() {
+        let prs = vec![
+            pr(1, "feature", "main"),
+            pr(2, "fix", "release"),
+            pr(3, "docs", "main"),
+            pr(4, "release", "main"),
+        ];
+
+        assert_eq!(find_pr_of_branch(&prs, "feature"), Some(1));
+        assert_eq!(find_pr_of_branch(&prs, "main"), Some(4));
+        // The PR from the branch wins over the one into it
+        assert_eq!(find_pr_of_branch(&prs, "release"), Some(4));
+        assert_eq!(find_pr_of_branch(&prs, "stale"), None);
+    }
+
+    #[test]
+    fn test_get_keep_reason() {
+        let patterns = vec![String::from("release/*")];
+        let prs = vec![pr(1, "feature", "main"), pr(2, "fix", "hotfix")];
+        let keep_reason = |branch: &Branch| get_keep_reason(branch, "main", &patterns, &prs);
+
+        assert_eq!(
+            keep_reason(&branch("main", false)).as_deref(),
+            Some("default branch")
+        );
+        assert_eq!(
+            keep_reason(&branch("develop", true)).as_deref(),
+            Some("protected")
+        );
+        assert_eq!(
+            keep_reason(&branch("release/1.0", false)).as_deref(),
+            Some("matches release/*")
+        );
+        assert_eq!(
+            keep_reason(&branch("feature", false)).as_deref(),
+            Some("open PR #1")
+        );
+        assert_eq!(
+            keep_reason(&branch("hotfix", false)).as_deref(),
+            Some("open PR #2")
+        );
+        assert_eq!(keep_reason(&branch("old", false)), None);
+    }
+
+    #[test]
+    fn test_find_merged_pr() {
+        let mut merged = pr(3, "feature", "main");
+        let mut closed = pr(4, "feature", "main");
+
+        merged.merged_at = Timestamp::parse("2024-01-02T00:00:00Z");
+        merged.head.as_mut().unwrap().sha = Some(String::from("abc"));
+        closed.head.as_mut().unwrap().sha = Some(String::from("abc"));
+
+        let prs = vec![merged, closed];
+
+        assert_eq!(find_merged_pr(&prs, "abc"), Some(3));
+        // The branch got new commits after the merge
+        assert_eq!(find_merged_pr(&prs, "def"), None);
+    }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("main", "main"));
+        assert!(!matches_glob("main", "main2"));
+        assert!(matches_glob("release/*", "release/1.0"));
+        assert!(matches_glob("release/*", "release/1.0/hotfix"));
+        assert!(!matches_glob("release/*", "releases/1.0"));
+        assert!(matches_glob("*-stable", "v2-stable"));
+        assert!(matches_glob("v?", "v2"));
+        assert!(!matches_glob("v?", "v10"));
+        assert!(matches_glob("*a*b", "xaxxab"));
+        assert!(matches_glob("*", ""));
+    }
+}
@@ -8,9 +8,11 @@ use clap::Args;
 use crate::{
     cli::{
         config::{self, Config},
-        forge::{self, ApiType, gitea, github, gitlab},
+        forge::{self, ApiType, ApiUrl, HttpClient, forgejo, gitea, github, gitlab},
+        issue::IssueComment,
     },
     git::{self, GitRemoteData},
+    io,
 };
 
 const DEFAULT_REMOTE: &str = "origin";
@@ -23,18 +25,53 @@ const DEFAULT_REMOTE: &str = "origin";
 #[derive(Args, Debug)]
 pub struct BrowseCommandArgs {
     /// Specify the forge which affects the API schema etc.
-    #[arg(long, value_name = "TYPE")]
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
     api: Option<ApiType>,
 
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection. Only used by --comment
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN) to fetch the comments
+    /// for --comment
+    #[arg(long)]
+    auth: bool,
+
+    /// Open the comment with this index of the issue given by --issues, e.g.
+    /// 1 for its first comment. The index counts the comments in the order of
+    /// `issue export --comments`
+    #[arg(
+        long,
+        requires = "issues",
+        value_name = "INDEX",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    comment: Option<u32>,
+
     /// Open this commit-ish. If <PATH> is provided, open the file at this
     /// commit-ish
     #[arg(short, long, group = "input-type", value_name = "COMMIT_ISH")]
     commit: Option<String>,
 
+    /// Instead of opening the URL in your browser, copy it to the clipboard
+    #[arg(long, conflicts_with = "no_browser")]
+    copy: bool,
+
     /// Open the issues page. If <NUMBER> is provided, open that specific issue
     #[arg(short, long, group = "input-type", value_name = "NUMBER")]
     issues: Option<Option<u32>>,
 
+    /// Open the labels page
+    #[arg(long, group = "input-type")]
+    labels: bool,
+
+    /// Open the milestones page
+    #[arg(long, group = "input-type")]
+    milestones: bool,
+
     /// Instead of opening the URL in your browser, print it to stdout
     #[arg(short, long)]
     no_browser: bool,
@@ -63,6 +100,14 @@ pub struct BrowseCommandArgs {
     releases: bool,
 }
 
+/// Where the URL of a repository link goes.
+#[derive(Clone, Copy)]
+enum UrlTarget {
+    Browser,
+    Clipboard,
+    Stdout,
+}
+
 // =============================================================================
 // Command Logic
 // =============================================================================
@@ -76,124 +121,212 @@ pub fn browse_repository(mut args: BrowseCommandArgs) -> anyhow::Result<()> {
             .get_string("browse/remote", None)
             .unwrap_or(DEFAULT_REMOTE.to_string())
     });
-    let remote = git::get_remote_data(&remote_name)
+    let remote = config
+        .get_remote_data(&remote_name)
         .with_context(|| format!("Failed to get remote URL for remote '{}'", &remote_name))?;
 
-    config::merge_config_into_args!(&config, args, Some(&remote), "browse", [api, no_browser]);
-
-    let api_type = match args.api {
-        Some(api_type) => api_type,
-        None => forge::guess_api_type_from_host(&remote.host)
-            .with_context(|| format!("Failed to guess forge from host: {}", &remote.host))?,
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "browse",
+        [api, api_url, auth, copy, no_browser]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let target = if args.copy {
+        UrlTarget::Clipboard
+    } else if args.no_browser {
+        UrlTarget::Stdout
+    } else {
+        UrlTarget::Browser
     };
 
     if let Some(path) = args.path.as_ref() {
-        return browse_path(
-            &remote,
-            &api_type,
-            path,
-            args.commit.as_deref(),
-            args.no_browser,
-        );
+        return browse_path(&remote, &api_type, path, args.commit.as_deref(), target);
     }
 
     if let Some(commit_ish) = args.commit {
-        return browse_commitish(&remote, &api_type, &commit_ish, args.no_browser);
+        return browse_commitish(&remote, &api_type, &commit_ish, target);
     }
 
     if let Some(issue_number) = args.issues {
-        return match issue_number {
-            Some(issue_number) => browse_issue(&remote, &api_type, issue_number, args.no_browser),
-            None => browse_issues(&remote, &api_type, args.no_browser),
+        return match (issue_number, args.comment) {
+            (Some(issue_number), Some(index)) => {
+                let url = get_issue_comment_url(
+                    &remote,
+                    &api_type,
+                    args.api_url.as_deref(),
+                    issue_number,
+                    index,
+                    args.auth,
+                )?;
+
+                print_or_open(&url, target)
+            }
+            (Some(issue_number), None) => browse_issue(&remote, &api_type, issue_number, target),
+            (None, Some(_)) => anyhow::bail!(
+                "--comment needs the number of the issue, e.g. `--issues 42 --comment 3`"
+            ),
+            (None, None) => browse_issues(&remote, &api_type, target),
         };
     }
 
     if let Some(pr_number) = args.prs {
         return match pr_number {
-            Some(pr_number) => browse_pr(&remote, &api_type, pr_number, args.no_browser),
-            None => browse_prs(&remote, &api_type, args.no_browser),
+            Some(pr_number) => browse_pr(&remote, &api_type, pr_number, target),
+            None => browse_prs(&remote, &api_type, target),
         };
     }
 
+    if args.labels {
+        return browse_labels(&remote, &api_type, target);
+    }
+
+    if args.milestones {
+        return browse_milestones(&remote, &api_type, target);
+    }
+
     if args.releases {
-        return browse_releases(&remote, &api_type, args.no_browser);
+        return browse_releases(&remote, &api_type, target);
     }
 
-    browse_home(&remote, &api_type, args.no_browser)
+    browse_home(&remote, &api_type, target)
 }
 
-fn browse_home(remote: &GitRemoteData, api_type: &ApiType, no_browser: bool) -> anyhow::Result<()> {
+fn browse_home(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    target: UrlTarget,
+) -> anyhow::Result<()> {
     let get_home_url = forge::function!(api_type, get_url_for_home);
     let url = get_home_url(remote);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
 }
 
 fn browse_commitish(
     remote: &GitRemoteData,
     api_type: &ApiType,
     commit_ish: &str,
-    no_browser: bool,
+    target: UrlTarget,
 ) -> anyhow::Result<()> {
     let get_commit_url = forge::function!(api_type, get_url_for_commit);
     let commit = git::rev_parse(commit_ish)
         .with_context(|| format!("Failed to resolve commit-ish: {commit_ish}"))?;
     let url = get_commit_url(remote, &commit);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
 }
 
 fn browse_issue(
     remote: &GitRemoteData,
     api_type: &ApiType,
     issue_number: u32,
-    no_browser: bool,
+    target: UrlTarget,
 ) -> anyhow::Result<()> {
     let get_issue_url = forge::function!(api_type, get_url_for_issue);
     let url = get_issue_url(remote, issue_number);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
+}
+
+/// Gets the URL of the comment of an issue with the 1-based `index`. The
+/// forges link comments by their id, so the comments have to be fetched.
+fn get_issue_comment_url(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    issue_number: u32,
+    index: u32,
+    use_auth: bool,
+) -> anyhow::Result<String> {
+    let get_issue_comments = forge::function!(api_type, get_issue_comments);
+    let get_comment_url = forge::function!(api_type, get_url_for_issue_comment);
+    let comments = get_issue_comments(&HttpClient::new(), remote, api_url, issue_number, use_auth)
+        .with_context(|| format!("Failed to fetch the comments of issue #{issue_number}"))?;
+    let comment = get_comment_by_index(&comments, issue_number, index)?;
+
+    Ok(get_comment_url(remote, issue_number, comment.id))
+}
+
+fn get_comment_by_index(
+    comments: &[IssueComment],
+    issue_number: u32,
+    index: u32,
+) -> anyhow::Result<&IssueComment> {
+    let position = (index as usize).saturating_sub(1);
+
+    comments.get(position).ok_or_else(|| match comments.len() {
+        0 => anyhow::anyhow!("Issue #{issue_number} has no comments"),
+        1 => anyhow::anyhow!("Issue #{issue_number} has only 1 comment"),
+        count => anyhow::anyhow!(
+            "Issue #{issue_number} has only {count} comments. Use an index from 1 to {count}"
+        ),
+    })
 }
 
 fn browse_issues(
     remote: &GitRemoteData,
     api_type: &ApiType,
-    no_browser: bool,
+    target: UrlTarget,
 ) -> anyhow::Result<()> {
     let get_issues_url = forge::function!(api_type, get_url_for_issues);
     let url = get_issues_url(remote);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
 }
 
 fn browse_pr(
     remote: &GitRemoteData,
     api_type: &ApiType,
     pr_number: u32,
-    no_browser: bool,
+    target: UrlTarget,
 ) -> anyhow::Result<()> {
     let get_pr_url = forge::function!(api_type, get_url_for_pr);
     let url = get_pr_url(remote, pr_number);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
 }
 
-fn browse_prs(remote: &GitRemoteData, api_type: &ApiType, no_browser: bool) -> anyhow::Result<()> {
+fn browse_prs(remote: &GitRemoteData, api_type: &ApiType, target: UrlTarget) -> anyhow::Result<()> {
     let get_prs_url = forge::function!(api_type, get_url_for_prs);
     let url = get_prs_url(remote);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
+}
+
+fn browse_labels(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    target: UrlTarget,
+) -> anyhow::Result<()> {
+    let get_labels_url = forge::function!(api_type, get_url_for_labels);
+    let url = get_labels_url(remote);
+
+    print_or_open(&url, target)
+}
+
+fn browse_milestones(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    target: UrlTarget,
+) -> anyhow::Result<()> {
+    let get_milestones_url = forge::function!(api_type, get_url_for_milestones);
+    let url = get_milestones_url(remote);
+
+    print_or_open(&url, target)
 }
 
 fn browse_releases(
     remote: &GitRemoteData,
     api_type: &ApiType,
-    no_browser: bool,
+    target: UrlTarget,
 ) -> anyhow::Result<()> {
     let get_releases_url = forge::function!(api_type, get_url_for_releases);
     let url = get_releases_url(remote);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
 }
 
 fn browse_path(
@@ -201,7 +334,7 @@ fn browse_path(
     api_type: &ApiType,
     path: &str,
     commit_ish: Option<&str>,
-    no_browser: bool,
+    target: UrlTarget,
 ) -> anyhow::Result<()> {
     let (file_path, line_number) = match path.rsplit_once(':') {
         Some((path_part, line_part)) => {
@@ -230,16 +363,22 @@ fn browse_path(
 
     let url = get_path_url(remote, &file_path, commit, line_number);
 
-    print_or_open(&url, no_browser)
+    print_or_open(&url, target)
 }
 
-fn print_or_open(url: &str, no_browser: bool) -> anyhow::Result<()> {
-    if no_browser {
-        println!("{url}");
-    } else {
-        eprintln!("Opening {url} in browser...");
+fn print_or_open(url: &str, target: UrlTarget) -> anyhow::Result<()> {
+    match target {
+        UrlTarget::Browser => {
+            eprintln!("Opening {url} in browser...");
+
+            open::that(url)?;
+        }
+        UrlTarget::Clipboard => {
+            io::copy_to_clipboard(url)?;
 
-        open::that(url)?;
+            eprintln!("Copied {url} to the clipboard");
+        }
+        UrlTarget::Stdout => println!("{url}"),
     }
 
     Ok(())
@@ -251,3 +390,37 @@ fn path_with_forward_slashes(path: &Path) -> String {
         .collect::<Vec<_>>()
         .join("/")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comments(ids: &[u64]) -> Vec<IssueComment> {
+        ids.iter()
+            .map(|&id| IssueComment {
+                id,
+                author: String::from("user"),
+                created_at: String::from("2024-01-01T00:00:00Z"),
+                body: String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_get_comment_by_index() {
+        let comments = comments(&[10, 20, 30]);
+
+        assert_eq!(get_comment_by_index(&comments, 42, 1).unwrap().id, 10);
+        assert_eq!(get_comment_by_index(&comments, 42, 3).unwrap().id, 30);
+        assert_eq!(
+            get_comment_by_index(&comments, 42, 4)
+                .unwrap_err()
+                .to_string(),
+            "Issue #42 has only 3 comments. Use an index from 1 to 3"
+        );
+        assert_eq!(
+            get_comment_by_index(&[], 42, 1).unwrap_err().to_string(),
+            "Issue #42 has no comments"
+        );
+    }
+}
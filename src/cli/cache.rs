@@ -0,0 +1,179 @@
+//! The `cache` subcommand.
+
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use crate::storage::{self, BaseDirs, StorageCategory};
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `cache` subcommand.
+#[derive(Args)]
+pub struct CacheCommandArgs {
+    #[command(subcommand)]
+    pub subcommand: CacheCommand,
+}
+
+/// Available subcommands for managing the stored data.
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Delete the stored data. The audit log is kept unless it's cleared
+    /// explicitly with --category audit.
+    Clear(CacheClearCommandArgs),
+
+    /// List the size of the stored data per category.
+    #[command(alias = "ls")]
+    List,
+
+    /// Print the directory of each category.
+    Path,
+}
+
+/// Command-line arguments for clearing the stored data.
+#[derive(Args)]
+pub struct CacheClearCommandArgs {
+    /// Only delete the data of this category. The audit log is only deleted
+    /// with this
+    #[arg(long)]
+    category: Option<StorageCategory>,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Deletes the stored data of one or all categories.
+pub fn cache_clear(args: CacheClearCommandArgs) -> anyhow::Result<()> {
+    let base_dirs = BaseDirs::from_env()?;
+
+    for (category, dir) in clear_categories(&base_dirs, args.category)? {
+        eprintln!("Cleared {category} ({})", dir.display());
+    }
+
+    Ok(())
+}
+
+/// Prints the size of each category as TSV.
+pub fn cache_list() -> anyhow::Result<()> {
+    let base_dirs = BaseDirs::from_env()?;
+
+    for category in StorageCategory::ALL {
+        let dir = base_dirs.get_category_dir(category);
+        let size = storage::get_dir_size(&dir)?;
+
+        println!("{category}\t{}", format_size(size));
+    }
+
+    Ok(())
+}
+
+/// Prints the directory of each category as TSV.
+pub fn cache_path() -> anyhow::Result<()> {
+    let base_dirs = BaseDirs::from_env()?;
+
+    for category in StorageCategory::ALL {
+        println!(
+            "{category}\t{}",
+            base_dirs.get_category_dir(category).display()
+        );
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// Deletes the data of the category or, without one, of every category but
+/// the audit log. The audit log is an opt-in record of changes, so a cleanup
+/// must not wipe it by accident. Returns the cleared categories and their
+/// directories.
+fn clear_categories(
+    base_dirs: &BaseDirs,
+    category: Option<StorageCategory>,
+) -> anyhow::Result<Vec<(StorageCategory, PathBuf)>> {
+    let categories = match category {
+        Some(category) => vec![category],
+        None => StorageCategory::ALL
+            .into_iter()
+            .filter(|&category| category != StorageCategory::Audit)
+            .collect(),
+    };
+
+    categories
+        .into_iter()
+        .map(|category| {
+            let dir = base_dirs.get_category_dir(category);
+
+            storage::remove_dir(&dir)?;
+
+            Ok((category, dir))
+        })
+        .collect()
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_category_dirs(base_dirs: &BaseDirs) {
+        for category in StorageCategory::ALL {
+            std::fs::create_dir_all(base_dirs.get_category_dir(category)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_clear_keeps_the_audit_log_by_default() {
+        let root = tempfile::tempdir().unwrap();
+        let base_dirs = BaseDirs {
+            cache: root.path().join("cache"),
+            data: root.path().join("data"),
+            state: root.path().join("state"),
+        };
+
+        create_category_dirs(&base_dirs);
+        clear_categories(&base_dirs, None).unwrap();
+
+        for category in StorageCategory::ALL {
+            assert_eq!(
+                base_dirs.get_category_dir(category).exists(),
+                category == StorageCategory::Audit,
+                "{category}"
+            );
+        }
+
+        clear_categories(&base_dirs, Some(StorageCategory::Audit)).unwrap();
+
+        assert!(!base_dirs.get_category_dir(StorageCategory::Audit).exists());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
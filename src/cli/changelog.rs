@@ -0,0 +1,446 @@
+//! The `changelog` subcommand.
+
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::{
+        config::{self, Config},
+        forge::{
+            self, ApiType, ApiUrl, HttpClient, PaginatedResponse, forgejo, gitea, github, gitlab,
+        },
+        pr::Pr,
+    },
+    date::Timestamp,
+    git::{self, GitRemoteData},
+};
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Used if `changelog/sections` isn't configured. The labels are the default
+/// labels of GitHub and Gitea.
+const DEFAULT_SECTIONS: &str = "enhancement:Features,bug:Bug Fixes";
+const DEFAULT_REMOTE: &str = "origin";
+const OTHER_SECTION_TITLE: &str = "Other Changes";
+const PER_PAGE: u32 = 100;
+
+/// Command-line arguments for the `changelog` subcommand.
+#[derive(Args)]
+pub struct ChangelogCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
+
+    /// Output format. JSON contains the sections with their entries, e.g. for
+    /// custom templates [default: markdown]
+    #[arg(short = 'o', long)]
+    format: Option<ChangelogFormat>,
+
+    /// Also include the closed issues
+    #[arg(long)]
+    issues: bool,
+
+    /// Collect the merged PRs of this milestone
+    #[arg(long, value_name = "TITLE", required_unless_present = "since_tag")]
+    milestone: Option<String>,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Collect the PRs merged after the commit of this tag
+    #[arg(long, value_name = "TAG", conflicts_with = "milestone")]
+    since_tag: Option<String>,
+}
+
+/// Output format of the changelog.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+/// Filters for the merged PRs and closed issues of a changelog.
+pub struct ChangelogFilters<'a> {
+    pub milestone: Option<&'a str>,
+    /// Only PRs merged and issues closed at or after this RFC 3339 timestamp.
+    pub since: Option<&'a str>,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+/// A merged PR or a closed issue listed in the changelog.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ChangelogEntry {
+    pub kind: ChangelogEntryKind,
+    pub id: u32,
+    pub title: String,
+    pub author: String,
+    pub url: String,
+    pub labels: Vec<String>,
+    /// When the PR was merged or the issue was closed. Used to filter the
+    /// entries if the forge can't do it.
+    #[serde(skip)]
+    pub closed_at: Option<Timestamp>,
+}
+
+impl From<Pr> for ChangelogEntry {
+    fn from(pr: Pr) -> Self {
+        ChangelogEntry {
+            kind: ChangelogEntryKind::Pr,
+            id: pr.id,
+            title: pr.title,
+            author: pr.author,
+            url: pr.url,
+            labels: pr.labels,
+            closed_at: pr.merged_at.or(pr.closed_at),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogEntryKind {
+    Pr,
+    Issue,
+}
+
+/// A section of the changelog, e.g. "Features".
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ChangelogSection {
+    pub title: String,
+    /// Entries with any of these labels belong to the section.
+    #[serde(skip)]
+    pub labels: Vec<String>,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Executes the `changelog` subcommand. The merged PRs, and optionally the
+/// closed issues, are grouped into sections by their labels and printed.
+pub fn generate_changelog(mut args: ChangelogCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("changelog/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "changelog",
+        [api, api_url, auth, format, issues]
+    );
+
+    let sections = parse_sections(
+        &config
+            .get_string("changelog/sections", Some(&remote))
+            .unwrap_or(DEFAULT_SECTIONS.to_string()),
+    )
+    .context("Invalid changelog/sections configuration")?;
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let since = match &args.since_tag {
+        Some(tag) => Some(git::get_tag_date(tag)?),
+        None => None,
+    };
+    let filters = ChangelogFilters {
+        milestone: args.milestone.as_deref(),
+        since: since.as_deref(),
+        page: 1,
+        per_page: PER_PAGE,
+    };
+    let http_client = HttpClient::new();
+    let api_url = args.api_url.as_deref();
+    let mut entries = get_all_entries(
+        forge::function!(api_type, get_merged_prs),
+        &http_client,
+        &remote,
+        api_url,
+        &filters,
+        args.auth,
+    )
+    .context("Failed fetching the merged pull requests")?;
+
+    if args.issues {
+        entries.extend(
+            get_all_entries(
+                forge::function!(api_type, get_closed_issues),
+                &http_client,
+                &remote,
+                api_url,
+                &filters,
+                args.auth,
+            )
+            .context("Failed fetching the closed issues")?,
+        );
+    }
+
+    if let Some(since) = &since {
+        entries = filter_entries_since(entries, since)?;
+    }
+
+    eprintln!("Found {} entries", entries.len());
+
+    let sections = group_entries(sections, entries);
+
+    match args.format.unwrap_or_default() {
+        ChangelogFormat::Markdown => print!("{}", format_markdown(&sections)),
+        ChangelogFormat::Json => println!("{}", serde_json::to_string_pretty(&sections)?),
+    }
+
+    Ok(())
+}
+
+/// Parses the comma-separated `<LABEL>:<TITLE>` pairs of the
+/// `changelog/sections` setting. Labels with the same title share a section.
+/// The sections keep the order of their first appearance.
+pub fn parse_sections(value: &str) -> anyhow::Result<Vec<ChangelogSection>> {
+    let mut sections: Vec<ChangelogSection> = vec![];
+
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let Some((label, title)) = pair
+            .split_once(':')
+            .map(|(label, title)| (label.trim(), title.trim()))
+            .filter(|(label, title)| !label.is_empty() && !title.is_empty())
+        else {
+            anyhow::bail!("Invalid section '{pair}'. Expected <LABEL>:<TITLE>");
+        };
+
+        match sections.iter_mut().find(|s| s.title == title) {
+            Some(section) => section.labels.push(label.to_string()),
+            None => sections.push(ChangelogSection {
+                title: title.to_string(),
+                labels: vec![label.to_string()],
+                entries: vec![],
+            }),
+        }
+    }
+
+    Ok(sections)
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+type GetEntries = fn(
+    &HttpClient,
+    &GitRemoteData,
+    Option<&str>,
+    &ChangelogFilters,
+    bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>>;
+
+fn get_all_entries(
+    get_entries: GetEntries,
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<Vec<ChangelogEntry>> {
+    let mut entries = vec![];
+
+    for page in filters.page.. {
+        let response = get_entries(
+            http_client,
+            remote,
+            api_url,
+            &ChangelogFilters { page, ..*filters },
+            use_auth,
+        )
+        .with_context(|| format!("Failed fetching page {page}"))?;
+
+        entries.extend(response.items);
+
+        if !response.has_next_page {
+            break;
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Not every forge can filter by the merge or close date, so the entries are
+/// filtered client-side as well. Entries without a date are kept.
+fn filter_entries_since(
+    entries: Vec<ChangelogEntry>,
+    since: &str,
+) -> anyhow::Result<Vec<ChangelogEntry>> {
+    let since =
+        Timestamp::parse(since).with_context(|| format!("Failed to parse the date '{since}'"))?;
+
+    Ok(entries
+        .into_iter()
+        .filter(|entry| entry.closed_at.is_none_or(|closed_at| closed_at >= since))
+        .collect())
+}
+
+/// Puts every entry into the first section that matches one of its labels.
+/// Entries without a matching label end up in a trailing "Other Changes"
+/// section. Empty sections are dropped.
+fn group_entries(
+    mut sections: Vec<ChangelogSection>,
+    entries: Vec<ChangelogEntry>,
+) -> Vec<ChangelogSection> {
+    let mut other = ChangelogSection {
+        title: OTHER_SECTION_TITLE.to_string(),
+        labels: vec![],
+        entries: vec![],
+    };
+
+    for entry in entries {
+        match sections
+            .iter_mut()
+            .find(|section| entry.labels.iter().any(|l| section.labels.contains(l)))
+        {
+            Some(section) => section.entries.push(entry),
+            None => other.entries.push(entry),
+        }
+    }
+
+    sections.push(other);
+    sections.retain(|section| !section.entries.is_empty());
+
+    sections
+}
+
+fn format_markdown(sections: &[ChangelogSection]) -> String {
+    sections
+        .iter()
+        .map(|section| {
+            let entries = section
+                .entries
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "- {} ([#{}]({})) by @{}\n",
+                        entry.title, entry.id, entry.url, entry.author
+                    )
+                })
+                .collect::<String>();
+
+            format!("### {}\n\n{entries}", section.title)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u32, labels: &[&str], closed_at: Option<&str>) -> ChangelogEntry {
+        ChangelogEntry {
+            kind: ChangelogEntryKind::Pr,
+            id,
+            title: format!("Change {id}"),
+            author: String::from("alice"),
+            url: format!("https://example.org/pulls/{id}"),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            closed_at: closed_at.and_then(Timestamp::parse),
+        }
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let sections =
+            parse_sections("feat:Features, fix:Bug Fixes,enhancement: Features").unwrap();
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].title, "Features");
+        assert_eq!(sections[0].labels, ["feat", "enhancement"]);
+        assert_eq!(sections[1].title, "Bug Fixes");
+        assert_eq!(sections[1].labels, ["fix"]);
+
+        assert!(parse_sections("").unwrap().is_empty());
+        assert!(parse_sections("feat").is_err());
+        assert!(parse_sections("feat:").is_err());
+    }
+
+    #[test]
+    fn test_group_entries() {
+        let sections = group_entries(
+            parse_sections(DEFAULT_SECTIONS).unwrap(),
+            vec![
+                entry(1, &["bug"], None),
+                entry(2, &["docs"], None),
+                entry(3, &["bug", "enhancement"], None),
+            ],
+        );
+
+        assert_eq!(
+            sections
+                .iter()
+                .map(|s| (s.title.as_str(), s.entries.iter().map(|e| e.id).collect()))
+                .collect::<Vec<(&str, Vec<u32>)>>(),
+            [
+                ("Features", vec![3]),
+                ("Bug Fixes", vec![1]),
+                ("Other Changes", vec![2])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_entries_since() {
+        let entries = vec![
+            entry(1, &[], Some("2024-01-01T12:00:00Z")),
+            entry(2, &[], Some("2024-01-01T12:30:00+01:00")),
+            entry(3, &[], Some("2024-01-02T00:00:00Z")),
+            entry(4, &[], None),
+        ];
+        let ids = filter_entries_since(entries, "2024-01-01T12:00:00+00:00")
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, [1, 3, 4]);
+    }
+
+    #[test]
+    fn test_format_markdown() {
+        let sections = group_entries(
+            parse_sections(DEFAULT_SECTIONS).unwrap(),
+            vec![entry(1, &["bug"], None), entry(2, &[], None)],
+        );
+
+        assert_eq!(
+            format_markdown(&sections),
+            "### Bug Fixes\n\n\
+             - Change 1 ([#1](https://example.org/pulls/1)) by @alice\n\
+             \n\
+             ### Other Changes\n\n\
+             - Change 2 ([#2](https://example.org/pulls/2)) by @alice\n"
+        );
+    }
+}
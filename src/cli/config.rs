@@ -4,23 +4,76 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
     fs,
+    path::{Path, PathBuf},
 };
 
 use anyhow::Context;
-use clap::{Args, Subcommand, ValueEnum};
+use clap::{Args, Subcommand, ValueEnum, error::ErrorKind};
 use dialoguer::Editor;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    cli::{forge::ApiType, issue::IssueState, pr::PrState},
+    audit,
+    cli::{
+        branch::BranchField,
+        changelog::{self, ChangelogFormat},
+        forge::{self, ApiType, ApiUrl, MissingLabels},
+        hook::HookField,
+        issue::{IssueField, IssuePostAction, IssueState},
+        pr::{PrField, PrPostAction, PrState},
+        repo::RepoField,
+    },
+    date::DateFormat,
     git::{self, GitRemoteData},
-    io::OutputFormat,
+    io::{self, CreatedItemFormat, DateStyle, OutputFormat, PromptMode, ResultFormat},
+    throttle,
 };
 
 const APP_NAME: &str = std::env!("CARGO_PKG_NAME");
 const CONFIG_NAME: &str = "config";
 const DEFAULT_REMOTE: &str = "origin";
+/// The name of the config file in the root of a repository, see
+/// [`ConfigFile::Repo`].
+pub const REPO_CONFIG_NAME: &str = ".git-forge.toml";
 const DEFAULT_SET_CMD_SCOPE: &str = "global";
+const WEB_BASE_URL_CONFIG_PATH: &str = "web-base-url";
+pub const API_BASE_URL_CONFIG_PATH: &str = "api-base-url";
+pub const FORGE_TYPE_CONFIG_PATH: &str = "forge-type";
+const SHOW_RATE_LIMIT_CONFIG_PATH: &str = "http/show-rate-limit";
+const MUTATION_INTERVAL_CONFIG_PATH: &str = "http/mutation-interval-ms";
+const DATE_FORMAT_CONFIG_PATH: &str = "locale/date-format";
+const RELATIVE_DATES_CONFIG_PATH: &str = "locale/relative-dates";
+const PROMPT_CONFIG_PATH: &str = "prompt";
+const AUTH_ALLOWED_HOSTS_CONFIG_PATH: &str = "auth/allowed-hosts";
+const AUDIT_LOG_CONFIG_PATH: &str = "audit-log";
+const AUDIT_LOG_MAX_SIZE_CONFIG_PATH: &str = "audit-log/max-size";
+/// The content of a new config file for `config edit`.
+const CONFIG_SKELETON: &str = r#"# Global settings, e.g.
+# editor-command = "vim"
+
+# Settings of a host, e.g.
+# [host."gitlab.example.com"]
+# forge-type = "gitlab"
+
+# Settings of a repository, e.g.
+# [remote."github.com/owner/repo"]
+# "pr/create/target" = "develop"
+"#;
+/// Sets the forge type if neither --api nor a setting does, e.g. in CI jobs
+/// without a config file.
+const API_ENV_VAR: &str = "GIT_FORGE_API";
+/// Sets the API base URL if neither --api-url nor a setting does.
+const API_URL_ENV_VAR: &str = "GIT_FORGE_API_URL";
+/// The settings that a repository's `.git-forge.toml` may contain besides the
+/// git remotes of the commands, e.g. `issue/remote`. The file comes with the
+/// repository, so it must not be able to run commands (e.g. `editor-command`)
+/// or to send tokens to other hosts (e.g. `auth/allowed-hosts`).
+const REPO_CONFIG_KEYS: &[&str] = &[
+    API_BASE_URL_CONFIG_PATH,
+    FORGE_TYPE_CONFIG_PATH,
+    WEB_BASE_URL_CONFIG_PATH,
+];
 
 // =============================================================================
 // CLI Arguments
@@ -48,6 +101,11 @@ pub enum ConfigCommand {
 
     /// Edit the configuration file.
     Edit,
+
+    /// Print the key of the `[host."<KEY>"]` or `[remote."<KEY>"]` section that
+    /// the settings of a git remote are looked up in, e.g. to edit the
+    /// configuration file by hand.
+    Key(ConfigKeyArgs),
 }
 
 const PATH_DEFINITION: &str = "A path follows the format [<COMMAND_PATH>/]<FLAG>, e.g. editor, pr/editor, or pr/create/editor.
@@ -64,9 +122,24 @@ pub struct ConfigGetArgs {
     #[arg(long)]
     pub scope: Option<ConfigScope>,
 
+    /// Print the values as they are written in the configuration file, i.e.
+    /// without expanding `~` and environment variables.
+    #[arg(long)]
+    pub raw: bool,
+
     /// Git remote to use (only relevant for host/remote scopes).
     #[arg(long, default_value = DEFAULT_REMOTE)]
     pub remote: String,
+
+    /// Target this host instead of the remote's host (only relevant for the
+    /// host scope). Doesn't require a git repository.
+    #[arg(long, value_name = "HOST[:PORT]", value_parser = parse_host_key, conflicts_with_all = ["remote", "repo"])]
+    pub host: Option<GitRemoteData>,
+
+    /// Target this repository instead of the remote's repository (only
+    /// relevant for host/remote scopes). Doesn't require a git repository.
+    #[arg(long, value_name = "HOST[:PORT]/OWNER/REPO", value_parser = parse_repo_key, conflicts_with = "remote")]
+    pub repo: Option<GitRemoteData>,
 }
 
 /// Arguments for `config set`.
@@ -82,9 +155,24 @@ pub struct ConfigSetArgs {
     #[arg(long, default_value = DEFAULT_SET_CMD_SCOPE)]
     pub scope: ConfigScope,
 
+    /// Set the value even if the path is unknown or the value is invalid, e.g.
+    /// for settings of a newer git-forge version.
+    #[arg(long)]
+    pub force: bool,
+
     /// Git remote to use (only relevant for host/remote scopes).
     #[arg(long, default_value = DEFAULT_REMOTE)]
     pub remote: String,
+
+    /// Target this host instead of the remote's host (only relevant for the
+    /// host scope). Doesn't require a git repository.
+    #[arg(long, value_name = "HOST[:PORT]", value_parser = parse_host_key, conflicts_with_all = ["remote", "repo"])]
+    pub host: Option<GitRemoteData>,
+
+    /// Target this repository instead of the remote's repository (only
+    /// relevant for host/remote scopes). Doesn't require a git repository.
+    #[arg(long, value_name = "HOST[:PORT]/OWNER/REPO", value_parser = parse_repo_key, conflicts_with = "remote")]
+    pub repo: Option<GitRemoteData>,
 }
 
 /// Arguments for `config unset`.
@@ -100,6 +188,28 @@ pub struct ConfigUnsetArgs {
     /// Git remote to use (only relevant for host/remote scopes).
     #[arg(long, default_value = DEFAULT_REMOTE)]
     pub remote: String,
+
+    /// Target this host instead of the remote's host (only relevant for the
+    /// host scope). Doesn't require a git repository.
+    #[arg(long, value_name = "HOST[:PORT]", value_parser = parse_host_key, conflicts_with_all = ["remote", "repo"])]
+    pub host: Option<GitRemoteData>,
+
+    /// Target this repository instead of the remote's repository (only
+    /// relevant for host/remote scopes). Doesn't require a git repository.
+    #[arg(long, value_name = "HOST[:PORT]/OWNER/REPO", value_parser = parse_repo_key, conflicts_with = "remote")]
+    pub repo: Option<GitRemoteData>,
+}
+
+/// Arguments for `config key`.
+#[derive(Args)]
+pub struct ConfigKeyArgs {
+    /// The scope of the section.
+    #[arg(long, default_value = "remote")]
+    pub scope: ConfigScope,
+
+    /// Git remote to use.
+    #[arg(long, default_value = DEFAULT_REMOTE)]
+    pub remote: String,
 }
 
 // =============================================================================
@@ -120,26 +230,139 @@ pub struct Config {
     /// Remote-specific settings: key is "<host>[:<port>]/<owner>/<repo>"
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub remote: HashMap<String, HashMap<String, String>>,
+
+    /// The settings of the repository's `.git-forge.toml`. They apply to the
+    /// paths that this config doesn't set in the same scope.
+    #[serde(skip)]
+    pub repo_config: Option<Box<Config>>,
+}
+
+/// A config file that settings can be written to.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ConfigFile {
+    /// The config file of the user.
+    User,
+    /// `.git-forge.toml` in the root of the repository, e.g. to commit it. It
+    /// may only contain the settings of `init` and is overridden by the
+    /// user's config file.
+    Repo,
+}
+
+impl ConfigFile {
+    pub fn get_path(self) -> anyhow::Result<PathBuf> {
+        match self {
+            ConfigFile::User => get_config_path(),
+            ConfigFile::Repo => {
+                Ok(PathBuf::from(git::get_absolute_repo_root()?).join(REPO_CONFIG_NAME))
+            }
+        }
+    }
+
+    /// Loads only this file. A missing file is an empty config.
+    pub fn load(self) -> anyhow::Result<Config> {
+        let path = self.get_path()?;
+
+        if self == ConfigFile::Repo && !path.exists() {
+            return Ok(Config::default());
+        }
+
+        Config::load_from_path(&path)
+    }
 }
 
 impl Config {
-    /// Load configuration from disk.
+    /// Load configuration from disk. The settings of the repository's
+    /// `.git-forge.toml` are used for the paths that the user's config
+    /// doesn't set.
     pub fn load_from_disk() -> anyhow::Result<Config> {
-        confy::load(APP_NAME, CONFIG_NAME).context("Failed to load configuration")
+        let mut config = Config::load_from_path(&get_config_path()?)?;
+
+        config.repo_config = load_repo_config().map(Box::new);
+
+        Ok(config)
+    }
+
+    /// Load configuration from the file at `path`.
+    fn load_from_path(path: &Path) -> anyhow::Result<Config> {
+        let mut config: Config = confy::load_path(path).context("Failed to load configuration")?;
+
+        config.normalize_scope_keys();
+
+        Ok(config)
+    }
+
+    /// Lowercases the hosts in the keys of the host and remote scope sections
+    /// like [`format_host_key`] does, so that sections of hosts written with
+    /// different casing still apply. Sections whose keys only differ in the
+    /// casing are merged; the settings of the lowercase key win.
+    fn normalize_scope_keys(&mut self) {
+        self.host = normalize_section_keys(std::mem::take(&mut self.host));
+        self.remote = normalize_section_keys(std::mem::take(&mut self.remote));
+    }
+
+    /// Gets the data of a git remote with the host-scoped `web-base-url` and
+    /// `api-base-url` settings applied.
+    pub fn get_remote_data(&self, remote_name: &str) -> anyhow::Result<GitRemoteData> {
+        let mut remote = git::get_remote_data(remote_name)?;
+
+        if let Some(url) = self.get_string_from_host_scope(WEB_BASE_URL_CONFIG_PATH, &remote) {
+            remote.web_base_url = url.trim_end_matches('/').to_string();
+        }
+
+        remote.api_base_url = self.get_api_base_url(&remote);
+
+        Ok(remote)
+    }
+
+    /// Gets the host-scoped `api-base-url` setting of a git remote or else the
+    /// GIT_FORGE_API_URL environment variable.
+    fn get_api_base_url(&self, remote: &GitRemoteData) -> Option<String> {
+        self.get_string_from_host_scope(API_BASE_URL_CONFIG_PATH, remote)
+            .or_else(|| get_env_var(API_URL_ENV_VAR))
+            .map(|url| url.trim_end_matches('/').to_string())
+    }
+
+    /// Gets the hosts of the `auth/allowed-hosts` setting (comma-separated)
+    /// that tokens may be sent to besides the hosts of the git remote.
+    pub fn get_allowed_auth_hosts(&self, remote: &GitRemoteData) -> Vec<String> {
+        self.get_string(AUTH_ALLOWED_HOSTS_CONFIG_PATH, Some(remote))
+            .map(|hosts| hosts.split(',').map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets the host-scoped `forge-type` setting of a git remote.
+    pub fn get_forge_type(&self, remote: &GitRemoteData) -> Option<ApiType> {
+        self.get_string_from_host_scope(FORGE_TYPE_CONFIG_PATH, remote)
+            .and_then(|value_str| {
+                ApiType::from_str(&value_str, true).ok().or_else(|| {
+                    log::warn!(
+                        "Invalid value for '{}' in {}: '{}' (expected one of: {})",
+                        FORGE_TYPE_CONFIG_PATH,
+                        ConfigScope::Host,
+                        value_str,
+                        get_possible_values::<ApiType>()
+                    );
+
+                    None
+                })
+            })
     }
 
     /// Get a boolean config value.
     pub fn get_bool(&self, path: &str, remote: Option<&GitRemoteData>) -> Option<bool> {
-        self.get_value_effective(path, remote).and_then(|(value_str, scope)| {
-            value_str.parse::<bool>().ok().or_else(|| {
-                eprintln!(
-                    "Warning: Invalid boolean value for '{}' in {}: '{}' (expected 'true' or 'false')",
-                    path, scope, value_str
-                );
+        self.get_value_effective(path, remote)
+            .and_then(|(value_str, scope)| {
+                value_str.parse::<bool>().ok().or_else(|| {
+                    log::warn!(
+                        "Invalid boolean value for '{}' in {}: '{}' (expected 'true' or 'false')",
+                        path,
+                        scope,
+                        value_str
+                    );
 
-                None
+                    None
+                })
             })
-        })
     }
 
     /// Get an enum config value with CLI override using clap's ValueEnum.
@@ -147,15 +370,12 @@ impl Config {
         self.get_value_effective(path, remote)
             .and_then(|(value_str, scope)| {
                 T::from_str(&value_str, true).ok().or_else(|| {
-                    let valid_values = T::value_variants()
-                        .iter()
-                        .filter_map(|v| v.to_possible_value().map(|v| v.get_name().to_string()))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-
-                    eprintln!(
-                        "Warning: Invalid value for '{}' in {}: '{}' (expected one of: {})",
-                        path, scope, value_str, valid_values
+                    log::warn!(
+                        "Invalid value for '{}' in {}: '{}' (expected one of: {})",
+                        path,
+                        scope,
+                        value_str,
+                        get_possible_values::<T>()
                     );
 
                     None
@@ -169,34 +389,36 @@ impl Config {
         path: &str,
         remote: Option<&GitRemoteData>,
     ) -> Option<Vec<T>> {
-        self.get_value_effective(path, remote).map(|(value_str, scope)| {
-            let valid_values = T::value_variants()
-                .iter()
-                .filter_map(|v| v.to_possible_value().map(|v| v.get_name().to_string()))
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            value_str
-                .split(',')
-                .filter_map(|s| {
-                    let trimmed = s.trim();
-
-                    T::from_str(trimmed, true).ok().or_else(|| {
-                        eprintln!(
-                            "Warning: Invalid value '{}' in list for '{}' in {} (expected one of: {})",
-                            trimmed, path, scope, valid_values
-                        );
-
-                        None
+        self.get_value_effective(path, remote)
+            .map(|(value_str, scope)| {
+                let valid_values = get_possible_values::<T>();
+
+                value_str
+                    .split(',')
+                    .filter_map(|s| {
+                        let trimmed = s.trim();
+
+                        T::from_str(trimmed, true).ok().or_else(|| {
+                            log::warn!(
+                                "Invalid value '{}' in list for '{}' in {} (expected one of: {})",
+                                trimmed,
+                                path,
+                                scope,
+                                valid_values
+                            );
+
+                            None
+                        })
                     })
-                })
-                .collect()
-        })
+                    .collect()
+            })
     }
 
-    /// Get a string config value.
+    /// Get a string config value. A leading `~` and environment variables
+    /// (`$VAR` or `${VAR}`) are expanded.
     pub fn get_string(&self, path: &str, remote: Option<&GitRemoteData>) -> Option<String> {
-        self.get_value_effective(path, remote).map(|(v, _)| v)
+        self.get_value_effective(path, remote)
+            .map(|(v, _)| expand_value(&v))
     }
 
     /// Get a string config value from the global scope.
@@ -204,18 +426,54 @@ impl Config {
         self.get_string(path, None)
     }
 
+    /// Get a string config value from the host scope without falling back to
+    /// the global scope.
+    pub fn get_string_from_host_scope(&self, path: &str, remote: &GitRemoteData) -> Option<String> {
+        self.get_value_from_scope(path, ConfigSource::Host(remote))
+    }
+
+    /// Gets a value of a scope of this file without falling back to other
+    /// scopes or the repository's `.git-forge.toml`.
+    pub fn get_value_in_scope(
+        &self,
+        path: &str,
+        scope: ConfigScope,
+        remote: &GitRemoteData,
+    ) -> Option<String> {
+        self.get_own_value_from_scope(path, ConfigSource::new(scope, Some(remote)).ok()?)
+    }
+
+    /// Sets a value in a scope without saving it.
+    pub fn set_value_in_scope(
+        &mut self,
+        path: &str,
+        value: &str,
+        scope: ConfigScope,
+        remote: &GitRemoteData,
+    ) -> anyhow::Result<()> {
+        self.set_value(path, value, ConfigSource::new(scope, Some(remote))?)
+    }
+
+    /// Saves the configuration to the file.
+    pub fn save_to_file(&self, file: ConfigFile) -> anyhow::Result<()> {
+        self.save_to_path(&file.get_path()?)
+    }
+
     /// Get a u32 config value.
     pub fn get_u32(&self, path: &str, remote: Option<&GitRemoteData>) -> Option<u32> {
-        self.get_value_effective(path, remote).and_then(|(value_str, scope)| {
-            value_str.parse::<u32>().ok().or_else(|| {
-                eprintln!(
-                    "Warning: Invalid number value for '{}' in {}: '{}' (expected a positive integer)",
-                    path, scope, value_str
-                );
+        self.get_value_effective(path, remote)
+            .and_then(|(value_str, scope)| {
+                value_str.parse::<u32>().ok().or_else(|| {
+                    log::warn!(
+                        "Invalid number value for '{}' in {}: '{}' (expected a positive integer)",
+                        path,
+                        scope,
+                        value_str
+                    );
 
-                None
+                    None
+                })
             })
-        })
     }
 
     /// Get effective value with precedence: remote > host > global.
@@ -238,8 +496,17 @@ impl Config {
             .map(|value| (value, ConfigScope::Global))
     }
 
-    /// Get value from a specific scope without precedence.
+    /// Get value from a specific scope without precedence. The repository's
+    /// `.git-forge.toml` is used if this config doesn't have the path.
     fn get_value_from_scope(&self, path: &str, source: ConfigSource) -> Option<String> {
+        self.get_own_value_from_scope(path, source).or_else(|| {
+            self.repo_config
+                .as_ref()?
+                .get_own_value_from_scope(path, source)
+        })
+    }
+
+    fn get_own_value_from_scope(&self, path: &str, source: ConfigSource) -> Option<String> {
         let path_variants = get_path_variants(path);
 
         match source {
@@ -281,9 +548,29 @@ impl Config {
         }
     }
 
+    /// Drops the settings that a repository's `.git-forge.toml` may not
+    /// contain (see [`REPO_CONFIG_KEYS`]) with a warning.
+    fn retain_repo_settings(&mut self) {
+        self.global
+            .retain(|path, value| is_repo_setting(path, value, None));
+
+        for (host_key, entries) in &mut self.host {
+            entries.retain(|path, value| is_repo_setting(path, value, Some(host_key)));
+        }
+
+        for entries in self.remote.values_mut() {
+            entries.retain(|path, value| is_repo_setting(path, value, None));
+        }
+    }
+
     /// Save configuration to disk.
     fn save_to_disk(&self) -> anyhow::Result<()> {
-        confy::store(APP_NAME, CONFIG_NAME, self).context("Failed to save configuration")
+        self.save_to_path(&get_config_path()?)
+    }
+
+    /// Save configuration to the file at `path`.
+    fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+        confy::store_path(path, self).context("Failed to save configuration")
     }
 
     /// Set a value in the configuration.
@@ -400,6 +687,226 @@ impl<'a> ConfigSource<'a> {
     }
 }
 
+/// Validates a config value. Returns the reason if the value is invalid.
+type ValueValidator = fn(&str) -> Result<(), String>;
+
+/// All full config paths that git-forge reads and the validators for their
+/// values. Keep this in sync with the `merge_config_into_args!` calls and the
+/// other config lookups.
+const CONFIG_KEYS: &[(&str, ValueValidator)] = &[
+    // Host scope settings for self-hosted forges
+    (API_BASE_URL_CONFIG_PATH, validate_url),
+    (AUDIT_LOG_CONFIG_PATH, validate_bool),
+    (AUDIT_LOG_MAX_SIZE_CONFIG_PATH, validate_size),
+    (AUTH_ALLOWED_HOSTS_CONFIG_PATH, validate_string),
+    ("branch/delete-merged/api", validate_enum::<ApiType>),
+    ("branch/delete-merged/api-url", validate_string),
+    ("branch/delete-merged/remote", validate_string),
+    ("branch/list/api", validate_enum::<ApiType>),
+    ("branch/list/api-url", validate_string),
+    ("branch/list/auth", validate_bool),
+    ("branch/list/fields", validate_fields::<BranchField>),
+    ("branch/list/format", validate_enum::<OutputFormat>),
+    ("branch/list/no-truncate", validate_bool),
+    ("branch/list/remote", validate_string),
+    ("branch/protect-patterns", validate_string),
+    ("browse/api", validate_enum::<ApiType>),
+    ("browse/api-url", validate_string),
+    ("browse/auth", validate_bool),
+    ("browse/copy", validate_bool),
+    ("browse/no-browser", validate_bool),
+    ("browse/remote", validate_string),
+    ("changelog/api", validate_enum::<ApiType>),
+    ("changelog/api-url", validate_string),
+    ("changelog/auth", validate_bool),
+    ("changelog/format", validate_enum::<ChangelogFormat>),
+    ("changelog/issues", validate_bool),
+    ("changelog/remote", validate_string),
+    ("changelog/sections", validate_changelog_sections),
+    ("context/api", validate_enum::<ApiType>),
+    ("context/api-url", validate_string),
+    ("context/auth", validate_bool),
+    ("context/format", validate_enum::<ResultFormat>),
+    ("context/remote", validate_string),
+    ("editor-command", validate_string),
+    (FORGE_TYPE_CONFIG_PATH, validate_enum::<ApiType>),
+    ("hook/disable/api", validate_enum::<ApiType>),
+    ("hook/disable/api-url", validate_string),
+    ("hook/disable/remote", validate_string),
+    ("hook/enable/api", validate_enum::<ApiType>),
+    ("hook/enable/api-url", validate_string),
+    ("hook/enable/remote", validate_string),
+    ("hook/list/api", validate_enum::<ApiType>),
+    ("hook/list/api-url", validate_string),
+    ("hook/list/fields", validate_fields::<HookField>),
+    ("hook/list/format", validate_enum::<OutputFormat>),
+    ("hook/list/no-truncate", validate_bool),
+    ("hook/list/remote", validate_string),
+    ("hook/ping/api", validate_enum::<ApiType>),
+    ("hook/ping/api-url", validate_string),
+    ("hook/ping/remote", validate_string),
+    (MUTATION_INTERVAL_CONFIG_PATH, validate_u32),
+    (SHOW_RATE_LIMIT_CONFIG_PATH, validate_bool),
+    ("issue/assign/api", validate_enum::<ApiType>),
+    ("issue/assign/api-url", validate_string),
+    ("issue/assign/format", validate_enum::<ResultFormat>),
+    ("issue/assign/remote", validate_string),
+    ("issue/create/api", validate_enum::<ApiType>),
+    ("issue/create/api-url", validate_string),
+    ("issue/create/editor", validate_bool),
+    ("issue/create/format", validate_enum::<CreatedItemFormat>),
+    (
+        "issue/create/missing-labels",
+        validate_enum::<MissingLabels>,
+    ),
+    ("issue/create/no-browser", validate_bool),
+    ("issue/create/no-precheck", validate_bool),
+    ("issue/create/post-action", validate_enum::<IssuePostAction>),
+    ("issue/create/prompt-labels", validate_bool),
+    ("issue/create/remote", validate_string),
+    ("issue/create/skip-duplicate-check", validate_bool),
+    ("issue/create/web", validate_bool),
+    ("issue/export/api", validate_enum::<ApiType>),
+    ("issue/export/api-url", validate_string),
+    ("issue/export/auth", validate_bool),
+    ("issue/export/format", validate_enum::<OutputFormat>),
+    ("issue/export/per-page", validate_u32),
+    ("issue/export/remote", validate_string),
+    ("issue/import/api", validate_enum::<ApiType>),
+    ("issue/import/api-url", validate_string),
+    ("issue/import/delay", validate_u32),
+    ("issue/import/remote", validate_string),
+    ("issue/label/api", validate_enum::<ApiType>),
+    ("issue/label/api-url", validate_string),
+    ("issue/label/format", validate_enum::<OutputFormat>),
+    ("issue/label/remote", validate_string),
+    ("issue/list/api", validate_enum::<ApiType>),
+    ("issue/list/api-url", validate_string),
+    ("issue/list/auth", validate_bool),
+    ("issue/list/fields", validate_fields::<IssueField>),
+    ("issue/list/format", validate_enum::<OutputFormat>),
+    ("issue/list/interactive", validate_bool),
+    ("issue/list/limit", validate_u32),
+    ("issue/list/max-title-width", validate_u32),
+    ("issue/list/no-truncate", validate_bool),
+    ("issue/list/per-page", validate_u32),
+    ("issue/list/remote", validate_string),
+    ("issue/list/state", validate_enum::<IssueState>),
+    ("issue/pin/api", validate_enum::<ApiType>),
+    ("issue/pin/api-url", validate_string),
+    ("issue/pin/remote", validate_string),
+    ("issue/snapshot/api", validate_enum::<ApiType>),
+    ("issue/snapshot/api-url", validate_string),
+    ("issue/snapshot/auth", validate_bool),
+    ("issue/snapshot/remote", validate_string),
+    ("issue/unassign/api", validate_enum::<ApiType>),
+    ("issue/unassign/api-url", validate_string),
+    ("issue/unassign/format", validate_enum::<ResultFormat>),
+    ("issue/unassign/remote", validate_string),
+    ("issue/view/api", validate_enum::<ApiType>),
+    ("issue/view/api-url", validate_string),
+    ("issue/view/auth", validate_bool),
+    ("issue/view/format", validate_enum::<OutputFormat>),
+    ("issue/view/remote", validate_string),
+    (DATE_FORMAT_CONFIG_PATH, validate_date_format),
+    (RELATIVE_DATES_CONFIG_PATH, validate_bool),
+    ("pr/checkout/api", validate_enum::<ApiType>),
+    ("pr/checkout/api-url", validate_string),
+    ("pr/checkout/auth", validate_bool),
+    ("pr/checkout/author", validate_string),
+    ("pr/checkout/draft", validate_bool),
+    ("pr/checkout/no-track", validate_bool),
+    ("pr/checkout/per-page", validate_u32),
+    ("pr/checkout/remote", validate_string),
+    ("pr/checkout/state", validate_enum::<PrState>),
+    ("pr/create/api", validate_enum::<ApiType>),
+    ("pr/create/api-url", validate_string),
+    ("pr/create/draft", validate_bool),
+    ("pr/create/editor", validate_bool),
+    ("pr/create/fill", validate_bool),
+    ("pr/create/fill-first", validate_bool),
+    ("pr/create/fill-verbose", validate_bool),
+    ("pr/create/format", validate_enum::<CreatedItemFormat>),
+    ("pr/create/missing-labels", validate_enum::<MissingLabels>),
+    ("pr/create/no-browser", validate_bool),
+    ("pr/create/no-maintainer-edit", validate_bool),
+    ("pr/create/no-precheck", validate_bool),
+    ("pr/create/no-push", validate_bool),
+    ("pr/create/post-action", validate_enum::<PrPostAction>),
+    ("pr/create/prompt-labels", validate_bool),
+    ("pr/create/remote", validate_string),
+    ("pr/create/remove-source-branch", validate_bool),
+    ("pr/create/squash-on-merge", validate_bool),
+    ("pr/create/target", validate_string),
+    ("pr/list/api", validate_enum::<ApiType>),
+    ("pr/list/api-url", validate_string),
+    ("pr/list/auth", validate_bool),
+    ("pr/list/bot-authors", validate_string),
+    ("pr/list/draft", validate_bool),
+    ("pr/list/fields", validate_fields::<PrField>),
+    ("pr/list/format", validate_enum::<OutputFormat>),
+    ("pr/list/interactive", validate_bool),
+    ("pr/list/limit", validate_u32),
+    ("pr/list/no-truncate", validate_bool),
+    ("pr/list/per-page", validate_u32),
+    ("pr/list/remote", validate_string),
+    ("pr/list/state", validate_enum::<PrState>),
+    ("pr/merge/api", validate_enum::<ApiType>),
+    ("pr/merge/api-url", validate_string),
+    ("pr/merge/remote", validate_string),
+    ("pr/reviewer/api", validate_enum::<ApiType>),
+    ("pr/reviewer/api-url", validate_string),
+    ("pr/reviewer/format", validate_enum::<ResultFormat>),
+    ("pr/reviewer/remote", validate_string),
+    ("pr/view/api", validate_enum::<ApiType>),
+    ("pr/view/api-url", validate_string),
+    ("pr/view/auth", validate_bool),
+    ("pr/view/comments", validate_bool),
+    ("pr/view/links", validate_bool),
+    ("pr/view/remote", validate_string),
+    ("pr/view/reviews", validate_bool),
+    (PROMPT_CONFIG_PATH, validate_enum::<PromptMode>),
+    ("repo/edit/api", validate_enum::<ApiType>),
+    ("repo/edit/api-url", validate_string),
+    ("repo/edit/format", validate_enum::<ResultFormat>),
+    ("repo/edit/remote", validate_string),
+    ("repo/list/api", validate_enum::<ApiType>),
+    ("repo/list/api-url", validate_string),
+    ("repo/list/auth", validate_bool),
+    ("repo/list/fields", validate_fields::<RepoField>),
+    ("repo/list/format", validate_enum::<OutputFormat>),
+    ("repo/list/no-truncate", validate_bool),
+    ("repo/list/per-page", validate_u32),
+    ("repo/list/remote", validate_string),
+    ("repo/view/api", validate_enum::<ApiType>),
+    ("repo/view/api-url", validate_string),
+    ("repo/view/auth", validate_bool),
+    ("repo/view/format", validate_enum::<ResultFormat>),
+    ("repo/view/remote", validate_string),
+    (WEB_BASE_URL_CONFIG_PATH, validate_url),
+];
+
+/// Why a value can't be set for a config path.
+#[derive(Debug, PartialEq)]
+enum ConfigValueError {
+    /// The path isn't used by any command.
+    UnknownPath,
+    /// The value is invalid for the full config path `key`, which the path
+    /// applies to.
+    InvalidValue { key: &'static str, reason: String },
+}
+
+impl Display for ConfigValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValueError::UnknownPath => write!(f, "unknown config path"),
+            ConfigValueError::InvalidValue { key, reason } => {
+                write!(f, "invalid value for {key}: {reason}")
+            }
+        }
+    }
+}
+
 /// Merges config values into args fields.
 ///
 /// It expects the following arguments (tt):
@@ -425,7 +932,7 @@ impl<'a> ConfigSource<'a> {
 /// }
 /// ```
 macro_rules! merge_config_into_args {
-    ($config:expr, $args:expr, $remote:expr, $command_path:literal, [$($field:ident),* $(,)?]$(,)?) => {
+    ($config:expr, $args:expr, $remote:expr, $command_path:expr, [$($field:ident),* $(,)?]$(,)?) => {
         $(
             {
                 let field_name = stringify!($field).replace('_', "-");
@@ -448,7 +955,10 @@ pub(crate) use merge_config_into_args;
 ///
 /// This module is public only for macro access but hidden from documentation.
 pub(crate) mod macro_internals {
-    use super::{ApiType, Config, GitRemoteData, IssueState, OutputFormat, PrState};
+    use super::{
+        ApiType, ApiUrl, ChangelogFormat, Config, CreatedItemFormat, GitRemoteData,
+        IssuePostAction, IssueState, OutputFormat, PrPostAction, PrState, ResultFormat, forge,
+    };
     use clap::ValueEnum;
 
     pub trait MergeConfigIntoArg {
@@ -476,6 +986,22 @@ pub(crate) mod macro_internals {
         }
     }
 
+    /// Parses the `api-url` settings like the --api-url options.
+    impl MergeConfigIntoArg for Option<ApiUrl> {
+        fn __merge_with_config(
+            &mut self,
+            config: &Config,
+            path: &str,
+            remote: Option<&GitRemoteData>,
+        ) {
+            if self.is_none() {
+                *self = config
+                    .get_string(path, remote)
+                    .and_then(|url| forge::parse_api_url(&url).ok());
+            }
+        }
+    }
+
     impl MergeConfigIntoArg for Option<u32> {
         fn __merge_with_config(
             &mut self,
@@ -533,9 +1059,14 @@ pub(crate) mod macro_internals {
     }
 
     impl_merge_from_config_for_enum!(ApiType);
+    impl_merge_from_config_for_enum!(ChangelogFormat);
+    impl_merge_from_config_for_enum!(CreatedItemFormat);
     impl_merge_from_config_for_enum!(OutputFormat);
     impl_merge_from_config_for_enum!(IssueState);
     impl_merge_from_config_for_enum!(PrState);
+    impl_merge_from_config_for_enum!(IssuePostAction);
+    impl_merge_from_config_for_enum!(PrPostAction);
+    impl_merge_from_config_for_enum!(ResultFormat);
 }
 
 // =============================================================================
@@ -549,36 +1080,36 @@ pub fn config_get(args: ConfigGetArgs) -> anyhow::Result<()> {
     match args.path {
         Some(path) => match args.scope {
             Some(scope) => {
-                let remote = get_remote_for_scope(&scope, &args.remote)?;
+                let remote = get_remote_for_scope(&scope, &args.remote, args.host, args.repo)?;
                 let source = ConfigSource::new(scope, remote.as_ref())?;
 
                 match config.get_value_from_scope(&path, source) {
-                    Some(value) => println!("{value}"),
+                    Some(value) => println!("{}", format_value(&value, args.raw)),
                     None => eprintln!("No value found for '{path}' in {scope}"),
                 }
             }
             None => {
                 // If there is no scope, fall back to global scope.
-                let remote = git::get_remote_data(&args.remote).ok();
+                let remote = get_remote_for_effective_config(&args.remote, args.host, args.repo);
 
                 match config.get_value_effective(&path, remote.as_ref()) {
-                    Some((value, _)) => println!("{value}"),
+                    Some((value, _)) => println!("{}", format_value(&value, args.raw)),
                     None => eprintln!("No value found for '{path}'"),
                 }
             }
         },
         None => match args.scope {
             Some(scope) => {
-                let remote = get_remote_for_scope(&scope, &args.remote)?;
+                let remote = get_remote_for_scope(&scope, &args.remote, args.host, args.repo)?;
                 let source = ConfigSource::new(scope, remote.as_ref())?;
 
-                print_entire_config_for_scope(&config, source)?;
+                print_entire_config_for_scope(&config, source, args.raw)?;
             }
             None => {
                 // If there is no scope, show global config.
-                let remote = git::get_remote_data(&args.remote).ok();
+                let remote = get_remote_for_effective_config(&args.remote, args.host, args.repo);
 
-                print_entire_effective_config(&config, remote.as_ref())?;
+                print_entire_effective_config(&config, remote.as_ref(), args.raw)?;
             }
         },
     };
@@ -589,9 +1120,23 @@ pub fn config_get(args: ConfigGetArgs) -> anyhow::Result<()> {
 /// Execute the `config set` subcommand.
 pub fn config_set(args: ConfigSetArgs) -> anyhow::Result<()> {
     let mut config = Config::load_from_disk().context("Failed to load configuration")?;
-    let remote = get_remote_for_scope(&args.scope, &args.remote)?;
+    let remote = get_remote_for_scope(&args.scope, &args.remote, args.host, args.repo)?;
     let source = ConfigSource::new(args.scope, remote.as_ref())?;
 
+    if !args.force
+        && let Err(e) = validate_config_value(&args.path, &args.value)
+    {
+        // Reported like clap's errors for invalid CLI values (incl. exit code)
+        return Err(clap::Error::raw(
+            ErrorKind::InvalidValue,
+            format!(
+                "Can't set '{}' to '{}': {e}. Use --force to set it anyway\n",
+                args.path, args.value
+            ),
+        )
+        .into());
+    }
+
     config.set_value(&args.path, &args.value, source)?;
     config.save_to_disk()?;
 
@@ -601,7 +1146,7 @@ pub fn config_set(args: ConfigSetArgs) -> anyhow::Result<()> {
 /// Execute the `config unset` subcommand.
 pub fn config_unset(args: ConfigUnsetArgs) -> anyhow::Result<()> {
     let mut config = Config::load_from_disk().context("Failed to load configuration")?;
-    let remote = get_remote_for_scope(&args.scope, &args.remote)?;
+    let remote = get_remote_for_scope(&args.scope, &args.remote, args.host, args.repo)?;
     let source = ConfigSource::new(args.scope, remote.as_ref())?;
     let was_removed = config.unset_value(&args.path, source)?;
 
@@ -615,33 +1160,61 @@ pub fn config_unset(args: ConfigUnsetArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Execute the `config key` subcommand.
+pub fn config_key(args: ConfigKeyArgs) -> anyhow::Result<()> {
+    let format_key = match args.scope {
+        ConfigScope::Global => anyhow::bail!(
+            "The global scope has no section key. Its settings are at the top level of the configuration file"
+        ),
+        ConfigScope::Host => format_host_key,
+        ConfigScope::Remote => format_remote_key,
+    };
+    let remote = git::get_remote_data(&args.remote)
+        .with_context(|| format!("Failed to get remote URL for remote '{}'", args.remote))?;
+
+    println!("{}", format_key(&remote));
+
+    Ok(())
+}
+
 /// Execute the `config edit` subcommand.
 pub fn config_edit() -> anyhow::Result<()> {
-    let config = Config::load_from_disk().context("Failed to load configuration")?;
-    let mut editor = Editor::new();
+    // A config file that can't be loaded is what the user may want to fix
+    let editor_command = match Config::load_from_disk() {
+        Ok(config) => config.get_string_from_global_scope("editor-command"),
+        Err(e) => {
+            log::warn!("Using the default editor since the configuration can't be loaded: {e:#}");
 
-    if let Some(cmd) = config.get_string_from_global_scope("editor-command") {
-        editor.executable(cmd);
+            None
+        }
     };
-
-    let config_path = match confy::get_configuration_file_path(APP_NAME, CONFIG_NAME) {
-        Ok(path) => path,
-        Err(e) => anyhow::bail!("Failed to get config path: {}", e),
+    let config = edit_config_file(&get_config_path()?, editor_command.as_deref(), |error| {
+        eprintln!("{error}");
+
+        io::prompt_confirm(
+            "The configuration isn't valid TOML. Edit it again?",
+            false,
+            "a valid configuration",
+        )
+    })?;
+    let Some(config) = config else {
+        return Ok(());
     };
-    let edited_content = editor
-        .edit(&fs::read_to_string(&config_path).unwrap_or_default())
-        .context("Failed to open editor")?;
-
-    if let Some(content) = edited_content {
-        fs::write(&config_path, content.as_bytes())
-            .context("Failed to write configuration file")?;
-
-        Config::load_from_disk()
-            .context("The config file may be corrupted. Please check the TOML file.")?;
+    let violations = find_invalid_values(&config);
 
-        println!("Configuration saved successfully.");
+    if !violations.is_empty() {
+        anyhow::bail!(
+            "Configuration saved but it contains invalid values:\n{}",
+            violations
+                .iter()
+                .map(|violation| format!("  - {violation}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
     }
 
+    println!("Configuration saved successfully.");
+
     Ok(())
 }
 
@@ -649,21 +1222,301 @@ pub fn config_edit() -> anyhow::Result<()> {
 // Private Helpers
 // =============================================================================
 
-/// Format a remote identifier for use as a config key.
-fn format_remote_key(remote: &GitRemoteData) -> String {
-    if let Some(port) = remote.port {
-        format!("{}:{}/{}", remote.host, port, remote.path)
+/// Opens the config file at `path` in the editor and saves the edited content
+/// only if it's a valid config. Otherwise, `reopen` shows the parse error and
+/// decides whether to edit the rejected content again. A missing or empty
+/// config file gets [`CONFIG_SKELETON`] first. Returns the saved config or
+/// `None` if the editor was closed without saving.
+fn edit_config_file(
+    path: &Path,
+    editor_command: Option<&str>,
+    mut reopen: impl FnMut(&toml::de::Error) -> anyhow::Result<bool>,
+) -> anyhow::Result<Option<Config>> {
+    let existing_content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(anyhow::Error::new(e).context("Failed to read the configuration file"));
+        }
+    };
+    // Loading the config creates an empty file, so it gets the skeleton as well
+    let mut content = if existing_content.trim().is_empty() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create the config directory")?;
+        }
+
+        fs::write(path, CONFIG_SKELETON).context("Failed to create the configuration file")?;
+
+        CONFIG_SKELETON.to_string()
     } else {
-        format!("{}/{}", remote.host, remote.path)
+        existing_content
+    };
+    let mut editor = Editor::new();
+
+    editor.extension(".toml");
+
+    if let Some(cmd) = editor_command {
+        editor.executable(cmd);
+    }
+
+    loop {
+        let Some(edited_content) = editor.edit(&content).context("Failed to open editor")? else {
+            return Ok(None);
+        };
+
+        match toml::from_str::<Config>(&edited_content) {
+            Ok(config) => {
+                fs::write(path, edited_content.as_bytes())
+                    .context("Failed to write configuration file")?;
+
+                return Ok(Some(config));
+            }
+            Err(e) if reopen(&e)? => content = edited_content,
+            // `reopen` showed the error already
+            Err(_) => anyhow::bail!(
+                "Discarded the edited configuration since it isn't valid TOML. The configuration file is unchanged"
+            ),
+        }
     }
 }
 
-/// Format a host identifier for use as a config key.
-fn format_host_key(remote: &GitRemoteData) -> String {
-    if let Some(port) = remote.port {
-        format!("{}:{}", remote.host, port)
-    } else {
-        remote.host.clone()
+/// Formats the `<HOST>[:<PORT>]/<OWNER>/<REPO>` key of the remote scope
+/// section of a git remote. [`parse_repo_key`] parses it back.
+pub fn format_remote_key(remote: &GitRemoteData) -> String {
+    format!("{}/{}", format_host_key(remote), remote.path)
+}
+
+/// Gets whether the `http/show-rate-limit` setting is enabled in the global
+/// scope. The config is `None` if it failed to load, which counts as disabled
+/// since the command reports that error itself.
+pub fn get_show_rate_limit_setting(config: Option<&Config>) -> bool {
+    config
+        .and_then(|config| config.get_bool(SHOW_RATE_LIMIT_CONFIG_PATH, None))
+        .unwrap_or_default()
+}
+
+/// Gets the minimum interval in milliseconds between requests that change
+/// something from the `http/mutation-interval-ms` setting of the global scope.
+/// Like for [`get_show_rate_limit_setting`], a config that failed to load
+/// counts as unset.
+pub fn get_mutation_interval_setting(config: Option<&Config>) -> u64 {
+    config
+        .and_then(|config| config.get_u32(MUTATION_INTERVAL_CONFIG_PATH, None))
+        .map_or(throttle::DEFAULT_MUTATION_INTERVAL_MS, u64::from)
+}
+
+/// Gets the size at which the audit log is rotated if the `audit-log` setting
+/// is enabled in the global scope. Like for [`get_show_rate_limit_setting`], a
+/// config that failed to load counts as disabled.
+pub fn get_audit_log_setting(config: Option<&Config>) -> Option<u64> {
+    let config = config?;
+
+    if !config.get_bool(AUDIT_LOG_CONFIG_PATH, None)? {
+        return None;
+    }
+
+    let max_size = config
+        .get_string(AUDIT_LOG_MAX_SIZE_CONFIG_PATH, None)
+        .and_then(|value| {
+            audit::parse_size(&value)
+                .inspect_err(|e| {
+                    log::warn!(
+                        "Invalid value for '{AUDIT_LOG_MAX_SIZE_CONFIG_PATH}' in global: '{value}' ({e})"
+                    )
+                })
+                .ok()
+        });
+
+    Some(max_size.unwrap_or(audit::DEFAULT_MAX_SIZE))
+}
+
+/// Gets how the human-readable output shows timestamps from the
+/// `locale/relative-dates` and `locale/date-format` settings of the global
+/// scope. Relative dates take precedence. Like for
+/// [`get_show_rate_limit_setting`], a config that failed to load counts as
+/// unset.
+pub fn get_date_style_setting(config: Option<&Config>) -> DateStyle {
+    let Some(config) = config else {
+        return DateStyle::default();
+    };
+
+    if config
+        .get_bool(RELATIVE_DATES_CONFIG_PATH, None)
+        .unwrap_or_default()
+    {
+        return DateStyle::Relative;
+    }
+
+    config
+        .get_string(DATE_FORMAT_CONFIG_PATH, None)
+        .and_then(|pattern| {
+            DateFormat::parse(&pattern)
+                .inspect_err(|e| {
+                    log::warn!(
+                        "Invalid value for '{DATE_FORMAT_CONFIG_PATH}' in global: '{pattern}' ({e})"
+                    )
+                })
+                .ok()
+        })
+        .map_or_else(DateStyle::default, DateStyle::Format)
+}
+
+/// Gets the `prompt` setting of the global scope. Like for
+/// [`get_show_rate_limit_setting`], a config that failed to load counts as
+/// unset.
+pub fn get_prompt_setting(config: Option<&Config>) -> Option<PromptMode> {
+    config.and_then(|config| config.get_enum(PROMPT_CONFIG_PATH, None))
+}
+
+/// Sets the `forge-type` setting for the host of a git remote in the config
+/// file, so that the forge type doesn't need to be asked for again.
+pub fn remember_forge_type(api_type: ApiType, remote: &GitRemoteData) -> anyhow::Result<()> {
+    remember_forge_type_in(&get_config_path()?, api_type, remote)
+}
+
+/// Formats the command that sets the `forge-type` setting for the host of a git
+/// remote.
+pub fn format_set_forge_type_command(remote: &GitRemoteData) -> String {
+    format_set_command(
+        FORGE_TYPE_CONFIG_PATH,
+        &format!("<{}>", get_possible_values::<ApiType>().replace(", ", "|")),
+        ConfigScope::Host,
+        remote,
+    )
+}
+
+/// Formats the `config set` command that sets a value in a scope of the git
+/// remote.
+pub fn format_set_command(
+    path: &str,
+    value: &str,
+    scope: ConfigScope,
+    remote: &GitRemoteData,
+) -> String {
+    match scope {
+        ConfigScope::Global => format!("git forge config set {path} {value}"),
+        ConfigScope::Host => format!(
+            "git forge config set --scope host --host {} {path} {value}",
+            format_host_key(remote)
+        ),
+        ConfigScope::Remote => format!(
+            "git forge config set --scope remote --repo {} {path} {value}",
+            format_remote_key(remote)
+        ),
+    }
+}
+
+/// Formats how a value in a scope of the git remote looks in a repository's
+/// `.git-forge.toml`.
+pub fn format_repo_setting(
+    path: &str,
+    value: &str,
+    scope: ConfigScope,
+    remote: &GitRemoteData,
+) -> String {
+    match scope {
+        ConfigScope::Global => format!("{path} = {value:?}"),
+        ConfigScope::Host => format!("host.{:?}.{path} = {value:?}", format_host_key(remote)),
+        ConfigScope::Remote => format!("remote.{:?}.{path} = {value:?}", format_remote_key(remote)),
+    }
+}
+
+/// Get the path of the config file.
+fn get_config_path() -> anyhow::Result<PathBuf> {
+    confy::get_configuration_file_path(APP_NAME, CONFIG_NAME).context("Failed to get config path")
+}
+
+/// Loads the settings of the repository's `.git-forge.toml` that it may
+/// contain. A broken file is skipped with a warning so that it can't break
+/// the commands.
+fn load_repo_config() -> Option<Config> {
+    let path = ConfigFile::Repo.get_path().ok()?;
+
+    if !path.exists() {
+        return None;
+    }
+
+    match Config::load_from_path(&path) {
+        Ok(mut config) => {
+            config.retain_repo_settings();
+
+            Some(config)
+        }
+        Err(e) => {
+            log::warn!("Ignoring {}: {e:#}", path.display());
+
+            None
+        }
+    }
+}
+
+/// Whether a repository's `.git-forge.toml` may contain the setting. URLs
+/// must point to the host whose scope they are in, so that tokens can't be
+/// redirected to other hosts.
+fn is_repo_setting(path: &str, value: &str, host_key: Option<&str>) -> bool {
+    if path.ends_with("/remote") {
+        return true;
+    }
+
+    if !REPO_CONFIG_KEYS.contains(&path) {
+        log::warn!(
+            "Ignoring '{path}' in {REPO_CONFIG_NAME}: only {} can be set there",
+            REPO_CONFIG_KEYS.join(", ")
+        );
+
+        return false;
+    }
+
+    if path != API_BASE_URL_CONFIG_PATH && path != WEB_BASE_URL_CONFIG_PATH {
+        return true;
+    }
+
+    let host = host_key.and_then(|host_key| git::parse_host_port(host_key).ok());
+    let url = Url::parse(value).ok();
+    let is_same_host = match (host, url.as_ref().and_then(Url::host_str)) {
+        (Some((host, _)), Some(url_host)) => host.eq_ignore_ascii_case(url_host),
+        _ => false,
+    };
+
+    if !is_same_host {
+        log::warn!(
+            "Ignoring '{path}' = '{value}' in {REPO_CONFIG_NAME}: it must point to the host of its scope"
+        );
+    }
+
+    is_same_host
+}
+
+/// Sets the `forge-type` setting for the host of a git remote in the config
+/// file at `path`. The file is reloaded first to not overwrite changes made
+/// since the config was loaded.
+fn remember_forge_type_in(
+    path: &Path,
+    api_type: ApiType,
+    remote: &GitRemoteData,
+) -> anyhow::Result<()> {
+    let mut config = Config::load_from_path(path)?;
+    let value = api_type
+        .to_possible_value()
+        .context("Forge type has no name")?;
+
+    config.set_value(
+        FORGE_TYPE_CONFIG_PATH,
+        value.get_name(),
+        ConfigSource::Host(remote),
+    )?;
+    config.save_to_path(path)
+}
+
+/// Formats the `<HOST>[:<PORT>]` key of the host scope section of a git
+/// remote. Hostnames are case-insensitive, so the host is lowercased.
+/// [`parse_host_key`] parses it back.
+pub fn format_host_key(remote: &GitRemoteData) -> String {
+    let host = remote.host.to_lowercase();
+
+    match remote.port {
+        Some(port) => format!("{host}:{port}"),
+        None => host,
     }
 }
 
@@ -710,14 +1563,25 @@ fn get_path_variants(path: &str) -> Vec<String> {
     variants
 }
 
-/// Gets the git remote for a given scope.
+/// Gets the git remote for a given scope. An explicit `--host` or `--repo`
+/// target takes precedence over looking up the git remote.
 fn get_remote_for_scope(
     scope: &ConfigScope,
     remote_name: &str,
+    host: Option<GitRemoteData>,
+    repo: Option<GitRemoteData>,
 ) -> anyhow::Result<Option<GitRemoteData>> {
-    let remote = match scope {
-        ConfigScope::Global => None,
-        ConfigScope::Host | ConfigScope::Remote => {
+    let remote = match (scope, host, repo) {
+        (ConfigScope::Global, None, None) => None,
+        (ConfigScope::Global, _, _) => {
+            anyhow::bail!("--host and --repo can only be used with the host or remote scope")
+        }
+        (ConfigScope::Remote, Some(_), _) => {
+            anyhow::bail!("--host can't target the remote scope. Use --repo instead")
+        }
+        (ConfigScope::Host, Some(host), _) => Some(host),
+        (ConfigScope::Host | ConfigScope::Remote, None, Some(repo)) => Some(repo),
+        (ConfigScope::Host | ConfigScope::Remote, None, None) => {
             Some(git::get_remote_data(remote_name).with_context(|| {
                 format!("Failed to get remote URL for remote '{}'", remote_name)
             })?)
@@ -727,38 +1591,376 @@ fn get_remote_for_scope(
     Ok(remote)
 }
 
-/// List values from a specific scope.
-fn print_entire_config_for_scope(config: &Config, source: ConfigSource) -> anyhow::Result<()> {
-    match source {
-        ConfigSource::Global => {
-            let mut sorted_entries: Vec<_> = config.global.iter().collect();
-            sorted_entries.sort_by_key(|(k, _)| *k);
+/// See [`Config::normalize_scope_keys`].
+fn normalize_section_keys(
+    sections: HashMap<String, HashMap<String, String>>,
+) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: Vec<_> = sections
+        .into_iter()
+        .map(|(key, settings)| (normalize_section_key(&key), key, settings))
+        .collect();
+    let mut normalized: HashMap<String, HashMap<String, String>> = HashMap::new();
+
+    // Already normalized keys go last, so that their settings win
+    sections.sort_by_key(|(normalized_key, key, _)| normalized_key == key);
+
+    for (normalized_key, _, settings) in sections {
+        normalized
+            .entry(normalized_key)
+            .or_default()
+            .extend(settings);
+    }
 
-            for (key, value) in sorted_entries {
-                println!("{} = {}", key, value);
-            }
-        }
-        ConfigSource::Host(remote) => {
-            let host_key = format_host_key(remote);
+    normalized
+}
 
-            if let Some(host_cfg) = config.host.get(&host_key) {
-                let mut sorted_entries: Vec<_> = host_cfg.iter().collect();
-                sorted_entries.sort_by_key(|(k, _)| *k);
+/// Lowercases the `<HOST>[:<PORT>]` part of a host or remote scope key.
+fn normalize_section_key(key: &str) -> String {
+    match key.split_once('/') {
+        Some((host, path)) => format!("{}/{path}", host.to_lowercase()),
+        None => key.to_lowercase(),
+    }
+}
 
-                for (key, value) in sorted_entries {
-                    println!("{} = {}", key, value);
-                }
-            }
-        }
-        ConfigSource::Remote(remote) => {
-            let remote_key = format_remote_key(remote);
+/// Gets the git remote to apply the precedence of the scopes with. Without a
+/// git remote, only the global scope is used.
+fn get_remote_for_effective_config(
+    remote_name: &str,
+    host: Option<GitRemoteData>,
+    repo: Option<GitRemoteData>,
+) -> Option<GitRemoteData> {
+    host.or(repo)
+        .or_else(|| git::get_remote_data(remote_name).ok())
+}
 
-            if let Some(remote_cfg) = config.remote.get(&remote_key) {
-                let mut sorted_entries: Vec<_> = remote_cfg.iter().collect();
+/// Parses a `<HOST>[:<PORT>]` host scope key. The host is lowercased like by
+/// [`format_host_key`].
+pub fn parse_host_key(value: &str) -> anyhow::Result<GitRemoteData> {
+    let value = value.to_lowercase();
+    let (host, port) = git::parse_host_port(&value)?;
+
+    if host.is_empty() || host.contains(['/', ' ']) {
+        anyhow::bail!("Expected <HOST>[:<PORT>], e.g. gitlab.example.com or localhost:3000");
+    }
+
+    Ok(GitRemoteData {
+        web_base_url: format!("https://{value}"),
+        host,
+        path: String::new(),
+        port,
+        api_base_url: None,
+    })
+}
+
+/// Parses a `<HOST>[:<PORT>]/<OWNER>/<REPO>` remote scope key. GitLab allows
+/// nested groups, so the path may have more than two segments.
+pub fn parse_repo_key(value: &str) -> anyhow::Result<GitRemoteData> {
+    let usage = "Expected <HOST>[:<PORT>]/<OWNER>/<REPO>, e.g. github.com/leleat/git-forge";
+    let (host, path) = value.split_once('/').context(usage)?;
+    let segments: Vec<&str> = path.split('/').collect();
+
+    if segments.len() < 2 || segments.iter().any(|segment| segment.trim().is_empty()) {
+        anyhow::bail!(usage);
+    }
+
+    Ok(GitRemoteData {
+        path: path.to_string(),
+        ..parse_host_key(host)?
+    })
+}
+
+/// Expands a leading `~` to the home directory and environment variables.
+fn expand_value(value: &str) -> String {
+    let home_dir = etcetera::home_dir().ok();
+
+    expand_value_with(
+        value,
+        home_dir.as_deref().and_then(|dir| dir.to_str()),
+        |name| std::env::var(name).ok(),
+    )
+}
+
+/// Expands a leading `~` (but not `~user`) to `home_dir` and `$VAR` and
+/// `${VAR}` to the variable's value. `$$` is an escaped `$`. Unknown variables
+/// are kept literally. Windows-style `%VAR%` isn't expanded.
+fn expand_value_with(
+    value: &str,
+    home_dir: Option<&str>,
+    get_var: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+
+    if let Some(after_tilde) = value.strip_prefix('~')
+        && (after_tilde.is_empty() || after_tilde.starts_with(['/', std::path::MAIN_SEPARATOR]))
+    {
+        match home_dir {
+            Some(home_dir) => {
+                expanded.push_str(home_dir);
+                rest = after_tilde;
+            }
+            None => {
+                log::warn!("Can't expand '~' in config value '{value}': unknown home directory")
+            }
+        }
+    }
+
+    while let Some(dollar_index) = rest.find('$') {
+        expanded.push_str(&rest[..dollar_index]);
+
+        let after_dollar = &rest[dollar_index + 1..];
+
+        if let Some(after_escape) = after_dollar.strip_prefix('$') {
+            expanded.push('$');
+            rest = after_escape;
+
+            continue;
+        }
+
+        let (name, reference_len) = match after_dollar.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after_dollar
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(after_dollar.len());
+
+                (&after_dollar[..end], end)
+            }
+        };
+
+        if name.is_empty() {
+            expanded.push('$');
+            rest = after_dollar;
+
+            continue;
+        }
+
+        match get_var(name) {
+            Some(var) => expanded.push_str(&var),
+            None => {
+                log::warn!("Environment variable '{name}' in config value '{value}' isn't set");
+                expanded.push_str(&rest[dollar_index..=dollar_index + reference_len]);
+            }
+        }
+
+        rest = &after_dollar[reference_len..];
+    }
+
+    expanded.push_str(rest);
+
+    expanded
+}
+
+/// Formats a config value for `config get`.
+fn format_value(value: &str, raw: bool) -> String {
+    if raw {
+        value.to_string()
+    } else {
+        expand_value(value)
+    }
+}
+
+/// Validates a value for a path against every known full config path that the
+/// path applies to (see `get_path_variants`). E.g. `per-page` has to be valid
+/// for `issue/list/per-page` and `pr/list/per-page`.
+fn validate_config_value(path: &str, value: &str) -> Result<(), ConfigValueError> {
+    let keys: Vec<_> = CONFIG_KEYS
+        .iter()
+        .filter(|(key, _)| get_path_variants(key).iter().any(|variant| variant == path))
+        .collect();
+
+    if keys.is_empty() {
+        return Err(ConfigValueError::UnknownPath);
+    }
+
+    for (key, validate) in keys {
+        validate(value).map_err(|reason| ConfigValueError::InvalidValue { key, reason })?;
+    }
+
+    Ok(())
+}
+
+/// Validates all values of all scopes. Unknown paths are only logged since
+/// they may be meant for a newer git-forge version.
+fn find_invalid_values(config: &Config) -> Vec<String> {
+    let host_entries = config
+        .host
+        .iter()
+        .map(|(host, entries)| (format!("host scope '{host}'"), entries));
+    let remote_entries = config
+        .remote
+        .iter()
+        .map(|(remote, entries)| (format!("remote scope '{remote}'"), entries));
+    let mut scopes: Vec<_> = host_entries.chain(remote_entries).collect();
+
+    scopes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    scopes.insert(0, (ConfigScope::Global.to_string(), &config.global));
+
+    let mut violations = vec![];
+
+    for (scope, entries) in scopes {
+        let mut sorted_entries: Vec<_> = entries.iter().collect();
+        sorted_entries.sort_by_key(|(k, _)| *k);
+
+        for (path, value) in sorted_entries {
+            match validate_config_value(path, value) {
+                Ok(()) => {}
+                Err(ConfigValueError::UnknownPath) => {
+                    log::warn!("Unknown config path '{path}' in {scope}");
+                }
+                Err(e) => violations.push(format!("'{path}' = '{value}' in {scope}: {e}")),
+            }
+        }
+    }
+
+    violations
+}
+
+/// Get the possible values of a `ValueEnum` as a comma-separated list.
+/// Gets the forge type of the GIT_FORGE_API environment variable.
+pub fn get_forge_type_from_env() -> anyhow::Result<Option<ApiType>> {
+    parse_forge_type_env(get_env_var(API_ENV_VAR).as_deref())
+}
+
+fn parse_forge_type_env(value: Option<&str>) -> anyhow::Result<Option<ApiType>> {
+    value
+        .map(|value| {
+            ApiType::from_str(value.trim(), true).map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid value for {API_ENV_VAR}: '{value}' (expected one of: {})",
+                    get_possible_values::<ApiType>()
+                )
+            })
+        })
+        .transpose()
+}
+
+/// Gets an environment variable. Empty values count as unset, e.g. for CI
+/// jobs that always define the variable.
+fn get_env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+fn get_possible_values<T: ValueEnum>() -> String {
+    T::value_variants()
+        .iter()
+        .filter_map(|v| v.to_possible_value().map(|v| v.get_name().to_string()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn validate_string(_value: &str) -> Result<(), String> {
+    Ok(())
+}
+
+fn validate_bool(value: &str) -> Result<(), String> {
+    value
+        .parse::<bool>()
+        .map(|_| ())
+        .map_err(|_| String::from("expected 'true' or 'false'"))
+}
+
+fn validate_u32(value: &str) -> Result<(), String> {
+    value
+        .parse::<u32>()
+        .map(|_| ())
+        .map_err(|_| String::from("expected a positive integer"))
+}
+
+fn validate_size(value: &str) -> Result<(), String> {
+    audit::parse_size(value).map(|_| ())
+}
+
+fn validate_url(value: &str) -> Result<(), String> {
+    match value.split_once("://") {
+        Some(("http" | "https", rest)) if !rest.is_empty() => Ok(()),
+        _ => Err(String::from(
+            "expected an http(s) URL, e.g. http://git.example.com:3000",
+        )),
+    }
+}
+
+fn validate_date_format(value: &str) -> Result<(), String> {
+    DateFormat::parse(value).map(|_| ())
+}
+
+fn validate_enum<T: ValueEnum>(value: &str) -> Result<(), String> {
+    T::from_str(value, true)
+        .map(|_| ())
+        .map_err(|_| format!("expected one of: {}", get_possible_values::<T>()))
+}
+
+/// Validates a comma-separated list of fields. The aliases of the fields are
+/// accepted as well, see `io::parse_field`.
+fn validate_fields<T: ValueEnum>(value: &str) -> Result<(), String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .try_for_each(|item| {
+            io::parse_field::<T>(item).map(|_| ()).map_err(|_| {
+                let aliases = io::get_field_aliases::<T>();
+
+                if aliases.is_empty() {
+                    format!("'{item}' isn't one of: {}", get_possible_values::<T>())
+                } else {
+                    format!(
+                        "'{item}' isn't one of: {} or the aliases {}",
+                        get_possible_values::<T>(),
+                        aliases.join(", ")
+                    )
+                }
+            })
+        })
+}
+
+fn validate_changelog_sections(value: &str) -> Result<(), String> {
+    changelog::parse_sections(value)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// List values from a specific scope.
+fn print_entire_config_for_scope(
+    config: &Config,
+    source: ConfigSource,
+    raw: bool,
+) -> anyhow::Result<()> {
+    match source {
+        ConfigSource::Global => {
+            let mut sorted_entries: Vec<_> = config.global.iter().collect();
+            sorted_entries.sort_by_key(|(k, _)| *k);
+
+            for (key, value) in sorted_entries {
+                println!("{} = {}", key, format_value(value, raw));
+            }
+        }
+        ConfigSource::Host(remote) => {
+            let host_key = format_host_key(remote);
+
+            if let Some(host_cfg) = config.host.get(&host_key) {
+                let mut sorted_entries: Vec<_> = host_cfg.iter().collect();
                 sorted_entries.sort_by_key(|(k, _)| *k);
 
                 for (key, value) in sorted_entries {
-                    println!("{} = {}", key, value);
+                    println!("{} = {}", key, format_value(value, raw));
+                }
+            }
+        }
+        ConfigSource::Remote(remote) => {
+            let remote_key = format_remote_key(remote);
+
+            if let Some(remote_cfg) = config.remote.get(&remote_key) {
+                let mut sorted_entries: Vec<_> = remote_cfg.iter().collect();
+                sorted_entries.sort_by_key(|(k, _)| *k);
+
+                for (key, value) in sorted_entries {
+                    println!("{} = {}", key, format_value(value, raw));
                 }
             }
         }
@@ -771,6 +1973,7 @@ fn print_entire_config_for_scope(config: &Config, source: ConfigSource) -> anyho
 fn print_entire_effective_config(
     config: &Config,
     remote: Option<&GitRemoteData>,
+    raw: bool,
 ) -> anyhow::Result<()> {
     let mut all_paths = HashSet::new();
 
@@ -804,7 +2007,7 @@ fn print_entire_effective_config(
 
     for path in sorted_paths {
         if let Some((value, scope)) = config.get_value_effective(path, remote) {
-            println!("{path} = {value} ({scope})");
+            println!("{path} = {} ({scope})", format_value(&value, raw));
         }
     }
 
@@ -814,15 +2017,280 @@ fn print_entire_effective_config(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_env::EnvGuard;
 
     fn create_git_remote(host: &str, path: &str, port: Option<u16>) -> GitRemoteData {
         GitRemoteData {
             host: host.to_string(),
             path: path.to_string(),
             port,
+            web_base_url: match port {
+                Some(port) => format!("https://{host}:{port}"),
+                None => format!("https://{host}"),
+            },
+            api_base_url: None,
         }
     }
 
+    // =========================================================================
+    // remember_forge_type
+    // =========================================================================
+
+    #[test]
+    fn test_remember_forge_type_persists_host_setting() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("config.toml");
+        let remote = create_git_remote("code.company.com", "owner/repo", None);
+        let mut config = Config::default();
+
+        config
+            .set_value("editor-command", "vim", ConfigSource::Global)
+            .unwrap();
+        config.save_to_path(&path).unwrap();
+
+        remember_forge_type_in(&path, ApiType::GitLab, &remote).unwrap();
+
+        let config = Config::load_from_path(&path).unwrap();
+
+        assert_eq!(config.get_forge_type(&remote), Some(ApiType::GitLab));
+        assert_eq!(
+            config.get_string_from_global_scope("editor-command"),
+            Some("vim".to_string())
+        );
+        assert_eq!(
+            config.get_forge_type(&create_git_remote("code.company.com", "other/repo", None)),
+            Some(ApiType::GitLab)
+        );
+        assert_eq!(
+            config.get_forge_type(&create_git_remote("git.company.com", "owner/repo", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remember_forge_type_creates_config_file() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("nested/config.toml");
+        let remote = create_git_remote("localhost", "owner/repo", Some(3000));
+
+        remember_forge_type_in(&path, ApiType::Forgejo, &remote).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+
+        assert!(content.contains("[host.\"localhost:3000\"]"));
+        assert!(content.contains("forge-type = \"forgejo\""));
+    }
+
+    // =========================================================================
+    // edit_config_file
+    // =========================================================================
+
+    /// Writes a shell script as the editor and returns the command to run it.
+    /// The script gets the path of the file to edit as `$1`.
+    #[cfg(unix)]
+    fn fake_editor(dir: &Path, script: &str) -> String {
+        let path = dir.join("editor.sh");
+
+        fs::write(&path, script).unwrap();
+
+        format!("sh {}", path.display())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_config_file_keeps_file_on_invalid_toml() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("config.toml");
+        let editor = fake_editor(root.path(), "printf 'editor-command = \"vim\\n' > \"$1\"");
+        let mut errors = vec![];
+
+        fs::write(&path, "editor-command = \"nano\"\n").unwrap();
+
+        let result = edit_config_file(&path, Some(&editor), |error| {
+            errors.push(error.to_string());
+
+            Ok(false)
+        });
+
+        assert!(result.is_err());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("line 1, column 22"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "editor-command = \"nano\"\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_config_file_reopens_rejected_content() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("config.toml");
+        let dir = root.path().display();
+        let editor = fake_editor(
+            root.path(),
+            &format!(
+                "if [ -e {dir}/reopened ]; then
+                    cp \"$1\" {dir}/rejected
+                    printf 'editor-command = \"vim\"\\n' > \"$1\"
+                else
+                    touch {dir}/reopened
+                    printf 'editor-command = \"vim\\n' > \"$1\"
+                fi"
+            ),
+        );
+
+        fs::write(&path, "editor-command = \"nano\"\n").unwrap();
+
+        let config = edit_config_file(&path, Some(&editor), |_| Ok(true))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            config.get_string_from_global_scope("editor-command"),
+            Some("vim".to_string())
+        );
+        assert_eq!(
+            fs::read_to_string(root.path().join("rejected")).unwrap(),
+            "editor-command = \"vim"
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "editor-command = \"vim\""
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_config_file_creates_skeleton() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("nested/config.toml");
+        let dir = root.path().display();
+        let editor = fake_editor(
+            root.path(),
+            &format!("cp \"$1\" {dir}/opened; printf 'prompt = \"never\"' > \"$1\""),
+        );
+
+        let config = edit_config_file(&path, Some(&editor), |_| Ok(false))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.path().join("opened")).unwrap(),
+            CONFIG_SKELETON
+        );
+        assert_eq!(
+            config.get_string_from_global_scope("prompt"),
+            Some("never".to_string())
+        );
+        assert!(toml::from_str::<Config>(CONFIG_SKELETON).is_ok());
+
+        // `Config::load_from_disk` leaves an empty file behind
+        fs::write(&path, "").unwrap();
+        edit_config_file(&path, Some(&editor), |_| Ok(false)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(root.path().join("opened")).unwrap(),
+            CONFIG_SKELETON
+        );
+    }
+
+    // =========================================================================
+    // repo config
+    // =========================================================================
+
+    #[test]
+    fn test_retain_repo_settings() {
+        let content = r#"
+            "issue/remote" = "upstream"
+            editor-command = "touch /tmp/pwned"
+
+            [host."git.internal"]
+            forge-type = "gitea"
+            api-base-url = "https://git.internal:3000/api/v1"
+            web-base-url = "https://attacker.example"
+            "auth/allowed-hosts" = "attacker.example"
+        "#;
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join(REPO_CONFIG_NAME);
+
+        fs::write(&path, content).unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        let remote = create_git_remote("git.internal", "owner/repo", None);
+
+        config.retain_repo_settings();
+
+        assert_eq!(
+            config.get_string_from_global_scope("issue/remote"),
+            Some("upstream".to_string())
+        );
+        assert_eq!(config.get_string_from_global_scope("editor-command"), None);
+        assert_eq!(config.get_forge_type(&remote), Some(ApiType::Gitea));
+        assert_eq!(
+            config.get_string_from_host_scope(API_BASE_URL_CONFIG_PATH, &remote),
+            Some("https://git.internal:3000/api/v1".to_string())
+        );
+        assert_eq!(
+            config.get_string_from_host_scope(WEB_BASE_URL_CONFIG_PATH, &remote),
+            None
+        );
+        assert_eq!(
+            config.get_string_from_host_scope(AUTH_ALLOWED_HOSTS_CONFIG_PATH, &remote),
+            None
+        );
+    }
+
+    #[test]
+    fn test_user_config_takes_precedence_over_repo_config() {
+        let remote = create_git_remote("git.internal", "owner/repo", None);
+        let mut repo_config = Config::default();
+        let mut config = Config::default();
+
+        repo_config
+            .set_value(FORGE_TYPE_CONFIG_PATH, "gitea", ConfigSource::Host(&remote))
+            .unwrap();
+        repo_config
+            .set_value("pr/remote", "upstream", ConfigSource::Global)
+            .unwrap();
+        config
+            .set_value(
+                FORGE_TYPE_CONFIG_PATH,
+                "forgejo",
+                ConfigSource::Host(&remote),
+            )
+            .unwrap();
+        config.repo_config = Some(Box::new(repo_config));
+
+        assert_eq!(config.get_forge_type(&remote), Some(ApiType::Forgejo));
+        assert_eq!(
+            config.get_string_from_global_scope("pr/remote"),
+            Some("upstream".to_string())
+        );
+        assert_eq!(
+            config.get_value_in_scope("pr/remote", ConfigScope::Global, &remote),
+            None
+        );
+    }
+
+    #[test]
+    fn test_format_set_command() {
+        let remote = create_git_remote("localhost", "owner/repo", Some(3000));
+
+        assert_eq!(
+            format_set_command("pr/remote", "upstream", ConfigScope::Global, &remote),
+            "git forge config set pr/remote upstream"
+        );
+        assert_eq!(
+            format_set_command(FORGE_TYPE_CONFIG_PATH, "gitea", ConfigScope::Host, &remote),
+            "git forge config set --scope host --host localhost:3000 forge-type gitea"
+        );
+        assert_eq!(
+            format_repo_setting(FORGE_TYPE_CONFIG_PATH, "gitea", ConfigScope::Host, &remote),
+            "host.\"localhost:3000\".forge-type = \"gitea\""
+        );
+    }
+
     // =========================================================================
     // get_path_variants
     // =========================================================================
@@ -1083,4 +2551,340 @@ mod tests {
         assert_eq!(result[0], ConfigScope::Global);
         assert_eq!(result[1], ConfigScope::Host);
     }
+
+    #[test]
+    fn test_parse_host_key() {
+        assert_eq!(
+            parse_host_key("gitlab.example.com").unwrap(),
+            create_git_remote("gitlab.example.com", "", None)
+        );
+        assert_eq!(
+            parse_host_key("localhost:3000").unwrap(),
+            create_git_remote("localhost", "", Some(3000))
+        );
+        assert!(parse_host_key("").is_err());
+        assert!(parse_host_key("localhost:port").is_err());
+        assert!(parse_host_key("github.com/leleat").is_err());
+    }
+
+    #[test]
+    fn test_parse_repo_key() {
+        assert_eq!(
+            parse_repo_key("github.com/leleat/git-forge").unwrap(),
+            create_git_remote("github.com", "leleat/git-forge", None)
+        );
+        assert_eq!(
+            parse_repo_key("localhost:3000/group/subgroup/repo").unwrap(),
+            create_git_remote("localhost", "group/subgroup/repo", Some(3000))
+        );
+        assert!(parse_repo_key("github.com").is_err());
+        assert!(parse_repo_key("github.com/leleat").is_err());
+        assert!(parse_repo_key("github.com/leleat/").is_err());
+        assert!(parse_repo_key("github.com:port/leleat/git-forge").is_err());
+    }
+
+    #[test]
+    fn test_merge_config_into_args_trims_api_urls() {
+        struct Args {
+            api_url: Option<ApiUrl>,
+            query: Option<String>,
+        }
+
+        let config: Config = toml::from_str(
+            r#"
+"issue/list/api-url" = "https://gitlab.example.com/api/v4/"
+"issue/list/query" = "crash/"
+"#,
+        )
+        .unwrap();
+        let mut args = Args {
+            api_url: None,
+            query: None,
+        };
+
+        merge_config_into_args!(&config, args, None, "issue/list", [api_url, query]);
+
+        assert_eq!(
+            args.api_url.as_deref(),
+            Some("https://gitlab.example.com/api/v4")
+        );
+        assert_eq!(args.query.as_deref(), Some("crash/"));
+    }
+
+    #[test]
+    fn test_scope_keys_round_trip() {
+        for key in ["gitlab.example.com", "localhost:3000"] {
+            assert_eq!(format_host_key(&parse_host_key(key).unwrap()), key);
+        }
+
+        for key in [
+            "github.com/leleat/git-forge",
+            "localhost:3000/group/subgroup/repo",
+            "gitlab.example.com:8443/Group/Sub.Group/Repo",
+        ] {
+            assert_eq!(format_remote_key(&parse_repo_key(key).unwrap()), key);
+        }
+
+        let remote = create_git_remote("GitLab.Example.com", "Group/Repo", Some(8443));
+
+        assert_eq!(format_host_key(&remote), "gitlab.example.com:8443");
+        assert_eq!(
+            format_remote_key(&remote),
+            "gitlab.example.com:8443/Group/Repo"
+        );
+        assert_eq!(
+            parse_repo_key("GitLab.Example.com:8443/Group/Repo").unwrap(),
+            parse_repo_key("gitlab.example.com:8443/Group/Repo").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_load_normalizes_scope_keys() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("config.toml");
+
+        std::fs::write(
+            &path,
+            r#"
+[host."GitLab.Example.com"]
+forge-type = "github"
+editor-command = "vim"
+
+[host."gitlab.example.com"]
+forge-type = "gitlab"
+
+[remote."GitLab.Example.com/Group/Repo"]
+"pr/create/draft" = "true"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&path).unwrap();
+        let remote = create_git_remote("gitlab.example.com", "Group/Repo", None);
+
+        assert_eq!(config.get_forge_type(&remote), Some(ApiType::GitLab));
+        assert_eq!(
+            config.get_string_from_host_scope("editor-command", &remote),
+            Some("vim".to_string())
+        );
+        assert_eq!(
+            config.get_value_from_scope("pr/create/draft", ConfigSource::Remote(&remote)),
+            Some("true".to_string())
+        );
+        assert_eq!(
+            config.get_value_from_scope(
+                "pr/create/draft",
+                ConfigSource::Remote(&create_git_remote("gitlab.example.com", "group/repo", None))
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_remote_for_scope_with_explicit_target() {
+        let host = create_git_remote("gitlab.example.com", "", None);
+        let repo = create_git_remote("gitlab.example.com", "owner/repo", None);
+
+        assert_eq!(
+            get_remote_for_scope(&ConfigScope::Host, "origin", Some(host.clone()), None).unwrap(),
+            Some(host.clone())
+        );
+        assert_eq!(
+            get_remote_for_scope(&ConfigScope::Remote, "origin", None, Some(repo.clone())).unwrap(),
+            Some(repo.clone())
+        );
+        assert!(get_remote_for_scope(&ConfigScope::Remote, "origin", Some(host), None).is_err());
+        assert!(get_remote_for_scope(&ConfigScope::Global, "origin", None, Some(repo)).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_value_types() {
+        assert_eq!(validate_config_value("issue/list/per-page", "50"), Ok(()));
+        assert_eq!(validate_config_value("pr/create/draft", "true"), Ok(()));
+        assert_eq!(validate_config_value("api", "GitLab"), Ok(()));
+        assert_eq!(
+            validate_config_value("issue/list/fields", "id, title,all"),
+            Ok(())
+        );
+        assert_eq!(
+            validate_config_value("editor-command", "code --wait"),
+            Ok(())
+        );
+
+        assert_eq!(
+            validate_config_value("issue/list/per-page", "banana"),
+            Err(ConfigValueError::InvalidValue {
+                key: "issue/list/per-page",
+                reason: String::from("expected a positive integer"),
+            })
+        );
+        assert_eq!(
+            validate_config_value("pr/create/draft", "yes"),
+            Err(ConfigValueError::InvalidValue {
+                key: "pr/create/draft",
+                reason: String::from("expected 'true' or 'false'"),
+            })
+        );
+        assert!(
+            validate_config_value("issue/list/fields", "id,tite")
+                .is_err_and(|e| e.to_string().contains("'tite' isn't one of: id, title"))
+        );
+        assert_eq!(
+            validate_config_value("pr/list/fields", "number,headRefName,isDraft"),
+            Ok(())
+        );
+        assert_eq!(
+            validate_config_value("locale/date-format", "%Y-%m-%d %H:%M"),
+            Ok(())
+        );
+        assert!(
+            validate_config_value("locale/date-format", "%c")
+                .is_err_and(|e| e.to_string().contains("'%c' isn't supported"))
+        );
+        assert!(
+            validate_config_value("issue/list/fields", "isDraft")
+                .is_err_and(|e| e.to_string().contains("or the aliases number (id)"))
+        );
+    }
+
+    #[test]
+    fn test_validate_config_value_applies_to_all_matching_keys() {
+        assert_eq!(validate_config_value("state", "open"), Ok(()));
+        assert!(
+            validate_config_value("state", "merged")
+                .is_err_and(|e| e.to_string().contains("issue/list/state"))
+        );
+        assert_eq!(validate_config_value("pr/state", "merged"), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_config_value_unknown_path() {
+        assert_eq!(
+            validate_config_value("issue/list/per_page", "50"),
+            Err(ConfigValueError::UnknownPath)
+        );
+        assert_eq!(
+            validate_config_value("list/per-page", "50"),
+            Err(ConfigValueError::UnknownPath)
+        );
+    }
+
+    #[test]
+    fn test_find_invalid_values_reports_all_violations() {
+        let mut config = Config::default();
+        config
+            .global
+            .insert("per-page".to_string(), "banana".to_string());
+        config
+            .global
+            .insert("some-future-setting".to_string(), "x".to_string());
+        config.host.insert(
+            "gitlab.example.com".to_string(),
+            HashMap::from([("pr/list/draft".to_string(), "maybe".to_string())]),
+        );
+        config.remote.insert(
+            "github.com/owner/repo".to_string(),
+            HashMap::from([("editor".to_string(), "true".to_string())]),
+        );
+
+        let violations = find_invalid_values(&config);
+
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].starts_with("'per-page' = 'banana' in global scope"));
+        assert!(violations[1].starts_with("'pr/list/draft' = 'maybe' in host scope"));
+    }
+
+    fn expand(value: &str) -> String {
+        expand_value_with(value, Some("/home/user"), |name| match name {
+            "HOME" => Some(String::from("/home/user")),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_expand_value_tilde() {
+        assert_eq!(expand("~"), "/home/user");
+        assert_eq!(expand("~/bin/editor"), "/home/user/bin/editor");
+        assert_eq!(expand("~user/bin"), "~user/bin");
+        assert_eq!(expand("a/~/b"), "a/~/b");
+        assert_eq!(expand_value_with("~/bin", None, |_| None), "~/bin");
+    }
+
+    #[test]
+    fn test_expand_value_variables() {
+        assert_eq!(expand("$HOME/certs/corp.pem"), "/home/user/certs/corp.pem");
+        assert_eq!(expand("${HOME}certs"), "/home/usercerts");
+        assert_eq!(expand("a${EMPTY}b"), "ab");
+        assert_eq!(expand("$HOME-$HOME"), "/home/user-/home/user");
+    }
+
+    #[test]
+    fn test_expand_value_corner_cases() {
+        assert_eq!(expand("price: $$5"), "price: $5");
+        assert_eq!(expand("$$HOME"), "$HOME");
+        assert_eq!(expand("$MISSING/bin"), "$MISSING/bin");
+        assert_eq!(expand("${MISSING}/bin"), "${MISSING}/bin");
+        assert_eq!(expand("${HOME"), "${HOME");
+        assert_eq!(expand("trailing $"), "trailing $");
+        assert_eq!(expand("$ and $/"), "$ and $/");
+        assert_eq!(expand(r"%HOME%\bin"), r"%HOME%\bin");
+    }
+
+    // =========================================================================
+    // Environment variables
+    // =========================================================================
+
+    #[test]
+    fn test_parse_forge_type_env() {
+        assert_eq!(parse_forge_type_env(None).unwrap(), None);
+        assert_eq!(
+            parse_forge_type_env(Some("GitLab")).unwrap(),
+            Some(ApiType::GitLab)
+        );
+        assert_eq!(
+            parse_forge_type_env(Some(" forgejo ")).unwrap(),
+            Some(ApiType::Forgejo)
+        );
+
+        let error = parse_forge_type_env(Some("gitbucket")).unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid value for GIT_FORGE_API: 'gitbucket'")
+        );
+    }
+
+    #[test]
+    fn test_get_api_base_url_falls_back_to_env_var() {
+        let remote = create_git_remote("gitlab.internal", "owner/repo", None);
+        let config = Config {
+            host: HashMap::from([(
+                "gitlab.internal".to_string(),
+                HashMap::from([(
+                    API_BASE_URL_CONFIG_PATH.to_string(),
+                    "https://api.gitlab.internal/".to_string(),
+                )]),
+            )]),
+            ..Default::default()
+        };
+
+        let mut env = EnvGuard::lock();
+
+        env.set(API_URL_ENV_VAR, "https://proxy.internal/api/v4/");
+
+        assert_eq!(
+            config.get_api_base_url(&remote),
+            Some("https://api.gitlab.internal".to_string())
+        );
+        assert_eq!(
+            Config::default().get_api_base_url(&remote),
+            Some("https://proxy.internal/api/v4".to_string())
+        );
+
+        env.set(API_URL_ENV_VAR, " ");
+
+        assert_eq!(Config::default().get_api_base_url(&remote), None);
+    }
 }
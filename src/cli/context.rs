@@ -0,0 +1,293 @@
+//! The `context` subcommand.
+
+use anyhow::Context as _;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        config::{self, Config},
+        forge::{self, ApiType, ApiUrl, HttpClient, forgejo, gitea, github, gitlab},
+    },
+    git::{self, GitRemoteData},
+    io::ResultFormat,
+};
+
+const DEFAULT_REMOTE: &str = "origin";
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `context` subcommand.
+#[derive(Args)]
+pub struct ContextCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection. Only used with --fresh
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN). Only used with --fresh
+    #[arg(long)]
+    auth: bool,
+
+    /// Output format [default: text]
+    #[arg(short = 'o', long)]
+    format: Option<ResultFormat>,
+
+    /// Ask the forge for the default branch instead of using what git knows
+    /// locally, and for the open PR of the current branch if git doesn't know
+    /// it
+    #[arg(long)]
+    fresh: bool,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+/// What repository and forge the current directory belongs to. The keys of
+/// the JSON output are stable: fields are only ever added, never renamed or
+/// removed, and unknown values are `null`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RepoContext {
+    /// The name of the git remote.
+    pub remote: String,
+    /// The URL of the git remote as configured in git.
+    pub url: String,
+    pub host: String,
+    /// The path of the repository, e.g. `owner/repo`.
+    pub path: String,
+    /// The forge type if it can be detected without asking, e.g. `github`.
+    pub forge: Option<String>,
+    /// The default branch as last fetched (`refs/remotes/<REMOTE>/HEAD`) or,
+    /// with `--fresh`, as the forge reports it.
+    pub default_branch: Option<String>,
+    /// The checked out branch. `null` in detached HEAD state.
+    pub branch: Option<String>,
+    /// The PR of the checked out branch as remembered by `pr checkout` and
+    /// `pr create`. Otherwise, with `--fresh`, its open PR on the forge.
+    pub pr: Option<u32>,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Prints the repository context. Without `--fresh`, it only asks git and
+/// reads the config, so that it's fast enough for shell prompts. That's also
+/// why it takes the config that `run` loaded for the global settings instead
+/// of loading it again.
+pub fn print_context(
+    mut args: ContextCommandArgs,
+    config: anyhow::Result<Config>,
+) -> anyhow::Result<()> {
+    let config = config.context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("context/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "context",
+        [api, api_url, auth, format]
+    );
+
+    // Unlike the other commands, an unknown forge isn't an error and isn't
+    // asked for, since a prompt would block the shell prompt
+    let api_type = forge::detect_api_type(args.api, &config, &remote)
+        .inspect_err(|e| log::debug!("{e:#}"))
+        .ok();
+    let branch = git::get_current_branch()
+        .inspect_err(|e| log::debug!("{e:#}"))
+        .ok();
+    let mut context = RepoContext {
+        url: git::get_remote_url(&remote_name)?,
+        host: remote.host.clone(),
+        path: remote.path.clone(),
+        forge: api_type.and_then(|api_type| {
+            api_type
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+        }),
+        default_branch: git::get_default_branch(&remote_name).ok(),
+        pr: branch.as_deref().and_then(git::get_branch_pr),
+        branch,
+        remote: remote_name,
+    };
+
+    if args.fresh {
+        let api_type = api_type.with_context(|| {
+            format!(
+                "--fresh needs the forge type. Specify it with --api or run `{}`",
+                config::format_set_forge_type_command(&remote)
+            )
+        })?;
+
+        refresh_context(
+            &mut context,
+            &remote,
+            api_type,
+            args.api_url.as_deref(),
+            args.auth,
+        )?;
+    }
+
+    match args.format.unwrap_or_default() {
+        ResultFormat::Text => print!("{}", format_context(&context)),
+        ResultFormat::Json => println!("{}", serde_json::to_string_pretty(&context)?),
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// Replaces the default branch with the one of the forge and looks up the
+/// open PR of the current branch if git doesn't know it yet.
+fn refresh_context(
+    context: &mut RepoContext,
+    remote: &GitRemoteData,
+    api_type: ApiType,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    let http_client = HttpClient::new();
+    let get_repo = forge::function!(api_type, get_repo);
+    let repo = get_repo(&http_client, remote, api_url, use_auth)
+        .context("Failed fetching the repository")?;
+
+    context.default_branch = Some(repo.default_branch);
+
+    if let Some(branch) = &context.branch {
+        context.pr = find_branch_pr(context.pr, || {
+            let get_open_prs_for_branch = forge::function!(api_type, get_open_prs_for_branch);
+            let prs = get_open_prs_for_branch(&http_client, remote, api_url, branch, use_auth)
+                .with_context(|| format!("Failed fetching the open PRs of branch \"{branch}\""))?;
+
+            Ok(prs.first().map(|pr| pr.id))
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Prefers the PR that `pr checkout` or `pr create` stored for the branch
+/// over looking it up. The branches of `pr checkout` are named `pr-<N>`,
+/// which no PR on the forge has as its head.
+fn find_branch_pr(
+    stored_pr: Option<u32>,
+    look_up: impl FnOnce() -> anyhow::Result<Option<u32>>,
+) -> anyhow::Result<Option<u32>> {
+    match stored_pr {
+        Some(pr) => Ok(Some(pr)),
+        None => look_up(),
+    }
+}
+
+/// Formats the context as `key<TAB>value` lines. Unknown values are empty.
+fn format_context(context: &RepoContext) -> String {
+    [
+        ("remote", Some(context.remote.clone())),
+        ("url", Some(context.url.clone())),
+        ("host", Some(context.host.clone())),
+        ("path", Some(context.path.clone())),
+        ("forge", context.forge.clone()),
+        ("default_branch", context.default_branch.clone()),
+        ("branch", context.branch.clone()),
+        ("pr", context.pr.map(|pr| pr.to_string())),
+    ]
+    .into_iter()
+    .map(|(key, value)| format!("{key}\t{}\n", value.unwrap_or_default()))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> RepoContext {
+        RepoContext {
+            remote: String::from("origin"),
+            url: String::from("git@github.com:user/repo.git"),
+            host: String::from("github.com"),
+            path: String::from("user/repo"),
+            forge: Some(String::from("github")),
+            default_branch: Some(String::from("main")),
+            branch: Some(String::from("pr-7")),
+            pr: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_json_keys_are_stable() {
+        let json = serde_json::to_value(RepoContext {
+            forge: None,
+            pr: None,
+            ..context()
+        })
+        .unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "remote": "origin",
+                "url": "git@github.com:user/repo.git",
+                "host": "github.com",
+                "path": "user/repo",
+                "forge": null,
+                "default_branch": "main",
+                "branch": "pr-7",
+                "pr": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_format_context() {
+        assert_eq!(
+            format_context(&RepoContext {
+                branch: None,
+                pr: None,
+                ..context()
+            }),
+            "remote\torigin\n\
+             url\tgit@github.com:user/repo.git\n\
+             host\tgithub.com\n\
+             path\tuser/repo\n\
+             forge\tgithub\n\
+             default_branch\tmain\n\
+             branch\t\n\
+             pr\t\n"
+        );
+    }
+
+    #[test]
+    fn test_find_branch_pr_keeps_the_stored_pr() {
+        // A `pr checkout` branch like pr-7 has no PR with it as its head
+        let pr = find_branch_pr(context().pr, || Ok(None)).unwrap();
+
+        assert_eq!(pr, Some(7));
+        assert_eq!(find_branch_pr(None, || Ok(Some(8))).unwrap(), Some(8));
+        assert_eq!(find_branch_pr(None, || Ok(None)).unwrap(), None);
+    }
+}
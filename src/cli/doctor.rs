@@ -0,0 +1,114 @@
+//! The `doctor` subcommand.
+
+use anyhow::Context;
+use clap::Args;
+
+use crate::{
+    cli::{
+        config::{self, Config},
+        forge::{self, ApiType, Capability, forgejo, gitea, github, gitlab},
+    },
+    logging,
+    storage::{BaseDirs, StorageCategory},
+};
+
+const DEFAULT_REMOTE: &str = "origin";
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `doctor` subcommand.
+#[derive(Args)]
+pub struct DoctorCommandArgs {
+    /// Specify the forge to resolve the API base URL for. By default, it's
+    /// guessed from the host
+    #[arg(long, value_name = "TYPE")]
+    api: Option<ApiType>,
+
+    /// Only print the location of the log file
+    #[arg(long)]
+    logs: bool,
+
+    /// Git remote to resolve the web and API base URLs for
+    #[arg(long, default_value = DEFAULT_REMOTE)]
+    remote: String,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Prints information that helps with debugging problems.
+pub fn doctor(args: DoctorCommandArgs) -> anyhow::Result<()> {
+    let log_file_path = logging::get_log_file_path()?;
+
+    if args.logs {
+        println!("{}", log_file_path.display());
+
+        return Ok(());
+    }
+
+    let config_path = confy::get_configuration_file_path(std::env!("CARGO_PKG_NAME"), "config")
+        .context("Failed to get config path")?;
+    let base_dirs = BaseDirs::from_env()?;
+
+    println!("version\t{}", std::env!("CARGO_PKG_VERSION"));
+    println!("config\t{}", config_path.display());
+    println!("log\t{}", log_file_path.display());
+
+    for category in StorageCategory::ALL {
+        println!(
+            "{category}\t{}",
+            base_dirs.get_category_dir(category).display()
+        );
+    }
+
+    let config = Config::load_from_disk()?;
+
+    match config.get_remote_data(&args.remote) {
+        Ok(remote) => {
+            println!("web\t{}", remote.web_base_url);
+
+            match forge::detect_api_type(args.api, &config, &remote) {
+                Ok(api_type) => {
+                    let get_url_for_api = forge::function!(api_type, get_url_for_api);
+
+                    println!("api\t{}", get_url_for_api(&remote));
+                    println!("client\t{}", get_client_name(api_type));
+
+                    for capability in Capability::ALL {
+                        println!(
+                            "capability/{}\t{}",
+                            capability.name(),
+                            forge::supports(api_type, capability)
+                        );
+                    }
+                }
+                Err(_) => println!(
+                    "api\tunknown forge (specify it with --api or run `{}`)",
+                    config::format_set_forge_type_command(&remote)
+                ),
+            }
+        }
+        Err(e) => log::info!("Skipping the base URLs of remote '{}': {e:#}", args.remote),
+    }
+
+    eprintln!("\nSet GIT_FORGE_LOG=debug or pass --verbose for more diagnostics.");
+
+    Ok(())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// The forge module that sends the requests for the forge type.
+fn get_client_name(api_type: ApiType) -> &'static str {
+    match api_type {
+        ApiType::GitHub => "github",
+        ApiType::GitLab => "gitlab",
+        ApiType::Gitea => "gitea",
+        ApiType::Forgejo => "forgejo (extends gitea)",
+    }
+}
@@ -1,4 +1,48 @@
+use std::ops::Deref;
+
+use anyhow::Context;
 use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        config::{self, Config},
+        forge::{auth_guard, forgejo},
+    },
+    git::GitRemoteData,
+    io,
+};
+
+/// Long help of the --api options. It must match [`detect_api_type`].
+pub const API_LONG_HELP: &str = "Specify the forge which affects the API schema etc
+
+The forge type is taken from the first of: --api, the `api` setting of the command, the host-scoped `forge-type` setting, the GIT_FORGE_API environment variable and the keywords in the hostname of the git remote";
+
+/// Long help of the --api-url options. It must match
+/// `Config::get_remote_data`.
+pub const API_URL_LONG_HELP: &str = "Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4) instead of relying on the auto-detection
+
+The API base URL is taken from the first of: --api-url, the `api-url` setting of the command, the host-scoped `api-base-url` setting, the GIT_FORGE_API_URL environment variable and the URL of the git remote";
+
+/// A base API URL from an --api-url option or an `api-url` setting, see
+/// [`parse_api_url`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ApiUrl(String);
+
+impl Deref for ApiUrl {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Parses the value of the --api-url options and `api-url` settings. Trailing
+/// slashes are trimmed since the API paths are appended with a slash, e.g.
+/// `<URL>/repos/...`.
+pub fn parse_api_url(value: &str) -> Result<ApiUrl, String> {
+    Ok(ApiUrl(value.trim_end_matches('/').to_string()))
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
 #[value(rename_all = "lowercase")]
@@ -9,6 +53,58 @@ pub enum ApiType {
     Forgejo,
 }
 
+/// Gets the forge type of a git remote like [`detect_api_type`]. If the forge
+/// type can't be detected and stdin and stderr are terminals, the user is asked
+/// for it instead and can choose to remember the answer for the host.
+pub fn get_api_type(
+    api_type: Option<ApiType>,
+    config: &Config,
+    remote: &GitRemoteData,
+) -> anyhow::Result<ApiType> {
+    let error = match detect_api_type(api_type, config, remote) {
+        Ok(api_type) => return Ok(api_type),
+        Err(e) => e,
+    };
+
+    match prompt_api_type(remote)? {
+        Some(api_type) => {
+            register_remote(api_type, config, remote);
+
+            Ok(api_type)
+        }
+        None => Err(error),
+    }
+}
+
+/// Gets the forge type of a git remote. An explicit forge type, e.g. from
+/// --api, takes precedence over the host-scoped `forge-type` setting, which
+/// takes precedence over the GIT_FORGE_API environment variable and guessing
+/// the forge type from the hostname. Tokens may be sent to the hosts of the
+/// remote's web interface and API afterwards.
+pub fn detect_api_type(
+    api_type: Option<ApiType>,
+    config: &Config,
+    remote: &GitRemoteData,
+) -> anyhow::Result<ApiType> {
+    let api_type = match api_type.or_else(|| config.get_forge_type(remote)) {
+        Some(api_type) => api_type,
+        None => match config::get_forge_type_from_env()? {
+            Some(api_type) => api_type,
+            None => guess_api_type_from_host(&remote.host).with_context(|| {
+                format!(
+                    "Failed to guess forge from host: {}. To always use a forge type for this host, run `{}`",
+                    &remote.host,
+                    config::format_set_forge_type_command(remote)
+                )
+            })?,
+        },
+    };
+
+    register_remote(api_type, config, remote);
+
+    Ok(api_type)
+}
+
 pub fn guess_api_type_from_host(host: &str) -> anyhow::Result<ApiType> {
     let host = host.to_lowercase();
 
@@ -27,6 +123,52 @@ pub fn guess_api_type_from_host(host: &str) -> anyhow::Result<ApiType> {
     )
 }
 
+/// Remembers the forge type of the remote's hosts for the requests of the
+/// command, i.e. that tokens may be sent to them and which of them are Forgejo
+/// instances.
+fn register_remote(api_type: ApiType, config: &Config, remote: &GitRemoteData) {
+    auth_guard::allow_remote(api_type, config, remote);
+
+    if api_type == ApiType::Forgejo {
+        forgejo::register_remote(remote);
+    }
+}
+
+/// Asks the user which forge the host of a git remote is. If the user wants
+/// to, the answer is saved to the host scope of the config. Returns `None` if
+/// the user can't be asked or doesn't answer.
+fn prompt_api_type(remote: &GitRemoteData) -> anyhow::Result<Option<ApiType>> {
+    let api_types = ApiType::value_variants();
+    let names: Vec<String> = api_types
+        .iter()
+        .filter_map(|api_type| api_type.to_possible_value())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    let Some(index) =
+        io::prompt_select(&format!("Which forge is {}?", remote.host), &names, "--api")?
+    else {
+        return Ok(None);
+    };
+    let api_type = api_types[index];
+
+    if io::prompt_confirm(
+        &format!("Remember {} for {}?", names[index], remote.host),
+        true,
+        "--api",
+    )? {
+        // Remembering is a convenience, so don't fail the command
+        match config::remember_forge_type(api_type, remote) {
+            Ok(()) => eprintln!(
+                "Saved forge-type {} for {} to the host scope of the config.",
+                names[index], remote.host
+            ),
+            Err(e) => log::warn!("Failed to remember the forge type: {e:#}"),
+        }
+    }
+
+    Ok(Some(api_type))
+}
+
 /// Gets a forge-specific function
 ///
 /// # Example
@@ -42,7 +184,8 @@ macro_rules! function {
         match $type {
             ApiType::GitHub => github::$fn_name,
             ApiType::GitLab => gitlab::$fn_name,
-            ApiType::Gitea | ApiType::Forgejo => gitea::$fn_name,
+            ApiType::Gitea => gitea::$fn_name,
+            ApiType::Forgejo => forgejo::$fn_name,
         }
     };
 }
@@ -51,8 +194,46 @@ pub(crate) use function;
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::*;
 
+    fn create_remote(host: &str) -> GitRemoteData {
+        GitRemoteData {
+            host: host.to_string(),
+            path: "owner/repo".to_string(),
+            port: None,
+            web_base_url: format!("https://{host}"),
+            api_base_url: None,
+        }
+    }
+
+    fn create_config_with_forge_type(host: &str, forge_type: &str) -> Config {
+        Config {
+            host: HashMap::from([(
+                host.to_string(),
+                HashMap::from([("forge-type".to_string(), forge_type.to_string())]),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_api_url_trims_trailing_slashes() {
+        assert_eq!(
+            &*parse_api_url("https://gitlab.example.com/api/v4/").unwrap(),
+            "https://gitlab.example.com/api/v4"
+        );
+        assert_eq!(
+            &*parse_api_url("http://localhost:3000/api/v1//").unwrap(),
+            "http://localhost:3000/api/v1"
+        );
+        assert_eq!(
+            &*parse_api_url("https://api.github.com").unwrap(),
+            "https://api.github.com"
+        );
+    }
+
     #[test]
     fn test_guess_api_type_from_host() {
         let github_result = guess_api_type_from_host("https://github.com");
@@ -79,4 +260,45 @@ mod tests {
 
         assert!(unknown_forge_result.is_err());
     }
+
+    #[test]
+    fn test_detect_api_type_prefers_explicit_type() {
+        let remote = create_remote("gitlab.example.com");
+        let config = create_config_with_forge_type("gitlab.example.com", "gitea");
+
+        let api_type = detect_api_type(Some(ApiType::GitHub), &config, &remote).unwrap();
+
+        assert_eq!(api_type, ApiType::GitHub);
+    }
+
+    #[test]
+    fn test_detect_api_type_prefers_configured_forge_type_over_host() {
+        let remote = create_remote("gitlab.example.com");
+        let config = create_config_with_forge_type("gitlab.example.com", "gitea");
+
+        let api_type = detect_api_type(None, &config, &remote).unwrap();
+
+        assert_eq!(api_type, ApiType::Gitea);
+    }
+
+    #[test]
+    fn test_detect_api_type_falls_back_to_host() {
+        let remote = create_remote("gitlab.example.com");
+        let config = create_config_with_forge_type("code.company.com", "gitea");
+
+        let api_type = detect_api_type(None, &config, &remote).unwrap();
+
+        assert_eq!(api_type, ApiType::GitLab);
+    }
+
+    #[test]
+    fn test_detect_api_type_suggests_forge_type_setting() {
+        let remote = create_remote("code.company.com");
+
+        let error = detect_api_type(None, &Config::default(), &remote).unwrap_err();
+
+        assert!(format!("{error:#}").contains(
+            "git forge config set --scope host --host code.company.com forge-type <github|gitlab|gitea|forgejo>"
+        ));
+    }
 }
@@ -0,0 +1,228 @@
+//! Guard against sending a token to the wrong host, e.g. because of a typo in
+//! --api-url.
+//!
+//! Resolving the forge type of a remote allows the hosts of its web interface
+//! and its API. Hosts of the `auth/allowed-hosts` setting are allowed as well.
+//! Commands without a git remote, e.g. `self-update`, allow the hosts of the
+//! repository they talk to themselves.
+//! `with_auth` refuses to attach a token to requests to any other host unless
+//! --force-auth-host is used.
+
+use std::sync::{
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use reqwest::Url;
+
+use crate::{
+    cli::{
+        config::Config,
+        forge::{ApiType, forgejo, function, gitea, github, gitlab},
+    },
+    git::GitRemoteData,
+};
+
+static FORCED: AtomicBool = AtomicBool::new(false);
+static ALLOWED_HOSTS: Mutex<Vec<AllowedHost>> = Mutex::new(vec![]);
+
+/// A host that tokens may be sent to. Without a port, any port of the host is
+/// allowed.
+#[derive(Debug, PartialEq)]
+struct AllowedHost {
+    host: String,
+    port: Option<u16>,
+}
+
+impl AllowedHost {
+    fn from_url(url: &str) -> Option<Self> {
+        let url = Url::parse(url).ok()?;
+
+        Some(Self {
+            host: url.host_str()?.to_lowercase(),
+            port: url.port_or_known_default(),
+        })
+    }
+
+    /// Parses an entry of the `auth/allowed-hosts` setting, e.g. `example.com`
+    /// or `example.com:8443`.
+    fn from_setting(entry: &str) -> Option<Self> {
+        let entry = entry.trim();
+
+        if entry.is_empty() {
+            return None;
+        }
+
+        match entry.rsplit_once(':') {
+            Some((host, port)) => Some(Self {
+                host: host.to_lowercase(),
+                port: Some(port.parse().ok()?),
+            }),
+            None => Some(Self {
+                host: entry.to_lowercase(),
+                port: None,
+            }),
+        }
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        url.host_str()
+            .is_some_and(|host| host.eq_ignore_ascii_case(&self.host))
+            && self
+                .port
+                .is_none_or(|port| url.port_or_known_default() == Some(port))
+    }
+}
+
+/// Sets whether tokens are sent to any host (--force-auth-host).
+pub fn set_forced(forced: bool) {
+    FORCED.store(forced, Ordering::Relaxed);
+}
+
+/// Allows sending tokens to the hosts of the remote's web interface and API
+/// and to the hosts of the `auth/allowed-hosts` setting.
+pub fn allow_remote(api_type: ApiType, config: &Config, remote: &GitRemoteData) {
+    let hosts = get_remote_hosts(api_type, config, remote);
+
+    if let Ok(mut allowed_hosts) = ALLOWED_HOSTS.lock() {
+        for host in hosts {
+            if !allowed_hosts.contains(&host) {
+                allowed_hosts.push(host);
+            }
+        }
+    }
+}
+
+/// Fails if the token of `env_var` must not be sent to the host of `url`.
+pub fn check(url: &Url, env_var: &str) -> anyhow::Result<()> {
+    if FORCED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    let allowed_hosts = ALLOWED_HOSTS
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Failed to read the hosts allowed for authentication"))?;
+
+    if is_allowed(url, &allowed_hosts) {
+        return Ok(());
+    }
+
+    let host = url.host_str().unwrap_or_default();
+    let host_with_port = match url.port_or_known_default() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+
+    anyhow::bail!(
+        "Refusing to send the token of {env_var} to {host_with_port} since it isn't a host of \
+         the git remote or its API. If the host is correct, allow it with `git forge config set \
+         auth/allowed-hosts {host_with_port}` or use --force-auth-host"
+    )
+}
+
+/// Whether `allow_remote` would allow sending tokens to the host of `url`.
+/// Unlike `check`, it doesn't depend on the hosts that were allowed before.
+#[cfg(all(test, feature = "self-update"))]
+pub fn is_allowed_for_remote(
+    url: &Url,
+    api_type: ApiType,
+    config: &Config,
+    remote: &GitRemoteData,
+) -> bool {
+    is_allowed(url, &get_remote_hosts(api_type, config, remote))
+}
+
+fn get_remote_hosts(
+    api_type: ApiType,
+    config: &Config,
+    remote: &GitRemoteData,
+) -> Vec<AllowedHost> {
+    let get_url_for_api = function!(api_type, get_url_for_api);
+
+    [remote.web_base_url.clone(), get_url_for_api(remote)]
+        .iter()
+        .filter_map(|url| AllowedHost::from_url(url))
+        .chain(
+            config
+                .get_allowed_auth_hosts(remote)
+                .iter()
+                .filter_map(|entry| AllowedHost::from_setting(entry)),
+        )
+        .collect()
+}
+
+fn is_allowed(url: &Url, allowed_hosts: &[AllowedHost]) -> bool {
+    allowed_hosts.iter().any(|allowed| allowed.matches(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed_hosts(urls: &[&str], entries: &[&str]) -> Vec<AllowedHost> {
+        urls.iter()
+            .filter_map(|url| AllowedHost::from_url(url))
+            .chain(
+                entries
+                    .iter()
+                    .filter_map(|entry| AllowedHost::from_setting(entry)),
+            )
+            .collect()
+    }
+
+    fn is_url_allowed(url: &str, allowed_hosts: &[AllowedHost]) -> bool {
+        is_allowed(&Url::parse(url).unwrap(), allowed_hosts)
+    }
+
+    #[test]
+    fn test_is_allowed_for_hosts_of_the_remote() {
+        let hosts = allowed_hosts(&["https://github.com", "https://api.github.com"], &[]);
+
+        assert!(is_url_allowed("https://api.github.com/repos/u/r", &hosts));
+        assert!(is_url_allowed("https://GitHub.com/api/graphql", &hosts));
+        assert!(!is_url_allowed("https://api.gihtub.com/repos/u/r", &hosts));
+        assert!(!is_url_allowed("https://github.com.evil.com/repos", &hosts));
+    }
+
+    #[test]
+    fn test_is_allowed_compares_ports() {
+        let hosts = allowed_hosts(&["https://git.internal:3000"], &[]);
+
+        assert!(is_url_allowed("http://git.internal:3000/api/v1", &hosts));
+        assert!(!is_url_allowed("https://git.internal/api/v1", &hosts));
+        assert!(!is_url_allowed("https://git.internal:3001/api/v1", &hosts));
+
+        // Default ports are the same as explicit ones
+        let hosts = allowed_hosts(&["https://gitlab.com"], &[]);
+
+        assert!(is_url_allowed("https://gitlab.com:443/api/v4", &hosts));
+        assert!(!is_url_allowed("http://gitlab.com/api/v4", &hosts));
+    }
+
+    #[test]
+    fn test_is_allowed_for_hosts_of_the_setting() {
+        let hosts = allowed_hosts(&[], &["proxy.internal", " mirror.internal:8443 ", ""]);
+
+        assert!(is_url_allowed("https://proxy.internal/api/v4", &hosts));
+        assert!(is_url_allowed("http://proxy.internal:8080/api/v4", &hosts));
+        assert!(is_url_allowed(
+            "https://mirror.internal:8443/api/v4",
+            &hosts
+        ));
+        assert!(!is_url_allowed("https://mirror.internal/api/v4", &hosts));
+        assert!(!is_url_allowed("https://example.com/api/v4", &hosts));
+    }
+
+    #[test]
+    fn test_allowed_host_from_setting() {
+        assert_eq!(
+            AllowedHost::from_setting("Example.com:8443"),
+            Some(AllowedHost {
+                host: String::from("example.com"),
+                port: Some(8443),
+            })
+        );
+        assert_eq!(AllowedHost::from_setting("example.com:abc"), None);
+        assert_eq!(AllowedHost::from_setting("  "), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Features that not every forge supports.
+//!
+//! Each forge module has a `supports` function that tells whether a
+//! [`Capability`] is available. The forge functions consult it before sending
+//! requests, so the errors for unsupported features read the same everywhere,
+//! and `doctor` prints the matrix for the forge of a remote.
+
+use std::fmt;
+
+use crate::cli::forge::{ApiType, forgejo, function, gitea, github, gitlab};
+
+/// A feature whose support differs between the forges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Capability {
+    /// Merging PRs once their checks pass (pr merge --auto).
+    AutoMerge,
+    /// Filtering issues by why they were closed (--closed-as).
+    ClosedAsFilter,
+    /// Creating and filtering draft PRs.
+    DraftPrs,
+    /// Enabling and disabling webhooks (hook enable/disable).
+    HookToggle,
+    /// Pinning and unpinning issues.
+    IssuePinning,
+    /// Keeping maintainers from pushing to the branch of a new PR (pr create
+    /// --no-maintainer-edit).
+    MaintainerEdit,
+    /// Filtering PRs by the merged state (--state merged).
+    MergedStateFilter,
+    /// Filtering issues by whether they are pinned (--pinned).
+    PinnedFilter,
+    /// Filtering and sorting listed issues by their reactions.
+    Reactions,
+    /// Deleting the branch of a PR once it's merged (pr create
+    /// --remove-source-branch).
+    RemoveSourceBranch,
+    /// Squashing the commits of a PR when it's merged (pr create
+    /// --squash-on-merge).
+    SquashOnMerge,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 11] = [
+        Capability::AutoMerge,
+        Capability::ClosedAsFilter,
+        Capability::DraftPrs,
+        Capability::HookToggle,
+        Capability::IssuePinning,
+        Capability::MaintainerEdit,
+        Capability::MergedStateFilter,
+        Capability::PinnedFilter,
+        Capability::Reactions,
+        Capability::RemoveSourceBranch,
+        Capability::SquashOnMerge,
+    ];
+
+    /// The name `doctor` prints for the capability.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::AutoMerge => "auto-merge",
+            Capability::ClosedAsFilter => "closed-as-filter",
+            Capability::DraftPrs => "draft-prs",
+            Capability::HookToggle => "hook-toggle",
+            Capability::IssuePinning => "issue-pinning",
+            Capability::MaintainerEdit => "maintainer-edit",
+            Capability::MergedStateFilter => "merged-state-filter",
+            Capability::PinnedFilter => "pinned-filter",
+            Capability::Reactions => "reactions",
+            Capability::RemoveSourceBranch => "remove-source-branch",
+            Capability::SquashOnMerge => "squash-on-merge",
+        }
+    }
+}
+
+/// Whether a forge supports a [`Capability`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Support {
+    Yes,
+    /// Unsupported. The reason is the error message for the user.
+    No(&'static str),
+    /// Supported with a workaround that behaves differently from the other
+    /// forges.
+    Degraded(&'static str),
+}
+
+impl Support {
+    /// Fails with the reason if the capability is unsupported.
+    pub fn ensure(self) -> anyhow::Result<()> {
+        match self {
+            Support::No(reason) => anyhow::bail!(reason),
+            Support::Yes | Support::Degraded(_) => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Support {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Support::Yes => write!(f, "yes"),
+            Support::No(reason) => write!(f, "no ({reason})"),
+            Support::Degraded(note) => write!(f, "degraded ({note})"),
+        }
+    }
+}
+
+/// Gets whether the forge supports the capability.
+pub fn supports(api_type: ApiType, capability: Capability) -> Support {
+    let supports = function!(api_type, supports);
+
+    supports(capability)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_fails_only_if_unsupported() {
+        assert!(Support::Yes.ensure().is_ok());
+        assert!(Support::Degraded("Filtered client-side").ensure().is_ok());
+        assert_eq!(
+            Support::No("Forge does not support it")
+                .ensure()
+                .unwrap_err()
+                .to_string(),
+            "Forge does not support it"
+        );
+    }
+
+    #[test]
+    fn test_supports_dispatches_to_the_forge() {
+        assert_eq!(
+            supports(ApiType::GitHub, Capability::MergedStateFilter),
+            Support::Yes
+        );
+        assert!(matches!(
+            supports(ApiType::Forgejo, Capability::MergedStateFilter),
+            Support::No(_)
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+//! Forgejo-specific differences on top of the Gitea module.
+//!
+//! Forgejo is a fork of Gitea and still serves the Gitea API, so the requests
+//! and the mapping of the responses live in [`gitea`] and are re-exported here.
+//! Resolving the forge type of a remote as Forgejo registers the hosts of its
+//! web interface and API. Requests to those hosts then use the Forgejo token,
+//! user agent and version endpoint.
+
+use std::sync::Mutex;
+
+use reqwest::Url;
+
+use crate::{cli::forge::gitea, git::GitRemoteData};
+
+pub use gitea::{
+    add_issue_labels, assign_issue, cancel_pr_auto_merge, close_issue, create_issue, create_label,
+    create_pr, delete_branch, edit_repo, get_authenticated_user, get_branches, get_closed_issues,
+    get_commit_date, get_hooks, get_issue, get_issue_comments, get_issues, get_issues_for_export,
+    get_labels, get_merged_prs, get_merged_prs_for_branch, get_open_prs, get_open_prs_for_branch,
+    get_pr, get_pr_approvals, get_pr_auto_merge, get_pr_closing_issues, get_pr_counts,
+    get_pr_merge_info, get_pr_ref, get_pr_review_status, get_pr_review_statuses,
+    get_pr_timeline_events, get_prs, get_repo, get_repo_status, get_repos, get_url_for_api,
+    get_url_for_commit, get_url_for_home, get_url_for_issue, get_url_for_issue_comment,
+    get_url_for_issue_creation, get_url_for_issue_search, get_url_for_issues, get_url_for_labels,
+    get_url_for_milestones, get_url_for_path, get_url_for_pr, get_url_for_pr_creation,
+    get_url_for_prs, get_url_for_releases, has_branch, is_branch_merged, merge_pr, ping_hook,
+    remove_issue_labels, set_hook_active, set_issue_labels, set_issue_pinned, supports,
+    supports_native_drafts, unassign_issue, update_pr_reviewers,
+};
+
+/// Used instead of the Gitea token if it's set.
+pub const AUTH_TOKEN: &str = "GIT_FORGE_FORGEJO_TOKEN";
+pub const USER_AGENT: &str = "git-forge (Forgejo)";
+
+static HOSTS: Mutex<Vec<ForgejoHost>> = Mutex::new(vec![]);
+
+#[derive(Debug, PartialEq)]
+struct ForgejoHost {
+    host: String,
+    port: Option<u16>,
+}
+
+impl ForgejoHost {
+    fn from_url(url: &Url) -> Option<Self> {
+        Some(Self {
+            host: url.host_str()?.to_lowercase(),
+            port: url.port_or_known_default(),
+        })
+    }
+}
+
+/// Remembers the hosts of the remote's web interface and API as Forgejo hosts.
+pub fn register_remote(remote: &GitRemoteData) {
+    let hosts = [remote.web_base_url.clone(), get_url_for_api(remote)]
+        .iter()
+        .filter_map(|url| Url::parse(url).ok())
+        .filter_map(|url| ForgejoHost::from_url(&url))
+        .collect::<Vec<_>>();
+
+    if let Ok(mut registered_hosts) = HOSTS.lock() {
+        for host in hosts {
+            if !registered_hosts.contains(&host) {
+                registered_hosts.push(host);
+            }
+        }
+    }
+}
+
+/// Whether the URL points to the host of a remote resolved as Forgejo.
+pub fn is_forgejo_url(url: &Url) -> bool {
+    let Some(host) = ForgejoHost::from_url(url) else {
+        return false;
+    };
+
+    HOSTS
+        .lock()
+        .is_ok_and(|registered_hosts| registered_hosts.contains(&host))
+}
+
+/// Gets the environment variable of the token for a request to `url`. Forgejo
+/// hosts use the Forgejo token and fall back to `env_var`, i.e. the Gitea
+/// token.
+pub fn get_auth_env_var<'a>(url: &Url, env_var: &'a str) -> &'a str {
+    choose_auth_env_var(
+        env_var,
+        is_forgejo_url(url),
+        std::env::var_os(AUTH_TOKEN).is_some(),
+    )
+}
+
+/// Gets the URL of the version endpoint that reports the Forgejo version
+/// besides the Gitea compatibility version, e.g.
+/// `https://codeberg.org/api/forgejo/v1/version`. Returns `None` if the base
+/// URL isn't the Gitea API of the instance.
+pub fn get_url_for_version(base_url: &str) -> Option<String> {
+    base_url
+        .trim_end_matches('/')
+        .strip_suffix("/api/v1")
+        .map(|instance_url| format!("{instance_url}/api/forgejo/v1/version"))
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+fn choose_auth_env_var(env_var: &str, is_forgejo_host: bool, has_forgejo_token: bool) -> &str {
+    if is_forgejo_host && has_forgejo_token {
+        AUTH_TOKEN
+    } else {
+        env_var
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_remote(web_base_url: &str) -> GitRemoteData {
+        let url = Url::parse(web_base_url).unwrap();
+
+        GitRemoteData {
+            host: url.host_str().unwrap().to_string(),
+            path: String::from("user/repo"),
+            port: url.port(),
+            web_base_url: web_base_url.to_string(),
+            api_base_url: None,
+        }
+    }
+
+    fn is_forgejo(url: &str) -> bool {
+        is_forgejo_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_is_forgejo_url_for_registered_remotes() {
+        register_remote(&create_remote("https://forgejo-test.internal:3000"));
+
+        assert!(is_forgejo(
+            "http://forgejo-test.internal:3000/api/v1/version"
+        ));
+        assert!(is_forgejo("https://FORGEJO-TEST.internal:3000/user/repo"));
+        assert!(!is_forgejo("https://forgejo-test.internal/api/v1"));
+        assert!(!is_forgejo("https://gitea-test.internal:3000/api/v1"));
+    }
+
+    #[test]
+    fn test_choose_auth_env_var_falls_back_to_gitea_token() {
+        let gitea_token = "GIT_FORGE_GITEA_TOKEN";
+
+        assert_eq!(choose_auth_env_var(gitea_token, true, true), AUTH_TOKEN);
+        assert_eq!(choose_auth_env_var(gitea_token, true, false), gitea_token);
+        assert_eq!(choose_auth_env_var(gitea_token, false, true), gitea_token);
+    }
+
+    #[test]
+    fn test_get_url_for_version() {
+        assert_eq!(
+            get_url_for_version("https://codeberg.org/api/v1").as_deref(),
+            Some("https://codeberg.org/api/forgejo/v1/version")
+        );
+        assert_eq!(
+            get_url_for_version("http://git.internal:3000/forgejo/api/v1/").as_deref(),
+            Some("http://git.internal:3000/forgejo/api/forgejo/v1/version")
+        );
+        assert_eq!(get_url_for_version("https://proxy.internal/gitea"), None);
+    }
+}
@@ -1,19 +1,54 @@
+use std::collections::HashMap;
+
 use anyhow::Context;
-use serde::Deserialize;
+use reqwest::blocking::RequestBuilder;
+use serde::{Deserialize, de::DeserializeOwned};
 
 use crate::{
     cli::{
-        forge::http_client::{
-            self, HttpClient, IntoPaginatedResponse, PaginatedResponse, WithAuth, WithHttpStatusOk,
+        branch::Branch,
+        changelog::{ChangelogEntry, ChangelogEntryKind, ChangelogFilters},
+        forge::{
+            Capability, Support, forgejo,
+            http_client::{
+                self, HttpClient, IntoPaginatedResponse, PaginatedResponse, ParseJson,
+                SendThrottled, WithAuth, WithHttpStatusOk,
+            },
+            identity::{self, Identity},
+            labels,
+            precheck::RepoStatus,
+            server_version, url_encoding,
+        },
+        hook::{self, Hook},
+        issue::{
+            self, CreateIssueOptions, ExportedIssue, Issue, IssueComment, IssueKind, IssueSort,
+            IssueState, ListIssueFilters,
+        },
+        pr::{
+            CreatePrOptions, ListPrsFilters, Pr, PrApprovals, PrBranch, PrCiStatus, PrCounts,
+            PrMergeInfo, PrMergeOutcome, PrMergeable, PrReviewStatus, PrState, PrTimelineEvent,
+            PrTimelineEventKind, PrTimelineOptions,
         },
-        issue::{CreateIssueOptions, Issue, IssueState, ListIssueFilters},
-        pr::{CreatePrOptions, ListPrsFilters, Pr, PrState},
+        repo::{self, ListReposFilters, Repo, RepoEdit, RepoOwner, RepoSummary},
     },
+    date::{self, DateRange, Timestamp},
     git::GitRemoteData,
 };
 
 const AUTH_TOKEN: &str = "GIT_FORGE_GITEA_TOKEN";
 const AUTH_SCHEME: &str = "token";
+/// The name the forge shows for deleted accounts.
+const DELETED_USER: &str = "Ghost";
+const HOOKS_PERMISSION_HINT: &str =
+    "Webhooks need a token with the write:repository scope and admin rights on the repository";
+
+/// The first Gitea version accepting the `draft` field when creating PRs.
+/// Forgejo reports its Gitea compatibility version, so this covers both.
+const MIN_NATIVE_DRAFT_VERSION: (u32, u32, u32) = (1, 24, 0);
+/// The first Gitea version that can schedule merges via the API.
+const MIN_SCHEDULED_MERGE_VERSION: (u32, u32, u32) = (1, 19, 0);
+/// Title prefixes Gitea/Forgejo treat as work-in-progress markers.
+const DRAFT_TITLE_PREFIXES: [&str; 4] = ["wip:", "[wip]", "draft:", "[draft]"];
 
 // =============================================================================
 // Domain Types
@@ -27,11 +62,20 @@ struct GiteaIssue {
     title: String,
     state: IssueState,
     labels: Vec<GiteaLabel>,
+    milestone: Option<GiteaMilestone>,
     user: GiteaUser,
+    /// `null` if the issue isn't assigned to anyone.
+    #[serde(default)]
+    assignees: Option<Vec<GiteaUser>>,
     html_url: String,
     pull_request: Option<GiteaIssuePrField>,
-    created_at: String,
-    updated_at: String,
+    body: Option<String>,
+    created_at: Timestamp,
+    updated_at: Timestamp,
+    closed_at: Option<Timestamp>,
+    /// The position among the pinned issues or 0 if the issue isn't pinned.
+    /// Missing in Gitea versions before 1.21.
+    pin_order: Option<u32>,
 }
 
 impl From<GiteaIssue> for Issue {
@@ -40,19 +84,57 @@ impl From<GiteaIssue> for Issue {
             id: issue.number,
             title: issue.title,
             state: issue.state,
+            state_reason: None,
+            author: issue.user.login,
+            url: issue.html_url,
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            pinned: issue.pin_order.map(|order| order > 0),
+            created_at: Some(issue.created_at),
+            closed_at: issue.closed_at,
+            // The issue listing doesn't contain the reactions.
+            reactions: None,
+            kind: issue.pull_request.map(|_| IssueKind::Pr),
+        }
+    }
+}
+
+impl From<GiteaIssue> for ChangelogEntry {
+    fn from(issue: GiteaIssue) -> Self {
+        ChangelogEntry {
+            kind: ChangelogEntryKind::Issue,
+            id: issue.number,
+            title: issue.title,
+            author: issue.user.login,
+            url: issue.html_url,
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            closed_at: issue.closed_at,
+        }
+    }
+}
+
+impl From<GiteaIssue> for ExportedIssue {
+    fn from(issue: GiteaIssue) -> Self {
+        ExportedIssue {
+            id: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            state: issue.state,
             author: issue.user.login,
+            created_at: issue.created_at.to_string(),
             url: issue.html_url,
             labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            comments: vec![],
         }
     }
 }
 
 impl From<GiteaIssue> for Pr {
     fn from(issue: GiteaIssue) -> Self {
-        let (draft, merged) = issue
+        let (draft, merged, merged_at) = issue
             .pull_request
-            .map(|pr| (pr.draft, pr.merged))
+            .map(|pr| (pr.draft, pr.merged, pr.merged_at))
             .unwrap_or_default();
+        let draft = draft || has_draft_title_prefix(&issue.title);
 
         Pr {
             id: issue.number,
@@ -70,9 +152,24 @@ impl From<GiteaIssue> for Pr {
             author: issue.user.login,
             url: issue.html_url,
             labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            milestone: issue.milestone.map(|milestone| milestone.title),
             created_at: issue.created_at,
             updated_at: issue.updated_at,
+            merged_at,
+            closed_at: issue.closed_at,
+            // The issues endpoint doesn't return the branches
+            head: None,
+            base: None,
             draft,
+            approvals_required: None,
+            approvals_left: None,
+            review_status: None,
+            mergeable: None,
+            ci: None,
+            // Unlike pull requests, issues don't count the review comments
+            comments: None,
+            commits: None,
+            changed_files: None,
         }
     }
 }
@@ -82,15 +179,133 @@ struct GiteaLabel {
     name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct GiteaMilestone {
+    title: String,
+}
+
+/// https://docs.gitea.com/api/next/#tag/issue/operation/issueListLabels
+#[derive(Debug, Deserialize)]
+struct GiteaRepoLabel {
+    id: u64,
+    name: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GiteaUser {
     login: String,
 }
 
+/// Gitea/Forgejo API response for the authenticated user.
+/// https://docs.gitea.com/api/#tag/user/operation/userGetCurrent
+#[derive(Debug, Deserialize)]
+struct GiteaAuthenticatedUser {
+    login: String,
+    id: u64,
+}
+
+impl From<GiteaAuthenticatedUser> for Identity {
+    fn from(user: GiteaAuthenticatedUser) -> Self {
+        Identity {
+            username: user.login,
+            id: user.id,
+        }
+    }
+}
+
+/// The assignees of a Gitea/Forgejo API issue response. `null` if there are
+/// none.
+#[derive(Debug, Deserialize)]
+struct GiteaIssueAssignees {
+    assignees: Option<Vec<GiteaUser>>,
+}
+
+/// The requested reviewers of a Gitea/Forgejo API pull request response.
+#[derive(Debug, Deserialize)]
+struct GiteaPrReviewers {
+    requested_reviewers: Option<Vec<GiteaUser>>,
+}
+
 #[derive(Debug, Deserialize)]
 struct GiteaIssuePrField {
+    // Older versions don't send the draft field
+    #[serde(default)]
     draft: bool,
     merged: bool,
+    merged_at: Option<Timestamp>,
+}
+
+/// Gitea/Forgejo API response for pull request reviews.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoListPullReviews
+#[derive(Debug, Deserialize)]
+struct GiteaReview {
+    user: Option<GiteaUser>,
+    state: String,
+    #[serde(default)]
+    dismissed: bool,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    submitted_at: Option<Timestamp>,
+}
+
+/// Gitea/Forgejo API response for issue comments. The single pull request
+/// endpoint has the same shape for the description.
+/// https://docs.gitea.com/api/#tag/issue/operation/issueGetComments
+#[derive(Debug, Deserialize)]
+struct GiteaComment {
+    id: u64,
+    user: Option<GiteaUser>,
+    body: Option<String>,
+    created_at: Timestamp,
+}
+
+impl From<GiteaComment> for IssueComment {
+    fn from(comment: GiteaComment) -> Self {
+        IssueComment {
+            id: comment.id,
+            author: get_login(comment.user.as_ref()),
+            created_at: comment.created_at.to_string(),
+            body: comment.body.unwrap_or_default(),
+        }
+    }
+}
+
+impl GiteaComment {
+    fn into_timeline_event(self, kind: PrTimelineEventKind) -> PrTimelineEvent {
+        PrTimelineEvent {
+            kind,
+            author: get_login(self.user.as_ref()),
+            created_at: self.created_at,
+            body: self.body.unwrap_or_default(),
+        }
+    }
+}
+
+/// Gitea/Forgejo API response for a single pull request.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoGetPullRequest
+#[derive(Debug, Deserialize)]
+struct GiteaPullRequestDetails {
+    mergeable: bool,
+    merged: bool,
+    head: GiteaCommitRef,
+    comments: Option<u32>,
+    /// Missing in Gitea versions before 1.22.
+    review_comments: Option<u32>,
+    changed_files: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaCommitRef {
+    sha: String,
+}
+
+/// Gitea/Forgejo API response for the combined commit status.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoGetCombinedStatusByRef
+#[derive(Debug, Deserialize)]
+struct GiteaCombinedStatus {
+    state: String,
+    total_count: u32,
 }
 
 /// Gitea/Forgejo API response for pull requests.
@@ -101,16 +316,43 @@ struct GiteaPullRequest {
     title: String,
     state: String,
     labels: Vec<GiteaLabel>,
+    milestone: Option<GiteaMilestone>,
     user: GiteaUser,
-    created_at: String,
-    updated_at: String,
+    created_at: Timestamp,
+    updated_at: Timestamp,
     html_url: String,
+    #[serde(default)]
     draft: bool,
     merged: bool,
+    merged_at: Option<Timestamp>,
+    closed_at: Option<Timestamp>,
+    head: GiteaBranchRef,
+    base: GiteaBranchRef,
+    comments: Option<u32>,
+    /// Missing in Gitea versions before 1.22.
+    review_comments: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaBranchRef {
+    #[serde(rename = "ref")]
+    name: String,
+    sha: String,
+}
+
+impl From<GiteaBranchRef> for PrBranch {
+    fn from(branch: GiteaBranchRef) -> Self {
+        PrBranch {
+            name: branch.name,
+            sha: Some(branch.sha),
+        }
+    }
 }
 
 impl From<GiteaPullRequest> for Pr {
     fn from(pr: GiteaPullRequest) -> Self {
+        let draft = pr.draft || has_draft_title_prefix(&pr.title);
+
         Pr {
             id: pr.number,
             title: pr.title,
@@ -122,17 +364,216 @@ impl From<GiteaPullRequest> for Pr {
             author: pr.user.login,
             url: pr.html_url,
             labels: pr.labels.into_iter().map(|l| l.name).collect(),
+            milestone: pr.milestone.map(|milestone| milestone.title),
             created_at: pr.created_at,
             updated_at: pr.updated_at,
-            draft: pr.draft,
+            merged_at: pr.merged_at,
+            closed_at: pr.closed_at,
+            head: Some(pr.head.into()),
+            base: Some(pr.base.into()),
+            draft,
+            approvals_required: None,
+            approvals_left: None,
+            review_status: None,
+            mergeable: None,
+            ci: None,
+            comments: get_comment_count(pr.comments, pr.review_comments),
+            commits: None,
+            changed_files: None,
+        }
+    }
+}
+
+/// Gitea/Forgejo API response for a repository.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoGet
+#[derive(Debug, Deserialize)]
+struct GiteaRepository {
+    full_name: String,
+    #[serde(default)]
+    description: String,
+    html_url: String,
+    #[serde(default)]
+    default_branch: String,
+    archived: bool,
+}
+
+/// Gitea/Forgejo API response for the repositories of an organization or user.
+/// https://docs.gitea.com/api/#tag/organization/operation/orgListRepos
+#[derive(Debug, Deserialize)]
+struct GiteaRepositorySummary {
+    full_name: String,
+    private: bool,
+    /// Only visible to signed-in users. Missing on older versions.
+    #[serde(default)]
+    internal: bool,
+    stars_count: u32,
+    #[serde(default)]
+    default_branch: String,
+    /// Gitea/Forgejo don't track pushes separately.
+    updated_at: Option<Timestamp>,
+    archived: bool,
+    fork: bool,
+    html_url: String,
+    clone_url: String,
+    ssh_url: String,
+}
+
+impl From<GiteaRepositorySummary> for RepoSummary {
+    fn from(repo: GiteaRepositorySummary) -> Self {
+        let visibility = if repo.private {
+            "private"
+        } else if repo.internal {
+            "internal"
+        } else {
+            "public"
+        };
+
+        RepoSummary {
+            name: repo.full_name,
+            visibility: String::from(visibility),
+            stars: repo.stars_count,
+            default_branch: repo.default_branch,
+            pushed_at: repo.updated_at,
+            archived: repo.archived,
+            fork: repo.fork,
+            url: repo.html_url,
+            clone_url: repo.clone_url,
+            ssh_url: repo.ssh_url,
+        }
+    }
+}
+
+/// Gitea/Forgejo API response for webhooks. The authorization header is left
+/// out since it may contain a secret.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoListHooks
+#[derive(Debug, Deserialize)]
+struct GiteaHook {
+    id: u64,
+    config: GiteaHookConfig,
+    events: Vec<String>,
+    active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaHookConfig {
+    #[serde(default)]
+    url: String,
+}
+
+impl From<GiteaHook> for Hook {
+    fn from(hook: GiteaHook) -> Self {
+        Hook {
+            id: hook.id,
+            url: hook::redact_url(&hook.config.url),
+            events: hook.events,
+            active: hook.active,
+            last_delivery: None,
+        }
+    }
+}
+
+/// Gitea/Forgejo API response for the topics of a repository.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoListTopics
+#[derive(Debug, Deserialize)]
+struct GiteaTopics {
+    topics: Vec<String>,
+}
+
+/// Gitea/Forgejo API response for branches.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoListBranches
+#[derive(Debug, Deserialize)]
+struct GiteaBranch {
+    name: String,
+    commit: GiteaBranchCommit,
+    protected: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaBranchCommit {
+    id: String,
+    timestamp: Timestamp,
+}
+
+impl From<GiteaBranch> for Branch {
+    fn from(branch: GiteaBranch) -> Self {
+        Branch {
+            name: branch.name,
+            sha: branch.commit.id,
+            last_commit_at: Some(branch.commit.timestamp),
+            age: None,
+            protected: branch.protected,
+            merged: None,
+            pr: None,
+            stale: None,
         }
     }
 }
 
+/// Gitea/Forgejo API response for a commit.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoGetSingleCommit
+#[derive(Debug, Deserialize)]
+struct GiteaCommit {
+    commit: GiteaCommitDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaCommitDetails {
+    committer: GiteaCommitSignature,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaCommitSignature {
+    date: Timestamp,
+}
+
+/// Gitea/Forgejo API response for comparing two commits.
+/// https://docs.gitea.com/api/#tag/repository/operation/repoCompareDiff
+#[derive(Debug, Deserialize)]
+struct GiteaComparison {
+    total_commits: u32,
+}
+
+/// Gitea/Forgejo API response for the server version.
+/// https://docs.gitea.com/api/#tag/miscellaneous/operation/getVersion
+#[derive(Debug, Deserialize)]
+struct GiteaServerVersion {
+    version: String,
+}
+
 // =============================================================================
 // Command Logic
 // =============================================================================
 
+pub fn supports(capability: Capability) -> Support {
+    match capability {
+        Capability::AutoMerge => Support::Degraded(
+            "needs Gitea 1.19 or newer and the API doesn't tell whether a merge is scheduled",
+        ),
+        Capability::ClosedAsFilter => Support::No(
+            "Gitea/Forgejo does not track why an issue was closed. --closed-as is only supported for GitHub",
+        ),
+        Capability::DraftPrs => Support::Degraded(
+            "versions before 1.24 mark drafts with a WIP: title prefix and drafts are filtered client-side",
+        ),
+        Capability::MergedStateFilter => Support::No(
+            "Gitea/Forgejo does not support filtering by merged state. Use --state=closed to see both closed and merged PRs",
+        ),
+        Capability::Reactions => Support::No(
+            "Gitea/Forgejo does not return reactions when listing issues. --min-reactions and --sort reactions are only supported for GitHub and GitLab",
+        ),
+        Capability::RemoveSourceBranch => Support::No(
+            "Gitea/Forgejo deletes the branch when merging instead. --remove-source-branch is only supported for GitLab",
+        ),
+        Capability::SquashOnMerge => Support::No(
+            "Gitea/Forgejo chooses the merge style when merging instead. --squash-on-merge is only supported for GitLab",
+        ),
+        Capability::HookToggle
+        | Capability::IssuePinning
+        | Capability::MaintainerEdit
+        | Capability::PinnedFilter => Support::Yes,
+    }
+}
+
 pub fn get_issues(
     http_client: &HttpClient,
     remote: &GitRemoteData,
@@ -140,48 +581,21 @@ pub fn get_issues(
     filters: &ListIssueFilters,
     use_auth: bool,
 ) -> anyhow::Result<PaginatedResponse<Issue>> {
-    let base_url = match api_url {
-        Some(url) => url,
-        None => &build_api_base_url(remote),
-    };
-    let repo_path = &remote.path;
-    let endpoint_url = format!("{base_url}/repos/{repo_path}/issues");
-
-    let mut request = http_client
-        .get(&endpoint_url)
-        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
-        .query(&[("state", filters.state)])
-        .query(&[("page", filters.page)])
-        .query(&[("limit", filters.per_page)])
-        .query(&[("type", "issues")]);
-
-    if let Some(assignee) = filters.assignee {
-        request = request.query(&[("assigned_by", assignee)]);
-    }
-
-    if let Some(author) = filters.author {
-        request = request.query(&[("created_by", author)]);
-    }
-
-    if !filters.labels.is_empty() {
-        request = request.query(&[("labels", filters.labels.join(","))]);
-    }
+    let mut response = fetch_issues(http_client, remote, api_url, filters, use_auth)?;
 
-    if let Some(query) = filters.query {
-        request = request.query(&[("q", query)]);
-    }
-
-    let response = request
-        .send()
-        .context("Failed to fetch items from Gitea Search API")?
-        .with_http_status_ok()?;
+    issue::mark_issue_kinds(&mut response.items, filters);
 
-    let has_next_page = http_client::has_next_link_header(&response);
+    Ok(response)
+}
 
-    response
-        .json()
-        .context("Failed to parse Gitea Search API response")
-        .map(|res: Vec<GiteaIssue>| res.into_paginated_response(has_next_page))
+pub fn get_issues_for_export(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ExportedIssue>> {
+    fetch_issues(http_client, remote, api_url, filters, use_auth)
 }
 
 pub fn create_issue(
@@ -196,9 +610,11 @@ pub fn create_issue(
     };
     let repo_path = &remote.path;
     let url = format!("{base_url}/repos/{repo_path}/issues");
+    let label_ids = get_label_ids(http_client, base_url, repo_path, options.labels)?;
     let request_body = serde_json::json!({
         "title": options.title,
         "body": options.body,
+        "labels": label_ids,
     });
 
     eprintln!("Creating issue on Gitea/Forgejo...");
@@ -209,194 +625,2645 @@ pub fn create_issue(
         .json(&request_body);
 
     request
-        .send()
+        .send_throttled()
         .context("Network request failed while creating issue on Gitea/Forgejo")?
         .with_http_status_ok()?
-        .json()
+        .parse_json()
         .context("Failed to parse Gitea/Forgejo API response")
         .map(|issue: GiteaIssue| issue.into())
 }
 
-pub fn get_prs(
+pub fn get_issue(
     http_client: &HttpClient,
     remote: &GitRemoteData,
     api_url: Option<&str>,
-    filters: &ListPrsFilters,
+    issue_number: u32,
     use_auth: bool,
-) -> anyhow::Result<PaginatedResponse<Pr>> {
-    // Check for unsupported filters
-    if filters.draft {
-        anyhow::bail!("Gitea/Forgejo does not support filtering by draft status");
-    }
+) -> anyhow::Result<ExportedIssue> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
 
-    if matches!(filters.state, PrState::Merged) {
-        anyhow::bail!(
-            "Gitea/Forgejo does not support filtering by merged state. Use --state=closed to see both closed and merged PRs"
-        );
-    }
+    get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/issues/{issue_number}", remote.path),
+        use_auth,
+    )
+    .map(|issue: GiteaIssue| issue.into())
+}
 
+pub fn get_issue_comments(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Vec<IssueComment>> {
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let repo_path = &remote.path;
-    let url = format!("{base_url}/repos/{repo_path}/issues");
-
-    let mut request = http_client
-        .get(&url)
-        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
-        .query(&[("type", "pulls")])
-        .query(&[("state", filters.state)])
-        .query(&[("page", filters.page)])
-        .query(&[("limit", filters.per_page)]);
-
-    if let Some(author) = filters.author {
-        request = request.query(&[("created_by", author)]);
-    }
-
-    if !filters.labels.is_empty() {
-        request = request.query(&[("labels", filters.labels.join(","))]);
-    }
-
-    if let Some(query) = filters.query {
-        request = request.query(&[("q", query)]);
-    }
-
-    let response = request
-        .send()
-        .context("Network request failed while fetching pull requests from Gitea/Forgejo")?
-        .with_http_status_ok()?;
-
-    let has_next_page = http_client::has_next_link_header(&response);
+    let comments: Vec<GiteaComment> = get_json(
+        http_client,
+        &format!("{base_url}/repos/{repo_path}/issues/{issue_number}/comments"),
+        use_auth,
+    )?;
 
-    response
-        .json()
-        .context("Failed to parse API response")
-        .map(|items: Vec<GiteaIssue>| {
-            items
-                .into_iter()
-                .map(Into::into)
-                .collect::<Vec<Pr>>()
-                .into_paginated_response(has_next_page)
-        })
+    Ok(comments.into_iter().map(IssueComment::from).collect())
 }
 
-pub fn create_pr(
+pub fn close_issue(
     http_client: &HttpClient,
     remote: &GitRemoteData,
     api_url: Option<&str>,
-    options: &CreatePrOptions,
-) -> anyhow::Result<Pr> {
+    issue_number: u32,
+) -> anyhow::Result<()> {
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let repo_path = &remote.path;
-    let url = format!("{base_url}/repos/{repo_path}/pulls");
-    let request_body = serde_json::json!({
-        "title": if options.draft { format!("WIP: {}", options.title) } else { options.title.to_string() },
-        "head": options.source_branch,
-        "base": options.target_branch,
-        "body": options.body,
-    });
-
-    eprintln!("Creating pull request on Gitea/Forgejo...");
 
-    let request = http_client
-        .post(&url)
+    http_client
+        .patch(&format!(
+            "{base_url}/repos/{repo_path}/issues/{issue_number}"
+        ))
         .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
-        .json(&request_body);
+        .json(&serde_json::json!({ "state": "closed" }))
+        .send_throttled()
+        .context("Network request failed while closing issue on Gitea/Forgejo")?
+        .with_http_status_ok()?;
 
-    request
-        .send()
-        .context("Network request failed while creating pull request on Gitea/Forgejo")?
-        .with_http_status_ok()?
-        .json()
-        .context("Failed to parse Gitea/Forgejo API response")
-        .map(|pr: GiteaPullRequest| pr.into())
+    Ok(())
 }
 
-pub fn get_pr_ref(pr_number: u32) -> String {
-    format!("pull/{pr_number}/head")
+/// Assigns the issue to the users and returns the resulting assignees.
+pub fn assign_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_assignees(http_client, remote, api_url, issue_number, assignees, true)
 }
 
-pub fn get_url_for_home(remote: &GitRemoteData) -> String {
-    build_web_base_url(remote)
+/// Removes the users from the assignees of the issue and returns the
+/// remaining assignees.
+pub fn unassign_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_assignees(http_client, remote, api_url, issue_number, assignees, false)
 }
 
-pub fn get_url_for_commit(remote: &GitRemoteData, commit: &str) -> String {
-    format!("{}/commit/{}", build_web_base_url(remote), commit)
-}
+/// Gets the username of the user that the token belongs to.
+pub fn get_authenticated_user(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<String> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
 
-pub fn get_url_for_issue(remote: &GitRemoteData, issue_number: u32) -> String {
-    format!("{}/issues/{}", build_web_base_url(remote), issue_number)
+    Ok(get_identity(http_client, base_url)?.username)
 }
 
-pub fn get_url_for_issues(remote: &GitRemoteData) -> String {
-    format!("{}/issues", build_web_base_url(remote))
+/// Adds the labels to the issue and returns its resulting labels.
+pub fn add_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let label_ids = get_label_ids(http_client, base_url, repo_path, labels)?;
+    let url = format!("{base_url}/repos/{repo_path}/issues/{issue_number}/labels");
+
+    send_issue_labels(http_client.post(&url), &label_ids)
 }
 
-pub fn get_url_for_issue_creation(remote: &GitRemoteData) -> String {
-    format!("{}/issues/new", build_web_base_url(remote))
+/// Removes the labels from the issue and returns its remaining labels. Labels
+/// that the issue doesn't have are ignored.
+pub fn remove_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!(
+        "{base_url}/repos/{}/issues/{issue_number}/labels",
+        remote.path
+    );
+    // Deleting a single label needs its id, so the labels are replaced with
+    // the remaining ones instead
+    let current: Vec<GiteaRepoLabel> = get_json(http_client, &url, true)?;
+    let remaining = labels::without_labels(
+        current.iter().map(|label| label.name.clone()).collect(),
+        labels,
+    );
+
+    if remaining.len() == current.len() {
+        return Ok(remaining);
+    }
+
+    let label_ids: Vec<u64> = current
+        .iter()
+        .filter(|label| remaining.contains(&label.name))
+        .map(|label| label.id)
+        .collect();
+
+    send_issue_labels(http_client.put(&url), &label_ids)
 }
 
-pub fn get_url_for_pr(remote: &GitRemoteData, pr_number: u32) -> String {
-    format!("{}/pulls/{}", build_web_base_url(remote), pr_number)
+/// Replaces the labels of the issue and returns its resulting labels.
+pub fn set_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let label_ids = get_label_ids(http_client, base_url, repo_path, labels)?;
+    let url = format!("{base_url}/repos/{repo_path}/issues/{issue_number}/labels");
+
+    send_issue_labels(http_client.put(&url), &label_ids)
 }
 
-pub fn get_url_for_pr_creation(
+pub fn set_issue_pinned(
+    http_client: &HttpClient,
     remote: &GitRemoteData,
-    target_branch: &str,
-    source_branch: &str,
-) -> String {
-    let base_url = build_web_base_url(remote);
+    api_url: Option<&str>,
+    issue_number: u32,
+    pinned: bool,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/issues/{issue_number}/pin");
+    let request = if pinned {
+        http_client.post(&url)
+    } else {
+        http_client.delete(&url)
+    };
 
-    format!("{base_url}/compare/{target_branch}...{source_branch}")
+    request
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while pinning issue on Gitea/Forgejo")?
+        .with_http_status_ok()?;
+
+    Ok(())
 }
 
-pub fn get_url_for_prs(remote: &GitRemoteData) -> String {
-    format!("{}/pulls", build_web_base_url(remote))
+pub fn get_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    fetch_labels(http_client, base_url, &remote.path)
+        .map(|labels| labels.into_iter().map(|l| l.name).collect())
 }
 
-pub fn get_url_for_releases(remote: &GitRemoteData) -> String {
-    format!("{}/releases", build_web_base_url(remote))
+/// Creates a label with a color given as `rrggbb`.
+pub fn create_label(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    name: &str,
+    color: &str,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/repos/{}/labels", remote.path);
+    let request_body = serde_json::json!({
+        "name": name,
+        "color": format!("#{color}"),
+    });
+
+    http_client
+        .post(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&request_body)
+        .send_throttled()
+        .context("Network request failed while creating label on Gitea/Forgejo")?
+        .with_http_status_ok()?;
+
+    Ok(())
 }
 
-pub fn get_url_for_path(
+pub fn get_prs(
+    http_client: &HttpClient,
     remote: &GitRemoteData,
-    path: &str,
-    commit: &str,
-    line_number: Option<u32>,
-) -> String {
-    let base = build_web_base_url(remote);
-    let mut url = format!("{}/src/commit/{}/{}", base, commit, path);
+    api_url: Option<&str>,
+    filters: &ListPrsFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<Pr>> {
+    // Check for unsupported filters
+    if matches!(filters.state, PrState::Merged) {
+        supports(Capability::MergedStateFilter).ensure()?;
+    }
 
-    if let Some(line) = line_number {
-        url.push_str(&format!("#L{}", line));
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/issues");
+    let fetch_page = |page| -> anyhow::Result<PaginatedResponse<Pr>> {
+        let response = build_pulls_request(http_client, &url, filters, page, use_auth)?
+            .send_throttled()
+            .context("Network request failed while fetching pull requests from Gitea/Forgejo")?
+            .with_http_status_ok()?;
+
+        let has_next_page = http_client::has_next_link_header(&response);
+
+        response
+            .parse_json()
+            .context("Failed to parse Gitea/Forgejo API response")
+            .map(|items: Vec<GiteaIssue>| {
+                items
+                    .into_iter()
+                    .map(Into::into)
+                    .collect::<Vec<Pr>>()
+                    .into_paginated_response(has_next_page)
+            })
+    };
+
+    get_filtered_prs(filters, fetch_page)
+}
+
+pub fn get_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Pr> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .get(&format!(
+            "{base_url}/repos/{}/pulls/{pr_number}",
+            remote.path
+        ))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching pull request from Gitea")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea API response")
+        .map(|pr: GiteaPullRequest| pr.into())
+}
+
+/// The API can't filter by the head branch, so the open PRs are filtered
+/// client-side.
+pub fn get_open_prs_for_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let prs = get_open_prs(http_client, remote, api_url, use_auth)?;
+
+    Ok(prs
+        .into_iter()
+        .filter(|pr| pr.head.as_ref().is_some_and(|head| head.name == branch))
+        .collect())
+}
+
+/// Closed PRs are only returned if they were merged.
+pub fn get_merged_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>> {
+    let response = fetch_closed_issues(http_client, remote, api_url, "pulls", filters, use_auth)?;
+    let has_next_page = response.has_next_page;
+
+    Ok(response
+        .items
+        .into_iter()
+        .map(Pr::from)
+        // The API can't filter by merged state, so filter client-side
+        .filter(|pr| pr.state == "merged")
+        .collect::<Vec<Pr>>()
+        .into_paginated_response(has_next_page))
+}
+
+pub fn get_closed_issues(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>> {
+    let response = fetch_closed_issues(http_client, remote, api_url, "issues", filters, use_auth)?;
+    let has_next_page = response.has_next_page;
+
+    Ok(response.items.into_paginated_response(has_next_page))
+}
+
+/// Checks whether the server accepts the `draft` field when creating PRs. Any
+/// failure falls back to the title prefix approach.
+pub fn supports_native_drafts(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> bool {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    match get_cached_server_version(http_client, base_url) {
+        Ok(version) => is_native_draft_version(&version),
+        Err(e) => {
+            log::info!("Falling back to WIP title prefix for drafts: {e:#}");
+
+            false
+        }
     }
+}
 
-    url
+pub fn create_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    options: &CreatePrOptions,
+) -> anyhow::Result<Pr> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/pulls");
+    let label_ids = get_label_ids(http_client, base_url, repo_path, options.labels)?;
+    let request_body = build_create_pr_request_body(options, &label_ids);
+
+    eprintln!("Creating pull request on Gitea/Forgejo...");
+
+    let request = http_client
+        .post(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&request_body);
+
+    request
+        .send_throttled()
+        .context("Network request failed while creating pull request on Gitea/Forgejo")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")
+        .map(|pr: GiteaPullRequest| pr.into())
 }
 
-// =============================================================================
-// Private Helpers
-// =============================================================================
+/// Adds the `add` users to the reviewers, removes the `remove` users and
+/// returns the resulting reviewers.
+pub fn update_pr_reviewers(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    add: &[String],
+    remove: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/pulls/{pr_number}");
+    let reviewers_url = format!("{url}/requested_reviewers");
 
-fn build_api_base_url(remote: &GitRemoteData) -> String {
-    let (host, port) = (&remote.host, remote.port);
+    if !add.is_empty() {
+        http_client
+            .post(&reviewers_url)
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .json(&serde_json::json!({ "reviewers": add }))
+            .send_throttled()
+            .context("Network request failed while requesting reviewers on Gitea/Forgejo")?
+            .with_http_status_ok()?;
+    }
 
-    match port {
-        Some(p) => format!("https://{host}:{p}/api/v1"),
-        None => format!("https://{host}/api/v1"),
+    if !remove.is_empty() {
+        http_client
+            .delete(&reviewers_url)
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .json(&serde_json::json!({ "reviewers": remove }))
+            .send_throttled()
+            .context("Network request failed while removing reviewers on Gitea/Forgejo")?
+            .with_http_status_ok()?;
     }
+
+    let pr: GiteaPrReviewers = get_json(http_client, &url, true)?;
+
+    Ok(pr
+        .requested_reviewers
+        .unwrap_or_default()
+        .into_iter()
+        .map(|reviewer| reviewer.login)
+        .collect())
 }
 
-fn build_web_base_url(remote: &GitRemoteData) -> String {
-    let host = &remote.host;
-    let path = &remote.path;
+/// Merges the PR or, with `auto`, schedules it to be merged once its checks
+/// succeed.
+pub fn merge_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    auto: bool,
+) -> anyhow::Result<PrMergeOutcome> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    if auto {
+        ensure_scheduled_merges(http_client, base_url)?;
+    }
+
+    http_client
+        .post(&format!(
+            "{base_url}/repos/{}/pulls/{pr_number}/merge",
+            remote.path
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "Do": "merge", "merge_when_checks_succeed": auto }))
+        .send_throttled()
+        .context("Network request failed while merging pull request on Gitea/Forgejo")?
+        .with_http_status_ok()?;
+
+    if auto {
+        Ok(PrMergeOutcome::Scheduled)
+    } else {
+        Ok(PrMergeOutcome::Merged)
+    }
+}
+
+pub fn cancel_pr_auto_merge(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    ensure_scheduled_merges(http_client, base_url)?;
+
+    http_client
+        .delete(&format!(
+            "{base_url}/repos/{}/pulls/{pr_number}/merge",
+            remote.path
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while cancelling the scheduled merge on Gitea/Forgejo")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+/// Gitea/Forgejo don't tell whether a merge is scheduled.
+pub fn get_pr_auto_merge(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_number: u32,
+    _use_auth: bool,
+) -> anyhow::Result<Option<bool>> {
+    Ok(None)
+}
+
+/// Gitea/Forgejo only expose approval requirements via branch protection rules,
+/// which require admin permissions.
+pub fn get_pr_approvals(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_number: u32,
+    _use_auth: bool,
+) -> anyhow::Result<Option<PrApprovals>> {
+    Ok(None)
+}
+
+/// Gitea/Forgejo can't fetch the review status of many PRs at once, so the
+/// caller fetches it per PR with [`get_pr_review_status`].
+pub fn get_pr_review_statuses(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_numbers: &[u32],
+    _use_auth: bool,
+) -> anyhow::Result<Option<Vec<PrReviewStatus>>> {
+    Ok(None)
+}
+
+pub fn get_pr_review_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrReviewStatus> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/repos/{}/pulls/{pr_number}/reviews", remote.path);
 
-    match remote.port {
-        Some(port) => format!("https://{host}:{port}/{path}"),
-        None => format!("https://{host}/{path}"),
+    let reviews: Vec<GiteaReview> = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching reviews from Gitea")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+
+    // Gitea lists requested reviews as reviews with the REQUEST_REVIEW state
+    let reviews = reviews.iter().filter_map(|review| {
+        let status = match review.state.as_str() {
+            _ if review.dismissed => PrReviewStatus::None,
+            "APPROVED" => PrReviewStatus::Approved,
+            "REQUEST_CHANGES" => PrReviewStatus::ChangesRequested,
+            "REQUEST_REVIEW" => PrReviewStatus::ReviewRequired,
+            // Comments and pending reviews don't change the verdict of a reviewer
+            _ => return None,
+        };
+
+        Some((review.user.as_ref()?.login.as_str(), status))
+    });
+
+    Ok(PrReviewStatus::from_reviews(reviews, false))
+}
+
+pub fn get_pr_merge_info(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrMergeInfo> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_url = format!("{base_url}/repos/{}", remote.path);
+
+    let pr: GiteaPullRequestDetails = http_client
+        .get(&format!("{repo_url}/pulls/{pr_number}"))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching pull request from Gitea")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+
+    let status: GiteaCombinedStatus = http_client
+        .get(&format!("{repo_url}/commits/{}/status", pr.head.sha))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching commit status from Gitea")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+
+    Ok(PrMergeInfo {
+        mergeable: match (pr.merged, pr.mergeable) {
+            // Gitea reports merged PRs as not mergeable
+            (true, _) => PrMergeable::Unknown,
+            (false, true) => PrMergeable::Mergeable,
+            (false, false) => PrMergeable::Conflicting,
+        },
+        ci: match status.state.as_str() {
+            _ if status.total_count == 0 => PrCiStatus::None,
+            "success" | "warning" => PrCiStatus::Success,
+            "pending" => PrCiStatus::Pending,
+            _ => PrCiStatus::Failure,
+        },
+    })
+}
+
+pub fn get_pr_counts(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrCounts> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let pr_url = format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path);
+
+    let pr: GiteaPullRequestDetails = http_client
+        .get(&pr_url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching pull request from Gitea")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+
+    // The pull request doesn't contain the number of commits, but the total
+    // of its paginated commits does
+    let commits_response = http_client
+        .get(&format!("{pr_url}/commits"))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[
+            ("limit", "1"),
+            ("stat", "false"),
+            ("verification", "false"),
+            ("files", "false"),
+        ])
+        .send_throttled()
+        .context("Network request failed while fetching pull request commits from Gitea")?
+        .with_http_status_ok()?;
+
+    Ok(PrCounts {
+        comments: get_comment_count(pr.comments, pr.review_comments),
+        commits: http_client::get_total_count_header(&commits_response, "x-total-count"),
+        changed_files: pr.changed_files,
+    })
+}
+
+pub fn get_repo(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Repo> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_url = format!("{base_url}/repos/{}", remote.path);
+    let send = |url: &str| {
+        http_client
+            .get(url)
+            .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+            .send_throttled()
+            .context("Network request failed while fetching repository from Gitea")?
+            .with_http_status_ok()
+    };
+    let repo: GiteaRepository = send(&repo_url)?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+    // Older versions don't include the topics in the repository
+    let topics: GiteaTopics = send(&format!("{repo_url}/topics"))?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+    // The bytes of the languages
+    let languages: HashMap<String, f64> = send(&format!("{repo_url}/languages"))?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+
+    Ok(Repo {
+        name: repo.full_name,
+        description: repo.description,
+        url: repo.html_url,
+        default_branch: repo.default_branch,
+        topics: topics.topics,
+        languages: repo::sort_languages(languages),
+        archived: repo.archived,
+    })
+}
+
+/// The API has no filters for archived repositories or forks, so they are
+/// filtered after fetching the page.
+pub fn get_repos(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListReposFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<RepoSummary>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = match filters.owner {
+        RepoOwner::Org(org) => format!(
+            "{base_url}/orgs/{}/repos",
+            url_encoding::encode_component(org)
+        ),
+        RepoOwner::User(user) => format!(
+            "{base_url}/users/{}/repos",
+            url_encoding::encode_component(user)
+        ),
+    };
+    let response = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("page", filters.page), ("limit", filters.per_page)])
+        .send_throttled()
+        .context("Network request failed while fetching repositories from Gitea/Forgejo")?
+        .with_http_status_ok()?;
+    let has_next_page = http_client::has_next_link_header(&response);
+    let mut response = response
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")
+        .map(|repos: Vec<GiteaRepositorySummary>| repos.into_paginated_response(has_next_page))?;
+
+    response.items.retain(|repo| filters.matches(repo));
+
+    Ok(response)
+}
+
+pub fn has_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+) -> anyhow::Result<bool> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let response = http_client
+        .get(&format!(
+            "{base_url}/repos/{}/branches/{}",
+            remote.path,
+            url_encoding::encode_component(branch)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching branch from Gitea")?;
+
+    http_client::is_found(response)
+}
+
+/// Saves the changed fields. Topics can only be replaced as a whole with their
+/// own endpoint.
+pub fn edit_repo(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    edit: &RepoEdit,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_url = format!("{base_url}/repos/{}", remote.path);
+    let mut fields = serde_json::Map::new();
+
+    if let Some(change) = &edit.description {
+        fields.insert(String::from("description"), change.new.clone().into());
+    }
+
+    if let Some(change) = &edit.default_branch {
+        fields.insert(String::from("default_branch"), change.new.clone().into());
+    }
+
+    if !fields.is_empty() {
+        http_client
+            .patch(&repo_url)
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .json(&fields)
+            .send_throttled()
+            .context("Network request failed while editing repository on Gitea")?
+            .with_http_status_ok()?;
+    }
+
+    if let Some(change) = &edit.topics {
+        http_client
+            .put(&format!("{repo_url}/topics"))
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .json(&serde_json::json!({ "topics": change.new }))
+            .send_throttled()
+            .context("Network request failed while replacing topics on Gitea")?
+            .with_http_status_ok()?;
+    }
+
+    Ok(())
+}
+
+/// The branches list contains the last commit's date but not whether the
+/// branch is merged.
+pub fn get_branches(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Branch>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let branches: Vec<GiteaBranch> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/branches", remote.path),
+        &[],
+        use_auth,
+    )?;
+
+    Ok(branches.into_iter().map(Branch::from).collect())
+}
+
+pub fn get_hooks(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<Vec<Hook>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let hooks: Vec<GiteaHook> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/hooks", remote.path),
+        &[],
+        true,
+    )
+    .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(hooks.into_iter().map(Hook::from).collect())
+}
+
+/// Gitea/Forgejo send a fake push event to the webhook.
+pub fn ping_hook(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    hook_id: u64,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .post(&format!(
+            "{base_url}/repos/{}/hooks/{hook_id}/tests",
+            remote.path
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while testing webhook on Gitea/Forgejo")?
+        .with_http_status_ok()
+        .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(())
+}
+
+pub fn set_hook_active(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    hook_id: u64,
+    active: bool,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .patch(&format!("{base_url}/repos/{}/hooks/{hook_id}", remote.path))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "active": active }))
+        .send_throttled()
+        .context("Network request failed while editing webhook on Gitea/Forgejo")?
+        .with_http_status_ok()
+        .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(())
+}
+
+pub fn get_commit_date(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    sha: &str,
+    use_auth: bool,
+) -> anyhow::Result<Timestamp> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let commit: GiteaCommit = get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/git/commits/{sha}", remote.path),
+        use_auth,
+    )?;
+
+    Ok(commit.commit.committer.date)
+}
+
+/// The commit is merged if `target` contains it, i.e. comparing `target` with
+/// the commit yields no commits.
+pub fn is_branch_merged(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    sha: &str,
+    target: &str,
+    use_auth: bool,
+) -> anyhow::Result<bool> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let comparison: GiteaComparison = get_json(
+        http_client,
+        &format!(
+            "{base_url}/repos/{}/compare/{}...{sha}",
+            remote.path,
+            url_encoding::encode_component(target)
+        ),
+        use_auth,
+    )?;
+
+    Ok(comparison.total_commits == 0)
+}
+
+pub fn get_open_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let prs: Vec<GiteaPullRequest> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls", remote.path),
+        &[("state", "open")],
+        use_auth,
+    )?;
+
+    Ok(prs.into_iter().map(Pr::from).collect())
+}
+
+/// The API can't filter by the head branch, so the closed PRs are filtered
+/// client-side.
+pub fn get_merged_prs_for_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let prs: Vec<GiteaPullRequest> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls", remote.path),
+        &[("state", "closed")],
+        use_auth,
+    )?;
+
+    Ok(prs
+        .into_iter()
+        .map(Pr::from)
+        .filter(|pr| pr.merged_at.is_some())
+        .filter(|pr| pr.head.as_ref().is_some_and(|head| head.name == branch))
+        .collect())
+}
+
+pub fn delete_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .delete(&format!(
+            "{base_url}/repos/{}/branches/{}",
+            remote.path,
+            url_encoding::encode_component(branch)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while deleting the branch on Gitea/Forgejo")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+pub fn get_repo_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<RepoStatus> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    let repo: GiteaRepository = http_client
+        .get(&format!("{base_url}/repos/{}", remote.path))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching repository from Gitea")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea API response")?;
+
+    Ok(RepoStatus {
+        archived: repo.archived,
+    })
+}
+
+pub fn get_pr_ref(pr_number: u32) -> String {
+    format!("pull/{pr_number}/head")
+}
+
+pub fn get_url_for_api(remote: &GitRemoteData) -> String {
+    build_api_base_url(remote)
+}
+
+pub fn get_url_for_home(remote: &GitRemoteData) -> String {
+    build_web_base_url(remote)
+}
+
+pub fn get_url_for_commit(remote: &GitRemoteData, commit: &str) -> String {
+    format!(
+        "{}/commit/{}",
+        build_web_base_url(remote),
+        url_encoding::encode_component(commit)
+    )
+}
+
+pub fn get_url_for_issue(remote: &GitRemoteData, issue_number: u32) -> String {
+    format!("{}/issues/{}", build_web_base_url(remote), issue_number)
+}
+
+pub fn get_url_for_issue_comment(
+    remote: &GitRemoteData,
+    issue_number: u32,
+    comment_id: u64,
+) -> String {
+    format!(
+        "{}#issuecomment-{comment_id}",
+        get_url_for_issue(remote, issue_number)
+    )
+}
+
+pub fn get_url_for_issues(remote: &GitRemoteData) -> String {
+    format!("{}/issues", build_web_base_url(remote))
+}
+
+/// The issues page of Gitea/Forgejo can't be filtered by dates via the URL,
+/// so this is the unfiltered issues page.
+pub fn get_url_for_issue_search(
+    remote: &GitRemoteData,
+    _created: &DateRange,
+    _closed: &DateRange,
+) -> String {
+    get_url_for_issues(remote)
+}
+
+pub fn get_url_for_issue_creation(remote: &GitRemoteData) -> String {
+    format!("{}/issues/new", build_web_base_url(remote))
+}
+
+pub fn get_url_for_pr(remote: &GitRemoteData, pr_number: u32) -> String {
+    format!("{}/pulls/{}", build_web_base_url(remote), pr_number)
+}
+
+pub fn get_url_for_pr_creation(
+    remote: &GitRemoteData,
+    target_branch: &str,
+    source_branch: &str,
+) -> String {
+    let base_url = build_web_base_url(remote);
+    let target_branch = url_encoding::encode_path(target_branch);
+    let source_branch = url_encoding::encode_path(source_branch);
+
+    format!("{base_url}/compare/{target_branch}...{source_branch}")
+}
+
+pub fn get_url_for_prs(remote: &GitRemoteData) -> String {
+    format!("{}/pulls", build_web_base_url(remote))
+}
+
+pub fn get_url_for_labels(remote: &GitRemoteData) -> String {
+    format!("{}/labels", build_web_base_url(remote))
+}
+
+pub fn get_url_for_milestones(remote: &GitRemoteData) -> String {
+    format!("{}/milestones", build_web_base_url(remote))
+}
+
+pub fn get_url_for_releases(remote: &GitRemoteData) -> String {
+    format!("{}/releases", build_web_base_url(remote))
+}
+
+pub fn get_url_for_path(
+    remote: &GitRemoteData,
+    path: &str,
+    commit: &str,
+    line_number: Option<u32>,
+) -> String {
+    let base = build_web_base_url(remote);
+    let mut url = format!(
+        "{}/src/commit/{}/{}",
+        base,
+        url_encoding::encode_component(commit),
+        url_encoding::encode_path(path)
+    );
+
+    if let Some(line) = line_number {
+        url.push_str(&format!("#L{}", line));
+    }
+
+    url
+}
+
+pub fn get_pr_timeline_events(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    options: &PrTimelineOptions,
+    use_auth: bool,
+) -> anyhow::Result<Vec<PrTimelineEvent>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let pr: GiteaComment = get_json(
+        http_client,
+        &format!("{base_url}/repos/{repo_path}/pulls/{pr_number}"),
+        use_auth,
+    )?;
+    let mut events = vec![pr.into_timeline_event(PrTimelineEventKind::Description)];
+
+    if options.comments {
+        let comments: Vec<GiteaComment> = get_json(
+            http_client,
+            &format!("{base_url}/repos/{repo_path}/issues/{pr_number}/comments"),
+            use_auth,
+        )?;
+
+        events.extend(
+            comments
+                .into_iter()
+                .map(|comment| comment.into_timeline_event(PrTimelineEventKind::Comment)),
+        );
+    }
+
+    if options.reviews {
+        let reviews: Vec<GiteaReview> = get_json(
+            http_client,
+            &format!("{base_url}/repos/{repo_path}/pulls/{pr_number}/reviews"),
+            use_auth,
+        )?;
+
+        events.extend(reviews.into_iter().filter_map(|review| {
+            let kind = match review.state.as_str() {
+                _ if review.dismissed => PrTimelineEventKind::Dismissed,
+                "APPROVED" => PrTimelineEventKind::Approved,
+                "REQUEST_CHANGES" => PrTimelineEventKind::ChangesRequested,
+                "COMMENT" => PrTimelineEventKind::Reviewed,
+                // Pending reviews and review requests aren't reviews yet
+                _ => return None,
+            };
+
+            Some(PrTimelineEvent {
+                kind,
+                author: get_login(review.user.as_ref()),
+                created_at: review.submitted_at?,
+                body: review.body.unwrap_or_default(),
+            })
+        }));
+    }
+
+    Ok(events)
+}
+
+/// Gitea/Forgejo don't expose the issues closed by a pull request. So we only
+/// rely on the closing keywords in the description, which the caller parses.
+pub fn get_pr_closing_issues(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_number: u32,
+    _use_auth: bool,
+) -> anyhow::Result<Vec<u32>> {
+    Ok(vec![])
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// Builds the body of the request that creates a PR. Without `native_draft`,
+/// drafts get the WIP: title prefix. PRs created via the API don't allow
+/// maintainer edits, so `no_maintainer_edit` needs nothing.
+fn build_create_pr_request_body(options: &CreatePrOptions, label_ids: &[u64]) -> serde_json::Value {
+    if options.native_draft || !options.draft {
+        serde_json::json!({
+            "title": options.title,
+            "head": options.source_branch,
+            "base": options.target_branch,
+            "body": options.body,
+            "draft": options.draft,
+            "labels": label_ids,
+        })
+    } else {
+        serde_json::json!({
+            "title": format!("WIP: {}", options.title),
+            "head": options.source_branch,
+            "base": options.target_branch,
+            "body": options.body,
+            "labels": label_ids,
+        })
+    }
+}
+
+fn get_identity(http_client: &HttpClient, base_url: &str) -> anyhow::Result<Identity> {
+    identity::get_identity(base_url, AUTH_TOKEN, || {
+        http_client
+            .get(&format!("{base_url}/user"))
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .send_throttled()
+            .context(
+                "Network request failed while fetching the authenticated user from Gitea/Forgejo",
+            )?
+            .with_http_status_ok()?
+            .parse_json()
+            .context("Failed to parse Gitea/Forgejo API response")
+            .map(|user: GiteaAuthenticatedUser| user.into())
+    })
+}
+
+/// Adds or removes assignees. Gitea/Forgejo only takes the complete list of
+/// assignees, so the current assignees are fetched first.
+fn update_issue_assignees(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+    add: bool,
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/issues/{issue_number}");
+    let issue: GiteaIssueAssignees = get_json(http_client, &url, true)?;
+    let mut new_assignees: Vec<String> = issue
+        .assignees
+        .unwrap_or_default()
+        .into_iter()
+        .map(|user| user.login)
+        .filter(|login| !assignees.contains(login))
+        .collect();
+
+    if add {
+        new_assignees.extend_from_slice(assignees);
+    }
+
+    let issue: GiteaIssueAssignees = http_client
+        .patch(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "assignees": new_assignees }))
+        .send_throttled()
+        .context("Network request failed while updating assignees on Gitea/Forgejo")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")?;
+
+    Ok(issue
+        .assignees
+        .unwrap_or_default()
+        .into_iter()
+        .map(|user| user.login)
+        .collect())
+}
+
+/// Sends the label ids to the labels endpoint of an issue, which answers with
+/// the resulting labels.
+fn send_issue_labels(request: RequestBuilder, label_ids: &[u64]) -> anyhow::Result<Vec<String>> {
+    let labels: Vec<GiteaLabel> = request
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "labels": label_ids }))
+        .send_throttled()
+        .context("Network request failed while updating labels on Gitea/Forgejo")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")?;
+
+    Ok(labels.into_iter().map(|l| l.name).collect())
+}
+
+/// Fetches a page of issues and converts them, e.g. to `Issue` for listing.
+fn fetch_issues<T: From<GiteaIssue>>(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<T>> {
+    // Check for unsupported filters
+    if filters.closed_as.is_some() {
+        supports(Capability::ClosedAsFilter).ensure()?;
+    }
+
+    if filters.min_reactions.is_some() || matches!(filters.sort, Some(IssueSort::Reactions)) {
+        supports(Capability::Reactions).ensure()?;
+    }
+
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+
+    if filters.pinned {
+        return fetch_pinned_issues(http_client, base_url, repo_path, filters, use_auth);
+    }
+
+    let endpoint_url = format!("{base_url}/repos/{repo_path}/issues");
+    let fetch_page = |page| -> anyhow::Result<PaginatedResponse<GiteaIssue>> {
+        let response = build_issues_request(http_client, &endpoint_url, filters, page, use_auth)?
+            .send_throttled()
+            .context("Failed to fetch items from Gitea Search API")?
+            .with_http_status_ok()?;
+
+        let has_next_page = http_client::has_next_link_header(&response);
+
+        response
+            .parse_json()
+            .context("Failed to parse Gitea Search API response")
+            .map(|res: Vec<GiteaIssue>| {
+                res.into_iter()
+                    .filter(|issue| filters.created.contains(Some(issue.created_at)))
+                    .filter(|issue| filters.closed.contains(issue.closed_at))
+                    .filter(|issue| filters.updated.contains(Some(issue.updated_at)))
+                    .collect::<Vec<_>>()
+                    .into_paginated_response(has_next_page)
+            })
+    };
+    let response = if !filters.labels.needs_client_side_filtering() {
+        fetch_page(filters.page)?
+    } else {
+        fetch_filled_page(filters.page, filters.per_page, fetch_page, |issue| {
+            filters.labels.matches(&get_label_names(issue))
+        })?
+    };
+
+    Ok(response
+        .items
+        .into_paginated_response(response.has_next_page))
+}
+
+/// Gets the requested page of the PRs that match the filters. The API can't
+/// filter by draft status, exclude labels or only list the PRs without labels,
+/// so these filters are applied after fetching, see `fetch_filled_page`.
+fn get_filtered_prs(
+    filters: &ListPrsFilters,
+    fetch_page: impl Fn(u32) -> anyhow::Result<PaginatedResponse<Pr>>,
+) -> anyhow::Result<PaginatedResponse<Pr>> {
+    if !filters.draft && !filters.labels.needs_client_side_filtering() {
+        fetch_page(filters.page)
+    } else {
+        fetch_filled_page(filters.page, filters.per_page, fetch_page, |pr| {
+            (!filters.draft || pr.draft) && filters.labels.matches(&pr.labels)
+        })
+    }
+}
+
+/// Gets the requested page of the items that `keep` accepts. Gitea/Forgejo
+/// can't apply some filters, e.g. excluding labels, so the other items are
+/// dropped after fetching them. To still fill the page with `per_page` items,
+/// the pages of the endpoint are fetched from the first one until the
+/// requested page is complete.
+fn fetch_filled_page<T>(
+    page: u32,
+    per_page: u32,
+    fetch_page: impl Fn(u32) -> anyhow::Result<PaginatedResponse<T>>,
+    keep: impl Fn(&T) -> bool,
+) -> anyhow::Result<PaginatedResponse<T>> {
+    let skip = (page.saturating_sub(1) * per_page) as usize;
+    let mut items = vec![];
+    let mut endpoint_page = 1;
+
+    loop {
+        let response = fetch_page(endpoint_page)?;
+
+        items.extend(response.items.into_iter().filter(|item| keep(item)));
+
+        if items.len() > skip + per_page as usize || !response.has_next_page {
+            break;
+        }
+
+        endpoint_page += 1;
+    }
+
+    let has_next_page = items.len() > skip + per_page as usize;
+
+    Ok(PaginatedResponse::new(
+        items
+            .into_iter()
+            .skip(skip)
+            .take(per_page as usize)
+            .collect(),
+        has_next_page,
+    ))
+}
+
+fn get_label_names(issue: &GiteaIssue) -> Vec<String> {
+    issue
+        .labels
+        .iter()
+        .map(|label| label.name.clone())
+        .collect()
+}
+
+/// The pinned issues are listed by their own endpoint without pagination or
+/// filters since only a few issues (3 by default) can be pinned. Filtering the
+/// issues endpoint instead would discard most of each fetched page.
+fn fetch_pinned_issues<T: From<GiteaIssue>>(
+    http_client: &HttpClient,
+    base_url: &str,
+    repo_path: &str,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<T>> {
+    let issues: Vec<GiteaIssue> = get_json(
+        http_client,
+        &format!("{base_url}/repos/{repo_path}/issues/pinned"),
+        use_auth,
+    )?;
+    let skip = (filters.page.saturating_sub(1) * filters.per_page) as usize;
+    let matching_issues = issues
+        .into_iter()
+        .filter(|issue| is_matching_pinned_issue(issue, filters))
+        .collect::<Vec<_>>();
+    let has_next_page = matching_issues.len() > skip + filters.per_page as usize;
+
+    Ok(matching_issues
+        .into_iter()
+        .skip(skip)
+        .take(filters.per_page as usize)
+        .map(T::from)
+        .collect::<Vec<_>>()
+        .into_paginated_response(has_next_page))
+}
+
+/// Applies the filters that the issues endpoint would apply to a pinned issue.
+/// The query only matches the title.
+fn is_matching_pinned_issue(issue: &GiteaIssue, filters: &ListIssueFilters) -> bool {
+    let query = filters.query.map(str::to_lowercase);
+
+    // Pinned PRs are listed as well
+    (filters.include_prs || issue.pull_request.is_none())
+        && match filters.state {
+            IssueState::All => true,
+            state => issue.state == *state,
+        }
+        && filters
+            .author
+            .is_none_or(|author| issue.user.login == author)
+        && filters.assignee.is_none_or(|assignee| {
+            issue
+                .assignees
+                .iter()
+                .flatten()
+                .any(|user| user.login == assignee)
+        })
+        && filters.labels.matches(&get_label_names(issue))
+        && query.is_none_or(|q| issue.title.to_lowercase().contains(&q))
+        && filters.created.contains(Some(issue.created_at))
+        && filters.closed.contains(issue.closed_at)
+        && filters.updated.contains(Some(issue.updated_at))
+}
+
+fn build_issues_request(
+    http_client: &HttpClient,
+    url: &str,
+    filters: &ListIssueFilters,
+    page: u32,
+    use_auth: bool,
+) -> anyhow::Result<RequestBuilder> {
+    let mut request = http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("state", filters.state)])
+        .query(&[("page", page)])
+        .query(&[("limit", filters.per_page)]);
+
+    // Without a type, the issues and PRs are listed together
+    if !filters.include_prs {
+        request = request.query(&[("type", "issues")]);
+    }
+
+    if let Some(assignee) = filters.assignee {
+        request = request.query(&[("assigned_by", assignee)]);
+    }
+
+    if let Some(author) = filters.author {
+        request = request.query(&[("created_by", author)]);
+    }
+
+    // Excluded labels and labeled issues of `--no-labels` are filtered out
+    // after fetching, see `fetch_filled_page`
+    if !filters.labels.include.is_empty() {
+        request = request.query(&[("labels", filters.labels.include.join(","))]);
+    }
+
+    if let Some(query) = filters.query {
+        request = request.query(&[("q", query)]);
+    }
+
+    // `since` compares the update time. Issues created or closed after a date
+    // were updated after it as well, so it narrows down the issues that are
+    // filtered by their dates after fetching them.
+    if let Some(since) = filters
+        .created
+        .after
+        .max(filters.closed.after)
+        .max(filters.updated.after)
+    {
+        request = request.query(&[("since", date::format_date(since))]);
+    }
+
+    Ok(request)
+}
+
+fn build_pulls_request(
+    http_client: &HttpClient,
+    url: &str,
+    filters: &ListPrsFilters,
+    page: u32,
+    use_auth: bool,
+) -> anyhow::Result<RequestBuilder> {
+    let mut request = http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("type", "pulls")])
+        .query(&[("state", filters.state)])
+        .query(&[("page", page)])
+        .query(&[("limit", filters.per_page)]);
+
+    if let Some(author) = filters.author {
+        request = request.query(&[("created_by", author)]);
+    }
+
+    // Excluded labels are filtered out after fetching, see `fetch_filled_page`
+    if !filters.labels.include.is_empty() {
+        request = request.query(&[("labels", filters.labels.include.join(","))]);
+    }
+
+    // Milestones may be given by name or id
+    if let Some(milestone) = filters.milestone {
+        request = request.query(&[("milestones", milestone)]);
+    }
+
+    if let Some(query) = filters.query {
+        request = request.query(&[("q", query)]);
+    }
+
+    Ok(request)
+}
+
+/// Fetches the closed issues or PRs (`issue_type` is `issues` or `pulls`) for
+/// a changelog. Gitea/Forgejo can't filter by the merge or close date. Items
+/// that were merged or closed after `filters.since` were updated after it as
+/// well, so filtering by the update date only leaves the rest to the caller.
+fn fetch_closed_issues(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_type: &str,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<GiteaIssue>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/issues");
+    let response = build_closed_issues_request(http_client, &url, issue_type, filters, use_auth)?
+        .send_throttled()
+        .context("Network request failed while fetching the changelog from Gitea/Forgejo")?
+        .with_http_status_ok()?;
+
+    let has_next_page = http_client::has_next_link_header(&response);
+
+    response
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")
+        .map(|items: Vec<GiteaIssue>| items.into_paginated_response(has_next_page))
+}
+
+fn build_closed_issues_request(
+    http_client: &HttpClient,
+    url: &str,
+    issue_type: &str,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<RequestBuilder> {
+    let mut request = http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("type", issue_type)])
+        .query(&[("state", "closed")])
+        .query(&[("page", filters.page)])
+        .query(&[("limit", filters.per_page)]);
+
+    // Milestones may be given by name or id
+    if let Some(milestone) = filters.milestone {
+        request = request.query(&[("milestones", milestone)]);
+    }
+
+    if let Some(since) = filters.since {
+        request = request.query(&[("since", since)]);
+    }
+
+    Ok(request)
+}
+
+fn build_api_base_url(remote: &GitRemoteData) -> String {
+    match &remote.api_base_url {
+        Some(url) => url.clone(),
+        None => format!("{}/api/v1", remote.web_base_url),
+    }
+}
+
+fn build_web_base_url(remote: &GitRemoteData) -> String {
+    format!("{}/{}", remote.web_base_url, remote.path)
+}
+
+fn has_draft_title_prefix(title: &str) -> bool {
+    let title = title.trim_start().to_lowercase();
+
+    DRAFT_TITLE_PREFIXES
+        .iter()
+        .any(|prefix| title.starts_with(prefix))
+}
+
+/// Fails if the server is known to be too old to schedule merges. If the
+/// version can't be fetched, the merge request itself tells.
+fn ensure_scheduled_merges(http_client: &HttpClient, base_url: &str) -> anyhow::Result<()> {
+    match get_cached_server_version(http_client, base_url) {
+        Ok(version) if !is_version_at_least(&version, MIN_SCHEDULED_MERGE_VERSION) => {
+            anyhow::bail!(
+                "Merging once the checks pass needs Gitea 1.19 or newer, but the server reports {version}"
+            )
+        }
+        Ok(_) => Ok(()),
+        Err(e) => {
+            log::info!("Failed to check whether the server can schedule merges: {e:#}");
+
+            Ok(())
+        }
+    }
+}
+
+/// Gets the server version, which is cached for a day.
+fn get_cached_server_version(http_client: &HttpClient, base_url: &str) -> anyhow::Result<String> {
+    server_version::get_server_version(base_url, || get_server_version(http_client, base_url))
+}
+
+fn get_json<T: DeserializeOwned>(
+    http_client: &HttpClient,
+    url: &str,
+    use_auth: bool,
+) -> anyhow::Result<T> {
+    http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("limit", "50")])
+        .send_throttled()
+        .context("Network request failed while fetching data from Gitea/Forgejo")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")
+}
+
+/// Fetches every page of a listing with the most items per page.
+fn get_json_pages<T: DeserializeOwned>(
+    http_client: &HttpClient,
+    url: &str,
+    query: &[(&str, &str)],
+    use_auth: bool,
+) -> anyhow::Result<Vec<T>> {
+    let mut items = vec![];
+
+    for page in 1.. {
+        let response = http_client
+            .get(url)
+            .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+            .query(query)
+            .query(&[("page", page), ("limit", 50)])
+            .send_throttled()
+            .context("Network request failed while fetching data from Gitea/Forgejo")?
+            .with_http_status_ok()?;
+        let has_next_page = http_client::has_next_link_header(&response);
+        let page_items: Vec<T> = response
+            .parse_json()
+            .context("Failed to parse Gitea/Forgejo API response")?;
+
+        items.extend(page_items);
+
+        if !has_next_page {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+/// Adds the review comments to the comments of a pull request. Gitea versions
+/// before 1.22 only count the comments.
+fn get_comment_count(comments: Option<u32>, review_comments: Option<u32>) -> Option<u32> {
+    comments.map(|comments| comments + review_comments.unwrap_or(0))
+}
+
+/// Deleted accounts are returned as `null` users.
+fn get_login(user: Option<&GiteaUser>) -> String {
+    user.map_or_else(|| DELETED_USER.to_string(), |user| user.login.clone())
+}
+
+fn fetch_labels(
+    http_client: &HttpClient,
+    base_url: &str,
+    repo_path: &str,
+) -> anyhow::Result<Vec<GiteaRepoLabel>> {
+    get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{repo_path}/labels"),
+        &[],
+        true,
+    )
+    .context("Failed fetching labels from Gitea/Forgejo")
+}
+
+/// Gitea/Forgejo expect label ids instead of names when creating issues or PRs.
+fn get_label_ids(
+    http_client: &HttpClient,
+    base_url: &str,
+    repo_path: &str,
+    names: &[String],
+) -> anyhow::Result<Vec<u64>> {
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let labels = fetch_labels(http_client, base_url, repo_path)?;
+
+    names
+        .iter()
+        .map(|name| {
+            labels
+                .iter()
+                .find(|label| &label.name == name)
+                .map(|label| label.id)
+                .with_context(|| format!("Label '{name}' doesn't exist in {repo_path}"))
+        })
+        .collect()
+}
+
+/// Gets the server version. Forgejo instances are asked via the Forgejo
+/// version endpoint first, which older versions don't have.
+fn get_server_version(http_client: &HttpClient, base_url: &str) -> anyhow::Result<String> {
+    let forgejo_version_url = reqwest::Url::parse(base_url)
+        .ok()
+        .filter(forgejo::is_forgejo_url)
+        .and_then(|_| forgejo::get_url_for_version(base_url));
+
+    if let Some(url) = forgejo_version_url {
+        match fetch_server_version(http_client, &url) {
+            Ok(version) => return Ok(version),
+            Err(e) => log::debug!("Falling back to the Gitea version endpoint: {e:#}"),
+        }
+    }
+
+    fetch_server_version(http_client, &format!("{base_url}/version"))
+}
+
+fn fetch_server_version(http_client: &HttpClient, url: &str) -> anyhow::Result<String> {
+    http_client
+        .get(url)
+        .send_throttled()
+        .context("Network request failed while fetching the Gitea/Forgejo version")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse Gitea/Forgejo API response")
+        .map(|res: GiteaServerVersion| res.version)
+}
+
+fn is_native_draft_version(version: &str) -> bool {
+    is_version_at_least(version, MIN_NATIVE_DRAFT_VERSION)
+}
+
+fn is_version_at_least(version: &str, min_version: (u32, u32, u32)) -> bool {
+    parse_gitea_version(version).is_some_and(|version| version >= min_version)
+}
+
+/// Parses a Gitea version or the Gitea compatibility version of Forgejo, e.g.
+/// `1.22.3`, `1.23.0+dev-12-gabcdef`, or `9.0.0+gitea-1.22.0`.
+fn parse_gitea_version(version: &str) -> Option<(u32, u32, u32)> {
+    let version = match version.split_once("+gitea-") {
+        Some((_, gitea_version)) => gitea_version,
+        None => version,
+    };
+    let version = version.trim_start_matches('v');
+    let core = version.split(['+', '-']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::forge::LabelFilter;
+
+    const URL: &str = "https://codeberg.org/api/v1/repos/user/repo/issues";
+
+    #[test]
+    fn test_parse_gitea_version() {
+        assert_eq!(parse_gitea_version("1.22.3"), Some((1, 22, 3)));
+        assert_eq!(parse_gitea_version("v1.21"), Some((1, 21, 0)));
+        assert_eq!(
+            parse_gitea_version("1.23.0+dev-12-gabcdef"),
+            Some((1, 23, 0))
+        );
+        assert_eq!(parse_gitea_version("9.0.0+gitea-1.22.0"), Some((1, 22, 0)));
+        assert_eq!(parse_gitea_version("development"), None);
+    }
+
+    #[test]
+    fn test_is_version_at_least() {
+        assert!(is_version_at_least("1.19.0", MIN_SCHEDULED_MERGE_VERSION));
+        assert!(is_version_at_least(
+            "7.0.0+gitea-1.21.0",
+            MIN_SCHEDULED_MERGE_VERSION
+        ));
+        assert!(!is_version_at_least("1.18.5", MIN_SCHEDULED_MERGE_VERSION));
+        assert!(!is_version_at_least("unknown", MIN_SCHEDULED_MERGE_VERSION));
+    }
+
+    #[test]
+    fn test_is_native_draft_version() {
+        assert!(is_native_draft_version("1.24.0"));
+        assert!(is_native_draft_version("1.25.2"));
+        assert!(is_native_draft_version("12.0.0+gitea-1.24.1"));
+        assert!(!is_native_draft_version("1.23.8"));
+        assert!(is_native_draft_version("1.24.0-rc1"));
+        assert!(!is_native_draft_version("9.0.0+gitea-1.22.0"));
+        assert!(!is_native_draft_version("unknown"));
+    }
+
+    #[test]
+    fn test_has_draft_title_prefix() {
+        assert!(has_draft_title_prefix("WIP: add feature"));
+        assert!(has_draft_title_prefix("[WIP] add feature"));
+        assert!(has_draft_title_prefix("Draft: add feature"));
+        assert!(has_draft_title_prefix("[draft] add feature"));
+        assert!(!has_draft_title_prefix("Add WIP: feature"));
+        assert!(!has_draft_title_prefix("Wipe cache"));
+    }
+
+    fn create_git_remote() -> GitRemoteData {
+        GitRemoteData {
+            host: "codeberg.org".to_string(),
+            path: "user/repo".to_string(),
+            port: None,
+            web_base_url: "https://codeberg.org".to_string(),
+            api_base_url: None,
+        }
+    }
+
+    #[test]
+    fn test_build_base_urls() {
+        let mut remote = create_git_remote();
+
+        assert_eq!(build_api_base_url(&remote), "https://codeberg.org/api/v1");
+        assert_eq!(
+            build_web_base_url(&remote),
+            "https://codeberg.org/user/repo"
+        );
+
+        // E.g. self-hosted over plain http with the SSH port in the remote URL
+        remote.host = "git.internal".to_string();
+        remote.port = Some(2222);
+        remote.web_base_url = "http://git.internal:3000".to_string();
+
+        assert_eq!(
+            build_api_base_url(&remote),
+            "http://git.internal:3000/api/v1"
+        );
+        assert_eq!(
+            build_web_base_url(&remote),
+            "http://git.internal:3000/user/repo"
+        );
+
+        remote.api_base_url = Some("https://api.git.internal".to_string());
+
+        assert_eq!(build_api_base_url(&remote), "https://api.git.internal");
+    }
+
+    #[test]
+    fn test_get_url_for_management_pages() {
+        let remote = create_git_remote();
+
+        assert_eq!(
+            get_url_for_labels(&remote),
+            "https://codeberg.org/user/repo/labels"
+        );
+        assert_eq!(
+            get_url_for_milestones(&remote),
+            "https://codeberg.org/user/repo/milestones"
+        );
+        assert_eq!(
+            get_url_for_releases(&remote),
+            "https://codeberg.org/user/repo/releases"
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_pr_creation_encodes_branches() {
+        let url = get_url_for_pr_creation(&create_git_remote(), "main", "fix/#42");
+
+        assert_eq!(
+            url,
+            "https://codeberg.org/user/repo/compare/main...fix/%2342"
+        );
+    }
+
+    #[test]
+    fn test_supports() {
+        assert!(matches!(
+            supports(Capability::DraftPrs),
+            Support::Degraded(_)
+        ));
+        assert!(matches!(
+            supports(Capability::MergedStateFilter),
+            Support::No(_)
+        ));
+        assert_eq!(supports(Capability::IssuePinning), Support::Yes);
+        assert_eq!(supports(Capability::HookToggle), Support::Yes);
+        assert!(matches!(
+            supports(Capability::AutoMerge),
+            Support::Degraded(_)
+        ));
+        assert!(matches!(
+            supports(Capability::RemoveSourceBranch),
+            Support::No(_)
+        ));
+    }
+
+    #[test]
+    fn test_build_create_pr_request_body() {
+        let options = CreatePrOptions {
+            title: "Fix crash",
+            source_branch: "fix",
+            target_branch: "main",
+            body: "",
+            draft: true,
+            native_draft: true,
+            labels: &[],
+            no_maintainer_edit: false,
+            remove_source_branch: false,
+            squash_on_merge: false,
+        };
+
+        assert_eq!(
+            build_create_pr_request_body(&options, &[3]),
+            serde_json::json!({
+                "title": "Fix crash",
+                "head": "fix",
+                "base": "main",
+                "body": "",
+                "draft": true,
+                "labels": [3],
+            })
+        );
+        assert_eq!(
+            build_create_pr_request_body(
+                &CreatePrOptions {
+                    native_draft: false,
+                    ..options
+                },
+                &[]
+            ),
+            serde_json::json!({
+                "title": "WIP: Fix crash",
+                "head": "fix",
+                "base": "main",
+                "body": "",
+                "labels": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_issue_comment() {
+        let url = get_url_for_issue_comment(&create_git_remote(), 42, 123);
+
+        assert_eq!(
+            url,
+            "https://codeberg.org/user/repo/issues/42#issuecomment-123"
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_path_encodes_path() {
+        let url = get_url_for_path(&create_git_remote(), "docs/my file ü.md", "HEAD", None);
+
+        assert_eq!(
+            url,
+            "https://codeberg.org/user/repo/src/commit/HEAD/docs/my%20file%20%C3%BC.md"
+        );
+    }
+
+    #[test]
+    fn test_get_comment_count() {
+        assert_eq!(get_comment_count(Some(2), Some(5)), Some(7));
+        assert_eq!(get_comment_count(Some(2), None), Some(2));
+        assert_eq!(get_comment_count(None, Some(5)), None);
+    }
+
+    #[test]
+    fn test_hooks_fixture_redacts_secrets() {
+        let hooks: Vec<GiteaHook> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/gitea/hooks.json"
+        ))
+        .unwrap();
+        let hooks: Vec<Hook> = hooks.into_iter().map(Hook::from).collect();
+
+        assert_eq!(
+            hooks[0].url,
+            "https://ci.example.com/hooks/***?token=***&project=app"
+        );
+        assert_eq!(hooks[1].url, "https://***@chat.example.com/notify");
+        assert!(!hooks[1].active);
+        assert!(!serde_json::to_string(&hooks).unwrap().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_created_items_match_golden_files() {
+        let issues: Vec<GiteaIssue> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/gitea/issue.json"
+        ))
+        .unwrap();
+        let prs: Vec<GiteaPullRequest> =
+            serde_json::from_str(include_str!("../../../tests/src/server/data/gitea/pr.json"))
+                .unwrap();
+        let issue = Issue::from(issues.into_iter().next().unwrap());
+        let pr = Pr::from(prs.into_iter().next().unwrap());
+
+        // The output of `issue create --format json` and `pr create --format json`
+        assert_eq!(
+            serde_json::to_string_pretty(&issue).unwrap() + "\n",
+            include_str!("../../../tests/golden/gitea/issue.json")
+        );
+        assert_eq!(
+            serde_json::to_string_pretty(&pr).unwrap() + "\n",
+            include_str!("../../../tests/golden/gitea/pr.json")
+        );
+    }
+
+    fn issue_filters(state: &IssueState) -> ListIssueFilters<'_> {
+        ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: LabelFilter::NONE,
+            min_reactions: None,
+            page: 1,
+            pinned: false,
+            per_page: 30,
+            query: None,
+            sort: None,
+            state,
+            updated: DateRange::default(),
+        }
+    }
+
+    fn pr_filters(state: &PrState) -> ListPrsFilters<'_> {
+        ListPrsFilters {
+            author: None,
+            labels: LabelFilter::NONE,
+            milestone: None,
+            page: 1,
+            per_page: 30,
+            query: None,
+            state,
+            draft: false,
+            review: None,
+            all_pages: false,
+            limit: None,
+        }
+    }
+
+    fn get_query_pairs(request: RequestBuilder) -> Vec<(String, String)> {
+        request
+            .build()
+            .unwrap()
+            .url()
+            .query_pairs()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_issues_request_states() {
+        let http_client = HttpClient::new();
+
+        for (state, expected) in [
+            (IssueState::Open, "open"),
+            (IssueState::Closed, "closed"),
+            (IssueState::All, "all"),
+        ] {
+            let request =
+                build_issues_request(&http_client, URL, &issue_filters(&state), 1, false).unwrap();
+
+            assert_eq!(
+                get_query_pairs(request),
+                [
+                    ("state".to_string(), expected.to_string()),
+                    ("page".to_string(), "1".to_string()),
+                    ("limit".to_string(), "30".to_string()),
+                    ("type".to_string(), "issues".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_issues_request_narrows_dates_with_since() {
+        let filters = ListIssueFilters {
+            closed: DateRange {
+                after: Some(date::parse_date("2024-05-01").unwrap()),
+                before: None,
+            },
+            created: DateRange {
+                after: Some(date::parse_date("2024-03-01").unwrap()),
+                before: Some(date::parse_date("2024-04-01").unwrap()),
+            },
+            ..issue_filters(&IssueState::Closed)
+        };
+        let request =
+            build_issues_request(&HttpClient::new(), URL, &filters, filters.page, false).unwrap();
+
+        assert!(
+            get_query_pairs(request)
+                .ends_with(&[("since".to_string(), "2024-05-01T00:00:00Z".to_string())])
+        );
+
+        let filters = ListIssueFilters {
+            updated: DateRange {
+                after: Some(date::parse_date("2024-06-01").unwrap()),
+                before: None,
+            },
+            ..filters
+        };
+        let request =
+            build_issues_request(&HttpClient::new(), URL, &filters, filters.page, false).unwrap();
+
+        assert!(
+            get_query_pairs(request)
+                .ends_with(&[("since".to_string(), "2024-06-01T00:00:00Z".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_build_pulls_request_states() {
+        let http_client = HttpClient::new();
+
+        for (state, expected) in [
+            (PrState::Open, "open"),
+            (PrState::Closed, "closed"),
+            (PrState::All, "all"),
+        ] {
+            let request =
+                build_pulls_request(&http_client, URL, &pr_filters(&state), 1, false).unwrap();
+
+            assert_eq!(
+                get_query_pairs(request),
+                [
+                    ("type".to_string(), "pulls".to_string()),
+                    ("state".to_string(), expected.to_string()),
+                    ("page".to_string(), "1".to_string()),
+                    ("limit".to_string(), "30".to_string()),
+                ]
+            );
+        }
+
+        // Merged PRs can't be listed, so this fails before sending a request
+        let error = get_prs(
+            &http_client,
+            &GitRemoteData {
+                host: "codeberg.org".to_string(),
+                path: "user/repo".to_string(),
+                port: None,
+                web_base_url: "https://codeberg.org".to_string(),
+                api_base_url: None,
+            },
+            Some(URL),
+            &pr_filters(&PrState::Merged),
+            false,
+        )
+        .err()
+        .unwrap();
+
+        assert!(
+            error
+                .to_string()
+                .contains("does not support filtering by merged state")
+        );
+    }
+
+    #[test]
+    fn test_build_issues_request_filters() {
+        let labels = LabelFilter::parse(&[
+            String::from("bug"),
+            String::from("-wontfix"),
+            String::from("ui"),
+        ]);
+        let filters = ListIssueFilters {
+            assignee: Some("alice"),
+            author: Some("bob"),
+            labels: &labels,
+            page: 2,
+            per_page: 50,
+            query: Some("crash"),
+            ..issue_filters(&IssueState::Open)
+        };
+        let request =
+            build_issues_request(&HttpClient::new(), URL, &filters, filters.page, false).unwrap();
+
+        assert_eq!(
+            get_query_pairs(request),
+            [
+                ("state".to_string(), "open".to_string()),
+                ("page".to_string(), "2".to_string()),
+                ("limit".to_string(), "50".to_string()),
+                ("type".to_string(), "issues".to_string()),
+                ("assigned_by".to_string(), "alice".to_string()),
+                ("created_by".to_string(), "bob".to_string()),
+                ("labels".to_string(), "bug,ui".to_string()),
+                ("q".to_string(), "crash".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_issues_request_without_labels() {
+        // The labeled issues are dropped after fetching
+        let labels = LabelFilter {
+            unlabeled: true,
+            ..LabelFilter::default()
+        };
+        let filters = ListIssueFilters {
+            labels: &labels,
+            ..issue_filters(&IssueState::Open)
+        };
+        let request =
+            build_issues_request(&HttpClient::new(), URL, &filters, filters.page, false).unwrap();
+
+        assert_eq!(
+            get_query_pairs(request),
+            [
+                ("state".to_string(), "open".to_string()),
+                ("page".to_string(), "1".to_string()),
+                ("limit".to_string(), "30".to_string()),
+                ("type".to_string(), "issues".to_string()),
+            ]
+        );
+        assert!(filters.labels.needs_client_side_filtering());
+    }
+
+    #[test]
+    fn test_build_issues_request_includes_prs() {
+        let filters = ListIssueFilters {
+            include_prs: true,
+            ..issue_filters(&IssueState::Open)
+        };
+        let request =
+            build_issues_request(&HttpClient::new(), URL, &filters, filters.page, false).unwrap();
+
+        assert!(
+            !get_query_pairs(request)
+                .iter()
+                .any(|(key, _)| key == "type")
+        );
+    }
+
+    #[test]
+    fn test_build_pulls_request_filters() {
+        let labels = LabelFilter::parse(&[String::from("bug")]);
+        let filters = ListPrsFilters {
+            author: Some("bob"),
+            labels: &labels,
+            milestone: Some("v2.0"),
+            query: Some("fix"),
+            ..pr_filters(&PrState::Open)
+        };
+        let request =
+            build_pulls_request(&HttpClient::new(), URL, &filters, filters.page, false).unwrap();
+
+        assert_eq!(
+            get_query_pairs(request),
+            [
+                ("type".to_string(), "pulls".to_string()),
+                ("state".to_string(), "open".to_string()),
+                ("page".to_string(), "1".to_string()),
+                ("limit".to_string(), "30".to_string()),
+                ("created_by".to_string(), "bob".to_string()),
+                ("labels".to_string(), "bug".to_string()),
+                ("milestones".to_string(), "v2.0".to_string()),
+                ("q".to_string(), "fix".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_closed_issues_request() {
+        let filters = ChangelogFilters {
+            milestone: Some("v1.0"),
+            since: Some("2024-01-01T00:00:00Z"),
+            page: 1,
+            per_page: 50,
+        };
+        let request =
+            build_closed_issues_request(&HttpClient::new(), URL, "pulls", &filters, false).unwrap();
+
+        assert_eq!(
+            get_query_pairs(request),
+            [
+                ("type".to_string(), "pulls".to_string()),
+                ("state".to_string(), "closed".to_string()),
+                ("page".to_string(), "1".to_string()),
+                ("limit".to_string(), "50".to_string()),
+                ("milestones".to_string(), "v1.0".to_string()),
+                ("since".to_string(), "2024-01-01T00:00:00Z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pr_timestamps_use_utc() {
+        // Gitea/Forgejo return the timestamps in the offset of the server
+        let issue: GiteaIssue = serde_json::from_value(serde_json::json!({
+            "number": 5,
+            "title": "Fix crash",
+            "state": "closed",
+            "labels": [],
+            "user": { "login": "bob" },
+            "html_url": "https://codeberg.org/user/repo/pulls/5",
+            "pull_request": {
+                "draft": false,
+                "merged": true,
+                "merged_at": "2024-05-02T02:00:00+08:00"
+            },
+            "created_at": "2024-05-01T12:00:00+02:00",
+            "updated_at": "2024-05-02T02:00:00+08:00",
+            "closed_at": "2024-05-02T02:00:00+08:00"
+        }))
+        .unwrap();
+        let pr = Pr::from(issue);
+        let json = serde_json::to_value(&pr).unwrap();
+
+        assert_eq!(json["created_at"], "2024-05-01T10:00:00Z");
+        assert_eq!(json["merged_at"], "2024-05-01T18:00:00Z");
+        assert!(pr.created_at < pr.updated_at);
+    }
+
+    #[test]
+    fn test_is_matching_pinned_issue() {
+        let pinned_issues: Vec<GiteaIssue> = serde_json::from_value(serde_json::json!([
+            {
+                "number": 1,
+                "title": "Crash on start",
+                "state": "open",
+                "labels": [{ "name": "bug" }],
+                "user": { "login": "bob" },
+                "assignees": [{ "login": "alice" }],
+                "html_url": "https://codeberg.org/user/repo/issues/1",
+                "created_at": "2024-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z",
+                "pin_order": 1
+            },
+            {
+                "number": 2,
+                "title": "Roadmap",
+                "state": "open",
+                "labels": [],
+                "user": { "login": "alice" },
+                "assignees": null,
+                "html_url": "https://codeberg.org/user/repo/issues/2",
+                "created_at": "2024-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z",
+                "pin_order": 2
+            },
+            {
+                "number": 3,
+                "title": "Crash fix",
+                "state": "open",
+                "labels": [{ "name": "bug" }],
+                "user": { "login": "bob" },
+                "html_url": "https://codeberg.org/user/repo/pulls/3",
+                "pull_request": { "draft": false, "merged": false },
+                "created_at": "2024-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z",
+                "pin_order": 3
+            }
+        ]))
+        .unwrap();
+        let labels = LabelFilter::parse(&[String::from("bug")]);
+        let matching_numbers = |filters: &ListIssueFilters| {
+            pinned_issues
+                .iter()
+                .filter(|issue| is_matching_pinned_issue(issue, filters))
+                .map(|issue| issue.number)
+                .collect::<Vec<_>>()
+        };
+        let filters = ListIssueFilters {
+            pinned: true,
+            ..issue_filters(&IssueState::Open)
+        };
+
+        assert_eq!(matching_numbers(&filters), [1, 2]);
+        assert_eq!(
+            matching_numbers(&ListIssueFilters {
+                assignee: Some("alice"),
+                ..filters
+            }),
+            [1]
+        );
+        assert_eq!(
+            matching_numbers(&ListIssueFilters {
+                include_prs: false,
+                labels: &labels,
+                query: Some("crash"),
+                ..filters
+            }),
+            [1]
+        );
+        assert_eq!(
+            matching_numbers(&ListIssueFilters {
+                labels: &LabelFilter {
+                    unlabeled: true,
+                    ..LabelFilter::default()
+                },
+                ..filters
+            }),
+            [2]
+        );
+        assert_eq!(
+            matching_numbers(&ListIssueFilters {
+                author: Some("alice"),
+                ..issue_filters(&IssueState::Closed)
+            }),
+            [] as [u32; 0]
+        );
+    }
+
+    #[test]
+    fn test_fetch_filled_page_tops_up_pages() {
+        // Items divisible by 3 are dropped, 3 endpoint pages of 4 items each
+        let fetch_page = |page: u32| {
+            let items = ((page - 1) * 4 + 1..=page * 4).collect::<Vec<u32>>();
+
+            Ok(PaginatedResponse::new(items, page < 3))
+        };
+        let keep = |item: &u32| !item.is_multiple_of(3);
+
+        let first = fetch_filled_page(1, 5, fetch_page, keep).unwrap();
+
+        assert_eq!(first.items, [1, 2, 4, 5, 7]);
+        assert!(first.has_next_page);
+
+        let second = fetch_filled_page(2, 5, fetch_page, keep).unwrap();
+
+        assert_eq!(second.items, [8, 10, 11]);
+        assert!(!second.has_next_page);
+    }
+
+    #[test]
+    fn test_get_filtered_prs_fills_pages_with_drafts() {
+        let prs: Vec<GiteaPullRequest> =
+            serde_json::from_str(include_str!("../../../tests/src/server/data/gitea/pr.json"))
+                .unwrap();
+        let template = Pr::from(prs.into_iter().next().unwrap());
+        // Every third PR is a draft, 3 endpoint pages of 6 PRs each
+        let fetch_page = |page: u32| {
+            let prs = ((page - 1) * 6 + 1..=page * 6)
+                .map(|id| Pr {
+                    id,
+                    draft: id.is_multiple_of(3),
+                    ..template.clone()
+                })
+                .collect::<Vec<Pr>>();
+
+            Ok(PaginatedResponse::new(prs, page < 3))
+        };
+        let filters = ListPrsFilters {
+            draft: true,
+            per_page: 2,
+            ..pr_filters(&PrState::Open)
+        };
+        let get_ids = |page| {
+            let response =
+                get_filtered_prs(&ListPrsFilters { page, ..filters }, fetch_page).unwrap();
+
+            (
+                response.items.iter().map(|pr| pr.id).collect::<Vec<u32>>(),
+                response.has_next_page,
+            )
+        };
+
+        assert_eq!(get_ids(1), (vec![3, 6], true));
+        assert_eq!(get_ids(2), (vec![9, 12], true));
+        assert_eq!(get_ids(3), (vec![15, 18], false));
+    }
+
+    #[test]
+    fn test_get_issues_fails_for_reactions() {
+        let remote = GitRemoteData {
+            host: "codeberg.org".to_string(),
+            path: "user/repo".to_string(),
+            port: None,
+            web_base_url: "https://codeberg.org".to_string(),
+            api_base_url: None,
+        };
+
+        for filters in [
+            ListIssueFilters {
+                min_reactions: Some(10),
+                ..issue_filters(&IssueState::Open)
+            },
+            ListIssueFilters {
+                sort: Some(&IssueSort::Reactions),
+                ..issue_filters(&IssueState::Open)
+            },
+        ] {
+            let error = get_issues(&HttpClient::new(), &remote, Some(URL), &filters, false)
+                .err()
+                .unwrap();
+
+            assert!(error.to_string().contains("does not return reactions"));
+        }
     }
 }
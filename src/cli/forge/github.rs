@@ -1,19 +1,52 @@
+use std::collections::HashMap;
+
 use anyhow::Context;
-use serde::{Deserialize, de::DeserializeOwned};
+use reqwest::blocking::RequestBuilder;
+use serde::{
+    Deserialize,
+    de::{DeserializeOwned, IgnoredAny},
+};
 
+#[cfg(feature = "self-update")]
+use crate::cli::self_update::{Release, ReleaseAsset};
 use crate::{
     cli::{
-        forge::http_client::{
-            self, HttpClient, IntoPaginatedResponse, PaginatedResponse, WithAuth, WithHttpStatusOk,
+        branch::Branch,
+        changelog::{ChangelogEntry, ChangelogEntryKind, ChangelogFilters},
+        forge::{
+            Capability, Support,
+            http_client::{
+                self, HttpClient, IntoPaginatedResponse, PaginatedResponse, ParseJson,
+                SendThrottled, WithAuth, WithHttpStatusOk,
+            },
+            identity::{self, Identity},
+            labels,
+            precheck::RepoStatus,
+            url_encoding,
+        },
+        hook::{self, Hook},
+        issue::{
+            self, CreateIssueOptions, ExportedIssue, Issue, IssueComment, IssueKind, IssueSort,
+            IssueState, IssueStateReason, ListIssueFilters,
         },
-        issue::{CreateIssueOptions, Issue, IssueState, ListIssueFilters},
-        pr::{CreatePrOptions, ListPrsFilters, Pr, PrState},
+        pr::{
+            CreatePrOptions, ListPrsFilters, Pr, PrApprovals, PrBranch, PrCiStatus, PrCounts,
+            PrMergeInfo, PrMergeOutcome, PrMergeable, PrReviewStatus, PrState, PrTimelineEvent,
+            PrTimelineEventKind, PrTimelineOptions,
+        },
+        repo::{self, ListReposFilters, Repo, RepoEdit, RepoOwner, RepoSummary},
     },
+    date::{self, DateRange, Timestamp},
     git::GitRemoteData,
 };
 
 const AUTH_TOKEN: &str = "GIT_FORGE_GITHUB_TOKEN";
 const AUTH_SCHEME: &str = "Bearer";
+/// The name the forge shows for deleted accounts.
+const DELETED_USER: &str = "ghost";
+/// The number of PRs whose review status is fetched with one GraphQL query.
+const REVIEW_STATUS_BATCH_SIZE: usize = 100;
+const HOOKS_PERMISSION_HINT: &str = "Webhooks need a token with the admin:repo_hook scope (classic) or the Webhooks permission (fine-grained) and admin rights on the repository";
 
 // =============================================================================
 // Domain Types
@@ -42,20 +75,53 @@ struct GitHubIssue {
     number: u32,
     title: String,
     state: IssueState,
+    state_reason: Option<IssueStateReason>,
     labels: Vec<GitHubLabel>,
     user: GitHubUser,
     html_url: String,
+    body: Option<String>,
+    created_at: Timestamp,
+    closed_at: Option<Timestamp>,
+    reactions: Option<GitHubReactions>,
+    /// Only set if the issue is a pull request.
+    pull_request: Option<GitHubIssuePrField>,
 }
 
+/// The pull request field of a GitHub API issue response.
+#[derive(Debug, Deserialize)]
+struct GitHubIssuePrField {}
+
 impl From<GitHubIssue> for Issue {
     fn from(issue: GitHubIssue) -> Self {
         Issue {
             id: issue.number,
             title: issue.title,
             state: issue.state,
+            state_reason: issue.state_reason,
+            author: issue.user.login,
+            url: issue.html_url,
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            pinned: None,
+            created_at: Some(issue.created_at),
+            closed_at: issue.closed_at,
+            reactions: issue.reactions.map(|reactions| reactions.total_count),
+            kind: issue.pull_request.map(|_| IssueKind::Pr),
+        }
+    }
+}
+
+impl From<GitHubIssue> for ExportedIssue {
+    fn from(issue: GitHubIssue) -> Self {
+        ExportedIssue {
+            id: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            state: issue.state,
             author: issue.user.login,
+            created_at: issue.created_at.to_string(),
             url: issue.html_url,
             labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            comments: vec![],
         }
     }
 }
@@ -65,12 +131,400 @@ struct GitHubLabel {
     name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitHubMilestone {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReactions {
+    total_count: u32,
+}
+
 #[derive(Debug, Deserialize)]
 struct GitHubUser {
     login: String,
 }
 
-/// GitHub API response for pull requests.
+/// GitHub API response for the authenticated user.
+/// https://docs.github.com/en/rest/users/users#get-the-authenticated-user
+#[derive(Debug, Deserialize)]
+struct GitHubAuthenticatedUser {
+    login: String,
+    id: u64,
+}
+
+impl From<GitHubAuthenticatedUser> for Identity {
+    fn from(user: GitHubAuthenticatedUser) -> Self {
+        Identity {
+            username: user.login,
+            id: user.id,
+        }
+    }
+}
+
+/// The assignees of a GitHub API issue response.
+#[derive(Debug, Deserialize)]
+struct GitHubIssueAssignees {
+    assignees: Vec<GitHubUser>,
+}
+
+/// The requested reviewers of a GitHub API pull request response.
+#[derive(Debug, Deserialize)]
+struct GitHubPrReviewers {
+    requested_reviewers: Vec<GitHubUser>,
+}
+
+/// The part of a GitHub API issue or pull request response that the GraphQL
+/// API needs.
+#[derive(Debug, Deserialize)]
+struct GitHubNodeId {
+    node_id: String,
+}
+
+/// The auto-merge request of a GitHub API pull request response. `null`
+/// unless auto-merge is enabled.
+#[derive(Debug, Deserialize)]
+struct GitHubPrAutoMerge {
+    auto_merge: Option<IgnoredAny>,
+}
+
+/// GitHub GraphQL API response. Errors are returned with a 200 status.
+#[derive(Debug, Deserialize)]
+struct GitHubGraphQlResponse<T = IgnoredAny> {
+    data: Option<T>,
+    errors: Option<Vec<GitHubGraphQlError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubGraphQlError {
+    message: String,
+}
+
+/// GitHub API response for pull request reviews.
+/// https://docs.github.com/en/rest/pulls/reviews#list-reviews-for-a-pull-request
+#[derive(Debug, Deserialize)]
+struct GitHubReview {
+    user: Option<GitHubUser>,
+    state: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    submitted_at: Option<Timestamp>,
+}
+
+/// GitHub API response for issue comments. The single pull request endpoint
+/// has the same shape for the description.
+/// https://docs.github.com/en/rest/issues/comments#list-issue-comments
+#[derive(Debug, Deserialize)]
+struct GitHubComment {
+    id: u64,
+    user: Option<GitHubUser>,
+    body: Option<String>,
+    created_at: Timestamp,
+}
+
+impl From<GitHubComment> for IssueComment {
+    fn from(comment: GitHubComment) -> Self {
+        IssueComment {
+            id: comment.id,
+            author: get_login(comment.user.as_ref()),
+            created_at: comment.created_at.to_string(),
+            body: comment.body.unwrap_or_default(),
+        }
+    }
+}
+
+impl GitHubComment {
+    fn into_timeline_event(self, kind: PrTimelineEventKind) -> PrTimelineEvent {
+        PrTimelineEvent {
+            kind,
+            author: get_login(self.user.as_ref()),
+            created_at: self.created_at,
+            body: self.body.unwrap_or_default(),
+        }
+    }
+}
+
+/// GitHub API response for requested reviewers.
+/// https://docs.github.com/en/rest/pulls/review-requests
+#[derive(Debug, Deserialize)]
+struct GitHubReviewRequests {
+    users: Vec<GitHubUser>,
+    teams: Vec<serde::de::IgnoredAny>,
+}
+
+/// GitHub GraphQL API response for the review status query, see
+/// `build_review_status_query`. The PRs are keyed by their alias, e.g. `pr42`,
+/// and are `null` if they don't exist.
+#[derive(Debug, Deserialize)]
+struct GitHubReviewStatusData {
+    repository: HashMap<String, Option<GitHubGraphQlPrReviews>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GitHubGraphQlPrReviews {
+    latest_reviews: GitHubGraphQlNodes<GitHubGraphQlReview>,
+    review_requests: GitHubGraphQlCount,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubGraphQlNodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GitHubGraphQlCount {
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubGraphQlReview {
+    author: Option<GitHubUser>,
+    state: String,
+}
+
+impl From<&GitHubGraphQlPrReviews> for PrReviewStatus {
+    fn from(pr: &GitHubGraphQlPrReviews) -> Self {
+        // Unlike the REST API, only the latest review of each reviewer is
+        // listed
+        let reviews = pr.latest_reviews.nodes.iter().filter_map(|review| {
+            let status = get_review_verdict(&review.state)?;
+
+            Some((review.author.as_ref()?.login.as_str(), status))
+        });
+
+        PrReviewStatus::from_reviews(reviews, pr.review_requests.total_count > 0)
+    }
+}
+
+/// GitHub API response for a single pull request. Only the single pull request
+/// endpoint contains the mergeability and the counts.
+/// https://docs.github.com/en/rest/pulls/pulls#get-a-pull-request
+#[derive(Debug, Deserialize)]
+struct GitHubPullRequestDetails {
+    mergeable: Option<bool>,
+    head: GitHubCommitRef,
+    comments: Option<u32>,
+    review_comments: Option<u32>,
+    commits: Option<u32>,
+    changed_files: Option<u32>,
+}
+
+impl From<GitHubPullRequestDetails> for PrCounts {
+    fn from(pr: GitHubPullRequestDetails) -> Self {
+        PrCounts {
+            comments: pr
+                .comments
+                .map(|comments| comments + pr.review_comments.unwrap_or(0)),
+            commits: pr.commits,
+            changed_files: pr.changed_files,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommitRef {
+    sha: String,
+}
+
+/// GitHub API response for check runs of a commit.
+/// https://docs.github.com/en/rest/checks/runs#list-check-runs-for-a-git-reference
+#[derive(Debug, Deserialize)]
+struct GitHubCheckRuns {
+    check_runs: Vec<GitHubCheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+/// GitHub API response for the combined (legacy) commit status.
+/// https://docs.github.com/en/rest/commits/statuses#get-the-combined-status-for-a-specific-reference
+#[derive(Debug, Deserialize)]
+struct GitHubCombinedStatus {
+    state: String,
+    total_count: u32,
+}
+
+/// GitHub API response for a repository.
+/// https://docs.github.com/en/rest/repos/repos#get-a-repository
+#[derive(Debug, Deserialize)]
+struct GitHubRepository {
+    full_name: String,
+    description: Option<String>,
+    html_url: String,
+    default_branch: String,
+    #[serde(default)]
+    topics: Vec<String>,
+    archived: bool,
+}
+
+/// GitHub API response for the repositories of an organization or user.
+/// https://docs.github.com/en/rest/repos/repos#list-organization-repositories
+#[derive(Debug, Deserialize)]
+struct GitHubRepositorySummary {
+    full_name: String,
+    private: bool,
+    /// Missing on older GitHub Enterprise Server versions.
+    visibility: Option<String>,
+    stargazers_count: u32,
+    default_branch: String,
+    /// Missing for repositories without pushes.
+    pushed_at: Option<Timestamp>,
+    archived: bool,
+    fork: bool,
+    html_url: String,
+    clone_url: String,
+    ssh_url: String,
+}
+
+impl From<GitHubRepositorySummary> for RepoSummary {
+    fn from(repo: GitHubRepositorySummary) -> Self {
+        RepoSummary {
+            name: repo.full_name,
+            visibility: repo
+                .visibility
+                .unwrap_or_else(|| String::from(if repo.private { "private" } else { "public" })),
+            stars: repo.stargazers_count,
+            default_branch: repo.default_branch,
+            pushed_at: repo.pushed_at,
+            archived: repo.archived,
+            fork: repo.fork,
+            url: repo.html_url,
+            clone_url: repo.clone_url,
+            ssh_url: repo.ssh_url,
+        }
+    }
+}
+
+/// GitHub API response for webhooks. The secret in the config is left out.
+/// https://docs.github.com/en/rest/repos/webhooks#list-repository-webhooks
+#[derive(Debug, Deserialize)]
+struct GitHubHook {
+    id: u64,
+    active: bool,
+    events: Vec<String>,
+    config: GitHubHookConfig,
+    last_response: Option<GitHubHookResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubHookConfig {
+    /// Missing for the hooks of GitHub Apps.
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubHookResponse {
+    code: Option<u16>,
+    status: Option<String>,
+}
+
+impl From<GitHubHook> for Hook {
+    fn from(hook: GitHubHook) -> Self {
+        Hook {
+            id: hook.id,
+            url: hook::redact_url(&hook.config.url.unwrap_or_default()),
+            events: hook.events,
+            active: hook.active,
+            last_delivery: hook.last_response.and_then(|response| {
+                response
+                    .code
+                    .map(|code| code.to_string())
+                    .or(response.status)
+            }),
+        }
+    }
+}
+
+/// GitHub API response for branches.
+/// https://docs.github.com/en/rest/branches/branches#list-branches
+#[derive(Debug, Deserialize)]
+struct GitHubBranch {
+    name: String,
+    commit: GitHubCommitRef,
+    protected: bool,
+}
+
+impl From<GitHubBranch> for Branch {
+    fn from(branch: GitHubBranch) -> Self {
+        Branch {
+            name: branch.name,
+            sha: branch.commit.sha,
+            last_commit_at: None,
+            age: None,
+            protected: branch.protected,
+            merged: None,
+            pr: None,
+            stale: None,
+        }
+    }
+}
+
+/// GitHub API response for a commit.
+/// https://docs.github.com/en/rest/commits/commits#get-a-commit
+#[derive(Debug, Deserialize)]
+struct GitHubCommit {
+    commit: GitHubCommitDetails,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommitDetails {
+    committer: GitHubCommitSignature,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCommitSignature {
+    date: Timestamp,
+}
+
+/// GitHub API response for comparing two commits.
+/// https://docs.github.com/en/rest/commits/commits#compare-two-commits
+#[derive(Debug, Deserialize)]
+struct GitHubComparison {
+    ahead_by: u32,
+}
+
+/// GitHub API response for releases.
+/// https://docs.github.com/en/rest/releases/releases
+#[cfg(feature = "self-update")]
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    assets: Vec<GitHubReleaseAsset>,
+}
+
+#[cfg(feature = "self-update")]
+#[derive(Debug, Deserialize)]
+struct GitHubReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+#[cfg(feature = "self-update")]
+impl From<GitHubRelease> for Release {
+    fn from(release: GitHubRelease) -> Self {
+        Release {
+            tag: release.tag_name,
+            assets: release
+                .assets
+                .into_iter()
+                .map(|asset| ReleaseAsset {
+                    name: asset.name,
+                    download_url: asset.browser_download_url,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GitHub API response for pull requests. The search API returns pull requests
+/// as issues, i.e. without the branches.
 /// https://docs.github.com/en/rest/pulls/pulls
 #[derive(Debug, Deserialize)]
 struct GitHubPullRequest {
@@ -78,12 +532,52 @@ struct GitHubPullRequest {
     title: String,
     state: String,
     labels: Vec<GitHubLabel>,
+    milestone: Option<GitHubMilestone>,
     user: GitHubUser,
-    created_at: String,
-    updated_at: String,
+    created_at: Timestamp,
+    updated_at: Timestamp,
     html_url: String,
     draft: Option<bool>,
-    merged_at: Option<String>,
+    merged_at: Option<Timestamp>,
+    closed_at: Option<Timestamp>,
+    head: Option<GitHubBranchRef>,
+    base: Option<GitHubBranchRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubBranchRef {
+    #[serde(rename = "ref")]
+    name: String,
+    sha: String,
+}
+
+impl From<GitHubBranchRef> for PrBranch {
+    fn from(branch: GitHubBranchRef) -> Self {
+        PrBranch {
+            name: branch.name,
+            sha: Some(branch.sha),
+        }
+    }
+}
+
+impl From<GitHubIssue> for ChangelogEntry {
+    fn from(issue: GitHubIssue) -> Self {
+        ChangelogEntry {
+            kind: ChangelogEntryKind::Issue,
+            id: issue.number,
+            title: issue.title,
+            author: issue.user.login,
+            url: issue.html_url,
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            closed_at: issue.closed_at,
+        }
+    }
+}
+
+impl From<GitHubPullRequest> for ChangelogEntry {
+    fn from(pr: GitHubPullRequest) -> Self {
+        Pr::from(pr).into()
+    }
 }
 
 impl From<GitHubPullRequest> for Pr {
@@ -99,9 +593,23 @@ impl From<GitHubPullRequest> for Pr {
             author: pr.user.login,
             url: pr.html_url,
             labels: pr.labels.into_iter().map(|l| l.name).collect(),
+            milestone: pr.milestone.map(|milestone| milestone.title),
             created_at: pr.created_at,
             updated_at: pr.updated_at,
+            merged_at: pr.merged_at,
+            closed_at: pr.closed_at,
+            head: pr.head.map(PrBranch::from),
+            base: pr.base.map(PrBranch::from),
             draft: pr.draft.unwrap_or(false),
+            approvals_required: None,
+            approvals_left: None,
+            review_status: None,
+            mergeable: None,
+            ci: None,
+            // The list endpoint doesn't return the counts
+            comments: None,
+            commits: None,
+            changed_files: None,
         }
     }
 }
@@ -110,6 +618,28 @@ impl From<GitHubPullRequest> for Pr {
 // Command Logic
 // =============================================================================
 
+pub fn supports(capability: Capability) -> Support {
+    match capability {
+        Capability::PinnedFilter => Support::No(
+            "GitHub only exposes pinned issues via its GraphQL API. --pinned is only supported for Gitea/Forgejo",
+        ),
+        Capability::RemoveSourceBranch => Support::No(
+            "GitHub deletes the branches of merged PRs with the \"Automatically delete head branches\" repository setting instead. --remove-source-branch is only supported for GitLab",
+        ),
+        Capability::SquashOnMerge => Support::No(
+            "GitHub chooses the merge method when merging instead. --squash-on-merge is only supported for GitLab",
+        ),
+        Capability::AutoMerge
+        | Capability::ClosedAsFilter
+        | Capability::DraftPrs
+        | Capability::HookToggle
+        | Capability::IssuePinning
+        | Capability::MaintainerEdit
+        | Capability::MergedStateFilter
+        | Capability::Reactions => Support::Yes,
+    }
+}
+
 pub fn get_issues(
     http_client: &HttpClient,
     remote: &GitRemoteData,
@@ -117,21 +647,29 @@ pub fn get_issues(
     filters: &ListIssueFilters,
     use_auth: bool,
 ) -> anyhow::Result<PaginatedResponse<Issue>> {
+    if filters.pinned {
+        supports(Capability::PinnedFilter).ensure()?;
+    }
+
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let url = format!("{base_url}/search/issues");
     let query_string = build_issue_search_query(&remote.path, filters);
-
-    find_items_with_search_api::<GitHubIssue, Issue>(
+    let mut response = find_items_with_search_api::<GitHubIssue, Issue>(
         http_client,
         &url,
         &query_string,
         filters.page,
         filters.per_page,
+        get_issue_search_sort(filters),
         use_auth,
-    )
+    )?;
+
+    issue::mark_issue_kinds(&mut response.items, filters);
+
+    Ok(response)
 }
 
 pub fn create_issue(
@@ -149,6 +687,7 @@ pub fn create_issue(
     let request_body = serde_json::json!({
         "title": options.title,
         "body": options.body,
+        "labels": options.labels,
     });
 
     eprintln!("Creating issue on GitHub...");
@@ -160,77 +699,1179 @@ pub fn create_issue(
         .json(&request_body);
 
     request
-        .send()
+        .send_throttled()
         .context("Network request failed while creating issue on GitHub")?
         .with_http_status_ok()?
-        .json()
+        .parse_json()
         .context("Failed to parse GitHub API response")
         .map(|issue: GitHubIssue| issue.into())
 }
 
-pub fn get_prs(
+pub fn get_issues_for_export(
     http_client: &HttpClient,
     remote: &GitRemoteData,
     api_url: Option<&str>,
-    filters: &ListPrsFilters,
+    filters: &ListIssueFilters,
     use_auth: bool,
-) -> anyhow::Result<PaginatedResponse<Pr>> {
+) -> anyhow::Result<PaginatedResponse<ExportedIssue>> {
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let url = format!("{base_url}/search/issues");
-    let query_string = build_pr_search_query(&remote.path, filters);
+    let query_string = build_issue_search_query(&remote.path, filters);
 
-    find_items_with_search_api::<GitHubPullRequest, Pr>(
+    find_items_with_search_api::<GitHubIssue, ExportedIssue>(
         http_client,
         &url,
         &query_string,
         filters.page,
         filters.per_page,
+        None,
         use_auth,
     )
 }
 
-pub fn create_pr(
+pub fn get_issue(
     http_client: &HttpClient,
     remote: &GitRemoteData,
     api_url: Option<&str>,
-    options: &CreatePrOptions,
-) -> anyhow::Result<Pr> {
+    issue_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<ExportedIssue> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/issues/{issue_number}", remote.path),
+        use_auth,
+    )
+    .map(|issue: GitHubIssue| issue.into())
+}
+
+pub fn get_issue_comments(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Vec<IssueComment>> {
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let repo_path = &remote.path;
-    let url = format!("{base_url}/repos/{repo_path}/pulls");
-    let request_body = serde_json::json!({
-        "title": options.title,
-        "head": options.source_branch,
-        "base": options.target_branch,
-        "body": options.body,
-        "draft": options.draft,
-    });
+    let comments: Vec<GitHubComment> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{repo_path}/issues/{issue_number}/comments"),
+        &[],
+        use_auth,
+    )?;
 
-    eprintln!("Creating pull request on GitHub...");
+    Ok(comments.into_iter().map(IssueComment::from).collect())
+}
 
-    let request = http_client
-        .post(&url)
+pub fn close_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+
+    http_client
+        .patch(&format!(
+            "{base_url}/repos/{repo_path}/issues/{issue_number}"
+        ))
         .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
         .header("Accept", "application/vnd.github+json")
-        .json(&request_body);
+        .json(&serde_json::json!({ "state": "closed" }))
+        .send_throttled()
+        .context("Network request failed while closing issue on GitHub")?
+        .with_http_status_ok()?;
 
-    request
-        .send()
-        .context("Network request failed while creating pull request on GitHub")?
-        .with_http_status_ok()?
-        .json()
-        .context("Failed to parse GitHub API response")
-        .map(|pr: GitHubPullRequest| pr.into())
+    Ok(())
 }
 
-pub fn get_pr_ref(pr_number: u32) -> String {
-    format!("pull/{pr_number}/head")
+/// Assigns the issue to the users and returns the resulting assignees.
+pub fn assign_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_assignees(http_client, remote, api_url, issue_number, assignees, true)
+}
+
+/// Removes the users from the assignees of the issue and returns the
+/// remaining assignees.
+pub fn unassign_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_assignees(http_client, remote, api_url, issue_number, assignees, false)
+}
+
+/// Gets the username of the user that the token belongs to.
+pub fn get_authenticated_user(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<String> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    Ok(get_identity(http_client, base_url)?.username)
+}
+
+/// Adds the labels to the issue and returns its resulting labels.
+pub fn add_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!(
+        "{base_url}/repos/{}/issues/{issue_number}/labels",
+        remote.path
+    );
+
+    send_issue_labels(http_client.post(&url), labels)
+}
+
+/// Removes the labels from the issue and returns its remaining labels. Labels
+/// that the issue doesn't have are ignored.
+pub fn remove_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!(
+        "{base_url}/repos/{}/issues/{issue_number}/labels",
+        remote.path
+    );
+    // Deleting a single label fails if the issue doesn't have it, so the
+    // labels are replaced with the remaining ones instead
+    let current: Vec<GitHubLabel> = get_json_pages(http_client, &url, &[], true)?;
+    let current: Vec<String> = current.into_iter().map(|l| l.name).collect();
+    let remaining = labels::without_labels(current.clone(), labels);
+
+    if remaining.len() == current.len() {
+        return Ok(current);
+    }
+
+    send_issue_labels(http_client.put(&url), &remaining)
+}
+
+/// Replaces the labels of the issue and returns its resulting labels.
+pub fn set_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!(
+        "{base_url}/repos/{}/issues/{issue_number}/labels",
+        remote.path
+    );
+
+    send_issue_labels(http_client.put(&url), labels)
+}
+
+/// Pinning is only available via the GraphQL API, which needs the node id of
+/// the issue instead of its number.
+pub fn set_issue_pinned(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    pinned: bool,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let issue: GitHubNodeId = http_client
+        .get(&format!(
+            "{base_url}/repos/{repo_path}/issues/{issue_number}"
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while fetching issue from GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")?;
+    let mutation = if pinned { "pinIssue" } else { "unpinIssue" };
+    let request_body = serde_json::json!({
+        "query": format!(
+            "mutation($issueId: ID!) {{ {mutation}(input: {{issueId: $issueId}}) {{ issue {{ number }} }} }}"
+        ),
+        "variables": { "issueId": issue.node_id },
+    });
+
+    send_graphql_mutation(http_client, base_url, &request_body, "pinning issue")
+}
+
+pub fn get_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/repos/{}/labels", remote.path);
+    let labels: Vec<GitHubLabel> = get_json_pages(http_client, &url, &[], true)?;
+
+    Ok(labels.into_iter().map(|l| l.name).collect())
+}
+
+/// Creates a label with a color given as `rrggbb`.
+pub fn create_label(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    name: &str,
+    color: &str,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/repos/{}/labels", remote.path);
+    let request_body = serde_json::json!({
+        "name": name,
+        "color": color,
+    });
+
+    http_client
+        .post(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .json(&request_body)
+        .send_throttled()
+        .context("Network request failed while creating label on GitHub")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+pub fn get_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListPrsFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/search/issues");
+    let query_string = build_pr_search_query(&remote.path, filters);
+
+    find_items_with_search_api::<GitHubPullRequest, Pr>(
+        http_client,
+        &url,
+        &query_string,
+        filters.page,
+        filters.per_page,
+        None,
+        use_auth,
+    )
+}
+
+pub fn get_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Pr> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path),
+        use_auth,
+    )
+    .map(|pr: GitHubPullRequest| pr.into())
+}
+
+pub fn get_open_prs_for_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/search/issues");
+    let query_string = format!("repo:{} is:pr is:open head:{branch}", remote.path);
+
+    find_items_with_search_api::<GitHubPullRequest, Pr>(
+        http_client,
+        &url,
+        &query_string,
+        1,
+        100,
+        None,
+        use_auth,
+    )
+    .map(|response| response.items)
+}
+
+pub fn get_merged_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/search/issues");
+    let query_string =
+        build_changelog_search_query(&remote.path, "is:pr is:merged", "merged", filters);
+
+    find_items_with_search_api::<GitHubPullRequest, ChangelogEntry>(
+        http_client,
+        &url,
+        &query_string,
+        filters.page,
+        filters.per_page,
+        None,
+        use_auth,
+    )
+}
+
+/// Issues closed as not planned or duplicates aren't part of a changelog, so
+/// only the completed issues are returned.
+pub fn get_closed_issues(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/search/issues");
+    let query_string = build_changelog_search_query(
+        &remote.path,
+        "is:issue is:closed reason:completed",
+        "closed",
+        filters,
+    );
+
+    find_items_with_search_api::<GitHubIssue, ChangelogEntry>(
+        http_client,
+        &url,
+        &query_string,
+        filters.page,
+        filters.per_page,
+        None,
+        use_auth,
+    )
+}
+
+/// GitHub always accepts the `draft` field when creating PRs.
+pub fn supports_native_drafts(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+) -> bool {
+    true
+}
+
+pub fn create_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    options: &CreatePrOptions,
+) -> anyhow::Result<Pr> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/pulls");
+    let request_body = build_create_pr_request_body(options);
+
+    eprintln!("Creating pull request on GitHub...");
+
+    let request = http_client
+        .post(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .json(&request_body);
+
+    let pr: Pr = request
+        .send_throttled()
+        .context("Network request failed while creating pull request on GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")
+        .map(|pr: GitHubPullRequest| pr.into())?;
+
+    // Pull requests can only be labeled via the issues API after creation
+    if !options.labels.is_empty() {
+        http_client
+            .post(&format!(
+                "{base_url}/repos/{repo_path}/issues/{}/labels",
+                pr.id
+            ))
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .header("Accept", "application/vnd.github+json")
+            .json(&serde_json::json!({ "labels": options.labels }))
+            .send_throttled()
+            .context("Network request failed while adding labels on GitHub")?
+            .with_http_status_ok()?;
+    }
+
+    Ok(pr)
+}
+
+/// Requests reviews from the `add` users, removes the review requests of the
+/// `remove` users and returns the resulting requested reviewers. GitHub drops
+/// users from the requested reviewers once they reviewed.
+pub fn update_pr_reviewers(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    add: &[String],
+    remove: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/pulls/{pr_number}/requested_reviewers");
+    let send = |request: RequestBuilder, reviewers: &[String]| -> anyhow::Result<Vec<String>> {
+        let pr: GitHubPrReviewers = request
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .header("Accept", "application/vnd.github+json")
+            .json(&serde_json::json!({ "reviewers": reviewers }))
+            .send_throttled()
+            .context("Network request failed while updating reviewers on GitHub")?
+            .with_http_status_ok()?
+            .parse_json()
+            .context("Failed to parse GitHub API response")?;
+
+        Ok(pr
+            .requested_reviewers
+            .into_iter()
+            .map(|user| user.login)
+            .collect())
+    };
+    let mut reviewers = vec![];
+
+    if !add.is_empty() {
+        reviewers = send(http_client.post(&url), add)?;
+    }
+
+    if !remove.is_empty() {
+        reviewers = send(http_client.delete(&url), remove)?;
+    }
+
+    Ok(reviewers)
+}
+
+/// Merges the PR or, with `auto`, enables auto-merge so that GitHub merges it
+/// once the required checks pass.
+pub fn merge_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    auto: bool,
+) -> anyhow::Result<PrMergeOutcome> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let pr_url = format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path);
+
+    if auto {
+        set_pr_auto_merge(http_client, base_url, &pr_url, true)?;
+
+        return Ok(PrMergeOutcome::Scheduled);
+    }
+
+    http_client
+        .put(&format!("{pr_url}/merge"))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while merging pull request on GitHub")?
+        .with_http_status_ok()?;
+
+    Ok(PrMergeOutcome::Merged)
+}
+
+pub fn cancel_pr_auto_merge(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let pr_url = format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path);
+
+    set_pr_auto_merge(http_client, base_url, &pr_url, false)
+}
+
+/// Gets whether auto-merge is enabled for the PR.
+pub fn get_pr_auto_merge(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Option<bool>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let pr: GitHubPrAutoMerge = get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path),
+        use_auth,
+    )?;
+
+    Ok(Some(pr.auto_merge.is_some()))
+}
+
+/// GitHub doesn't expose approval requirements on pull requests; they are part
+/// of the branch protection rules.
+pub fn get_pr_approvals(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_number: u32,
+    _use_auth: bool,
+) -> anyhow::Result<Option<PrApprovals>> {
+    Ok(None)
+}
+
+pub fn get_pr_review_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrReviewStatus> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let pr_url = format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path);
+
+    // A long-running PR may have more reviews than fit on a page and the
+    // latest verdict of a reviewer may be on the last one
+    let reviews: Vec<GitHubReview> =
+        get_json_pages(http_client, &format!("{pr_url}/reviews"), &[], use_auth)
+            .context("Failed fetching reviews from GitHub")?;
+
+    let review_requests: GitHubReviewRequests = http_client
+        .get(&format!("{pr_url}/requested_reviewers"))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while fetching review requests from GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")?;
+
+    let has_pending_requests =
+        !review_requests.users.is_empty() || !review_requests.teams.is_empty();
+    let reviews = reviews.iter().filter_map(|review| {
+        let status = get_review_verdict(&review.state)?;
+
+        Some((review.user.as_ref()?.login.as_str(), status))
+    });
+
+    Ok(PrReviewStatus::from_reviews(reviews, has_pending_requests))
+}
+
+/// Gets the review status of many PRs with one GraphQL query per 100 PRs
+/// instead of two REST requests per PR. GraphQL needs a token, so without
+/// `use_auth`, `None` is returned and the caller falls back to
+/// [`get_pr_review_status`].
+pub fn get_pr_review_statuses(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_numbers: &[u32],
+    use_auth: bool,
+) -> anyhow::Result<Option<Vec<PrReviewStatus>>> {
+    if !use_auth {
+        return Ok(None);
+    }
+
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let mut statuses = Vec::with_capacity(pr_numbers.len());
+
+    for chunk in pr_numbers.chunks(REVIEW_STATUS_BATCH_SIZE) {
+        let data: GitHubReviewStatusData = send_graphql_query(
+            http_client,
+            base_url,
+            &build_review_status_query(&remote.path, chunk)?,
+            "fetching review statuses",
+        )?;
+
+        for pr_number in chunk {
+            let pr = data
+                .repository
+                .get(&format!("pr{pr_number}"))
+                .and_then(Option::as_ref)
+                .with_context(|| format!("GitHub didn't return PR {pr_number}"))?;
+
+            statuses.push(PrReviewStatus::from(pr));
+        }
+    }
+
+    Ok(Some(statuses))
+}
+
+pub fn get_pr_merge_info(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrMergeInfo> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_url = format!("{base_url}/repos/{}", remote.path);
+
+    let pr: GitHubPullRequestDetails = get_json(
+        http_client,
+        &format!("{repo_url}/pulls/{pr_number}"),
+        use_auth,
+    )?;
+    let commit_url = format!("{repo_url}/commits/{}", pr.head.sha);
+    // The check runs are a listing even though they are wrapped in an object
+    let check_runs: GitHubCheckRuns = http_client
+        .get(&format!("{commit_url}/check-runs"))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .query(&[("per_page", "100")])
+        .send_throttled()
+        .context("Network request failed while fetching check runs from GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")?;
+    let combined_status: GitHubCombinedStatus =
+        get_json(http_client, &format!("{commit_url}/status"), use_auth)?;
+
+    let check_runs_ci = check_runs.check_runs.iter().map(|run| {
+        match (run.status.as_str(), run.conclusion.as_deref()) {
+            ("completed", Some("success" | "neutral" | "skipped")) => PrCiStatus::Success,
+            ("completed", _) => PrCiStatus::Failure,
+            _ => PrCiStatus::Pending,
+        }
+    });
+    let combined_status_ci = match combined_status.state.as_str() {
+        _ if combined_status.total_count == 0 => PrCiStatus::None,
+        "success" => PrCiStatus::Success,
+        "pending" => PrCiStatus::Pending,
+        _ => PrCiStatus::Failure,
+    };
+
+    Ok(PrMergeInfo {
+        mergeable: match pr.mergeable {
+            Some(true) => PrMergeable::Mergeable,
+            Some(false) => PrMergeable::Conflicting,
+            // GitHub computes the mergeability in the background
+            None => PrMergeable::Unknown,
+        },
+        ci: check_runs_ci
+            .chain([combined_status_ci])
+            .max()
+            .unwrap_or(PrCiStatus::None),
+    })
+}
+
+pub fn get_pr_counts(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrCounts> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let pr: GitHubPullRequestDetails = get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls/{pr_number}", remote.path),
+        use_auth,
+    )?;
+
+    Ok(pr.into())
+}
+
+pub fn get_repo(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Repo> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_url = format!("{base_url}/repos/{}", remote.path);
+    let repo: GitHubRepository = get_json(http_client, &repo_url, use_auth)?;
+    let languages: HashMap<String, f64> =
+        get_json(http_client, &format!("{repo_url}/languages"), use_auth)?;
+
+    Ok(Repo {
+        name: repo.full_name,
+        description: repo.description.unwrap_or_default(),
+        url: repo.html_url,
+        default_branch: repo.default_branch,
+        topics: repo.topics,
+        languages: repo::sort_languages(languages),
+        archived: repo.archived,
+    })
+}
+
+/// The API has no filters for archived repositories and only filters the forks
+/// of organizations, so both are filtered after fetching the page.
+pub fn get_repos(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListReposFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<RepoSummary>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = match filters.owner {
+        RepoOwner::Org(org) => format!(
+            "{base_url}/orgs/{}/repos",
+            url_encoding::encode_component(org)
+        ),
+        RepoOwner::User(user) => format!(
+            "{base_url}/users/{}/repos",
+            url_encoding::encode_component(user)
+        ),
+    };
+    let response = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .query(&[("page", filters.page), ("per_page", filters.per_page)])
+        .send_throttled()
+        .context("Network request failed while fetching repositories from GitHub")?
+        .with_http_status_ok()?;
+    let has_next_page = http_client::has_next_link_header(&response);
+    let mut response = response
+        .parse_json()
+        .context("Failed to parse GitHub API response")
+        .map(|repos: Vec<GitHubRepositorySummary>| repos.into_paginated_response(has_next_page))?;
+
+    response.items.retain(|repo| filters.matches(repo));
+
+    Ok(response)
+}
+
+pub fn has_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+) -> anyhow::Result<bool> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let response = http_client
+        .get(&format!(
+            "{base_url}/repos/{}/branches/{}",
+            remote.path,
+            url_encoding::encode_component(branch)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while fetching branch from GitHub")?;
+
+    http_client::is_found(response)
+}
+
+/// Saves the changed fields. Topics can only be replaced as a whole with their
+/// own endpoint.
+pub fn edit_repo(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    edit: &RepoEdit,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_url = format!("{base_url}/repos/{}", remote.path);
+    let mut fields = serde_json::Map::new();
+
+    if let Some(change) = &edit.description {
+        fields.insert(String::from("description"), change.new.clone().into());
+    }
+
+    if let Some(change) = &edit.default_branch {
+        fields.insert(String::from("default_branch"), change.new.clone().into());
+    }
+
+    if !fields.is_empty() {
+        http_client
+            .patch(&repo_url)
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .header("Accept", "application/vnd.github+json")
+            .json(&fields)
+            .send_throttled()
+            .context("Network request failed while editing repository on GitHub")?
+            .with_http_status_ok()?;
+    }
+
+    if let Some(change) = &edit.topics {
+        http_client
+            .put(&format!("{repo_url}/topics"))
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .header("Accept", "application/vnd.github+json")
+            .json(&serde_json::json!({ "names": change.new }))
+            .send_throttled()
+            .context("Network request failed while replacing topics on GitHub")?
+            .with_http_status_ok()?;
+    }
+
+    Ok(())
+}
+
+/// The branches list doesn't contain the last commit's date or whether the
+/// branch is merged.
+pub fn get_branches(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Branch>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let branches: Vec<GitHubBranch> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/branches", remote.path),
+        &[],
+        use_auth,
+    )?;
+
+    Ok(branches.into_iter().map(Branch::from).collect())
+}
+
+pub fn get_hooks(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<Vec<Hook>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let hooks: Vec<GitHubHook> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/hooks", remote.path),
+        &[],
+        true,
+    )
+    .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(hooks.into_iter().map(Hook::from).collect())
+}
+
+/// GitHub sends a `ping` event to the webhook.
+pub fn ping_hook(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    hook_id: u64,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .post(&format!(
+            "{base_url}/repos/{}/hooks/{hook_id}/pings",
+            remote.path
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while pinging webhook on GitHub")?
+        .with_http_status_ok()
+        .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(())
+}
+
+pub fn set_hook_active(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    hook_id: u64,
+    active: bool,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .patch(&format!("{base_url}/repos/{}/hooks/{hook_id}", remote.path))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .json(&serde_json::json!({ "active": active }))
+        .send_throttled()
+        .context("Network request failed while editing webhook on GitHub")?
+        .with_http_status_ok()
+        .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(())
+}
+
+pub fn get_commit_date(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    sha: &str,
+    use_auth: bool,
+) -> anyhow::Result<Timestamp> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let commit: GitHubCommit = get_json(
+        http_client,
+        &format!("{base_url}/repos/{}/commits/{sha}", remote.path),
+        use_auth,
+    )?;
+
+    Ok(commit.commit.committer.date)
+}
+
+/// The commit is merged if `target` contains it, i.e. the commit isn't ahead of
+/// `target`.
+pub fn is_branch_merged(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    sha: &str,
+    target: &str,
+    use_auth: bool,
+) -> anyhow::Result<bool> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let comparison: GitHubComparison = get_json(
+        http_client,
+        &format!(
+            "{base_url}/repos/{}/compare/{}...{sha}",
+            remote.path,
+            url_encoding::encode_component(target)
+        ),
+        use_auth,
+    )?;
+
+    Ok(comparison.ahead_by == 0)
+}
+
+/// Uses the pulls endpoint instead of the search API since only its PRs
+/// contain the source and target branches.
+pub fn get_open_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let prs: Vec<GitHubPullRequest> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls", remote.path),
+        &[("state", "open")],
+        use_auth,
+    )?;
+
+    Ok(prs.into_iter().map(Pr::from).collect())
+}
+
+/// Lists the merged PRs from `branch` of this repository, not from forks.
+pub fn get_merged_prs_for_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let owner = remote.path.split('/').next().unwrap_or_default();
+    let prs: Vec<GitHubPullRequest> = get_json_pages(
+        http_client,
+        &format!("{base_url}/repos/{}/pulls", remote.path),
+        &[("state", "closed"), ("head", &format!("{owner}:{branch}"))],
+        use_auth,
+    )?;
+
+    Ok(prs
+        .into_iter()
+        .map(Pr::from)
+        .filter(|pr| pr.merged_at.is_some())
+        .collect())
+}
+
+/// Deletes the branch through its git ref.
+pub fn delete_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .delete(&format!(
+            "{base_url}/repos/{}/git/refs/heads/{}",
+            remote.path,
+            url_encoding::encode_path(branch)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while deleting the branch on GitHub")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+pub fn get_repo_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<RepoStatus> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo: GitHubRepository = get_json(
+        http_client,
+        &format!("{base_url}/repos/{}", remote.path),
+        true,
+    )?;
+
+    Ok(RepoStatus {
+        archived: repo.archived,
+    })
+}
+
+#[cfg(feature = "self-update")]
+pub fn get_latest_release(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Release> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = format!("{base_url}/repos/{}/releases/latest", remote.path);
+
+    http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while fetching the latest release from GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")
+        .map(|release: GitHubRelease| release.into())
+}
+
+pub fn get_pr_ref(pr_number: u32) -> String {
+    format!("pull/{pr_number}/head")
+}
+
+pub fn get_url_for_api(remote: &GitRemoteData) -> String {
+    build_api_base_url(remote)
 }
 
 pub fn get_url_for_home(remote: &GitRemoteData) -> String {
@@ -238,17 +1879,51 @@ pub fn get_url_for_home(remote: &GitRemoteData) -> String {
 }
 
 pub fn get_url_for_commit(remote: &GitRemoteData, commit: &str) -> String {
-    format!("{}/commit/{}", build_web_base_url(remote), commit)
+    format!(
+        "{}/commit/{}",
+        build_web_base_url(remote),
+        url_encoding::encode_component(commit)
+    )
 }
 
 pub fn get_url_for_issue(remote: &GitRemoteData, issue_number: u32) -> String {
     format!("{}/issues/{}", build_web_base_url(remote), issue_number)
 }
 
+pub fn get_url_for_issue_comment(
+    remote: &GitRemoteData,
+    issue_number: u32,
+    comment_id: u64,
+) -> String {
+    format!(
+        "{}#issuecomment-{comment_id}",
+        get_url_for_issue(remote, issue_number)
+    )
+}
+
 pub fn get_url_for_issues(remote: &GitRemoteData) -> String {
     format!("{}/issues", build_web_base_url(remote))
 }
 
+/// Gets the URL of the issues page filtered by the dates with the search
+/// syntax, e.g. `https://github.com/owner/repo/issues?q=is%3Aissue%20...`.
+pub fn get_url_for_issue_search(
+    remote: &GitRemoteData,
+    created: &DateRange,
+    closed: &DateRange,
+) -> String {
+    let mut query_string = String::from("is:issue");
+
+    push_date_qualifier(&mut query_string, "created", created);
+    push_date_qualifier(&mut query_string, "closed", closed);
+
+    format!(
+        "{}/issues?q={}",
+        build_web_base_url(remote),
+        url_encoding::encode_component(&query_string)
+    )
+}
+
 pub fn get_url_for_issue_creation(remote: &GitRemoteData) -> String {
     format!("{}/issues/new", build_web_base_url(remote))
 }
@@ -263,6 +1938,8 @@ pub fn get_url_for_pr_creation(
     source_branch: &str,
 ) -> String {
     let base_url = build_web_base_url(remote);
+    let target_branch = url_encoding::encode_path(target_branch);
+    let source_branch = url_encoding::encode_path(source_branch);
 
     format!("{base_url}/compare/{target_branch}...{source_branch}?expand=1")
 }
@@ -271,6 +1948,14 @@ pub fn get_url_for_prs(remote: &GitRemoteData) -> String {
     format!("{}/pulls", build_web_base_url(remote))
 }
 
+pub fn get_url_for_labels(remote: &GitRemoteData) -> String {
+    format!("{}/labels", build_web_base_url(remote))
+}
+
+pub fn get_url_for_milestones(remote: &GitRemoteData) -> String {
+    format!("{}/milestones", build_web_base_url(remote))
+}
+
 pub fn get_url_for_releases(remote: &GitRemoteData) -> String {
     format!("{}/releases", build_web_base_url(remote))
 }
@@ -282,7 +1967,12 @@ pub fn get_url_for_path(
     line_number: Option<u32>,
 ) -> String {
     let base = build_web_base_url(remote);
-    let mut url = format!("{}/blob/{}/{}", base, commit, path);
+    let mut url = format!(
+        "{}/blob/{}/{}",
+        base,
+        url_encoding::encode_component(commit),
+        url_encoding::encode_path(path)
+    );
 
     if let Some(line) = line_number {
         url.push_str(&format!("#L{}", line));
@@ -291,46 +1981,330 @@ pub fn get_url_for_path(
     url
 }
 
+pub fn get_pr_timeline_events(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    options: &PrTimelineOptions,
+    use_auth: bool,
+) -> anyhow::Result<Vec<PrTimelineEvent>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let pr: GitHubComment = get_json(
+        http_client,
+        &format!("{base_url}/repos/{repo_path}/pulls/{pr_number}"),
+        use_auth,
+    )?;
+    let mut events = vec![pr.into_timeline_event(PrTimelineEventKind::Description)];
+
+    if options.comments {
+        let comments: Vec<GitHubComment> = get_json_pages(
+            http_client,
+            &format!("{base_url}/repos/{repo_path}/issues/{pr_number}/comments"),
+            &[],
+            use_auth,
+        )?;
+
+        events.extend(
+            comments
+                .into_iter()
+                .map(|comment| comment.into_timeline_event(PrTimelineEventKind::Comment)),
+        );
+    }
+
+    if options.reviews {
+        let reviews: Vec<GitHubReview> = get_json_pages(
+            http_client,
+            &format!("{base_url}/repos/{repo_path}/pulls/{pr_number}/reviews"),
+            &[],
+            use_auth,
+        )?;
+
+        events.extend(reviews.into_iter().filter_map(|review| {
+            let kind = match review.state.as_str() {
+                "APPROVED" => PrTimelineEventKind::Approved,
+                "CHANGES_REQUESTED" => PrTimelineEventKind::ChangesRequested,
+                "COMMENTED" => PrTimelineEventKind::Reviewed,
+                "DISMISSED" => PrTimelineEventKind::Dismissed,
+                // Pending reviews haven't been submitted yet
+                _ => return None,
+            };
+
+            Some(PrTimelineEvent {
+                kind,
+                author: get_login(review.user.as_ref()),
+                created_at: review.submitted_at?,
+                body: review.body.unwrap_or_default(),
+            })
+        }));
+    }
+
+    Ok(events)
+}
+
+/// Only the GraphQL API exposes the issues closed by a pull request and it
+/// requires authentication. So we only rely on the closing keywords in the
+/// description, which the caller parses.
+pub fn get_pr_closing_issues(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_number: u32,
+    _use_auth: bool,
+) -> anyhow::Result<Vec<u32>> {
+    Ok(vec![])
+}
+
 // =============================================================================
 // Private Helpers
 // =============================================================================
 
-fn build_api_base_url(remote: &GitRemoteData) -> String {
-    let (host, port) = (&remote.host, remote.port);
+/// Builds the body of the request that creates a PR. GitHub lets maintainers
+/// push to the branch of a PR by default.
+fn build_create_pr_request_body(options: &CreatePrOptions) -> serde_json::Value {
+    let mut request_body = serde_json::json!({
+        "title": options.title,
+        "head": options.source_branch,
+        "base": options.target_branch,
+        "body": options.body,
+        "draft": options.draft,
+    });
+
+    if options.no_maintainer_edit {
+        request_body["maintainer_can_modify"] = false.into();
+    }
+
+    request_body
+}
+
+fn get_identity(http_client: &HttpClient, base_url: &str) -> anyhow::Result<Identity> {
+    identity::get_identity(base_url, AUTH_TOKEN, || {
+        http_client
+            .get(&format!("{base_url}/user"))
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .header("Accept", "application/vnd.github+json")
+            .send_throttled()
+            .context("Network request failed while fetching the authenticated user from GitHub")?
+            .with_http_status_ok()?
+            .parse_json()
+            .context("Failed to parse GitHub API response")
+            .map(|user: GitHubAuthenticatedUser| user.into())
+    })
+}
+
+/// Auto-merge is only available via the GraphQL API, which needs the node id of
+/// the PR instead of its number.
+fn set_pr_auto_merge(
+    http_client: &HttpClient,
+    base_url: &str,
+    pr_url: &str,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let pr: GitHubNodeId = http_client
+        .get(pr_url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while fetching pull request from GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")?;
+    let mutation = if enabled {
+        "enablePullRequestAutoMerge"
+    } else {
+        "disablePullRequestAutoMerge"
+    };
+    let request_body = serde_json::json!({
+        "query": format!(
+            "mutation($prId: ID!) {{ {mutation}(input: {{pullRequestId: $prId}}) {{ pullRequest {{ number }} }} }}"
+        ),
+        "variables": { "prId": pr.node_id },
+    });
+
+    send_graphql_mutation(http_client, base_url, &request_body, "changing auto-merge")
+}
+
+/// Sends a GraphQL mutation. `action` describes it for the error message, e.g.
+/// "pinning issue".
+fn send_graphql_mutation(
+    http_client: &HttpClient,
+    base_url: &str,
+    request_body: &serde_json::Value,
+    action: &str,
+) -> anyhow::Result<()> {
+    send_graphql_query::<IgnoredAny>(http_client, base_url, request_body, action).map(|_| ())
+}
+
+/// Sends a GraphQL query or mutation and returns its data. `action` describes
+/// it for the error message, e.g. "pinning issue".
+fn send_graphql_query<T: DeserializeOwned>(
+    http_client: &HttpClient,
+    base_url: &str,
+    request_body: &serde_json::Value,
+    action: &str,
+) -> anyhow::Result<T> {
+    let response: GitHubGraphQlResponse<T> = http_client
+        .post(&build_graphql_url(base_url))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(request_body)
+        .send_throttled()
+        .with_context(|| format!("Network request failed while {action} on GitHub"))?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub GraphQL API response")?;
 
-    if host == "github.com" {
-        "https://api.github.com".to_string()
+    if let Some(errors) = response.errors {
+        let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+
+        anyhow::bail!("GitHub GraphQL API error: {}", messages.join("; "));
+    }
+
+    response
+        .data
+        .context("GitHub GraphQL API response without data")
+}
+
+/// Builds the query for the review status of the PRs. Each PR is fetched with
+/// the alias `pr<NUMBER>` since a field can't be queried twice otherwise.
+fn build_review_status_query(
+    repo_path: &str,
+    pr_numbers: &[u32],
+) -> anyhow::Result<serde_json::Value> {
+    let (owner, name) = repo_path
+        .split_once('/')
+        .with_context(|| format!("Invalid GitHub repository path '{repo_path}'"))?;
+    let prs: Vec<String> = pr_numbers
+        .iter()
+        .map(|number| format!("pr{number}: pullRequest(number: {number}) {{ ...ReviewStatus }}"))
+        .collect();
+
+    Ok(serde_json::json!({
+        "query": format!(
+            "query($owner: String!, $name: String!) {{ repository(owner: $owner, name: $name) {{ {} }} }} \
+            fragment ReviewStatus on PullRequest {{ latestReviews(first: 100) {{ nodes {{ state author {{ login }} }} }} reviewRequests {{ totalCount }} }}",
+            prs.join(" ")
+        ),
+        "variables": { "owner": owner, "name": name },
+    }))
+}
+
+/// The verdict of a review of the REST or GraphQL API. Comments don't change
+/// the verdict of a reviewer, so they are `None`.
+fn get_review_verdict(state: &str) -> Option<PrReviewStatus> {
+    match state {
+        "APPROVED" => Some(PrReviewStatus::Approved),
+        "CHANGES_REQUESTED" => Some(PrReviewStatus::ChangesRequested),
+        // A dismissed review no longer counts as a verdict
+        "DISMISSED" => Some(PrReviewStatus::None),
+        _ => None,
+    }
+}
+
+/// Adds or removes assignees with the assignees endpoint, which returns the
+/// updated issue.
+fn update_issue_assignees(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+    add: bool,
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let repo_path = &remote.path;
+    let url = format!("{base_url}/repos/{repo_path}/issues/{issue_number}/assignees");
+    let request = if add {
+        http_client.post(&url)
     } else {
-        match port {
-            Some(p) => format!("https://{host}:{p}/api/v3"),
-            None => format!("https://{host}/api/v3"),
-        }
+        http_client.delete(&url)
+    };
+    let issue: GitHubIssueAssignees = request
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .json(&serde_json::json!({ "assignees": assignees }))
+        .send_throttled()
+        .context("Network request failed while updating assignees on GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")?;
+
+    Ok(issue.assignees.into_iter().map(|user| user.login).collect())
+}
+
+/// Sends the labels to the labels endpoint of an issue, which answers with the
+/// resulting labels.
+fn send_issue_labels(request: RequestBuilder, labels: &[String]) -> anyhow::Result<Vec<String>> {
+    let labels: Vec<GitHubLabel> = request
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .json(&serde_json::json!({ "labels": labels }))
+        .send_throttled()
+        .context("Network request failed while updating labels on GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")?;
+
+    Ok(labels.into_iter().map(|l| l.name).collect())
+}
+
+fn build_api_base_url(remote: &GitRemoteData) -> String {
+    match &remote.api_base_url {
+        Some(url) => url.clone(),
+        None if remote.host == "github.com" => "https://api.github.com".to_string(),
+        None => format!("{}/api/v3", remote.web_base_url),
+    }
+}
+
+/// The GraphQL endpoint isn't below the REST base URL, e.g.
+/// https://api.github.com/graphql or https://ghe.internal/api/graphql.
+fn build_graphql_url(api_base_url: &str) -> String {
+    match api_base_url.strip_suffix("/api/v3") {
+        Some(web_base_url) => format!("{web_base_url}/api/graphql"),
+        None => format!("{api_base_url}/graphql"),
     }
 }
 
 fn build_web_base_url(remote: &GitRemoteData) -> String {
-    let host = &remote.host;
-    let path = &remote.path;
+    format!("{}/{}", remote.web_base_url, remote.path)
+}
 
-    match remote.port {
-        Some(port) => format!("https://{host}:{port}/{path}"),
-        None => format!("https://{host}/{path}"),
-    }
+/// Deleted accounts are returned as `null` users.
+fn get_login(user: Option<&GitHubUser>) -> String {
+    user.map_or_else(|| DELETED_USER.to_string(), |user| user.login.clone())
 }
 
 /// https://docs.github.com/en/search-github/searching-on-github/searching-issues-and-pull-requests
 fn build_issue_search_query(repo_path: &str, filters: &ListIssueFilters) -> String {
     let mut query_string = match filters.query {
-        Some(query) => format!("{query} in:title in:body repo:{repo_path} is:issue"),
-        None => format!("repo:{repo_path} is:issue"),
+        Some(query) => format!("{query} in:title in:body repo:{repo_path}"),
+        None => format!("repo:{repo_path}"),
     };
 
+    if !filters.include_prs {
+        query_string.push_str(" is:issue");
+    }
+
     match filters.state {
         IssueState::Open => query_string.push_str(" is:open"),
         IssueState::Closed => query_string.push_str(" is:closed"),
         IssueState::All => {}
     }
 
+    match filters.closed_as {
+        Some(IssueStateReason::Completed) => query_string.push_str(" reason:completed"),
+        Some(IssueStateReason::NotPlanned) => query_string.push_str(r#" reason:"not planned""#),
+        Some(IssueStateReason::Duplicate) => query_string.push_str(" reason:duplicate"),
+        Some(IssueStateReason::Reopened) => query_string.push_str(" reason:reopened"),
+        None => {}
+    }
+
     if let Some(assignee) = filters.assignee {
         query_string.push_str(" assignee:");
         query_string.push_str(assignee);
@@ -341,11 +2315,75 @@ fn build_issue_search_query(repo_path: &str, filters: &ListIssueFilters) -> Stri
         query_string.push_str(author);
     }
 
-    for label in filters.labels {
+    for label in &filters.labels.include {
         query_string.push_str(" label:");
         query_string.push_str(label);
     }
 
+    for label in &filters.labels.exclude {
+        query_string.push_str(" -label:");
+        query_string.push_str(label);
+    }
+
+    if filters.labels.unlabeled {
+        query_string.push_str(" no:label");
+    }
+
+    push_date_qualifier(&mut query_string, "created", &filters.created);
+    push_date_qualifier(&mut query_string, "closed", &filters.closed);
+    push_date_qualifier(&mut query_string, "updated", &filters.updated);
+
+    query_string
+}
+
+/// Appends a date qualifier like `created:>=2024-03-01T00:00:00Z` for the
+/// range. The end is given as its last second since `start..end` ranges
+/// include their end.
+fn push_date_qualifier(query_string: &mut String, qualifier: &str, range: &DateRange) {
+    let condition = match (range.after, range.get_inclusive_before()) {
+        (Some(after), Some(before)) => {
+            format!(
+                "{}..{}",
+                date::format_date(after),
+                date::format_date(before)
+            )
+        }
+        (Some(after), None) => format!(">={}", date::format_date(after)),
+        (None, Some(before)) => format!("<={}", date::format_date(before)),
+        (None, None) => return,
+    };
+
+    query_string.push_str(&format!(" {qualifier}:{condition}"));
+}
+
+/// The search API can only sort by reactions. Other `--sort` values are
+/// applied to the fetched issues.
+fn get_issue_search_sort(filters: &ListIssueFilters) -> Option<&'static str> {
+    match filters.sort {
+        Some(IssueSort::Reactions) => Some("reactions"),
+        Some(IssueSort::Closed) | None => None,
+    }
+}
+
+/// `date_qualifier` is the search qualifier that `filters.since` applies to,
+/// e.g. `merged`.
+/// https://docs.github.com/en/search-github/searching-on-github/searching-issues-and-pull-requests
+fn build_changelog_search_query(
+    repo_path: &str,
+    qualifiers: &str,
+    date_qualifier: &str,
+    filters: &ChangelogFilters,
+) -> String {
+    let mut query_string = format!("repo:{repo_path} {qualifiers}");
+
+    if let Some(milestone) = filters.milestone {
+        push_milestone_qualifier(&mut query_string, milestone);
+    }
+
+    if let Some(since) = filters.since {
+        query_string.push_str(&format!(" {date_qualifier}:>={since}"));
+    }
+
     query_string
 }
 
@@ -368,11 +2406,20 @@ fn build_pr_search_query(repo_path: &str, filters: &ListPrsFilters) -> String {
         query_string.push_str(author);
     }
 
-    for label in filters.labels {
+    for label in &filters.labels.include {
         query_string.push_str(" label:");
         query_string.push_str(label);
     }
 
+    for label in &filters.labels.exclude {
+        query_string.push_str(" -label:");
+        query_string.push_str(label);
+    }
+
+    if let Some(milestone) = filters.milestone {
+        push_milestone_qualifier(&mut query_string, milestone);
+    }
+
     if filters.draft {
         query_string.push_str(" draft:true");
     }
@@ -380,19 +2427,78 @@ fn build_pr_search_query(repo_path: &str, filters: &ListPrsFilters) -> String {
     query_string
 }
 
+/// Milestone titles may contain spaces, so the title is quoted.
+fn push_milestone_qualifier(query_string: &mut String, milestone: &str) {
+    query_string.push_str(&format!(r#" milestone:"{milestone}""#));
+}
+
+/// Fetches a single object. Listings are fetched with `get_json_pages`.
+fn get_json<T: DeserializeOwned>(
+    http_client: &HttpClient,
+    url: &str,
+    use_auth: bool,
+) -> anyhow::Result<T> {
+    http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .header("Accept", "application/vnd.github+json")
+        .send_throttled()
+        .context("Network request failed while fetching data from GitHub")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitHub API response")
+}
+
+/// Fetches every page of a listing with the most items per page.
+fn get_json_pages<T: DeserializeOwned>(
+    http_client: &HttpClient,
+    url: &str,
+    query: &[(&str, &str)],
+    use_auth: bool,
+) -> anyhow::Result<Vec<T>> {
+    let mut items = vec![];
+
+    for page in 1.. {
+        let response = http_client
+            .get(url)
+            .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+            .header("Accept", "application/vnd.github+json")
+            .query(query)
+            .query(&[("page", page), ("per_page", 100)])
+            .send_throttled()
+            .context("Network request failed while fetching data from GitHub")?
+            .with_http_status_ok()?;
+        let has_next_page = http_client::has_next_link_header(&response);
+        let page_items: Vec<T> = response
+            .parse_json()
+            .context("Failed to parse GitHub API response")?;
+
+        items.extend(page_items);
+
+        if !has_next_page {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+/// `sort` is the field to sort the results by in descending order instead of
+/// the best match, e.g. `reactions`.
 fn find_items_with_search_api<T, U>(
     http_client: &HttpClient,
     url: &str,
     query_string: &str,
     page: u32,
     per_page: u32,
+    sort: Option<&str>,
     use_auth: bool,
 ) -> anyhow::Result<PaginatedResponse<U>>
 where
     T: DeserializeOwned,
     U: From<T>,
 {
-    let request = http_client
+    let mut request = http_client
         .get(url)
         .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
         .header("Accept", "application/vnd.github+json")
@@ -400,15 +2506,553 @@ where
         .query(&[("page", page)])
         .query(&[("per_page", per_page)]);
 
+    if let Some(sort) = sort {
+        request = request.query(&[("sort", sort), ("order", "desc")]);
+    }
+
     let response = request
-        .send()
+        .send_throttled()
         .context("Failed to fetch items from GitHub Search API")?
         .with_http_status_ok()?;
 
     let has_next_page = http_client::has_next_link_header(&response);
 
     response
-        .json()
+        .parse_json()
         .context("Failed to parse GitHub Search API response")
         .map(|res: GitHubSearchResponse<T>| res.into_paginated_response(has_next_page))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::forge::LabelFilter;
+
+    fn create_git_remote() -> GitRemoteData {
+        GitRemoteData {
+            host: "github.com".to_string(),
+            path: "user/repo".to_string(),
+            port: None,
+            web_base_url: "https://github.com".to_string(),
+            api_base_url: None,
+        }
+    }
+
+    #[test]
+    fn test_build_base_urls() {
+        let mut remote = create_git_remote();
+
+        assert_eq!(build_api_base_url(&remote), "https://api.github.com");
+        assert_eq!(build_web_base_url(&remote), "https://github.com/user/repo");
+
+        // E.g. self-hosted over plain http with the SSH port in the remote URL
+        remote.host = "git.internal".to_string();
+        remote.port = Some(2222);
+        remote.web_base_url = "http://git.internal:3000".to_string();
+
+        assert_eq!(
+            build_api_base_url(&remote),
+            "http://git.internal:3000/api/v3"
+        );
+        assert_eq!(
+            build_web_base_url(&remote),
+            "http://git.internal:3000/user/repo"
+        );
+
+        remote.api_base_url = Some("https://api.git.internal".to_string());
+
+        assert_eq!(build_api_base_url(&remote), "https://api.git.internal");
+    }
+
+    #[test]
+    fn test_get_url_for_management_pages() {
+        let remote = create_git_remote();
+
+        assert_eq!(
+            get_url_for_labels(&remote),
+            "https://github.com/user/repo/labels"
+        );
+        assert_eq!(
+            get_url_for_milestones(&remote),
+            "https://github.com/user/repo/milestones"
+        );
+        assert_eq!(
+            get_url_for_releases(&remote),
+            "https://github.com/user/repo/releases"
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_pr_creation_encodes_branches() {
+        let url = get_url_for_pr_creation(&create_git_remote(), "main", "fix/#42");
+
+        assert_eq!(
+            url,
+            "https://github.com/user/repo/compare/main...fix/%2342?expand=1"
+        );
+    }
+
+    #[test]
+    fn test_supports() {
+        assert_eq!(supports(Capability::DraftPrs), Support::Yes);
+        assert_eq!(supports(Capability::MergedStateFilter), Support::Yes);
+        assert!(matches!(supports(Capability::PinnedFilter), Support::No(_)));
+        assert_eq!(supports(Capability::MaintainerEdit), Support::Yes);
+        assert!(matches!(
+            supports(Capability::SquashOnMerge),
+            Support::No(_)
+        ));
+    }
+
+    #[test]
+    fn test_build_create_pr_request_body() {
+        let options = CreatePrOptions {
+            title: "Fix crash",
+            source_branch: "fix",
+            target_branch: "main",
+            body: "",
+            draft: false,
+            native_draft: true,
+            labels: &[],
+            no_maintainer_edit: false,
+            remove_source_branch: false,
+            squash_on_merge: false,
+        };
+
+        assert_eq!(
+            build_create_pr_request_body(&options),
+            serde_json::json!({
+                "title": "Fix crash",
+                "head": "fix",
+                "base": "main",
+                "body": "",
+                "draft": false,
+            })
+        );
+        assert_eq!(
+            build_create_pr_request_body(&CreatePrOptions {
+                no_maintainer_edit: true,
+                ..options
+            })["maintainer_can_modify"],
+            false
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_issue_comment() {
+        let url = get_url_for_issue_comment(&create_git_remote(), 42, 123);
+
+        assert_eq!(
+            url,
+            "https://github.com/user/repo/issues/42#issuecomment-123"
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_path_encodes_path() {
+        let url = get_url_for_path(&create_git_remote(), "docs/my file ü.md", "HEAD", Some(3));
+
+        assert_eq!(
+            url,
+            "https://github.com/user/repo/blob/HEAD/docs/my%20file%20%C3%BC.md#L3"
+        );
+    }
+
+    #[test]
+    fn test_build_graphql_url() {
+        assert_eq!(
+            build_graphql_url("https://api.github.com"),
+            "https://api.github.com/graphql"
+        );
+        assert_eq!(
+            build_graphql_url("https://ghe.internal/api/v3"),
+            "https://ghe.internal/api/graphql"
+        );
+    }
+
+    #[test]
+    fn test_build_review_status_query() {
+        let request_body = build_review_status_query("user/repo", &[1, 42]).unwrap();
+        let query = request_body["query"].as_str().unwrap();
+
+        assert!(query.contains(
+            "pr1: pullRequest(number: 1) { ...ReviewStatus } pr42: pullRequest(number: 42)"
+        ));
+        assert_eq!(
+            request_body["variables"],
+            serde_json::json!({ "owner": "user", "name": "repo" })
+        );
+        assert!(build_review_status_query("repo", &[1]).is_err());
+    }
+
+    #[test]
+    fn test_review_status_from_graphql() {
+        let data: GitHubReviewStatusData = serde_json::from_value(serde_json::json!({
+            "repository": {
+                "pr1": {
+                    "latestReviews": { "nodes": [
+                        { "state": "APPROVED", "author": { "login": "bob" } },
+                        { "state": "COMMENTED", "author": { "login": "carol" } },
+                    ] },
+                    "reviewRequests": { "totalCount": 1 },
+                },
+                "pr2": {
+                    "latestReviews": { "nodes": [
+                        { "state": "APPROVED", "author": { "login": "bob" } },
+                        { "state": "CHANGES_REQUESTED", "author": null },
+                    ] },
+                    "reviewRequests": { "totalCount": 0 },
+                },
+                "pr3": {
+                    "latestReviews": { "nodes": [] },
+                    "reviewRequests": { "totalCount": 2 },
+                },
+                "pr4": null,
+            }
+        }))
+        .unwrap();
+        let status = |alias: &str| data.repository[alias].as_ref().map(PrReviewStatus::from);
+
+        assert_eq!(status("pr1"), Some(PrReviewStatus::Approved));
+        // Reviews of deleted accounts are skipped like with the REST API
+        assert_eq!(status("pr2"), Some(PrReviewStatus::Approved));
+        assert_eq!(status("pr3"), Some(PrReviewStatus::ReviewRequired));
+        assert_eq!(status("pr4"), None);
+    }
+
+    #[test]
+    fn test_build_issue_search_query_with_closed_as() {
+        let query = build_issue_search_query(
+            "user/repo",
+            &ListIssueFilters {
+                assignee: None,
+                author: None,
+                closed: DateRange::default(),
+                closed_as: Some(&IssueStateReason::NotPlanned),
+                created: DateRange::default(),
+                include_prs: false,
+                labels: LabelFilter::NONE,
+                min_reactions: None,
+                page: 1,
+                pinned: false,
+                per_page: 30,
+                query: None,
+                sort: None,
+                state: &IssueState::Closed,
+                updated: DateRange::default(),
+            },
+        );
+
+        assert_eq!(
+            query,
+            r#"repo:user/repo is:issue is:closed reason:"not planned""#
+        );
+    }
+
+    #[test]
+    fn test_build_issue_search_query_without_labels() {
+        let labels = LabelFilter {
+            unlabeled: true,
+            ..LabelFilter::default()
+        };
+        let filters = ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: &labels,
+            min_reactions: None,
+            page: 1,
+            pinned: false,
+            per_page: 30,
+            query: None,
+            sort: Some(&IssueSort::Reactions),
+            state: &IssueState::Open,
+            updated: DateRange::default(),
+        };
+
+        assert_eq!(
+            build_issue_search_query("user/repo", &filters),
+            "repo:user/repo is:issue is:open no:label"
+        );
+        assert_eq!(get_issue_search_sort(&filters), Some("reactions"));
+    }
+
+    #[test]
+    fn test_build_issue_search_query_with_dates() {
+        let march = DateRange {
+            after: Some(date::parse_date("2024-03-01").unwrap()),
+            before: Some(date::parse_date("2024-04-01").unwrap()),
+        };
+        let closed = DateRange {
+            after: None,
+            before: march.before,
+        };
+        let query = build_issue_search_query(
+            "user/repo",
+            &ListIssueFilters {
+                assignee: None,
+                author: None,
+                closed,
+                closed_as: None,
+                created: march,
+                include_prs: false,
+                labels: LabelFilter::NONE,
+                min_reactions: None,
+                page: 1,
+                pinned: false,
+                per_page: 30,
+                query: None,
+                sort: None,
+                state: &IssueState::All,
+                updated: DateRange::default(),
+            },
+        );
+
+        assert_eq!(
+            query,
+            "repo:user/repo is:issue created:2024-03-01T00:00:00Z..2024-03-31T23:59:59Z \
+            closed:<=2024-03-31T23:59:59Z"
+        );
+        assert_eq!(
+            get_url_for_issue_search(&create_git_remote(), &DateRange::default(), &closed),
+            "https://github.com/user/repo/issues?q=is%3Aissue%20closed%3A%3C%3D2024-03-31T23%3A59%3A59Z"
+        );
+    }
+
+    #[test]
+    fn test_build_issue_search_query_with_updated_after() {
+        let filters = ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: LabelFilter::NONE,
+            min_reactions: None,
+            page: 1,
+            pinned: false,
+            per_page: 30,
+            query: None,
+            sort: None,
+            state: &IssueState::All,
+            updated: DateRange {
+                after: Some(date::parse_date("2024-05-01").unwrap()),
+                before: None,
+            },
+        };
+
+        assert_eq!(
+            build_issue_search_query("user/repo", &filters),
+            "repo:user/repo is:issue updated:>=2024-05-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_build_search_queries_states() {
+        for (state, expected) in [
+            (IssueState::Open, "repo:user/repo is:issue is:open"),
+            (IssueState::Closed, "repo:user/repo is:issue is:closed"),
+            (IssueState::All, "repo:user/repo is:issue"),
+        ] {
+            let query = build_issue_search_query(
+                "user/repo",
+                &ListIssueFilters {
+                    assignee: None,
+                    author: None,
+                    closed: DateRange::default(),
+                    closed_as: None,
+                    created: DateRange::default(),
+                    include_prs: false,
+                    labels: LabelFilter::NONE,
+                    min_reactions: None,
+                    page: 1,
+                    pinned: false,
+                    per_page: 30,
+                    query: None,
+                    sort: None,
+                    state: &state,
+                    updated: DateRange::default(),
+                },
+            );
+
+            assert_eq!(query, expected);
+        }
+
+        let query = build_issue_search_query(
+            "user/repo",
+            &ListIssueFilters {
+                assignee: None,
+                author: None,
+                closed: DateRange::default(),
+                closed_as: None,
+                created: DateRange::default(),
+                include_prs: true,
+                labels: LabelFilter::NONE,
+                min_reactions: None,
+                page: 1,
+                pinned: false,
+                per_page: 30,
+                query: None,
+                sort: None,
+                state: &IssueState::Open,
+                updated: DateRange::default(),
+            },
+        );
+
+        assert_eq!(query, "repo:user/repo is:open");
+
+        for (state, expected) in [
+            (PrState::Open, "repo:user/repo is:pr is:open"),
+            (
+                PrState::Closed,
+                "repo:user/repo is:pr is:closed is:unmerged",
+            ),
+            (PrState::Merged, "repo:user/repo is:pr is:merged"),
+            (PrState::All, "repo:user/repo is:pr"),
+        ] {
+            let query = build_pr_search_query(
+                "user/repo",
+                &ListPrsFilters {
+                    author: None,
+                    labels: LabelFilter::NONE,
+                    milestone: None,
+                    page: 1,
+                    per_page: 30,
+                    query: None,
+                    state: &state,
+                    draft: false,
+                    review: None,
+                    all_pages: false,
+                    limit: None,
+                },
+            );
+
+            assert_eq!(query, expected);
+        }
+
+        let query = build_pr_search_query(
+            "user/repo",
+            &ListPrsFilters {
+                author: None,
+                labels: LabelFilter::NONE,
+                milestone: Some("Release 2.0"),
+                page: 1,
+                per_page: 30,
+                query: None,
+                state: &PrState::Merged,
+                draft: false,
+                review: None,
+                all_pages: false,
+                limit: None,
+            },
+        );
+
+        assert_eq!(
+            query,
+            r#"repo:user/repo is:pr is:merged milestone:"Release 2.0""#
+        );
+
+        let labels = LabelFilter::parse(&[String::from("bug"), String::from("-triaged")]);
+        let query = build_pr_search_query(
+            "user/repo",
+            &ListPrsFilters {
+                author: None,
+                labels: &labels,
+                milestone: None,
+                page: 1,
+                per_page: 30,
+                query: None,
+                state: &PrState::Open,
+                draft: false,
+                review: None,
+                all_pages: false,
+                limit: None,
+            },
+        );
+
+        assert_eq!(
+            query,
+            "repo:user/repo is:pr is:open label:bug -label:triaged"
+        );
+    }
+
+    #[test]
+    fn test_pr_counts_include_review_comments() {
+        let pr: GitHubPullRequestDetails = serde_json::from_value(serde_json::json!({
+            "mergeable": true,
+            "head": { "sha": "abc123" },
+            "comments": 2,
+            "review_comments": 5,
+            "commits": 3,
+            "changed_files": 4,
+        }))
+        .unwrap();
+
+        assert_eq!(
+            PrCounts::from(pr),
+            PrCounts {
+                comments: Some(7),
+                commits: Some(3),
+                changed_files: Some(4),
+            }
+        );
+    }
+
+    #[test]
+    fn test_hooks_fixture_redacts_secrets() {
+        let hooks: Vec<GitHubHook> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/github/hooks.json"
+        ))
+        .unwrap();
+        let hooks: Vec<Hook> = hooks.into_iter().map(Hook::from).collect();
+
+        assert_eq!(
+            hooks,
+            vec![
+                Hook {
+                    id: 101,
+                    url: String::from("https://ci.example.com/hooks/***?token=***&project=app"),
+                    events: vec![String::from("push"), String::from("pull_request")],
+                    active: true,
+                    last_delivery: Some(String::from("200")),
+                },
+                Hook {
+                    id: 102,
+                    url: String::from("https://***@chat.example.com/notify"),
+                    events: vec![String::from("release")],
+                    active: false,
+                    last_delivery: Some(String::from("unused")),
+                },
+            ]
+        );
+        assert!(!serde_json::to_string(&hooks).unwrap().contains("********"));
+    }
+
+    #[test]
+    fn test_created_items_match_golden_files() {
+        let issues: Vec<GitHubIssue> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/github/issue.json"
+        ))
+        .unwrap();
+        let prs: Vec<GitHubPullRequest> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/github/pr.json"
+        ))
+        .unwrap();
+        let issue = Issue::from(issues.into_iter().next().unwrap());
+        let pr = Pr::from(prs.into_iter().next().unwrap());
+
+        // The output of `issue create --format json` and `pr create --format json`
+        assert_eq!(
+            serde_json::to_string_pretty(&issue).unwrap() + "\n",
+            include_str!("../../../tests/golden/github/issue.json")
+        );
+        assert_eq!(
+            serde_json::to_string_pretty(&pr).unwrap() + "\n",
+            include_str!("../../../tests/golden/github/pr.json")
+        );
+    }
+}
@@ -1,24 +1,80 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+};
+
 use anyhow::Context;
-use serde::Deserialize;
+use reqwest::blocking::RequestBuilder;
+use serde::{Deserialize, de::DeserializeOwned};
 
 use crate::{
     cli::{
-        forge::http_client::{
-            self, HttpClient, IntoPaginatedResponse, PaginatedResponse, WithAuth, WithHttpStatusOk,
+        branch::Branch,
+        changelog::{ChangelogEntry, ChangelogEntryKind, ChangelogFilters},
+        forge::{
+            Capability, Support,
+            http_client::{
+                self, HttpClient, IntoPaginatedResponse, PaginatedResponse, ParseJson,
+                SendThrottled, WithAuth, WithHttpStatusOk,
+            },
+            identity::{self, Identity},
+            precheck::RepoStatus,
+            url_encoding,
+        },
+        hook::{self, Hook},
+        issue::{
+            CreateIssueOptions, ExportedIssue, Issue, IssueComment, IssueKind, IssueSort,
+            IssueState, ListIssueFilters,
         },
-        issue::{CreateIssueOptions, Issue, IssueState, ListIssueFilters},
-        pr::{CreatePrOptions, ListPrsFilters, Pr, PrState},
+        pr::{
+            CreatePrOptions, ListPrsFilters, Pr, PrApprovals, PrBranch, PrCiStatus, PrCounts,
+            PrMergeInfo, PrMergeOutcome, PrMergeable, PrReviewStatus, PrState, PrTimelineEvent,
+            PrTimelineEventKind, PrTimelineOptions,
+        },
+        repo::{self, ListReposFilters, Repo, RepoEdit, RepoOwner, RepoSummary},
     },
+    date::{self, DateRange, Timestamp},
     git::GitRemoteData,
 };
 
 const AUTH_TOKEN: &str = "GIT_FORGE_GITLAB_TOKEN";
 const AUTH_SCHEME: &str = "Bearer";
+/// Title prefixes GitLab uses to mark merge requests as drafts.
+const DRAFT_TITLE_PREFIXES: [&str; 5] = ["draft:", "[draft]", "(draft)", "wip:", "[wip]"];
+const HOOKS_PERMISSION_HINT: &str =
+    "Webhooks need a token with the api scope and the Maintainer role in the project";
+/// The event that `hook ping` tests a webhook with if it's subscribed to it.
+const PING_EVENT: &str = "push";
 
 // =============================================================================
 // Domain Types
 // =============================================================================
 
+impl IssueState {
+    /// GitLab calls open issues `opened`, so queries must use this instead of
+    /// `Display`.
+    fn as_gitlab_str(&self) -> &'static str {
+        match self {
+            IssueState::Open => "opened",
+            IssueState::Closed => "closed",
+            IssueState::All => "all",
+        }
+    }
+}
+
+impl PrState {
+    /// GitLab calls open merge requests `opened`, so queries must use this
+    /// instead of `Display`.
+    fn as_gitlab_str(&self) -> &'static str {
+        match self {
+            PrState::Open => "opened",
+            PrState::Closed => "closed",
+            PrState::Merged => "merged",
+            PrState::All => "all",
+        }
+    }
+}
+
 /// GitLab API response for issues.
 /// https://docs.gitlab.com/api/issues/#list-project-issues
 #[derive(Debug, Deserialize)]
@@ -29,34 +85,168 @@ struct GitLabIssue {
     labels: Vec<String>,
     author: GitLabUser,
     web_url: String,
+    description: Option<String>,
+    created_at: Timestamp,
+    updated_at: Option<Timestamp>,
+    closed_at: Option<Timestamp>,
+    upvotes: Option<u32>,
 }
 
-impl From<GitLabIssue> for Issue {
-    fn from(issue: GitLabIssue) -> Self {
-        let state = if issue.state == "opened" {
+impl GitLabIssue {
+    fn get_state(&self) -> IssueState {
+        if self.state == IssueState::Open.as_gitlab_str() {
             IssueState::Open
-        } else if issue.state == "closed" {
+        } else if self.state == IssueState::Closed.as_gitlab_str()
+            || self.state == PrState::Merged.as_gitlab_str()
+        {
             IssueState::Closed
         } else {
             IssueState::All
-        };
+        }
+    }
+}
 
+impl From<GitLabIssue> for Issue {
+    fn from(issue: GitLabIssue) -> Self {
         Issue {
             id: issue.iid,
+            state: issue.get_state(),
             author: issue.author.username,
             labels: issue.labels,
-            state,
+            state_reason: None,
+            title: issue.title,
+            pinned: None,
+            url: issue.web_url,
+            created_at: Some(issue.created_at),
+            closed_at: issue.closed_at,
+            reactions: issue.upvotes,
+            kind: None,
+        }
+    }
+}
+
+impl From<GitLabIssue> for ChangelogEntry {
+    fn from(issue: GitLabIssue) -> Self {
+        ChangelogEntry {
+            kind: ChangelogEntryKind::Issue,
+            id: issue.iid,
+            title: issue.title,
+            author: issue.author.username,
+            url: issue.web_url,
+            labels: issue.labels,
+            closed_at: issue.closed_at,
+        }
+    }
+}
+
+impl From<GitLabIssue> for ExportedIssue {
+    fn from(issue: GitLabIssue) -> Self {
+        ExportedIssue {
+            id: issue.iid,
+            state: issue.get_state(),
             title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            author: issue.author.username,
+            created_at: issue.created_at.to_string(),
             url: issue.web_url,
+            labels: issue.labels,
+            comments: vec![],
         }
     }
 }
 
+/// GitLab API response for a single merge request with the fields for the
+/// description.
+/// https://docs.gitlab.com/api/merge_requests/#get-single-mr
+#[derive(Debug, Deserialize)]
+struct GitLabMergeRequestDescription {
+    author: GitLabUser,
+    description: Option<String>,
+    created_at: Timestamp,
+}
+
+/// GitLab API response for the issues that a merge request closes.
+/// https://docs.gitlab.com/api/merge_requests/#list-issues-that-close-on-merge
+#[derive(Debug, Deserialize)]
+struct GitLabClosedIssue {
+    iid: u32,
+}
+
+/// GitLab API response for notes. System notes record events like approvals.
+/// https://docs.gitlab.com/api/notes/#list-all-merge-request-notes
+#[derive(Debug, Deserialize)]
+struct GitLabNote {
+    id: u64,
+    author: GitLabUser,
+    body: String,
+    created_at: Timestamp,
+    system: bool,
+}
+
+/// https://docs.gitlab.com/api/labels/
+#[derive(Debug, Deserialize)]
+struct GitLabLabel {
+    name: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GitLabUser {
     username: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitLabMilestone {
+    title: String,
+}
+
+/// GitLab API response for users when only the id is needed. Assigning
+/// needs the ids instead of the usernames.
+#[derive(Debug, Deserialize)]
+struct GitLabUserId {
+    id: u64,
+}
+
+/// GitLab API response for the authenticated user.
+/// https://docs.gitlab.com/api/users/#retrieve-the-current-user
+#[derive(Debug, Deserialize)]
+struct GitLabAuthenticatedUser {
+    id: u64,
+    username: String,
+}
+
+impl From<GitLabAuthenticatedUser> for Identity {
+    fn from(user: GitLabAuthenticatedUser) -> Self {
+        Identity {
+            username: user.username,
+            id: user.id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabMember {
+    id: u64,
+    username: String,
+}
+
+/// The assignees of a GitLab API issue response.
+#[derive(Debug, Deserialize)]
+struct GitLabIssueAssignees {
+    assignees: Vec<GitLabMember>,
+}
+
+/// The labels of a GitLab API issue response.
+#[derive(Debug, Deserialize)]
+struct GitLabIssueLabels {
+    labels: Vec<String>,
+}
+
+/// The reviewers of a GitLab API merge request response.
+#[derive(Debug, Deserialize)]
+struct GitLabMrReviewers {
+    reviewers: Vec<GitLabMember>,
+}
+
 /// GitLab API response for pull requests.
 /// https://docs.gitlab.com/api/merge_requests/#list-project-merge-requests
 #[derive(Debug, Deserialize)]
@@ -65,37 +255,271 @@ struct GitLabMergeRequest {
     title: String,
     state: String,
     labels: Vec<String>,
+    milestone: Option<GitLabMilestone>,
     author: GitLabUser,
-    created_at: String,
-    updated_at: String,
+    created_at: Timestamp,
+    updated_at: Timestamp,
     web_url: String,
     draft: bool,
+    merged_at: Option<Timestamp>,
+    closed_at: Option<Timestamp>,
+    source_branch: String,
+    target_branch: String,
+    /// The head commit. The list endpoint doesn't return the target's commit.
+    sha: Option<String>,
+    user_notes_count: Option<u32>,
+}
+
+impl From<GitLabMergeRequest> for ChangelogEntry {
+    fn from(mr: GitLabMergeRequest) -> Self {
+        Pr::from(mr).into()
+    }
 }
 
 impl From<GitLabMergeRequest> for Pr {
     fn from(mr: GitLabMergeRequest) -> Self {
         Pr {
             id: mr.iid,
-            title: mr.title,
-            state: if mr.state == "opened" {
-                "open".to_string()
+            title: strip_draft_title_prefix(&mr.title).to_string(),
+            state: if mr.state == PrState::Open.as_gitlab_str() {
+                PrState::Open.to_string()
             } else {
                 mr.state
             },
             author: mr.author.username,
             url: mr.web_url,
             labels: mr.labels,
+            milestone: mr.milestone.map(|milestone| milestone.title),
             created_at: mr.created_at,
             updated_at: mr.updated_at,
+            merged_at: mr.merged_at,
+            closed_at: mr.closed_at,
+            head: Some(PrBranch {
+                name: mr.source_branch,
+                sha: mr.sha,
+            }),
+            base: Some(PrBranch {
+                name: mr.target_branch,
+                sha: None,
+            }),
             draft: mr.draft,
+            approvals_required: None,
+            approvals_left: None,
+            review_status: None,
+            mergeable: None,
+            ci: None,
+            comments: mr.user_notes_count,
+            commits: None,
+            changed_files: None,
+        }
+    }
+}
+
+/// GitLab API response for a single merge request. Only the single merge
+/// request endpoint contains the head pipeline.
+/// https://docs.gitlab.com/api/merge_requests/#get-single-mr
+#[derive(Debug, Deserialize)]
+struct GitLabMergeRequestDetails {
+    merge_status: String,
+    #[serde(default)]
+    has_conflicts: bool,
+    head_pipeline: Option<GitLabPipeline>,
+    user_notes_count: Option<u32>,
+    /// The number of changed files as a string. It's capped, e.g. `1000+`.
+    changes_count: Option<String>,
+}
+
+/// The auto-merge state of a GitLab API merge request response.
+#[derive(Debug, Deserialize)]
+struct GitLabMrAutoMerge {
+    #[serde(default)]
+    merge_when_pipeline_succeeds: bool,
+}
+
+/// The state of a GitLab API merge request response after merging.
+#[derive(Debug, Deserialize)]
+struct GitLabMrState {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabPipeline {
+    status: String,
+}
+
+/// GitLab API response for a project.
+/// https://docs.gitlab.com/api/projects/#get-a-single-project
+#[derive(Debug, Deserialize)]
+struct GitLabProject {
+    path_with_namespace: String,
+    description: Option<String>,
+    web_url: String,
+    /// Empty projects don't have a default branch.
+    default_branch: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    archived: bool,
+}
+
+/// GitLab API response for the projects of a group or user.
+/// https://docs.gitlab.com/api/groups/#list-projects
+#[derive(Debug, Deserialize)]
+struct GitLabProjectSummary {
+    path_with_namespace: String,
+    visibility: String,
+    star_count: u32,
+    /// Empty projects don't have a default branch.
+    default_branch: Option<String>,
+    /// GitLab doesn't track pushes separately.
+    last_activity_at: Option<Timestamp>,
+    archived: bool,
+    /// Only set for forks.
+    #[serde(default)]
+    forked_from_project: Option<serde::de::IgnoredAny>,
+    web_url: String,
+    http_url_to_repo: String,
+    ssh_url_to_repo: String,
+}
+
+impl From<GitLabProjectSummary> for RepoSummary {
+    fn from(project: GitLabProjectSummary) -> Self {
+        RepoSummary {
+            name: project.path_with_namespace,
+            visibility: project.visibility,
+            stars: project.star_count,
+            default_branch: project.default_branch.unwrap_or_default(),
+            pushed_at: project.last_activity_at,
+            archived: project.archived,
+            fork: project.forked_from_project.is_some(),
+            url: project.web_url,
+            clone_url: project.http_url_to_repo,
+            ssh_url: project.ssh_url_to_repo,
+        }
+    }
+}
+
+/// GitLab API response for project webhooks. GitLab doesn't return their
+/// secret token.
+/// https://docs.gitlab.com/api/project_webhooks/#list-webhooks-for-a-project
+#[derive(Debug, Deserialize)]
+struct GitLabHook {
+    id: u64,
+    url: String,
+    /// `executable`, `temporarily_disabled` or `disabled`. Missing on older
+    /// versions.
+    alert_status: Option<String>,
+    /// The `<event>_events` flags and the other fields.
+    #[serde(flatten)]
+    fields: BTreeMap<String, serde_json::Value>,
+}
+
+impl GitLabHook {
+    /// Gets the events the webhook is subscribed to, e.g. `push` for
+    /// `push_events`, ordered by name.
+    fn get_events(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .filter(|(_, value)| value.as_bool() == Some(true))
+            .filter_map(|(name, _)| name.strip_suffix("_events"))
+            .map(String::from)
+            .collect()
+    }
+}
+
+impl From<GitLabHook> for Hook {
+    fn from(hook: GitLabHook) -> Self {
+        Hook {
+            id: hook.id,
+            url: hook::redact_url(&hook.url),
+            events: hook.get_events(),
+            active: hook
+                .alert_status
+                .as_deref()
+                .is_none_or(|status| status == "executable"),
+            last_delivery: None,
+        }
+    }
+}
+
+/// GitLab API response for branches.
+/// https://docs.gitlab.com/api/branches/#list-repository-branches
+#[derive(Debug, Deserialize)]
+struct GitLabBranch {
+    name: String,
+    commit: GitLabCommit,
+    protected: bool,
+    /// Whether the default branch contains the branch.
+    merged: bool,
+}
+
+impl From<GitLabBranch> for Branch {
+    fn from(branch: GitLabBranch) -> Self {
+        Branch {
+            name: branch.name,
+            sha: branch.commit.id,
+            last_commit_at: Some(branch.commit.committed_date),
+            age: None,
+            protected: branch.protected,
+            merged: Some(branch.merged),
+            pr: None,
+            stale: None,
         }
     }
 }
 
+/// GitLab API response for a commit.
+/// https://docs.gitlab.com/api/commits/#get-a-single-commit
+#[derive(Debug, Deserialize)]
+struct GitLabCommit {
+    id: String,
+    committed_date: Timestamp,
+}
+
+/// GitLab API response for comparing two refs.
+/// https://docs.gitlab.com/api/repositories/#compare-branches-tags-or-commits
+#[derive(Debug, Deserialize)]
+struct GitLabComparison {
+    commits: Vec<serde::de::IgnoredAny>,
+}
+
+/// GitLab API response for merge request approvals.
+/// https://docs.gitlab.com/api/merge_request_approvals/#single-merge-request-approval-state
+#[derive(Debug, Deserialize)]
+struct GitLabApprovals {
+    approvals_required: u32,
+    approvals_left: u32,
+    #[serde(default)]
+    approved_by: Vec<serde::de::IgnoredAny>,
+}
+
 // =============================================================================
 // Command Logic
 // =============================================================================
 
+pub fn supports(capability: Capability) -> Support {
+    match capability {
+        Capability::ClosedAsFilter => Support::No(
+            "GitLab does not track why an issue was closed. --closed-as is only supported for GitHub",
+        ),
+        Capability::HookToggle => Support::No(
+            "GitLab has no API to disable webhooks, it only disables failing ones by itself. `hook enable` and `hook disable` are only supported for GitHub and Gitea/Forgejo",
+        ),
+        Capability::IssuePinning => Support::No(
+            "GitLab does not support pinning issues. Pinning is only supported for GitHub and Gitea/Forgejo",
+        ),
+        Capability::PinnedFilter => Support::No(
+            "GitLab does not support pinning issues. --pinned is only supported for Gitea/Forgejo",
+        ),
+        Capability::Reactions => Support::Degraded("GitLab only counts upvotes of issues"),
+        Capability::AutoMerge
+        | Capability::DraftPrs
+        | Capability::MaintainerEdit
+        | Capability::MergedStateFilter
+        | Capability::RemoveSourceBranch
+        | Capability::SquashOnMerge => Support::Yes,
+    }
+}
+
 pub fn get_issues(
     http_client: &HttpClient,
     remote: &GitRemoteData,
@@ -103,50 +527,21 @@ pub fn get_issues(
     filters: &ListIssueFilters,
     use_auth: bool,
 ) -> anyhow::Result<PaginatedResponse<Issue>> {
-    let base_url = match api_url {
-        Some(url) => url,
-        None => &build_api_base_url(remote),
-    };
-    let encoded_path = encode_repo_path(&remote.path);
-    let url = format!("{base_url}/projects/{encoded_path}/issues");
-    let state = match filters.state {
-        IssueState::Open => "opened".to_string(),
-        _ => filters.state.to_string(),
-    };
-    let mut request = http_client
-        .get(&url)
-        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
-        .query(&[("state", state)])
-        .query(&[("page", filters.page)])
-        .query(&[("per_page", filters.per_page)]);
-
-    if let Some(assignee) = filters.assignee {
-        request = request.query(&[("assignee_username", assignee)]);
+    if filters.include_prs {
+        return fetch_issues_and_merge_requests(http_client, remote, api_url, filters, use_auth);
     }
 
-    if let Some(author) = filters.author {
-        request = request.query(&[("author_username", author)]);
-    }
-
-    if !filters.labels.is_empty() {
-        request = request.query(&[("labels", filters.labels.join(","))]);
-    }
-
-    if let Some(query) = filters.query {
-        request = request.query(&[("search", query)]);
-    }
-
-    let response = request
-        .send()
-        .context("Network request failed while fetching issues from GitLab")?
-        .with_http_status_ok()?;
-
-    let has_next_page = http_client::has_next_link_header(&response);
+    fetch_issues(http_client, remote, api_url, "issues", filters, use_auth)
+}
 
-    response
-        .json()
-        .context("Failed to parse GitHub Search API response")
-        .map(|vec: Vec<GitLabIssue>| vec.into_paginated_response(has_next_page))
+pub fn get_issues_for_export(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ExportedIssue>> {
+    fetch_issues(http_client, remote, api_url, "issues", filters, use_auth)
 }
 
 pub fn create_issue(
@@ -164,6 +559,7 @@ pub fn create_issue(
     let request_body = serde_json::json!({
         "title": options.title,
         "description": options.body,
+        "labels": options.labels.join(","),
     });
 
     eprintln!("Creating issue on GitLab...");
@@ -174,193 +570,2256 @@ pub fn create_issue(
         .json(&request_body);
 
     request
-        .send()
+        .send_throttled()
         .context("Network request failed while creating issue on GitLab")?
         .with_http_status_ok()?
-        .json()
+        .parse_json()
         .context("Failed to parse GitLab API response")
         .map(|issue: GitLabIssue| issue.into())
 }
 
-pub fn get_prs(
+pub fn get_issue(
     http_client: &HttpClient,
     remote: &GitRemoteData,
     api_url: Option<&str>,
-    filters: &ListPrsFilters,
+    issue_number: u32,
     use_auth: bool,
-) -> anyhow::Result<PaginatedResponse<Pr>> {
+) -> anyhow::Result<ExportedIssue> {
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let encoded_path = encode_repo_path(&remote.path);
-    let url = format!("{base_url}/projects/{encoded_path}/merge_requests");
-    let state = match filters.state {
-        PrState::Open => "opened".to_string(),
-        _ => filters.state.to_string(),
-    };
-    let mut request = http_client
+    let url = format!("{base_url}/projects/{encoded_path}/issues/{issue_number}");
+
+    http_client
         .get(&url)
         .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
-        .query(&[("state", state)])
-        .query(&[("page", filters.page)])
-        .query(&[("per_page", filters.per_page)]);
-
-    if let Some(author_name) = filters.author {
-        request = request.query(&[("author_username", author_name)]);
-    }
-
-    if !filters.labels.is_empty() {
-        request = request.query(&[("labels", filters.labels.join(","))]);
-    }
-
-    if let Some(query) = filters.query {
-        request = request.query(&[("search", query)]);
-    }
-
-    if filters.draft {
-        request = request.query(&[("wip", "yes")]);
-    }
-
-    let response = request
-        .send()
-        .context("Network request failed while fetching merge requests from GitLab")?
-        .with_http_status_ok()?;
+        .send_throttled()
+        .context("Network request failed while fetching issue from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|issue: GitLabIssue| issue.into())
+}
 
-    let has_next_page = http_client::has_next_link_header(&response);
+/// System notes (e.g. label changes) aren't comments, so they are skipped.
+pub fn get_issue_comments(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Vec<IssueComment>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let notes: Vec<GitLabNote> = http_client
+        .get(&format!(
+            "{base_url}/projects/{encoded_path}/issues/{issue_number}/notes"
+        ))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("per_page", "100"), ("sort", "asc")])
+        .send_throttled()
+        .context("Network request failed while fetching notes from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
 
-    response
-        .json()
-        .context("Failed to parse GitHub Search API response")
-        .map(|vec: Vec<GitLabMergeRequest>| vec.into_paginated_response(has_next_page))
+    Ok(notes
+        .into_iter()
+        .filter(|note| !note.system)
+        .map(|note| IssueComment {
+            id: note.id,
+            author: note.author.username,
+            created_at: note.created_at.to_string(),
+            body: note.body,
+        })
+        .collect())
 }
 
-pub fn create_pr(
+pub fn close_issue(
     http_client: &HttpClient,
     remote: &GitRemoteData,
     api_url: Option<&str>,
-    options: &CreatePrOptions,
-) -> anyhow::Result<Pr> {
+    issue_number: u32,
+) -> anyhow::Result<()> {
     let base_url = match api_url {
         Some(url) => url,
         None => &build_api_base_url(remote),
     };
     let encoded_path = encode_repo_path(&remote.path);
-    let url = format!("{base_url}/projects/{encoded_path}/merge_requests");
-    let request_body = serde_json::json!({
-        "source_branch": options.source_branch,
-        "target_branch": options.target_branch,
-        "title": if options.draft { format!("Draft: {}", options.title) } else { options.title.to_string() },
-        "description": options.body,
-    });
-
-    eprintln!("Creating merge request on GitLab...");
 
-    let request = http_client
-        .post(&url)
+    http_client
+        .put(&format!(
+            "{base_url}/projects/{encoded_path}/issues/{issue_number}"
+        ))
         .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
-        .json(&request_body);
+        .json(&serde_json::json!({ "state_event": "close" }))
+        .send_throttled()
+        .context("Network request failed while closing issue on GitLab")?
+        .with_http_status_ok()?;
 
-    request
-        .send()
-        .context("Network request failed while creating merge request on GitLab")?
-        .with_http_status_ok()?
-        .json()
-        .context("Failed to parse GitLab API response")
-        .map(|mr: GitLabMergeRequest| mr.into())
+    Ok(())
 }
 
-pub fn get_pr_ref(pr_number: u32) -> String {
-    format!("merge-requests/{pr_number}/head")
+/// Assigns the issue to the users and returns the resulting assignees.
+pub fn assign_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_assignees(http_client, remote, api_url, issue_number, assignees, true)
 }
 
-pub fn get_url_for_home(remote: &GitRemoteData) -> String {
-    build_web_base_url(remote)
-}
+/// Removes the users from the assignees of the issue and returns the
+/// remaining assignees.
+pub fn unassign_issue(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_assignees(http_client, remote, api_url, issue_number, assignees, false)
+}
 
-pub fn get_url_for_commit(remote: &GitRemoteData, commit: &str) -> String {
-    format!("{}/-/commit/{}", build_web_base_url(remote), commit)
+/// Gets the username of the user that the token belongs to.
+pub fn get_authenticated_user(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<String> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    Ok(get_identity(http_client, base_url)?.username)
 }
 
-pub fn get_url_for_issue(remote: &GitRemoteData, issue_number: u32) -> String {
-    format!("{}/-/issues/{}", build_web_base_url(remote), issue_number)
+/// Adds the labels to the issue and returns its resulting labels. GitLab
+/// creates the labels that don't exist in the project.
+pub fn add_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_labels(
+        http_client,
+        remote,
+        api_url,
+        issue_number,
+        "add_labels",
+        labels,
+    )
 }
 
-pub fn get_url_for_issues(remote: &GitRemoteData) -> String {
-    format!("{}/-/issues", build_web_base_url(remote))
+/// Removes the labels from the issue and returns its remaining labels. Labels
+/// that the issue doesn't have are ignored.
+pub fn remove_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_labels(
+        http_client,
+        remote,
+        api_url,
+        issue_number,
+        "remove_labels",
+        labels,
+    )
 }
 
-pub fn get_url_for_issue_creation(remote: &GitRemoteData) -> String {
-    format!("{}/-/issues/new", build_web_base_url(remote))
+/// Replaces the labels of the issue and returns its resulting labels.
+pub fn set_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    update_issue_labels(http_client, remote, api_url, issue_number, "labels", labels)
 }
 
-pub fn get_url_for_pr(remote: &GitRemoteData, pr_number: u32) -> String {
-    format!(
-        "{}/-/merge_requests/{}",
-        build_web_base_url(remote),
-        pr_number
-    )
+pub fn set_issue_pinned(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _issue_number: u32,
+    _pinned: bool,
+) -> anyhow::Result<()> {
+    supports(Capability::IssuePinning).ensure()
 }
 
-pub fn get_url_for_pr_creation(
+pub fn get_labels(
+    http_client: &HttpClient,
     remote: &GitRemoteData,
-    target_branch: &str,
-    source_branch: &str,
-) -> String {
-    let base_url = build_web_base_url(remote);
+    api_url: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/labels");
 
-    format!(
-        "{base_url}/merge_requests/new?merge_request[source_branch]={source_branch}&merge_request[target_branch]={target_branch}"
+    http_client
+        .get(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("per_page", 100)])
+        .send_throttled()
+        .context("Network request failed while fetching labels from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|labels: Vec<GitLabLabel>| labels.into_iter().map(|l| l.name).collect())
+}
+
+/// Creates a label with a color given as `rrggbb`.
+pub fn create_label(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    name: &str,
+    color: &str,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/labels");
+    let request_body = serde_json::json!({
+        "name": name,
+        "color": format!("#{color}"),
+    });
+
+    http_client
+        .post(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&request_body)
+        .send_throttled()
+        .context("Network request failed while creating label on GitLab")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+pub fn get_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListPrsFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests");
+    let response = build_merge_requests_request(http_client, &url, filters, use_auth)?
+        .send_throttled()
+        .context("Network request failed while fetching merge requests from GitLab")?
+        .with_http_status_ok()?;
+
+    let has_next_page = http_client::has_next_link_header(&response);
+
+    response
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|vec: Vec<GitLabMergeRequest>| vec.into_paginated_response(has_next_page))
+}
+
+pub fn get_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Pr> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests/{pr_number}");
+
+    http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|mr: GitLabMergeRequest| mr.into())
+}
+
+pub fn get_open_prs_for_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests");
+
+    http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[
+            ("state", PrState::Open.as_gitlab_str()),
+            ("source_branch", branch),
+        ])
+        .query(&[("per_page", 100)])
+        .send_throttled()
+        .context("Network request failed while fetching merge requests from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|mrs: Vec<GitLabMergeRequest>| mrs.into_iter().map(Into::into).collect())
+}
+
+pub fn get_merged_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>> {
+    fetch_changelog_entries::<GitLabMergeRequest>(
+        http_client,
+        remote,
+        api_url,
+        "merge_requests",
+        PrState::Merged.as_gitlab_str(),
+        filters,
+        use_auth,
     )
 }
 
-pub fn get_url_for_prs(remote: &GitRemoteData) -> String {
-    format!("{}/-/merge_requests", build_web_base_url(remote))
+pub fn get_closed_issues(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>> {
+    fetch_changelog_entries::<GitLabIssue>(
+        http_client,
+        remote,
+        api_url,
+        "issues",
+        IssueState::Closed.as_gitlab_str(),
+        filters,
+        use_auth,
+    )
 }
 
-pub fn get_url_for_releases(remote: &GitRemoteData) -> String {
-    format!("{}/-/releases", build_web_base_url(remote))
+/// GitLab marks drafts with the `Draft:` title prefix itself, which every
+/// version understands.
+pub fn supports_native_drafts(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+) -> bool {
+    true
 }
 
-pub fn get_url_for_path(
+pub fn create_pr(
+    http_client: &HttpClient,
     remote: &GitRemoteData,
-    path: &str,
-    commit: &str,
-    line_number: Option<u32>,
-) -> String {
-    let base = build_web_base_url(remote);
-    let mut url = format!("{base}/-/blob/{commit}/{path}");
+    api_url: Option<&str>,
+    options: &CreatePrOptions,
+) -> anyhow::Result<Pr> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests");
+    let request_body = build_create_pr_request_body(options);
 
-    if let Some(line) = line_number {
-        url.push_str(&format!("#L{}", line));
-    }
+    eprintln!("Creating merge request on GitLab...");
 
-    url
+    let request = http_client
+        .post(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&request_body);
+
+    request
+        .send_throttled()
+        .context("Network request failed while creating merge request on GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|mr: GitLabMergeRequest| mr.into())
 }
 
-// =============================================================================
-// Private Helpers
-// =============================================================================
+/// Adds the `add` users to the reviewers, removes the `remove` users and
+/// returns the resulting reviewers. GitLab only takes the complete list of
+/// reviewer ids, so the usernames are resolved to ids first.
+pub fn update_pr_reviewers(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    add: &[String],
+    remove: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests/{pr_number}");
+    let mr: GitLabMrReviewers = http_client
+        .get(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+    let mut reviewer_ids: Vec<u64> = mr
+        .reviewers
+        .iter()
+        .filter(|reviewer| !remove.contains(&reviewer.username))
+        .map(|reviewer| reviewer.id)
+        .collect();
 
-fn build_api_base_url(remote: &GitRemoteData) -> String {
-    let (host, port) = (&remote.host, remote.port);
+    for username in add {
+        let id = get_user_id(http_client, base_url, username)?;
 
-    match port {
-        Some(p) => format!("https://{host}:{p}/api/v4"),
-        None => format!("https://{host}/api/v4"),
+        if !reviewer_ids.contains(&id) {
+            reviewer_ids.push(id);
+        }
     }
+
+    let mr: GitLabMrReviewers = http_client
+        .put(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "reviewer_ids": reviewer_ids }))
+        .send_throttled()
+        .context("Network request failed while updating reviewers on GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(mr
+        .reviewers
+        .into_iter()
+        .map(|reviewer| reviewer.username)
+        .collect())
 }
 
-fn build_web_base_url(remote: &GitRemoteData) -> String {
-    let host = &remote.host;
-    let path = &remote.path;
+/// Merges the MR or, with `auto`, sets it to be merged once its pipeline
+/// succeeds. GitLab merges right away if the pipeline already succeeded.
+pub fn merge_pr(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    auto: bool,
+) -> anyhow::Result<PrMergeOutcome> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let mr: GitLabMrState = http_client
+        .put(&format!(
+            "{base_url}/projects/{encoded_path}/merge_requests/{pr_number}/merge"
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "merge_when_pipeline_succeeds": auto }))
+        .send_throttled()
+        .context("Network request failed while merging merge request on GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
 
-    match remote.port {
-        Some(port) => format!("https://{host}:{port}/{path}"),
-        None => format!("https://{host}/{path}"),
+    match mr.state.as_str() {
+        "merged" => Ok(PrMergeOutcome::Merged),
+        _ => Ok(PrMergeOutcome::Scheduled),
     }
 }
 
-fn encode_repo_path(repo_path: &str) -> String {
-    repo_path.replace("/", "%2F")
+pub fn cancel_pr_auto_merge(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+
+    http_client
+        .post(&format!(
+            "{base_url}/projects/{encoded_path}/merge_requests/{pr_number}/cancel_merge_when_pipeline_succeeds"
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while cancelling auto-merge on GitLab")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+/// Gets whether the MR is set to be merged once its pipeline succeeds.
+pub fn get_pr_auto_merge(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Option<bool>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let mr: GitLabMrAutoMerge = http_client
+        .get(&format!(
+            "{base_url}/projects/{encoded_path}/merge_requests/{pr_number}"
+        ))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(Some(mr.merge_when_pipeline_succeeds))
+}
+
+pub fn get_pr_approvals(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Option<PrApprovals>> {
+    let approvals = fetch_approvals(http_client, remote, api_url, pr_number, use_auth)?;
+
+    Ok(Some(PrApprovals {
+        required: approvals.approvals_required,
+        left: approvals.approvals_left,
+    }))
+}
+
+/// GitLab can't fetch the approval state of many merge requests at once, so
+/// the caller fetches it per merge request with [`get_pr_review_status`].
+pub fn get_pr_review_statuses(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _pr_numbers: &[u32],
+    _use_auth: bool,
+) -> anyhow::Result<Option<Vec<PrReviewStatus>>> {
+    Ok(None)
+}
+
+/// GitLab (Free) has no concept of requesting changes, so the review status is
+/// derived from the approval state.
+pub fn get_pr_review_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrReviewStatus> {
+    let approvals = fetch_approvals(http_client, remote, api_url, pr_number, use_auth)?;
+
+    let review_status = if approvals.approvals_left > 0 {
+        PrReviewStatus::ReviewRequired
+    } else if !approvals.approved_by.is_empty() {
+        PrReviewStatus::Approved
+    } else {
+        PrReviewStatus::None
+    };
+
+    Ok(review_status)
+}
+
+pub fn get_pr_merge_info(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrMergeInfo> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests/{pr_number}");
+
+    let mr: GitLabMergeRequestDetails = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(PrMergeInfo {
+        mergeable: match mr.merge_status.as_str() {
+            _ if mr.has_conflicts => PrMergeable::Conflicting,
+            "can_be_merged" => PrMergeable::Mergeable,
+            "cannot_be_merged" => PrMergeable::Conflicting,
+            _ => PrMergeable::Unknown,
+        },
+        ci: match mr.head_pipeline.as_ref().map(|p| p.status.as_str()) {
+            None | Some("skipped") => PrCiStatus::None,
+            Some("success") => PrCiStatus::Success,
+            Some("failed" | "canceled") => PrCiStatus::Failure,
+            Some(_) => PrCiStatus::Pending,
+        },
+    })
+}
+
+pub fn get_pr_counts(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<PrCounts> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests/{pr_number}");
+
+    let mr: GitLabMergeRequestDetails = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    // The merge request doesn't contain the number of commits, but the total
+    // of its paginated commits does
+    let commits_response = http_client
+        .get(&format!("{url}/commits"))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("per_page", "1")])
+        .send_throttled()
+        .context("Network request failed while fetching merge request commits from GitLab")?
+        .with_http_status_ok()?;
+
+    Ok(PrCounts {
+        comments: mr.user_notes_count,
+        commits: http_client::get_total_count_header(&commits_response, "x-total"),
+        changed_files: mr.changes_count.as_deref().and_then(parse_changes_count),
+    })
+}
+
+pub fn get_repo(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Repo> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let project_url = format!("{base_url}/projects/{}", encode_repo_path(&remote.path));
+    let send = |url: &str| {
+        http_client
+            .get(url)
+            .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+            .send_throttled()
+            .context("Network request failed while fetching project from GitLab")?
+            .with_http_status_ok()
+    };
+    let project: GitLabProject = send(&project_url)?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+    // The percentages of the languages
+    let languages: HashMap<String, f64> = send(&format!("{project_url}/languages"))?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(Repo {
+        name: project.path_with_namespace,
+        description: project.description.unwrap_or_default(),
+        url: project.web_url,
+        default_branch: project.default_branch.unwrap_or_default(),
+        topics: project.topics,
+        languages: repo::sort_languages(languages),
+        archived: project.archived,
+    })
+}
+
+/// Only archived projects are filtered on GitLab's side. The projects of
+/// subgroups aren't listed.
+pub fn get_repos(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListReposFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<RepoSummary>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let url = match filters.owner {
+        RepoOwner::Org(group) => format!("{base_url}/groups/{}/projects", encode_repo_path(group)),
+        RepoOwner::User(user) => format!(
+            "{base_url}/users/{}/projects",
+            url_encoding::encode_component(user)
+        ),
+    };
+    let mut request = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("page", filters.page), ("per_page", filters.per_page)]);
+
+    if let Some(archived) = filters.archived {
+        request = request.query(&[("archived", archived)]);
+    }
+
+    let response = request
+        .send_throttled()
+        .context("Network request failed while fetching projects from GitLab")?
+        .with_http_status_ok()?;
+    let has_next_page = http_client::has_next_link_header(&response);
+    let mut response = response
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|projects: Vec<GitLabProjectSummary>| {
+            projects.into_paginated_response(has_next_page)
+        })?;
+
+    response.items.retain(|repo| filters.matches(repo));
+
+    Ok(response)
+}
+
+pub fn has_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+) -> anyhow::Result<bool> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let response = http_client
+        .get(&format!(
+            "{base_url}/projects/{}/repository/branches/{}",
+            encode_repo_path(&remote.path),
+            url_encoding::encode_component(branch)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching branch from GitLab")?;
+
+    http_client::is_found(response)
+}
+
+pub fn edit_repo(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    edit: &RepoEdit,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let mut fields = serde_json::Map::new();
+
+    if let Some(change) = &edit.description {
+        fields.insert(String::from("description"), change.new.clone().into());
+    }
+
+    if let Some(change) = &edit.topics {
+        fields.insert(String::from("topics"), change.new.clone().into());
+    }
+
+    if let Some(change) = &edit.default_branch {
+        fields.insert(String::from("default_branch"), change.new.clone().into());
+    }
+
+    http_client
+        .put(&format!(
+            "{base_url}/projects/{}",
+            encode_repo_path(&remote.path)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&fields)
+        .send_throttled()
+        .context("Network request failed while editing project on GitLab")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+/// The branches list already contains the last commit's date and whether the
+/// branch is merged into the default branch.
+pub fn get_branches(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Branch>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let branches: Vec<GitLabBranch> = get_json_pages(
+        http_client,
+        &format!(
+            "{base_url}/projects/{}/repository/branches",
+            encode_repo_path(&remote.path)
+        ),
+        &[],
+        use_auth,
+    )?;
+
+    Ok(branches.into_iter().map(Branch::from).collect())
+}
+
+pub fn get_hooks(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<Vec<Hook>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let hooks: Vec<GitLabHook> = get_json_pages(
+        http_client,
+        &format!(
+            "{base_url}/projects/{}/hooks",
+            encode_repo_path(&remote.path)
+        ),
+        &[],
+        true,
+    )
+    .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(hooks.into_iter().map(Hook::from).collect())
+}
+
+/// GitLab tests webhooks with a trigger per event, so the webhook is tested
+/// with its push events if it's subscribed to them and with its first other
+/// event otherwise.
+pub fn ping_hook(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    hook_id: u64,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let hook_url = format!(
+        "{base_url}/projects/{}/hooks/{hook_id}",
+        encode_repo_path(&remote.path)
+    );
+    let hook: GitLabHook = http_client
+        .get(&hook_url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching webhook from GitLab")?
+        .with_http_status_ok()
+        .context(HOOKS_PERMISSION_HINT)?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+    let events = hook.get_events();
+    let Some(event) = events
+        .iter()
+        .find(|event| *event == PING_EVENT)
+        .or(events.first())
+    else {
+        anyhow::bail!("Webhook {hook_id} isn't subscribed to any events to test it with");
+    };
+
+    http_client
+        .post(&format!("{hook_url}/test/{event}_events"))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while testing webhook on GitLab")?
+        .with_http_status_ok()
+        .context(HOOKS_PERMISSION_HINT)?;
+
+    Ok(())
+}
+
+pub fn set_hook_active(
+    _http_client: &HttpClient,
+    _remote: &GitRemoteData,
+    _api_url: Option<&str>,
+    _hook_id: u64,
+    _active: bool,
+) -> anyhow::Result<()> {
+    supports(Capability::HookToggle).ensure()
+}
+
+pub fn get_commit_date(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    sha: &str,
+    use_auth: bool,
+) -> anyhow::Result<Timestamp> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .get(&format!(
+            "{base_url}/projects/{}/repository/commits/{sha}",
+            encode_repo_path(&remote.path)
+        ))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching commit from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|commit: GitLabCommit| commit.committed_date)
+}
+
+/// The commit is merged if `target` contains it, i.e. comparing `target` with
+/// the commit yields no commits.
+pub fn is_branch_merged(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    sha: &str,
+    target: &str,
+    use_auth: bool,
+) -> anyhow::Result<bool> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .get(&format!(
+            "{base_url}/projects/{}/repository/compare",
+            encode_repo_path(&remote.path)
+        ))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("from", target), ("to", sha)])
+        .send_throttled()
+        .context("Network request failed while comparing commits on GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|comparison: GitLabComparison| comparison.commits.is_empty())
+}
+
+pub fn get_open_prs(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let mrs: Vec<GitLabMergeRequest> = get_json_pages(
+        http_client,
+        &format!(
+            "{base_url}/projects/{}/merge_requests",
+            encode_repo_path(&remote.path)
+        ),
+        &[("state", PrState::Open.as_gitlab_str())],
+        use_auth,
+    )?;
+
+    Ok(mrs.into_iter().map(Pr::from).collect())
+}
+
+pub fn get_merged_prs_for_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Pr>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let mrs: Vec<GitLabMergeRequest> = get_json_pages(
+        http_client,
+        &format!(
+            "{base_url}/projects/{}/merge_requests",
+            encode_repo_path(&remote.path)
+        ),
+        &[
+            ("state", PrState::Merged.as_gitlab_str()),
+            ("source_branch", branch),
+        ],
+        use_auth,
+    )?;
+
+    Ok(mrs.into_iter().map(Pr::from).collect())
+}
+
+pub fn delete_branch(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    branch: &str,
+) -> anyhow::Result<()> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+
+    http_client
+        .delete(&format!(
+            "{base_url}/projects/{}/repository/branches/{}",
+            encode_repo_path(&remote.path),
+            url_encoding::encode_component(branch)
+        ))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while deleting the branch on GitLab")?
+        .with_http_status_ok()?;
+
+    Ok(())
+}
+
+pub fn get_repo_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+) -> anyhow::Result<RepoStatus> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+
+    let project: GitLabProject = http_client
+        .get(&format!("{base_url}/projects/{encoded_path}"))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching project from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(RepoStatus {
+        archived: project.archived,
+    })
+}
+
+pub fn get_pr_ref(pr_number: u32) -> String {
+    format!("merge-requests/{pr_number}/head")
+}
+
+pub fn get_url_for_api(remote: &GitRemoteData) -> String {
+    build_api_base_url(remote)
+}
+
+pub fn get_url_for_home(remote: &GitRemoteData) -> String {
+    build_web_base_url(remote)
+}
+
+pub fn get_url_for_commit(remote: &GitRemoteData, commit: &str) -> String {
+    format!(
+        "{}/-/commit/{}",
+        build_web_base_url(remote),
+        url_encoding::encode_component(commit)
+    )
+}
+
+pub fn get_url_for_issue(remote: &GitRemoteData, issue_number: u32) -> String {
+    format!("{}/-/issues/{}", build_web_base_url(remote), issue_number)
+}
+
+pub fn get_url_for_issue_comment(
+    remote: &GitRemoteData,
+    issue_number: u32,
+    comment_id: u64,
+) -> String {
+    format!(
+        "{}#note_{comment_id}",
+        get_url_for_issue(remote, issue_number)
+    )
+}
+
+pub fn get_url_for_issues(remote: &GitRemoteData) -> String {
+    format!("{}/-/issues", build_web_base_url(remote))
+}
+
+/// The issues page of GitLab can't be filtered by dates via the URL, so this
+/// is the unfiltered issues page.
+pub fn get_url_for_issue_search(
+    remote: &GitRemoteData,
+    _created: &DateRange,
+    _closed: &DateRange,
+) -> String {
+    get_url_for_issues(remote)
+}
+
+pub fn get_url_for_issue_creation(remote: &GitRemoteData) -> String {
+    format!("{}/-/issues/new", build_web_base_url(remote))
+}
+
+pub fn get_url_for_pr(remote: &GitRemoteData, pr_number: u32) -> String {
+    format!(
+        "{}/-/merge_requests/{}",
+        build_web_base_url(remote),
+        pr_number
+    )
+}
+
+pub fn get_url_for_pr_creation(
+    remote: &GitRemoteData,
+    target_branch: &str,
+    source_branch: &str,
+) -> String {
+    let base_url = build_web_base_url(remote);
+    let source_branch = url_encoding::encode_component(source_branch);
+    let target_branch = url_encoding::encode_component(target_branch);
+
+    format!(
+        "{base_url}/merge_requests/new?merge_request[source_branch]={source_branch}&merge_request[target_branch]={target_branch}"
+    )
+}
+
+pub fn get_url_for_prs(remote: &GitRemoteData) -> String {
+    format!("{}/-/merge_requests", build_web_base_url(remote))
+}
+
+pub fn get_url_for_labels(remote: &GitRemoteData) -> String {
+    format!("{}/-/labels", build_web_base_url(remote))
+}
+
+pub fn get_url_for_milestones(remote: &GitRemoteData) -> String {
+    format!("{}/-/milestones", build_web_base_url(remote))
+}
+
+pub fn get_url_for_releases(remote: &GitRemoteData) -> String {
+    format!("{}/-/releases", build_web_base_url(remote))
+}
+
+pub fn get_url_for_path(
+    remote: &GitRemoteData,
+    path: &str,
+    commit: &str,
+    line_number: Option<u32>,
+) -> String {
+    let base = build_web_base_url(remote);
+    let commit = url_encoding::encode_component(commit);
+    let path = url_encoding::encode_path(path);
+    let mut url = format!("{base}/-/blob/{commit}/{path}");
+
+    if let Some(line) = line_number {
+        url.push_str(&format!("#L{}", line));
+    }
+
+    url
+}
+
+/// GitLab has no review summaries. Instead, approvals and requested changes
+/// are taken from the system notes.
+pub fn get_pr_timeline_events(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    options: &PrTimelineOptions,
+    use_auth: bool,
+) -> anyhow::Result<Vec<PrTimelineEvent>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let mr_url = format!("{base_url}/projects/{encoded_path}/merge_requests/{pr_number}");
+    let mr: GitLabMergeRequestDescription = http_client
+        .get(&mr_url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+    let mut events = vec![PrTimelineEvent {
+        kind: PrTimelineEventKind::Description,
+        author: mr.author.username,
+        created_at: mr.created_at,
+        body: mr.description.unwrap_or_default(),
+    }];
+
+    if !options.comments && !options.reviews {
+        return Ok(events);
+    }
+
+    let notes: Vec<GitLabNote> = http_client
+        .get(&format!("{mr_url}/notes"))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("per_page", "100"), ("sort", "asc")])
+        .send_throttled()
+        .context("Network request failed while fetching notes from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    events.extend(notes.into_iter().filter_map(|note| {
+        let kind = match note.body.as_str() {
+            _ if !note.system => PrTimelineEventKind::Comment,
+            "approved this merge request" => PrTimelineEventKind::Approved,
+            "unapproved this merge request" => PrTimelineEventKind::Dismissed,
+            "requested changes" => PrTimelineEventKind::ChangesRequested,
+            // Other system notes (pushes, label changes etc.) aren't part of
+            // the discussion
+            _ => return None,
+        };
+        let include = match kind {
+            PrTimelineEventKind::Comment => options.comments,
+            _ => options.reviews,
+        };
+
+        include.then(|| PrTimelineEvent {
+            kind,
+            author: note.author.username,
+            created_at: note.created_at,
+            // The body of system notes only describes the event itself
+            body: if note.system {
+                String::new()
+            } else {
+                note.body
+            },
+        })
+    }));
+
+    Ok(events)
+}
+
+pub fn get_pr_closing_issues(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<Vec<u32>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let issues: Vec<GitLabClosedIssue> = http_client
+        .get(&format!(
+            "{base_url}/projects/{encoded_path}/merge_requests/{pr_number}/closes_issues"
+        ))
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching closed issues from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(issues.into_iter().map(|issue| issue.iid).collect())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// Builds the body of the request that creates a merge request. The merge
+/// options are only sent if they are set, so that they don't override the
+/// defaults of the project. Commits from the members who can merge aren't
+/// allowed by default, so `no_maintainer_edit` needs nothing.
+fn build_create_pr_request_body(options: &CreatePrOptions) -> serde_json::Value {
+    let title = if options.draft {
+        format!("Draft: {}", options.title)
+    } else {
+        options.title.to_string()
+    };
+    let mut request_body = serde_json::json!({
+        "source_branch": options.source_branch,
+        "target_branch": options.target_branch,
+        "title": title,
+        "description": options.body,
+        "labels": options.labels.join(","),
+    });
+
+    if options.remove_source_branch {
+        request_body["remove_source_branch"] = true.into();
+    }
+
+    if options.squash_on_merge {
+        request_body["squash"] = true.into();
+    }
+
+    request_body
+}
+
+fn get_identity(http_client: &HttpClient, base_url: &str) -> anyhow::Result<Identity> {
+    identity::get_identity(base_url, AUTH_TOKEN, || {
+        http_client
+            .get(&format!("{base_url}/user"))
+            .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+            .send_throttled()
+            .context("Network request failed while fetching the authenticated user from GitLab")?
+            .with_http_status_ok()?
+            .parse_json()
+            .context("Failed to parse GitLab API response")
+            .map(|user: GitLabAuthenticatedUser| user.into())
+    })
+}
+
+/// Gets the id of the user with the given username.
+fn get_user_id(http_client: &HttpClient, base_url: &str, username: &str) -> anyhow::Result<u64> {
+    let users: Vec<GitLabUserId> = http_client
+        .get(&format!("{base_url}/users"))
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("username", username)])
+        .send_throttled()
+        .context("Network request failed while fetching user from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    match users.first() {
+        Some(user) => Ok(user.id),
+        None => anyhow::bail!("GitLab user '{username}' not found"),
+    }
+}
+
+/// Adds or removes assignees. GitLab only takes the complete list of
+/// assignee ids, so the current assignees are fetched first.
+fn update_issue_assignees(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    assignees: &[String],
+    add: bool,
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/issues/{issue_number}");
+    let ids = assignees
+        .iter()
+        .map(|username| get_user_id(http_client, base_url, username))
+        .collect::<anyhow::Result<Vec<u64>>>()?;
+    let issue: GitLabIssueAssignees = http_client
+        .get(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching issue from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+    let mut assignee_ids: Vec<u64> = issue
+        .assignees
+        .iter()
+        .map(|assignee| assignee.id)
+        .filter(|assignee_id| !ids.contains(assignee_id))
+        .collect();
+
+    if add {
+        assignee_ids.extend(ids);
+    }
+
+    let issue: GitLabIssueAssignees = http_client
+        .put(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ "assignee_ids": assignee_ids }))
+        .send_throttled()
+        .context("Network request failed while updating assignees on GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(issue
+        .assignees
+        .into_iter()
+        .map(|assignee| assignee.username)
+        .collect())
+}
+
+/// Updates the labels of an issue with the `add_labels`, `remove_labels` or
+/// `labels` parameter and returns the resulting labels.
+fn update_issue_labels(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    issue_number: u32,
+    parameter: &str,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/issues/{issue_number}");
+    let issue: GitLabIssueLabels = http_client
+        .put(&url)
+        .with_auth(true, AUTH_TOKEN, AUTH_SCHEME)?
+        .json(&serde_json::json!({ parameter: labels.join(",") }))
+        .send_throttled()
+        .context("Network request failed while updating labels on GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")?;
+
+    Ok(issue.labels)
+}
+
+/// Fetches a page of issues and converts them, e.g. to `Issue` for listing.
+/// The `resource` is `issues` or `merge_requests` since their responses are
+/// alike.
+fn fetch_issues<T: From<GitLabIssue>>(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    resource: &str,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<T>> {
+    // Check for unsupported filters
+    if filters.closed_as.is_some() {
+        supports(Capability::ClosedAsFilter).ensure()?;
+    }
+
+    if filters.pinned {
+        supports(Capability::PinnedFilter).ensure()?;
+    }
+
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/{resource}");
+    let response = build_issues_request(http_client, &url, filters, use_auth)?
+        .send_throttled()
+        .context("Network request failed while fetching issues from GitLab")?
+        .with_http_status_ok()?;
+
+    let has_next_page = http_client::has_next_link_header(&response);
+
+    // There is no parameter for the closing date, so it's filtered here. Pages
+    // may contain fewer issues then.
+    response
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|vec: Vec<GitLabIssue>| {
+            vec.into_iter()
+                .filter(|issue| filters.closed.contains(issue.closed_at))
+                .collect::<Vec<_>>()
+                .into_paginated_response(has_next_page)
+        })
+}
+
+/// GitLab lists issues and merge requests separately, so the same page of
+/// both is fetched and merged by the update time. Such a page may contain up
+/// to twice as many items as the filters' page size.
+fn fetch_issues_and_merge_requests(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<Issue>> {
+    let issues: PaginatedResponse<GitLabIssue> =
+        fetch_issues(http_client, remote, api_url, "issues", filters, use_auth)?;
+    let mrs: PaginatedResponse<GitLabIssue> = fetch_issues(
+        http_client,
+        remote,
+        api_url,
+        "merge_requests",
+        filters,
+        use_auth,
+    )?;
+    let has_next_page = issues.has_next_page || mrs.has_next_page;
+    let mut items = issues
+        .items
+        .into_iter()
+        .map(|issue| (IssueKind::Issue, issue))
+        .chain(mrs.items.into_iter().map(|mr| (IssueKind::Pr, mr)))
+        .collect::<Vec<_>>();
+
+    items.sort_by_cached_key(|(_, item)| Reverse(item.updated_at));
+
+    Ok(PaginatedResponse::new(
+        items
+            .into_iter()
+            .map(|(kind, item)| Issue {
+                kind: Some(kind),
+                ..item.into()
+            })
+            .collect(),
+        has_next_page,
+    ))
+}
+
+fn build_issues_request(
+    http_client: &HttpClient,
+    url: &str,
+    filters: &ListIssueFilters,
+    use_auth: bool,
+) -> anyhow::Result<RequestBuilder> {
+    let mut request = http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("state", filters.state.as_gitlab_str())])
+        .query(&[("page", filters.page)])
+        .query(&[("per_page", filters.per_page)]);
+
+    if let Some(assignee) = filters.assignee {
+        request = request.query(&[("assignee_username", assignee)]);
+    }
+
+    if let Some(author) = filters.author {
+        request = request.query(&[("author_username", author)]);
+    }
+
+    // `None` is GitLab's keyword for the issues without labels
+    if filters.labels.unlabeled {
+        request = request.query(&[("labels", "None")]);
+    } else if !filters.labels.include.is_empty() {
+        request = request.query(&[("labels", filters.labels.include.join(","))]);
+    }
+
+    if !filters.labels.exclude.is_empty() {
+        request = request.query(&[("not[labels]", filters.labels.exclude.join(","))]);
+    }
+
+    if let Some(query) = filters.query {
+        request = request.query(&[("search", query)]);
+    }
+
+    if let Some(after) = filters.created.after {
+        request = request.query(&[("created_after", date::format_date(after))]);
+    }
+
+    // GitLab includes issues created at `created_before`
+    if let Some(before) = filters.created.get_inclusive_before() {
+        request = request.query(&[("created_before", date::format_date(before))]);
+    }
+
+    // Issues closed after the date were updated after it as well
+    if let Some(after) = filters.closed.after.max(filters.updated.after) {
+        request = request.query(&[("updated_after", date::format_date(after))]);
+    }
+
+    // GitLab includes issues updated at `updated_before`
+    if let Some(before) = filters.updated.get_inclusive_before() {
+        request = request.query(&[("updated_before", date::format_date(before))]);
+    }
+
+    // The popularity is the number of upvotes. Other `--sort` values are
+    // applied to the fetched issues. Merge requests can't be sorted by
+    // popularity, so issues and merge requests are both sorted by their update
+    // time to merge them.
+    if filters.include_prs {
+        request = request.query(&[("order_by", "updated_at"), ("sort", "desc")]);
+    } else if let Some(IssueSort::Reactions) = filters.sort {
+        request = request.query(&[("order_by", "popularity"), ("sort", "desc")]);
+    }
+
+    Ok(request)
+}
+
+fn build_merge_requests_request(
+    http_client: &HttpClient,
+    url: &str,
+    filters: &ListPrsFilters,
+    use_auth: bool,
+) -> anyhow::Result<RequestBuilder> {
+    let mut request = http_client
+        .get(url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("state", filters.state.as_gitlab_str())])
+        .query(&[("page", filters.page)])
+        .query(&[("per_page", filters.per_page)]);
+
+    if let Some(author_name) = filters.author {
+        request = request.query(&[("author_username", author_name)]);
+    }
+
+    if !filters.labels.include.is_empty() {
+        request = request.query(&[("labels", filters.labels.include.join(","))]);
+    }
+
+    if !filters.labels.exclude.is_empty() {
+        request = request.query(&[("not[labels]", filters.labels.exclude.join(","))]);
+    }
+
+    if let Some(milestone) = filters.milestone {
+        request = request.query(&[("milestone", milestone)]);
+    }
+
+    if let Some(query) = filters.query {
+        request = request.query(&[("search", query)]);
+    }
+
+    if filters.draft {
+        request = request.query(&[("wip", "yes")]);
+    }
+
+    Ok(request)
+}
+
+/// GitLab can't filter by the merge or close date. Items that were merged or
+/// closed after `filters.since` were updated after it as well, so filtering
+/// by the update date only leaves the rest to the caller.
+fn fetch_changelog_entries<T>(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    endpoint: &str,
+    state: &str,
+    filters: &ChangelogFilters,
+    use_auth: bool,
+) -> anyhow::Result<PaginatedResponse<ChangelogEntry>>
+where
+    T: DeserializeOwned,
+    ChangelogEntry: From<T>,
+{
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/{endpoint}");
+    let mut request = http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .query(&[("state", state)])
+        .query(&[("page", filters.page)])
+        .query(&[("per_page", filters.per_page)]);
+
+    if let Some(milestone) = filters.milestone {
+        request = request.query(&[("milestone", milestone)]);
+    }
+
+    if let Some(since) = filters.since {
+        request = request.query(&[("updated_after", since)]);
+    }
+
+    let response = request
+        .send_throttled()
+        .context("Network request failed while fetching the changelog from GitLab")?
+        .with_http_status_ok()?;
+
+    let has_next_page = http_client::has_next_link_header(&response);
+
+    response
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+        .map(|vec: Vec<T>| vec.into_paginated_response(has_next_page))
+}
+
+/// Parses the `changes_count` of a merge request. GitLab caps it, e.g. at
+/// `1000+`, in which case the cap is returned.
+fn parse_changes_count(changes_count: &str) -> Option<u32> {
+    changes_count.trim_end_matches('+').parse().ok()
+}
+
+/// Fetches every page of a listing with the most items per page.
+fn get_json_pages<T: DeserializeOwned>(
+    http_client: &HttpClient,
+    url: &str,
+    query: &[(&str, &str)],
+    use_auth: bool,
+) -> anyhow::Result<Vec<T>> {
+    let mut items = vec![];
+
+    for page in 1.. {
+        let response = http_client
+            .get(url)
+            .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+            .query(query)
+            .query(&[("page", page), ("per_page", 100)])
+            .send_throttled()
+            .context("Network request failed while fetching data from GitLab")?
+            .with_http_status_ok()?;
+        let has_next_page = http_client::has_next_link_header(&response);
+        let page_items: Vec<T> = response
+            .parse_json()
+            .context("Failed to parse GitLab API response")?;
+
+        items.extend(page_items);
+
+        if !has_next_page {
+            break;
+        }
+    }
+
+    Ok(items)
+}
+
+fn build_api_base_url(remote: &GitRemoteData) -> String {
+    match &remote.api_base_url {
+        Some(url) => url.clone(),
+        None => format!("{}/api/v4", remote.web_base_url),
+    }
+}
+
+fn build_web_base_url(remote: &GitRemoteData) -> String {
+    format!("{}/{}", remote.web_base_url, remote.path)
+}
+
+fn encode_repo_path(repo_path: &str) -> String {
+    url_encoding::encode_component(repo_path)
+}
+
+fn fetch_approvals(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    pr_number: u32,
+    use_auth: bool,
+) -> anyhow::Result<GitLabApprovals> {
+    let base_url = match api_url {
+        Some(url) => url,
+        None => &build_api_base_url(remote),
+    };
+    let encoded_path = encode_repo_path(&remote.path);
+    let url = format!("{base_url}/projects/{encoded_path}/merge_requests/{pr_number}/approvals");
+
+    http_client
+        .get(&url)
+        .with_auth(use_auth, AUTH_TOKEN, AUTH_SCHEME)?
+        .send_throttled()
+        .context("Network request failed while fetching merge request approvals from GitLab")?
+        .with_http_status_ok()?
+        .parse_json()
+        .context("Failed to parse GitLab API response")
+}
+
+/// Strips the draft marker from a merge request title like GitLab's UI does.
+fn strip_draft_title_prefix(title: &str) -> &str {
+    let trimmed = title.trim_start();
+
+    for prefix in DRAFT_TITLE_PREFIXES {
+        if let Some(candidate) = trimmed.get(..prefix.len())
+            && candidate.eq_ignore_ascii_case(prefix)
+        {
+            return trimmed[prefix.len()..].trim_start();
+        }
+    }
+
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::forge::LabelFilter;
+
+    const URL: &str = "https://gitlab.com/api/v4/projects/group%2Fsub%2Frepo/issues";
+
+    fn create_git_remote() -> GitRemoteData {
+        GitRemoteData {
+            host: "gitlab.com".to_string(),
+            path: "group/sub/repo".to_string(),
+            port: None,
+            web_base_url: "https://gitlab.com".to_string(),
+            api_base_url: None,
+        }
+    }
+
+    #[test]
+    fn test_build_base_urls() {
+        let mut remote = create_git_remote();
+
+        assert_eq!(build_api_base_url(&remote), "https://gitlab.com/api/v4");
+        assert_eq!(
+            build_web_base_url(&remote),
+            "https://gitlab.com/group/sub/repo"
+        );
+
+        // E.g. self-hosted over plain http with the SSH port in the remote URL
+        remote.host = "git.internal".to_string();
+        remote.port = Some(2222);
+        remote.web_base_url = "http://git.internal:3000".to_string();
+
+        assert_eq!(
+            build_api_base_url(&remote),
+            "http://git.internal:3000/api/v4"
+        );
+        assert_eq!(
+            build_web_base_url(&remote),
+            "http://git.internal:3000/group/sub/repo"
+        );
+
+        remote.api_base_url = Some("https://api.git.internal".to_string());
+
+        assert_eq!(build_api_base_url(&remote), "https://api.git.internal");
+    }
+
+    #[test]
+    fn test_strip_draft_title_prefix() {
+        assert_eq!(strip_draft_title_prefix("Draft: Add login"), "Add login");
+        assert_eq!(strip_draft_title_prefix("draft:Add login"), "Add login");
+        assert_eq!(strip_draft_title_prefix("[Draft] Add login"), "Add login");
+        assert_eq!(strip_draft_title_prefix("(Draft) Add login"), "Add login");
+        assert_eq!(strip_draft_title_prefix("WIP: Add login"), "Add login");
+        assert_eq!(
+            strip_draft_title_prefix("Add draft: login"),
+            "Add draft: login"
+        );
+        assert_eq!(strip_draft_title_prefix("Drafting docs"), "Drafting docs");
+    }
+
+    #[test]
+    fn test_encode_repo_path() {
+        assert_eq!(encode_repo_path("group/sub/repo"), "group%2Fsub%2Frepo");
+    }
+
+    #[test]
+    fn test_get_url_for_management_pages() {
+        let remote = create_git_remote();
+
+        assert_eq!(
+            get_url_for_labels(&remote),
+            "https://gitlab.com/group/sub/repo/-/labels"
+        );
+        assert_eq!(
+            get_url_for_milestones(&remote),
+            "https://gitlab.com/group/sub/repo/-/milestones"
+        );
+        assert_eq!(
+            get_url_for_releases(&remote),
+            "https://gitlab.com/group/sub/repo/-/releases"
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_pr_creation_encodes_branches() {
+        let url = get_url_for_pr_creation(&create_git_remote(), "main", "fix/#42");
+
+        assert_eq!(
+            url,
+            "https://gitlab.com/group/sub/repo/merge_requests/new?merge_request[source_branch]=fix%2F%2342&merge_request[target_branch]=main"
+        );
+    }
+
+    #[test]
+    fn test_supports() {
+        assert_eq!(supports(Capability::DraftPrs), Support::Yes);
+        assert_eq!(supports(Capability::MergedStateFilter), Support::Yes);
+        assert!(matches!(
+            supports(Capability::Reactions),
+            Support::Degraded(_)
+        ));
+        assert!(matches!(supports(Capability::IssuePinning), Support::No(_)));
+        assert_eq!(supports(Capability::RemoveSourceBranch), Support::Yes);
+        assert_eq!(supports(Capability::SquashOnMerge), Support::Yes);
+        assert!(matches!(supports(Capability::HookToggle), Support::No(_)));
+    }
+
+    #[test]
+    fn test_hooks_fixture_redacts_secrets() {
+        let hooks: Vec<GitLabHook> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/gitlab/hooks.json"
+        ))
+        .unwrap();
+        let hooks: Vec<Hook> = hooks.into_iter().map(Hook::from).collect();
+
+        assert_eq!(
+            hooks,
+            vec![
+                Hook {
+                    id: 101,
+                    url: String::from("https://ci.example.com/hooks/***?token=***&project=app"),
+                    events: vec![
+                        String::from("merge_requests"),
+                        String::from("pipeline"),
+                        String::from("push"),
+                    ],
+                    active: true,
+                    last_delivery: None,
+                },
+                Hook {
+                    id: 102,
+                    url: String::from("https://***@chat.example.com/notify"),
+                    events: vec![String::from("releases")],
+                    active: false,
+                    last_delivery: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_create_pr_request_body() {
+        let options = CreatePrOptions {
+            title: "Fix crash",
+            source_branch: "fix",
+            target_branch: "main",
+            body: "",
+            draft: false,
+            native_draft: true,
+            labels: &[String::from("bug"), String::from("ui")],
+            no_maintainer_edit: false,
+            remove_source_branch: false,
+            squash_on_merge: false,
+        };
+
+        assert_eq!(
+            build_create_pr_request_body(&options),
+            serde_json::json!({
+                "source_branch": "fix",
+                "target_branch": "main",
+                "title": "Fix crash",
+                "description": "",
+                "labels": "bug,ui",
+            })
+        );
+
+        let request_body = build_create_pr_request_body(&CreatePrOptions {
+            draft: true,
+            remove_source_branch: true,
+            squash_on_merge: true,
+            ..options
+        });
+
+        assert_eq!(request_body["title"], "Draft: Fix crash");
+        assert_eq!(request_body["remove_source_branch"], true);
+        assert_eq!(request_body["squash"], true);
+    }
+
+    #[test]
+    fn test_set_issue_pinned_is_unsupported() {
+        let error =
+            set_issue_pinned(&HttpClient::new(), &create_git_remote(), None, 1, true).unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("GitLab does not support pinning issues")
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_issue_comment() {
+        let url = get_url_for_issue_comment(&create_git_remote(), 42, 123);
+
+        assert_eq!(
+            url,
+            "https://gitlab.com/group/sub/repo/-/issues/42#note_123"
+        );
+    }
+
+    #[test]
+    fn test_get_url_for_path_encodes_path() {
+        let url = get_url_for_path(&create_git_remote(), "docs/my file ü.md", "HEAD", None);
+
+        assert_eq!(
+            url,
+            "https://gitlab.com/group/sub/repo/-/blob/HEAD/docs/my%20file%20%C3%BC.md"
+        );
+    }
+
+    #[test]
+    fn test_parse_changes_count() {
+        assert_eq!(parse_changes_count("12"), Some(12));
+        assert_eq!(parse_changes_count("1000+"), Some(1000));
+        assert_eq!(parse_changes_count(""), None);
+    }
+
+    #[test]
+    fn test_created_items_match_golden_files() {
+        let issues: Vec<GitLabIssue> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/gitlab/issue.json"
+        ))
+        .unwrap();
+        let prs: Vec<GitLabMergeRequest> = serde_json::from_str(include_str!(
+            "../../../tests/src/server/data/gitlab/mr.json"
+        ))
+        .unwrap();
+        let issue = Issue::from(issues.into_iter().next().unwrap());
+        let pr = Pr::from(prs.into_iter().next().unwrap());
+
+        // The output of `issue create --format json` and `pr create --format json`
+        assert_eq!(
+            serde_json::to_string_pretty(&issue).unwrap() + "\n",
+            include_str!("../../../tests/golden/gitlab/issue.json")
+        );
+        assert_eq!(
+            serde_json::to_string_pretty(&pr).unwrap() + "\n",
+            include_str!("../../../tests/golden/gitlab/pr.json")
+        );
+    }
+
+    fn issue_filters(state: &IssueState) -> ListIssueFilters<'_> {
+        ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: LabelFilter::NONE,
+            min_reactions: None,
+            page: 1,
+            pinned: false,
+            per_page: 30,
+            query: None,
+            sort: None,
+            state,
+            updated: DateRange::default(),
+        }
+    }
+
+    fn pr_filters(state: &PrState) -> ListPrsFilters<'_> {
+        ListPrsFilters {
+            author: None,
+            labels: LabelFilter::NONE,
+            milestone: None,
+            page: 1,
+            per_page: 30,
+            query: None,
+            state,
+            draft: false,
+            review: None,
+            all_pages: false,
+            limit: None,
+        }
+    }
+
+    fn get_query_pairs(request: RequestBuilder) -> Vec<(String, String)> {
+        request
+            .build()
+            .unwrap()
+            .url()
+            .query_pairs()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_issues_request_translates_states() {
+        let http_client = HttpClient::new();
+
+        for (state, expected) in [
+            (IssueState::Open, "opened"),
+            (IssueState::Closed, "closed"),
+            (IssueState::All, "all"),
+        ] {
+            let request =
+                build_issues_request(&http_client, URL, &issue_filters(&state), false).unwrap();
+
+            assert_eq!(
+                get_query_pairs(request),
+                [
+                    ("state".to_string(), expected.to_string()),
+                    ("page".to_string(), "1".to_string()),
+                    ("per_page".to_string(), "30".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_issues_request_sorts_by_popularity() {
+        let filters = ListIssueFilters {
+            sort: Some(&IssueSort::Reactions),
+            ..issue_filters(&IssueState::Open)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(get_query_pairs(request).ends_with(&[
+            ("order_by".to_string(), "popularity".to_string()),
+            ("sort".to_string(), "desc".to_string()),
+        ]));
+
+        // Sorting by the close date happens after fetching
+        let filters = ListIssueFilters {
+            sort: Some(&IssueSort::Closed),
+            ..issue_filters(&IssueState::Open)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(
+            !get_query_pairs(request)
+                .iter()
+                .any(|(key, _)| key == "order_by")
+        );
+
+        // Issues and merge requests are merged by their update time
+        let filters = ListIssueFilters {
+            include_prs: true,
+            sort: Some(&IssueSort::Reactions),
+            ..issue_filters(&IssueState::Open)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(get_query_pairs(request).ends_with(&[
+            ("order_by".to_string(), "updated_at".to_string()),
+            ("sort".to_string(), "desc".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn test_build_issues_request_with_dates() {
+        let filters = ListIssueFilters {
+            closed: DateRange {
+                after: Some(date::parse_date("2024-05-01").unwrap()),
+                before: None,
+            },
+            created: DateRange {
+                after: Some(date::parse_date("2024-03-01").unwrap()),
+                before: Some(date::parse_date("2024-04-01").unwrap()),
+            },
+            ..issue_filters(&IssueState::Closed)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(get_query_pairs(request).ends_with(&[
+            (
+                "created_after".to_string(),
+                "2024-03-01T00:00:00Z".to_string()
+            ),
+            (
+                "created_before".to_string(),
+                "2024-03-31T23:59:59Z".to_string()
+            ),
+            (
+                "updated_after".to_string(),
+                "2024-05-01T00:00:00Z".to_string()
+            ),
+        ]));
+    }
+
+    #[test]
+    fn test_build_issues_request_with_updated_dates() {
+        let filters = ListIssueFilters {
+            updated: DateRange {
+                after: Some(date::parse_date("2024-05-01").unwrap()),
+                before: Some(date::parse_date("2024-06-01").unwrap()),
+            },
+            ..issue_filters(&IssueState::All)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(get_query_pairs(request).ends_with(&[
+            (
+                "updated_after".to_string(),
+                "2024-05-01T00:00:00Z".to_string()
+            ),
+            (
+                "updated_before".to_string(),
+                "2024-05-31T23:59:59Z".to_string()
+            ),
+        ]));
+    }
+
+    #[test]
+    fn test_build_issues_request_excludes_labels() {
+        let labels = LabelFilter::parse(&[String::from("bug"), String::from("-triaged")]);
+        let filters = ListIssueFilters {
+            labels: &labels,
+            ..issue_filters(&IssueState::Open)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(get_query_pairs(request).ends_with(&[
+            ("labels".to_string(), "bug".to_string()),
+            ("not[labels]".to_string(), "triaged".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn test_build_issues_request_without_labels() {
+        let labels = LabelFilter {
+            unlabeled: true,
+            ..LabelFilter::default()
+        };
+        let filters = ListIssueFilters {
+            labels: &labels,
+            sort: Some(&IssueSort::Reactions),
+            ..issue_filters(&IssueState::Open)
+        };
+        let request = build_issues_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(get_query_pairs(request).ends_with(&[
+            ("labels".to_string(), "None".to_string()),
+            ("order_by".to_string(), "popularity".to_string()),
+            ("sort".to_string(), "desc".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn test_build_merge_requests_request_translates_states() {
+        let http_client = HttpClient::new();
+
+        for (state, expected) in [
+            (PrState::Open, "opened"),
+            (PrState::Closed, "closed"),
+            (PrState::Merged, "merged"),
+            (PrState::All, "all"),
+        ] {
+            let request =
+                build_merge_requests_request(&http_client, URL, &pr_filters(&state), false)
+                    .unwrap();
+
+            assert_eq!(
+                get_query_pairs(request),
+                [
+                    ("state".to_string(), expected.to_string()),
+                    ("page".to_string(), "1".to_string()),
+                    ("per_page".to_string(), "30".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_merge_requests_request_with_milestone() {
+        let filters = ListPrsFilters {
+            milestone: Some("v2.0"),
+            ..pr_filters(&PrState::Merged)
+        };
+        let request =
+            build_merge_requests_request(&HttpClient::new(), URL, &filters, false).unwrap();
+
+        assert!(
+            get_query_pairs(request).ends_with(&[("milestone".to_string(), "v2.0".to_string())])
+        );
+    }
 }
@@ -1,6 +1,24 @@
-use reqwest::blocking::{RequestBuilder, Response};
+use std::time::Duration;
+
+use reqwest::{
+    Method, StatusCode,
+    blocking::{RequestBuilder, Response},
+};
+use serde::de::DeserializeOwned;
+use time::OffsetDateTime;
+
+use crate::{
+    audit,
+    cli::forge::{auth_guard, forgejo, identity},
+    rate_limit, throttle,
+};
 
 const USER_AGENT: &str = "git-forge";
+/// Number of characters of the response body to show when parsing failed.
+const BODY_PREVIEW_LENGTH: usize = 200;
+/// Upper bound for a request. It also bounds how long the thread of a fetch
+/// that was cancelled in the TUI keeps running.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct HttpClient {
     reqwest_client: reqwest::blocking::Client,
@@ -9,20 +27,64 @@ pub struct HttpClient {
 impl HttpClient {
     pub fn new() -> Self {
         Self {
-            reqwest_client: reqwest::blocking::Client::new(),
+            reqwest_client: reqwest::blocking::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to initialize the HTTP client"),
         }
     }
 
     pub fn get(&self, url: &str) -> RequestBuilder {
+        log::debug!("GET {url}");
+
         self.reqwest_client
             .get(url)
-            .header("User-Agent", USER_AGENT)
+            .header("User-Agent", get_user_agent(url))
     }
 
     pub fn post(&self, url: &str) -> RequestBuilder {
+        log::debug!("POST {url}");
+        audit::begin_request("POST", url);
+
         self.reqwest_client
             .post(url)
-            .header("User-Agent", USER_AGENT)
+            .header("User-Agent", get_user_agent(url))
+    }
+
+    pub fn patch(&self, url: &str) -> RequestBuilder {
+        log::debug!("PATCH {url}");
+        audit::begin_request("PATCH", url);
+
+        self.reqwest_client
+            .patch(url)
+            .header("User-Agent", get_user_agent(url))
+    }
+
+    pub fn put(&self, url: &str) -> RequestBuilder {
+        log::debug!("PUT {url}");
+        audit::begin_request("PUT", url);
+
+        self.reqwest_client
+            .put(url)
+            .header("User-Agent", get_user_agent(url))
+    }
+
+    pub fn delete(&self, url: &str) -> RequestBuilder {
+        log::debug!("DELETE {url}");
+        audit::begin_request("DELETE", url);
+
+        self.reqwest_client
+            .delete(url)
+            .header("User-Agent", get_user_agent(url))
+    }
+}
+
+/// Forgejo instances get their own user agent, so that their admins can tell
+/// the requests apart from the ones to Gitea.
+fn get_user_agent(url: &str) -> &'static str {
+    match reqwest::Url::parse(url) {
+        Ok(url) if forgejo::is_forgejo_url(&url) => forgejo::USER_AGENT,
+        _ => USER_AGENT,
     }
 }
 
@@ -68,9 +130,14 @@ impl WithAuth for RequestBuilder {
         auth_scheme: &str,
     ) -> anyhow::Result<RequestBuilder> {
         if !use_auth {
+            log::debug!("Sending request without authentication");
+
             return Ok(self);
         }
 
+        let (client, request) = self.build_split();
+        let request = request?;
+        let env_var = forgejo::get_auth_env_var(request.url(), env_var);
         let token = match std::env::var(env_var) {
             Ok(token) => token,
             Err(e) => {
@@ -80,36 +147,199 @@ impl WithAuth for RequestBuilder {
             }
         };
 
-        Ok(self.header("Authorization", format!("{auth_scheme} {token}")))
+        // Check the host of the final URL, so that a typo in --api-url
+        // doesn't leak the token.
+        auth_guard::check(request.url(), env_var)?;
+
+        Ok(RequestBuilder::from_parts(client, request)
+            .header("Authorization", format!("{auth_scheme} {token}")))
+    }
+}
+
+pub trait SendThrottled {
+    /// Like `RequestBuilder::send()`, but requests that change something keep
+    /// the minimum interval of `http/mutation-interval-ms` to the previous one.
+    /// A request refused with a 403 or 429 and a `Retry-After` header is sent
+    /// once more after the delay. See `throttle`.
+    fn send_throttled(self) -> reqwest::Result<Response>;
+}
+
+impl SendThrottled for RequestBuilder {
+    fn send_throttled(self) -> reqwest::Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let is_mutation = !matches!(*request.method(), Method::GET | Method::HEAD);
+        // Only requests with a buffered body can be sent again, which are all
+        // of the forge requests
+        let mut retry = request.try_clone();
+        let can_retry = retry.is_some();
+        let mut request = Some(request);
+
+        throttle::send(
+            is_mutation,
+            || {
+                let request = request
+                    .take()
+                    .or_else(|| retry.take())
+                    .expect("A request is only retried once");
+
+                client.execute(request)
+            },
+            |response| {
+                if !can_retry {
+                    return None;
+                }
+
+                throttle::get_retry_after(
+                    response.status(),
+                    response.headers(),
+                    OffsetDateTime::now_utc().unix_timestamp(),
+                )
+            },
+        )
     }
 }
 
 pub trait WithHttpStatusOk {
-    /// Middleware to make sure that we have a 200 status.
+    /// Middleware to make sure that we have a 200 status. It also records the
+    /// rate limit headers for `--show-rate-limit` and the outcome of changing
+    /// requests for the audit log.
     fn with_http_status_ok(self) -> anyhow::Result<Response>;
 }
 
 impl WithHttpStatusOk for Response {
     fn with_http_status_ok(self) -> anyhow::Result<Response> {
+        rate_limit::record(self.url(), self.headers());
+        audit::finish_request(self.url(), self.status());
+
         let url = self.url().to_string();
         let status = self.status();
 
+        if status == StatusCode::UNAUTHORIZED {
+            identity::invalidate(self.url());
+        }
+
         if !status.is_success() {
             let error_body = self
                 .text()
                 .unwrap_or_else(|_| String::from("(unable to read response body)"));
 
-            anyhow::bail!(
+            let mut message = format!(
                 "HTTP {status}\n\
                  URL: {url}\n\
-                 Response: {error_body}",
+                 Response: {error_body}"
             );
+
+            if let Some(hint) = get_error_hint(status, &error_body) {
+                message.push_str(&format!("\nHint: {hint}"));
+            }
+
+            anyhow::bail!(message);
         }
 
         Ok(self)
     }
 }
 
+/// Checks whether the requested resource exists, e.g. a branch. A 404 means
+/// that it doesn't, while other failed statuses are errors.
+pub fn is_found(response: Response) -> anyhow::Result<bool> {
+    if response.status() == StatusCode::NOT_FOUND {
+        rate_limit::record(response.url(), response.headers());
+        audit::finish_request(response.url(), response.status());
+
+        return Ok(false);
+    }
+
+    response.with_http_status_ok()?;
+
+    Ok(true)
+}
+
+/// Gets advice for failed requests whose cause the forges tell apart. They
+/// refuse changes to archived repositories with a 403, 422 or 423 whose
+/// message mentions the archival, e.g. GitHub's "Repository was archived so is
+/// read-only". Other 403s mean that the token lacks a permission, except for
+/// GitHub's rate limits.
+fn get_error_hint(status: StatusCode, body: &str) -> Option<&'static str> {
+    let body = body.to_lowercase();
+    let is_refusal = matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::UNPROCESSABLE_ENTITY | StatusCode::LOCKED
+    );
+
+    if is_refusal && body.contains("archived") {
+        return Some(
+            "The repository is archived and read-only. Issues and pull requests can't be created or changed until it's unarchived",
+        );
+    }
+
+    if status == StatusCode::FORBIDDEN && !body.contains("rate limit") {
+        return Some(
+            "The token lacks the permission for this. Check its scopes and your role in the repository",
+        );
+    }
+
+    None
+}
+
+pub trait ParseJson {
+    /// Like `Response::json()` but with an error that tells what went wrong:
+    /// the serde error with its position, the content type, the start of the
+    /// body and a hint if the body is an HTML page instead of JSON.
+    fn parse_json<T: DeserializeOwned>(self) -> anyhow::Result<T>;
+}
+
+impl ParseJson for Response {
+    fn parse_json<T: DeserializeOwned>(self) -> anyhow::Result<T> {
+        let url = self.url().to_string();
+        let content_type = self
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = self.text()?;
+
+        parse_json_body(&body, &url, content_type.as_deref())
+    }
+}
+
+fn parse_json_body<T: DeserializeOwned>(
+    body: &str,
+    url: &str,
+    content_type: Option<&str>,
+) -> anyhow::Result<T> {
+    let error = match serde_json::from_str(body) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+
+    let mut preview: String = body.chars().take(BODY_PREVIEW_LENGTH).collect();
+
+    if preview.len() < body.len() {
+        preview.push('…');
+    }
+
+    let content_type = content_type.unwrap_or("(none)");
+    let mut message = format!(
+        "{error}\n\
+         URL: {url}\n\
+         Content-Type: {content_type}\n\
+         Body: {preview}"
+    );
+
+    let looks_like_html = content_type.contains("text/html") || body.trim_start().starts_with('<');
+
+    if looks_like_html {
+        message.push_str(&format!(
+            "\nHint: The response is an HTML page instead of JSON. This usually \
+             means authentication is required or the API URL is wrong (tried {url})"
+        ));
+    }
+
+    anyhow::bail!(message)
+}
+
 pub fn has_next_link_header(response: &Response) -> bool {
     response
         .headers()
@@ -122,3 +352,105 @@ pub fn has_next_link_header(response: &Response) -> bool {
         })
         .unwrap_or(false)
 }
+
+/// Reads a header with the total number of items of a paginated listing, e.g.
+/// GitLab's `x-total` or Gitea's `x-total-count`. This allows counting items
+/// with a page size of 1.
+pub fn get_total_count_header(response: &Response, name: &str) -> Option<u32> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    const URL: &str = "https://example.com/api/v4/projects/1/issues";
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Item {
+        id: u32,
+        title: String,
+    }
+
+    #[test]
+    fn test_parse_json_body_valid() {
+        let items: Vec<Item> = parse_json_body(
+            r#"[{"id": 1, "title": "a"}]"#,
+            URL,
+            Some("application/json"),
+        )
+        .unwrap();
+
+        assert_eq!(items.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_json_body_missing_field() {
+        let error = parse_json_body::<Vec<Item>>(r#"[{"id": 1}]"#, URL, Some("application/json"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("missing field `title` at line 1 column 10"));
+        assert!(error.contains("Content-Type: application/json"));
+        assert!(!error.contains("Hint:"));
+    }
+
+    #[test]
+    fn test_parse_json_body_html_login_page() {
+        let body = format!("<!DOCTYPE html><html>{}</html>", "x".repeat(300));
+        let error = parse_json_body::<Vec<Item>>(&body, URL, Some("text/html; charset=utf-8"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("Hint: The response is an HTML page"));
+        assert!(error.contains(&format!("tried {URL}")));
+        assert!(error.contains(&format!("Body: {}…", &body[..BODY_PREVIEW_LENGTH])));
+    }
+
+    #[test]
+    fn test_parse_json_body_html_without_content_type() {
+        let error = parse_json_body::<Vec<Item>>("  <html></html>", URL, None)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("Content-Type: (none)"));
+        assert!(error.contains("Hint:"));
+    }
+
+    #[test]
+    fn test_get_error_hint() {
+        let archived_hint = get_error_hint(
+            StatusCode::FORBIDDEN,
+            r#"{"message":"Repository was archived so is read-only."}"#,
+        );
+
+        assert!(archived_hint.is_some_and(|hint| hint.contains("archived")));
+        assert_eq!(
+            get_error_hint(StatusCode::LOCKED, r#"{"message":"repo is archived"}"#),
+            archived_hint
+        );
+
+        let permission_hint =
+            get_error_hint(StatusCode::FORBIDDEN, r#"{"message":"403 Forbidden"}"#);
+
+        assert!(permission_hint.is_some_and(|hint| hint.contains("permission")));
+        assert_eq!(
+            get_error_hint(
+                StatusCode::FORBIDDEN,
+                r#"{"message":"API rate limit exceeded for 127.0.0.1."}"#
+            ),
+            None
+        );
+        assert_eq!(
+            get_error_hint(StatusCode::NOT_FOUND, r#"{"message":"archived"}"#),
+            None
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Cache of the authenticated user, e.g. for assigning issues to oneself.
+//!
+//! Looking up the user costs a request per command, which is noticeable on
+//! slow instances. So the user is stored in the `identity` category for a day,
+//! keyed by the API host and a hash of the token. The token itself is never
+//! written to disk. A response with status 401 forgets the users of its host,
+//! and --refresh-identity skips the cache.
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{
+    cli::forge::forgejo,
+    storage::{self, StorageCategory},
+};
+
+/// How long a cached user is used before it's fetched again.
+const TTL_SECONDS: i64 = 24 * 60 * 60;
+
+static REFRESH: AtomicBool = AtomicBool::new(false);
+
+/// The user a token belongs to.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Identity {
+    pub username: String,
+    /// GitLab expects user ids instead of usernames, e.g. for assignees.
+    pub id: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedIdentity {
+    identity: Identity,
+    /// Unix timestamp of when the user was fetched.
+    fetched_at: i64,
+}
+
+/// Sets whether the cached users are ignored and fetched again
+/// (--refresh-identity).
+pub fn set_refresh(refresh: bool) {
+    REFRESH.store(refresh, Ordering::Relaxed);
+}
+
+/// Gets the user the token of `env_var` belongs to. The user is taken from the
+/// cache unless it's older than a day. Otherwise, it's fetched with `fetch` and
+/// cached. Every lookup of the authenticated user should go through here.
+pub fn get_identity(
+    base_url: &str,
+    env_var: &str,
+    fetch: impl FnOnce() -> anyhow::Result<Identity>,
+) -> anyhow::Result<Identity> {
+    let Some(path) = get_cache_path_for_env_var(base_url, env_var) else {
+        return fetch();
+    };
+
+    get_identity_at(
+        &path,
+        OffsetDateTime::now_utc().unix_timestamp(),
+        REFRESH.load(Ordering::Relaxed),
+        fetch,
+    )
+}
+
+/// Forgets the cached users of the host of `url`, e.g. because it rejected a
+/// token.
+pub fn invalidate(url: &Url) {
+    let Ok(identity_dir) = storage::get_category_dir(StorageCategory::Identity) else {
+        return;
+    };
+    let Some(host_dir) = get_host_dir(&identity_dir, url) else {
+        return;
+    };
+
+    if let Err(e) = storage::remove_dir(&host_dir) {
+        log::debug!("Failed to forget the cached users of {url}: {e:#}");
+    }
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+fn get_cache_path_for_env_var(base_url: &str, env_var: &str) -> Option<PathBuf> {
+    let url = Url::parse(base_url).ok()?;
+    let token = std::env::var(forgejo::get_auth_env_var(&url, env_var)).ok()?;
+    let identity_dir = storage::get_category_dir(StorageCategory::Identity).ok()?;
+
+    get_cache_path(&identity_dir, &url, &token)
+}
+
+fn get_identity_at(
+    path: &Path,
+    now: i64,
+    refresh: bool,
+    fetch: impl FnOnce() -> anyhow::Result<Identity>,
+) -> anyhow::Result<Identity> {
+    if !refresh && let Some(identity) = read_cached_identity(path, now) {
+        log::debug!("Using the cached user {}", identity.username);
+
+        return Ok(identity);
+    }
+
+    let identity = fetch()?;
+    let cached_identity = CachedIdentity {
+        identity,
+        fetched_at: now,
+    };
+
+    // Caching is best-effort; we can always fetch the user again
+    if let Err(e) = storage::write_json(path, &cached_identity) {
+        log::debug!("Failed to cache the authenticated user: {e:#}");
+    }
+
+    Ok(cached_identity.identity)
+}
+
+fn read_cached_identity(path: &Path, now: i64) -> Option<Identity> {
+    match storage::read_json::<CachedIdentity>(path) {
+        Ok(Some(cached)) if is_fresh(cached.fetched_at, now) => Some(cached.identity),
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("Ignoring the cached user: {e:#}");
+
+            None
+        }
+    }
+}
+
+/// Whether a user fetched at `fetched_at` may still be used. Timestamps in the
+/// future, e.g. because the clock was changed, count as expired.
+fn is_fresh(fetched_at: i64, now: i64) -> bool {
+    (0..TTL_SECONDS).contains(&(now - fetched_at))
+}
+
+fn get_host_dir(identity_dir: &Path, url: &Url) -> Option<PathBuf> {
+    let host = url.host_str()?.to_lowercase();
+    let host = match url.port() {
+        Some(port) => format!("{host}_{port}"),
+        None => host,
+    };
+
+    Some(identity_dir.join(host))
+}
+
+/// The hash only needs to tell tokens apart. If it changes with a Rust update,
+/// the user is fetched once more.
+fn get_cache_path(identity_dir: &Path, url: &Url, token: &str) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+
+    token.hash(&mut hasher);
+
+    Some(get_host_dir(identity_dir, url)?.join(format!("{:016x}.json", hasher.finish())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn create_identity(username: &str) -> Identity {
+        Identity {
+            username: username.to_string(),
+            id: 42,
+        }
+    }
+
+    /// Looks up the user at `now` and returns it and whether it was fetched.
+    fn lookup(path: &Path, now: i64, refresh: bool, username: &str) -> (Identity, bool) {
+        let fetched = Cell::new(false);
+        let identity = get_identity_at(path, now, refresh, || {
+            fetched.set(true);
+
+            Ok(create_identity(username))
+        })
+        .unwrap();
+
+        (identity, fetched.get())
+    }
+
+    #[test]
+    fn test_get_identity_caches_for_a_day() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("host/token.json");
+
+        assert_eq!(
+            lookup(&path, NOW, false, "alice"),
+            (create_identity("alice"), true)
+        );
+        assert_eq!(
+            lookup(&path, NOW + TTL_SECONDS - 1, false, "bob"),
+            (create_identity("alice"), false)
+        );
+        assert_eq!(
+            lookup(&path, NOW + TTL_SECONDS, false, "bob"),
+            (create_identity("bob"), true)
+        );
+        assert_eq!(
+            lookup(&path, NOW + TTL_SECONDS + 1, false, "carol"),
+            (create_identity("bob"), false)
+        );
+    }
+
+    #[test]
+    fn test_get_identity_refetches_on_refresh_and_clock_changes() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("host/token.json");
+
+        lookup(&path, NOW, false, "alice");
+
+        assert_eq!(
+            lookup(&path, NOW, true, "bob"),
+            (create_identity("bob"), true)
+        );
+        assert_eq!(
+            lookup(&path, NOW - 1, false, "carol"),
+            (create_identity("carol"), true)
+        );
+    }
+
+    #[test]
+    fn test_get_identity_does_not_cache_failures() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("host/token.json");
+        let result = get_identity_at(&path, NOW, false, || anyhow::bail!("HTTP 401"));
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_invalidating_the_host_dir_forgets_all_tokens() {
+        let root = tempfile::tempdir().unwrap();
+        let url = Url::parse("https://git.internal:3000/api/v1").unwrap();
+        let path_a = get_cache_path(root.path(), &url, "token-a").unwrap();
+        let path_b = get_cache_path(root.path(), &url, "token-b").unwrap();
+
+        assert_ne!(path_a, path_b);
+        assert!(path_a.starts_with(root.path().join("git.internal_3000")));
+
+        lookup(&path_a, NOW, false, "alice");
+        lookup(&path_b, NOW, false, "bob");
+
+        storage::remove_dir(&get_host_dir(root.path(), &url).unwrap()).unwrap();
+
+        assert_eq!(
+            lookup(&path_a, NOW, false, "carol"),
+            (create_identity("carol"), true)
+        );
+        assert_eq!(
+            lookup(&path_b, NOW, false, "dave"),
+            (create_identity("dave"), true)
+        );
+    }
+}
@@ -0,0 +1,438 @@
+//! Checks of the labels of new issues and PRs, of label changes and the label
+//! filters of the list commands.
+//!
+//! The forges handle labels that don't exist in the repository differently:
+//! GitHub drops them, GitLab creates them and Gitea/Forgejo fail. So the create
+//! commands resolve the requested labels against the labels of the repository
+//! first and handle the missing ones the same way on every forge, see
+//! [`MissingLabels`].
+
+use anyhow::Context;
+use clap::ValueEnum;
+
+use crate::{
+    cli::forge::{ApiType, HttpClient, forgejo, function, gitea, github, gitlab},
+    git::GitRemoteData,
+};
+
+/// Colors for created labels. These are the colors of GitHub's default labels.
+const LABEL_COLORS: [&str; 8] = [
+    "d73a4a", "0075ca", "cfd3d7", "a2eeef", "7057ff", "008672", "e4e669", "d876e3",
+];
+
+/// What to do with requested labels that don't exist in the repository.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum MissingLabels {
+    /// Fail before creating the issue or PR.
+    #[default]
+    Error,
+    /// Create the missing labels with a generated color.
+    Create,
+    /// Leave out the missing labels with a warning.
+    Ignore,
+}
+
+impl MissingLabels {
+    /// Gets the policy of the `--create-missing-labels` and
+    /// `--ignore-missing-labels` flags if either is set.
+    pub fn from_flags(create: bool, ignore: bool) -> Option<Self> {
+        match (create, ignore) {
+            (true, _) => Some(Self::Create),
+            (_, true) => Some(Self::Ignore),
+            _ => None,
+        }
+    }
+}
+
+/// The `@labels=` value of the TUI that lists the items without any labels.
+const UNLABELED_OPTION: &str = "none";
+
+/// The labels that listed issues and PRs must have (`include`) or must not
+/// have (`exclude`). Excluded labels are given with a `-` prefix, e.g.
+/// `--labels bug,-triaged` or `@labels=bug,-triaged` in the TUI. `unlabeled`
+/// only matches the items without any labels (`--no-labels`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LabelFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub unlabeled: bool,
+}
+
+impl LabelFilter {
+    /// A filter that matches every item.
+    pub const NONE: &'static LabelFilter = &LabelFilter {
+        include: Vec::new(),
+        exclude: Vec::new(),
+        unlabeled: false,
+    };
+
+    /// Splits the labels into the included and the excluded ones. Empty names
+    /// are ignored.
+    pub fn parse(labels: &[String]) -> Self {
+        let mut filter = LabelFilter::default();
+
+        for label in labels
+            .iter()
+            .filter(|label| !matches!(label.as_str(), "" | "-"))
+        {
+            match label.strip_prefix('-') {
+                Some(name) => push_unique(&mut filter.exclude, name),
+                None => push_unique(&mut filter.include, label),
+            }
+        }
+
+        filter
+    }
+
+    /// Parses the `@labels=` option of the TUI like [`LabelFilter::parse`],
+    /// except that `none` matches the items without any labels.
+    pub fn parse_option(labels: &[String]) -> Self {
+        match labels {
+            [label] if label == UNLABELED_OPTION => LabelFilter {
+                unlabeled: true,
+                ..LabelFilter::default()
+            },
+            labels => LabelFilter::parse(labels),
+        }
+    }
+
+    /// The `@labels=` option of the TUI for the filter, see
+    /// [`LabelFilter::parse_option`].
+    pub fn to_option(labels: &[String], unlabeled: bool) -> Vec<String> {
+        if unlabeled {
+            vec![String::from(UNLABELED_OPTION)]
+        } else {
+            labels.to_vec()
+        }
+    }
+
+    /// Whether an item with `labels` has all included and none of the
+    /// excluded labels.
+    pub fn matches(&self, labels: &[String]) -> bool {
+        self.include.iter().all(|label| labels.contains(label))
+            && !self.exclude.iter().any(|label| labels.contains(label))
+            && (!self.unlabeled || labels.is_empty())
+    }
+
+    /// Whether the filter drops items that the forges without a negated
+    /// label filter, i.e. Gitea/Forgejo, return.
+    pub fn needs_client_side_filtering(&self) -> bool {
+        self.unlabeled || !self.exclude.is_empty()
+    }
+}
+
+/// Resolves the requested labels against the labels of the repository and
+/// returns the labels to use for the new item. The names are spelled like in
+/// the repository since labels are matched case-insensitively.
+pub fn resolve_labels(
+    http_client: &HttpClient,
+    api_type: &ApiType,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    labels: &[String],
+    missing_labels: MissingLabels,
+) -> anyhow::Result<Vec<String>> {
+    if labels.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let get_labels = function!(api_type, get_labels);
+    let existing = get_labels(http_client, remote, api_url)
+        .context("Failed to fetch the labels of the repository to check --labels")?;
+    let resolution = match_labels(labels, &existing, missing_labels, &remote.path)?;
+
+    if !resolution.skipped.is_empty() {
+        log::warn!(
+            "Leaving out the labels that don't exist in {}: {}",
+            remote.path,
+            quote_labels(&resolution.skipped)
+        );
+    }
+
+    let create_label = function!(api_type, create_label);
+
+    for name in &resolution.created {
+        create_label(
+            http_client,
+            remote,
+            api_url,
+            name,
+            generate_label_color(name),
+        )
+        .with_context(|| format!("Failed to create label '{name}'"))?;
+
+        eprintln!("Created label '{name}'");
+    }
+
+    Ok(resolution.labels)
+}
+
+/// Resolves the labels of a label change, e.g. `issue label add`, against the
+/// labels of the repository. Labels that don't exist are an error that lists
+/// the available labels since the forges would drop, create or reject them.
+pub fn find_labels(
+    http_client: &HttpClient,
+    api_type: &ApiType,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    labels: &[String],
+) -> anyhow::Result<Vec<String>> {
+    if labels.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let get_labels = function!(api_type, get_labels);
+    let existing = get_labels(http_client, remote, api_url)
+        .context("Failed to fetch the labels of the repository")?;
+
+    match_existing_labels(labels, &existing, &remote.path)
+}
+
+/// Leaves out the `removed` labels, which are matched case-insensitively like
+/// the forges do, for the forges that can only replace the labels of an issue.
+pub fn without_labels(labels: Vec<String>, removed: &[String]) -> Vec<String> {
+    labels
+        .into_iter()
+        .filter(|label| {
+            !removed
+                .iter()
+                .any(|name| name.to_lowercase() == label.to_lowercase())
+        })
+        .collect()
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+#[derive(Debug, Default, PartialEq)]
+struct LabelResolution {
+    /// The labels to use for the new item.
+    labels: Vec<String>,
+    /// The missing labels that need to be created.
+    created: Vec<String>,
+    /// The missing labels that are left out.
+    skipped: Vec<String>,
+}
+
+fn match_labels(
+    requested: &[String],
+    existing: &[String],
+    missing_labels: MissingLabels,
+    repo_path: &str,
+) -> anyhow::Result<LabelResolution> {
+    let find_existing = |name: &str| {
+        existing
+            .iter()
+            .find(|label| label.to_lowercase() == name.to_lowercase())
+    };
+    let mut missing = vec![];
+
+    for name in requested
+        .iter()
+        .filter(|name| find_existing(name).is_none())
+    {
+        push_unique(&mut missing, name);
+    }
+
+    if missing_labels == MissingLabels::Error && !missing.is_empty() {
+        let (subject, verb, pronoun) = match missing.as_slice() {
+            [_] => ("Label", "doesn't", "it"),
+            _ => ("Labels", "don't", "them"),
+        };
+
+        anyhow::bail!(
+            "{subject} {} {verb} exist in {repo_path}. Pass --create-missing-labels to create {pronoun} or --ignore-missing-labels to leave {pronoun} out",
+            quote_labels(&missing),
+        );
+    }
+
+    let mut resolution = LabelResolution::default();
+
+    for name in requested {
+        match find_existing(name) {
+            Some(label) => push_unique(&mut resolution.labels, label),
+            None if missing_labels == MissingLabels::Create => {
+                push_unique(&mut resolution.labels, name);
+                push_unique(&mut resolution.created, name);
+            }
+            None => push_unique(&mut resolution.skipped, name),
+        }
+    }
+
+    Ok(resolution)
+}
+
+fn match_existing_labels(
+    requested: &[String],
+    existing: &[String],
+    repo_path: &str,
+) -> anyhow::Result<Vec<String>> {
+    let resolution = match_labels(requested, existing, MissingLabels::Ignore, repo_path)?;
+
+    if resolution.skipped.is_empty() {
+        return Ok(resolution.labels);
+    }
+
+    let (subject, verb) = match resolution.skipped.as_slice() {
+        [_] => ("Label", "doesn't"),
+        _ => ("Labels", "don't"),
+    };
+    let available = match existing {
+        [] => String::from("none"),
+        existing => quote_labels(existing),
+    };
+
+    anyhow::bail!(
+        "{subject} {} {verb} exist in {repo_path}. Available labels: {available}",
+        quote_labels(&resolution.skipped),
+    )
+}
+
+/// Picks a color for a new label. The same name always gets the same color.
+fn generate_label_color(name: &str) -> &'static str {
+    // FNV-1a, since the std hasher isn't stable across releases
+    let hash = name.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+
+    LABEL_COLORS[(hash % LABEL_COLORS.len() as u64) as usize]
+}
+
+fn push_unique(labels: &mut Vec<String>, label: &str) {
+    if !labels.iter().any(|l| l == label) {
+        labels.push(label.to_string());
+    }
+}
+
+fn quote_labels(labels: &[String]) -> String {
+    labels
+        .iter()
+        .map(|label| format!("'{label}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_label_filter() {
+        let filter = LabelFilter::parse(&labels(&["bug", "-triaged", "", "-", "bug", "ui"]));
+
+        assert_eq!(filter.include, ["bug", "ui"]);
+        assert_eq!(filter.exclude, ["triaged"]);
+        assert!(filter.matches(&labels(&["ui", "bug", "docs"])));
+        assert!(!filter.matches(&labels(&["ui", "bug", "triaged"])));
+        assert!(!filter.matches(&labels(&["bug"])));
+        assert!(LabelFilter::parse(&[]).matches(&[]));
+        assert_eq!(
+            LabelFilter::parse(&labels(&["", "-"])),
+            LabelFilter::default()
+        );
+    }
+
+    #[test]
+    fn test_unlabeled_label_filter() {
+        let filter = LabelFilter::parse_option(&labels(&["none"]));
+
+        assert!(filter.unlabeled);
+        assert!(filter.needs_client_side_filtering());
+        assert!(filter.matches(&[]));
+        assert!(!filter.matches(&labels(&["bug"])));
+        assert_eq!(
+            LabelFilter::parse_option(&labels(&["none", "bug"])).include,
+            ["none", "bug"]
+        );
+        assert_eq!(LabelFilter::to_option(&labels(&["bug"]), true), ["none"]);
+        assert_eq!(LabelFilter::to_option(&labels(&["bug"]), false), ["bug"]);
+    }
+
+    #[test]
+    fn test_match_labels_with_missing_labels_policies() {
+        let existing = labels(&["bug", "enhancement"]);
+        let requested = labels(&["Bug", "new", "enhancement", "bug", "new"]);
+
+        let error = match_labels(&requested, &existing, MissingLabels::Error, "user/repo")
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.starts_with("Label 'new' doesn't exist in user/repo."));
+        assert!(error.contains("--create-missing-labels to create it"));
+
+        assert_eq!(
+            match_labels(&requested, &existing, MissingLabels::Create, "user/repo").unwrap(),
+            LabelResolution {
+                labels: labels(&["bug", "new", "enhancement"]),
+                created: labels(&["new"]),
+                skipped: vec![],
+            }
+        );
+        assert_eq!(
+            match_labels(&requested, &existing, MissingLabels::Ignore, "user/repo").unwrap(),
+            LabelResolution {
+                labels: labels(&["bug", "enhancement"]),
+                created: vec![],
+                skipped: labels(&["new"]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_labels_lists_all_missing_labels() {
+        let error = match_labels(
+            &labels(&["a", "bug", "b"]),
+            &labels(&["bug"]),
+            MissingLabels::Error,
+            "user/repo",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.starts_with("Labels 'a', 'b' don't exist in user/repo."));
+        assert!(error.ends_with("--ignore-missing-labels to leave them out"));
+    }
+
+    #[test]
+    fn test_match_existing_labels_lists_available_labels() {
+        let existing = labels(&["bug", "enhancement"]);
+
+        assert_eq!(
+            match_existing_labels(&labels(&["Bug", "bug"]), &existing, "user/repo").unwrap(),
+            ["bug"]
+        );
+        assert_eq!(
+            match_existing_labels(&labels(&["bug", "ux"]), &existing, "user/repo")
+                .unwrap_err()
+                .to_string(),
+            "Label 'ux' doesn't exist in user/repo. Available labels: 'bug', 'enhancement'"
+        );
+        assert_eq!(
+            match_existing_labels(&labels(&["a", "b"]), &[], "user/repo")
+                .unwrap_err()
+                .to_string(),
+            "Labels 'a', 'b' don't exist in user/repo. Available labels: none"
+        );
+    }
+
+    #[test]
+    fn test_without_labels() {
+        assert_eq!(
+            without_labels(labels(&["bug", "UI", "docs"]), &labels(&["ui", "triaged"])),
+            ["bug", "docs"]
+        );
+    }
+
+    #[test]
+    fn test_generate_label_color_is_stable() {
+        let color = generate_label_color("needs-triage");
+
+        assert_eq!(color, generate_label_color("needs-triage"));
+        assert!(LABEL_COLORS.contains(&color));
+    }
+}
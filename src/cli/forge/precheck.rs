@@ -0,0 +1,69 @@
+//! Checks of the repository before creating issues and PRs.
+//!
+//! A forge only refuses to create an item on an archived repository once the
+//! item is sent, i.e. after the user wrote it, possibly at length in the text
+//! editor. So the create commands fetch the repository first and stop early if
+//! it can't take new items. The request is authenticated like the creation, so
+//! a token without access to the repository fails here as well. --no-precheck
+//! skips the check.
+
+use anyhow::Context;
+
+use crate::{
+    cli::forge::{ApiType, HttpClient, forgejo, function, gitea, github, gitlab},
+    git::GitRemoteData,
+};
+
+/// The state of a repository that decides whether items can be created on it.
+#[derive(Debug, Default, PartialEq)]
+pub struct RepoStatus {
+    pub archived: bool,
+}
+
+/// Fails if `items`, e.g. "issues", can't be created on the repository.
+pub fn check_repo(
+    http_client: &HttpClient,
+    api_type: &ApiType,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    items: &str,
+) -> anyhow::Result<()> {
+    let get_repo_status = function!(api_type, get_repo_status);
+    let status = get_repo_status(http_client, remote, api_url).with_context(|| {
+        format!(
+            "Failed to check whether {items} can be created. Pass --no-precheck to skip the check"
+        )
+    })?;
+
+    check_repo_status(&status, items)
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+fn check_repo_status(status: &RepoStatus, items: &str) -> anyhow::Result<()> {
+    if status.archived {
+        anyhow::bail!(
+            "The repository is archived, so {items} can't be created. It has to be unarchived on the forge first"
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_repo_status() {
+        assert!(check_repo_status(&RepoStatus::default(), "issues").is_ok());
+
+        let error = check_repo_status(&RepoStatus { archived: true }, "pull requests")
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("archived, so pull requests can't be created"));
+    }
+}
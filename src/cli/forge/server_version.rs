@@ -0,0 +1,166 @@
+//! Cache of the server version of Gitea/Forgejo instances.
+//!
+//! Some features depend on the version of the server, e.g. native draft PRs.
+//! Asking for it costs a request, so the version is stored in the `http`
+//! category for a day, keyed by the API host. After that, the server is asked
+//! again, so that an upgrade is noticed.
+
+use std::path::{Path, PathBuf};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::storage::{self, StorageCategory};
+
+/// How long a cached version is used before it's fetched again.
+const TTL_SECONDS: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedServerVersion {
+    version: String,
+    /// Unix timestamp of when the version was fetched.
+    fetched_at: i64,
+}
+
+/// Gets the version of the server at `base_url`. The version is taken from the
+/// cache unless it's older than a day. Otherwise, it's fetched with `fetch` and
+/// cached.
+pub fn get_server_version(
+    base_url: &str,
+    fetch: impl FnOnce() -> anyhow::Result<String>,
+) -> anyhow::Result<String> {
+    let Some(path) = get_cache_path_for_url(base_url) else {
+        return fetch();
+    };
+
+    get_server_version_at(&path, OffsetDateTime::now_utc().unix_timestamp(), fetch)
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+fn get_cache_path_for_url(base_url: &str) -> Option<PathBuf> {
+    let url = Url::parse(base_url).ok()?;
+    let http_dir = storage::get_category_dir(StorageCategory::Http).ok()?;
+
+    get_cache_path(&http_dir, &url)
+}
+
+fn get_server_version_at(
+    path: &Path,
+    now: i64,
+    fetch: impl FnOnce() -> anyhow::Result<String>,
+) -> anyhow::Result<String> {
+    if let Some(version) = read_cached_version(path, now) {
+        log::debug!("Using the cached server version {version}");
+
+        return Ok(version);
+    }
+
+    let cached_version = CachedServerVersion {
+        version: fetch()?,
+        fetched_at: now,
+    };
+
+    // Caching is best-effort; we can always fetch the version again
+    if let Err(e) = storage::write_json(path, &cached_version) {
+        log::debug!("Failed to cache the server version: {e:#}");
+    }
+
+    Ok(cached_version.version)
+}
+
+fn read_cached_version(path: &Path, now: i64) -> Option<String> {
+    match storage::read_json::<CachedServerVersion>(path) {
+        Ok(Some(cached)) if is_fresh(cached.fetched_at, now) => Some(cached.version),
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("Ignoring the cached server version: {e:#}");
+
+            None
+        }
+    }
+}
+
+/// Whether a version fetched at `fetched_at` may still be used. Timestamps in
+/// the future, e.g. because the clock was changed, count as expired.
+fn is_fresh(fetched_at: i64, now: i64) -> bool {
+    (0..TTL_SECONDS).contains(&(now - fetched_at))
+}
+
+fn get_cache_path(http_dir: &Path, url: &Url) -> Option<PathBuf> {
+    let host = url.host_str()?.to_lowercase();
+    let host = match url.port() {
+        Some(port) => format!("{host}_{port}"),
+        None => host,
+    };
+
+    Some(http_dir.join(host).join("server-version.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// Looks up the version at `now` and returns it and whether it was fetched.
+    fn lookup(path: &Path, now: i64, version: &str) -> (String, bool) {
+        let fetched = Cell::new(false);
+        let version = get_server_version_at(path, now, || {
+            fetched.set(true);
+
+            Ok(version.to_string())
+        })
+        .unwrap();
+
+        (version, fetched.get())
+    }
+
+    #[test]
+    fn test_get_server_version_caches_for_a_day() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("host/server-version.json");
+
+        assert_eq!(lookup(&path, NOW, "1.23.0"), (String::from("1.23.0"), true));
+        assert_eq!(
+            lookup(&path, NOW + TTL_SECONDS - 1, "1.24.0"),
+            (String::from("1.23.0"), false)
+        );
+        // An upgraded server is noticed once the cached version expired
+        assert_eq!(
+            lookup(&path, NOW + TTL_SECONDS, "1.24.0"),
+            (String::from("1.24.0"), true)
+        );
+        // So is a clock that was set back
+        assert_eq!(
+            lookup(&path, NOW + TTL_SECONDS - 1, "1.25.0"),
+            (String::from("1.25.0"), true)
+        );
+    }
+
+    #[test]
+    fn test_get_server_version_does_not_cache_failures() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("host/server-version.json");
+        let result = get_server_version_at(&path, NOW, || anyhow::bail!("HTTP 404"));
+
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_cache_path_is_per_host() {
+        let root = tempfile::tempdir().unwrap();
+        let url = Url::parse("https://Git.Internal:3000/api/v1").unwrap();
+
+        assert_eq!(
+            get_cache_path(root.path(), &url),
+            Some(root.path().join("git.internal_3000/server-version.json"))
+        );
+    }
+}
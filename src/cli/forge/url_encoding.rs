@@ -0,0 +1,84 @@
+//! Percent-encoding for hand-built URLs.
+//!
+//! Query parameters sent via reqwest's `.query()` are already encoded. These
+//! helpers are for the parts of a URL that we build by hand, e.g. web URLs
+//! containing branch names or file paths.
+//!
+//! Only the unreserved characters of RFC 3986 (`A-Z`, `a-z`, `0-9`, `-`, `.`,
+//! `_`, `~`) are kept as-is. Everything else is encoded byte-wise as UTF-8.
+
+/// Encodes a single path segment or query value. Slashes are encoded as well.
+pub fn encode_component(input: &str) -> String {
+    encode(input, |_| false)
+}
+
+/// Encodes a path while keeping the slashes that separate its segments.
+pub fn encode_path(input: &str) -> String {
+    encode(input, |byte| byte == b'/')
+}
+
+fn encode(input: &str, keep: impl Fn(u8) -> bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+
+    for byte in input.bytes() {
+        if is_unreserved(byte) || keep(byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    encoded
+}
+
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_component_keeps_unreserved_characters() {
+        assert_eq!(encode_component("abcXYZ019-._~"), "abcXYZ019-._~");
+    }
+
+    #[test]
+    fn test_encode_component_label_with_space() {
+        assert_eq!(encode_component("help wanted"), "help%20wanted");
+    }
+
+    #[test]
+    fn test_encode_component_encodes_slashes() {
+        assert_eq!(encode_component("group/sub/repo"), "group%2Fsub%2Frepo");
+    }
+
+    #[test]
+    fn test_encode_component_branch_with_hash() {
+        assert_eq!(encode_component("fix/#42"), "fix%2F%2342");
+    }
+
+    #[test]
+    fn test_encode_path_branch_with_hash() {
+        assert_eq!(encode_path("fix/#42-crash"), "fix/%2342-crash");
+    }
+
+    #[test]
+    fn test_encode_path_with_spaces() {
+        assert_eq!(encode_path("docs/my file.md"), "docs/my%20file.md");
+    }
+
+    #[test]
+    fn test_encode_path_non_ascii() {
+        assert_eq!(
+            encode_path("src/über/日本.rs"),
+            "src/%C3%BCber/%E6%97%A5%E6%9C%AC.rs"
+        );
+    }
+
+    #[test]
+    fn test_encode_path_reserved_characters() {
+        assert_eq!(encode_path("a?b&c=d#e%f"), "a%3Fb%26c%3Dd%23e%25f");
+    }
+}
@@ -0,0 +1,422 @@
+//! The `init` subcommand.
+
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+
+use crate::{
+    cli::{
+        config::{self, Config, ConfigFile, ConfigScope},
+        forge::{self, ApiType, ApiUrl, HttpClient, forgejo, gitea, github, gitlab},
+    },
+    git::{self, GitRemoteData},
+    io,
+};
+
+const DEFAULT_REMOTE: &str = "origin";
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `init` subcommand.
+#[derive(Args)]
+pub struct InitCommandArgs {
+    /// Specify the forge instead of detecting it
+    #[arg(long, value_name = "TYPE")]
+    api: Option<ApiType>,
+
+    /// Specify the API base URL, e.g. if a self-hosted forge's API isn't
+    /// reachable via https on the host of the git remote URL
+    #[arg(long, value_name = "URL", value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Config file to save the settings to. By default, you are asked
+    #[arg(long)]
+    file: Option<ConfigFile>,
+
+    /// Git remote of the repository on the forge. By default, you are asked if
+    /// there are several remotes
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Save all proposed settings without asking. Uses the `origin` remote and
+    /// the user's config file unless --remote or --file say otherwise
+    #[arg(short, long)]
+    yes: bool,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Detects the forge of a git remote, checks that its API is reachable and
+/// saves the recommended settings for it after asking for each of them.
+pub fn init(args: InitCommandArgs) -> anyhow::Result<()> {
+    if !args.yes && !io::is_interactive() {
+        anyhow::bail!(
+            "Can't ask for confirmation without a terminal. Pass --yes to save all settings"
+        );
+    }
+
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = choose_remote(args.remote, args.yes)?;
+    let mut remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to get remote URL for remote '{}'", &remote_name))?;
+    let api_type = choose_api_type(args.api, &config, &remote, args.yes)?;
+
+    if let Some(api_url) = &args.api_url {
+        remote.api_base_url = Some(api_url.to_string());
+    }
+
+    let get_url_for_api = forge::function!(api_type, get_url_for_api);
+    let api_url = get_url_for_api(&remote);
+
+    eprintln!(
+        "Remote '{remote_name}' is a {} repository at {}.",
+        get_api_type_name(api_type),
+        remote.web_base_url
+    );
+
+    check_api(&api_url, args.yes)?;
+
+    let file = choose_file(args.file, args.yes)?;
+    let path = file.get_path()?;
+    let mut file_config = file
+        .load()
+        .with_context(|| format!("Failed to load {}", path.display()))?;
+    let settings = get_recommended_settings(api_type, remote.api_base_url.as_deref());
+    let mut has_changes = false;
+
+    for setting in &settings {
+        let new_line = format_setting(setting, &setting.value, file, &remote);
+
+        match get_change(&file_config, setting, &remote) {
+            SettingChange::Unchanged => {
+                eprintln!("  {new_line}");
+
+                continue;
+            }
+            SettingChange::Added => eprintln!("+ {new_line}"),
+            SettingChange::Changed { old } => {
+                eprintln!("- {}", format_setting(setting, &old, file, &remote));
+                eprintln!("+ {new_line}");
+            }
+        }
+
+        if io::skips_confirmation(args.yes)
+            || io::prompt_confirm("Save this setting?", true, "--yes")?
+        {
+            file_config.set_value_in_scope(setting.path, &setting.value, setting.scope, &remote)?;
+            has_changes = true;
+        }
+    }
+
+    if !has_changes {
+        eprintln!("Nothing to change in {}.", path.display());
+
+        return Ok(());
+    }
+
+    file_config
+        .save_to_file(file)
+        .with_context(|| format!("Failed to save {}", path.display()))?;
+
+    eprintln!("Saved the settings to {}.", path.display());
+
+    if file == ConfigFile::Repo {
+        eprintln!(
+            "Commit {} to share the settings with the other contributors.",
+            config::REPO_CONFIG_NAME
+        );
+    }
+
+    if remote_name != DEFAULT_REMOTE {
+        eprintln!(
+            "The commands use the '{DEFAULT_REMOTE}' remote by default. Pass --remote {remote_name} or set <command>/remote, e.g. `{}`.",
+            config::format_set_command("issue/remote", &remote_name, ConfigScope::Global, &remote)
+        );
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// A setting that `init` proposes.
+#[derive(Debug, PartialEq)]
+struct RecommendedSetting {
+    path: &'static str,
+    value: String,
+    scope: ConfigScope,
+}
+
+/// How saving a setting changes a config file.
+#[derive(Debug, PartialEq)]
+enum SettingChange {
+    Unchanged,
+    Added,
+    Changed { old: String },
+}
+
+/// Gets the settings for the forge of the git remote. The API base URL is
+/// only proposed if it was passed or is already configured.
+fn get_recommended_settings(api_type: ApiType, api_url: Option<&str>) -> Vec<RecommendedSetting> {
+    let mut settings = vec![RecommendedSetting {
+        path: config::FORGE_TYPE_CONFIG_PATH,
+        value: get_api_type_name(api_type),
+        scope: ConfigScope::Host,
+    }];
+
+    if let Some(api_url) = api_url {
+        settings.push(RecommendedSetting {
+            path: config::API_BASE_URL_CONFIG_PATH,
+            value: api_url.to_string(),
+            scope: ConfigScope::Host,
+        });
+    }
+
+    settings
+}
+
+/// Compares the setting with the value in the same scope of the config file.
+fn get_change(
+    config: &Config,
+    setting: &RecommendedSetting,
+    remote: &GitRemoteData,
+) -> SettingChange {
+    match config.get_value_in_scope(setting.path, setting.scope, remote) {
+        None => SettingChange::Added,
+        Some(old) if old == setting.value => SettingChange::Unchanged,
+        Some(old) => SettingChange::Changed { old },
+    }
+}
+
+/// Formats the setting as the `config set` command for the user's config
+/// file and as TOML for a repository's `.git-forge.toml`.
+fn format_setting(
+    setting: &RecommendedSetting,
+    value: &str,
+    file: ConfigFile,
+    remote: &GitRemoteData,
+) -> String {
+    match file {
+        ConfigFile::User => config::format_set_command(setting.path, value, setting.scope, remote),
+        ConfigFile::Repo => config::format_repo_setting(setting.path, value, setting.scope, remote),
+    }
+}
+
+fn choose_remote(remote_name: Option<String>, yes: bool) -> anyhow::Result<String> {
+    if let Some(remote_name) = remote_name {
+        return Ok(remote_name);
+    }
+
+    let remote_names = git::list_remotes()?;
+
+    match remote_names.as_slice() {
+        [] => anyhow::bail!("The repository has no git remotes"),
+        [remote_name] => Ok(remote_name.clone()),
+        _ if yes => remote_names
+            .iter()
+            .find(|remote_name| *remote_name == DEFAULT_REMOTE)
+            .cloned()
+            .with_context(|| {
+                format!(
+                    "The repository has several git remotes but no '{DEFAULT_REMOTE}'. Choose one with --remote"
+                )
+            }),
+        _ => {
+            let index = io::prompt_select(
+                "Which git remote is the repository on the forge?",
+                &remote_names,
+                "--remote",
+            )?
+            .context("No git remote was chosen")?;
+
+            Ok(remote_names[index].clone())
+        }
+    }
+}
+
+fn choose_api_type(
+    api_type: Option<ApiType>,
+    config: &Config,
+    remote: &GitRemoteData,
+    yes: bool,
+) -> anyhow::Result<ApiType> {
+    let error = match forge::detect_api_type(api_type, config, remote) {
+        Ok(api_type) => return Ok(api_type),
+        Err(e) if yes => return Err(e),
+        Err(e) => e,
+    };
+    let api_types = ApiType::value_variants();
+    let names: Vec<String> = api_types.iter().copied().map(get_api_type_name).collect();
+    let index = io::prompt_select(&format!("Which forge is {}?", remote.host), &names, "--api")?
+        .ok_or(error)?;
+
+    Ok(api_types[index])
+}
+
+/// Checks that the API answers at all. Any HTTP response will do since the
+/// API may need authentication or not have a resource at its base URL.
+fn check_api(api_url: &str, yes: bool) -> anyhow::Result<()> {
+    let error = match HttpClient::new().get(api_url).send() {
+        Ok(response) => {
+            eprintln!(
+                "The API at {api_url} is reachable (HTTP {}).",
+                response.status().as_u16()
+            );
+
+            return Ok(());
+        }
+        Err(e) => anyhow::anyhow!(e).context(format!(
+            "Failed to reach the API at {api_url}. Pass its base URL with --api-url if it's elsewhere"
+        )),
+    };
+
+    if yes {
+        return Err(error);
+    }
+
+    eprintln!("{error:#}");
+
+    if !io::prompt_confirm("Continue anyway?", false, "--api-url")? {
+        anyhow::bail!("Aborted");
+    }
+
+    Ok(())
+}
+
+fn choose_file(file: Option<ConfigFile>, yes: bool) -> anyhow::Result<ConfigFile> {
+    if let Some(file) = file {
+        return Ok(file);
+    }
+
+    if yes {
+        return Ok(ConfigFile::User);
+    }
+
+    let files = [ConfigFile::User, ConfigFile::Repo];
+    let items = [
+        format!(
+            "Your config file ({})",
+            ConfigFile::User.get_path()?.display()
+        ),
+        format!(
+            "{} in the repository to commit it ({})",
+            config::REPO_CONFIG_NAME,
+            ConfigFile::Repo.get_path()?.display()
+        ),
+    ];
+    let index = io::prompt_select("Where should the settings be saved?", &items, "--file")?
+        .context("No config file was chosen")?;
+
+    Ok(files[index])
+}
+
+fn get_api_type_name(api_type: ApiType) -> String {
+    api_type
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn create_remote() -> GitRemoteData {
+        GitRemoteData {
+            host: String::from("git.internal"),
+            path: String::from("owner/repo"),
+            port: None,
+            web_base_url: String::from("https://git.internal"),
+            api_base_url: None,
+        }
+    }
+
+    fn create_setting(path: &'static str, value: &str, scope: ConfigScope) -> RecommendedSetting {
+        RecommendedSetting {
+            path,
+            value: value.to_string(),
+            scope,
+        }
+    }
+
+    #[test]
+    fn test_get_recommended_settings() {
+        assert_eq!(
+            get_recommended_settings(ApiType::Gitea, None),
+            vec![create_setting("forge-type", "gitea", ConfigScope::Host)]
+        );
+        assert_eq!(
+            get_recommended_settings(ApiType::Forgejo, Some("https://git.internal/api/v1")),
+            vec![
+                create_setting("forge-type", "forgejo", ConfigScope::Host),
+                create_setting(
+                    "api-base-url",
+                    "https://git.internal/api/v1",
+                    ConfigScope::Host
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_change() {
+        let remote = create_remote();
+        let config = Config {
+            host: HashMap::from([(
+                String::from("git.internal"),
+                HashMap::from([(String::from("forge-type"), String::from("gitea"))]),
+            )]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_change(
+                &config,
+                &create_setting("forge-type", "gitea", ConfigScope::Host),
+                &remote
+            ),
+            SettingChange::Unchanged
+        );
+        assert_eq!(
+            get_change(
+                &config,
+                &create_setting("forge-type", "forgejo", ConfigScope::Host),
+                &remote
+            ),
+            SettingChange::Changed {
+                old: String::from("gitea")
+            }
+        );
+        assert_eq!(
+            get_change(
+                &config,
+                &create_setting("api-base-url", "https://git.internal", ConfigScope::Host),
+                &remote
+            ),
+            SettingChange::Added
+        );
+    }
+
+    #[test]
+    fn test_format_setting() {
+        let remote = create_remote();
+        let setting = create_setting("forge-type", "gitea", ConfigScope::Host);
+
+        assert_eq!(
+            format_setting(&setting, "gitea", ConfigFile::User, &remote),
+            "git forge config set --scope host --host git.internal forge-type gitea"
+        );
+        assert_eq!(
+            format_setting(&setting, "gitea", ConfigFile::Repo, &remote),
+            "host.\"git.internal\".forge-type = \"gitea\""
+        );
+    }
+}
@@ -1,26 +1,49 @@
 //! The `issue` subcommand.
 
+use std::{cmp::Reverse, collections::HashSet, io::Read, path::PathBuf, thread, time::Duration};
+
 use anyhow::Context;
-use clap::{Args, Subcommand, ValueEnum};
-use dialoguer::Input;
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 
 use crate::{
     cli::{
         config::{self, Config},
-        forge::{self, ApiType, HttpClient, gitea, github, gitlab},
+        forge::{
+            self, ApiType, ApiUrl, HttpClient, LabelFilter, MissingLabels, PaginatedResponse,
+            forgejo, gitea, github, gitlab,
+        },
     },
+    date::{self, DateRange, Timestamp},
     git::{self, GitRemoteData},
-    io::{self, OutputFormat},
-    tui::{self, FetchOptions, ListableItem},
+    io::{self, CreatedItemFormat, OutputFormat, ResultFormat, Truncation},
+    run_marker::RunMarker,
+    storage, throttle,
+    tui::{self, FetchOptionSpec, FetchOptions, ListableItem, SortKey, SortValue},
+    watch::{self, WatchedRow},
 };
 
 // =============================================================================
 // CLI Arguments
 // =============================================================================
 
+const DEFAULT_IMPORT_DELAY: u32 = 1;
 const DEFAULT_PER_PAGE: u32 = 30;
 const DEFAULT_REMOTE: &str = "origin";
+/// The number of title words to search for duplicates of a new issue.
+const DUPLICATE_QUERY_WORDS: usize = 8;
+/// The number of open issues to fetch when searching for duplicates of a new
+/// issue.
+const DUPLICATE_SEARCH_PAGE_SIZE: u32 = 100;
+/// Hidden marker in the body of imported issues. It's followed by the URL of
+/// the source issue, so that `issue import` can skip it when run again.
+const IMPORT_MARKER_PREFIX: &str = "<!-- git-forge-import: ";
+const IMPORT_MARKER_SUFFIX: &str = " -->";
+const ISSUE_SNAPSHOT_NAME: &str = "issues";
+const MAX_CONCURRENT_FETCHES: usize = 4;
+const MAX_DUPLICATE_CANDIDATES: usize = 5;
+const SNAPSHOT_PER_PAGE: u32 = 100;
 
 /// Command-line arguments for the `issue` subcommand.
 #[derive(Args)]
@@ -39,22 +62,71 @@ pub enum IssueCommand {
     /// Create an issue and open it in the web browser.
     #[command(alias = "cr")]
     Create(IssueCreateCommandArgs),
+
+    /// Save all issues to a local snapshot for use with `issue list --offline`
+    /// or compare the current issues with the last snapshot.
+    Snapshot(IssueSnapshotCommandArgs),
+
+    /// Print the issues including their description as JSON, e.g. to migrate
+    /// them to another forge with `issue import`.
+    Export(IssueExportCommandArgs),
+
+    /// Recreate the issues of an `issue export` file on the remote's forge.
+    Import(IssueImportCommandArgs),
+
+    /// Pin an issue to the top of the issue tracker (GitHub and
+    /// Gitea/Forgejo only).
+    Pin(IssuePinCommandArgs),
+
+    /// Unpin a pinned issue (GitHub and Gitea/Forgejo only).
+    Unpin(IssuePinCommandArgs),
+
+    /// Assign an issue to you or other users and print its assignees.
+    Assign(IssueAssignCommandArgs),
+
+    /// Remove you or other users from the assignees of an issue and print
+    /// the remaining assignees.
+    Unassign(IssueUnassignCommandArgs),
+
+    /// Add, remove or replace the labels of an issue or of many issues at
+    /// once.
+    Label(IssueLabelCommandArgs),
+
+    /// Show the details of an issue including its description.
+    #[command(alias = "v")]
+    View(IssueViewCommandArgs),
 }
 
 /// Command-line arguments for listing issues.
 #[derive(Args)]
 pub struct IssueListCommandArgs {
+    /// Fetch all pages instead of only the one given by --page
+    #[arg(
+        long,
+        conflicts_with_all = ["interactive", "limit", "page", "watch", "web"]
+    )]
+    all_pages: bool,
+
+    /// List the issues of every git remote whose forge is known instead of a
+    /// single remote. The remote's name is added as the first column
+    #[arg(
+        long,
+        conflicts_with_all = ["api", "api_url", "ids_only", "offline", "remote", "watch", "web"]
+    )]
+    all_remotes: bool,
+
     /// Specify the forge which affects the API schema etc
-    #[arg(long, value_name = "TYPE")]
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
     api: Option<ApiType>,
 
     /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
     /// instead of relying on the auto-detection
-    #[arg(long)]
-    api_url: Option<String>,
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
     /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
-    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN)
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
     #[arg(long)]
     auth: bool,
 
@@ -65,22 +137,135 @@ pub struct IssueListCommandArgs {
     #[arg(long, value_name = "USERNAME", help = "Filter by author")]
     author: Option<String>,
 
-    /// Fields to include in output (comma-separated)
-    #[arg(short, long, value_delimiter = ',')]
+    /// Filter closed issues by the reason they were closed. Implies
+    /// --state=closed (GitHub only)
+    /// Only list issues closed on or after this date. Takes a date
+    /// (2024-03-01), a timestamp (2024-03-01T12:00:00Z) or a duration before
+    /// now (12h, 7d, 2w). Implies --state=closed unless a state is given
+    #[arg(
+        long,
+        value_name = "DATE",
+        value_parser = date::parse_date,
+        conflicts_with = "interactive"
+    )]
+    closed_after: Option<OffsetDateTime>,
+
+    /// Only list issues closed before this date. See --closed-after for the
+    /// date formats. Implies --state=closed unless a state is given
+    #[arg(
+        long,
+        value_name = "DATE",
+        value_parser = date::parse_date,
+        conflicts_with = "interactive"
+    )]
+    closed_before: Option<OffsetDateTime>,
+
+    #[arg(long, value_name = "REASON", conflicts_with = "state")]
+    closed_as: Option<IssueStateReason>,
+
+    /// Only list issues created on or after this date. See --closed-after for
+    /// the date formats. With --web, only GitHub applies the date filters
+    #[arg(
+        long,
+        value_name = "DATE",
+        value_parser = date::parse_date,
+        conflicts_with = "interactive"
+    )]
+    created_after: Option<OffsetDateTime>,
+
+    /// Only list issues created before this date, e.g. `--created-after
+    /// 2024-03-01 --created-before 2024-04-01` for the issues of March. See
+    /// --closed-after for the date formats
+    #[arg(
+        long,
+        value_name = "DATE",
+        value_parser = date::parse_date,
+        conflicts_with = "interactive"
+    )]
+    created_before: Option<OffsetDateTime>,
+
+    /// With --since-last-run, list the new issues without advancing the marker
+    #[arg(long, requires = "since_last_run")]
+    dry_run: bool,
+
+    /// Exit with status 1 if any issues are found, e.g. to assert in CI that
+    /// there are no open release blockers. The issues are printed first
+    #[arg(
+        long,
+        conflicts_with_all = ["fail_if_none", "interactive", "watch", "web"]
+    )]
+    fail_if_any: bool,
+
+    /// Exit with status 1 if no issues are found. The issues are printed
+    /// first
+    #[arg(long, conflicts_with_all = ["interactive", "watch", "web"])]
+    fail_if_none: bool,
+
+    /// Fields to include in output (comma-separated). The columns and JSON keys
+    /// follow their order. Use `all` for all fields. The names of `gh --json`,
+    /// e.g. `number`, are accepted as aliases
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        value_parser = io::FieldParser::<IssueField>::new()
+    )]
     fields: Vec<IssueField>,
 
-    /// Output format
+    /// Output format. `compact` prints one line per issue with its number,
+    /// title, labels and author and ignores --fields
     #[arg(short = 'o', long)]
     format: Option<OutputFormat>,
 
+    /// Also list the pull requests. The `type` field tells them apart from
+    /// the issues. GitLab merges a page of issues and a page of merge requests
+    /// by their update time, so its pages may contain up to twice as many
+    /// items
+    #[arg(long, conflicts_with = "offline")]
+    include_prs: bool,
+
+    /// Print only the issue numbers, one per line, e.g. for piping into
+    /// xargs. Ignores --fields and --format
+    #[arg(
+        long,
+        conflicts_with_all = ["fields", "format", "interactive", "watch", "web"]
+    )]
+    ids_only: bool,
+
     /// Use interactive TUI for searching and selecting an issue
     #[arg(short, long, group = "interaction-type")]
     interactive: bool,
 
-    /// Filter by labels (comma-separated)
-    #[arg(long, value_delimiter = ',')]
+    /// Filter by labels (comma-separated). Prefix a label with `-` to exclude
+    /// it, e.g. `bug,-triaged`
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
     labels: Vec<String>,
 
+    /// List at most this many issues in total. Pages starting at --page are
+    /// fetched until the limit is reached. The interactive TUI stops loading
+    /// more issues once it has loaded this many
+    #[arg(
+        long,
+        short_alias = 'l',
+        value_name = "NUMBER",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    limit: Option<u32>,
+
+    /// Only list pinned issues (Gitea/Forgejo only)
+    #[arg(long)]
+    pinned: bool,
+
+    /// Seconds between refreshes in watch mode (minimum 10)
+    #[arg(
+        long,
+        default_value_t = 60,
+        value_parser = clap::value_parser!(u64).range(10..),
+        value_name = "SECONDS",
+        requires = "watch"
+    )]
+    interval: u64,
+
     /// Page number to fetch
     #[arg(
         long,
@@ -90,8 +275,39 @@ pub struct IssueListCommandArgs {
     )]
     page: u32,
 
-    /// Number of issues per page
-    #[arg(long, short_alias = 'l', alias = "limit", value_name = "NUMBER")]
+    /// Only list issues with at least this many reactions (GitHub) or upvotes
+    /// (GitLab). The filter is applied after fetching the page, so a page may
+    /// contain fewer issues
+    #[arg(
+        long,
+        alias = "reactions-threshold",
+        value_name = "NUMBER",
+        conflicts_with_all = ["all_remotes", "interactive", "watch", "web"]
+    )]
+    min_reactions: Option<u32>,
+
+    /// Truncate the titles to this many columns in the TSV and compact output
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    max_title_width: Option<u32>,
+
+    /// Only list issues without any labels, e.g. to find untriaged issues.
+    /// Gitea/Forgejo can't filter by it, so the labeled issues are dropped
+    /// after fetching them
+    #[arg(long, conflicts_with = "labels")]
+    no_labels: bool,
+
+    /// Don't truncate the cells of rows that are wider than the terminal
+    #[arg(long)]
+    no_truncate: bool,
+
+    /// Read the issues from the last snapshot (see `issue snapshot`) instead
+    /// of the forge API
+    #[arg(long, conflicts_with_all = ["interactive", "watch", "web"])]
+    offline: bool,
+
+    /// Number of issues per page [default: 30, or --limit up to 100]. The
+    /// interactive TUI fetches as many as fit on the screen by default
+    #[arg(long, value_name = "NUMBER")]
     per_page: Option<u32>,
 
     /// Search keywords
@@ -102,10 +318,56 @@ pub struct IssueListCommandArgs {
     #[arg(long)]
     remote: Option<String>,
 
+    /// With --since-last-run, ignore the marker and list all issues as if it
+    /// were the first run
+    #[arg(long, requires = "since_last_run")]
+    reset_marker: bool,
+
+    /// Only list the issues created since the last successful run with the
+    /// same filters in this repository, e.g. for a cron job that reports new
+    /// issues. With --since-last-run=updated, the issues that were created or
+    /// updated since then are listed instead, e.g. commented, closed or
+    /// relabeled ones. Both have their own marker. The first run lists all
+    /// issues. Fetches all pages
+    #[arg(
+        long,
+        value_name = "CHANGE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "created",
+        conflicts_with_all = [
+            "all_remotes",
+            "created_after",
+            "interactive",
+            "limit",
+            "offline",
+            "page",
+            "watch",
+            "web"
+        ]
+    )]
+    since_last_run: Option<SinceLastRun>,
+
+    /// Sort the issues, newest or most reactions first. Only GitHub and GitLab
+    /// sort by reactions on their side. Otherwise only the fetched issues are
+    /// sorted, so use --all-pages to sort all issues
+    #[arg(long, conflicts_with_all = ["all_remotes", "interactive", "watch", "web"])]
+    sort: Option<IssueSort>,
+
     /// Filter by state
     #[arg(long)]
     state: Option<IssueState>,
 
+    /// Only list issues updated on or after this date. There's no option for
+    /// it; --since-last-run=updated sets it
+    #[arg(skip)]
+    updated_after: Option<OffsetDateTime>,
+
+    /// Refresh the listing every interval and highlight changes until Ctrl-C.
+    /// Using --auth is strongly suggested to avoid hitting rate limits
+    #[arg(long, conflicts_with_all = ["interactive", "web"])]
+    watch: bool,
+
     /// Open the issues page in the web browser
     #[arg(short, long)]
     web: bool,
@@ -115,30 +377,71 @@ pub struct IssueListCommandArgs {
 #[derive(Args)]
 pub struct IssueCreateCommandArgs {
     /// Specify the forge which affects the API schema etc.
-    #[arg(long, value_name = "TYPE")]
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
     api: Option<ApiType>,
 
     /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
     /// instead of relying on the auto-detection
-    #[arg(long)]
-    api_url: Option<String>,
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
     /// Issue description
     #[arg(short, long)]
     body: Option<String>,
 
+    /// Create the labels of --labels that don't exist in the repository
+    /// instead of failing. Can also be set with the
+    /// `issue/create/missing-labels=create` setting
+    #[arg(long, conflicts_with = "ignore_missing_labels")]
+    create_missing_labels: bool,
+
     /// Open your text editor to write the issue message
     #[arg(short, long)]
     editor: bool,
 
+    /// Output format of the created issue. `json` prints the whole issue as a
+    /// JSON object even when the browser is opened [default: url]
+    #[arg(short = 'o', long, conflicts_with = "web")]
+    format: Option<CreatedItemFormat>,
+
+    /// Leave out the labels of --labels that don't exist in the repository
+    /// instead of failing. Can also be set with the
+    /// `issue/create/missing-labels=ignore` setting
+    #[arg(long)]
+    ignore_missing_labels: bool,
+
+    /// Labels to add (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    labels: Vec<String>,
+
     /// Don't open the issue in the browser after creation
     #[arg(short, long)]
     no_browser: bool,
 
+    /// Don't check whether the repository is archived or accessible with the
+    /// token before asking for the issue
+    #[arg(long)]
+    no_precheck: bool,
+
+    /// Don't ask for labels after writing the message in the text editor. Can
+    /// also be disabled with the `issue/create/prompt-labels=false` setting
+    #[arg(long)]
+    no_prompt: bool,
+
+    /// What to do with the issue after creating it instead of asking. Without
+    /// a terminal the issue is opened in the browser. --no-browser skips this
+    #[arg(long, value_name = "ACTION")]
+    post_action: Option<IssuePostAction>,
+
     /// Git remote to use
     #[arg(long)]
     remote: Option<String>,
 
+    /// Don't search for similar open issues before creating the issue. The
+    /// check only runs if stdin and stderr are terminals
+    #[arg(long)]
+    skip_duplicate_check: bool,
+
     /// Issue title
     #[arg(short, long)]
     title: Option<String>,
@@ -148,321 +451,2574 @@ pub struct IssueCreateCommandArgs {
     web: bool,
 }
 
-// =============================================================================
-// Domain Types
-// =============================================================================
+/// Command-line arguments for snapshotting issues.
+#[derive(Args)]
+pub struct IssueSnapshotCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize, ValueEnum)]
-#[serde(rename_all = "snake_case")]
-pub enum IssueState {
-    /// Open issues that haven't been closed yet.
-    #[default]
-    Open,
-    /// Closed issues that have been resolved.
-    Closed,
-    /// All issues regardless of state.
-    All,
-}
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
-impl std::fmt::Display for IssueState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            IssueState::Open => write!(f, "open"),
-            IssueState::Closed => write!(f, "closed"),
-            IssueState::All => write!(f, "all"),
-        }
-    }
-}
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
 
-#[derive(Clone, Debug, Deserialize, Serialize, ValueEnum)]
-#[serde(rename_all = "snake_case")]
-pub enum IssueField {
-    Id,
-    Title,
-    State,
-    Labels,
-    Author,
-    Url,
-}
+    /// Print the issues that were created, closed, reopened or retitled since
+    /// the last snapshot instead of updating the snapshot
+    #[arg(long)]
+    diff: bool,
 
-/// An issue from a git forge.
-#[derive(Clone, Serialize)]
-pub struct Issue {
-    /// The issue number (e.g., #42).
-    pub id: u32,
-    /// The issue title.
-    pub title: String,
-    /// The current state (open, closed, etc.).
-    pub state: IssueState,
-    /// The username of the issue author.
-    pub author: String,
-    /// The web URL to view this issue.
-    pub url: String,
-    /// Labels attached to this issue.
-    pub labels: Vec<String>,
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
 }
 
-impl ListableItem for Issue {
-    fn get_display_text(&self) -> String {
-        format!("{}: {}", self.id, self.title)
-    }
-}
+/// Command-line arguments for exporting issues.
+#[derive(Args)]
+pub struct IssueExportCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
 
-pub struct ListIssueFilters<'a> {
-    pub assignee: Option<&'a str>,
-    pub author: Option<&'a str>,
-    pub labels: &'a [String],
-    pub page: u32,
-    pub per_page: u32,
-    pub query: Option<&'a str>,
-    pub state: &'a IssueState,
-}
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
-pub struct CreateIssueOptions<'a> {
-    pub title: &'a str,
-    pub body: &'a str,
-}
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
 
-// =============================================================================
-// Command Logic
-// =============================================================================
+    /// Fetch all pages instead of only the one given by --page
+    #[arg(long, conflicts_with = "page")]
+    all_pages: bool,
 
-/// Lists issues from the remote repository's forge and outputs them or
-/// open the issues page in the web browser.
-pub fn list_issues(mut args: IssueListCommandArgs) -> anyhow::Result<()> {
-    let config = Config::load_from_disk().context("Failed to load configuration")?;
-    let remote_name = args.remote.clone().unwrap_or_else(|| {
-        config
-            .get_string("issue/list/remote", None)
-            .unwrap_or(DEFAULT_REMOTE.to_string())
-    });
-    let remote = git::get_remote_data(&remote_name)
-        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+    /// Include the comments of each issue. This takes an extra request per
+    /// issue
+    #[arg(long)]
+    comments: bool,
 
-    config::merge_config_into_args!(
-        &config,
-        args,
-        Some(&remote),
-        "issue/list",
-        [
-            api,
-            api_url,
-            auth,
-            fields,
-            format,
-            per_page,
-            state,
-            interactive
-        ]
-    );
+    /// Output format. Only JSON can be read by `issue import` [default: json]
+    #[arg(short = 'o', long)]
+    format: Option<OutputFormat>,
 
-    let api_type = match args.api {
-        Some(api_type) => api_type,
-        None => forge::guess_api_type_from_host(&remote.host)
-            .with_context(|| format!("Failed to guess forge from host: {}", &remote.host))?,
-    };
+    /// Page number to fetch
+    #[arg(long, default_value_t = 1, value_name = "NUMBER")]
+    page: u32,
 
-    if args.interactive {
-        list_issues_interactively(remote, api_type, args)
-    } else if args.web {
-        list_issues_in_web_browser(&remote, &api_type)
-    } else {
-        list_issues_to_stdout(
-            &remote,
-            &api_type,
-            args.api_url.as_deref(),
-            &ListIssueFilters {
-                assignee: args.assignee.as_deref(),
-                author: args.author.as_deref(),
-                labels: &args.labels,
-                page: args.page,
-                per_page: args.per_page.unwrap_or(DEFAULT_PER_PAGE),
-                query: args.query.as_deref(),
-                state: &args.state.unwrap_or_default(),
-            },
-            args.fields,
-            &args.format.unwrap_or_default(),
-            args.auth,
-        )
-    }
-}
+    /// Number of issues per page
+    #[arg(long, short_alias = 'l', alias = "limit", value_name = "NUMBER")]
+    per_page: Option<u32>,
 
-/// Executes the `issue create` subcommand to create an issue.
-pub fn create_issue(mut args: IssueCreateCommandArgs) -> anyhow::Result<()> {
-    let config = Config::load_from_disk().context("Failed to load configuration")?;
-    let remote_name = args.remote.clone().unwrap_or_else(|| {
-        config
-            .get_string("issue/create/remote", None)
-            .unwrap_or(DEFAULT_REMOTE.to_string())
-    });
-    let remote = git::get_remote_data(&remote_name)
-        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
 
-    config::merge_config_into_args!(
-        &config,
-        args,
-        Some(&remote),
-        "issue/create",
-        [api, api_url, editor, no_browser, web]
-    );
+    /// Filter by state [default: all]
+    #[arg(long)]
+    state: Option<IssueState>,
+}
 
-    let api_type = match args.api {
-        Some(api_type) => api_type,
-        None => forge::guess_api_type_from_host(&remote.host)
-            .with_context(|| format!("Failed to guess forge from host: {}", &remote.host))?,
-    };
+/// Command-line arguments for importing issues.
+#[derive(Args)]
+pub struct IssueImportCommandArgs {
+    /// The JSON file written by `issue export`. Comments aren't imported
+    file: PathBuf,
 
-    if args.web {
-        return create_issue_via_browser(&remote, &api_type);
-    }
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Seconds to wait between creating issues to stay below the forge's
+    /// rate limits [default: 1]
+    #[arg(long, value_name = "SECONDS")]
+    delay: Option<u32>,
+
+    /// Only print which issues would be created or skipped
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+/// Command-line arguments for pinning and unpinning issues.
+#[derive(Args)]
+pub struct IssuePinCommandArgs {
+    /// The number of the issue
+    number: u32,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+/// Command-line arguments for assigning issues.
+#[derive(Args)]
+pub struct IssueAssignCommandArgs {
+    /// The number of the issue
+    number: u32,
+
+    /// The users to assign. Defaults to yourself
+    #[arg(value_delimiter = ',', value_name = "USERNAMES")]
+    users: Vec<String>,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Output format of the resulting assignees [default: text]
+    #[arg(short = 'o', long)]
+    format: Option<ResultFormat>,
+
+    /// Assign yourself in addition to the given users
+    #[arg(long)]
+    me: bool,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// The user to assign. Kept for compatibility, pass the users as
+    /// arguments instead
+    #[arg(long, value_name = "USERNAME", hide = true)]
+    to: Option<String>,
+}
+
+/// Command-line arguments for unassigning issues.
+#[derive(Args)]
+pub struct IssueUnassignCommandArgs {
+    /// The number of the issue
+    number: u32,
+
+    /// The users to unassign. Defaults to yourself
+    #[arg(value_delimiter = ',', value_name = "USERNAMES")]
+    users: Vec<String>,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Output format of the remaining assignees [default: text]
+    #[arg(short = 'o', long)]
+    format: Option<ResultFormat>,
+
+    /// The user to unassign. Kept for compatibility, pass the users as
+    /// arguments instead
+    #[arg(long, value_name = "USERNAME", hide = true)]
+    from: Option<String>,
+
+    /// Unassign yourself in addition to the given users
+    #[arg(long)]
+    me: bool,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+/// Command-line arguments for the `issue label` subcommand.
+#[derive(Args)]
+pub struct IssueLabelCommandArgs {
+    #[command(subcommand)]
+    pub subcommand: IssueLabelCommand,
+}
+
+/// Available subcommands for changing the labels of issues.
+#[derive(Subcommand)]
+pub enum IssueLabelCommand {
+    /// Add labels to an issue, e.g. `issue label add 42 bug ui`, or to the
+    /// issues given with --numbers, --stdin or --where.
+    Add(IssueLabelEditCommandArgs),
+
+    /// Remove labels from an issue or from the issues given with --numbers,
+    /// --stdin or --where.
+    Remove(IssueLabelEditCommandArgs),
+
+    /// Replace the labels of an issue or of the issues given with --numbers,
+    /// --stdin or --where. `issue label set 42` removes all labels.
+    Set(IssueLabelEditCommandArgs),
+}
+
+/// Command-line arguments for adding, removing and replacing the labels of
+/// issues.
+#[derive(Args)]
+#[command(group(ArgGroup::new("issues")))]
+pub struct IssueLabelEditCommandArgs {
+    /// The number of the issue followed by the labels. Only the labels if the
+    /// issues are given with --numbers, --stdin or --where. The labels need
+    /// to exist in the repository
+    #[arg(required = true, value_name = "LABEL")]
+    labels: Vec<String>,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Print the issues that would be changed without changing them
+    #[arg(long, conflicts_with = "yes")]
+    dry_run: bool,
+
+    /// Output format of the resulting labels [default: tsv]
+    #[arg(short = 'o', long)]
+    format: Option<OutputFormat>,
+
+    /// The numbers of the issues to change (comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "NUMBERS", group = "issues")]
+    numbers: Vec<u32>,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Read the numbers of the issues to change from stdin, separated by
+    /// whitespace, e.g. from `issue list --ids-only`
+    #[arg(long, group = "issues")]
+    stdin: bool,
+
+    /// Change the issues that `issue list` lists with these options, e.g.
+    /// `--where '--labels bug --state open --query crash'`. All pages are
+    /// fetched unless --limit is given. The issues are shown and need to be
+    /// confirmed first
+    #[arg(
+        long = "where",
+        value_name = "OPTIONS",
+        group = "issues",
+        allow_hyphen_values = true
+    )]
+    filters: Option<String>,
+
+    /// Change the issues matching --where without asking for confirmation
+    #[arg(short, long)]
+    yes: bool,
+}
+
+/// Command-line arguments for viewing an issue.
+#[derive(Args)]
+pub struct IssueViewCommandArgs {
+    /// The number of the issue
+    number: u32,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
+
+    /// Print the issue in this format instead of rendering it. Use `json` for
+    /// tooling
+    #[arg(short = 'o', long)]
+    format: Option<OutputFormat>,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Open the issue in the web browser instead of printing it
+    #[arg(short, long, conflicts_with = "format")]
+    web: bool,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueState {
+    /// Open issues that haven't been closed yet.
+    #[default]
+    Open,
+    /// Closed issues that have been resolved.
+    Closed,
+    /// All issues regardless of state.
+    All,
+}
+
+impl std::fmt::Display for IssueState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueState::Open => write!(f, "open"),
+            IssueState::Closed => write!(f, "closed"),
+            IssueState::All => write!(f, "all"),
+        }
+    }
+}
+
+/// What to do with an issue after `issue create`.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum IssuePostAction {
+    /// Open the issue in the browser.
+    Open,
+    /// Copy the URL of the issue to the clipboard.
+    Copy,
+    /// Assign the issue to the user of the token.
+    Assign,
+    /// Do nothing.
+    #[value(name = "none")]
+    Nothing,
+}
+
+impl std::fmt::Display for IssuePostAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssuePostAction::Open => write!(f, "Open in browser"),
+            IssuePostAction::Copy => write!(f, "Copy URL"),
+            IssuePostAction::Assign => write!(f, "Assign to me"),
+            IssuePostAction::Nothing => write!(f, "Nothing"),
+        }
+    }
+}
+
+/// Why an issue was closed (or reopened). Only GitHub tracks this.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStateReason {
+    /// Closed because the issue was resolved.
+    Completed,
+    /// Closed without being resolved, e.g. won't fix or stale.
+    NotPlanned,
+    /// Closed as a duplicate of another issue.
+    Duplicate,
+    /// Reopened after being closed.
+    #[value(skip)]
+    Reopened,
+}
+
+impl std::fmt::Display for IssueStateReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueStateReason::Completed => write!(f, "completed"),
+            IssueStateReason::NotPlanned => write!(f, "not-planned"),
+            IssueStateReason::Duplicate => write!(f, "duplicate"),
+            IssueStateReason::Reopened => write!(f, "reopened"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueField {
+    Id,
+    Title,
+    State,
+    StateReason,
+    Labels,
+    Author,
+    Url,
+    Pinned,
+    CreatedAt,
+    ClosedAt,
+    Reactions,
+    Type,
+    All,
+    /// The git remote the issue was fetched from. Only available with
+    /// `--all-remotes`.
+    #[value(skip)]
+    Remote,
+}
+
+/// An issue from a git forge.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Issue {
+    /// The issue number (e.g., #42).
+    pub id: u32,
+    /// The issue title.
+    pub title: String,
+    /// The current state (open, closed, etc.).
+    pub state: IssueState,
+    /// Why the issue was closed or reopened. Only set for GitHub.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_reason: Option<IssueStateReason>,
+    /// The username of the issue author.
+    pub author: String,
+    /// The web URL to view this issue.
+    pub url: String,
+    /// Labels attached to this issue.
+    pub labels: Vec<String>,
+    /// Whether the issue is pinned to the top of the issue tracker. Only set
+    /// for Gitea/Forgejo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    /// Timestamp when the issue was created. Missing in snapshots of older
+    /// versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<Timestamp>,
+    /// Timestamp when the issue was closed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<Timestamp>,
+    /// The number of reactions (GitHub) or upvotes (GitLab). Not available
+    /// for Gitea/Forgejo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<u32>,
+    /// Whether the item is an issue or a PR. Only set by `issue list
+    /// --include-prs`.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<IssueKind>,
+}
+
+/// The type of an item listed by `issue list --include-prs`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    Issue,
+    Pr,
+}
+
+/// What `issue list --since-last-run` compares with the last run.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SinceLastRun {
+    /// The issues created since the last run.
+    Created,
+    /// The issues created or updated since the last run.
+    Updated,
+}
+
+/// What issues can be sorted by with `issue list --sort`.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum IssueSort {
+    /// Recently closed issues first. Open issues come last.
+    Closed,
+    /// Issues with the most reactions (GitHub) or upvotes (GitLab) first.
+    Reactions,
+}
+
+impl ListableItem for Issue {
+    fn get_display_text(&self) -> String {
+        let badge = match self.kind {
+            Some(IssueKind::Pr) => "PR ",
+            _ => "",
+        };
+
+        match self.reactions {
+            Some(reactions) if reactions > 0 => {
+                format!("{}: {badge}{} (+{reactions})", self.id, self.title)
+            }
+            _ => format!("{}: {badge}{}", self.id, self.title),
+        }
+    }
+
+    fn get_id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn get_sort_value(&self, key: SortKey) -> Option<SortValue> {
+        match key {
+            SortKey::Id => Some(SortValue::Number(u64::from(self.id))),
+            SortKey::Created => self.created_at.map(SortValue::Timestamp),
+            SortKey::Title => Some(SortValue::Text(self.title.clone())),
+            SortKey::Reactions => self.reactions.map(|n| SortValue::Number(u64::from(n))),
+            SortKey::Updated => None,
+        }
+    }
+}
+
+/// An issue together with the git remote it was fetched from. Used by
+/// `issue list --all-remotes`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RemoteIssue {
+    /// The name of the git remote.
+    pub remote: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+}
+
+impl ListableItem for RemoteIssue {
+    fn get_display_text(&self) -> String {
+        format!("[{}] {}", self.remote, self.issue.get_display_text())
+    }
+
+    fn get_id(&self) -> String {
+        format!("{}/{}", self.remote, self.issue.id)
+    }
+
+    fn get_sort_value(&self, key: SortKey) -> Option<SortValue> {
+        self.issue.get_sort_value(key)
+    }
+}
+
+pub struct ListIssueFilters<'a> {
+    pub assignee: Option<&'a str>,
+    pub author: Option<&'a str>,
+    pub closed: DateRange,
+    pub closed_as: Option<&'a IssueStateReason>,
+    pub created: DateRange,
+    /// Also list the PRs like GitHub's combined view of issues and PRs.
+    pub include_prs: bool,
+    pub labels: &'a LabelFilter,
+    pub min_reactions: Option<u32>,
+    pub page: u32,
+    pub pinned: bool,
+    pub per_page: u32,
+    pub query: Option<&'a str>,
+    pub sort: Option<&'a IssueSort>,
+    pub state: &'a IssueState,
+    pub updated: DateRange,
+}
+
+/// A change of an issue between two snapshots.
+#[derive(Debug, PartialEq)]
+pub enum IssueChange<'a> {
+    Created(&'a Issue),
+    Closed(&'a Issue),
+    Reopened(&'a Issue),
+    Retitled { old: &'a Issue, new: &'a Issue },
+}
+
+pub struct CreateIssueOptions<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub labels: &'a [String],
+}
+
+/// An issue with the data needed to recreate it on another forge. Used by
+/// `issue export` and `issue import`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExportedIssue {
+    /// The issue number on the source forge.
+    pub id: u32,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub state: IssueState,
+    /// The username of the issue author.
+    pub author: String,
+    /// The creation date as returned by the forge (RFC 3339).
+    pub created_at: String,
+    /// The web URL of the issue on the source forge.
+    pub url: String,
+    pub labels: Vec<String>,
+    /// Only set with `issue export --comments`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<IssueComment>,
+}
+
+/// A comment of an exported issue.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IssueComment {
+    /// The id of the comment on the forge it was exported from.
+    #[serde(default)]
+    pub id: u64,
+    pub author: String,
+    pub created_at: String,
+    pub body: String,
+}
+
+/// The `issue list` options of `issue label --where`.
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct IssueFilterArgs {
+    #[command(flatten)]
+    list: IssueListCommandArgs,
+}
+
+/// Whether `issue label` adds, removes or replaces the labels.
+#[derive(Clone, Copy, PartialEq)]
+enum LabelEdit {
+    Add,
+    Remove,
+    Set,
+}
+
+/// The labels of an issue after `issue label`.
+#[derive(Debug, Serialize)]
+struct IssueLabels {
+    id: u32,
+    labels: Vec<String>,
+}
+
+/// The assignees of an issue after `issue assign` or `issue unassign`.
+#[derive(Debug, Serialize)]
+struct IssueAssignees {
+    id: u32,
+    assignees: Vec<String>,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Lists issues from the remote repository's forge and outputs them or
+/// open the issues page in the web browser.
+pub fn list_issues(mut args: IssueListCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+
+    if args.limit.is_some() && args.per_page.is_none() {
+        log::warn!(
+            "--limit caps the total number of issues now. Use --per-page to only set the page size"
+        );
+    }
+
+    let (created, closed) = get_date_ranges(&args);
+
+    created.validate("--created")?;
+    closed.validate("--closed")?;
+
+    // Only closed issues have a closing date. This comes before the config
+    // so that a configured state doesn't hide all issues.
+    if closed.is_set() && args.state.is_none() {
+        args.state = Some(IssueState::Closed);
+    }
+
+    if args.all_remotes {
+        return list_issues_of_all_remotes(&config, args);
+    }
+
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/list/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/list",
+        [
+            api,
+            api_url,
+            auth,
+            format,
+            limit,
+            max_title_width,
+            per_page,
+            state,
+            interactive,
+            no_truncate
+        ]
+    );
+
+    args.state = Some(get_implied_state(args.state, args.closed_as.as_ref()));
+
+    args.fields = io::resolve_fields(
+        args.fields,
+        config.get_string("issue/list/fields", Some(&remote)),
+        vec![IssueField::Title, IssueField::Id, IssueField::Url],
+    )?;
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+
+    if let Some(since_last_run) = args.since_last_run {
+        list_issues_since_last_run(&remote, &api_type, since_last_run, args)
+    } else if args.interactive {
+        list_issues_interactively(remote, api_type, args)
+    } else if args.web {
+        list_issues_in_web_browser(&remote, &api_type, created, closed)
+    } else if args.watch {
+        watch_issues(&remote, &api_type, args)
+    } else if args.offline {
+        list_issues_offline(&remote, args)
+    } else {
+        list_issues_to_stdout(&remote, &api_type, args)
+    }
+}
+
+/// Executes the `issue create` subcommand to create an issue.
+pub fn create_issue(mut args: IssueCreateCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/create/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/create",
+        [
+            api,
+            api_url,
+            editor,
+            format,
+            no_browser,
+            no_precheck,
+            post_action,
+            skip_duplicate_check,
+            web
+        ]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+
+    if args.web {
+        return create_issue_via_browser(&remote, &api_type);
+    }
+
+    if !args.no_precheck {
+        forge::check_repo(
+            &HttpClient::new(),
+            &api_type,
+            &remote,
+            args.api_url.as_deref(),
+            "issues",
+        )?;
+    }
+
+    let missing_labels =
+        MissingLabels::from_flags(args.create_missing_labels, args.ignore_missing_labels)
+            .or_else(|| config.get_enum("issue/create/missing-labels", Some(&remote)))
+            .unwrap_or_default();
+    let labels = forge::resolve_labels(
+        &HttpClient::new(),
+        &api_type,
+        &remote,
+        args.api_url.as_deref(),
+        &args.labels,
+        missing_labels,
+    )?;
+    let check_duplicates = !args.skip_duplicate_check && io::is_interactive();
+    let issue = if args.editor {
+        let prompt_labels = !args.no_prompt
+            && config
+                .get_bool("issue/create/prompt-labels", Some(&remote))
+                .unwrap_or(true);
+
+        create_issue_with_text_editor(
+            &remote,
+            &api_type,
+            args.api_url.as_deref(),
+            config
+                .get_string_from_global_scope("editor-command")
+                .as_deref(),
+            labels,
+            prompt_labels,
+            check_duplicates,
+        )?
+    } else {
+        let title = match args.title {
+            Some(t) => t,
+            None => io::prompt_text("Enter issue title", "--title")?,
+        };
+
+        create_issue_via_api(
+            &remote,
+            &api_type,
+            args.api_url.as_deref(),
+            &CreateIssueOptions {
+                title: &title,
+                body: &args.body.unwrap_or_default(),
+                labels: &labels,
+            },
+            check_duplicates,
+        )?
+    };
+    let Some(issue) = issue else {
+        eprintln!("Didn't create the issue");
+
+        return Ok(());
+    };
+
+    match args.format.unwrap_or_default() {
+        CreatedItemFormat::Url if args.no_browser => println!("{}", issue.url),
+        CreatedItemFormat::Url => {}
+        CreatedItemFormat::Json => println!("{}", serde_json::to_string_pretty(&issue)?),
+    }
+
+    if args.no_browser {
+        return Ok(());
+    }
+
+    let post_action = match args.post_action {
+        Some(post_action) => post_action,
+        None => io::prompt_post_action(
+            &format!("Created issue #{}. What next?", issue.id),
+            &[
+                IssuePostAction::Open,
+                IssuePostAction::Copy,
+                IssuePostAction::Assign,
+                IssuePostAction::Nothing,
+            ],
+            "--post-action or --no-browser",
+        )?,
+    };
+
+    match post_action {
+        IssuePostAction::Open => {
+            eprintln!("Opening issue in browser: {}", issue.url);
+
+            open::that(&issue.url)?;
+        }
+        IssuePostAction::Copy => {
+            io::copy_to_clipboard(&issue.url)?;
+
+            eprintln!("Copied {} to the clipboard", issue.url);
+        }
+        IssuePostAction::Assign => {
+            let http_client = HttpClient::new();
+            let me = resolve_assignees(
+                &http_client,
+                &api_type,
+                &remote,
+                args.api_url.as_deref(),
+                vec![],
+                true,
+            )?;
+            let assign_issue = forge::function!(api_type, assign_issue);
+
+            assign_issue(
+                &http_client,
+                &remote,
+                args.api_url.as_deref(),
+                issue.id,
+                &me,
+            )
+            .with_context(|| format!("Failed to assign issue #{}", issue.id))?;
+
+            eprintln!("Assigned issue #{} to you", issue.id);
+        }
+        IssuePostAction::Nothing => {}
+    }
+
+    Ok(())
+}
+
+/// Executes the `issue snapshot` subcommand.
+pub fn snapshot_issues(mut args: IssueSnapshotCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/snapshot/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/snapshot",
+        [api, api_url, auth]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let snapshot_path = storage::get_snapshot_path(&remote, ISSUE_SNAPSHOT_NAME)?;
+
+    eprintln!("Fetching all issues...");
+
+    let issues = get_all_issues(&remote, &api_type, args.api_url.as_deref(), args.auth)?;
+
+    if args.diff {
+        let Some(snapshot) = storage::read_json::<Vec<Issue>>(&snapshot_path)? else {
+            anyhow::bail!("No snapshot found. Create one with `git forge issue snapshot`");
+        };
+
+        for change in diff_issues(&snapshot, &issues) {
+            match change {
+                IssueChange::Created(issue) => println!("created\t{}\t{}", issue.id, issue.title),
+                IssueChange::Closed(issue) => println!("closed\t{}\t{}", issue.id, issue.title),
+                IssueChange::Reopened(issue) => println!("reopened\t{}\t{}", issue.id, issue.title),
+                IssueChange::Retitled { old, new } => {
+                    println!("retitled\t{}\t{}\t{}", new.id, new.title, old.title)
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    storage::write_json(&snapshot_path, &issues)?;
+
+    eprintln!(
+        "Saved {} issues to {}",
+        issues.len(),
+        snapshot_path.display()
+    );
+
+    Ok(())
+}
+
+/// Executes the `issue export` subcommand.
+pub fn export_issues(mut args: IssueExportCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/export/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/export",
+        [api, api_url, auth, format, per_page]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+    let mut issues = get_issues_for_export(
+        &http_client,
+        &remote,
+        &api_type,
+        args.api_url.as_deref(),
+        &ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: LabelFilter::NONE,
+            min_reactions: None,
+            pinned: false,
+            page: args.page,
+            per_page: args.per_page.unwrap_or(SNAPSHOT_PER_PAGE),
+            query: None,
+            sort: None,
+            state: &args.state.unwrap_or(IssueState::All),
+            updated: DateRange::default(),
+        },
+        args.all_pages,
+        args.auth,
+    )?;
+
+    if args.comments {
+        let get_issue_comments = forge::function!(api_type, get_issue_comments);
+
+        for issue in &mut issues {
+            issue.comments = get_issue_comments(
+                &http_client,
+                &remote,
+                args.api_url.as_deref(),
+                issue.id,
+                args.auth,
+            )
+            .with_context(|| format!("Failed fetching the comments of issue #{}", issue.id))?;
+        }
+    }
+
+    eprintln!("Exported {} issues", issues.len());
+
+    println!(
+        "{}",
+        io::format::<_, IssueField>(&issues, &[], &args.format.unwrap_or(OutputFormat::Json))?
+    );
+
+    Ok(())
+}
+
+/// Executes the `issue import` subcommand. Issues that were imported before
+/// are recognized by the marker in their body and skipped.
+pub fn import_issues(mut args: IssueImportCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/import/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/import",
+        [api, api_url, delay]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let file = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+    let source_issues: Vec<ExportedIssue> = serde_json::from_str(&file).with_context(|| {
+        format!(
+            "Failed to parse {}. Export the issues with `git forge issue export --format json`",
+            args.file.display()
+        )
+    })?;
+    let http_client = HttpClient::new();
+
+    eprintln!("Checking for previously imported issues...");
+
+    // Authenticate even for dry runs, so that they see the same issues as
+    // the actual import
+    let existing_issues = get_issues_for_export(
+        &http_client,
+        &remote,
+        &api_type,
+        args.api_url.as_deref(),
+        &ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: LabelFilter::NONE,
+            min_reactions: None,
+            pinned: false,
+            page: 1,
+            per_page: SNAPSHOT_PER_PAGE,
+            query: None,
+            sort: None,
+            state: &IssueState::All,
+            updated: DateRange::default(),
+        },
+        true,
+        true,
+    )?;
+    let imported_urls: HashSet<&str> = existing_issues
+        .iter()
+        .filter_map(|issue| get_import_source_url(&issue.body))
+        .collect();
+    let (skipped, pending): (Vec<_>, Vec<_>) = source_issues
+        .iter()
+        .partition(|issue| imported_urls.contains(issue.url.as_str()));
+
+    if args.dry_run {
+        for issue in &skipped {
+            println!("skip\t{}\t{}", issue.id, issue.title);
+        }
+
+        for issue in &pending {
+            println!("create\t{}\t{}\t{}", issue.id, issue.state, issue.title);
+        }
+
+        eprintln!(
+            "Would create {} issues ({} closed) and skip {} already imported issues",
+            pending.len(),
+            pending
+                .iter()
+                .filter(|issue| issue.state == IssueState::Closed)
+                .count(),
+            skipped.len()
+        );
+
+        return Ok(());
+    }
+
+    let create_issue = forge::function!(api_type, create_issue);
+    let close_issue = forge::function!(api_type, close_issue);
+    let delay = Duration::from_secs(args.delay.unwrap_or(DEFAULT_IMPORT_DELAY).into());
+
+    for (index, issue) in pending.iter().enumerate() {
+        if index > 0 {
+            thread::sleep(delay);
+        }
+
+        let created_issue = create_issue(
+            &http_client,
+            &remote,
+            args.api_url.as_deref(),
+            &CreateIssueOptions {
+                title: &issue.title,
+                body: &build_imported_issue_body(issue),
+                labels: &issue.labels,
+            },
+        )
+        .with_context(|| {
+            format!(
+                "Failed to import issue #{}. Rerun the command to continue, imported issues are skipped",
+                issue.id
+            )
+        })?;
+
+        if issue.state == IssueState::Closed {
+            thread::sleep(delay);
+
+            close_issue(
+                &http_client,
+                &remote,
+                args.api_url.as_deref(),
+                created_issue.id,
+            )
+            .with_context(|| format!("Failed to close the imported issue #{}", created_issue.id))?;
+        }
+
+        println!("{}\t{}", issue.id, created_issue.url);
+        eprintln!(
+            "Imported {}/{} issues{}",
+            index + 1,
+            pending.len(),
+            throttle::take_wait_note()
+        );
+    }
+
+    eprintln!(
+        "Imported {} issues and skipped {} already imported issues",
+        pending.len(),
+        skipped.len()
+    );
+
+    Ok(())
+}
+
+/// Executes the `issue pin` subcommand.
+pub fn pin_issue(args: IssuePinCommandArgs) -> anyhow::Result<()> {
+    set_issue_pinned(args, true)
+}
+
+/// Executes the `issue unpin` subcommand.
+pub fn unpin_issue(args: IssuePinCommandArgs) -> anyhow::Result<()> {
+    set_issue_pinned(args, false)
+}
+
+/// Executes the `issue assign` subcommand.
+pub fn assign_issue(mut args: IssueAssignCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/assign/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/assign",
+        [api, api_url, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+    let users = resolve_assignees(
+        &http_client,
+        &api_type,
+        &remote,
+        args.api_url.as_deref(),
+        args.users.into_iter().chain(args.to).collect(),
+        args.me,
+    )?;
+    let assign_issue = forge::function!(api_type, assign_issue);
+    let assignees = assign_issue(
+        &http_client,
+        &remote,
+        args.api_url.as_deref(),
+        args.number,
+        &users,
+    )
+    .with_context(|| format!("Failed to assign issue #{}", args.number))?;
+
+    print_issue_assignees(
+        &IssueAssignees {
+            id: args.number,
+            assignees,
+        },
+        &args.format.unwrap_or_default(),
+    )
+}
+
+/// Executes the `issue unassign` subcommand.
+pub fn unassign_issue(mut args: IssueUnassignCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/unassign/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/unassign",
+        [api, api_url, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+    let users = resolve_assignees(
+        &http_client,
+        &api_type,
+        &remote,
+        args.api_url.as_deref(),
+        args.users.into_iter().chain(args.from).collect(),
+        args.me,
+    )?;
+    let unassign_issue = forge::function!(api_type, unassign_issue);
+    let assignees = unassign_issue(
+        &http_client,
+        &remote,
+        args.api_url.as_deref(),
+        args.number,
+        &users,
+    )
+    .with_context(|| format!("Failed to unassign issue #{}", args.number))?;
+
+    print_issue_assignees(
+        &IssueAssignees {
+            id: args.number,
+            assignees,
+        },
+        &args.format.unwrap_or_default(),
+    )
+}
+
+/// Executes the `issue label add` subcommand.
+pub fn add_issue_labels(args: IssueLabelEditCommandArgs) -> anyhow::Result<()> {
+    edit_issue_labels(args, LabelEdit::Add)
+}
+
+/// Executes the `issue label remove` subcommand.
+pub fn remove_issue_labels(args: IssueLabelEditCommandArgs) -> anyhow::Result<()> {
+    edit_issue_labels(args, LabelEdit::Remove)
+}
+
+/// Executes the `issue label set` subcommand.
+pub fn set_issue_labels(args: IssueLabelEditCommandArgs) -> anyhow::Result<()> {
+    edit_issue_labels(args, LabelEdit::Set)
+}
+
+/// Executes the `issue view` subcommand.
+pub fn view_issue(mut args: IssueViewCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/view/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/view",
+        [api, api_url, auth, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+
+    if args.web {
+        let get_issue_url = forge::function!(api_type, get_url_for_issue);
+
+        open::that(get_issue_url(&remote, args.number))?;
+
+        return Ok(());
+    }
+
+    let get_issue = forge::function!(api_type, get_issue);
+    let issue = get_issue(
+        &HttpClient::new(),
+        &remote,
+        args.api_url.as_deref(),
+        args.number,
+        args.auth,
+    )
+    .with_context(|| format!("Failed fetching issue #{}", args.number))?;
+
+    match args.format {
+        Some(format) => println!(
+            "{}",
+            io::format::<_, IssueField>(std::slice::from_ref(&issue), &[], &format)?
+        ),
+        None => io::print_with_pager(&render_issue(&issue))?,
+    }
+
+    Ok(())
+}
+
+/// Marks the items that the forge didn't mark as PRs as issues if the filters
+/// include PRs, so that every item of `issue list --include-prs` has a type.
+pub fn mark_issue_kinds(issues: &mut [Issue], filters: &ListIssueFilters) {
+    if filters.include_prs {
+        for issue in issues {
+            issue.kind.get_or_insert(IssueKind::Issue);
+        }
+    }
+}
+
+/// Applies the list filters to issues, e.g. from a snapshot. The query only
+/// matches the title (case-insensitive).
+pub fn filter_issues(issues: Vec<Issue>, filters: &ListIssueFilters) -> Vec<Issue> {
+    let query = filters.query.map(str::to_lowercase);
+    let skip = (filters.page.saturating_sub(1) * filters.per_page) as usize;
+
+    issues
+        .into_iter()
+        .filter(|issue| match filters.state {
+            IssueState::All => true,
+            state => issue.state == *state,
+        })
+        .filter(|issue| filters.author.is_none_or(|author| issue.author == author))
+        .filter(|issue| {
+            filters
+                .closed_as
+                .is_none_or(|reason| issue.state_reason.as_ref() == Some(reason))
+        })
+        .filter(|issue| filters.labels.matches(&issue.labels))
+        .filter(|issue| !filters.pinned || issue.pinned == Some(true))
+        .filter(|issue| has_min_reactions(issue, filters.min_reactions))
+        .filter(|issue| filters.created.contains(issue.created_at))
+        .filter(|issue| filters.closed.contains(issue.closed_at))
+        .filter(|issue| {
+            query
+                .as_ref()
+                .is_none_or(|q| issue.title.to_lowercase().contains(q))
+        })
+        .skip(skip)
+        .take(filters.per_page as usize)
+        .collect()
+}
+
+/// Compares two lists of issues by id and returns how the issues changed.
+pub fn diff_issues<'a>(old_issues: &'a [Issue], new_issues: &'a [Issue]) -> Vec<IssueChange<'a>> {
+    let mut changes = vec![];
+
+    for new in new_issues {
+        let Some(old) = old_issues.iter().find(|old| old.id == new.id) else {
+            changes.push(IssueChange::Created(new));
+            continue;
+        };
+
+        match (&old.state, &new.state) {
+            (IssueState::Open, IssueState::Closed) => changes.push(IssueChange::Closed(new)),
+            (IssueState::Closed, IssueState::Open) => changes.push(IssueChange::Reopened(new)),
+            _ => {}
+        }
+
+        if old.title != new.title {
+            changes.push(IssueChange::Retitled { old, new });
+        }
+    }
+
+    changes
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// `--closed-as` only makes sense for closed issues, so it implies the
+/// closed state.
+fn get_implied_state(
+    state: Option<IssueState>,
+    closed_as: Option<&IssueStateReason>,
+) -> IssueState {
+    match closed_as {
+        Some(_) => IssueState::Closed,
+        None => state.unwrap_or_default(),
+    }
+}
+
+/// Gets the users to assign or unassign. That's yourself if no users are
+/// given or if `me` is set.
+fn resolve_assignees(
+    http_client: &HttpClient,
+    api_type: &ApiType,
+    remote: &GitRemoteData,
+    api_url: Option<&str>,
+    users: Vec<String>,
+    me: bool,
+) -> anyhow::Result<Vec<String>> {
+    let users = normalize_usernames(users);
+    let mut assignees = vec![];
+
+    if me || users.is_empty() {
+        let get_authenticated_user = forge::function!(api_type, get_authenticated_user);
+        let username = get_authenticated_user(http_client, remote, api_url)
+            .context("Failed to get the authenticated user")?;
+
+        assignees.push(username);
+    }
+
+    assignees.extend(users);
+
+    Ok(normalize_usernames(assignees))
+}
+
+/// Drops empty and duplicate usernames and the `@` of mentions.
+fn normalize_usernames(users: Vec<String>) -> Vec<String> {
+    let mut usernames: Vec<String> = vec![];
+
+    for user in &users {
+        let username = user.trim().trim_start_matches('@');
+
+        if !username.is_empty() && !usernames.iter().any(|u| u == username) {
+            usernames.push(username.to_string());
+        }
+    }
+
+    usernames
+}
+
+fn print_issue_assignees(assignees: &IssueAssignees, format: &ResultFormat) -> anyhow::Result<()> {
+    match format {
+        ResultFormat::Text => println!("{}", format_issue_assignees(assignees)),
+        ResultFormat::Json => println!("{}", serde_json::to_string_pretty(assignees)?),
+    }
+
+    Ok(())
+}
+
+fn format_issue_assignees(assignees: &IssueAssignees) -> String {
+    if assignees.assignees.is_empty() {
+        return format!("Issue #{} has no assignees", assignees.id);
+    }
+
+    format!(
+        "Assignees of issue #{}: {}",
+        assignees.id,
+        assignees.assignees.join(", ")
+    )
+}
+
+/// Renders an issue as a header of its metadata followed by the description.
+fn render_issue(issue: &ExportedIssue) -> String {
+    let created_at = match Timestamp::parse(&issue.created_at) {
+        Some(created_at) => io::format_timestamp(created_at),
+        None => issue.created_at.clone(),
+    };
+    let labels = match issue.labels.as_slice() {
+        [] => String::new(),
+        labels => format!("Labels: {}\n", labels.join(", ")),
+    };
+    let body = match issue.body.trim() {
+        "" => "No description provided.",
+        body => body,
+    };
+
+    format!(
+        "# {} (#{})\n\nState: {}\nAuthor: {} ({created_at})\n{labels}URL: {}\n\n{body}\n",
+        issue.title, issue.id, issue.state, issue.author, issue.url
+    )
+}
+
+/// Adds or removes the labels of the issues given by --numbers, --stdin or
+/// --where one by one. A failed issue doesn't stop the others, but the command
+/// fails at the end.
+fn edit_issue_labels(mut args: IssueLabelEditCommandArgs, edit: LabelEdit) -> anyhow::Result<()> {
+    let verb = match edit {
+        LabelEdit::Add | LabelEdit::Set => "Labeled",
+        LabelEdit::Remove => "Unlabeled",
+    };
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/label/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "issue/label",
+        [api, api_url, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let has_issue_args = !args.numbers.is_empty() || args.stdin || args.filters.is_some();
+    let (number, labels) = if has_issue_args {
+        (None, args.labels.clone())
+    } else {
+        let (number, labels) = split_issue_number(&args.labels)?;
+
+        (Some(number), labels)
+    };
+
+    if labels.is_empty() && edit != LabelEdit::Set {
+        anyhow::bail!("Pass the labels to change after the issue number");
+    }
+
+    let http_client = HttpClient::new();
+    let labels = forge::find_labels(
+        &http_client,
+        &api_type,
+        &remote,
+        args.api_url.as_deref(),
+        &labels,
+    )?;
+    let targets: Vec<(u32, Option<String>)> = if let Some(number) = number {
+        vec![(number, None)]
+    } else if let Some(filters) = &args.filters {
+        find_issues_for_filters(&remote, &api_type, args.api_url.as_deref(), filters)?
+            .into_iter()
+            .map(|issue| (issue.id, Some(issue.title)))
+            .collect()
+    } else if args.stdin {
+        let mut input = String::new();
+
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("Failed to read the issue numbers from stdin")?;
+
+        parse_issue_numbers(&input)?
+            .into_iter()
+            .map(|number| (number, None))
+            .collect()
+    } else {
+        let mut seen = HashSet::new();
+
+        args.numbers
+            .iter()
+            .filter(|number| seen.insert(**number))
+            .map(|number| (*number, None))
+            .collect()
+    };
+
+    if args.dry_run {
+        for (id, title) in &targets {
+            println!("{}", format_label_target(*id, title.as_deref()));
+        }
+
+        eprintln!("Would change the labels of {} issues", targets.len());
+
+        return Ok(());
+    }
+
+    if targets.is_empty() {
+        eprintln!("No issues to change.");
+
+        return Ok(());
+    }
+
+    if args.filters.is_some() {
+        eprintln!("The labels of these issues will be changed:");
+
+        for (id, title) in &targets {
+            eprintln!("  {}", format_label_target(*id, title.as_deref()));
+        }
+
+        if !io::skips_confirmation(args.yes)
+            && !io::prompt_confirm(
+                &format!("Change the labels of {} issues?", targets.len()),
+                false,
+                "--yes",
+            )?
+        {
+            anyhow::bail!("Aborted. Pass --yes to change the labels without confirmation");
+        }
+    }
+
+    let edit_labels = match edit {
+        LabelEdit::Add => forge::function!(api_type, add_issue_labels),
+        LabelEdit::Remove => forge::function!(api_type, remove_issue_labels),
+        LabelEdit::Set => forge::function!(api_type, set_issue_labels),
+    };
+    let mut results = vec![];
+    let mut failed = vec![];
+
+    for (index, (id, _)) in targets.iter().enumerate() {
+        match edit_labels(&http_client, &remote, args.api_url.as_deref(), *id, &labels) {
+            Ok(labels) => results.push(IssueLabels { id: *id, labels }),
+            Err(e) => {
+                eprintln!("Failed to change the labels of issue #{id}: {e:#}");
+                failed.push(*id);
+            }
+        }
+
+        eprintln!(
+            "{verb} {}/{} issues{}",
+            index + 1 - failed.len(),
+            targets.len(),
+            throttle::take_wait_note()
+        );
+    }
+
+    if !results.is_empty() {
+        println!(
+            "{}",
+            io::format(&results, &[] as &[String], &args.format.unwrap_or_default())?
+        );
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!(
+            "Failed to change the labels of {} of {} issues: {}",
+            failed.len(),
+            targets.len(),
+            failed
+                .iter()
+                .map(|id| format!("#{id}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetches the issues that `issue list` lists with the options of --where.
+fn find_issues_for_filters(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    filters: &str,
+) -> anyhow::Result<Vec<Issue>> {
+    let mut args = IssueFilterArgs::try_parse_from(split_options(filters)?)
+        .map_err(|e| {
+            // The first line is the error without the usage of the whole
+            // command, which doesn't apply to --where
+            let error = e.to_string();
+            let message = error.lines().next().unwrap_or_default();
+
+            anyhow::anyhow!(
+                "Invalid --where '{filters}': {}",
+                message.trim_start_matches("error: ")
+            )
+        })?
+        .list;
+    let (created, closed) = get_date_ranges(&args);
+
+    created.validate("--created")?;
+    closed.validate("--closed")?;
+
+    if closed.is_set() && args.state.is_none() {
+        args.state = Some(IssueState::Closed);
+    }
+
+    args.state = Some(get_implied_state(args.state, args.closed_as.as_ref()));
+    args.per_page = Some(io::get_per_page(
+        args.per_page,
+        args.limit,
+        SNAPSHOT_PER_PAGE,
+    ));
+    args.all_pages = args.limit.is_none();
+    args.api_url = api_url.and_then(|url| forge::parse_api_url(url).ok());
+    args.auth = true;
+
+    eprintln!("Fetching the issues matching --where...");
+
+    fetch_issues(remote, api_type, &args)
+}
+
+/// Splits the options of --where into words like a shell. Single and double
+/// quotes group words, e.g. `--query 'crash on start'`.
+fn split_options(options: &str) -> anyhow::Result<Vec<String>> {
+    let mut words = vec![];
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+
+    for c in options.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_default().push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_default();
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_default().push(c),
+        }
+    }
+
+    if let Some(quote) = quote {
+        anyhow::bail!("Unterminated {quote} in --where '{options}'");
+    }
+
+    words.extend(word);
+
+    Ok(words)
+}
+
+/// Parses the issue numbers of --stdin, e.g. from `issue list --ids-only`.
+/// Duplicates are left out.
+fn parse_issue_numbers(input: &str) -> anyhow::Result<Vec<u32>> {
+    let mut seen = HashSet::new();
+    let mut numbers = vec![];
+
+    for word in input.split_whitespace() {
+        let number = word
+            .trim_start_matches('#')
+            .parse()
+            .with_context(|| format!("'{word}' isn't an issue number"))?;
+
+        if seen.insert(number) {
+            numbers.push(number);
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Splits the issue number off the labels of `issue label add 42 bug`.
+fn split_issue_number(args: &[String]) -> anyhow::Result<(u32, Vec<String>)> {
+    let Some((first, labels)) = args.split_first() else {
+        anyhow::bail!("Pass the issue number and the labels");
+    };
+    let number = first.trim_start_matches('#').parse().map_err(|_| {
+        anyhow::anyhow!(
+            "'{first}' isn't an issue number. Pass the issue number before the labels or the issues with --numbers, --stdin or --where"
+        )
+    })?;
+
+    Ok((number, labels.to_vec()))
+}
+
+fn format_label_target(id: u32, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("#{id}\t{title}"),
+        None => format!("#{id}"),
+    }
+}
+
+fn set_issue_pinned(mut args: IssuePinCommandArgs, pinned: bool) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("issue/pin/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(&config, args, Some(&remote), "issue/pin", [api, api_url]);
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let set_issue_pinned = forge::function!(api_type, set_issue_pinned);
+
+    set_issue_pinned(
+        &HttpClient::new(),
+        &remote,
+        args.api_url.as_deref(),
+        args.number,
+        pinned,
+    )
+    .with_context(|| {
+        let action = if pinned { "pin" } else { "unpin" };
+
+        format!("Failed to {action} issue #{}", args.number)
+    })?;
 
-    if args.editor {
-        return create_issue_with_text_editor(
-            &remote,
-            &api_type,
+    if pinned {
+        println!("Issue #{} is pinned", args.number);
+    } else {
+        println!("Issue #{} is no longer pinned", args.number);
+    }
+
+    Ok(())
+}
+
+fn list_issues_in_web_browser(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    created: DateRange,
+    closed: DateRange,
+) -> anyhow::Result<()> {
+    let url = if created.is_set() || closed.is_set() {
+        let get_issue_search_url = forge::function!(api_type, get_url_for_issue_search);
+
+        get_issue_search_url(remote, &created, &closed)
+    } else {
+        let get_issues_url = forge::function!(api_type, get_url_for_issues);
+
+        get_issues_url(remote)
+    };
+
+    open::that(url)?;
+
+    Ok(())
+}
+
+fn list_issues_to_stdout(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: IssueListCommandArgs,
+) -> anyhow::Result<()> {
+    let count = fetch_and_print_issues(remote, api_type, &args)?;
+
+    io::check_list_assertions(count, args.fail_if_any, args.fail_if_none)
+}
+
+/// Lists the issues created or updated since the last successful run with the
+/// same filters and advances the marker to the start of this run unless
+/// --dry-run is given. The marker is advanced before --fail-if-any/
+/// --fail-if-none are checked so that the same issues don't fail the next run
+/// again.
+fn list_issues_since_last_run(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    since_last_run: SinceLastRun,
+    mut args: IssueListCommandArgs,
+) -> anyhow::Result<()> {
+    // The markers are kept apart since one run mustn't advance the other's
+    let marker_name = match since_last_run {
+        SinceLastRun::Created => "issue-list",
+        SinceLastRun::Updated => "issue-list-updated",
+    };
+    let marker = RunMarker::new(remote, marker_name, &get_marker_filters(&args))?;
+    let started_at = OffsetDateTime::now_utc();
+    let last_run = if args.reset_marker {
+        None
+    } else {
+        marker.get_last_run()?
+    };
+
+    match since_last_run {
+        SinceLastRun::Created => args.created_after = last_run,
+        SinceLastRun::Updated => args.updated_after = last_run,
+    }
+
+    args.all_pages = true;
+    args.limit = None;
+
+    let count = fetch_and_print_issues(remote, api_type, &args)?;
+
+    if !args.dry_run {
+        marker.set_last_run(started_at)?;
+    }
+
+    io::check_list_assertions(count, args.fail_if_any, args.fail_if_none)
+}
+
+/// Fetches the issues of the pages given by the arguments and prints them.
+/// Returns how many were printed.
+fn fetch_and_print_issues(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: &IssueListCommandArgs,
+) -> anyhow::Result<usize> {
+    let issues = fetch_issues(remote, api_type, args)?;
+
+    print_issues(&issues, args)?;
+
+    Ok(issues.len())
+}
+
+/// Fetches the issues of the pages given by the arguments, sorted by --sort.
+fn fetch_issues(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: &IssueListCommandArgs,
+) -> anyhow::Result<Vec<Issue>> {
+    let get_issues = forge::function!(api_type, get_issues);
+    let http_client = HttpClient::new();
+    let state = args.state.clone().unwrap_or_default();
+    let per_page = io::get_per_page(args.per_page, args.limit, DEFAULT_PER_PAGE);
+    let (created, closed) = get_date_ranges(args);
+    let labels = get_label_filter(args);
+    let mut issues = vec![];
+
+    for page in args.page.. {
+        let response = get_issues(
+            &http_client,
+            remote,
             args.api_url.as_deref(),
-            config
-                .get_string_from_global_scope("editor-command")
-                .as_deref(),
-            args.no_browser,
+            &ListIssueFilters {
+                assignee: args.assignee.as_deref(),
+                author: args.author.as_deref(),
+                closed,
+                closed_as: args.closed_as.as_ref(),
+                created,
+                include_prs: args.include_prs,
+                labels: &labels,
+                min_reactions: args.min_reactions,
+                pinned: args.pinned,
+                page,
+                per_page,
+                query: args.query.as_deref(),
+                sort: args.sort.as_ref(),
+                state: &state,
+                updated: DateRange {
+                    after: args.updated_after,
+                    before: None,
+                },
+            },
+            args.auth,
+        )
+        .with_context(|| format!("Failed fetching issues (page {page})"))?;
+
+        issues.extend(
+            response
+                .items
+                .into_iter()
+                .filter(|issue| has_min_reactions(issue, args.min_reactions)),
         );
+
+        if !io::should_fetch_next_page(
+            issues.len(),
+            response.has_next_page,
+            args.all_pages,
+            args.limit,
+        ) {
+            break;
+        }
     }
 
-    let title = match args.title {
-        Some(t) => t,
-        None => Input::new()
-            .with_prompt("Enter issue title")
-            .interact_text()?,
-    };
+    if let Some(limit) = args.limit {
+        issues.truncate(limit as usize);
+    }
 
-    create_issue_via_api(
-        &remote,
-        &api_type,
-        args.api_url.as_deref(),
-        &CreateIssueOptions {
-            title: &title,
-            body: &args.body.unwrap_or_default(),
+    sort_issues(&mut issues, args.sort.as_ref());
+
+    Ok(issues)
+}
+
+/// Gets the ranges of --created-after/before and --closed-after/before.
+fn get_date_ranges(args: &IssueListCommandArgs) -> (DateRange, DateRange) {
+    (
+        DateRange {
+            after: args.created_after,
+            before: args.created_before,
+        },
+        DateRange {
+            after: args.closed_after,
+            before: args.closed_before,
         },
-        args.no_browser,
     )
 }
 
-// =============================================================================
-// Private Helpers
-// =============================================================================
+/// Gets the filters that tell the markers of --since-last-run apart. The dates
+/// are left out since relative ones change with every run.
+fn get_marker_filters(args: &IssueListCommandArgs) -> serde_json::Value {
+    serde_json::json!({
+        "api_url": args.api_url,
+        "assignee": args.assignee,
+        "author": args.author,
+        "closed_as": args.closed_as,
+        "include_prs": args.include_prs,
+        "labels": args.labels,
+        "min_reactions": args.min_reactions,
+        "pinned": args.pinned,
+        "query": args.query,
+        "state": args.state,
+    })
+}
+
+fn get_label_filter(args: &IssueListCommandArgs) -> LabelFilter {
+    LabelFilter {
+        unlabeled: args.no_labels,
+        ..LabelFilter::parse(&args.labels)
+    }
+}
+
+fn get_truncation(args: &IssueListCommandArgs) -> Truncation {
+    Truncation {
+        fit_to_terminal: !args.no_truncate,
+        max_title_width: args.max_title_width.map(|width| width as usize),
+    }
+}
 
-fn list_issues_in_web_browser(remote: &GitRemoteData, api_type: &ApiType) -> anyhow::Result<()> {
-    let get_issues_url = forge::function!(api_type, get_url_for_issues);
+fn has_min_reactions(issue: &Issue, min_reactions: Option<u32>) -> bool {
+    min_reactions.is_none_or(|min| issue.reactions.unwrap_or_default() >= min)
+}
+
+/// Sorts the issues for `--sort`, newest or most reactions first. Issues
+/// without the timestamp or reactions come last.
+fn sort_issues(issues: &mut [Issue], sort: Option<&IssueSort>) {
+    match sort {
+        Some(IssueSort::Closed) => issues.sort_by_key(|issue| Reverse(issue.closed_at)),
+        Some(IssueSort::Reactions) => issues.sort_by_key(|issue| Reverse(issue.reactions)),
+        None => {}
+    }
+}
 
-    open::that(get_issues_url(remote))?;
+fn print_issues(issues: &[Issue], args: &IssueListCommandArgs) -> anyhow::Result<()> {
+    if args.ids_only {
+        io::print_ids(issues.iter().map(|issue| issue.id));
+    } else if !issues.is_empty() {
+        io::print_list(
+            issues,
+            &args.fields,
+            &args.format.clone().unwrap_or_default(),
+            get_truncation(args),
+        )?;
+    }
 
     Ok(())
 }
 
-fn list_issues_to_stdout(
+fn watch_issues(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: IssueListCommandArgs,
+) -> anyhow::Result<()> {
+    let (created, closed) = get_date_ranges(&args);
+    let labels = get_label_filter(&args);
+    let output_format = args.format.unwrap_or_default();
+
+    if matches!(output_format, OutputFormat::Json) {
+        anyhow::bail!("--watch doesn't support the JSON output format");
+    }
+
+    let get_issues = forge::function!(api_type, get_issues);
+    let http_client = HttpClient::new();
+    let fields = args.fields;
+    let state = args.state.unwrap_or_default();
+    let filters = ListIssueFilters {
+        assignee: args.assignee.as_deref(),
+        author: args.author.as_deref(),
+        closed,
+        closed_as: args.closed_as.as_ref(),
+        created,
+        include_prs: args.include_prs,
+        labels: &labels,
+        min_reactions: None,
+        pinned: args.pinned,
+        page: args.page,
+        per_page: io::get_per_page(args.per_page, args.limit, DEFAULT_PER_PAGE),
+        query: args.query.as_deref(),
+        sort: None,
+        state: &state,
+        updated: DateRange::default(),
+    };
+
+    watch::watch(Duration::from_secs(args.interval), || {
+        let response = get_issues(
+            &http_client,
+            remote,
+            args.api_url.as_deref(),
+            &filters,
+            args.auth,
+        )
+        .context("Failed fetching issues")?;
+
+        response
+            .items
+            .iter()
+            .map(|issue| {
+                Ok(WatchedRow {
+                    id: issue.id,
+                    line: io::format(&[issue], &fields, &output_format)?,
+                })
+            })
+            .collect()
+    })
+}
+
+fn list_issues_offline(remote: &GitRemoteData, args: IssueListCommandArgs) -> anyhow::Result<()> {
+    if args.assignee.is_some() {
+        anyhow::bail!(
+            "--assignee can't be used with --offline since snapshots don't contain assignees"
+        );
+    }
+
+    let snapshot_path = storage::get_snapshot_path(remote, ISSUE_SNAPSHOT_NAME)?;
+    let Some(snapshot) = storage::read_json::<Vec<Issue>>(&snapshot_path)? else {
+        anyhow::bail!("No snapshot found. Create one with `git forge issue snapshot`");
+    };
+    let (created, closed) = get_date_ranges(&args);
+    let labels = get_label_filter(&args);
+    let mut issues = filter_issues(
+        snapshot,
+        &ListIssueFilters {
+            assignee: None,
+            author: args.author.as_deref(),
+            closed,
+            closed_as: args.closed_as.as_ref(),
+            created,
+            include_prs: false,
+            labels: &labels,
+            min_reactions: args.min_reactions,
+            pinned: args.pinned,
+            page: args.page,
+            // A snapshot has all issues, so there is nothing to fetch and the
+            // limit doesn't need to be split into pages.
+            per_page: if args.all_pages {
+                u32::MAX
+            } else {
+                args.per_page.or(args.limit).unwrap_or(DEFAULT_PER_PAGE)
+            },
+            query: args.query.as_deref(),
+            sort: args.sort.as_ref(),
+            state: &args.state.clone().unwrap_or_default(),
+            updated: DateRange::default(),
+        },
+    );
+
+    if let Some(limit) = args.limit {
+        issues.truncate(limit as usize);
+    }
+
+    sort_issues(&mut issues, args.sort.as_ref());
+    print_issues(&issues, &args)?;
+
+    io::check_list_assertions(issues.len(), args.fail_if_any, args.fail_if_none)
+}
+
+/// Fetches the issues of all pages in all states.
+fn get_all_issues(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Vec<Issue>> {
+    let get_issues = forge::function!(api_type, get_issues);
+    let http_client = HttpClient::new();
+    let mut issues = vec![];
+
+    for page in 1.. {
+        let response = get_issues(
+            &http_client,
+            remote,
+            api_url,
+            &ListIssueFilters {
+                assignee: None,
+                author: None,
+                closed: DateRange::default(),
+                closed_as: None,
+                created: DateRange::default(),
+                include_prs: false,
+                labels: LabelFilter::NONE,
+                min_reactions: None,
+                pinned: false,
+                page,
+                per_page: SNAPSHOT_PER_PAGE,
+                query: None,
+                sort: None,
+                state: &IssueState::All,
+                updated: DateRange::default(),
+            },
+            use_auth,
+        )
+        .with_context(|| format!("Failed fetching issues (page {page})"))?;
+
+        issues.extend(response.items);
+
+        if !response.has_next_page {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Fetches the issues with their description starting at the filters' page.
+/// With `all_pages`, the following pages are fetched as well.
+fn get_issues_for_export(
+    http_client: &HttpClient,
     remote: &GitRemoteData,
     api_type: &ApiType,
     api_url: Option<&str>,
     filters: &ListIssueFilters,
-    fields: Vec<IssueField>,
-    output_format: &OutputFormat,
+    all_pages: bool,
     use_auth: bool,
+) -> anyhow::Result<Vec<ExportedIssue>> {
+    let get_issues = forge::function!(api_type, get_issues_for_export);
+    let mut issues = vec![];
+
+    for page in filters.page.. {
+        let response = get_issues(
+            http_client,
+            remote,
+            api_url,
+            &ListIssueFilters { page, ..*filters },
+            use_auth,
+        )
+        .with_context(|| format!("Failed fetching issues (page {page})"))?;
+
+        issues.extend(response.items);
+
+        if !all_pages || !response.has_next_page {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Builds the body of an imported issue. Forge APIs don't allow to set the
+/// author or creation date, so they are mentioned above the original body.
+/// The hidden marker identifies the source issue.
+fn build_imported_issue_body(issue: &ExportedIssue) -> String {
+    let mut body = format!(
+        "{IMPORT_MARKER_PREFIX}{url}{IMPORT_MARKER_SUFFIX}\n_Originally created by **{author}** on {created_at}: {url}_",
+        url = issue.url,
+        author = issue.author,
+        created_at = issue.created_at,
+    );
+
+    if !issue.body.is_empty() {
+        body.push_str("\n\n");
+        body.push_str(&issue.body);
+    }
+
+    body
+}
+
+/// Gets the URL of the source issue from the marker in an imported issue's
+/// body.
+fn get_import_source_url(body: &str) -> Option<&str> {
+    let (_, rest) = body.split_once(IMPORT_MARKER_PREFIX)?;
+    let (url, _) = rest.split_once(IMPORT_MARKER_SUFFIX)?;
+
+    Some(url)
+}
+
+fn list_issues_interactively(
+    remote: GitRemoteData,
+    api_type: ApiType,
+    args: IssueListCommandArgs,
 ) -> anyhow::Result<()> {
     let get_issues = forge::function!(api_type, get_issues);
-    let response = get_issues(&HttpClient::new(), remote, api_url, filters, use_auth)
-        .context("Failed fetching issues")?;
+    let http_client = HttpClient::new();
+    let api_url = args.api_url.clone();
+    let use_auth = args.auth;
 
-    let fields = if fields.is_empty() {
-        vec![IssueField::Title, IssueField::Id, IssueField::Url]
-    } else {
-        fields
-    };
+    eprintln!("Loading issues...");
+
+    let issue = select_issue_interactively(
+        build_initial_fetch_options(&args),
+        args.per_page,
+        args.limit,
+        move |filters| get_issues(&http_client, &remote, api_url.as_deref(), filters, use_auth),
+    )?;
+
+    let output_format = args.format.unwrap_or_default();
+    let fields = args.fields;
+
+    println!("{}", io::format(&[&issue], &fields, &output_format)?);
+
+    if args.web {
+        open::that(issue.url)?;
+    }
+
+    Ok(())
+}
+
+fn build_initial_fetch_options(args: &IssueListCommandArgs) -> FetchOptions {
+    tui::build_fetch_options! {
+        "assignee": args.assignee.clone(),
+        "author": args.author.clone(),
+        "closed-as": args.closed_as.clone(),
+        "include-prs": args.include_prs,
+        "labels": LabelFilter::to_option(&args.labels, args.no_labels),
+        "pinned": args.pinned,
+        "query": args.query.clone(),
+        "state": args.state.clone(),
+    }
+}
+
+/// The fetch options of the TUI. They must match [`build_initial_fetch_options`].
+fn get_fetch_option_specs() -> Vec<FetchOptionSpec> {
+    vec![
+        FetchOptionSpec::new("assignee"),
+        FetchOptionSpec::new("author"),
+        FetchOptionSpec::with_enum_values::<IssueStateReason>("closed-as"),
+        FetchOptionSpec::flag("include-prs"),
+        FetchOptionSpec::new("labels"),
+        FetchOptionSpec::flag("pinned"),
+        FetchOptionSpec::with_enum_values::<IssueState>("state"),
+    ]
+}
+
+/// Shows the TUI to select an issue. `get_issues` fetches a page of issues
+/// matching the filters entered in the TUI. Without `per_page`, the TUI picks
+/// a page size that fills the terminal. The TUI stops loading more pages once
+/// it has loaded `limit` issues.
+fn select_issue_interactively<T, F>(
+    initial_options: FetchOptions,
+    per_page: Option<u32>,
+    limit: Option<u32>,
+    get_issues: F,
+) -> anyhow::Result<T>
+where
+    T: ListableItem,
+    F: Fn(&ListIssueFilters) -> anyhow::Result<PaginatedResponse<T>> + Send + Sync + 'static,
+{
+    tui::select_item_with(
+        initial_options,
+        get_fetch_option_specs(),
+        move |page, options, result| {
+            let assignee = options.parse_str("assignee");
+            let author = options.parse_str("author");
+            let closed_as = options.parse_enum("closed-as");
+            let include_prs = options.parse("include-prs").unwrap_or_default();
+            let labels =
+                LabelFilter::parse_option(&options.parse_list("labels").unwrap_or_default());
+            let pinned = options.parse("pinned").unwrap_or_default();
+            let query = options.parse_str("query");
+            let issue_state = get_implied_state(options.parse_enum("state"), closed_as.as_ref());
+            let per_page = per_page.unwrap_or(result.page_size());
+
+            let response = get_issues(&ListIssueFilters {
+                author,
+                closed: DateRange::default(),
+                closed_as: closed_as.as_ref(),
+                created: DateRange::default(),
+                include_prs,
+                labels: &labels,
+                min_reactions: None,
+                pinned,
+                page,
+                per_page,
+                query,
+                sort: None,
+                state: &issue_state,
+                updated: DateRange::default(),
+                assignee,
+            })?;
+
+            Ok(result.with_items(response.items).with_more_items(
+                response.has_next_page && io::is_below_limit(page, per_page, limit),
+            ))
+        },
+    )
+}
+
+/// Lists the issues of every git remote whose forge is known. Remotes that
+/// fail are skipped with a warning instead of failing the whole command.
+fn list_issues_of_all_remotes(
+    config: &Config,
+    mut args: IssueListCommandArgs,
+) -> anyhow::Result<()> {
+    config::merge_config_into_args!(
+        config,
+        args,
+        None,
+        "issue/list",
+        [
+            auth,
+            format,
+            limit,
+            max_title_width,
+            per_page,
+            state,
+            interactive,
+            no_truncate
+        ]
+    );
+
+    args.state = Some(get_implied_state(args.state, args.closed_as.as_ref()));
+
+    let mut fields = io::resolve_fields(
+        args.fields.clone(),
+        config.get_string("issue/list/fields", None),
+        vec![IssueField::Title, IssueField::Id, IssueField::Url],
+    )?;
+
+    fields.insert(0, IssueField::Remote);
+
+    let targets = get_remote_targets(config)?;
+    let output_format = args.format.clone().unwrap_or_default();
+    let use_auth = args.auth;
+    let truncation = get_truncation(&args);
+    let (created, closed) = get_date_ranges(&args);
+    let labels = get_label_filter(&args);
+
+    if args.interactive {
+        eprintln!("Loading issues...");
+
+        let issue = select_issue_interactively(
+            build_initial_fetch_options(&args),
+            args.per_page,
+            args.limit,
+            move |filters| get_issues_of_remotes(&targets, filters, use_auth),
+        )?;
+
+        println!("{}", io::format(&[&issue], &fields, &output_format)?);
+
+        return Ok(());
+    }
+
+    let mut response = get_issues_of_remotes(
+        &targets,
+        &ListIssueFilters {
+            assignee: args.assignee.as_deref(),
+            author: args.author.as_deref(),
+            closed,
+            closed_as: args.closed_as.as_ref(),
+            created,
+            include_prs: args.include_prs,
+            labels: &labels,
+            min_reactions: None,
+            pinned: args.pinned,
+            page: args.page,
+            per_page: io::get_per_page(args.per_page, args.limit, DEFAULT_PER_PAGE),
+            query: args.query.as_deref(),
+            sort: None,
+            state: &args.state.unwrap_or_default(),
+            updated: DateRange::default(),
+        },
+        use_auth,
+    )?;
+
+    if let Some(limit) = args.limit {
+        response.items.truncate(limit as usize);
+    }
 
     if !response.items.is_empty() {
-        println!("{}", io::format(&response.items, &fields, output_format)?);
+        io::print_list(&response.items, &fields, &output_format, truncation)?;
     }
 
-    Ok(())
+    io::check_list_assertions(response.items.len(), args.fail_if_any, args.fail_if_none)
 }
 
-fn list_issues_interactively(
+/// A git remote whose issues are listed with `--all-remotes`.
+struct RemoteTarget {
+    name: String,
     remote: GitRemoteData,
     api_type: ApiType,
-    args: IssueListCommandArgs,
-) -> anyhow::Result<()> {
-    let fetch_options = tui::build_fetch_options! {
-        "assignee": args.assignee,
-        "author": args.author,
-        "labels": args.labels,
-        "query": args.query,
-        "state": args.state,
-    };
-
-    eprintln!("Loading issues...");
-
-    let issue = select_issue_interactively(
-        remote,
-        api_type,
-        args.api_url,
-        fetch_options,
-        args.per_page.unwrap_or(DEFAULT_PER_PAGE),
-        args.auth,
-    )?;
-
-    let output_format = args.format.unwrap_or_default();
-    let fields = if args.fields.is_empty() {
-        vec![IssueField::Title, IssueField::Id, IssueField::Url]
-    } else {
-        args.fields
-    };
-
-    println!("{}", io::format(&[&issue], &fields, &output_format)?);
+    api_url: Option<ApiUrl>,
+}
 
-    if args.web {
-        open::that(issue.url)?;
+/// Gets the git remotes whose forge is either configured or can be guessed
+/// from the host. Other remotes are skipped.
+fn get_remote_targets(config: &Config) -> anyhow::Result<Vec<RemoteTarget>> {
+    let targets: Vec<RemoteTarget> = git::list_remotes()?
+        .into_iter()
+        .filter_map(|name| {
+            let remote = config
+                .get_remote_data(&name)
+                .inspect_err(|e| log::info!("Skipping remote '{name}': {e:#}"))
+                .ok()?;
+            let api_type = config
+                .get_enum("issue/list/api", Some(&remote))
+                .or_else(|| forge::detect_api_type(None, config, &remote).ok());
+            let Some(api_type) = api_type else {
+                log::info!(
+                    "Skipping remote '{name}': unknown forge on host '{}'",
+                    remote.host
+                );
+
+                return None;
+            };
+            let api_url = config
+                .get_string("issue/list/api-url", Some(&remote))
+                .and_then(|url| forge::parse_api_url(&url).ok());
+
+            Some(RemoteTarget {
+                name,
+                remote,
+                api_type,
+                api_url,
+            })
+        })
+        .collect();
+
+    if targets.is_empty() {
+        anyhow::bail!("None of the git remotes belongs to a known forge");
     }
 
-    Ok(())
+    Ok(targets)
 }
 
-fn select_issue_interactively(
-    remote: GitRemoteData,
-    api_type: ApiType,
-    api_url: Option<String>,
-    initial_options: FetchOptions,
-    per_page: u32,
+/// Fetches a page of issues from each remote with a few threads at a time.
+/// The response has a next page if any remote has one.
+///
+/// # Errors
+///
+/// Returns an error only if fetching failed for every remote. Otherwise, the
+/// failures are logged as warnings.
+fn get_issues_of_remotes(
+    targets: &[RemoteTarget],
+    filters: &ListIssueFilters,
     use_auth: bool,
-) -> anyhow::Result<Issue> {
-    let get_issues = forge::function!(api_type, get_issues);
-    let http_client = HttpClient::new();
-
-    tui::select_item_with(initial_options, move |page, options, result| {
-        let assignee = options.parse_str("assignee");
-        let author = options.parse_str("author");
-        let labels = options.parse_list("labels").unwrap_or_default();
-        let issue_state = options.parse_enum("state").unwrap_or_default();
-        let query = options.parse_str("query");
+) -> anyhow::Result<PaginatedResponse<RemoteIssue>> {
+    let results: Vec<anyhow::Result<PaginatedResponse<Issue>>> = targets
+        .chunks(MAX_CONCURRENT_FETCHES)
+        .flat_map(|chunk| {
+            thread::scope(|scope| {
+                let handles: Vec<_> = chunk
+                    .iter()
+                    .map(|target| {
+                        scope.spawn(move || {
+                            let get_issues = forge::function!(target.api_type, get_issues);
+
+                            get_issues(
+                                &HttpClient::new(),
+                                &target.remote,
+                                target.api_url.as_deref(),
+                                filters,
+                                use_auth,
+                            )
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|_| Err(anyhow::anyhow!("The fetch thread panicked")))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut items = vec![];
+    let mut has_next_page = false;
+    let mut failures = 0;
+
+    for (target, result) in targets.iter().zip(results) {
+        match result {
+            Ok(response) => {
+                has_next_page |= response.has_next_page;
+                items.extend(response.items.into_iter().map(|issue| RemoteIssue {
+                    remote: target.name.clone(),
+                    issue,
+                }));
+            }
+            Err(e) => {
+                log::warn!("Remote '{}': Failed fetching issues: {e:#}", target.name);
+                failures += 1;
+            }
+        }
+    }
 
-        let response = get_issues(
-            &http_client,
-            &remote,
-            api_url.as_deref(),
-            &ListIssueFilters {
-                author,
-                labels: &labels,
-                page,
-                per_page,
-                query,
-                state: &issue_state,
-                assignee,
-            },
-            use_auth,
-        )?;
+    if failures == targets.len() {
+        anyhow::bail!("Failed fetching issues from all remotes");
+    }
 
-        Ok(result
-            .with_items(response.items)
-            .with_more_items(response.has_next_page))
-    })
+    Ok(PaginatedResponse::new(items, has_next_page))
 }
 
 fn create_issue_via_browser(remote: &GitRemoteData, api_type: &ApiType) -> anyhow::Result<()> {
@@ -478,8 +3034,10 @@ fn create_issue_with_text_editor(
     api_type: &ApiType,
     api_url: Option<&str>,
     editor_command: Option<&str>,
-    no_browser: bool,
-) -> anyhow::Result<()> {
+    mut labels: Vec<String>,
+    prompt_labels: bool,
+    check_duplicates: bool,
+) -> anyhow::Result<Option<Issue>> {
     let message = match editor_command {
         Some(cmd) => io::prompt_with_custom_text_editor(cmd),
         None => io::prompt_with_default_text_editor(),
@@ -489,6 +3047,15 @@ fn create_issue_with_text_editor(
         anyhow::bail!("Issue title cannot be empty. Please provide a title on the first line.");
     }
 
+    if labels.is_empty() && prompt_labels {
+        let get_labels = forge::function!(api_type, get_labels);
+
+        labels = io::prompt_labels(
+            || get_labels(&HttpClient::new(), remote, api_url),
+            "--labels or --no-prompt",
+        )?;
+    }
+
     create_issue_via_api(
         remote,
         api_type,
@@ -496,29 +3063,791 @@ fn create_issue_with_text_editor(
         &CreateIssueOptions {
             title: &message.title,
             body: &message.body,
+            labels: &labels,
         },
-        no_browser,
+        check_duplicates,
     )
 }
 
+/// Creates the issue. With `check_duplicates`, similar open issues are shown
+/// first and the user decides whether to create it anyway. Returns `None` if
+/// the user decided against it.
 fn create_issue_via_api(
     remote: &GitRemoteData,
     api_type: &ApiType,
     api_url: Option<&str>,
     create_options: &CreateIssueOptions,
-    no_browser: bool,
-) -> anyhow::Result<()> {
+    check_duplicates: bool,
+) -> anyhow::Result<Option<Issue>> {
     let http_client = HttpClient::new();
+
+    if check_duplicates
+        && !confirm_no_duplicates(
+            &http_client,
+            remote,
+            api_type,
+            api_url,
+            create_options.title,
+        )?
+    {
+        return Ok(None);
+    }
+
     let create_issue = forge::function!(api_type, create_issue);
-    let issue = create_issue(&http_client, remote, api_url, create_options)?;
 
-    if no_browser {
-        println!("{}", issue.url);
-    } else {
-        eprintln!("Opening issue in browser: {}", issue.url);
+    create_issue(&http_client, remote, api_url, create_options).map(Some)
+}
+
+/// Shows the open issues with titles similar to `title` and asks whether to
+/// create the issue anyway. The user can open a candidate instead. The check
+/// is a convenience, so failing to fetch the candidates doesn't stop the
+/// creation.
+fn confirm_no_duplicates(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    title: &str,
+) -> anyhow::Result<bool> {
+    let issues = match fetch_duplicate_candidates(http_client, remote, api_type, api_url, title) {
+        Ok(issues) => issues,
+        Err(e) => {
+            log::warn!("Skipping the duplicate check: {e:#}");
+
+            return Ok(true);
+        }
+    };
+    let candidates = find_duplicate_candidates(title, &issues);
 
-        open::that(&issue.url)?;
+    if candidates.is_empty() {
+        return Ok(true);
     }
 
-    Ok(())
+    eprintln!("{}", format_duplicate_candidates(&candidates));
+
+    let mut choices = vec![String::from("yes"), String::from("no")];
+
+    choices.extend(candidates.iter().map(|issue| format!("open #{}", issue.id)));
+
+    match io::prompt_select(
+        "Create anyway? (Enter: no)",
+        &choices,
+        "--skip-duplicate-check",
+    )? {
+        Some(0) => Ok(true),
+        Some(index) if index >= 2 => {
+            let issue = candidates[index - 2];
+
+            eprintln!("Opening issue in browser: {}", issue.url);
+
+            open::that(&issue.url)?;
+
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Searches the open issues with the words of `title`. Since the forges may
+/// require all words to match, the first page of open issues is searched
+/// client-side if the search finds nothing.
+fn fetch_duplicate_candidates(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    title: &str,
+) -> anyhow::Result<Vec<Issue>> {
+    let Some(query) = build_duplicate_query(title) else {
+        return Ok(vec![]);
+    };
+    let get_issues = forge::function!(api_type, get_issues);
+    let fetch = |query: Option<&str>| {
+        get_issues(
+            http_client,
+            remote,
+            api_url,
+            &ListIssueFilters {
+                author: None,
+                closed: DateRange::default(),
+                closed_as: None,
+                created: DateRange::default(),
+                include_prs: false,
+                labels: LabelFilter::NONE,
+                min_reactions: None,
+                pinned: false,
+                page: 1,
+                per_page: DUPLICATE_SEARCH_PAGE_SIZE,
+                query,
+                sort: None,
+                state: &IssueState::Open,
+                updated: DateRange::default(),
+                assignee: None,
+            },
+            true,
+        )
+    };
+    let issues = fetch(Some(&query))?.items;
+
+    if !issues.is_empty() {
+        return Ok(issues);
+    }
+
+    Ok(fetch(None)?.items)
+}
+
+/// Gets the lowercase words of a title that are long enough to tell issues
+/// apart, i.e. without words like "a" or "to".
+fn get_title_words(title: &str) -> Vec<String> {
+    title
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Builds the search query for issues similar to `title` from its first
+/// words. Returns `None` if the title has no words to search for.
+fn build_duplicate_query(title: &str) -> Option<String> {
+    let words = get_title_words(title);
+
+    if words.is_empty() {
+        return None;
+    }
+
+    Some(words[..words.len().min(DUPLICATE_QUERY_WORDS)].join(" "))
+}
+
+/// Gets the issues whose titles share at least half of the query words of
+/// `title`, most similar first.
+fn find_duplicate_candidates<'a>(title: &str, issues: &'a [Issue]) -> Vec<&'a Issue> {
+    let words = get_title_words(title);
+    let words = &words[..words.len().min(DUPLICATE_QUERY_WORDS)];
+
+    if words.is_empty() {
+        return vec![];
+    }
+
+    let mut candidates: Vec<(usize, &Issue)> = issues
+        .iter()
+        .map(|issue| {
+            let issue_words = get_title_words(&issue.title);
+            let shared = words
+                .iter()
+                .filter(|word| issue_words.contains(word))
+                .count();
+
+            (shared, issue)
+        })
+        .filter(|(shared, _)| shared * 2 >= words.len())
+        .collect();
+
+    candidates.sort_by_key(|(shared, _)| Reverse(*shared));
+
+    candidates
+        .into_iter()
+        .take(MAX_DUPLICATE_CANDIDATES)
+        .map(|(_, issue)| issue)
+        .collect()
+}
+
+fn format_duplicate_candidates(candidates: &[&Issue]) -> String {
+    let mut text = String::from("Found similar open issues:");
+
+    for issue in candidates {
+        text.push_str(&format!(
+            "\n  #{} {}\n    {}",
+            issue.id, issue.title, issue.url
+        ));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(id: u32, title: &str, state: IssueState, labels: &[&str]) -> Issue {
+        Issue {
+            id,
+            title: title.to_string(),
+            state,
+            state_reason: None,
+            author: if id.is_multiple_of(2) { "alice" } else { "bob" }.to_string(),
+            url: format!("https://example.com/issues/{id}"),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            pinned: None,
+            created_at: Timestamp::parse(&format!("2024-0{id}-01T00:00:00Z")),
+            closed_at: None,
+            reactions: None,
+            kind: None,
+        }
+    }
+
+    fn filters<'a>(state: &'a IssueState) -> ListIssueFilters<'a> {
+        ListIssueFilters {
+            assignee: None,
+            author: None,
+            closed: DateRange::default(),
+            closed_as: None,
+            created: DateRange::default(),
+            include_prs: false,
+            labels: LabelFilter::NONE,
+            min_reactions: None,
+            page: 1,
+            pinned: false,
+            per_page: 30,
+            query: None,
+            sort: None,
+            state,
+            updated: DateRange::default(),
+        }
+    }
+
+    fn ids(issues: &[Issue]) -> Vec<u32> {
+        issues.iter().map(|i| i.id).collect()
+    }
+
+    fn sample_issues() -> Vec<Issue> {
+        vec![
+            issue(1, "Crash on startup", IssueState::Open, &["bug"]),
+            issue(2, "Add dark mode", IssueState::Open, &["enhancement", "ui"]),
+            issue(3, "Fix typo", IssueState::Closed, &["docs"]),
+            issue(4, "Crash when saving", IssueState::Closed, &["bug", "ui"]),
+        ]
+    }
+
+    #[test]
+    fn test_sort_issues_by_closed_at() {
+        let mut issues = vec![
+            issue(1, "open", IssueState::Open, &[]),
+            Issue {
+                closed_at: Timestamp::parse("2024-05-01T10:00:00Z"),
+                ..issue(2, "closed first", IssueState::Closed, &[])
+            },
+            Issue {
+                closed_at: Timestamp::parse("2024-05-02T10:00:00.000Z"),
+                ..issue(3, "closed last", IssueState::Closed, &[])
+            },
+        ];
+
+        sort_issues(&mut issues, Some(&IssueSort::Closed));
+
+        assert_eq!(ids(&issues), [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_sort_and_filter_issues_by_reactions() {
+        let mut issues = vec![
+            Issue {
+                reactions: Some(3),
+                ..issue(1, "some", IssueState::Open, &[])
+            },
+            // Gitea/Forgejo doesn't return reactions
+            issue(2, "unknown", IssueState::Open, &[]),
+            Issue {
+                reactions: Some(12),
+                ..issue(3, "popular", IssueState::Open, &[])
+            },
+            Issue {
+                reactions: Some(0),
+                ..issue(4, "none", IssueState::Open, &[])
+            },
+        ];
+
+        sort_issues(&mut issues, Some(&IssueSort::Reactions));
+        assert_eq!(ids(&issues), [3, 1, 4, 2]);
+
+        let state = IssueState::All;
+        let filtered = filter_issues(
+            issues,
+            &ListIssueFilters {
+                min_reactions: Some(3),
+                ..filters(&state)
+            },
+        );
+        assert_eq!(ids(&filtered), [3, 1]);
+        assert_eq!(filtered[0].get_display_text(), "3: popular (+12)");
+    }
+
+    #[test]
+    fn test_filter_issues_by_state() {
+        assert_eq!(
+            ids(&filter_issues(sample_issues(), &filters(&IssueState::Open))),
+            [1, 2]
+        );
+        assert_eq!(
+            ids(&filter_issues(sample_issues(), &filters(&IssueState::All))),
+            [1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_filter_issues_by_labels_author_and_query() {
+        let labels = LabelFilter::parse(&["bug".to_string(), "ui".to_string()]);
+        let excluded_labels = LabelFilter::parse(&["bug".to_string(), "-ui".to_string()]);
+        let all = IssueState::All;
+
+        assert_eq!(
+            ids(&filter_issues(
+                sample_issues(),
+                &ListIssueFilters {
+                    include_prs: false,
+                    labels: &labels,
+                    ..filters(&all)
+                }
+            )),
+            [4]
+        );
+        assert_eq!(
+            ids(&filter_issues(
+                sample_issues(),
+                &ListIssueFilters {
+                    labels: &excluded_labels,
+                    ..filters(&all)
+                }
+            )),
+            [1]
+        );
+        assert_eq!(
+            ids(&filter_issues(
+                sample_issues(),
+                &ListIssueFilters {
+                    author: Some("bob"),
+                    query: Some("CRASH"),
+                    ..filters(&all)
+                }
+            )),
+            [1]
+        );
+    }
+
+    #[test]
+    fn test_filter_issues_by_closed_as() {
+        let mut issues = sample_issues();
+        issues[2].state_reason = Some(IssueStateReason::NotPlanned);
+        issues[3].state_reason = Some(IssueStateReason::Completed);
+        let closed = IssueState::Closed;
+
+        assert_eq!(
+            ids(&filter_issues(
+                issues,
+                &ListIssueFilters {
+                    closed_as: Some(&IssueStateReason::NotPlanned),
+                    ..filters(&closed)
+                }
+            )),
+            [3]
+        );
+    }
+
+    #[test]
+    fn test_filter_issues_by_dates() {
+        let mut issues = sample_issues();
+        issues[2].closed_at = Timestamp::parse("2024-05-01T00:00:00Z");
+        issues[3].closed_at = Timestamp::parse("2024-06-01T00:00:00Z");
+        let all = IssueState::All;
+        let parse_date = |value| date::parse_date(value).unwrap();
+
+        assert_eq!(
+            ids(&filter_issues(
+                issues.clone(),
+                &ListIssueFilters {
+                    created: DateRange {
+                        after: Some(parse_date("2024-02-01")),
+                        before: Some(parse_date("2024-04-01")),
+                    },
+                    ..filters(&all)
+                }
+            )),
+            [2, 3]
+        );
+        assert_eq!(
+            ids(&filter_issues(
+                issues,
+                &ListIssueFilters {
+                    closed: DateRange {
+                        after: None,
+                        before: Some(parse_date("2024-06-01")),
+                    },
+                    ..filters(&all)
+                }
+            )),
+            [3]
+        );
+    }
+
+    #[test]
+    fn test_filter_issues_by_pinned() {
+        let mut issues = sample_issues();
+        issues[0].pinned = Some(true);
+        issues[1].pinned = Some(false);
+        let all = IssueState::All;
+
+        assert_eq!(
+            ids(&filter_issues(
+                issues,
+                &ListIssueFilters {
+                    pinned: true,
+                    ..filters(&all)
+                }
+            )),
+            [1]
+        );
+    }
+
+    #[test]
+    fn test_mark_issue_kinds() {
+        let mut issues = sample_issues();
+        issues[0].kind = Some(IssueKind::Pr);
+        let all = IssueState::All;
+
+        mark_issue_kinds(&mut issues, &filters(&all));
+
+        assert_eq!(issues[1].kind, None);
+
+        mark_issue_kinds(
+            &mut issues,
+            &ListIssueFilters {
+                include_prs: true,
+                ..filters(&all)
+            },
+        );
+
+        assert_eq!(issues[0].kind, Some(IssueKind::Pr));
+        assert!(
+            issues[1..]
+                .iter()
+                .all(|issue| issue.kind == Some(IssueKind::Issue))
+        );
+
+        let json = serde_json::to_string(&issues[0]).unwrap();
+
+        assert!(json.contains(r#""type":"pr""#));
+    }
+
+    #[test]
+    fn test_get_implied_state() {
+        assert_eq!(get_implied_state(None, None), IssueState::Open);
+        assert_eq!(
+            get_implied_state(Some(IssueState::All), None),
+            IssueState::All
+        );
+        assert_eq!(
+            get_implied_state(None, Some(&IssueStateReason::NotPlanned)),
+            IssueState::Closed
+        );
+        // A configured default state must not hide the closed issues
+        assert_eq!(
+            get_implied_state(Some(IssueState::Open), Some(&IssueStateReason::Completed)),
+            IssueState::Closed
+        );
+    }
+
+    #[test]
+    fn test_issue_state_reason_serialization() {
+        let mut issue = issue(1, "Crash on startup", IssueState::Closed, &[]);
+
+        assert!(
+            !serde_json::to_string(&issue)
+                .unwrap()
+                .contains("state_reason")
+        );
+
+        issue.state_reason = Some(IssueStateReason::NotPlanned);
+        let json = serde_json::to_string(&issue).unwrap();
+
+        assert!(json.contains(r#""state_reason":"not_planned""#));
+        assert_eq!(serde_json::from_str::<Issue>(&json).unwrap(), issue);
+    }
+
+    #[test]
+    fn test_filter_issues_paginates() {
+        let all = IssueState::All;
+
+        assert_eq!(
+            ids(&filter_issues(
+                sample_issues(),
+                &ListIssueFilters {
+                    page: 2,
+                    per_page: 3,
+                    ..filters(&all)
+                }
+            )),
+            [4]
+        );
+    }
+
+    #[test]
+    fn test_diff_issues() {
+        let old = sample_issues();
+        let new = vec![
+            issue(1, "Crash on startup", IssueState::Closed, &["bug"]),
+            issue(2, "Add a dark mode", IssueState::Open, &["enhancement"]),
+            issue(3, "Fix typo", IssueState::Open, &["docs"]),
+            issue(4, "Crash when saving", IssueState::Closed, &["bug", "ui"]),
+            issue(5, "New issue", IssueState::Open, &[]),
+        ];
+
+        assert_eq!(
+            diff_issues(&old, &new),
+            [
+                IssueChange::Closed(&new[0]),
+                IssueChange::Retitled {
+                    old: &old[1],
+                    new: &new[1]
+                },
+                IssueChange::Reopened(&new[2]),
+                IssueChange::Created(&new[4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_remote_issues() {
+        let issues = vec![
+            RemoteIssue {
+                remote: "origin".to_string(),
+                issue: issue(1, "Crash on startup", IssueState::Open, &["bug"]),
+            },
+            RemoteIssue {
+                remote: "github".to_string(),
+                issue: issue(2, "Add dark mode", IssueState::Open, &[]),
+            },
+        ];
+        let fields = [IssueField::Remote, IssueField::Id, IssueField::Title];
+
+        assert_eq!(
+            io::format(&issues, &fields, &OutputFormat::Tsv).unwrap(),
+            "origin\t1\tCrash on startup\ngithub\t2\tAdd dark mode"
+        );
+        assert_eq!(issues[1].get_display_text(), "[github] 2: Add dark mode");
+    }
+
+    #[test]
+    fn test_all_fields_exclude_remote() {
+        let fields = io::resolve_fields(vec![IssueField::All], None, vec![]).unwrap();
+
+        assert!(!fields.contains(&IssueField::Remote));
+    }
+
+    #[test]
+    fn test_imported_issue_body_contains_source_url() {
+        let issue = ExportedIssue {
+            id: 3,
+            title: "Fix typo".to_string(),
+            body: "The README has a typo".to_string(),
+            state: IssueState::Closed,
+            author: "alice".to_string(),
+            created_at: "2024-01-08T11:30:00Z".to_string(),
+            url: "https://gitlab.com/u/r/-/issues/3".to_string(),
+            labels: vec![],
+            comments: vec![],
+        };
+        let body = build_imported_issue_body(&issue);
+
+        assert!(body.ends_with("\n\nThe README has a typo"));
+        assert!(body.contains("**alice** on 2024-01-08T11:30:00Z"));
+        assert_eq!(
+            get_import_source_url(&body),
+            Some("https://gitlab.com/u/r/-/issues/3")
+        );
+    }
+
+    #[test]
+    fn test_get_import_source_url_without_marker() {
+        assert_eq!(get_import_source_url("A regular issue"), None);
+        assert_eq!(get_import_source_url("<!-- git-forge-import: x"), None);
+    }
+
+    #[test]
+    fn test_render_issue() {
+        let mut issue = ExportedIssue {
+            id: 2,
+            title: "Fix crash on startup".to_string(),
+            body: "It crashes.\n".to_string(),
+            state: IssueState::Open,
+            author: "bob".to_string(),
+            created_at: "2024-01-10T09:00:00Z".to_string(),
+            url: "https://github.com/u/r/issues/2".to_string(),
+            labels: vec!["bug".to_string(), "critical".to_string()],
+            comments: vec![],
+        };
+
+        assert_eq!(
+            render_issue(&issue),
+            "# Fix crash on startup (#2)\n\n\
+             State: open\n\
+             Author: bob (2024-01-10T09:00:00Z)\n\
+             Labels: bug, critical\n\
+             URL: https://github.com/u/r/issues/2\n\n\
+             It crashes.\n"
+        );
+
+        issue.body = String::new();
+        issue.labels = vec![];
+
+        let rendered = render_issue(&issue);
+
+        assert!(!rendered.contains("Labels:"));
+        assert!(rendered.ends_with("\n\nNo description provided.\n"));
+    }
+
+    #[test]
+    fn test_format_issue_assignees() {
+        let mut assignees = IssueAssignees {
+            id: 7,
+            assignees: vec![String::from("alice"), String::from("bob")],
+        };
+
+        assert_eq!(
+            format_issue_assignees(&assignees),
+            "Assignees of issue #7: alice, bob"
+        );
+
+        assignees.assignees.clear();
+
+        assert_eq!(
+            format_issue_assignees(&assignees),
+            "Issue #7 has no assignees"
+        );
+    }
+
+    #[test]
+    fn test_normalize_usernames() {
+        let users = ["alice", " @bob", "", "alice", "@"]
+            .map(String::from)
+            .to_vec();
+
+        assert_eq!(normalize_usernames(users), ["alice", "bob"]);
+    }
+
+    #[test]
+    fn test_split_options() {
+        assert_eq!(
+            split_options(" --labels bug  --query 'crash on start' --author \"\"").unwrap(),
+            [
+                "--labels",
+                "bug",
+                "--query",
+                "crash on start",
+                "--author",
+                ""
+            ]
+        );
+        assert!(split_options("--query \"crash").is_err());
+    }
+
+    #[test]
+    fn test_where_options_are_list_options() {
+        let args = IssueFilterArgs::try_parse_from(
+            split_options("--labels bug,-triaged --state open -q crash").unwrap(),
+        )
+        .unwrap()
+        .list;
+
+        assert_eq!(args.labels, ["bug", "-triaged"]);
+        assert_eq!(args.state, Some(IssueState::Open));
+        assert_eq!(args.query.as_deref(), Some("crash"));
+        assert!(IssueFilterArgs::try_parse_from(["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_since_last_run_defaults_to_created() {
+        let parse = |args: &[&str]| {
+            IssueFilterArgs::try_parse_from(args)
+                .unwrap()
+                .list
+                .since_last_run
+        };
+
+        assert_eq!(parse(&[]), None);
+        assert_eq!(parse(&["--since-last-run"]), Some(SinceLastRun::Created));
+        assert_eq!(
+            parse(&["--since-last-run=updated"]),
+            Some(SinceLastRun::Updated)
+        );
+        // The value must be attached, so it doesn't swallow other arguments
+        assert!(IssueFilterArgs::try_parse_from(["--since-last-run", "updated"]).is_err());
+    }
+
+    #[test]
+    fn test_split_issue_number() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            split_issue_number(&args(&["#42", "bug", "ui"])).unwrap(),
+            (42, args(&["bug", "ui"]))
+        );
+        assert_eq!(split_issue_number(&args(&["7"])).unwrap(), (7, vec![]));
+        assert!(
+            split_issue_number(&args(&["bug", "ui"]))
+                .unwrap_err()
+                .to_string()
+                .starts_with("'bug' isn't an issue number.")
+        );
+    }
+
+    #[test]
+    fn test_parse_issue_numbers() {
+        assert_eq!(parse_issue_numbers("3\n#1 3\n\n7\n").unwrap(), [3, 1, 7]);
+        assert!(parse_issue_numbers("3 abc").is_err());
+    }
+
+    #[test]
+    fn test_build_duplicate_query() {
+        assert_eq!(
+            build_duplicate_query(
+                "Crash: the app doesn't start on Windows 11 after the update to v2"
+            ),
+            Some(String::from(
+                "crash the app doesn't start windows after the"
+            ))
+        );
+        assert_eq!(build_duplicate_query("Do it!"), None);
+    }
+
+    #[test]
+    fn test_find_duplicate_candidates() {
+        let issues = [
+            issue(1, "Login page is slow", IssueState::Open, &[]),
+            issue(2, "Crash on startup", IssueState::Open, &[]),
+            issue(
+                3,
+                "App crash on startup with Wayland",
+                IssueState::Open,
+                &[],
+            ),
+            issue(4, "Startup takes long", IssueState::Open, &[]),
+        ];
+        let candidates = find_duplicate_candidates("App crash on startup", &issues);
+        let ids: Vec<u32> = candidates.iter().map(|issue| issue.id).collect();
+
+        assert_eq!(ids, [3, 2]);
+        assert!(find_duplicate_candidates("Do it!", &issues).is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_candidates_keeps_the_most_similar() {
+        let issues: Vec<Issue> = (1..=8)
+            .map(|id| issue(id, "Crash on startup", IssueState::Open, &[]))
+            .collect();
+
+        assert_eq!(
+            find_duplicate_candidates("Crash on startup", &issues).len(),
+            MAX_DUPLICATE_CANDIDATES
+        );
+    }
+
+    #[test]
+    fn test_format_duplicate_candidates() {
+        let crash = issue(3, "App crash on startup", IssueState::Open, &[]);
+
+        assert_eq!(
+            format_duplicate_candidates(&[&crash]),
+            "Found similar open issues:\n  #3 App crash on startup\n    https://example.com/issues/3"
+        );
+    }
 }
@@ -1,18 +1,25 @@
 //! The `pr` subcommand.
 
+use std::{cmp::Reverse, collections::HashMap, time::Duration};
+
 use anyhow::Context;
 use clap::{Args, Subcommand, ValueEnum};
-use dialoguer::Input;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     cli::{
         config::{self, Config},
-        forge::{self, ApiType, HttpClient, gitea, github, gitlab},
+        forge::{
+            self, ApiType, ApiUrl, Capability, HttpClient, LabelFilter, MissingLabels, forgejo,
+            gitea, github, gitlab,
+        },
     },
+    date::Timestamp,
     git::{self, GitRemoteData},
-    io::{self, OutputFormat},
-    tui::{self, FetchOptions, ListableItem},
+    io::{self, CellColor, CreatedItemFormat, FieldStyle, OutputFormat, ResultFormat, Truncation},
+    tui::{self, FetchOptionSpec, FetchOptions, ListableItem, SortKey, SortValue},
+    watch::{self, WatchedRow},
 };
 
 // =============================================================================
@@ -21,6 +28,25 @@ use crate::{
 
 const DEFAULT_PER_PAGE: u32 = 30;
 const DEFAULT_REMOTE: &str = "origin";
+/// Number of concurrent requests for lookups that need one request per PR.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+const FIELD_STYLES: &[FieldStyle] = &[
+    FieldStyle {
+        field: "review_status",
+        symbol: |_| None,
+        color: PrReviewStatus::color,
+    },
+    FieldStyle {
+        field: "mergeable",
+        symbol: PrMergeable::symbol,
+        color: |_| None,
+    },
+    FieldStyle {
+        field: "ci",
+        symbol: PrCiStatus::symbol,
+        color: |_| None,
+    },
+];
 
 /// Command-line arguments for the `pr` subcommand.
 #[derive(Args)]
@@ -44,22 +70,34 @@ pub enum PrCommand {
     /// List pull requests.
     #[command(alias = "ls")]
     List(PrListCommandArgs),
+
+    /// Merge a pull request or let the forge merge it once its checks pass.
+    Merge(PrMergeCommandArgs),
+
+    /// Add or remove requested reviewers of a pull request and print the
+    /// resulting reviewers.
+    Reviewer(PrReviewerCommandArgs),
+
+    /// Print the description of a pull request and optionally its discussion
+    /// as a timeline.
+    View(PrViewCommandArgs),
 }
 
 /// Command-line arguments for checking out a pull request.
 #[derive(Args)]
 pub struct PrCheckoutCommandArgs {
     /// Specify the forge which affects the API schema etc
-    #[arg(long, value_name = "TYPE")]
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
     api: Option<ApiType>,
 
     /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
     /// instead of relying on the auto-detection
-    #[arg(long)]
-    api_url: Option<String>,
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
     /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
-    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN) for interactive selection
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN) for interactive selection and --branch
     #[arg(long)]
     auth: bool,
 
@@ -67,18 +105,34 @@ pub struct PrCheckoutCommandArgs {
     #[arg(long)]
     author: Option<String>,
 
+    /// Checkout the open PR whose source branch is BRANCH. If several PRs
+    /// match, pick one of them
+    #[arg(long, conflicts_with = "number")]
+    branch: Option<String>,
+
     /// Filter to only draft PRs for interactive selection
     #[arg(long)]
     draft: bool,
 
-    /// Filter by labels (comma-separated) for interactive selection
-    #[arg(long, value_delimiter = ',')]
+    /// Filter by labels (comma-separated) for interactive selection. Prefix a
+    /// label with `-` to exclude it
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
     labels: Vec<String>,
 
-    /// PR number to checkout. Omit for interactive selection
-    number: Option<u32>,
+    /// Don't set the upstream of the branch. By default, the branch tracks the
+    /// source branch of the PR if it's in the repository of the remote.
+    /// Otherwise, e.g. for PRs from forks, it tracks the PR ref, which lets
+    /// `git pull` get the updates of the PR but can't be pushed to
+    #[arg(long)]
+    no_track: bool,
+
+    /// PR number or web URL (e.g. https://github.com/o/r/pull/123) to
+    /// checkout. Omit for interactive selection
+    #[arg(value_name = "NUMBER|URL", value_parser = parse_pr_reference)]
+    number: Option<PrReference>,
 
-    /// Number of PRs per page for interactive selection
+    /// Number of PRs per page for interactive selection. Defaults to as many
+    /// as fit on the screen
     #[arg(long, short_alias = 'l', alias = "limit", value_name = "NUMBER")]
     per_page: Option<u32>,
 
@@ -95,21 +149,42 @@ pub struct PrCheckoutCommandArgs {
     state: Option<PrState>,
 }
 
+/// A PR given by its number or by its web URL.
+#[derive(Clone, Debug, PartialEq)]
+enum PrReference {
+    Number(u32),
+    Url(PrUrl),
+}
+
+/// The parts of the web URL of a PR.
+#[derive(Clone, Debug, PartialEq)]
+struct PrUrl {
+    host: String,
+    repo_path: String,
+    number: u32,
+}
+
 /// Command-line arguments for creating a new pull request.
 #[derive(Args)]
 pub struct PrCreateCommandArgs {
     /// Specify the forge which affects the API schema etc
-    #[arg(long, value_name = "TYPE")]
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
     api: Option<ApiType>,
 
     /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4) instead of relying on the auto-detection
-    #[arg(long)]
-    api_url: Option<String>,
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
     // PR description
     #[arg(long)]
     body: Option<String>,
 
+    /// Create the labels of --labels that don't exist in the repository
+    /// instead of failing. Can also be set with the
+    /// `pr/create/missing-labels=create` setting
+    #[arg(long, conflicts_with = "ignore_missing_labels")]
+    create_missing_labels: bool,
+
     /// Create as draft PR
     #[arg(long)]
     draft: bool,
@@ -133,18 +208,62 @@ pub struct PrCreateCommandArgs {
     #[arg(long, group = "input-mode")]
     fill_verbose: bool,
 
+    /// Output format of the created PR. `json` prints the whole PR as a JSON
+    /// object even when the browser is opened [default: url]
+    #[arg(short = 'o', long, conflicts_with = "web")]
+    format: Option<CreatedItemFormat>,
+
+    /// Leave out the labels of --labels that don't exist in the repository
+    /// instead of failing. Can also be set with the
+    /// `pr/create/missing-labels=ignore` setting
+    #[arg(long)]
+    ignore_missing_labels: bool,
+
+    /// Labels to add (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    labels: Vec<String>,
+
     /// Don't open the issue in the browser after creation
     #[arg(short, long)]
     no_browser: bool,
 
+    /// Don't allow the maintainers of the target repository to push to the
+    /// branch of the PR. Only GitHub allows them to by default
+    #[arg(long, conflicts_with = "web")]
+    no_maintainer_edit: bool,
+
+    /// Don't check whether the repository is archived or accessible with the
+    /// token before pushing the branch and asking for the PR
+    #[arg(long)]
+    no_precheck: bool,
+
+    /// Don't ask for labels after writing the message in the text editor. Can
+    /// also be disabled with the `pr/create/prompt-labels=false` setting
+    #[arg(long)]
+    no_prompt: bool,
+
     /// Don't push the branch. Expect the branch to already exist at the remote.
     #[arg(long)]
     no_push: bool,
 
+    /// What to do with the PR after creating it instead of asking. Without a
+    /// terminal the PR is opened in the browser. --no-browser skips this
+    #[arg(long, value_name = "ACTION")]
+    post_action: Option<PrPostAction>,
+
     /// Git remote to use
     #[arg(long)]
     remote: Option<String>,
 
+    /// Delete the branch once the PR is merged. Only supported for GitLab
+    #[arg(long, conflicts_with = "web")]
+    remove_source_branch: bool,
+
+    /// Squash the commits of the PR when it's merged. Only supported for
+    /// GitLab
+    #[arg(long, conflicts_with = "web")]
+    squash_on_merge: bool,
+
     /// Target branch
     #[arg(long)]
     target: Option<String>,
@@ -161,17 +280,25 @@ pub struct PrCreateCommandArgs {
 /// Command-line arguments for listing pull requests.
 #[derive(Args, Clone)]
 pub struct PrListCommandArgs {
+    /// Fetch all pages instead of only the one given by --page
+    #[arg(
+        long,
+        conflicts_with_all = ["interactive", "limit", "page", "watch", "web"]
+    )]
+    all_pages: bool,
+
     /// Specify the forge which affects the API schema etc
-    #[arg(long, value_name = "TYPE")]
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
     api: Option<ApiType>,
 
     /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
     /// instead of relying on the auto-detection
-    #[arg(long)]
-    api_url: Option<String>,
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
 
     /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
-    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN)
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
     #[arg(long)]
     auth: bool,
 
@@ -179,26 +306,86 @@ pub struct PrListCommandArgs {
     #[arg(long)]
     author: Option<String>,
 
+    /// Only list PRs of bot accounts, e.g. dependency updates. Authors ending
+    /// with `[bot]` and the names in the `pr/list/bot-authors` setting
+    /// (comma-separated) are bots. The filter is applied after fetching the
+    /// page, so a page may contain fewer PRs
+    #[arg(long, conflicts_with_all = ["interactive", "no_bot", "web"])]
+    bot: bool,
+
     /// Filter to only draft PRs
     #[arg(long)]
     draft: bool,
 
-    /// Fields to include in output (comma-separated)
-    #[arg(short, long, value_delimiter = ',')]
+    /// Exit with status 1 if any PRs are found, e.g. to assert in CI that
+    /// nothing is waiting for a review. The PRs are printed first
+    #[arg(
+        long,
+        conflicts_with_all = ["fail_if_none", "interactive", "watch", "web"]
+    )]
+    fail_if_any: bool,
+
+    /// Exit with status 1 if no PRs are found. The PRs are printed first
+    #[arg(long, conflicts_with_all = ["interactive", "watch", "web"])]
+    fail_if_none: bool,
+
+    /// Fields to include in output (comma-separated). The columns and JSON keys
+    /// follow their order. Use `all` for all fields. The names of `gh --json`,
+    /// e.g. `number` or `headRefName`, are accepted as aliases
+    #[arg(
+        short,
+        long,
+        value_delimiter = ',',
+        value_parser = io::FieldParser::<PrField>::new()
+    )]
     fields: Vec<PrField>,
 
     /// Output format
     #[arg(short = 'o', long)]
     format: Option<OutputFormat>,
 
+    /// Print only the PR numbers, one per line, e.g. for piping into xargs.
+    /// Ignores --fields and --format
+    #[arg(
+        long,
+        conflicts_with_all = ["fields", "format", "interactive", "watch", "web"]
+    )]
+    ids_only: bool,
+
     /// Use interactive TUI for searching and selecting a PR
     #[arg(short, long, group = "interaction-type")]
     interactive: bool,
 
-    /// Filter by labels (comma-separated)
-    #[arg(long, value_delimiter = ',')]
+    /// Filter by labels (comma-separated). Prefix a label with `-` to exclude
+    /// it, e.g. `bug,-triaged`
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
     labels: Vec<String>,
 
+    /// List at most this many PRs in total. Pages starting at --page are
+    /// fetched until the limit is reached. The interactive TUI stops loading
+    /// more PRs once it has loaded this many
+    #[arg(
+        long,
+        short_alias = 'l',
+        value_name = "NUMBER",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    limit: Option<u32>,
+
+    /// Filter by milestone title
+    #[arg(long, value_name = "TITLE")]
+    milestone: Option<String>,
+
+    /// Seconds between refreshes in watch mode (minimum 10)
+    #[arg(
+        long,
+        default_value_t = 60,
+        value_parser = clap::value_parser!(u64).range(10..),
+        value_name = "SECONDS",
+        requires = "watch"
+    )]
+    interval: u64,
+
     /// Page number to fetch
     #[arg(
         long,
@@ -208,8 +395,17 @@ pub struct PrListCommandArgs {
     )]
     page: u32,
 
-    /// Number of PRs per page
-    #[arg(long, short_alias = 'l', alias = "limit", value_name = "NUMBER")]
+    /// Don't list PRs of bot accounts. See --bot for which authors are bots
+    #[arg(long, conflicts_with_all = ["interactive", "web"])]
+    no_bot: bool,
+
+    /// Don't truncate the cells of rows that are wider than the terminal
+    #[arg(long)]
+    no_truncate: bool,
+
+    /// Number of PRs per page [default: 30, or --limit up to 100]. The
+    /// interactive TUI fetches as many as fit on the screen by default
+    #[arg(long, value_name = "NUMBER")]
     per_page: Option<u32>,
 
     /// Search keywords
@@ -220,15 +416,168 @@ pub struct PrListCommandArgs {
     #[arg(long)]
     remote: Option<String>,
 
+    /// Filter by review status. This needs extra API requests per PR (on
+    /// GitHub with --auth, one request per page) and is applied after fetching
+    /// the page, so a page may contain fewer PRs
+    #[arg(long, value_name = "STATUS")]
+    review: Option<PrReviewStatus>,
+
+    /// Sort the PRs by a timestamp, newest first. Only the fetched PRs are
+    /// sorted, so use --all-pages to sort all PRs
+    #[arg(long, conflicts_with_all = ["interactive", "watch", "web"])]
+    sort: Option<PrSort>,
+
     /// Filter by state
     #[arg(long)]
     state: Option<PrState>,
 
+    /// Refresh the listing every interval and highlight changes until Ctrl-C.
+    /// Using --auth is strongly suggested to avoid hitting rate limits
+    #[arg(long, conflicts_with_all = ["interactive", "web"])]
+    watch: bool,
+
     /// Open the prs page in the web browser
     #[arg(short, long)]
     web: bool,
 }
 
+/// Command-line arguments for merging a pull request.
+#[derive(Args)]
+pub struct PrMergeCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Let the forge merge the pull request once its checks pass instead of
+    /// merging it right away
+    #[arg(long, conflicts_with = "disable_auto")]
+    auto: bool,
+
+    /// Cancel the merge scheduled with --auto
+    #[arg(long)]
+    disable_auto: bool,
+
+    /// PR number to merge
+    number: u32,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+/// Command-line arguments for changing the reviewers of a pull request.
+#[derive(Args)]
+pub struct PrReviewerCommandArgs {
+    /// Request reviews from these users (comma-separated)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "USERNAMES",
+        required_unless_present = "remove"
+    )]
+    add: Vec<String>,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Output format of the resulting reviewers [default: text]
+    #[arg(short = 'o', long)]
+    format: Option<ResultFormat>,
+
+    /// PR number to change the reviewers of
+    number: u32,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Remove the review requests of these users (comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "USERNAMES")]
+    remove: Vec<String>,
+}
+
+/// Command-line arguments for viewing a pull request.
+#[derive(Args)]
+pub struct PrViewCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
+
+    /// Include the discussion comments in the timeline
+    #[arg(short, long)]
+    comments: bool,
+
+    /// Copy the values of --field to the clipboard instead of printing them
+    #[arg(long, requires = "fields")]
+    copy: bool,
+
+    /// Fetch the target branch before comparing the current branch with it.
+    /// This is the default
+    #[arg(long, overrides_with = "no_fetch")]
+    fetch: bool,
+
+    /// Only print the value of this field of the pull request, e.g. for a
+    /// command substitution. Can be repeated to print several values
+    /// tab-separated on one line. Arrays are joined with commas
+    #[arg(
+        short,
+        long = "field",
+        value_name = "NAME",
+        value_parser = io::FieldParser::<PrField>::new(),
+        conflicts_with_all = ["comments", "format", "links", "reviews"]
+    )]
+    fields: Vec<PrField>,
+
+    /// Print the timeline events in this format instead of rendering them.
+    /// Use `json` for tooling
+    #[arg(short = 'o', long)]
+    format: Option<OutputFormat>,
+
+    /// Also list the issues that the pull request closes. With `--format
+    /// json`, the output is an object with the `timeline` and the `links`
+    #[arg(short, long)]
+    links: bool,
+
+    /// Compare the current branch with the last fetched state of the target
+    /// branch instead of fetching it
+    #[arg(long, overrides_with = "fetch")]
+    no_fetch: bool,
+
+    /// PR number to view
+    number: u32,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Include the review summaries (approvals, requested changes) in the
+    /// timeline
+    #[arg(short, long)]
+    reviews: bool,
+}
+
 // =============================================================================
 // Domain Types
 // =============================================================================
@@ -247,6 +596,31 @@ pub enum PrState {
     All,
 }
 
+/// What to do with a PR after `pr create`.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum PrPostAction {
+    /// Open the PR in the browser.
+    Open,
+    /// Copy the URL of the PR to the clipboard.
+    Copy,
+    /// Check out the PR as a local branch like `pr checkout`.
+    Checkout,
+    /// Do nothing.
+    #[value(name = "none")]
+    Nothing,
+}
+
+impl std::fmt::Display for PrPostAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrPostAction::Open => write!(f, "Open in browser"),
+            PrPostAction::Copy => write!(f, "Copy URL"),
+            PrPostAction::Checkout => write!(f, "Check out locally"),
+            PrPostAction::Nothing => write!(f, "Nothing"),
+        }
+    }
+}
+
 impl std::fmt::Display for PrState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -258,7 +632,7 @@ impl std::fmt::Display for PrState {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, ValueEnum)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
 #[serde(rename_all = "snake_case")]
 #[value(rename_all = "snake_case")]
 pub enum PrField {
@@ -266,13 +640,161 @@ pub enum PrField {
     Title,
     State,
     Labels,
+    Milestone,
     Author,
     CreatedAt,
     UpdatedAt,
+    MergedAt,
+    ClosedAt,
     Url,
+    #[serde(rename = "head.ref")]
     Source,
+    #[serde(rename = "head.sha")]
+    SourceSha,
+    #[serde(rename = "base.ref")]
     Target,
+    #[serde(rename = "base.sha")]
+    TargetSha,
     Draft,
+    ApprovalsRequired,
+    ApprovalsLeft,
+    ReviewStatus,
+    Mergeable,
+    Ci,
+    Comments,
+    Commits,
+    ChangedFiles,
+    All,
+}
+
+/// The timestamps PRs can be sorted by with `pr list --sort`.
+#[derive(Clone, Debug, ValueEnum)]
+pub enum PrSort {
+    /// Recently closed or merged PRs first. Open PRs come last.
+    Closed,
+    /// Recently merged PRs first. Unmerged PRs come last.
+    Merged,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum PrReviewStatus {
+    /// At least one reviewer approved and nobody requested changes.
+    Approved,
+    /// At least one reviewer requested changes.
+    ChangesRequested,
+    /// A review was requested but hasn't been given yet.
+    ReviewRequired,
+    /// No reviews and no pending review requests.
+    None,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrMergeable {
+    /// The pull request can be merged without conflicts.
+    Mergeable,
+    /// The pull request has merge conflicts.
+    Conflicting,
+    /// The forge hasn't computed the mergeability (yet).
+    Unknown,
+}
+
+/// The combined CI state. Ordered by severity, so combining multiple states is
+/// just taking the maximum.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrCiStatus {
+    /// No CI ran for the pull request.
+    None,
+    /// All CI jobs succeeded.
+    Success,
+    /// At least one CI job is still running.
+    Pending,
+    /// At least one CI job failed.
+    Failure,
+}
+
+impl PrMergeable {
+    /// The symbol of a serialized mergeability in a table.
+    fn symbol(mergeable: &str) -> Option<&'static str> {
+        match mergeable {
+            "mergeable" => Some("✓"),
+            "conflicting" => Some("✗"),
+            "unknown" => Some("…"),
+            _ => None,
+        }
+    }
+}
+
+impl PrCiStatus {
+    /// The symbol of a serialized CI state in a table. Without CI, the word
+    /// is kept since there is nothing to mark.
+    fn symbol(ci: &str) -> Option<&'static str> {
+        match ci {
+            "success" => Some("✓"),
+            "failure" => Some("✗"),
+            "pending" => Some("…"),
+            _ => None,
+        }
+    }
+}
+
+/// What `pr merge` did.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PrMergeOutcome {
+    Merged,
+    /// The forge merges the pull request once its checks pass.
+    Scheduled,
+}
+
+/// Mergeability and CI state of a pull request.
+pub struct PrMergeInfo {
+    pub mergeable: PrMergeable,
+    pub ci: PrCiStatus,
+}
+
+/// The sizes of a pull request that help estimating the effort of a review.
+/// A count is `None` if the forge doesn't report it.
+#[derive(Debug, Default, PartialEq)]
+pub struct PrCounts {
+    pub comments: Option<u32>,
+    pub commits: Option<u32>,
+    pub changed_files: Option<u32>,
+}
+
+impl PrReviewStatus {
+    /// Combines the reviews of a pull request into a single status. The
+    /// reviews are expected in chronological order; only the latest review of
+    /// each reviewer counts. Requested changes take precedence over approvals.
+    pub fn from_reviews<'a>(
+        reviews: impl IntoIterator<Item = (&'a str, PrReviewStatus)>,
+        has_pending_requests: bool,
+    ) -> Self {
+        let latest_reviews: HashMap<&str, PrReviewStatus> = reviews.into_iter().collect();
+        let has_status = |status: PrReviewStatus| latest_reviews.values().any(|s| *s == status);
+
+        if has_status(PrReviewStatus::ChangesRequested) {
+            PrReviewStatus::ChangesRequested
+        } else if has_status(PrReviewStatus::Approved) {
+            PrReviewStatus::Approved
+        } else if has_pending_requests || has_status(PrReviewStatus::ReviewRequired) {
+            PrReviewStatus::ReviewRequired
+        } else {
+            PrReviewStatus::None
+        }
+    }
+
+    /// The color of a serialized review status in a table, e.g. green for an
+    /// approval.
+    fn color(status: &str) -> Option<CellColor> {
+        match status {
+            "approved" => Some(CellColor::Green),
+            "changes_requested" => Some(CellColor::Red),
+            "review_required" => Some(CellColor::Yellow),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Serialize)]
@@ -289,28 +811,180 @@ pub struct Pr {
     pub url: String,
     /// Labels attached to this pull request.
     pub labels: Vec<String>,
+    /// The title of the milestone the pull request belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
     /// Timestamp when the pull request was created.
-    pub created_at: String,
+    pub created_at: Timestamp,
     /// Timestamp when the pull request was last updated.
-    pub updated_at: String,
+    pub updated_at: Timestamp,
+    /// Timestamp when the pull request was merged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merged_at: Option<Timestamp>,
+    /// Timestamp when the pull request was closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<Timestamp>,
+    /// The source branch. Not every forge returns it when listing pull
+    /// requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<PrBranch>,
+    /// The target branch. Not every forge returns it when listing pull
+    /// requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<PrBranch>,
     /// Whether the pull request is a draft.
     pub draft: bool,
+    /// Number of approvals required before merging. Only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approvals_required: Option<u32>,
+    /// Number of approvals still missing before merging. Only fetched on
+    /// request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approvals_left: Option<u32>,
+    /// The combined review status. Only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review_status: Option<PrReviewStatus>,
+    /// Whether the pull request can be merged. Only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mergeable: Option<PrMergeable>,
+    /// The combined CI state of the head commit. Only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ci: Option<PrCiStatus>,
+    /// Number of comments including review comments. Some forges return it
+    /// when listing pull requests, otherwise it's only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<u32>,
+    /// Number of commits. Only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commits: Option<u32>,
+    /// Number of changed files. Only fetched on request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_files: Option<u32>,
+}
+
+/// The source or target branch of a pull request.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PrBranch {
+    /// The branch name.
+    #[serde(rename = "ref")]
+    pub name: String,
+    /// The commit the branch pointed to, if the forge returned it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+}
+
+/// Approval state of a pull request.
+pub struct PrApprovals {
+    pub required: u32,
+    pub left: u32,
 }
 
 impl ListableItem for Pr {
     fn get_display_text(&self) -> String {
         format!("{}: {}", self.id, self.title)
     }
+
+    fn get_id(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn get_sort_value(&self, key: SortKey) -> Option<SortValue> {
+        match key {
+            SortKey::Id => Some(SortValue::Number(u64::from(self.id))),
+            SortKey::Updated => Some(SortValue::Timestamp(self.updated_at)),
+            SortKey::Created => Some(SortValue::Timestamp(self.created_at)),
+            SortKey::Title => Some(SortValue::Text(self.title.clone())),
+            SortKey::Reactions => None,
+        }
+    }
 }
 
 pub struct ListPrsFilters<'a> {
     pub author: Option<&'a str>,
-    pub labels: &'a [String],
+    pub labels: &'a LabelFilter,
+    /// The title of the milestone.
+    pub milestone: Option<&'a str>,
     pub page: u32,
     pub per_page: u32,
     pub query: Option<&'a str>,
     pub state: &'a PrState,
     pub draft: bool,
+    /// Applied client-side after fetching since it needs extra requests per PR.
+    pub review: Option<&'a PrReviewStatus>,
+    /// Fetch the pages following `page` as well. The forge functions only
+    /// fetch `page`; `get_prs_with_fields` fetches the following pages.
+    pub all_pages: bool,
+    /// Fetch the pages following `page` until there are this many PRs. Like
+    /// `all_pages`, it's only used by `get_prs_with_fields`.
+    pub limit: Option<u32>,
+}
+
+/// What happened in a pull request's timeline.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrTimelineEventKind {
+    /// The pull request was opened with this description.
+    Description,
+    /// A discussion comment.
+    Comment,
+    /// A review that approved the pull request.
+    Approved,
+    /// A review that requested changes.
+    ChangesRequested,
+    /// A review without a verdict, e.g. only with inline comments.
+    Reviewed,
+    /// A review that was dismissed later.
+    Dismissed,
+}
+
+/// A single event of a pull request's timeline.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PrTimelineEvent {
+    pub kind: PrTimelineEventKind,
+    pub author: String,
+    /// When the event happened.
+    pub created_at: Timestamp,
+    pub body: String,
+}
+
+/// Issues and pull requests linked to a pull request.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct PrLinks {
+    /// The numbers of the issues that are closed when the pull request is
+    /// merged.
+    pub closes: Vec<u32>,
+}
+
+/// How far the checked out source branch of a pull request diverged from its
+/// target branch.
+#[derive(Debug, PartialEq)]
+struct PrBranchStatus {
+    /// The remote-tracking branch of the target, e.g. `origin/main`.
+    target: String,
+    /// Commits on the current branch that aren't on the target.
+    ahead: u32,
+    /// Commits on the target that aren't on the current branch.
+    behind: u32,
+}
+
+/// JSON output of `pr view --links`.
+#[derive(Serialize)]
+struct PrViewWithLinks<'a> {
+    timeline: &'a [PrTimelineEvent],
+    links: &'a PrLinks,
+}
+
+/// Which events to fetch in addition to the description.
+pub struct PrTimelineOptions {
+    pub comments: bool,
+    pub reviews: bool,
+}
+
+/// The requested reviewers of a PR after `pr reviewer`.
+#[derive(Debug, Serialize)]
+struct PrReviewers {
+    id: u32,
+    reviewers: Vec<String>,
 }
 
 pub struct CreatePrOptions<'a> {
@@ -319,6 +993,17 @@ pub struct CreatePrOptions<'a> {
     pub target_branch: &'a str,
     pub body: &'a str,
     pub draft: bool,
+    /// Whether the forge accepts marking the PR as a draft. Otherwise, a draft
+    /// gets a title prefix, see `supports_native_drafts` of the forges.
+    pub native_draft: bool,
+    pub labels: &'a [String],
+    /// Don't allow maintainers of the target repository to push to the
+    /// branch.
+    pub no_maintainer_edit: bool,
+    /// Delete the branch once the PR is merged.
+    pub remove_source_branch: bool,
+    /// Squash the commits when the PR is merged.
+    pub squash_on_merge: bool,
 }
 
 // =============================================================================
@@ -329,12 +1014,20 @@ pub struct CreatePrOptions<'a> {
 /// TSV or open the prs page in the web browser.
 pub fn list_prs(mut args: PrListCommandArgs) -> anyhow::Result<()> {
     let config = Config::load_from_disk().context("Failed to load configuration")?;
+
+    if args.limit.is_some() && args.per_page.is_none() {
+        log::warn!(
+            "--limit caps the total number of PRs now. Use --per-page to only set the page size"
+        );
+    }
+
     let remote_name = args.remote.clone().unwrap_or_else(|| {
         config
             .get_string("pr/list/remote", None)
             .unwrap_or(DEFAULT_REMOTE.to_string())
     });
-    let remote = git::get_remote_data(&remote_name)
+    let remote = config
+        .get_remote_data(&remote_name)
         .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
 
     config::merge_config_into_args!(
@@ -347,113 +1040,436 @@ pub fn list_prs(mut args: PrListCommandArgs) -> anyhow::Result<()> {
             api_url,
             auth,
             draft,
-            fields,
             format,
             interactive,
+            limit,
+            no_truncate,
             per_page,
             state
         ]
     );
 
-    let api_type = match args.api {
-        Some(api_type) => api_type,
-        None => forge::guess_api_type_from_host(&remote.host)
-            .with_context(|| format!("Failed to guess forge from host: {}", &remote.host))?,
-    };
+    args.fields = io::resolve_fields(
+        args.fields,
+        config.get_string("pr/list/fields", Some(&remote)),
+        vec![PrField::Title, PrField::Id, PrField::Url],
+    )?;
+
+    // Skip the extra requests for fields that won't be printed.
+    if args.ids_only {
+        args.fields = vec![PrField::Id];
+    }
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let bot_authors: Vec<String> = config
+        .get_string("pr/list/bot-authors", Some(&remote))
+        .map(|authors| {
+            authors
+                .split(',')
+                .map(str::trim)
+                .filter(|author| !author.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
 
     if args.interactive {
         list_prs_interactively(remote, api_type, args)
     } else if args.web {
         list_prs_in_web_browser(&remote, &api_type)
+    } else if args.watch {
+        watch_prs(&remote, &api_type, args, &bot_authors)
     } else {
-        list_prs_to_stdout(
-            &remote,
-            &api_type,
-            args.api_url.as_deref(),
-            &ListPrsFilters {
-                author: args.author.as_deref(),
-                labels: &args.labels,
-                page: args.page,
-                per_page: args.per_page.unwrap_or(DEFAULT_PER_PAGE),
-                query: args.query.as_deref(),
-                state: &args.state.unwrap_or_default(),
-                draft: args.draft,
-            },
-            args.fields,
-            &args.format.unwrap_or_default(),
-            args.auth,
-        )
+        list_prs_to_stdout(&remote, &api_type, args, &bot_authors)
     }
 }
 
-/// Checks out a pull request as a local branch.
-pub fn checkout_pr(mut args: PrCheckoutCommandArgs) -> anyhow::Result<()> {
+/// Merges a PR, schedules it to be merged once its checks pass (--auto) or
+/// cancels the scheduled merge (--disable-auto).
+pub fn merge_pr(mut args: PrMergeCommandArgs) -> anyhow::Result<()> {
     let config = Config::load_from_disk().context("Failed to load configuration")?;
     let remote_name = args.remote.clone().unwrap_or_else(|| {
         config
-            .get_string("pr/checkout/remote", None)
+            .get_string("pr/merge/remote", None)
             .unwrap_or(DEFAULT_REMOTE.to_string())
     });
-    let remote_result = git::get_remote_data(&remote_name);
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
 
-    config::merge_config_into_args!(
-        &config,
-        args,
-        remote_result.as_ref().ok(),
-        "pr/checkout",
-        [api, api_url, auth, author, draft, per_page, state]
-    );
+    config::merge_config_into_args!(&config, args, Some(&remote), "pr/merge", [api, api_url]);
 
-    // Allow remote detection to fail if user provides --api explicitly.
-    let api_type = match remote_result {
-        Ok(ref remote) => forge::guess_api_type_from_host(&remote.host)
-            .with_context(|| format!("Failed to guess forge from host: {}", &remote.host))?,
-        Err(ref e) => match args.api {
-            Some(api_type) => api_type,
-            None => anyhow::bail!(
-                "Could not detect the forge type from the remote URL: {e}\nSpecify the forge type explicitly with --api (github|gitlab|gitea|forgejo)"
-            ),
-        },
-    };
-    let get_pr_ref = forge::function!(api_type, get_pr_ref);
-    let pr_number = match args.number {
-        Some(nr) => nr,
-        None => {
-            let remote = remote_result?;
-            let fetch_options = tui::build_fetch_options! {
-                "author": args.author,
-                "draft": args.draft,
-                "labels": args.labels,
-                "query": args.query,
-                "state": args.state,
-            };
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+    let api_url = args.api_url.as_deref();
 
-            eprintln!("Loading pull requests...");
+    if args.auto || args.disable_auto {
+        forge::supports(api_type, Capability::AutoMerge).ensure()?;
+    }
+
+    if args.disable_auto {
+        let cancel_pr_auto_merge = forge::function!(api_type, cancel_pr_auto_merge);
+
+        cancel_pr_auto_merge(&http_client, &remote, api_url, args.number).with_context(|| {
+            format!(
+                "Failed to cancel the scheduled merge of PR #{}",
+                args.number
+            )
+        })?;
+
+        eprintln!("Cancelled the scheduled merge of PR #{}", args.number);
+
+        return Ok(());
+    }
+
+    let merge_pr = forge::function!(api_type, merge_pr);
+    let outcome = merge_pr(&http_client, &remote, api_url, args.number, args.auto)
+        .with_context(|| format!("Failed to merge PR #{}", args.number))?;
+
+    match outcome {
+        PrMergeOutcome::Merged => eprintln!("Merged PR #{}", args.number),
+        PrMergeOutcome::Scheduled => eprintln!(
+            "Scheduled PR #{} to be merged once its checks pass. It isn't merged yet",
+            args.number
+        ),
+    }
+
+    Ok(())
+}
+
+/// Adds or removes the requested reviewers of a PR and prints the resulting
+/// reviewers.
+pub fn update_pr_reviewers(mut args: PrReviewerCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("pr/reviewer/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "pr/reviewer",
+        [api, api_url, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let update_pr_reviewers = forge::function!(api_type, update_pr_reviewers);
+    let reviewers = PrReviewers {
+        id: args.number,
+        reviewers: update_pr_reviewers(
+            &HttpClient::new(),
+            &remote,
+            args.api_url.as_deref(),
+            args.number,
+            &args.add,
+            &args.remove,
+        )
+        .with_context(|| format!("Failed to update the reviewers of PR #{}", args.number))?,
+    };
+
+    match args.format.unwrap_or_default() {
+        ResultFormat::Text => println!("{}", format_pr_reviewers(&reviewers)),
+        ResultFormat::Json => println!("{}", serde_json::to_string_pretty(&reviewers)?),
+    }
+
+    Ok(())
+}
+
+/// Prints the description of a pull request and, if requested, its comments
+/// and reviews in chronological order.
+pub fn view_pr(mut args: PrViewCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("pr/view/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "pr/view",
+        [api, api_url, auth, comments, links, reviews]
+    );
+
+    if args.links && matches!(args.format, Some(OutputFormat::Csv | OutputFormat::Tsv)) {
+        anyhow::bail!("--links can only be combined with --format json");
+    }
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+
+    if !args.fields.is_empty() {
+        let fields = io::resolve_fields(args.fields, None, vec![])?;
+        let get_pr = forge::function!(api_type, get_pr);
+        let mut pr = get_pr(
+            &http_client,
+            &remote,
+            args.api_url.as_deref(),
+            args.number,
+            args.auth,
+        )
+        .with_context(|| format!("Failed fetching PR #{}", args.number))?;
+
+        add_fields(
+            &http_client,
+            &remote,
+            &api_type,
+            args.api_url.as_deref(),
+            std::slice::from_mut(&mut pr),
+            &fields,
+            args.auth,
+        )?;
+
+        return io::print_field_values(&pr, &fields, args.copy);
+    }
+
+    let get_pr_timeline_events = forge::function!(api_type, get_pr_timeline_events);
+    let events = get_pr_timeline_events(
+        &http_client,
+        &remote,
+        args.api_url.as_deref(),
+        args.number,
+        &PrTimelineOptions {
+            comments: args.comments,
+            reviews: args.reviews,
+        },
+        args.auth,
+    )
+    .with_context(|| format!("Failed fetching the timeline of PR #{}", args.number))?;
+    let timeline = build_timeline(events);
+    let links = if args.links {
+        let description = timeline
+            .iter()
+            .find(|event| event.kind == PrTimelineEventKind::Description)
+            .map(|event| event.body.as_str())
+            .unwrap_or_default();
+        let get_pr_closing_issues = forge::function!(api_type, get_pr_closing_issues);
+        let mut closes = parse_closing_issue_references(description);
+
+        closes.extend(
+            get_pr_closing_issues(
+                &http_client,
+                &remote,
+                args.api_url.as_deref(),
+                args.number,
+                args.auth,
+            )
+            .with_context(|| format!("Failed fetching the issues closed by PR #{}", args.number))?,
+        );
+        closes.sort_unstable();
+        closes.dedup();
+
+        Some(PrLinks { closes })
+    } else {
+        None
+    };
+
+    // Only the rendered view shows the auto-merge state, so that the JSON output
+    // stays a list of timeline events
+    let auto_merge = match args.format {
+        Some(_) => None,
+        None => {
+            let get_pr_auto_merge = forge::function!(api_type, get_pr_auto_merge);
+
+            get_pr_auto_merge(
+                &http_client,
+                &remote,
+                args.api_url.as_deref(),
+                args.number,
+                args.auth,
+            )
+            .with_context(|| {
+                format!(
+                    "Failed fetching the auto-merge state of PR #{}",
+                    args.number
+                )
+            })?
+        }
+    };
+
+    // Only the rendered view compares the PR with the checked out branch
+    let branch_status = match args.format {
+        Some(_) => None,
+        None => {
+            let get_pr = forge::function!(api_type, get_pr);
+            let pr = get_pr(
+                &http_client,
+                &remote,
+                args.api_url.as_deref(),
+                args.number,
+                args.auth,
+            )
+            .with_context(|| format!("Failed fetching PR #{}", args.number))?;
+
+            get_branch_status(&pr, &remote_name, args.fetch || !args.no_fetch)
+        }
+    };
+
+    match (args.format, links) {
+        (Some(_), Some(links)) => println!(
+            "{}",
+            serde_json::to_string_pretty(&PrViewWithLinks {
+                timeline: &timeline,
+                links: &links,
+            })?
+        ),
+        (Some(format), None) => {
+            println!("{}", io::format(&timeline, &[] as &[PrField], &format)?)
+        }
+        (None, Some(links)) => io::print_with_pager(&format!(
+            "{}{}{}\n{}",
+            render_branch_status(branch_status.as_ref()),
+            render_auto_merge(auto_merge),
+            render_timeline(&timeline),
+            render_links(&links)
+        ))?,
+        (None, None) => io::print_with_pager(&format!(
+            "{}{}{}",
+            render_branch_status(branch_status.as_ref()),
+            render_auto_merge(auto_merge),
+            render_timeline(&timeline)
+        ))?,
+    }
+
+    Ok(())
+}
+
+/// Sorts the timeline events chronologically with the description first.
+/// Reviews without a verdict and without a summary are dropped since their
+/// content lives in inline comments.
+pub fn build_timeline(events: Vec<PrTimelineEvent>) -> Vec<PrTimelineEvent> {
+    let mut timeline: Vec<PrTimelineEvent> = events
+        .into_iter()
+        .filter(|event| {
+            event.kind != PrTimelineEventKind::Reviewed || !event.body.trim().is_empty()
+        })
+        .collect();
+
+    timeline.sort_by_key(|event| {
+        (
+            event.kind != PrTimelineEventKind::Description,
+            event.created_at,
+        )
+    });
+
+    timeline
+}
+
+/// Checks out a pull request as a local branch.
+pub fn checkout_pr(mut args: PrCheckoutCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("pr/checkout/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote_result = config.get_remote_data(&remote_name);
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        remote_result.as_ref().ok(),
+        "pr/checkout",
+        [api, api_url, auth, author, draft, no_track, per_page, state]
+    );
+
+    // Allow remote detection to fail if user provides --api (or GIT_FORGE_API)
+    // explicitly.
+    let api_type = match remote_result {
+        Ok(ref remote) => forge::get_api_type(args.api, &config, remote)?,
+        Err(ref e) => match args.api {
+            Some(api_type) => api_type,
+            None => config::get_forge_type_from_env()?.with_context(|| {
+                format!(
+                    "Could not detect the forge type from the remote URL: {e}\nSpecify the forge type explicitly with --api (github|gitlab|gitea|forgejo)"
+                )
+            })?,
+        },
+    };
+    let get_pr_ref = forge::function!(api_type, get_pr_ref);
+    let pr_number = match (args.number, args.branch) {
+        (Some(PrReference::Number(nr)), _) => nr,
+        (Some(PrReference::Url(pr_url)), _) => {
+            // Without remote data (see above), the URL can't be checked
+            if let Ok(ref remote) = remote_result {
+                ensure_pr_url_matches_remote(&pr_url, remote, &remote_name)?;
+            }
+
+            pr_url.number
+        }
+        (None, Some(branch)) => select_pr_for_branch(
+            &remote_result?,
+            api_type,
+            args.api_url.as_deref(),
+            &branch,
+            args.auth,
+        )?,
+        (None, None) => {
+            let remote = remote_result?;
+            let fetch_options = tui::build_fetch_options! {
+                "author": args.author,
+                "draft": args.draft,
+                "labels": args.labels,
+                "query": args.query,
+                "state": args.state,
+            };
+
+            eprintln!("Loading pull requests...");
 
             let pr = select_pr_interactively(
                 remote,
                 api_type,
-                args.api_url,
+                args.api_url.clone(),
                 fetch_options,
-                args.per_page.unwrap_or(DEFAULT_PER_PAGE),
+                args.per_page,
+                None,
                 args.auth,
             )?;
 
             pr.id
         }
     };
-    let pr_ref = get_pr_ref(pr_number);
-    let branch_name = format!("pr-{pr_number}");
-
-    eprintln!("Fetching {pr_ref}:{branch_name} from {remote_name}...");
-    git::fetch_pull_request(&pr_ref, &branch_name, &remote_name)?;
 
-    eprintln!("Checking out {branch_name}...");
-    git::checkout_branch(&branch_name)?;
+    let pr_ref = get_pr_ref(pr_number);
+    let branch_name = fetch_and_checkout_pr(&pr_ref, pr_number, &remote_name)?;
 
-    eprintln!("Successfully checked out PR \"{pr_number}\" to branch \"{branch_name}\"");
+    if args.no_track {
+        return Ok(());
+    }
 
-    Ok(())
+    // The source branch is only needed for the upstream, so the PR ref is
+    // tracked if the PR can't be fetched
+    let head_branch = config
+        .get_remote_data(&remote_name)
+        .ok()
+        .and_then(|remote| {
+            let get_pr = forge::function!(api_type, get_pr);
+
+            get_pr(
+                &HttpClient::new(),
+                &remote,
+                args.api_url.as_deref(),
+                pr_number,
+                args.auth,
+            )
+            .inspect_err(|e| log::debug!("Failed to get the source branch of the PR: {e:#}"))
+            .ok()
+            .and_then(|pr| pr.head)
+            .map(|head| head.name)
+        });
+
+    track_pr_head(&branch_name, &pr_ref, &remote_name, head_branch.as_deref())
 }
 
 /// Creates a new pull request from the current branch.
@@ -464,7 +1480,8 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
             .get_string("pr/create/remote", None)
             .unwrap_or(DEFAULT_REMOTE.to_string())
     });
-    let remote = git::get_remote_data(&remote_name)
+    let remote = config
+        .get_remote_data(&remote_name)
         .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
 
     config::merge_config_into_args!(
@@ -480,14 +1497,20 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
             fill,
             fill_first,
             fill_verbose,
+            format,
             no_browser,
+            no_maintainer_edit,
+            no_precheck,
             no_push,
+            post_action,
+            remove_source_branch,
+            squash_on_merge,
             target
         ]
     );
 
     let current_branch = git::get_current_branch()?;
-    let target_branch = match args.target {
+    let target_branch = match args.target.take() {
         Some(target) => target,
         None => git::get_default_branch(&remote_name)
             .context("Could not determine the target branch for this PR")?,
@@ -501,16 +1524,47 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
     }
 
     let http_client = HttpClient::new();
-    let api_type = match args.api {
-        Some(api_type) => api_type,
-        None => forge::guess_api_type_from_host(&remote.host)
-            .with_context(|| format!("Failed to guess forge from host: {}", &remote.host))?,
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+
+    if !args.web {
+        ensure_pr_options_supported(api_type, &args)?;
+    }
+
+    if !args.web && !args.no_precheck {
+        forge::check_repo(
+            &http_client,
+            &api_type,
+            &remote,
+            args.api_url.as_deref(),
+            "pull requests",
+        )?;
+    }
+
+    let mut labels = if args.web {
+        args.labels
+    } else {
+        let missing_labels =
+            MissingLabels::from_flags(args.create_missing_labels, args.ignore_missing_labels)
+                .or_else(|| config.get_enum("pr/create/missing-labels", Some(&remote)))
+                .unwrap_or_default();
+
+        forge::resolve_labels(
+            &http_client,
+            &api_type,
+            &remote,
+            args.api_url.as_deref(),
+            &args.labels,
+            missing_labels,
+        )?
     };
 
     if !args.no_push {
         eprintln!("Pushing branch '{current_branch}'...");
 
-        git::push_branch(&current_branch, &remote_name, true)?;
+        git::push_branch(&current_branch, &remote_name, true).context(
+            "Aborting without creating the PR since the branch couldn't be pushed. \
+             Use --no-push if the branch already exists at the remote",
+        )?;
     }
 
     if args.web {
@@ -520,11 +1574,26 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
     let create_pr = forge::function!(api_type, create_pr);
 
     let (title, body) = if args.editor {
-        get_title_and_body_for_pr_for_editor_flag(
+        let (title, body) = get_title_and_body_for_pr_for_editor_flag(
             config
                 .get_string_from_global_scope("editor-command")
                 .as_deref(),
-        )?
+        )?;
+        let prompt_labels = !args.no_prompt
+            && config
+                .get_bool("pr/create/prompt-labels", Some(&remote))
+                .unwrap_or(true);
+
+        if labels.is_empty() && prompt_labels {
+            let get_labels = forge::function!(api_type, get_labels);
+
+            labels = io::prompt_labels(
+                || get_labels(&http_client, &remote, args.api_url.as_deref()),
+                "--labels or --no-prompt",
+            )?;
+        }
+
+        (title, body)
     } else if args.fill {
         let (generated_title, generated_body) =
             get_title_and_body_for_pr_for_fill_flag(&target_branch, &current_branch)?;
@@ -553,18 +1622,25 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
         (
             match args.title {
                 Some(t) => t,
-                None => Input::new().with_prompt("Enter PR title").interact_text()?,
+                None => io::prompt_text("Enter PR title", "--title")?,
             },
             args.body.unwrap_or_default(),
         )
     };
 
+    let supports_native_drafts = forge::function!(api_type, supports_native_drafts);
     let create_options = CreatePrOptions {
         title: &title,
         source_branch: &current_branch,
         target_branch: &target_branch,
         body: &body,
         draft: args.draft,
+        native_draft: args.draft
+            && supports_native_drafts(&http_client, &remote, args.api_url.as_deref()),
+        labels: &labels,
+        no_maintainer_edit: args.no_maintainer_edit,
+        remove_source_branch: args.remove_source_branch,
+        squash_on_merge: args.squash_on_merge,
     };
     let pr = create_pr(
         &http_client,
@@ -573,12 +1649,52 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
         &create_options,
     )?;
 
+    // So that `context` knows the PR of the branch without asking the forge
+    if let Err(e) = git::set_branch_pr(&current_branch, pr.id) {
+        log::warn!("{e:#}");
+    }
+
+    match args.format.unwrap_or_default() {
+        CreatedItemFormat::Url if args.no_browser => println!("{}", pr.url),
+        CreatedItemFormat::Url => {}
+        CreatedItemFormat::Json => println!("{}", serde_json::to_string_pretty(&pr)?),
+    }
+
     if args.no_browser {
-        println!("{}", pr.url);
-    } else {
-        eprintln!("Opening PR in browser: {}", pr.url);
+        return Ok(());
+    }
 
-        open::that(pr.url)?;
+    let post_action = match args.post_action {
+        Some(post_action) => post_action,
+        None => io::prompt_post_action(
+            &format!("Created PR #{}. What next?", pr.id),
+            &[
+                PrPostAction::Open,
+                PrPostAction::Copy,
+                PrPostAction::Checkout,
+                PrPostAction::Nothing,
+            ],
+            "--post-action or --no-browser",
+        )?,
+    };
+
+    match post_action {
+        PrPostAction::Open => {
+            eprintln!("Opening PR in browser: {}", pr.url);
+
+            open::that(pr.url)?;
+        }
+        PrPostAction::Copy => {
+            io::copy_to_clipboard(&pr.url)?;
+
+            eprintln!("Copied {} to the clipboard", pr.url);
+        }
+        PrPostAction::Checkout => {
+            let get_pr_ref = forge::function!(api_type, get_pr_ref);
+
+            fetch_and_checkout_pr(&get_pr_ref(pr.id), pr.id, &remote_name)?;
+        }
+        PrPostAction::Nothing => {}
     }
 
     Ok(())
@@ -588,68 +1704,413 @@ pub fn create_pr(mut args: PrCreateCommandArgs) -> anyhow::Result<()> {
 // Private Helpers
 // =============================================================================
 
-fn create_pr_in_browser(
-    api_type: &ApiType,
-    remote: &GitRemoteData,
-    target_branch: &str,
-    source_branch: &str,
-) -> anyhow::Result<()> {
-    let get_url_for_creation = forge::function!(api_type, get_url_for_pr_creation);
+fn format_pr_reviewers(reviewers: &PrReviewers) -> String {
+    if reviewers.reviewers.is_empty() {
+        return format!("PR #{} has no requested reviewers", reviewers.id);
+    }
 
-    let url = get_url_for_creation(remote, target_branch, source_branch);
+    format!(
+        "Requested reviewers of PR #{}: {}",
+        reviewers.id,
+        reviewers.reviewers.join(", ")
+    )
+}
 
-    eprintln!(
-        "Opening URL to create PR for {source_branch} targeting {target_branch} in browser..."
-    );
+fn parse_pr_reference(value: &str) -> Result<PrReference, String> {
+    if let Ok(number) = value.parse() {
+        return Ok(PrReference::Number(number));
+    }
 
-    open::that(&url)?;
+    parse_pr_url(value).map(PrReference::Url).ok_or_else(|| {
+        format!(
+            "'{value}' is neither a PR number nor the URL of a GitHub, GitLab or Gitea/Forgejo PR"
+        )
+    })
+}
 
-    eprintln!("Opened URL in browser: {url}");
+/// Parses the web URL of a PR, e.g. `https://github.com/o/r/pull/1`,
+/// `https://gitlab.com/g/sub/r/-/merge_requests/1` or
+/// `https://codeberg.org/o/r/pulls/1`. Anything after the number like
+/// `/files` is ignored.
+fn parse_pr_url(value: &str) -> Option<PrUrl> {
+    let url = Url::parse(value).ok()?;
 
-    Ok(())
-}
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
 
-fn get_title_and_body_for_pr_for_editor_flag(
-    editor_command: Option<&str>,
-) -> anyhow::Result<(String, String)> {
-    let message = match editor_command {
-        Some(cmd) => io::prompt_with_custom_text_editor(cmd),
-        None => io::prompt_with_default_text_editor(),
-    }?;
+    let segments: Vec<&str> = url.path_segments()?.collect();
+    let (index, number) = segments.windows(2).enumerate().find_map(|(i, pair)| {
+        matches!(pair[0], "pull" | "pulls" | "merge_requests")
+            .then(|| pair[1].parse().ok())
+            .flatten()
+            .map(|number| (i, number))
+    })?;
+    let repo_segments = match segments[..index].strip_suffix(&["-"]) {
+        Some(repo_segments) if segments[index] == "merge_requests" => repo_segments,
+        _ => &segments[..index],
+    };
 
-    if message.title.is_empty() {
-        anyhow::bail!("PR title cannot be empty. Please provide a title on the first line.");
+    if repo_segments.len() < 2 || repo_segments.contains(&"") {
+        return None;
     }
 
-    Ok((message.title, message.body))
+    Some(PrUrl {
+        host: url.host_str()?.to_string(),
+        repo_path: repo_segments.join("/"),
+        number,
+    })
 }
 
-fn get_title_and_body_for_pr_for_fill_flag(
-    target_branch: &str,
-    current_branch: &str,
-) -> anyhow::Result<(String, String)> {
-    let commit_shas = git::get_commit_range(target_branch, current_branch)
-        .context("Failed to get commits for PR")?;
-
-    if commit_shas.is_empty() {
+/// PR numbers are per repository, so a URL of another repository would check
+/// out an unrelated PR.
+fn ensure_pr_url_matches_remote(
+    pr_url: &PrUrl,
+    remote: &GitRemoteData,
+    remote_name: &str,
+) -> anyhow::Result<()> {
+    let web_host = Url::parse(&remote.web_base_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+    let is_same_host = pr_url.host.eq_ignore_ascii_case(&remote.host)
+        || web_host.is_some_and(|host| pr_url.host.eq_ignore_ascii_case(&host));
+    let repo_path = pr_url.repo_path.to_lowercase();
+    let remote_path = remote.path.to_lowercase();
+    // Forges may be served from a subpath, e.g. https://example.com/gitea
+    let is_same_repo = repo_path == remote_path || repo_path.ends_with(&format!("/{remote_path}"));
+
+    if !is_same_host || !is_same_repo {
         anyhow::bail!(
-            "No commits found between '{target_branch}' and '{current_branch}'. Cannot create PR with --fill.",
+            "The PR URL belongs to {}/{} but the remote '{remote_name}' is {}/{}. Use --remote to checkout the PR from another remote",
+            pr_url.host,
+            pr_url.repo_path,
+            remote.host,
+            remote.path
         );
     }
 
-    let title = current_branch.to_string();
-    let body = commit_shas
-        .iter()
-        .rev()
-        .map(|sha| {
-            git::get_commit_message(sha)
-                .context("Failed to get commit message")
-                .map(|(subject, _)| format!("- {subject}"))
-        })
-        .collect::<Result<Vec<_>, _>>()?
-        .join("\n");
+    Ok(())
+}
 
-    Ok((title, body))
+/// Gets the number of the open PR whose source branch is `branch`. If several
+/// PRs match, the user picks one of them.
+fn select_pr_for_branch(
+    remote: &GitRemoteData,
+    api_type: ApiType,
+    api_url: Option<&str>,
+    branch: &str,
+    use_auth: bool,
+) -> anyhow::Result<u32> {
+    let get_open_prs_for_branch = forge::function!(api_type, get_open_prs_for_branch);
+    let prs = get_open_prs_for_branch(&HttpClient::new(), remote, api_url, branch, use_auth)?;
+
+    match prs.as_slice() {
+        [] => anyhow::bail!("There is no open PR for the branch '{branch}'"),
+        [pr] => Ok(pr.id),
+        _ => {
+            let items: Vec<String> = prs
+                .iter()
+                .map(|pr| format!("#{} {} ({})", pr.id, pr.title, pr.author))
+                .collect();
+
+            match io::prompt_select(
+                &format!("Which PR of '{branch}'?"),
+                &items,
+                "the number of the PR",
+            )? {
+                Some(index) => Ok(prs[index].id),
+                None => anyhow::bail!(
+                    "There are several open PRs for the branch '{branch}':\n  {}\nCheckout one of them by its number",
+                    items.join("\n  ")
+                ),
+            }
+        }
+    }
+}
+
+/// Fetches the PR ref into the branch `pr-<NUMBER>`, checks it out and returns
+/// the name of the branch. The branch remembers the PR for `context`.
+fn fetch_and_checkout_pr(
+    pr_ref: &str,
+    pr_number: u32,
+    remote_name: &str,
+) -> anyhow::Result<String> {
+    let branch_name = format!("pr-{pr_number}");
+
+    eprintln!("Fetching {pr_ref}:{branch_name} from {remote_name}...");
+    git::fetch_pull_request(pr_ref, &branch_name, remote_name)?;
+
+    eprintln!("Checking out {branch_name}...");
+    git::checkout_branch(&branch_name)?;
+
+    if let Err(e) = git::set_branch_pr(&branch_name, pr_number) {
+        log::warn!("{e:#}");
+    }
+
+    eprintln!("Successfully checked out PR \"{pr_number}\" to branch \"{branch_name}\"");
+
+    Ok(branch_name)
+}
+
+/// Sets the upstream of the branch of a checked out PR, so that `git pull`
+/// gets the updates of the PR. The source branch is tracked if the remote has
+/// it at the commit of the PR. Otherwise, e.g. if it's in a fork, the PR ref
+/// is tracked.
+fn track_pr_head(
+    branch_name: &str,
+    pr_ref: &str,
+    remote_name: &str,
+    head_branch: Option<&str>,
+) -> anyhow::Result<()> {
+    if let Some(head_branch) = head_branch {
+        let remote_sha = git::get_remote_branch_sha(remote_name, head_branch)
+            .inspect_err(|e| log::debug!("{e:#}"))
+            .ok()
+            .flatten();
+
+        if remote_sha.is_some_and(|sha| git::rev_parse(branch_name).is_ok_and(|pr| pr == sha)) {
+            git::set_branch_tracking(
+                branch_name,
+                remote_name,
+                &format!("refs/heads/{head_branch}"),
+            )?;
+            eprintln!("Branch \"{branch_name}\" tracks {remote_name}/{head_branch}");
+
+            return Ok(());
+        }
+    }
+
+    git::set_branch_tracking(branch_name, remote_name, &format!("refs/{pr_ref}"))?;
+    eprintln!(
+        "Branch \"{branch_name}\" tracks {pr_ref} of {remote_name}. The source branch of the PR isn't on {remote_name}, e.g. since it's in a fork, so `git pull` gets the updates of the PR, but pushing can't update it"
+    );
+
+    Ok(())
+}
+
+fn render_timeline(timeline: &[PrTimelineEvent]) -> String {
+    timeline
+        .iter()
+        .map(|event| {
+            let action = match event.kind {
+                PrTimelineEventKind::Description => "opened the pull request",
+                PrTimelineEventKind::Comment => "commented",
+                PrTimelineEventKind::Approved => "approved",
+                PrTimelineEventKind::ChangesRequested => "requested changes",
+                PrTimelineEventKind::Reviewed => "reviewed",
+                PrTimelineEventKind::Dismissed => "reviewed (dismissed)",
+            };
+            let body = match (&event.kind, event.body.trim()) {
+                (PrTimelineEventKind::Description, "") => "No description provided.",
+                (_, body) => body,
+            };
+            let header = format!(
+                "## {} {action} ({})",
+                event.author,
+                io::format_timestamp(event.created_at)
+            );
+
+            match body {
+                "" => format!("{header}\n"),
+                body => format!("{header}\n\n{body}\n"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Only an enabled auto-merge is shown since it's the exception.
+/// Compares the current branch with the target branch of the PR if the
+/// current branch is its source branch. Problems with git only skip the
+/// comparison since it's an addition to the view.
+fn get_branch_status(pr: &Pr, remote_name: &str, fetch: bool) -> Option<PrBranchStatus> {
+    let current_branch = git::get_current_branch().ok()?;
+    let (Some(head), Some(base)) = (&pr.head, &pr.base) else {
+        return None;
+    };
+
+    if head.name != current_branch {
+        return None;
+    }
+
+    if fetch && let Err(e) = git::fetch_branch(remote_name, &base.name) {
+        log::warn!("Comparing with the last fetched target branch: {e:#}");
+    }
+
+    let target = format!("{remote_name}/{}", base.name);
+
+    match git::ahead_behind(&target, "HEAD") {
+        Ok((ahead, behind)) => Some(PrBranchStatus {
+            target,
+            ahead,
+            behind,
+        }),
+        Err(e) => {
+            log::warn!("Skipping the branch status: {e:#}");
+
+            None
+        }
+    }
+}
+
+fn render_branch_status(status: Option<&PrBranchStatus>) -> String {
+    let Some(status) = status else {
+        return String::new();
+    };
+    let hint = if status.behind > 0 {
+        format!(" — consider `git rebase {}`", status.target)
+    } else {
+        String::new()
+    };
+
+    format!(
+        "Branch: {} ahead, {} behind {}{hint}\n\n",
+        status.ahead, status.behind, status.target
+    )
+}
+
+fn render_auto_merge(auto_merge: Option<bool>) -> &'static str {
+    match auto_merge {
+        Some(true) => "Auto-merge: enabled (merged once the checks pass)\n\n",
+        Some(false) | None => "",
+    }
+}
+
+fn render_links(links: &PrLinks) -> String {
+    let closes = match links.closes.as_slice() {
+        [] => String::from("This pull request doesn't close any issues."),
+        closes => format!(
+            "Closes {}",
+            closes
+                .iter()
+                .map(|number| format!("#{number}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    format!("## Linked issues\n\n{closes}\n")
+}
+
+/// Finds the issues that a text closes with GitHub's closing keywords, e.g.
+/// "Fixes #12" or "closes: #34". Each reference needs its own keyword and
+/// references to other repositories (`owner/repo#12`) are ignored.
+/// https://docs.github.com/en/issues/tracking-your-work-with-issues/using-issues/linking-a-pull-request-to-an-issue
+fn parse_closing_issue_references(text: &str) -> Vec<u32> {
+    const KEYWORDS: [&str; 9] = [
+        "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+    ];
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    words
+        .windows(2)
+        .filter_map(|pair| {
+            let keyword = pair[0]
+                .trim_start_matches(|c: char| !c.is_alphanumeric())
+                .trim_end_matches(':')
+                .to_lowercase();
+
+            if !KEYWORDS.contains(&keyword.as_str()) {
+                return None;
+            }
+
+            let reference = pair[1].strip_prefix('#')?;
+            let digits_end = reference
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(reference.len());
+            let (digits, rest) = reference.split_at(digits_end);
+
+            if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                return None;
+            }
+
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+/// Fails if the forge doesn't support a forge-specific option of the new PR,
+/// so that it isn't dropped silently.
+fn ensure_pr_options_supported(
+    api_type: ApiType,
+    args: &PrCreateCommandArgs,
+) -> anyhow::Result<()> {
+    let options = [
+        (args.no_maintainer_edit, Capability::MaintainerEdit),
+        (args.remove_source_branch, Capability::RemoveSourceBranch),
+        (args.squash_on_merge, Capability::SquashOnMerge),
+    ];
+
+    for (_, capability) in options.into_iter().filter(|(is_set, _)| *is_set) {
+        forge::supports(api_type, capability).ensure()?;
+    }
+
+    Ok(())
+}
+
+fn create_pr_in_browser(
+    api_type: &ApiType,
+    remote: &GitRemoteData,
+    target_branch: &str,
+    source_branch: &str,
+) -> anyhow::Result<()> {
+    let get_url_for_creation = forge::function!(api_type, get_url_for_pr_creation);
+
+    let url = get_url_for_creation(remote, target_branch, source_branch);
+
+    eprintln!(
+        "Opening URL to create PR for {source_branch} targeting {target_branch} in browser..."
+    );
+
+    open::that(&url)?;
+
+    eprintln!("Opened URL in browser: {url}");
+
+    Ok(())
+}
+
+fn get_title_and_body_for_pr_for_editor_flag(
+    editor_command: Option<&str>,
+) -> anyhow::Result<(String, String)> {
+    let message = match editor_command {
+        Some(cmd) => io::prompt_with_custom_text_editor(cmd),
+        None => io::prompt_with_default_text_editor(),
+    }?;
+
+    if message.title.is_empty() {
+        anyhow::bail!("PR title cannot be empty. Please provide a title on the first line.");
+    }
+
+    Ok((message.title, message.body))
+}
+
+fn get_title_and_body_for_pr_for_fill_flag(
+    target_branch: &str,
+    current_branch: &str,
+) -> anyhow::Result<(String, String)> {
+    let commit_shas = git::get_commit_range(target_branch, current_branch)
+        .context("Failed to get commits for PR")?;
+
+    if commit_shas.is_empty() {
+        anyhow::bail!(
+            "No commits found between '{target_branch}' and '{current_branch}'. Cannot create PR with --fill.",
+        );
+    }
+
+    let title = current_branch.to_string();
+    let body = commit_shas
+        .iter()
+        .rev()
+        .map(|sha| {
+            git::get_commit_message(sha)
+                .context("Failed to get commit message")
+                .map(|(subject, _)| format!("- {subject}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .join("\n");
+
+    Ok((title, body))
 }
 
 fn get_title_and_body_for_pr_for_fill_first_flag(
@@ -717,30 +2178,417 @@ fn list_prs_in_web_browser(remote: &GitRemoteData, api_type: &ApiType) -> anyhow
 }
 
 fn list_prs_to_stdout(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: PrListCommandArgs,
+    bot_authors: &[String],
+) -> anyhow::Result<()> {
+    let mut prs = get_prs_with_fields(
+        &HttpClient::new(),
+        remote,
+        api_type,
+        args.api_url.as_deref(),
+        &ListPrsFilters {
+            author: args.author.as_deref(),
+            labels: &LabelFilter::parse(&args.labels),
+            milestone: args.milestone.as_deref(),
+            page: args.page,
+            per_page: io::get_per_page(args.per_page, args.limit, DEFAULT_PER_PAGE),
+            query: args.query.as_deref(),
+            state: &args.state.unwrap_or_default(),
+            draft: args.draft,
+            review: args.review.as_ref(),
+            all_pages: args.all_pages,
+            limit: args.limit,
+        },
+        &args.fields,
+        args.auth,
+    )?;
+
+    retain_by_bot_filter(&mut prs, args.bot, args.no_bot, bot_authors);
+    sort_prs(&mut prs, args.sort.as_ref());
+
+    if args.ids_only {
+        io::print_ids(prs.iter().map(|pr| pr.id));
+    } else if !prs.is_empty() {
+        io::print_list_with_styles(
+            &prs,
+            &args.fields,
+            &args.format.unwrap_or_default(),
+            Truncation {
+                fit_to_terminal: !args.no_truncate,
+                max_title_width: None,
+            },
+            |pr| is_bot_author(&pr.author, bot_authors),
+            FIELD_STYLES,
+        )?;
+    }
+
+    io::check_list_assertions(prs.len(), args.fail_if_any, args.fail_if_none)
+}
+
+fn watch_prs(
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    args: PrListCommandArgs,
+    bot_authors: &[String],
+) -> anyhow::Result<()> {
+    let output_format = args.format.unwrap_or_default();
+
+    if matches!(output_format, OutputFormat::Json) {
+        anyhow::bail!("--watch doesn't support the JSON output format");
+    }
+
+    let http_client = HttpClient::new();
+    let fields = args.fields;
+    let state = args.state.unwrap_or_default();
+    let labels = LabelFilter::parse(&args.labels);
+    let filters = ListPrsFilters {
+        author: args.author.as_deref(),
+        labels: &labels,
+        milestone: args.milestone.as_deref(),
+        page: args.page,
+        per_page: io::get_per_page(args.per_page, args.limit, DEFAULT_PER_PAGE),
+        query: args.query.as_deref(),
+        state: &state,
+        draft: args.draft,
+        review: args.review.as_ref(),
+        all_pages: false,
+        limit: args.limit,
+    };
+
+    watch::watch(Duration::from_secs(args.interval), || {
+        let mut prs = get_prs_with_fields(
+            &http_client,
+            remote,
+            api_type,
+            args.api_url.as_deref(),
+            &filters,
+            &fields,
+            args.auth,
+        )?;
+
+        retain_by_bot_filter(&mut prs, args.bot, args.no_bot, bot_authors);
+
+        prs.iter()
+            .map(|pr| {
+                Ok(WatchedRow {
+                    id: pr.id,
+                    line: io::format(&[pr], &fields, &output_format)?,
+                })
+            })
+            .collect()
+    })
+}
+
+/// Bot accounts on GitHub, e.g. `dependabot[bot]`, have this suffix. Bots on
+/// other forges are regular accounts that need to be configured.
+const BOT_AUTHOR_SUFFIX: &str = "[bot]";
+
+fn is_bot_author(author: &str, bot_authors: &[String]) -> bool {
+    author.ends_with(BOT_AUTHOR_SUFFIX)
+        || bot_authors
+            .iter()
+            .any(|bot_author| bot_author.eq_ignore_ascii_case(author))
+}
+
+/// Keeps only the PRs of bots with `--bot` or only the PRs of humans with
+/// `--no-bot`.
+fn retain_by_bot_filter(prs: &mut Vec<Pr>, bot: bool, no_bot: bool, bot_authors: &[String]) {
+    if bot || no_bot {
+        prs.retain(|pr| is_bot_author(&pr.author, bot_authors) == bot);
+    }
+}
+
+/// Sorts the PRs for `--sort`, newest first. PRs without the timestamp come
+/// last.
+fn sort_prs(prs: &mut [Pr], sort: Option<&PrSort>) {
+    let Some(sort) = sort else {
+        return;
+    };
+
+    prs.sort_by_key(|pr| {
+        Reverse(match sort {
+            // GitLab doesn't set `closed_at` for merged MRs.
+            PrSort::Closed => pr.closed_at.or(pr.merged_at),
+            PrSort::Merged => pr.merged_at,
+        })
+    });
+}
+
+/// Fetches the PRs and the data for the requested fields which aren't part of
+/// the PR listing of the forge APIs.
+fn get_prs_with_fields(
+    http_client: &HttpClient,
     remote: &GitRemoteData,
     api_type: &ApiType,
     api_url: Option<&str>,
     filters: &ListPrsFilters,
-    fields: Vec<PrField>,
-    output_format: &OutputFormat,
+    fields: &[PrField],
     use_auth: bool,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Vec<Pr>> {
     let get_prs = forge::function!(api_type, get_prs);
-    let response = get_prs(&HttpClient::new(), remote, api_url, filters, use_auth)?;
+    let mut prs = vec![];
 
-    let fields = if fields.is_empty() {
-        vec![PrField::Title, PrField::Id, PrField::Url]
-    } else {
-        fields
-    };
+    for page in filters.page.. {
+        let response = get_prs(
+            http_client,
+            remote,
+            api_url,
+            &ListPrsFilters { page, ..*filters },
+            use_auth,
+        )?;
+
+        prs.extend(response.items);
+
+        if !io::should_fetch_next_page(
+            prs.len(),
+            response.has_next_page,
+            filters.all_pages,
+            filters.limit,
+        ) {
+            break;
+        }
+    }
 
-    if !response.items.is_empty() {
-        println!("{}", io::format(&response.items, &fields, output_format)?);
+    if let Some(limit) = filters.limit {
+        prs.truncate(limit as usize);
+    }
+
+    add_fields(
+        http_client,
+        remote,
+        api_type,
+        api_url,
+        &mut prs,
+        fields,
+        use_auth,
+    )?;
+
+    if let Some(review) = filters.review {
+        // The review filter needs the review status even if it isn't printed
+        if !fields.iter().any(|f| matches!(f, PrField::ReviewStatus)) {
+            add_review_status(http_client, remote, api_type, api_url, &mut prs, use_auth)?;
+        }
+
+        prs.retain(|pr| pr.review_status.as_ref() == Some(review));
+    }
+
+    Ok(prs)
+}
+
+/// Fetches the data for the requested fields which isn't part of the PRs that
+/// the forge APIs return.
+fn add_fields(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    prs: &mut [Pr],
+    fields: &[PrField],
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    // Approvals need an extra request per PR, so only fetch them on request.
+    if fields
+        .iter()
+        .any(|f| matches!(f, PrField::ApprovalsRequired | PrField::ApprovalsLeft))
+    {
+        add_approvals(http_client, remote, api_type, api_url, prs, use_auth)?;
+    }
+
+    // Same for the review status
+    if fields.iter().any(|f| matches!(f, PrField::ReviewStatus)) {
+        add_review_status(http_client, remote, api_type, api_url, prs, use_auth)?;
+    }
+
+    if fields
+        .iter()
+        .any(|f| matches!(f, PrField::Mergeable | PrField::Ci))
+    {
+        add_merge_info(http_client, remote, api_type, api_url, prs, use_auth)?;
+    }
+
+    // Some forges list the comment count but no forge lists the other counts
+    if fields.iter().any(|f| {
+        matches!(
+            f,
+            PrField::Comments | PrField::Commits | PrField::ChangedFiles
+        )
+    }) {
+        add_counts(
+            http_client,
+            remote,
+            api_type,
+            api_url,
+            prs,
+            fields,
+            use_auth,
+        )?;
     }
 
     Ok(())
 }
 
+fn add_approvals(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    prs: &mut [Pr],
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    let get_pr_approvals = forge::function!(api_type, get_pr_approvals);
+    let approvals = fetch_for_each_pr(prs, |pr| {
+        get_pr_approvals(http_client, remote, api_url, pr.id, use_auth)
+            .with_context(|| format!("Failed to fetch approvals for PR {}", pr.id))
+    })?;
+
+    for (pr, approvals) in prs.iter_mut().zip(approvals) {
+        if let Some(approvals) = approvals {
+            pr.approvals_required = Some(approvals.required);
+            pr.approvals_left = Some(approvals.left);
+        }
+    }
+
+    Ok(())
+}
+
+fn add_review_status(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    prs: &mut [Pr],
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    let get_pr_review_statuses = forge::function!(api_type, get_pr_review_statuses);
+    let get_pr_review_status = forge::function!(api_type, get_pr_review_status);
+    let pr_numbers: Vec<u32> = prs.iter().map(|pr| pr.id).collect();
+    let review_statuses =
+        match get_pr_review_statuses(http_client, remote, api_url, &pr_numbers, use_auth)
+            .context("Failed to fetch review statuses")?
+        {
+            Some(review_statuses) => review_statuses,
+            None => fetch_for_each_pr(prs, |pr| {
+                get_pr_review_status(http_client, remote, api_url, pr.id, use_auth)
+                    .with_context(|| format!("Failed to fetch review status for PR {}", pr.id))
+            })?,
+        };
+
+    for (pr, review_status) in prs.iter_mut().zip(review_statuses) {
+        pr.review_status = Some(review_status);
+    }
+
+    Ok(())
+}
+
+fn add_merge_info(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    prs: &mut [Pr],
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    let get_pr_merge_info = forge::function!(api_type, get_pr_merge_info);
+    let merge_infos = fetch_for_each_pr(prs, |pr| {
+        get_pr_merge_info(http_client, remote, api_url, pr.id, use_auth)
+            .with_context(|| format!("Failed to fetch merge info for PR {}", pr.id))
+    })?;
+
+    for (pr, merge_info) in prs.iter_mut().zip(merge_infos) {
+        pr.mergeable = Some(merge_info.mergeable);
+        pr.ci = Some(merge_info.ci);
+    }
+
+    Ok(())
+}
+
+/// Adds the requested counts to the PRs that lack any of them. The PRs that
+/// the listing already brought all requested counts for are skipped.
+fn add_counts(
+    http_client: &HttpClient,
+    remote: &GitRemoteData,
+    api_type: &ApiType,
+    api_url: Option<&str>,
+    prs: &mut [Pr],
+    fields: &[PrField],
+    use_auth: bool,
+) -> anyhow::Result<()> {
+    let get_pr_counts = forge::function!(api_type, get_pr_counts);
+    let pr_ids = prs
+        .iter()
+        .filter(|pr| is_missing_counts(pr, fields))
+        .map(|pr| pr.id)
+        .collect::<Vec<_>>();
+
+    if pr_ids.is_empty() {
+        return Ok(());
+    }
+
+    log::info!(
+        "Fetching the counts of {} PRs with at least one extra request per PR",
+        pr_ids.len()
+    );
+
+    let counts = fetch_for_each_pr(&pr_ids, |pr_id| {
+        get_pr_counts(http_client, remote, api_url, *pr_id, use_auth)
+            .with_context(|| format!("Failed to fetch counts for PR {pr_id}"))
+    })?;
+    let mut counts = pr_ids.into_iter().zip(counts).collect::<HashMap<_, _>>();
+
+    for pr in prs.iter_mut() {
+        if let Some(counts) = counts.remove(&pr.id) {
+            pr.comments = pr.comments.or(counts.comments);
+            pr.commits = counts.commits;
+            pr.changed_files = counts.changed_files;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_missing_counts(pr: &Pr, fields: &[PrField]) -> bool {
+    fields.iter().any(|field| match field {
+        PrField::Comments => pr.comments.is_none(),
+        PrField::Commits => pr.commits.is_none(),
+        PrField::ChangedFiles => pr.changed_files.is_none(),
+        _ => false,
+    })
+}
+
+/// Runs `fetch` for every item, e.g. a PR, with a few requests in flight at
+/// once. The results are in the same order as the items.
+fn fetch_for_each_pr<P: Sync, T: Send>(
+    prs: &[P],
+    fetch: impl Fn(&P) -> anyhow::Result<T> + Sync,
+) -> anyhow::Result<Vec<T>> {
+    let fetch = &fetch;
+    let mut results = Vec::with_capacity(prs.len());
+
+    for chunk in prs.chunks(MAX_CONCURRENT_REQUESTS) {
+        let chunk_results: Vec<anyhow::Result<T>> = std::thread::scope(|scope| {
+            chunk
+                .iter()
+                .map(|pr| scope.spawn(move || fetch(pr)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+
+        for result in chunk_results {
+            results.push(result?);
+        }
+    }
+
+    Ok(results)
+}
+
 fn list_prs_interactively(
     remote: GitRemoteData,
     api_type: ApiType,
@@ -750,6 +2598,7 @@ fn list_prs_interactively(
         "author": args.author,
         "draft": args.draft,
         "labels": args.labels,
+        "milestone": args.milestone,
         "query": args.query,
         "state": args.state,
     );
@@ -761,16 +2610,13 @@ fn list_prs_interactively(
         api_type,
         args.api_url,
         fetch_options,
-        args.per_page.unwrap_or(DEFAULT_PER_PAGE),
+        args.per_page,
+        args.limit,
         args.auth,
     )?;
 
     let output_format = args.format.unwrap_or_default();
-    let fields = if args.fields.is_empty() {
-        vec![PrField::Title, PrField::Id, PrField::Url]
-    } else {
-        args.fields
-    };
+    let fields = args.fields;
 
     println!("{}", io::format(&[&pr], &fields, &output_format)?);
 
@@ -781,43 +2627,495 @@ fn list_prs_interactively(
     Ok(())
 }
 
+/// Shows the TUI to select a PR. Without `per_page`, the TUI picks a page size
+/// that fills the terminal. The TUI stops loading more pages once it has
+/// loaded `limit` PRs.
 fn select_pr_interactively(
     remote: GitRemoteData,
     api_type: ApiType,
-    api_url: Option<String>,
+    api_url: Option<ApiUrl>,
     initial_options: FetchOptions,
-    per_page: u32,
+    per_page: Option<u32>,
+    limit: Option<u32>,
     use_auth: bool,
 ) -> anyhow::Result<Pr> {
     let get_prs = forge::function!(api_type, get_prs);
 
     let http_client = HttpClient::new();
 
-    tui::select_item_with(initial_options, move |page, options, result| {
-        let author: Option<&str> = options.parse_str("author");
-        let draft: bool = options.parse("draft").unwrap_or_default();
-        let labels: Vec<String> = options.parse_list("labels").unwrap_or_default();
-        let query: Option<&str> = options.parse_str("query");
-        let state: PrState = options.parse_enum("state").unwrap_or_default();
+    let option_specs = vec![
+        FetchOptionSpec::new("author"),
+        FetchOptionSpec::flag("draft"),
+        FetchOptionSpec::new("labels"),
+        FetchOptionSpec::new("milestone"),
+        FetchOptionSpec::with_enum_values::<PrState>("state"),
+    ];
+
+    tui::select_item_with(
+        initial_options,
+        option_specs,
+        move |page, options, result| {
+            let author: Option<&str> = options.parse_str("author");
+            let draft: bool = options.parse("draft").unwrap_or_default();
+            let labels = LabelFilter::parse(&options.parse_list("labels").unwrap_or_default());
+            let milestone: Option<&str> = options.parse_str("milestone");
+            let query: Option<&str> = options.parse_str("query");
+            let state: PrState = options.parse_enum("state").unwrap_or_default();
+            let per_page = per_page.unwrap_or(result.page_size());
+
+            let response = get_prs(
+                &http_client,
+                &remote,
+                api_url.as_deref(),
+                &ListPrsFilters {
+                    author,
+                    draft,
+                    labels: &labels,
+                    milestone,
+                    page,
+                    per_page,
+                    query,
+                    state: &state,
+                    review: None,
+                    all_pages: false,
+                    limit: None,
+                },
+                use_auth,
+            )?;
 
-        let response = get_prs(
-            &http_client,
-            &remote,
-            api_url.as_deref(),
-            &ListPrsFilters {
-                author,
-                draft,
-                labels: &labels,
-                page,
-                per_page,
-                query,
-                state: &state,
-            },
-            use_auth,
-        )?;
+            Ok(result.with_items(response.items).with_more_items(
+                response.has_next_page && io::is_below_limit(page, per_page, limit),
+            ))
+        },
+    )
+}
 
-        Ok(result
-            .with_items(response.items)
-            .with_more_items(response.has_next_page))
-    })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: PrTimelineEventKind, created_at: &str, body: &str) -> PrTimelineEvent {
+        PrTimelineEvent {
+            kind,
+            author: "alice".to_string(),
+            created_at: Timestamp::parse(created_at).unwrap(),
+            body: body.to_string(),
+        }
+    }
+
+    fn pr(id: u32, closed_at: Option<&str>, merged_at: Option<&str>) -> Pr {
+        Pr {
+            id,
+            title: format!("PR {id}"),
+            state: String::from("open"),
+            author: String::from("alice"),
+            url: format!("https://example.com/pulls/{id}"),
+            labels: vec![],
+            milestone: None,
+            created_at: Timestamp::parse("2024-05-01T10:00:00Z").unwrap(),
+            updated_at: Timestamp::parse("2024-05-01T10:00:00Z").unwrap(),
+            merged_at: merged_at.and_then(Timestamp::parse),
+            closed_at: closed_at.and_then(Timestamp::parse),
+            head: None,
+            base: None,
+            draft: false,
+            approvals_required: None,
+            approvals_left: None,
+            review_status: None,
+            mergeable: None,
+            ci: None,
+            comments: None,
+            commits: None,
+            changed_files: None,
+        }
+    }
+
+    fn pr_ids(prs: &[Pr]) -> Vec<u32> {
+        prs.iter().map(|pr| pr.id).collect()
+    }
+
+    #[test]
+    fn test_sort_prs_newest_first() {
+        let mut prs = vec![
+            pr(1, None, None),
+            pr(
+                2,
+                Some("2024-05-01T10:00:00Z"),
+                Some("2024-05-01T10:00:00Z"),
+            ),
+            // Closed earlier once the offset is taken into account
+            pr(3, Some("2024-05-01T11:00:00+02:00"), None),
+            // GitLab doesn't set `closed_at` for merged MRs
+            pr(4, None, Some("2024-05-02T10:00:00Z")),
+        ];
+
+        sort_prs(&mut prs, None);
+        assert_eq!(pr_ids(&prs), [1, 2, 3, 4]);
+
+        sort_prs(&mut prs, Some(&PrSort::Closed));
+        assert_eq!(pr_ids(&prs), [4, 2, 3, 1]);
+
+        sort_prs(&mut prs, Some(&PrSort::Merged));
+        assert_eq!(pr_ids(&prs), [4, 2, 3, 1]);
+
+        prs[3].merged_at = Timestamp::parse("2024-05-03T10:00:00Z");
+        sort_prs(&mut prs, Some(&PrSort::Merged));
+        assert_eq!(pr_ids(&prs), [1, 4, 2, 3]);
+    }
+
+    #[test]
+    fn test_build_timeline_sorts_chronologically() {
+        let events = vec![
+            event(PrTimelineEventKind::Description, "2024-05-01T10:00:00Z", ""),
+            event(
+                PrTimelineEventKind::Comment,
+                "2024-05-02T10:00:00Z",
+                "later",
+            ),
+            // Same day but earlier once the offset is taken into account
+            event(
+                PrTimelineEventKind::Approved,
+                "2024-05-02T11:00:00+02:00",
+                "",
+            ),
+            event(
+                PrTimelineEventKind::Comment,
+                "2024-05-01T12:00:00Z",
+                "first",
+            ),
+        ];
+
+        let timeline = build_timeline(events);
+
+        assert_eq!(
+            timeline.iter().map(|e| &e.kind).collect::<Vec<_>>(),
+            [
+                &PrTimelineEventKind::Description,
+                &PrTimelineEventKind::Comment,
+                &PrTimelineEventKind::Approved,
+                &PrTimelineEventKind::Comment,
+            ]
+        );
+        assert_eq!(timeline[1].body, "first");
+    }
+
+    #[test]
+    fn test_build_timeline_drops_empty_reviews_without_verdict() {
+        let events = vec![
+            event(
+                PrTimelineEventKind::Description,
+                "2024-05-01T10:00:00Z",
+                "desc",
+            ),
+            event(PrTimelineEventKind::Reviewed, "2024-05-01T11:00:00Z", " "),
+            event(PrTimelineEventKind::Reviewed, "2024-05-01T12:00:00Z", "nit"),
+            event(PrTimelineEventKind::Approved, "2024-05-01T13:00:00Z", ""),
+        ];
+
+        let timeline = build_timeline(events);
+
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline[1].body, "nit");
+    }
+
+    #[test]
+    fn test_ci_status_combines_to_most_severe() {
+        let combined = [
+            PrCiStatus::Success,
+            PrCiStatus::Failure,
+            PrCiStatus::Pending,
+        ]
+        .into_iter()
+        .max();
+
+        assert_eq!(combined, Some(PrCiStatus::Failure));
+        assert!(PrCiStatus::None < PrCiStatus::Success);
+        assert!(PrCiStatus::Success < PrCiStatus::Pending);
+    }
+
+    fn serialize_to_string(value: impl Serialize) -> String {
+        serde_json::to_value(value)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_review_status_color_matches_the_serialized_status() {
+        let color = |status| PrReviewStatus::color(&serialize_to_string(status));
+
+        assert_eq!(color(PrReviewStatus::Approved), Some(CellColor::Green));
+        assert_eq!(
+            color(PrReviewStatus::ChangesRequested),
+            Some(CellColor::Red)
+        );
+        assert_eq!(
+            color(PrReviewStatus::ReviewRequired),
+            Some(CellColor::Yellow)
+        );
+        assert_eq!(color(PrReviewStatus::None), None);
+    }
+
+    #[test]
+    fn test_symbols_match_the_serialized_statuses() {
+        let mergeable = |mergeable| PrMergeable::symbol(&serialize_to_string(mergeable));
+        let ci = |ci| PrCiStatus::symbol(&serialize_to_string(ci));
+
+        assert_eq!(mergeable(PrMergeable::Mergeable), Some("✓"));
+        assert_eq!(mergeable(PrMergeable::Conflicting), Some("✗"));
+        assert_eq!(mergeable(PrMergeable::Unknown), Some("…"));
+        assert_eq!(ci(PrCiStatus::Success), Some("✓"));
+        assert_eq!(ci(PrCiStatus::Failure), Some("✗"));
+        assert_eq!(ci(PrCiStatus::Pending), Some("…"));
+        assert_eq!(ci(PrCiStatus::None), None);
+    }
+
+    #[test]
+    fn test_review_status_without_reviews() {
+        assert_eq!(
+            PrReviewStatus::from_reviews([], false),
+            PrReviewStatus::None
+        );
+        assert_eq!(
+            PrReviewStatus::from_reviews([], true),
+            PrReviewStatus::ReviewRequired
+        );
+    }
+
+    #[test]
+    fn test_review_status_changes_requested_wins() {
+        let reviews = [
+            ("alice", PrReviewStatus::Approved),
+            ("bob", PrReviewStatus::ChangesRequested),
+        ];
+
+        assert_eq!(
+            PrReviewStatus::from_reviews(reviews, false),
+            PrReviewStatus::ChangesRequested
+        );
+    }
+
+    #[test]
+    fn test_review_status_uses_latest_review_per_reviewer() {
+        let reviews = [
+            ("bob", PrReviewStatus::ChangesRequested),
+            ("alice", PrReviewStatus::Approved),
+            ("bob", PrReviewStatus::Approved),
+        ];
+
+        assert_eq!(
+            PrReviewStatus::from_reviews(reviews, true),
+            PrReviewStatus::Approved
+        );
+    }
+
+    #[test]
+    fn test_review_status_re_requested_review() {
+        let reviews = [
+            ("bob", PrReviewStatus::Approved),
+            ("bob", PrReviewStatus::ReviewRequired),
+        ];
+
+        assert_eq!(
+            PrReviewStatus::from_reviews(reviews, false),
+            PrReviewStatus::ReviewRequired
+        );
+    }
+
+    #[test]
+    fn test_render_auto_merge_only_if_enabled() {
+        assert_eq!(
+            render_auto_merge(Some(true)),
+            "Auto-merge: enabled (merged once the checks pass)\n\n"
+        );
+        assert_eq!(render_auto_merge(Some(false)), "");
+        assert_eq!(render_auto_merge(None), "");
+    }
+
+    #[test]
+    fn test_render_branch_status() {
+        let mut status = PrBranchStatus {
+            target: "origin/main".to_string(),
+            ahead: 2,
+            behind: 5,
+        };
+
+        assert_eq!(
+            render_branch_status(Some(&status)),
+            "Branch: 2 ahead, 5 behind origin/main — consider `git rebase origin/main`\n\n"
+        );
+
+        status.behind = 0;
+
+        assert_eq!(
+            render_branch_status(Some(&status)),
+            "Branch: 2 ahead, 0 behind origin/main\n\n"
+        );
+        assert_eq!(render_branch_status(None), "");
+    }
+
+    #[test]
+    fn test_parse_closing_issue_references_keywords() {
+        let text = "Fixes #1\ncloses #2. Resolved: #3 and FIX #4\n(resolves #5)";
+
+        assert_eq!(parse_closing_issue_references(text), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_parse_closing_issue_references_ignores_non_closing_references() {
+        let text = "See #1, fixes owner/repo#2, prefixes #3, fixes #4a, fixes #, fixes\n\n#5";
+
+        assert_eq!(parse_closing_issue_references(text), [5]);
+        assert_eq!(parse_closing_issue_references("Fixes #6, #7"), [6]);
+        assert!(parse_closing_issue_references("").is_empty());
+    }
+
+    #[test]
+    fn test_view_fields_are_validated() {
+        use clap::Parser;
+
+        use crate::cli::Cli;
+
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(["git-forge", "pr", "view", "1"].iter().chain(args))
+                .err()
+                .map(|e| e.kind())
+        };
+
+        assert_eq!(parse(&["--field", "url", "-f", "labels", "--copy"]), None);
+        assert_eq!(
+            parse(&["--field", "nope"]),
+            Some(clap::error::ErrorKind::InvalidValue)
+        );
+        assert_eq!(
+            parse(&["--field", "url", "--format", "json"]),
+            Some(clap::error::ErrorKind::ArgumentConflict)
+        );
+        assert_eq!(
+            parse(&["--copy"]),
+            Some(clap::error::ErrorKind::MissingRequiredArgument)
+        );
+    }
+
+    #[test]
+    fn test_is_missing_counts() {
+        let pr = Pr {
+            comments: Some(3),
+            ..pr(1, None, None)
+        };
+
+        assert!(!is_missing_counts(&pr, &[PrField::Id, PrField::Comments]));
+        assert!(is_missing_counts(
+            &pr,
+            &[PrField::Comments, PrField::Commits]
+        ));
+        assert!(is_missing_counts(&pr, &[PrField::ChangedFiles]));
+    }
+
+    #[test]
+    fn test_is_bot_author() {
+        let bot_authors = vec![String::from("renovate"), String::from("Forgejo-Bot")];
+
+        assert!(is_bot_author("dependabot[bot]", &[]));
+        assert!(is_bot_author("github-actions[bot]", &bot_authors));
+        assert!(is_bot_author("renovate", &bot_authors));
+        assert!(is_bot_author("forgejo-bot", &bot_authors));
+
+        assert!(!is_bot_author("alice", &bot_authors));
+        assert!(!is_bot_author("robot", &[]));
+        assert!(!is_bot_author("renovate", &[]));
+        assert!(!is_bot_author("[bot]alice", &[]));
+    }
+
+    fn pr_url(host: &str, repo_path: &str, number: u32) -> Option<PrUrl> {
+        Some(PrUrl {
+            host: host.to_string(),
+            repo_path: repo_path.to_string(),
+            number,
+        })
+    }
+
+    #[test]
+    fn test_parse_pr_url_github() {
+        assert_eq!(
+            parse_pr_url("https://github.com/o/r/pull/123"),
+            pr_url("github.com", "o/r", 123)
+        );
+        assert_eq!(
+            parse_pr_url("https://github.com/o/r/pull/123/files#diff-1"),
+            pr_url("github.com", "o/r", 123)
+        );
+        // A repository named like a marker
+        assert_eq!(
+            parse_pr_url("https://github.com/o/pull/pull/7"),
+            pr_url("github.com", "o/pull", 7)
+        );
+    }
+
+    #[test]
+    fn test_parse_pr_url_gitlab() {
+        assert_eq!(
+            parse_pr_url("https://gitlab.com/group/sub/r/-/merge_requests/5"),
+            pr_url("gitlab.com", "group/sub/r", 5)
+        );
+        assert_eq!(
+            parse_pr_url("http://git.internal:8080/g/r/-/merge_requests/5/diffs?view=inline"),
+            pr_url("git.internal", "g/r", 5)
+        );
+        assert_eq!(
+            parse_pr_url("https://gitlab.com/g/r/merge_requests/5"),
+            pr_url("gitlab.com", "g/r", 5)
+        );
+    }
+
+    #[test]
+    fn test_parse_pr_url_gitea() {
+        assert_eq!(
+            parse_pr_url("https://codeberg.org/o/r/pulls/42"),
+            pr_url("codeberg.org", "o/r", 42)
+        );
+        assert_eq!(
+            parse_pr_url("https://example.com/gitea/o/r/pulls/42/files"),
+            pr_url("example.com", "gitea/o/r", 42)
+        );
+    }
+
+    #[test]
+    fn test_parse_pr_url_invalid() {
+        assert_eq!(parse_pr_url("https://github.com/o/r/issues/1"), None);
+        assert_eq!(parse_pr_url("https://github.com/o/r/pulls"), None);
+        assert_eq!(parse_pr_url("https://github.com/r/pull/1"), None);
+        assert_eq!(parse_pr_url("https://github.com/o/r/pull/abc"), None);
+        assert_eq!(parse_pr_url("ssh://github.com/o/r/pull/1"), None);
+        assert_eq!(parse_pr_url("feature/login"), None);
+    }
+
+    #[test]
+    fn test_parse_pr_reference() {
+        assert_eq!(parse_pr_reference("42"), Ok(PrReference::Number(42)));
+        assert_eq!(
+            parse_pr_reference("https://github.com/o/r/pull/42"),
+            Ok(PrReference::Url(pr_url("github.com", "o/r", 42).unwrap()))
+        );
+        assert!(parse_pr_reference("-1").is_err());
+    }
+
+    #[test]
+    fn test_ensure_pr_url_matches_remote() {
+        let remote = GitRemoteData {
+            host: String::from("gitlab.com"),
+            path: String::from("Group/r"),
+            port: None,
+            web_base_url: String::from("https://gitlab.com"),
+            api_base_url: None,
+        };
+        let matches = |url: &str| {
+            ensure_pr_url_matches_remote(&parse_pr_url(url).unwrap(), &remote, "origin").is_ok()
+        };
+
+        assert!(matches("https://gitlab.com/group/r/-/merge_requests/1"));
+        assert!(!matches("https://gitlab.com/fork/r/-/merge_requests/1"));
+        assert!(!matches("https://github.com/group/r/pull/1"));
+    }
 }
@@ -0,0 +1,892 @@
+//! The `repo` subcommand.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use clap::{Args, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::{
+        config::{self, Config},
+        forge::{self, ApiType, ApiUrl, HttpClient, forgejo, gitea, github, gitlab},
+    },
+    date::Timestamp,
+    git::{self, GitRemoteData},
+    io::{self, OutputFormat, ResultFormat, Truncation},
+    tui::{self, FetchOptions, ListableItem, SortKey, SortValue},
+};
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+const DEFAULT_REMOTE: &str = "origin";
+const DEFAULT_PER_PAGE: u32 = 30;
+/// GitHub's limits for topics. They are applied to all forges, so that the
+/// same topics work everywhere.
+const MAX_TOPIC_LENGTH: usize = 50;
+const MAX_TOPICS: usize = 20;
+
+/// Command-line arguments for the `repo` subcommand.
+#[derive(Args)]
+pub struct RepoCommandArgs {
+    #[command(subcommand)]
+    pub subcommand: RepoCommand,
+}
+
+/// Available subcommands for repository operations.
+#[derive(Subcommand)]
+pub enum RepoCommand {
+    /// Change the description, topics or default branch of the repository and
+    /// print the changes.
+    Edit(RepoEditCommandArgs),
+
+    /// List the repositories of an organization or user, e.g. to find the
+    /// archived ones or to pick one to clone.
+    #[command(alias = "ls")]
+    List(RepoListCommandArgs),
+
+    /// Print the metadata of the repository, e.g. its topics and languages.
+    View(RepoViewCommandArgs),
+}
+
+/// Command-line arguments for editing a repository.
+#[derive(Args)]
+pub struct RepoEditCommandArgs {
+    /// Add these topics (comma-separated). Topics are lowercased and may only
+    /// contain letters, numbers and hyphens
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "TOPICS",
+        value_parser = parse_topic,
+        required_unless_present_any = ["default_branch", "description", "remove_topic"]
+    )]
+    add_topic: Vec<String>,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Change the default branch. The branch has to exist on the forge
+    #[arg(long, value_name = "BRANCH")]
+    default_branch: Option<String>,
+
+    /// Change the description. An empty text removes it
+    #[arg(long, value_name = "TEXT")]
+    description: Option<String>,
+
+    /// Print the changes without saving them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Output format of the changes [default: text]
+    #[arg(short = 'o', long)]
+    format: Option<ResultFormat>,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Remove these topics (comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "TOPICS", value_parser = parse_topic)]
+    remove_topic: Vec<String>,
+}
+
+/// Command-line arguments for listing the repositories of an organization or
+/// user.
+#[derive(Args)]
+pub struct RepoListCommandArgs {
+    /// Fetch all pages instead of only the one given by --page
+    #[arg(long, conflicts_with_all = ["interactive", "page"])]
+    all_pages: bool,
+
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Only list archived repositories. Only GitLab filters on its side,
+    /// otherwise a page may contain fewer repositories
+    #[arg(long, conflicts_with = "no_archived")]
+    archived: bool,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN). Private repositories are only listed with it
+    #[arg(long)]
+    auth: bool,
+
+    /// Clone the repository selected in the interactive TUI into the current
+    /// directory instead of printing its clone URL
+    #[arg(long, requires = "interactive")]
+    clone: bool,
+
+    /// Fields to include in output (comma-separated). The columns and JSON keys
+    /// follow their order. Use `all` for all fields
+    #[arg(short, long, value_delimiter = ',')]
+    fields: Vec<RepoField>,
+
+    /// Only list forks. The filter is applied after fetching the page, so a
+    /// page may contain fewer repositories
+    #[arg(long, conflicts_with = "source")]
+    fork: bool,
+
+    /// Output format
+    #[arg(short = 'o', long)]
+    format: Option<OutputFormat>,
+
+    /// Use interactive TUI for searching and selecting a repository. The clone
+    /// URL of the selected repository is printed with the protocol of the git
+    /// remote
+    #[arg(short, long, conflicts_with_all = ["fields", "format"])]
+    interactive: bool,
+
+    /// Don't list archived repositories. See --archived
+    #[arg(long)]
+    no_archived: bool,
+
+    /// Don't truncate the cells of rows that are wider than the terminal
+    #[arg(long)]
+    no_truncate: bool,
+
+    /// List the repositories of this organization (GitLab: group)
+    #[arg(long, value_name = "NAME", required_unless_present = "user")]
+    org: Option<String>,
+
+    /// Page number to fetch
+    #[arg(long, default_value_t = 1, value_name = "NUMBER")]
+    page: u32,
+
+    /// Number of repositories per page [default: 30]. The interactive TUI
+    /// fetches as many as fit on the screen by default
+    #[arg(long, value_name = "NUMBER")]
+    per_page: Option<u32>,
+
+    /// Git remote to use. Its forge is the one whose repositories are listed
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Only list repositories that aren't forks. See --fork
+    #[arg(long)]
+    source: bool,
+
+    /// List the repositories of this user
+    #[arg(long, value_name = "NAME", conflicts_with = "org")]
+    user: Option<String>,
+}
+
+/// Command-line arguments for viewing a repository.
+#[derive(Args)]
+pub struct RepoViewCommandArgs {
+    /// Specify the forge which affects the API schema etc
+    #[arg(long, value_name = "TYPE", long_help = forge::API_LONG_HELP)]
+    api: Option<ApiType>,
+
+    /// Explicitly provide the base API URL (e.g. https://gitlab.com/api/v4)
+    /// instead of relying on the auto-detection
+    #[arg(long, long_help = forge::API_URL_LONG_HELP, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with environment variables (GIT_FORGE_GITHUB_TOKEN,
+    /// GIT_FORGE_GITLAB_TOKEN, GIT_FORGE_GITEA_TOKEN,
+    /// GIT_FORGE_FORGEJO_TOKEN)
+    #[arg(long)]
+    auth: bool,
+
+    /// Output format of the repository [default: text]
+    #[arg(short = 'o', long)]
+    format: Option<ResultFormat>,
+
+    /// Git remote to use
+    #[arg(long)]
+    remote: Option<String>,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+/// The metadata of a repository on a forge.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Repo {
+    /// The path of the repository, e.g. `owner/repo`.
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub default_branch: String,
+    pub topics: Vec<String>,
+    /// The languages of the code, the most used one first.
+    pub languages: Vec<String>,
+    pub archived: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum RepoField {
+    Name,
+    Visibility,
+    Stars,
+    DefaultBranch,
+    PushedAt,
+    Archived,
+    Fork,
+    Url,
+    CloneUrl,
+    SshUrl,
+    All,
+}
+
+/// A repository in the listing of an organization or user.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RepoSummary {
+    /// The path of the repository, e.g. `owner/repo`.
+    pub name: String,
+    /// `public`, `private` or `internal`.
+    pub visibility: String,
+    pub stars: u32,
+    /// Empty for GitLab projects without commits.
+    pub default_branch: String,
+    /// Timestamp of the last push. GitLab and Gitea/Forgejo don't track
+    /// pushes, so it's the last activity or update there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pushed_at: Option<Timestamp>,
+    pub archived: bool,
+    pub fork: bool,
+    pub url: String,
+    /// The URL to clone the repository via HTTPS.
+    pub clone_url: String,
+    /// The URL to clone the repository via SSH.
+    pub ssh_url: String,
+}
+
+impl ListableItem for RepoSummary {
+    fn get_display_text(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_id(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_sort_value(&self, key: SortKey) -> Option<SortValue> {
+        match key {
+            SortKey::Updated => self.pushed_at.map(SortValue::Timestamp),
+            SortKey::Title => Some(SortValue::Text(self.name.clone())),
+            SortKey::Id | SortKey::Created | SortKey::Reactions => None,
+        }
+    }
+}
+
+/// The account whose repositories are listed.
+#[derive(Clone, Copy, Debug)]
+pub enum RepoOwner<'a> {
+    /// An organization, or a group on GitLab.
+    Org(&'a str),
+    User(&'a str),
+}
+
+pub struct ListReposFilters<'a> {
+    pub owner: RepoOwner<'a>,
+    /// Only archived (`true`) or only active (`false`) repositories.
+    pub archived: Option<bool>,
+    /// Only forks (`true`) or only sources (`false`).
+    pub fork: Option<bool>,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+impl ListReposFilters<'_> {
+    /// Checks the filters that the forges don't apply on their side.
+    pub fn matches(&self, repo: &RepoSummary) -> bool {
+        self.archived
+            .is_none_or(|archived| repo.archived == archived)
+            && self.fork.is_none_or(|fork| repo.fork == fork)
+    }
+}
+
+/// The fields that `repo edit` changes. Fields that stay the same are `None`.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct RepoEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<FieldChange<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topics: Option<FieldChange<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<FieldChange<String>>,
+}
+
+impl RepoEdit {
+    fn is_empty(&self) -> bool {
+        self.description.is_none() && self.topics.is_none() && self.default_branch.is_none()
+    }
+}
+
+/// The value of a field before and after `repo edit`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FieldChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Prints the metadata of the repository of the git remote.
+pub fn view_repo(mut args: RepoViewCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("repo/view/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "repo/view",
+        [api, api_url, auth, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let get_repo = forge::function!(api_type, get_repo);
+    let repo = get_repo(
+        &HttpClient::new(),
+        &remote,
+        args.api_url.as_deref(),
+        args.auth,
+    )
+    .context("Failed fetching the repository")?;
+
+    match args.format.unwrap_or_default() {
+        ResultFormat::Text => println!("{}", format_repo(&repo)),
+        ResultFormat::Json => println!("{}", serde_json::to_string_pretty(&repo)?),
+    }
+
+    Ok(())
+}
+
+/// Lists the repositories of an organization or user on the forge of the git
+/// remote.
+pub fn list_repos(mut args: RepoListCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("repo/list/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "repo/list",
+        [api, api_url, auth, format, no_truncate, per_page]
+    );
+
+    args.fields = io::resolve_fields(
+        args.fields,
+        config.get_string("repo/list/fields", Some(&remote)),
+        vec![
+            RepoField::Name,
+            RepoField::Visibility,
+            RepoField::Stars,
+            RepoField::DefaultBranch,
+            RepoField::PushedAt,
+        ],
+    )?;
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+
+    if args.interactive {
+        return list_repos_interactively(&remote_name, remote, api_type, args);
+    }
+
+    let get_repos = forge::function!(api_type, get_repos);
+    let http_client = HttpClient::new();
+    let per_page = args.per_page.unwrap_or(DEFAULT_PER_PAGE);
+    let mut repos = vec![];
+
+    for page in args.page.. {
+        let response = get_repos(
+            &http_client,
+            &remote,
+            args.api_url.as_deref(),
+            &ListReposFilters {
+                owner: get_repo_owner(args.org.as_deref(), args.user.as_deref()),
+                archived: get_flag_filter(args.archived, args.no_archived),
+                fork: get_flag_filter(args.fork, args.source),
+                page,
+                per_page,
+            },
+            args.auth,
+        )
+        .with_context(|| format!("Failed fetching repositories (page {page})"))?;
+
+        repos.extend(response.items);
+
+        if !io::should_fetch_next_page(repos.len(), response.has_next_page, args.all_pages, None) {
+            break;
+        }
+    }
+
+    if !repos.is_empty() {
+        io::print_list(
+            &repos,
+            &args.fields,
+            &args.format.unwrap_or_default(),
+            Truncation {
+                fit_to_terminal: !args.no_truncate,
+                max_title_width: None,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Changes the description, topics or default branch of the repository of the
+/// git remote and prints the old and new values of the changed fields.
+pub fn edit_repo(mut args: RepoEditCommandArgs) -> anyhow::Result<()> {
+    let config = Config::load_from_disk().context("Failed to load configuration")?;
+    let remote_name = args.remote.clone().unwrap_or_else(|| {
+        config
+            .get_string("repo/edit/remote", None)
+            .unwrap_or(DEFAULT_REMOTE.to_string())
+    });
+    let remote = config
+        .get_remote_data(&remote_name)
+        .with_context(|| format!("Failed to parse remote URL for remote '{}'", &remote_name))?;
+
+    config::merge_config_into_args!(
+        &config,
+        args,
+        Some(&remote),
+        "repo/edit",
+        [api, api_url, format]
+    );
+
+    let api_type = forge::get_api_type(args.api, &config, &remote)?;
+    let http_client = HttpClient::new();
+    let api_url = args.api_url.as_deref();
+    let get_repo = forge::function!(api_type, get_repo);
+    let repo =
+        get_repo(&http_client, &remote, api_url, true).context("Failed fetching the repository")?;
+    let edit = get_repo_edit(
+        &repo,
+        args.description,
+        &args.add_topic,
+        &args.remove_topic,
+        args.default_branch,
+    )?;
+
+    if edit.is_empty() {
+        eprintln!("Nothing to change in {}.", repo.name);
+
+        return Ok(());
+    }
+
+    if let Some(change) = &edit.default_branch {
+        let has_branch = forge::function!(api_type, has_branch);
+
+        if !has_branch(&http_client, &remote, api_url, &change.new)? {
+            anyhow::bail!(
+                "The branch '{}' doesn't exist on the forge. Push it before making it the default branch",
+                change.new
+            );
+        }
+    }
+
+    if args.dry_run {
+        eprintln!("Dry run: the changes to {} aren't saved.", repo.name);
+    } else {
+        let edit_repo = forge::function!(api_type, edit_repo);
+
+        edit_repo(&http_client, &remote, api_url, &edit)
+            .with_context(|| format!("Failed to edit {}", repo.name))?;
+    }
+
+    match args.format.unwrap_or_default() {
+        ResultFormat::Text => println!("{}", format_repo_edit(&edit)),
+        ResultFormat::Json => println!("{}", serde_json::to_string_pretty(&edit)?),
+    }
+
+    Ok(())
+}
+
+/// Orders the languages of a forge's languages endpoint by their share of the
+/// code, i.e. bytes or percentages depending on the forge. Languages with the
+/// same share are ordered by name.
+pub fn sort_languages(languages: HashMap<String, f64>) -> Vec<String> {
+    let mut languages: Vec<(String, f64)> = languages.into_iter().collect();
+
+    languages.sort_by(|(name_a, share_a), (name_b, share_b)| {
+        share_b.total_cmp(share_a).then_with(|| name_a.cmp(name_b))
+    });
+
+    languages.into_iter().map(|(name, _)| name).collect()
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+/// Lowercases a topic and checks it against GitHub's rules: at most 50
+/// letters, numbers and hyphens, starting with a letter or number.
+fn parse_topic(value: &str) -> Result<String, String> {
+    let topic = value.trim().to_lowercase();
+
+    if topic.is_empty() {
+        return Err(String::from("topics can't be empty"));
+    }
+
+    if topic.chars().count() > MAX_TOPIC_LENGTH {
+        return Err(format!(
+            "'{topic}' is longer than {MAX_TOPIC_LENGTH} characters"
+        ));
+    }
+
+    if !topic.starts_with(|c: char| c.is_ascii_alphanumeric())
+        || !topic.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(format!(
+            "'{topic}' has to start with a letter or number and may only contain letters, numbers and hyphens"
+        ));
+    }
+
+    Ok(topic)
+}
+
+/// Compares the requested values with the repository. Topics to add are
+/// appended unless the repository already has them.
+fn get_repo_edit(
+    repo: &Repo,
+    description: Option<String>,
+    add_topics: &[String],
+    remove_topics: &[String],
+    default_branch: Option<String>,
+) -> anyhow::Result<RepoEdit> {
+    let mut topics = repo.topics.clone();
+
+    for topic in add_topics {
+        if !topics.contains(topic) {
+            topics.push(topic.clone());
+        }
+    }
+
+    topics.retain(|topic| !remove_topics.contains(topic));
+
+    if topics.len() > MAX_TOPICS && topics.len() > repo.topics.len() {
+        anyhow::bail!(
+            "A repository can have at most {MAX_TOPICS} topics, but {} would have {}",
+            repo.name,
+            topics.len()
+        );
+    }
+
+    Ok(RepoEdit {
+        description: get_field_change(&repo.description, description),
+        topics: get_field_change(&repo.topics, Some(topics)),
+        default_branch: get_field_change(&repo.default_branch, default_branch),
+    })
+}
+
+fn get_field_change<T: Clone + PartialEq>(old: &T, new: Option<T>) -> Option<FieldChange<T>> {
+    new.filter(|new| new != old).map(|new| FieldChange {
+        old: old.clone(),
+        new,
+    })
+}
+
+/// Shows the TUI to select a repository and prints its clone URL or clones it.
+/// A search text filters the fetched repositories by their name.
+fn list_repos_interactively(
+    remote_name: &str,
+    remote: GitRemoteData,
+    api_type: ApiType,
+    args: RepoListCommandArgs,
+) -> anyhow::Result<()> {
+    let get_repos = forge::function!(api_type, get_repos);
+    let http_client = HttpClient::new();
+    let api_url = args.api_url;
+    let org = args.org;
+    let user = args.user;
+    let archived = get_flag_filter(args.archived, args.no_archived);
+    let fork = get_flag_filter(args.fork, args.source);
+    let per_page = args.per_page;
+    let use_auth = args.auth;
+
+    eprintln!("Loading repositories...");
+
+    let repo: RepoSummary = tui::select_item_with(
+        FetchOptions::default(),
+        vec![],
+        move |page, options, result| {
+            let query = options.parse_str("query").map(str::to_lowercase);
+            let per_page = per_page.unwrap_or(result.page_size());
+            let mut response = get_repos(
+                &http_client,
+                &remote,
+                api_url.as_deref(),
+                &ListReposFilters {
+                    owner: get_repo_owner(org.as_deref(), user.as_deref()),
+                    archived,
+                    fork,
+                    page,
+                    per_page,
+                },
+                use_auth,
+            )?;
+
+            if let Some(query) = query {
+                response
+                    .items
+                    .retain(|repo| repo.name.to_lowercase().contains(&query));
+            }
+
+            Ok(result
+                .with_items(response.items)
+                .with_more_items(response.has_next_page))
+        },
+    )?;
+    let clone_url = get_clone_url(&repo, remote_name);
+
+    if args.clone {
+        git::clone_repo(&clone_url)
+    } else {
+        println!("{clone_url}");
+
+        Ok(())
+    }
+}
+
+/// Clap makes sure that either --org or --user is given.
+fn get_repo_owner<'a>(org: Option<&'a str>, user: Option<&'a str>) -> RepoOwner<'a> {
+    match org {
+        Some(org) => RepoOwner::Org(org),
+        None => RepoOwner::User(user.unwrap_or_default()),
+    }
+}
+
+/// Gets the filter of a pair of flags like --archived and --no-archived.
+fn get_flag_filter(only: bool, exclude: bool) -> Option<bool> {
+    match (only, exclude) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Picks the clone URL with the protocol of the git remote, so that the same
+/// credentials work.
+fn get_clone_url(repo: &RepoSummary, remote_name: &str) -> String {
+    match git::get_remote_url(remote_name) {
+        Ok(url) if !url.starts_with("http") => repo.ssh_url.clone(),
+        _ => repo.clone_url.clone(),
+    }
+}
+
+fn format_repo(repo: &Repo) -> String {
+    let mut lines = vec![
+        format!("Name:           {}", repo.name),
+        format!("Description:    {}", repo.description),
+        format!("URL:            {}", repo.url),
+        format!("Default branch: {}", repo.default_branch),
+        format!("Topics:         {}", format_list(&repo.topics)),
+        format!("Languages:      {}", format_list(&repo.languages)),
+    ];
+
+    if repo.archived {
+        lines.push(String::from("Archived:       yes"));
+    }
+
+    lines.join("\n")
+}
+
+/// Formats the changed fields as removed and added lines of a diff.
+fn format_repo_edit(edit: &RepoEdit) -> String {
+    let mut lines = vec![];
+    let mut push_change = |field: &str, old: String, new: String| {
+        lines.push(format!("- {field}: {old}"));
+        lines.push(format!("+ {field}: {new}"));
+    };
+
+    if let Some(change) = &edit.description {
+        push_change("description", change.old.clone(), change.new.clone());
+    }
+
+    if let Some(change) = &edit.topics {
+        push_change("topics", format_list(&change.old), format_list(&change.new));
+    }
+
+    if let Some(change) = &edit.default_branch {
+        push_change("default-branch", change.old.clone(), change.new.clone());
+    }
+
+    lines.join("\n")
+}
+
+fn format_list(items: &[String]) -> String {
+    items.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_repo() -> Repo {
+        Repo {
+            name: String::from("owner/repo"),
+            description: String::from("A tool"),
+            url: String::from("https://github.com/owner/repo"),
+            default_branch: String::from("main"),
+            topics: vec![String::from("cli"), String::from("git")],
+            languages: vec![String::from("Rust")],
+            archived: false,
+        }
+    }
+
+    fn to_strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_topic() {
+        assert_eq!(parse_topic(" Rust-CLI "), Ok(String::from("rust-cli")));
+        assert!(parse_topic("").is_err());
+        assert!(parse_topic("-cli").is_err());
+        assert!(parse_topic("git forge").is_err());
+        assert!(parse_topic(&"a".repeat(MAX_TOPIC_LENGTH)).is_ok());
+        assert!(parse_topic(&"a".repeat(MAX_TOPIC_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_get_repo_edit() {
+        let repo = create_repo();
+        let edit = get_repo_edit(
+            &repo,
+            Some(String::from("A tool")),
+            &to_strings(&["forge", "cli"]),
+            &to_strings(&["git", "unknown"]),
+            Some(String::from("develop")),
+        )
+        .unwrap();
+
+        assert_eq!(
+            edit,
+            RepoEdit {
+                description: None,
+                topics: Some(FieldChange {
+                    old: to_strings(&["cli", "git"]),
+                    new: to_strings(&["cli", "forge"]),
+                }),
+                default_branch: Some(FieldChange {
+                    old: String::from("main"),
+                    new: String::from("develop"),
+                }),
+            }
+        );
+        assert!(
+            get_repo_edit(&repo, None, &to_strings(&["git"]), &[], None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_get_repo_edit_limits_topics() {
+        let repo = create_repo();
+        let topics: Vec<String> = (0..MAX_TOPICS).map(|i| format!("topic-{i}")).collect();
+
+        assert!(get_repo_edit(&repo, None, &topics, &[], None).is_err());
+        assert!(get_repo_edit(&repo, None, &topics, &to_strings(&["cli", "git"]), None).is_ok());
+    }
+
+    #[test]
+    fn test_sort_languages() {
+        let languages = HashMap::from([
+            (String::from("Shell"), 10.0),
+            (String::from("Rust"), 80.5),
+            (String::from("Nix"), 10.0),
+        ]);
+
+        assert_eq!(
+            sort_languages(languages),
+            to_strings(&["Rust", "Nix", "Shell"])
+        );
+    }
+
+    #[test]
+    fn test_list_repos_filters_match() {
+        let repo = RepoSummary {
+            name: String::from("owner/repo"),
+            visibility: String::from("public"),
+            stars: 3,
+            default_branch: String::from("main"),
+            pushed_at: None,
+            archived: true,
+            fork: false,
+            url: String::from("https://github.com/owner/repo"),
+            clone_url: String::from("https://github.com/owner/repo.git"),
+            ssh_url: String::from("git@github.com:owner/repo.git"),
+        };
+        let filters = |archived, fork| ListReposFilters {
+            owner: RepoOwner::Org("owner"),
+            archived,
+            fork,
+            page: 1,
+            per_page: 30,
+        };
+
+        assert!(filters(None, None).matches(&repo));
+        assert!(filters(Some(true), Some(false)).matches(&repo));
+        assert!(!filters(Some(false), None).matches(&repo));
+        assert!(!filters(None, Some(true)).matches(&repo));
+    }
+
+    #[test]
+    fn test_get_flag_filter() {
+        assert_eq!(get_flag_filter(false, false), None);
+        assert_eq!(get_flag_filter(true, false), Some(true));
+        assert_eq!(get_flag_filter(false, true), Some(false));
+    }
+
+    #[test]
+    fn test_format_repo_edit() {
+        let edit = RepoEdit {
+            description: Some(FieldChange {
+                old: String::from("A tool"),
+                new: String::new(),
+            }),
+            topics: Some(FieldChange {
+                old: to_strings(&["cli"]),
+                new: to_strings(&["cli", "git"]),
+            }),
+            default_branch: None,
+        };
+
+        assert_eq!(
+            format_repo_edit(&edit),
+            "- description: A tool\n+ description: \n- topics: cli\n+ topics: cli, git"
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! The `self-update` subcommand and the update check of `version --check`.
+//!
+//! The releases of git-forge itself are looked up with the GitHub client, so
+//! `--api-url` can point to an internal mirror of the releases.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use clap::Args;
+use semver::Version;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    cli::{
+        config::Config,
+        forge::{self, ApiType, ApiUrl, HttpClient, WithHttpStatusOk, github},
+    },
+    git,
+};
+
+const REPOSITORY_URL: &str = std::env!("CARGO_PKG_REPOSITORY");
+const CURRENT_VERSION: &str = std::env!("CARGO_PKG_VERSION");
+const CHECKSUMS_ASSET_NAME: &str = "SHA256SUMS";
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `self-update` subcommand.
+#[derive(Args)]
+pub struct SelfUpdateCommandArgs {
+    /// Explicitly provide the base API URL (e.g. of an internal mirror) to
+    /// look up the releases
+    #[arg(long, value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with the environment variable GIT_FORGE_GITHUB_TOKEN
+    #[arg(long)]
+    auth: bool,
+}
+
+// =============================================================================
+// Domain Types
+// =============================================================================
+
+/// A release of a repository.
+pub struct Release {
+    /// The git tag of the release (e.g., v1.2.3).
+    pub tag: String,
+    /// The files attached to the release.
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release.
+pub struct ReleaseAsset {
+    pub name: String,
+    pub download_url: String,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Replaces the running binary with the latest release.
+pub fn self_update(args: SelfUpdateCommandArgs) -> anyhow::Result<()> {
+    let http_client = HttpClient::new();
+    let release = get_latest_release(&http_client, args.api_url.as_deref(), args.auth)?;
+    let latest_version = parse_version(&release.tag)?;
+
+    if latest_version <= parse_version(CURRENT_VERSION)? {
+        eprintln!("git-forge is already up to date ({CURRENT_VERSION})");
+
+        return Ok(());
+    }
+
+    let asset_name = get_asset_name(
+        &latest_version,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )?;
+    let asset = find_asset(&release, &asset_name)?;
+    let checksums_asset = find_asset(&release, CHECKSUMS_ASSET_NAME)?;
+
+    eprintln!("Downloading {asset_name}...");
+
+    let checksums = String::from_utf8(download(&http_client, &checksums_asset.download_url)?)
+        .context("The checksums file isn't valid UTF-8")?;
+    let expected_checksum = find_checksum(&checksums, &asset_name)
+        .with_context(|| format!("No checksum found for {asset_name}"))?;
+    let binary = download(&http_client, &asset.download_url)?;
+    let actual_checksum = format_sha256(&binary);
+
+    if actual_checksum != expected_checksum {
+        anyhow::bail!(
+            "Checksum mismatch for {asset_name}: expected {expected_checksum}, got {actual_checksum}"
+        );
+    }
+
+    replace_current_exe(&binary)?;
+
+    eprintln!("Updated git-forge from {CURRENT_VERSION} to {latest_version}");
+
+    Ok(())
+}
+
+/// Returns the latest released version if it's newer than the running one.
+pub fn check_for_update(api_url: Option<&str>, use_auth: bool) -> anyhow::Result<Option<Version>> {
+    let release = get_latest_release(&HttpClient::new(), api_url, use_auth)?;
+    let latest_version = parse_version(&release.tag)?;
+
+    if latest_version > parse_version(CURRENT_VERSION)? {
+        Ok(Some(latest_version))
+    } else {
+        Ok(None)
+    }
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+fn get_latest_release(
+    http_client: &HttpClient,
+    api_url: Option<&str>,
+    use_auth: bool,
+) -> anyhow::Result<Release> {
+    let remote = git::parse_remote_url(REPOSITORY_URL)
+        .with_context(|| format!("Failed to parse the repository URL {REPOSITORY_URL}"))?;
+
+    // The repository of the releases isn't a git remote, so its hosts aren't
+    // allowed for the token by resolving a forge. A mirror of --api-url needs
+    // the `auth/allowed-hosts` setting or --force-auth-host like with every
+    // other command.
+    if use_auth {
+        let config = Config::load_from_disk().context("Failed to load configuration")?;
+
+        forge::allow_auth_for_remote(ApiType::GitHub, &config, &remote);
+    }
+
+    github::get_latest_release(http_client, &remote, api_url, use_auth)
+}
+
+fn parse_version(tag: &str) -> anyhow::Result<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag))
+        .with_context(|| format!("Failed to parse version {tag}"))
+}
+
+/// Returns the name of the release asset for the platform. See the release
+/// workflow for the naming scheme.
+fn get_asset_name(version: &Version, os: &str, arch: &str) -> anyhow::Result<String> {
+    let (target, extension) = match os {
+        "linux" => ("unknown-linux-gnu", ""),
+        "macos" => ("apple-darwin", ""),
+        "windows" => ("pc-windows-msvc", ".exe"),
+        _ => anyhow::bail!("There are no prebuilt binaries for {os}"),
+    };
+
+    if !matches!(arch, "x86_64" | "aarch64") {
+        anyhow::bail!("There are no prebuilt binaries for {arch}");
+    }
+
+    Ok(format!("git-forge-{version}-{arch}-{target}{extension}"))
+}
+
+fn find_asset<'a>(release: &'a Release, name: &str) -> anyhow::Result<&'a ReleaseAsset> {
+    release
+        .assets
+        .iter()
+        .find(|asset| asset.name == name)
+        .with_context(|| format!("Release {} has no asset named {name}", release.tag))
+}
+
+/// Finds the checksum of a file in the output of `sha256sum`.
+fn find_checksum<'a>(checksums: &'a str, file_name: &str) -> Option<&'a str> {
+    checksums.lines().find_map(|line| {
+        let (checksum, name) = line.split_once(char::is_whitespace)?;
+        // sha256sum marks files read in binary mode with an asterisk
+        let name = name.trim_start().trim_start_matches('*');
+
+        (name == file_name).then_some(checksum)
+    })
+}
+
+fn format_sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn download(http_client: &HttpClient, url: &str) -> anyhow::Result<Vec<u8>> {
+    let bytes = http_client
+        .get(url)
+        .send()
+        .with_context(|| format!("Network request failed while downloading {url}"))?
+        .with_http_status_ok()?
+        .bytes()
+        .with_context(|| format!("Failed to download {url}"))?;
+
+    Ok(bytes.to_vec())
+}
+
+/// Replaces the running binary. The new binary is written next to the current
+/// one and then renamed over it, so the replacement is atomic. Windows doesn't
+/// allow replacing a running binary but allows renaming it, so the current
+/// binary is moved out of the way first.
+fn replace_current_exe(binary: &[u8]) -> anyhow::Result<()> {
+    let current_exe = std::env::current_exe()
+        .and_then(|path| path.canonicalize())
+        .context("Failed to locate the running binary")?;
+    let new_exe = append_to_file_name(&current_exe, ".new");
+
+    fs::write(&new_exe, binary)
+        .with_context(|| format!("Failed to write {}", new_exe.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(&new_exe, fs::Permissions::from_mode(0o755))
+            .context("Failed to make the new binary executable")?;
+    }
+
+    #[cfg(windows)]
+    {
+        let old_exe = append_to_file_name(&current_exe, ".old");
+
+        // Left over from the previous update
+        let _ = fs::remove_file(&old_exe);
+
+        fs::rename(&current_exe, &old_exe).context("Failed to move the running binary")?;
+
+        if let Err(e) = fs::rename(&new_exe, &current_exe) {
+            let _ = fs::rename(&old_exe, &current_exe);
+
+            return Err(e).context("Failed to replace the running binary");
+        }
+
+        return Ok(());
+    }
+
+    #[allow(unreachable_code)]
+    fs::rename(&new_exe, &current_exe).context("Failed to replace the running binary")
+}
+
+fn append_to_file_name(path: &std::path::Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::config::ConfigScope;
+
+    #[test]
+    fn test_parse_version_with_v_prefix() {
+        assert_eq!(parse_version("v1.2.3").unwrap(), Version::new(1, 2, 3));
+        assert_eq!(parse_version("0.2.1").unwrap(), Version::new(0, 2, 1));
+        assert!(parse_version("latest").is_err());
+    }
+
+    #[test]
+    fn test_get_asset_name() {
+        let version = Version::new(1, 2, 3);
+
+        assert_eq!(
+            get_asset_name(&version, "linux", "x86_64").unwrap(),
+            "git-forge-1.2.3-x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            get_asset_name(&version, "macos", "aarch64").unwrap(),
+            "git-forge-1.2.3-aarch64-apple-darwin"
+        );
+        assert_eq!(
+            get_asset_name(&version, "windows", "x86_64").unwrap(),
+            "git-forge-1.2.3-x86_64-pc-windows-msvc.exe"
+        );
+        assert!(get_asset_name(&version, "freebsd", "x86_64").is_err());
+        assert!(get_asset_name(&version, "linux", "riscv64").is_err());
+    }
+
+    #[test]
+    fn test_find_checksum() {
+        let checksums = "\
+            aaaa  git-forge-1.2.3-x86_64-unknown-linux-gnu\n\
+            bbbb *git-forge-1.2.3-x86_64-pc-windows-msvc.exe\n";
+
+        assert_eq!(
+            find_checksum(checksums, "git-forge-1.2.3-x86_64-unknown-linux-gnu"),
+            Some("aaaa")
+        );
+        assert_eq!(
+            find_checksum(checksums, "git-forge-1.2.3-x86_64-pc-windows-msvc.exe"),
+            Some("bbbb")
+        );
+        assert_eq!(find_checksum(checksums, "git-forge"), None);
+    }
+
+    #[test]
+    fn test_only_the_release_hosts_are_allowed_for_the_token() {
+        let remote = git::parse_remote_url(REPOSITORY_URL).unwrap();
+        let is_allowed = |base_url: &str, config: &Config| {
+            let url = format!("{base_url}/repos/{}/releases/latest", remote.path);
+
+            forge::is_auth_allowed_for_remote(
+                &reqwest::Url::parse(&url).unwrap(),
+                ApiType::GitHub,
+                config,
+                &remote,
+            )
+        };
+        let mirror_url = "https://releases.mirror.internal:8443/api/v3";
+        let mut config = Config::default();
+
+        assert!(is_allowed(&github::get_url_for_api(&remote), &config));
+        assert!(!is_allowed("https://api.gihtub.com", &config));
+        assert!(!is_allowed(mirror_url, &config));
+
+        config
+            .set_value_in_scope(
+                "auth/allowed-hosts",
+                "releases.mirror.internal:8443",
+                ConfigScope::Global,
+                &remote,
+            )
+            .unwrap();
+
+        assert!(is_allowed(mirror_url, &config));
+    }
+
+    #[test]
+    fn test_format_sha256() {
+        assert_eq!(
+            format_sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
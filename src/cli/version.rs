@@ -0,0 +1,54 @@
+//! The `version` subcommand.
+
+use clap::Args;
+
+#[cfg(feature = "self-update")]
+use crate::cli::forge::{self, ApiUrl};
+
+// =============================================================================
+// CLI Arguments
+// =============================================================================
+
+/// Command-line arguments for the `version` subcommand.
+#[derive(Args)]
+pub struct VersionCommandArgs {
+    /// Check whether a newer version has been released
+    #[cfg(feature = "self-update")]
+    #[arg(long)]
+    check: bool,
+
+    /// Explicitly provide the base API URL (e.g. of an internal mirror) to
+    /// look up the releases
+    #[cfg(feature = "self-update")]
+    #[arg(long, requires = "check", value_parser = forge::parse_api_url)]
+    api_url: Option<ApiUrl>,
+
+    /// Use authentication with the environment variable GIT_FORGE_GITHUB_TOKEN
+    #[cfg(feature = "self-update")]
+    #[arg(long, requires = "check")]
+    auth: bool,
+}
+
+// =============================================================================
+// Command Logic
+// =============================================================================
+
+/// Prints the version and optionally checks for a newer release.
+pub fn print_version(args: VersionCommandArgs) -> anyhow::Result<()> {
+    println!("{}", std::env!("CARGO_PKG_VERSION"));
+
+    #[cfg(feature = "self-update")]
+    if args.check {
+        match super::self_update::check_for_update(args.api_url.as_deref(), args.auth)? {
+            Some(version) => {
+                eprintln!("A newer version is available: {version}. Run `git forge self-update`")
+            }
+            None => eprintln!("git-forge is up to date"),
+        }
+    }
+
+    #[cfg(not(feature = "self-update"))]
+    let _ = args;
+
+    Ok(())
+}
@@ -0,0 +1,418 @@
+//! Dates of list filters like `--created-after` and timestamps of the forges.
+//!
+//! A date is either absolute, e.g. `2024-03-01` (midnight UTC) or
+//! `2024-03-01T12:00:00+02:00`, or relative to now, e.g. `7d` for seven days
+//! ago. Ranges include their start and exclude their end, so
+//! `--created-after 2024-03-01 --created-before 2024-04-01` covers March.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::{
+    Date, Duration, OffsetDateTime, UtcOffset,
+    format_description::{
+        self, OwnedFormatItem,
+        well_known::{Iso8601, Rfc3339},
+    },
+};
+
+/// The units of relative dates and their length in seconds.
+const RELATIVE_UNITS: [(char, i64); 3] =
+    [('h', 60 * 60), ('d', 24 * 60 * 60), ('w', 7 * 24 * 60 * 60)];
+
+/// A point in time returned by a forge, e.g. when an issue was created. It's
+/// (de)serialized as an RFC 3339 string and displayed in UTC, so timestamps
+/// of all forges look alike, e.g. `2024-03-01T12:00:00Z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(OffsetDateTime);
+
+impl Timestamp {
+    /// Parses a timestamp of a forge. Besides RFC 3339, e.g. GitHub's
+    /// `2024-03-01T12:00:00Z` or GitLab's `2024-03-01T12:00:00.000Z`, it
+    /// accepts other ISO 8601 timestamps, e.g. with an offset without a colon
+    /// like `+0200`, and plain dates (midnight UTC).
+    pub fn parse(value: &str) -> Option<Timestamp> {
+        let value = value.trim();
+
+        OffsetDateTime::parse(value, &Rfc3339)
+            .or_else(|_| OffsetDateTime::parse(value, &Iso8601::DEFAULT))
+            .or_else(|_| {
+                Date::parse(value, &Iso8601::DATE).map(|date| date.midnight().assume_utc())
+            })
+            .ok()
+            .map(Timestamp)
+    }
+
+    /// Formats how long ago the timestamp was in the largest unit of relative
+    /// dates that fits, e.g. `3d` or `12w`.
+    pub fn format_age(&self) -> String {
+        self.format_age_at(OffsetDateTime::now_utc())
+    }
+
+    fn format_age_at(&self, now: OffsetDateTime) -> String {
+        let seconds = (now - self.0).whole_seconds().max(0);
+        let (unit, length) = RELATIVE_UNITS
+            .iter()
+            .rev()
+            .find(|(_, length)| seconds >= *length)
+            .unwrap_or(&RELATIVE_UNITS[0]);
+
+        format!("{}{unit}", seconds / length)
+    }
+
+    /// Formats the timestamp in UTC with a custom format.
+    pub fn format_with(&self, format: &DateFormat) -> String {
+        self.0
+            .to_offset(UtcOffset::UTC)
+            .format(&format.0)
+            .unwrap_or_else(|_| self.to_string())
+    }
+}
+
+/// A date format given as a strftime pattern, e.g. `%Y-%m-%d %H:%M` or
+/// `%d %b %Y`. Timestamps are formatted in UTC, so `%Z` is always `UTC`.
+#[derive(Clone, Debug)]
+pub struct DateFormat(OwnedFormatItem);
+
+impl DateFormat {
+    /// Parses a strftime pattern. Only the common conversion specifications
+    /// are supported, the ones of locales like `%c` aren't.
+    pub fn parse(pattern: &str) -> Result<DateFormat, String> {
+        if pattern.trim().is_empty() {
+            return Err(String::from("expected a strftime pattern, e.g. %Y-%m-%d"));
+        }
+
+        let mut description = String::new();
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '%' => {
+                    let component = match chars.next() {
+                        Some('Y') => "[year]",
+                        Some('y') => "[year repr:last_two]",
+                        Some('m') => "[month]",
+                        Some('b' | 'h') => "[month repr:short]",
+                        Some('B') => "[month repr:long]",
+                        Some('d') => "[day]",
+                        Some('e') => "[day padding:space]",
+                        Some('j') => "[ordinal]",
+                        Some('a') => "[weekday repr:short]",
+                        Some('A') => "[weekday repr:long]",
+                        Some('H') => "[hour]",
+                        Some('I') => "[hour repr:12]",
+                        Some('p') => "[period]",
+                        Some('M') => "[minute]",
+                        Some('S') => "[second]",
+                        Some('F') => "[year]-[month]-[day]",
+                        Some('T') => "[hour]:[minute]:[second]",
+                        Some('R') => "[hour]:[minute]",
+                        Some('z') => "+0000",
+                        Some('Z') => "UTC",
+                        Some('%') => "%",
+                        Some(other) => {
+                            return Err(format!("'%{other}' isn't supported in date formats"));
+                        }
+                        None => return Err(String::from("the pattern ends with a single '%'")),
+                    };
+
+                    description.push_str(component);
+                }
+                '[' => description.push_str("[["),
+                c => description.push(c),
+            }
+        }
+
+        format_description::parse_owned::<1>(&description)
+            .map(DateFormat)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl From<OffsetDateTime> for Timestamp {
+    fn from(date: OffsetDateTime) -> Self {
+        Timestamp(date)
+    }
+}
+
+impl From<Timestamp> for OffsetDateTime {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_date(self.0))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        Timestamp::parse(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp '{value}'")))
+    }
+}
+
+/// A range of dates. Unset bounds don't restrict the range.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DateRange {
+    /// The inclusive start.
+    pub after: Option<OffsetDateTime>,
+    /// The exclusive end.
+    pub before: Option<OffsetDateTime>,
+}
+
+impl DateRange {
+    /// Whether any bound is set.
+    pub fn is_set(&self) -> bool {
+        self.after.is_some() || self.before.is_some()
+    }
+
+    /// Gets the last second before the end for APIs whose end is inclusive.
+    pub fn get_inclusive_before(&self) -> Option<OffsetDateTime> {
+        self.before.map(|before| before - Duration::SECOND)
+    }
+
+    /// Whether the timestamp is within the range. A missing timestamp, e.g.
+    /// the closing date of an open issue, is only within a range without
+    /// bounds.
+    pub fn contains(&self, timestamp: Option<Timestamp>) -> bool {
+        if !self.is_set() {
+            return true;
+        }
+
+        let Some(Timestamp(date)) = timestamp else {
+            return false;
+        };
+
+        self.after.is_none_or(|after| date >= after)
+            && self.before.is_none_or(|before| date < before)
+    }
+
+    /// Fails if the start isn't before the end. `flag` is the common prefix of
+    /// the options that set the bounds, e.g. `--created`.
+    pub fn validate(&self, flag: &str) -> anyhow::Result<()> {
+        if let (Some(after), Some(before)) = (self.after, self.before)
+            && after >= before
+        {
+            anyhow::bail!(
+                "{flag}-after ({}) must be before {flag}-before ({})",
+                format_date(after),
+                format_date(before)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses an absolute or relative date. Meant as the value parser of clap
+/// options.
+pub fn parse_date(value: &str) -> Result<OffsetDateTime, String> {
+    parse_date_at(value, OffsetDateTime::now_utc())
+}
+
+/// Formats the date as an RFC 3339 timestamp in UTC, e.g. for query
+/// parameters.
+pub fn format_date(date: OffsetDateTime) -> String {
+    date.to_offset(UtcOffset::UTC)
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| date.unix_timestamp().to_string())
+}
+
+// =============================================================================
+// Private Helpers
+// =============================================================================
+
+fn parse_date_at(value: &str, now: OffsetDateTime) -> Result<OffsetDateTime, String> {
+    let value = value.trim();
+
+    if let Some(date) = parse_relative_date(value, now) {
+        return Ok(date);
+    }
+
+    if let Ok(date) = OffsetDateTime::parse(value, &Rfc3339) {
+        return Ok(date);
+    }
+
+    Date::parse(value, &Iso8601::DATE)
+        .map(|date| date.midnight().assume_utc())
+        .map_err(|_| {
+            format!(
+                "invalid date '{value}'. Use a date like 2024-03-01, a timestamp like \
+                2024-03-01T12:00:00Z or a duration like 12h, 7d or 2w"
+            )
+        })
+}
+
+/// Parses a duration like `7d` as the date that long before `now`. The date
+/// is truncated to seconds since the APIs don't take fractions of a second.
+fn parse_relative_date(value: &str, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    let unit = value.chars().last()?;
+    let (_, seconds) = RELATIVE_UNITS.iter().find(|(u, _)| *u == unit)?;
+    let count: i64 = value.strip_suffix(unit)?.parse().ok()?;
+    let date = now.checked_sub(Duration::seconds(count.checked_mul(*seconds)?))?;
+
+    date.replace_nanosecond(0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_rfc3339(timestamp: &str) -> OffsetDateTime {
+        OffsetDateTime::parse(timestamp, &Rfc3339).unwrap()
+    }
+
+    fn timestamp(value: &str) -> Option<Timestamp> {
+        Timestamp::parse(value)
+    }
+
+    #[test]
+    fn test_parse_date_at() {
+        let now = parse_rfc3339("2024-03-15T12:30:00.5Z");
+
+        assert_eq!(
+            parse_date_at("2024-03-01", now),
+            Ok(parse_rfc3339("2024-03-01T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_date_at("2024-03-01T12:00:00+02:00", now),
+            Ok(parse_rfc3339("2024-03-01T10:00:00Z"))
+        );
+        assert_eq!(
+            parse_date_at("12h", now),
+            Ok(parse_rfc3339("2024-03-15T00:30:00Z"))
+        );
+        assert_eq!(
+            parse_date_at("2w", now),
+            Ok(parse_rfc3339("2024-03-01T12:30:00Z"))
+        );
+        assert!(parse_date_at("2 weeks", now).is_err());
+        assert!(parse_date_at("d", now).is_err());
+        assert!(parse_date_at("2024-13-01", now).is_err());
+    }
+
+    #[test]
+    fn test_date_range_contains() {
+        let march = DateRange {
+            after: Some(parse_rfc3339("2024-03-01T00:00:00Z")),
+            before: Some(parse_rfc3339("2024-04-01T00:00:00Z")),
+        };
+
+        assert!(march.contains(timestamp("2024-03-01T00:00:00Z")));
+        assert!(march.contains(timestamp("2024-03-31T23:59:59.999Z")));
+        assert!(!march.contains(timestamp("2024-04-01T00:00:00Z")));
+        assert!(!march.contains(timestamp("2024-02-29T23:59:59Z")));
+        assert!(!march.contains(None));
+        assert!(DateRange::default().contains(None));
+    }
+
+    #[test]
+    fn test_date_range_validate() {
+        let date = parse_rfc3339("2024-03-01T00:00:00Z");
+        let range = DateRange {
+            after: Some(date),
+            before: Some(date),
+        };
+
+        assert_eq!(
+            range.validate("--created").unwrap_err().to_string(),
+            "--created-after (2024-03-01T00:00:00Z) must be before \
+            --created-before (2024-03-01T00:00:00Z)"
+        );
+        assert!(
+            DateRange {
+                after: Some(date),
+                before: None
+            }
+            .validate("--created")
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(
+            format_date(parse_rfc3339("2024-03-01T02:00:00+02:00")),
+            "2024-03-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_format_age_at() {
+        let now = parse_rfc3339("2024-03-15T12:30:00Z");
+        let format_age_at = |value: &str| timestamp(value).unwrap().format_age_at(now);
+
+        assert_eq!(format_age_at("2024-03-15T12:00:00Z"), "0h");
+        assert_eq!(format_age_at("2024-03-15T00:30:00Z"), "12h");
+        assert_eq!(format_age_at("2024-03-10T00:00:00Z"), "5d");
+        assert_eq!(format_age_at("2023-12-01T00:00:00Z"), "15w");
+    }
+
+    #[test]
+    fn test_format_with_strftime_patterns() {
+        let timestamp = timestamp("2024-03-01T18:05:09+02:00").unwrap();
+        let format_with =
+            |pattern: &str| timestamp.format_with(&DateFormat::parse(pattern).unwrap());
+
+        assert_eq!(format_with("%Y-%m-%d %H:%M"), "2024-03-01 16:05");
+        assert_eq!(
+            format_with("%a, %e %b %Y %T %Z"),
+            "Fri,  1 Mar 2024 16:05:09 UTC"
+        );
+        assert_eq!(format_with("%d.%m.%y %I:%M %p"), "01.03.24 04:05 PM");
+        assert_eq!(format_with("[%F] 100%%"), "[2024-03-01] 100%");
+    }
+
+    #[test]
+    fn test_date_format_rejects_unsupported_patterns() {
+        assert!(DateFormat::parse("").is_err());
+        assert!(DateFormat::parse("%c").is_err_and(|e| e.contains("'%c' isn't supported")));
+        assert!(DateFormat::parse("%Y%").is_err());
+    }
+
+    #[test]
+    fn test_timestamp_parses_forge_formats() {
+        let expected = timestamp("2024-03-01T10:30:00Z");
+
+        // GitHub
+        assert_eq!(timestamp("2024-03-01T10:30:00Z"), expected);
+        // GitLab
+        assert_eq!(timestamp("2024-03-01T10:30:00.000Z"), expected);
+        // Gitea/Forgejo use the offset of the server
+        assert_eq!(timestamp("2024-03-01T18:30:00+08:00"), expected);
+        assert_eq!(timestamp("2024-03-01T11:30:00+0100"), expected);
+        assert_eq!(timestamp(" 2024-03-01T10:30:00Z\n"), expected);
+        assert_eq!(timestamp("2024-03-01"), timestamp("2024-03-01T00:00:00Z"));
+        assert_eq!(timestamp("yesterday"), None);
+        assert_eq!(timestamp(""), None);
+    }
+
+    #[test]
+    fn test_timestamp_serde() {
+        let timestamp: Timestamp =
+            serde_json::from_str(r#""2024-03-01T18:30:00.5+08:00""#).unwrap();
+
+        assert_eq!(timestamp.to_string(), "2024-03-01T10:30:00.5Z");
+        assert_eq!(
+            serde_json::to_string(&timestamp).unwrap(),
+            r#""2024-03-01T10:30:00.5Z""#
+        );
+        assert!(
+            serde_json::from_str::<Timestamp>(r#""not a date""#)
+                .unwrap_err()
+                .to_string()
+                .contains("invalid timestamp 'not a date'")
+        );
+    }
+}
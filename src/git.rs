@@ -1,9 +1,27 @@
 //! Git operations and utilities.
 
-use std::process::Command;
+use std::{
+    io::{self, Read, Write},
+    process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
 
 use anyhow::Context;
 
+use crate::audit;
+
+/// The key of the branch config that remembers the PR of a branch.
+const BRANCH_PR_KEY: &str = "git-forge-pr";
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether the output of git commands is streamed to the terminal while
+/// they run. Otherwise, it's only shown as part of the error if they fail.
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
 /// Gets and parses the remote URL
 ///
 /// # Errors
@@ -29,25 +47,26 @@ pub fn get_remote_data(remote: &str) -> anyhow::Result<GitRemoteData> {
 /// Returns an error if git command fails; for instance if the remote doesn't
 /// exist.
 pub fn get_remote_url(remote: &str) -> anyhow::Result<String> {
-    let output = Command::new("git")
-        .args(["remote", "get-url", remote])
-        .output()
-        .with_context(|| format!("Failed to execute git command for remote '{remote}'"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    run_git(&["remote", "get-url", remote], RunOptions::default()).with_context(|| {
+        format!("Remote '{remote}' not found. Run 'git remote -v' to see available remotes.")
+    })
+}
 
-        anyhow::bail!(
-            "Remote '{remote}' not found. {}",
-            if !stderr.is_empty() {
-                stderr
-            } else {
-                String::from("Run 'git remote -v' to see available remotes.")
-            }
-        );
-    }
+/// Lists the names of all git remotes.
+///
+/// # Errors
+///
+/// Returns an error if the git command fails.
+pub fn list_remotes() -> anyhow::Result<Vec<String>> {
+    let output =
+        run_git(&["remote"], RunOptions::default()).context("Failed to list the git remotes")?;
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
 }
 
 /// Fetches a pull request ref and creates a local branch.
@@ -56,15 +75,11 @@ pub fn get_remote_url(remote: &str) -> anyhow::Result<String> {
 ///
 /// Returns an error if the fetch operation fails.
 pub fn fetch_pull_request(pr_ref: &str, branch_name: &str, remote: &str) -> anyhow::Result<()> {
-    let output = Command::new("git")
-        .args(["fetch", remote, &format!("{pr_ref}:{branch_name}")])
-        .output()
-        .with_context(|| format!("Failed to execute git fetch for ref '{}'", pr_ref))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to fetch pull request ref {pr_ref}: {stderr}");
-    }
+    run_git(
+        &["fetch", remote, &format!("{pr_ref}:{branch_name}")],
+        RunOptions { interactive: true },
+    )
+    .with_context(|| format!("Failed to fetch pull request ref {pr_ref}"))?;
 
     Ok(())
 }
@@ -75,44 +90,149 @@ pub fn fetch_pull_request(pr_ref: &str, branch_name: &str, remote: &str) -> anyh
 ///
 /// Returns an error if the checkout fails.
 pub fn checkout_branch(branch_name: &str) -> anyhow::Result<()> {
-    let output = Command::new("git")
-        .args(["checkout", branch_name])
-        .output()
-        .with_context(|| format!("Failed to execute git checkout for branch '{branch_name}'"))?;
+    run_git(&["checkout", branch_name], RunOptions::default())
+        .with_context(|| format!("Failed to checkout branch \"{branch_name}\""))?;
+
+    Ok(())
+}
+
+/// Sets the upstream of a local branch, so that `git pull` without arguments
+/// merges `merge_ref` of `remote`, e.g. `refs/heads/main` or `refs/pull/1/head`.
+///
+/// # Errors
+///
+/// Returns an error if the git config can't be written.
+pub fn set_branch_tracking(branch: &str, remote: &str, merge_ref: &str) -> anyhow::Result<()> {
+    set_branch_tracking_with(|| Command::new("git"), branch, remote, merge_ref)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to checkout branch \"{branch_name}\": {stderr}");
+fn set_branch_tracking_with(
+    git: impl Fn() -> Command,
+    branch: &str,
+    remote: &str,
+    merge_ref: &str,
+) -> anyhow::Result<()> {
+    for (key, value) in [("remote", remote), ("merge", merge_ref)] {
+        run_git_command(
+            git(),
+            &["config", &format!("branch.{branch}.{key}"), value],
+            RunOptions::default(),
+        )
+        .with_context(|| format!("Failed to set the upstream of branch \"{branch}\""))?;
     }
 
     Ok(())
 }
 
-/// Gets the name of the current git branch.
+/// Remembers the PR of a local branch in the git config
+/// (`branch.<NAME>.git-forge-pr`), so that it can be looked up without asking
+/// the forge, e.g. by `context`.
 ///
 /// # Errors
 ///
-/// Returns an error if the git operation fails or no branch is checked out.
-pub fn get_current_branch() -> anyhow::Result<String> {
-    let output = Command::new("git")
-        .args(["branch", "--show-current"])
-        .output()
-        .context("Failed to execute git command to get current branch")?;
+/// Returns an error if the git config can't be written.
+pub fn set_branch_pr(branch: &str, pr_number: u32) -> anyhow::Result<()> {
+    set_branch_pr_with(Command::new("git"), branch, pr_number)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+fn set_branch_pr_with(command: Command, branch: &str, pr_number: u32) -> anyhow::Result<()> {
+    run_git_command(
+        command,
+        &[
+            "config",
+            &format!("branch.{branch}.{BRANCH_PR_KEY}"),
+            &pr_number.to_string(),
+        ],
+        RunOptions::default(),
+    )
+    .with_context(|| format!("Failed to remember the PR of branch \"{branch}\""))?;
 
-        anyhow::bail!("Failed to get current branch: {stderr}");
-    }
+    Ok(())
+}
+
+/// Gets the PR remembered by [`set_branch_pr`] for a local branch or `None`
+/// if there is none.
+pub fn get_branch_pr(branch: &str) -> Option<u32> {
+    get_branch_pr_with(Command::new("git"), branch)
+}
+
+fn get_branch_pr_with(command: Command, branch: &str) -> Option<u32> {
+    // git config fails if the key isn't set
+    run_git_command(
+        command,
+        &["config", &format!("branch.{branch}.{BRANCH_PR_KEY}")],
+        RunOptions::default(),
+    )
+    .ok()?
+    .parse()
+    .ok()
+}
 
-    let branch = String::from_utf8_lossy(&output.stdout);
-    let branch = branch.trim();
+/// Gets the commit a branch points to on a remote or `None` if the remote
+/// doesn't have the branch.
+///
+/// # Errors
+///
+/// Returns an error if the remote can't be reached.
+pub fn get_remote_branch_sha(remote: &str, branch: &str) -> anyhow::Result<Option<String>> {
+    get_remote_branch_sha_with(Command::new("git"), remote, branch)
+}
+
+fn get_remote_branch_sha_with(
+    command: Command,
+    remote: &str,
+    branch: &str,
+) -> anyhow::Result<Option<String>> {
+    let output = run_git_command(
+        command,
+        &["ls-remote", remote, &format!("refs/heads/{branch}")],
+        RunOptions::default(),
+    )
+    .with_context(|| format!("Failed to look up branch \"{branch}\" on {remote}"))?;
+
+    Ok(output.split_whitespace().next().map(String::from))
+}
+
+/// Clones a repository into a new directory in the current directory.
+///
+/// # Errors
+///
+/// Returns an error if the clone fails.
+pub fn clone_repo(url: &str) -> anyhow::Result<()> {
+    run_git(&["clone", url], RunOptions { interactive: true })
+        .with_context(|| format!("Failed to clone {url}"))?;
+
+    Ok(())
+}
+
+/// Gets the name of the current git branch.
+///
+/// # Errors
+///
+/// Returns an error if the git operation fails or no branch is checked out.
+pub fn get_current_branch() -> anyhow::Result<String> {
+    let branch = run_git(&["branch", "--show-current"], RunOptions::default())
+        .context("Failed to get current branch")?;
 
     if branch.is_empty() {
         anyhow::bail!("No branch is currently checked out (detached HEAD state)");
     }
 
-    Ok(branch.to_string())
+    Ok(branch)
+}
+
+/// Gets the committer date of the commit a tag points to as an RFC 3339
+/// timestamp.
+///
+/// # Errors
+///
+/// Returns an error if the tag doesn't exist.
+pub fn get_tag_date(tag: &str) -> anyhow::Result<String> {
+    run_git(
+        &["log", "-1", "--format=%cI", &format!("refs/tags/{tag}")],
+        RunOptions::default(),
+    )
+    .with_context(|| format!("Failed to get the date of tag '{tag}'"))
 }
 
 /// Gets the default branch for a remote.
@@ -124,31 +244,23 @@ pub fn get_current_branch() -> anyhow::Result<String> {
 ///
 /// Returns an error if we can't determine the default branch.
 pub fn get_default_branch(remote: &str) -> anyhow::Result<String> {
-    let output = Command::new("git")
-        .args(["symbolic-ref", &format!("refs/remotes/{remote}/HEAD")])
-        .output()
-        .with_context(|| format!("Failed to get default branch for '{remote}'"))?;
-
-    if output.status.success() {
-        let ref_name = String::from_utf8_lossy(&output.stdout);
-        let ref_name = ref_name.trim();
+    let head_ref = format!("refs/remotes/{remote}/HEAD");
 
-        if let Some(branch) = ref_name.split('/').next_back() {
-            return Ok(branch.to_string());
-        }
+    if let Ok(ref_name) = run_git(&["symbolic-ref", &head_ref], RunOptions::default())
+        && let Some(branch) = ref_name.split('/').next_back()
+    {
+        return Ok(branch.to_string());
     }
 
     for branch in ["main", "master"] {
-        let output = Command::new("git")
-            .args(["rev-parse", "--verify", &format!("{remote}/{branch}")])
-            .output()
-            .with_context(|| {
-                format!(
-                    "Failed to get default branch when falling back to main or master for '{remote}'",
-                )
-            })?;
-
-        if output.status.success() {
+        let remote_branch = format!("{remote}/{branch}");
+
+        if run_git(
+            &["rev-parse", "--verify", &remote_branch],
+            RunOptions::default(),
+        )
+        .is_ok()
+        {
             return Ok(branch.to_string());
         }
     }
@@ -170,45 +282,80 @@ pub fn push_branch(branch: &str, remote: &str, set_upstream: bool) -> anyhow::Re
         args.push("-u");
     }
 
-    let output = Command::new("git")
-        .args(&args)
-        .output()
-        .with_context(|| format!("Failed to execute git push for branch '{branch}'"))?;
+    let result = run_git(&args, RunOptions { interactive: true });
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to push branch \"{branch}\" to {remote}: {stderr}");
-    }
+    audit::record_push(remote, branch, result.is_ok());
+    result.with_context(|| format!("Failed to push branch \"{branch}\" to {remote}"))?;
 
     Ok(())
 }
 
-/// Parses commit-ish into their corresponding commit SHAs
+/// Fetches a branch from a remote, which also updates its remote-tracking
+/// branch `<remote>/<branch>`.
 ///
 /// # Errors
 ///
-/// Returns an error if the push operation fails.
-pub fn rev_parse(arg: &str) -> anyhow::Result<String> {
-    let output = Command::new("git")
-        .arg("rev-parse")
-        .arg(arg)
-        .output()
-        .context("Failed to execute git-rev-parse")?;
+/// Returns an error if the fetch operation fails.
+pub fn fetch_branch(remote: &str, branch: &str) -> anyhow::Result<()> {
+    fetch_branch_with(Command::new("git"), remote, branch)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+fn fetch_branch_with(command: Command, remote: &str, branch: &str) -> anyhow::Result<()> {
+    run_git_command(
+        command,
+        &["fetch", remote, branch],
+        RunOptions { interactive: true },
+    )
+    .with_context(|| format!("Failed to fetch branch \"{branch}\" from {remote}"))?;
+
+    Ok(())
+}
 
-        anyhow::bail!("Failed to resolve '{arg}' to a commit: {stderr}");
+/// Counts the commits that `head` is ahead and behind of `base` and returns
+/// them in this order.
+///
+/// # Errors
+///
+/// Returns an error if the git command fails; e.g. if a ref doesn't exist.
+pub fn ahead_behind(base: &str, head: &str) -> anyhow::Result<(u32, u32)> {
+    ahead_behind_with(Command::new("git"), base, head)
+}
+
+fn ahead_behind_with(command: Command, base: &str, head: &str) -> anyhow::Result<(u32, u32)> {
+    let output = run_git_command(
+        command,
+        &[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{base}...{head}"),
+        ],
+        RunOptions::default(),
+    )
+    .with_context(|| format!("Failed to compare {head} with {base}"))?;
+
+    // The left side are the commits only in `base`, the right side the ones
+    // only in `head`
+    match output.split_whitespace().collect::<Vec<_>>()[..] {
+        [behind, ahead] => Ok((ahead.parse()?, behind.parse()?)),
+        _ => anyhow::bail!("Unexpected output of git rev-list: {output}"),
     }
+}
 
-    let sha = String::from_utf8_lossy(&output.stdout);
-    let sha = sha.trim();
+/// Parses commit-ish into their corresponding commit SHAs
+///
+/// # Errors
+///
+/// Returns an error if the push operation fails.
+pub fn rev_parse(arg: &str) -> anyhow::Result<String> {
+    let sha = run_git(&["rev-parse", arg], RunOptions::default())
+        .with_context(|| format!("Failed to resolve '{arg}' to a commit"))?;
 
     if sha.is_empty() {
         anyhow::bail!("git rev-parse returned no commit hash for '{arg}'");
     }
 
-    Ok(sha.to_string())
+    Ok(sha)
 }
 
 /// Gets the absolute path of the git repository
@@ -228,18 +375,13 @@ pub fn get_absolute_repo_root() -> anyhow::Result<String> {
 ///
 /// Returns an error if the git command fails.
 pub fn get_commit_range(start: &str, end: &str) -> anyhow::Result<Vec<String>> {
-    let output = Command::new("git")
-        .args(["log", "--format=%H", &format!("{start}..{end}")])
-        .output()
-        .context("Failed to execute git log")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        anyhow::bail!("Failed to get commits between {start} and {end}: {stderr}");
-    }
+    let output = run_git(
+        &["log", "--format=%H", &format!("{start}..{end}")],
+        RunOptions::default(),
+    )
+    .with_context(|| format!("Failed to get commits between {start} and {end}"))?;
 
-    let commits = String::from_utf8_lossy(&output.stdout)
+    let commits = output
         .lines()
         .filter_map(|line| {
             let line = line.trim();
@@ -263,18 +405,11 @@ pub fn get_commit_range(start: &str, end: &str) -> anyhow::Result<Vec<String>> {
 ///
 /// Returns an error if the git command fails.
 pub fn get_commit_message(commit_sha: &str) -> anyhow::Result<(String, String)> {
-    let output = Command::new("git")
-        .args(["log", "-1", "--format=%s%n%n%b", commit_sha])
-        .output()
-        .with_context(|| format!("Failed to get commit message for {commit_sha}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-
-        anyhow::bail!("Failed to get commit message for {commit_sha}: {stderr}");
-    }
-
-    let message = String::from_utf8_lossy(&output.stdout).to_string();
+    let message = run_git(
+        &["log", "-1", "--format=%s%n%n%b", commit_sha],
+        RunOptions::default(),
+    )
+    .with_context(|| format!("Failed to get commit message for {commit_sha}"))?;
     let lines = message.lines().collect::<Vec<_>>();
 
     if lines.is_empty() {
@@ -288,7 +423,7 @@ pub fn get_commit_message(commit_sha: &str) -> anyhow::Result<(String, String)>
 }
 
 /// Parsed data from a git remote URL.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GitRemoteData {
     /// The hostname (e.g., "github.com", "gitlab.com").
     pub host: String,
@@ -296,17 +431,25 @@ pub struct GitRemoteData {
     pub path: String,
     /// The port number, if specified in the URL.
     pub port: Option<u16>,
+    /// The base URL of the web interface, i.e. `<scheme>://<host>[:<port>]`.
+    /// It's derived from the remote URL, where SSH remotes use https without
+    /// the SSH port, unless the host-scoped `web-base-url` setting overrides
+    /// it.
+    pub web_base_url: String,
+    /// The host-scoped `api-base-url` setting. If it isn't set, the forge
+    /// derives the API base URL from `web_base_url`.
+    pub api_base_url: Option<String>,
 }
 
 /// Parses a git remote URL into its components.
 ///
 /// Supports the following URL formats:
-/// - HTTPS: `https://<host>[:<port>]/<user>/<repo>.git`
+/// - HTTP(S): `http[s]://<host>[:<port>]/<user>/<repo>.git`
 /// - SSH: `ssh://git@<host>[:<port>]/<user>/<repo>.git`
 /// - Git SSH: `git@<host>:<user>/<repo>.git` (port not supported in this format)
 pub fn parse_remote_url(url: &str) -> Option<GitRemoteData> {
-    // https://<host>[:<port>]/<user>/<repo>.git
-    if let Some(rest) = url.strip_prefix("https://") {
+    // http[s]://<host>[:<port>]/<user>/<repo>.git
+    if let Some((scheme @ ("https" | "http"), rest)) = url.split_once("://") {
         let parts: Vec<&str> = rest.splitn(2, '/').collect();
 
         if parts.len() != 2 {
@@ -314,15 +457,22 @@ pub fn parse_remote_url(url: &str) -> Option<GitRemoteData> {
         }
 
         let (host, port) = match parse_host_port(parts[0]) {
-            Ok(v) => v,
+            Ok((host, port)) => (normalize_host(&host), port),
             Err(_) => return None,
         };
-        let path = parts[1]
-            .strip_suffix(".git")
-            .unwrap_or(parts[1])
-            .to_string();
+        let path = normalize_repo_path(parts[1]);
+        let web_base_url = match port {
+            Some(port) => format!("{scheme}://{host}:{port}"),
+            None => format!("{scheme}://{host}"),
+        };
 
-        return Some(GitRemoteData { host, path, port });
+        return Some(GitRemoteData {
+            web_base_url,
+            host,
+            path,
+            port,
+            api_base_url: None,
+        });
     }
 
     // ssh://git@<host>[:<port>]/<user>/<repo>.git
@@ -334,15 +484,19 @@ pub fn parse_remote_url(url: &str) -> Option<GitRemoteData> {
         }
 
         let (host, port) = match parse_host_port(parts[0]) {
-            Ok(v) => v,
+            Ok((host, port)) => (normalize_host(&host), port),
             Err(_) => return None,
         };
-        let path = parts[1]
-            .strip_suffix(".git")
-            .unwrap_or(parts[1])
-            .to_string();
+        let path = normalize_repo_path(parts[1]);
 
-        return Some(GitRemoteData { host, path, port });
+        // The port is the SSH port, which the web interface doesn't use
+        return Some(GitRemoteData {
+            web_base_url: format!("https://{host}"),
+            host,
+            path,
+            port,
+            api_base_url: None,
+        });
     }
 
     // git@<host>:<user>/<repo>.git
@@ -353,23 +507,41 @@ pub fn parse_remote_url(url: &str) -> Option<GitRemoteData> {
             return None;
         }
 
-        let host = parts[0].to_string();
-        let path = parts[1]
-            .strip_suffix(".git")
-            .unwrap_or(parts[1])
-            .to_string();
+        let host = normalize_host(parts[0]);
+        let path = normalize_repo_path(parts[1]);
 
         return Some(GitRemoteData {
+            web_base_url: format!("https://{host}"),
             host,
             path,
             port: None,
+            api_base_url: None,
         });
     }
 
     None
 }
 
-fn parse_host_port(host_str: &str) -> anyhow::Result<(String, Option<u16>)> {
+/// Hostnames are case-insensitive and may be written fully qualified with a
+/// trailing dot, e.g. `GitHub.com.`, so they are normalized to compare them
+/// and to use them in the config keys.
+fn normalize_host(host: &str) -> String {
+    host.trim_end_matches('.').to_lowercase()
+}
+
+/// Strips trailing slashes and the `.git` suffix from the path of a remote URL.
+fn normalize_repo_path(path: &str) -> String {
+    let path = path.trim_end_matches('/');
+
+    path.strip_suffix(".git").unwrap_or(path).to_string()
+}
+
+/// Parses `<HOST>[:<PORT>]` into the host and the optional port.
+///
+/// # Errors
+///
+/// Returns an error if the port isn't a valid number.
+pub fn parse_host_port(host_str: &str) -> anyhow::Result<(String, Option<u16>)> {
     if let Some(colon_pos) = host_str.rfind(':') {
         let host = host_str[..colon_pos].to_string();
         let port_str = &host_str[colon_pos + 1..];
@@ -383,10 +555,367 @@ fn parse_host_port(host_str: &str) -> anyhow::Result<(String, Option<u16>)> {
     }
 }
 
+/// Options for `run_git`.
+#[derive(Default)]
+struct RunOptions {
+    /// Whether the command may need user interaction, e.g. credential prompts
+    /// of askpass programs or credential helpers. If so, stdin is inherited
+    /// and the output is always streamed.
+    interactive: bool,
+}
+
+/// Runs git with the given arguments and returns its trimmed stdout.
+///
+/// # Errors
+///
+/// Returns an error if git can't be executed or exits unsuccessfully. The
+/// error contains git's stderr.
+fn run_git(args: &[&str], opts: RunOptions) -> anyhow::Result<String> {
+    run_git_command(Command::new("git"), args, opts)
+}
+
+/// See `run_git`. If the output is streamed, it goes to stderr so that it
+/// doesn't mix with our own output. Either way, it is also captured for the
+/// return value and error messages.
+fn run_git_command(
+    mut command: Command,
+    args: &[&str],
+    opts: RunOptions,
+) -> anyhow::Result<String> {
+    let command_line = format!("git {}", args.join(" "));
+    let stream = opts.interactive || VERBOSE.load(Ordering::Relaxed);
+
+    log::debug!("Running `{command_line}`");
+
+    let mut child = command
+        .args(args)
+        .stdin(if opts.interactive {
+            Stdio::inherit()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => git_not_found_error(),
+            _ => anyhow::Error::new(e).context(format!("Failed to execute `{command_line}`")),
+        })?;
+    let mut child_stdout = child.stdout.take().expect("stdout should be piped");
+    let mut child_stderr = child.stderr.take().expect("stderr should be piped");
+    // Read both pipes concurrently so that neither can fill up and block git
+    let stderr_reader = thread::spawn(move || capture(&mut child_stderr, stream));
+    let stdout = capture(&mut child_stdout, stream);
+    let stderr = stderr_reader.join().unwrap_or_default();
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for `{command_line}`"))?;
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr).trim().to_string();
+
+        anyhow::bail!(
+            "`{command_line}` failed: {}",
+            if stderr.is_empty() {
+                status.to_string()
+            } else {
+                stderr
+            }
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+}
+
+/// The error for a missing git executable. It's a usage error so that the
+/// message is printed on its own instead of the chain of the failed commands
+/// and the process exits with the usage exit code. Only the `config`
+/// subcommands have a --repo option to work without a git remote.
+fn git_not_found_error() -> anyhow::Error {
+    clap::Error::raw(
+        clap::error::ErrorKind::Io,
+        "the `git` executable was not found in PATH; install git or, for the \
+        `config get/set/unset` commands, use --repo <HOST[:PORT]/OWNER/REPO>\n",
+    )
+    .into()
+}
+
+/// Reads everything from the reader and returns it. If `stream` is set, the
+/// data is also copied to stderr as soon as it arrives so that progress
+/// output and prompts show up immediately.
+fn capture(reader: &mut impl Read, stream: bool) -> Vec<u8> {
+    let mut captured = vec![];
+    let mut buffer = [0; 1024];
+    let mut stderr = io::stderr();
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                if stream {
+                    let _ = stderr.write_all(&buffer[..n]);
+                }
+
+                captured.extend_from_slice(&buffer[..n]);
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+
+    captured
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Creates a fake `git` in a temporary directory and returns a command
+    /// that finds it first on its PATH.
+    #[cfg(unix)]
+    fn fake_git(script: &str) -> (tempfile::TempDir, Command) {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("git");
+
+        fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut command = Command::new("git");
+
+        command.env("PATH", dir.path());
+
+        (dir, command)
+    }
+
+    #[test]
+    fn test_run_git_command_reports_missing_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut command = Command::new("git");
+
+        command.env("PATH", dir.path());
+
+        let error = run_git_command(command, &["status"], RunOptions::default())
+            .context("Failed to get the status")
+            .unwrap_err();
+        let clap_error = error.downcast_ref::<clap::Error>().unwrap();
+
+        assert_eq!(clap_error.exit_code(), 2);
+        assert!(
+            clap_error
+                .to_string()
+                .contains("`git` executable was not found")
+        );
+    }
+
+    #[test]
+    fn test_capture_returns_everything() {
+        let output = b"Username for 'https://example.com': \nerror: denied\n";
+
+        assert_eq!(capture(&mut &output[..], false), output);
+        assert_eq!(capture(&mut &output[..], true), output);
+    }
+
+    #[test]
+    fn test_run_git_returns_stderr_on_failure() {
+        let result = run_git(
+            &["fetch", "/does/not/exist"],
+            RunOptions { interactive: true },
+        );
+
+        assert!(result.is_err_and(|e| e.to_string().contains("fatal")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_git_command_returns_trimmed_stdout() {
+        let (_dir, command) = fake_git(r#"echo "  $*  ""#);
+        let output = run_git_command(command, &["log", "-1"], RunOptions::default());
+
+        assert_eq!(output.unwrap(), "log -1");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_git_command_formats_errors() {
+        let (_dir, command) = fake_git("echo out; echo 'fatal: no such ref ' >&2; exit 128");
+        let error = run_git_command(command, &["fetch", "origin"], RunOptions::default());
+
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "`git fetch origin` failed: fatal: no such ref"
+        );
+
+        let (_dir, command) = fake_git("exit 3");
+        let error = run_git_command(command, &["push"], RunOptions::default());
+
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "`git push` failed: exit status: 3"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_git_command_captures_streamed_output() {
+        let (_dir, command) = fake_git("echo progress >&2; echo done");
+        let output = run_git_command(command, &["fetch"], RunOptions { interactive: true });
+
+        assert_eq!(output.unwrap(), "done");
+    }
+
+    /// Creates a repository with a commit on `main` in a temporary directory
+    /// and returns a function that creates git commands running in it.
+    fn scripted_repo() -> (tempfile::TempDir, impl Fn() -> Command) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+        let git = move || {
+            let mut command = Command::new("git");
+
+            command
+                .current_dir(&path)
+                .env("GIT_AUTHOR_NAME", "Test User")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "Test User")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com");
+
+            command
+        };
+        let run = |args: &[&str]| {
+            run_git_command(git(), args, RunOptions::default()).unwrap();
+        };
+
+        run(&["init", "--initial-branch=main"]);
+        run(&["commit", "--allow-empty", "-m", "Initial commit"]);
+
+        (dir, git)
+    }
+
+    #[test]
+    fn test_ahead_behind() {
+        let (_dir, git) = scripted_repo();
+        let run = |args: &[&str]| {
+            run_git_command(git(), args, RunOptions::default()).unwrap();
+        };
+
+        run(&["checkout", "-b", "feature"]);
+        run(&["commit", "--allow-empty", "-m", "Feature 1"]);
+        run(&["commit", "--allow-empty", "-m", "Feature 2"]);
+        run(&["checkout", "main"]);
+
+        for message in ["Main 1", "Main 2", "Main 3"] {
+            run(&["commit", "--allow-empty", "-m", message]);
+        }
+
+        assert_eq!(ahead_behind_with(git(), "main", "feature").unwrap(), (2, 3));
+        assert_eq!(ahead_behind_with(git(), "feature", "main").unwrap(), (3, 2));
+        assert_eq!(ahead_behind_with(git(), "main", "main").unwrap(), (0, 0));
+        assert!(ahead_behind_with(git(), "main", "missing").is_err());
+    }
+
+    #[test]
+    fn test_fetch_branch_updates_remote_tracking_branch() {
+        let (upstream_dir, upstream_git) = scripted_repo();
+        let (_dir, git) = scripted_repo();
+        let upstream_path = upstream_dir.path().to_str().unwrap();
+
+        run_git_command(
+            upstream_git(),
+            &["commit", "--allow-empty", "-m", "Upstream change"],
+            RunOptions::default(),
+        )
+        .unwrap();
+        run_git_command(
+            git(),
+            &["remote", "add", "origin", upstream_path],
+            RunOptions::default(),
+        )
+        .unwrap();
+
+        fetch_branch_with(git(), "origin", "main").unwrap();
+
+        assert_eq!(
+            run_git_command(git(), &["rev-parse", "origin/main"], RunOptions::default()).unwrap(),
+            run_git_command(
+                upstream_git(),
+                &["rev-parse", "HEAD"],
+                RunOptions::default()
+            )
+            .unwrap()
+        );
+        assert!(fetch_branch_with(git(), "origin", "missing").is_err());
+    }
+
+    #[test]
+    fn test_branch_pr() {
+        let (_dir, git) = scripted_repo();
+
+        assert_eq!(get_branch_pr_with(git(), "pr-7"), None);
+
+        set_branch_pr_with(git(), "pr-7", 7).unwrap();
+
+        assert_eq!(get_branch_pr_with(git(), "pr-7"), Some(7));
+        assert_eq!(get_branch_pr_with(git(), "main"), None);
+    }
+
+    #[test]
+    fn test_set_branch_tracking_lets_git_pull_the_pr_ref() {
+        let (upstream_dir, upstream_git) = scripted_repo();
+        let (_dir, git) = scripted_repo();
+        let upstream_path = upstream_dir.path().to_str().unwrap();
+        let run = |git: Command, args: &[&str]| {
+            run_git_command(git, args, RunOptions::default()).unwrap()
+        };
+
+        run(upstream_git(), &["update-ref", "refs/pull/1/head", "HEAD"]);
+        run(git(), &["remote", "add", "origin", upstream_path]);
+        run(git(), &["fetch", "origin", "pull/1/head:pr-1"]);
+        run(git(), &["checkout", "pr-1"]);
+
+        set_branch_tracking_with(&git, "pr-1", "origin", "refs/pull/1/head").unwrap();
+
+        assert_eq!(run(git(), &["config", "branch.pr-1.remote"]), "origin");
+        assert_eq!(
+            run(git(), &["config", "branch.pr-1.merge"]),
+            "refs/pull/1/head"
+        );
+
+        run(
+            upstream_git(),
+            &["commit", "--allow-empty", "-m", "Update the PR"],
+        );
+        run(upstream_git(), &["update-ref", "refs/pull/1/head", "HEAD"]);
+        run(git(), &["pull", "--ff-only"]);
+
+        assert_eq!(
+            run(git(), &["rev-parse", "HEAD"]),
+            run(upstream_git(), &["rev-parse", "HEAD"])
+        );
+    }
+
+    #[test]
+    fn test_get_remote_branch_sha() {
+        let (upstream_dir, upstream_git) = scripted_repo();
+        let upstream_path = upstream_dir.path().to_str().unwrap();
+        let head = run_git_command(
+            upstream_git(),
+            &["rev-parse", "HEAD"],
+            RunOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_remote_branch_sha_with(upstream_git(), upstream_path, "main").unwrap(),
+            Some(head)
+        );
+        assert_eq!(
+            get_remote_branch_sha_with(upstream_git(), upstream_path, "missing").unwrap(),
+            None
+        );
+        assert!(get_remote_branch_sha_with(upstream_git(), "/does/not/exist", "main").is_err());
+    }
+
     #[test]
     fn test_parse_remote_url_https() {
         // https://github.com/user/repo.git
@@ -399,6 +928,8 @@ mod tests {
                 host: "github.com".to_string(),
                 path: "user/repo".to_string(),
                 port: None,
+                web_base_url: "https://github.com".to_string(),
+                api_base_url: None,
             }
         );
 
@@ -412,6 +943,8 @@ mod tests {
                 host: "github.com".to_string(),
                 path: "user/repo".to_string(),
                 port: None,
+                web_base_url: "https://github.com".to_string(),
+                api_base_url: None,
             }
         );
     }
@@ -428,6 +961,8 @@ mod tests {
                 host: "gitlab.example.com".to_string(),
                 path: "user/repo".to_string(),
                 port: Some(8443),
+                web_base_url: "https://gitlab.example.com:8443".to_string(),
+                api_base_url: None,
             }
         );
 
@@ -441,6 +976,25 @@ mod tests {
                 host: "localhost".to_string(),
                 path: "user/repo".to_string(),
                 port: Some(3000),
+                web_base_url: "https://localhost:3000".to_string(),
+                api_base_url: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_remote_url_http() {
+        // http://gitea.internal:3000/user/repo.git
+        let result = parse_remote_url("http://gitea.internal:3000/user/repo.git");
+
+        assert_eq!(
+            result.unwrap(),
+            GitRemoteData {
+                host: "gitea.internal".to_string(),
+                path: "user/repo".to_string(),
+                port: Some(3000),
+                web_base_url: "http://gitea.internal:3000".to_string(),
+                api_base_url: None,
             }
         );
     }
@@ -457,6 +1011,8 @@ mod tests {
                 host: "github.com".to_string(),
                 path: "user/repo".to_string(),
                 port: None,
+                web_base_url: "https://github.com".to_string(),
+                api_base_url: None,
             }
         );
 
@@ -470,6 +1026,8 @@ mod tests {
                 host: "github.com".to_string(),
                 path: "user/repo".to_string(),
                 port: None,
+                web_base_url: "https://github.com".to_string(),
+                api_base_url: None,
             }
         );
     }
@@ -486,6 +1044,8 @@ mod tests {
                 host: "gitlab.example.com".to_string(),
                 path: "user/repo".to_string(),
                 port: Some(2222),
+                web_base_url: "https://gitlab.example.com".to_string(),
+                api_base_url: None,
             }
         );
 
@@ -499,6 +1059,8 @@ mod tests {
                 host: "localhost".to_string(),
                 path: "user/repo".to_string(),
                 port: Some(22022),
+                web_base_url: "https://localhost".to_string(),
+                api_base_url: None,
             }
         );
     }
@@ -515,6 +1077,8 @@ mod tests {
                 host: "github.com".to_string(),
                 path: "user/repo".to_string(),
                 port: None,
+                web_base_url: "https://github.com".to_string(),
+                api_base_url: None,
             }
         );
 
@@ -528,6 +1092,40 @@ mod tests {
                 host: "github.com".to_string(),
                 path: "user/repo".to_string(),
                 port: None,
+                web_base_url: "https://github.com".to_string(),
+                api_base_url: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_remote_url_normalizes_hosts_and_paths() {
+        let expected = GitRemoteData {
+            host: "github.com".to_string(),
+            path: "User/Repo".to_string(),
+            port: None,
+            web_base_url: "https://github.com".to_string(),
+            api_base_url: None,
+        };
+
+        for url in [
+            "https://GitHub.COM/User/Repo.git",
+            "https://github.com./User/Repo/",
+            "https://github.com/User/Repo.git/",
+            "ssh://git@GitHub.com/User/Repo.git",
+            "git@GITHUB.com.:User/Repo/",
+        ] {
+            assert_eq!(parse_remote_url(url), Some(expected.clone()), "{url}");
+        }
+
+        assert_eq!(
+            parse_remote_url("http://Gitea.Internal:3000/user/repo/").unwrap(),
+            GitRemoteData {
+                host: "gitea.internal".to_string(),
+                path: "user/repo".to_string(),
+                port: Some(3000),
+                web_base_url: "http://gitea.internal:3000".to_string(),
+                api_base_url: None,
             }
         );
     }
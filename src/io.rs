@@ -1,11 +1,98 @@
 //! Input/Output utilities.
 
+use std::{
+    borrow::Cow,
+    ffi::OsStr,
+    io::{BufRead, IsTerminal, Write},
+    iter::Peekable,
+    marker::PhantomData,
+    process::{Command, Stdio},
+    str::Chars,
+    sync::OnceLock,
+};
+
 use anyhow::Context;
-use clap::ValueEnum;
-use csv::WriterBuilder;
-use dialoguer::Editor;
+use clap::{
+    ValueEnum,
+    builder::{PossibleValue, TypedValueParser},
+    error::ErrorKind,
+};
+use csv::{QuoteStyle, WriterBuilder};
+use dialoguer::{Editor, Input, MultiSelect};
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::date::{DateFormat, Timestamp};
+
+/// The largest page size that all forges accept.
+const MAX_PER_PAGE: u32 = 100;
+const STYLE_DIM: &str = "\x1b[2m";
+const STYLE_GREEN: &str = "\x1b[32m";
+const STYLE_RED: &str = "\x1b[31m";
+const STYLE_YELLOW: &str = "\x1b[33m";
+const STYLE_RESET: &str = "\x1b[0m";
+
+static OUTPUT_SETTINGS: OnceLock<OutputSettings> = OnceLock::new();
+static PROMPT_MODE: OnceLock<PromptMode> = OnceLock::new();
+
+/// Settings of the output that apply to the whole process. They are set once
+/// from the global options in `run()`. The TUI doesn't use them since it
+/// draws the whole terminal itself.
+#[derive(Clone, Debug, Default)]
+pub struct OutputSettings {
+    /// Whether the output on stdout may be styled, resolved from --color and
+    /// the environment.
+    pub color: bool,
+    /// Whether the fields of TSV and CSV output are single-quoted for the
+    /// shell (--escape-shell).
+    pub escape_shell: bool,
+    /// How the human-readable output shows timestamps. TSV, CSV and JSON
+    /// always use RFC 3339, so that scripts don't depend on the settings.
+    pub date_style: DateStyle,
+}
+
+/// How timestamps are shown in human-readable output, set with the
+/// `locale/date-format` and `locale/relative-dates` settings.
+#[derive(Clone, Debug, Default)]
+pub enum DateStyle {
+    /// RFC 3339 in UTC, e.g. `2024-03-01T12:00:00Z`.
+    #[default]
+    Rfc3339,
+    /// A custom strftime format.
+    Format(DateFormat),
+    /// How long ago, e.g. `3d ago`.
+    Relative,
+}
+
+/// When to style the output (--color).
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ColorChoice {
+    /// Style the output if stdout is a terminal, unless the NO_COLOR
+    /// environment variable is set. CLICOLOR_FORCE styles piped output, too.
+    #[default]
+    Auto,
+    /// Always style the output, e.g. when piping into `less -R`.
+    Always,
+    /// Never style the output.
+    Never,
+}
+
+/// When the user is asked (--prompt).
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum PromptMode {
+    /// Ask if stdin and stderr are terminals. Otherwise, fall back to the
+    /// defaults or fail if there is none.
+    #[default]
+    Auto,
+    /// Never ask. Every question fails with the option that answers it
+    /// instead, e.g. so that CI jobs don't hang.
+    Never,
+    /// Also ask for the confirmations that --yes skips. Fails without a
+    /// terminal.
+    Always,
+}
 
 #[derive(Debug)]
 pub struct InputMessage {
@@ -87,6 +174,346 @@ fn prompt_with_text_editor(cmd: Option<&str>) -> anyhow::Result<InputMessage> {
     })
 }
 
+/// Checks whether the user can be asked, i.e. stdin and stderr are terminals.
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// Sets when the user is asked. Called once from the global options in
+/// `run()`.
+pub fn set_prompt_mode(mode: PromptMode) {
+    let _ = PROMPT_MODE.set(mode);
+}
+
+/// Checks whether a confirmation is skipped, i.e. `yes` (--yes) is given and
+/// --prompt always doesn't force the confirmation anyway.
+pub fn skips_confirmation(yes: bool) -> bool {
+    yes && PROMPT_MODE.get().copied().unwrap_or_default() != PromptMode::Always
+}
+
+/// Checks whether the user may be asked `prompt`. All prompts go through here,
+/// so that they follow --prompt. `flag` names the option that answers the
+/// prompt for the errors, e.g. `--title`.
+fn can_prompt(prompt: &str, flag: &str) -> anyhow::Result<bool> {
+    check_prompt(
+        PROMPT_MODE.get().copied().unwrap_or_default(),
+        is_interactive(),
+        prompt,
+        flag,
+    )
+}
+
+fn check_prompt(
+    mode: PromptMode,
+    is_interactive: bool,
+    prompt: &str,
+    flag: &str,
+) -> anyhow::Result<bool> {
+    match mode {
+        PromptMode::Auto => Ok(is_interactive),
+        PromptMode::Never => {
+            anyhow::bail!("Can't ask \"{prompt}\" with --prompt never. Pass {flag} instead")
+        }
+        PromptMode::Always if is_interactive => Ok(true),
+        PromptMode::Always => {
+            anyhow::bail!("Can't ask \"{prompt}\" without a terminal (--prompt always)")
+        }
+    }
+}
+
+/// Asks the user for a line of text. Unlike the other prompts, there is no
+/// default to fall back to, so it fails without a terminal.
+pub fn prompt_text(prompt: &str, flag: &str) -> anyhow::Result<String> {
+    if !can_prompt(prompt, flag)? {
+        anyhow::bail!("Can't ask \"{prompt}\" without a terminal. Pass {flag} instead");
+    }
+
+    Ok(Input::new().with_prompt(prompt).interact_text()?)
+}
+
+/// Lets the user pick labels from a checkbox list if stdin and stderr are
+/// terminals. Picking labels is optional, so if fetching the labels fails, the
+/// selection is skipped with a warning instead of failing.
+pub fn prompt_labels(
+    fetch_labels: impl FnOnce() -> anyhow::Result<Vec<String>>,
+    flag: &str,
+) -> anyhow::Result<Vec<String>> {
+    let prompt = "Select labels (space to toggle, enter to confirm)";
+
+    if !can_prompt(prompt, flag)? {
+        return Ok(vec![]);
+    }
+
+    let labels = match fetch_labels() {
+        Ok(labels) if labels.is_empty() => return Ok(vec![]),
+        Ok(labels) => labels,
+        Err(e) => {
+            log::warn!("Skipping the label selection: {e:#}");
+
+            return Ok(vec![]);
+        }
+    };
+
+    match MultiSelect::new()
+        .with_prompt(prompt)
+        .items(&labels)
+        .interact()
+    {
+        Ok(selection) => Ok(selection.into_iter().map(|i| labels[i].clone()).collect()),
+        Err(e) => {
+            log::warn!("Skipping the label selection: {e}");
+
+            Ok(vec![])
+        }
+    }
+}
+
+/// Lets the user pick one of the items by its number or name if stdin and
+/// stderr are terminals. Returns the index of the picked item or `None` if
+/// there is no terminal or the user entered nothing.
+pub fn prompt_select(prompt: &str, items: &[String], flag: &str) -> anyhow::Result<Option<usize>> {
+    if !can_prompt(prompt, flag)? {
+        return Ok(None);
+    }
+
+    select(
+        &mut std::io::stdin().lock(),
+        &mut std::io::stderr(),
+        prompt,
+        items,
+    )
+}
+
+/// Asks the user a yes/no question if stdin and stderr are terminals. Returns
+/// `default` if there is no terminal or the user entered nothing.
+pub fn prompt_confirm(prompt: &str, default: bool, flag: &str) -> anyhow::Result<bool> {
+    if !can_prompt(prompt, flag)? {
+        return Ok(default);
+    }
+
+    confirm(
+        &mut std::io::stdin().lock(),
+        &mut std::io::stderr(),
+        prompt,
+        default,
+    )
+}
+
+/// Lets the user pick what to do with a created item if stdin and stderr are
+/// terminals. The first action is picked if the user entered nothing or if
+/// there is no terminal.
+pub fn prompt_post_action<T: Clone + std::fmt::Display>(
+    prompt: &str,
+    actions: &[T],
+    flag: &str,
+) -> anyhow::Result<T> {
+    let items: Vec<String> = actions.iter().map(ToString::to_string).collect();
+    let index = prompt_select(&format!("{prompt} (Enter: {})", items[0]), &items, flag)?;
+
+    Ok(actions[index.unwrap_or_default()].clone())
+}
+
+/// Commands that copy their stdin to the system clipboard. They are tried in
+/// order since which of them works depends on the OS and display server.
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[
+    &["pbcopy"],
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["clip.exe"],
+];
+
+/// Copies the text to the system clipboard with the first clipboard command
+/// that succeeds.
+pub fn copy_to_clipboard(text: &str) -> anyhow::Result<()> {
+    for command in CLIPBOARD_COMMANDS {
+        let child = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let Ok(mut child) = child else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        if child.wait()?.success() {
+            return Ok(());
+        }
+
+        log::debug!("Copying to the clipboard with {} failed", command[0]);
+    }
+
+    let names: Vec<&str> = CLIPBOARD_COMMANDS
+        .iter()
+        .map(|command| command[0])
+        .collect();
+
+    anyhow::bail!(
+        "Failed to copy to the clipboard. Install one of these commands: {}",
+        names.join(", ")
+    )
+}
+
+/// Prints the numbered items and reads lines from `input` until one of them
+/// is picked by its number or name.
+fn select(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    prompt: &str,
+    items: &[String],
+) -> anyhow::Result<Option<usize>> {
+    writeln!(output, "{prompt}")?;
+
+    for (i, item) in items.iter().enumerate() {
+        writeln!(output, "  {}) {item}", i + 1)?;
+    }
+
+    loop {
+        write!(output, "Select [1-{}]: ", items.len())?;
+        output.flush()?;
+
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let answer = line.trim();
+
+        if answer.is_empty() {
+            return Ok(None);
+        }
+
+        let index = match answer.parse::<usize>() {
+            Ok(number) => number.checked_sub(1).filter(|&i| i < items.len()),
+            Err(_) => items
+                .iter()
+                .position(|item| item.eq_ignore_ascii_case(answer)),
+        };
+
+        match index {
+            Some(index) => return Ok(Some(index)),
+            None => writeln!(output, "Invalid selection '{answer}'")?,
+        }
+    }
+}
+
+/// Reads lines from `input` until the user answers the yes/no question.
+fn confirm(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    prompt: &str,
+    default: bool,
+) -> anyhow::Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+
+    loop {
+        write!(output, "{prompt} {hint} ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+
+        if input.read_line(&mut line)? == 0 {
+            return Ok(default);
+        }
+
+        match line.trim().to_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            answer => writeln!(output, "Invalid answer '{answer}' (expected y or n)")?,
+        }
+    }
+}
+
+/// Prints the text through a pager if stdout is a terminal. The pager is taken
+/// from `GIT_FORGE_PAGER` or `PAGER` and defaults to `less -FRX`. If the pager
+/// can't be started, the text is printed directly.
+pub fn print_with_pager(text: &str) -> anyhow::Result<()> {
+    if !std::io::stdout().is_terminal() {
+        println!("{text}");
+
+        return Ok(());
+    }
+
+    let pager = std::env::var("GIT_FORGE_PAGER")
+        .or_else(|_| std::env::var("PAGER"))
+        .unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut pager_args = pager.split_whitespace();
+    let Some(program) = pager_args.next() else {
+        println!("{text}");
+
+        return Ok(());
+    };
+
+    let mut child = match Command::new(program)
+        .args(pager_args)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            log::debug!("Failed to start the pager '{pager}': {e}");
+            println!("{text}");
+
+            return Ok(());
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything
+        let _ = writeln!(stdin, "{text}");
+    }
+
+    child.wait().context("Failed waiting for the pager")?;
+
+    Ok(())
+}
+
+/// Name of the pseudo-field that expands to all fields.
+const ALL_FIELDS: &str = "all";
+
+/// Aliases of field names as pairs of the alias and the field name, mostly the
+/// names of `gh --json`, so that scripts written for `gh` keep working. The
+/// output always uses the field names. An alias only applies to the commands
+/// that have its field.
+const FIELD_ALIASES: &[(&str, &str)] = &[
+    ("number", "id"),
+    ("iid", "id"),
+    ("stateReason", "state_reason"),
+    ("isPinned", "pinned"),
+    ("createdAt", "created_at"),
+    ("updatedAt", "updated_at"),
+    ("mergedAt", "merged_at"),
+    ("closedAt", "closed_at"),
+    ("headRefName", "source"),
+    ("source_branch", "source"),
+    ("headRefOid", "source_sha"),
+    ("baseRefName", "target"),
+    ("target_branch", "target"),
+    ("baseRefOid", "target_sha"),
+    ("isDraft", "draft"),
+    ("reviewDecision", "review_status"),
+    ("statusCheckRollup", "ci"),
+    ("changedFiles", "changed_files"),
+];
+
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// Appended to truncated text.
+const ELLIPSIS: &str = "…";
+
+/// Cells aren't truncated below this width when fitting rows to the terminal.
+const MIN_CELL_WIDTH: usize = 8;
+
+/// Terminals put tab stops every 8 columns.
+const TAB_WIDTH: usize = 8;
+
 /// Output format.
 #[derive(Clone, Debug, Default, ValueEnum)]
 pub enum OutputFormat {
@@ -97,6 +524,111 @@ pub enum OutputFormat {
     Tsv,
     /// JSON format.
     Json,
+    /// One line per item with its id, title, labels and author, e.g. `#123
+    /// Fix crash in TUI (bug, tui) @alice`. The fields are ignored.
+    #[value(alias = "id-title")]
+    Compact,
+}
+
+/// How `print_list` shortens the output for reading it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Truncation {
+    /// Fit the rows into the terminal's width if stdout is a terminal.
+    pub fit_to_terminal: bool,
+    /// Truncate the titles to this many columns in TSV and compact output.
+    pub max_title_width: Option<usize>,
+}
+
+/// A color of a cell in a TSV table on a terminal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CellColor {
+    Green,
+    Red,
+    Yellow,
+}
+
+impl CellColor {
+    fn style(self) -> &'static str {
+        match self {
+            CellColor::Green => STYLE_GREEN,
+            CellColor::Red => STYLE_RED,
+            CellColor::Yellow => STYLE_YELLOW,
+        }
+    }
+}
+
+/// How the cells of a top-level field look in a TSV table on a terminal.
+/// Piped output keeps the plain values.
+pub struct FieldStyle {
+    pub field: &'static str,
+    /// The symbol that replaces a value, e.g. ✓ for `success`.
+    pub symbol: fn(&str) -> Option<&'static str>,
+    /// The color of a cell by its text, i.e. after the symbol replaced it.
+    /// Only used if the output may be styled, see [`use_color`].
+    pub color: fn(&str) -> Option<CellColor>,
+}
+
+/// Output format of a single created item.
+#[derive(Clone, Debug, Default, ValueEnum)]
+pub enum CreatedItemFormat {
+    /// The web URL of the item.
+    #[default]
+    Url,
+    /// The whole item as a JSON object.
+    Json,
+}
+
+/// Output format of a single result, e.g. the assignees of a changed item.
+#[derive(Clone, Debug, Default, ValueEnum)]
+pub enum ResultFormat {
+    /// A human-readable sentence.
+    #[default]
+    Text,
+    /// The result as a JSON object.
+    Json,
+}
+
+/// Sets the output settings of the process. Only the first call has an
+/// effect.
+pub fn init_output_settings(color: ColorChoice, escape_shell: bool, date_style: DateStyle) {
+    let color = resolve_color(
+        color,
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::env::var_os("CLICOLOR_FORCE").as_deref(),
+        std::io::stdout().is_terminal(),
+    );
+
+    let _ = OUTPUT_SETTINGS.set(OutputSettings {
+        color,
+        escape_shell,
+        date_style,
+    });
+}
+
+/// Gets the output settings of the process. Without `init_output_settings`,
+/// e.g. in tests, nothing is styled or escaped.
+pub fn get_output_settings() -> &'static OutputSettings {
+    static DEFAULT: OutputSettings = OutputSettings {
+        color: false,
+        escape_shell: false,
+        date_style: DateStyle::Rfc3339,
+    };
+
+    OUTPUT_SETTINGS.get().unwrap_or(&DEFAULT)
+}
+
+/// Formats a timestamp for human-readable output with the `date_style` of the
+/// output settings. Machine-readable output serializes the timestamps instead.
+pub fn format_timestamp(timestamp: Timestamp) -> String {
+    format_timestamp_with(timestamp, &get_output_settings().date_style)
+}
+
+fn format_timestamp_with(timestamp: Timestamp, date_style: &DateStyle) -> String {
+    match date_style {
+        DateStyle::Rfc3339 => timestamp.to_string(),
+        DateStyle::Format(format) => timestamp.format_with(format),
+        DateStyle::Relative => format!("{} ago", timestamp.format_age()),
+    }
 }
 
 /// Format a collection of items using the specified output format.
@@ -105,13 +637,87 @@ where
     T: Serialize,
     F: Serialize,
 {
+    let escape_shell = get_output_settings().escape_shell;
+
     match format {
-        OutputFormat::Tsv => format_delimited(items, fields, b'\t'),
-        OutputFormat::Csv => format_delimited(items, fields, b','),
+        OutputFormat::Tsv => format_delimited(items, fields, b'\t', escape_shell),
+        OutputFormat::Csv => format_delimited(items, fields, b',', escape_shell),
         OutputFormat::Json => format_json(items, fields),
+        OutputFormat::Compact => format_compact(items, None, false),
     }
 }
 
+/// Formats each item as a single line of its id, title, labels and author.
+/// Parts that the item doesn't have are left out. With `max_width`, the title
+/// is truncated so that the line fits. With `color`, the labels are dimmed.
+fn format_compact<T: Serialize>(
+    items: &[T],
+    max_width: Option<usize>,
+    color: bool,
+) -> anyhow::Result<String> {
+    let lines = items
+        .iter()
+        .map(|item| {
+            serde_json::to_value(item).map(|value| format_compact_line(&value, max_width, color))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(lines.join("\n"))
+}
+
+fn format_compact_line(item: &Value, max_width: Option<usize>, color: bool) -> String {
+    let id = item
+        .get("id")
+        .map(|id| format!("#{}", stringify_json_value_for_serialization(id)));
+    let title = item
+        .get("title")
+        .and_then(Value::as_str)
+        .map(sanitize_field);
+    let labels = item
+        .get("labels")
+        .and_then(Value::as_array)
+        .filter(|labels| !labels.is_empty())
+        .map(|labels| {
+            let labels: Vec<Cow<str>> = labels
+                .iter()
+                .filter_map(Value::as_str)
+                .map(sanitize_field)
+                .collect();
+
+            format!("({})", labels.join(", "))
+        });
+    let author = item
+        .get("author")
+        .and_then(Value::as_str)
+        .map(|author| format!("@{}", sanitize_field(author)));
+    let title = match (title, max_width) {
+        (Some(title), Some(max_width)) => {
+            let other_parts = [&id, &labels, &author];
+            let other_width: usize = other_parts
+                .iter()
+                .filter_map(|part| part.as_ref())
+                .map(|part| part.width() + 1)
+                .sum();
+            let title_width = max_width.saturating_sub(other_width).max(MIN_CELL_WIDTH);
+
+            Some(Cow::Owned(
+                truncate_to_width(&title, title_width).into_owned(),
+            ))
+        }
+        (title, _) => title,
+    };
+    let labels = match labels {
+        Some(labels) if color => Some(format!("{STYLE_DIM}{labels}{STYLE_RESET}")),
+        labels => labels,
+    };
+
+    [id, title.map(Cow::into_owned), labels, author]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn format_json<T, F>(items: &[T], fields: &[F]) -> anyhow::Result<String>
 where
     T: Serialize,
@@ -129,19 +735,38 @@ where
     let field_names = get_field_names(fields);
     let filtered_items = json_values
         .into_iter()
-        .map(|mut item| {
-            if let Value::Object(ref mut map) = item {
-                map.retain(|key, _| field_names.iter().any(|name| name == key));
-            }
+        .map(|item| match item {
+            Value::Object(mut map) => {
+                // Rebuild the object so that its keys follow the order of the
+                // fields like the columns of the other formats
+                let mut filtered_map = Map::new();
+
+                for name in &field_names {
+                    let key = get_top_level_key(name);
 
-            item
+                    if let Some(value) = map.remove(key) {
+                        filtered_map.insert(key.to_string(), value);
+                    }
+                }
+
+                Value::Object(filtered_map)
+            }
+            item => item,
         })
         .collect::<Vec<Value>>();
 
     Ok(serde_json::to_string_pretty(&filtered_items)?)
 }
 
-fn format_delimited<T, F>(items: &[T], fields: &[F], delimiter: u8) -> anyhow::Result<String>
+/// Formats the items as delimiter-separated values. The fields are sanitized
+/// since they may contain control characters from the forge. With
+/// `escape_shell`, the fields are single-quoted instead of being quoted as CSV.
+fn format_delimited<T, F>(
+    items: &[T],
+    fields: &[F],
+    delimiter: u8,
+    escape_shell: bool,
+) -> anyhow::Result<String>
 where
     T: Serialize,
     F: Serialize,
@@ -149,6 +774,11 @@ where
     let mut writer = WriterBuilder::new()
         .delimiter(delimiter)
         .has_headers(false)
+        .quote_style(if escape_shell {
+            QuoteStyle::Never
+        } else {
+            QuoteStyle::Necessary
+        })
         .from_writer(vec![]);
     let field_names = get_field_names(fields);
 
@@ -159,10 +789,10 @@ where
                 .values()
                 .map(stringify_json_value_for_serialization)
                 .collect(),
-            Value::Object(map) => field_names
+            Value::Object(_) => field_names
                 .iter()
                 .map(|field_name| {
-                    map.get(field_name)
+                    get_value_by_path(&json_value, field_name)
                         .map(stringify_json_value_for_serialization)
                         .unwrap_or_default()
                 })
@@ -170,7 +800,15 @@ where
             _ => vec![stringify_json_value_for_serialization(&json_value)],
         };
 
-        writer.write_record(&record)?;
+        writer.write_record(record.iter().map(|field| {
+            let field = sanitize_field(field);
+
+            if escape_shell {
+                quote_for_shell(&field)
+            } else {
+                field.into_owned()
+            }
+        }))?;
     }
 
     let bytes = writer.into_inner()?;
@@ -183,6 +821,460 @@ where
     Ok(output)
 }
 
+/// Prints the items in the output format. If stdout is a terminal, TSV rows
+/// and compact lines are fit into the terminal's width according to
+/// `truncation` and numeric TSV columns are right-aligned.
+pub fn print_list<T, F>(
+    items: &[T],
+    fields: &[F],
+    format: &OutputFormat,
+    truncation: Truncation,
+) -> anyhow::Result<()>
+where
+    T: Serialize,
+    F: Serialize,
+{
+    print_list_with_dimmed_rows(items, fields, format, truncation, |_| false)
+}
+
+/// Like `print_list` but dims the rows of the items for which `is_dimmed`
+/// returns true if the output is a TSV table on a terminal, e.g. to make
+/// automated entries less prominent.
+pub fn print_list_with_dimmed_rows<T, F>(
+    items: &[T],
+    fields: &[F],
+    format: &OutputFormat,
+    truncation: Truncation,
+    is_dimmed: impl Fn(&T) -> bool,
+) -> anyhow::Result<()>
+where
+    T: Serialize,
+    F: Serialize,
+{
+    print_list_with_styles(items, fields, format, truncation, is_dimmed, &[])
+}
+
+/// Like `print_list_with_dimmed_rows` but also applies the `field_styles` if
+/// the output is a TSV table on a terminal.
+pub fn print_list_with_styles<T, F>(
+    items: &[T],
+    fields: &[F],
+    format: &OutputFormat,
+    truncation: Truncation,
+    is_dimmed: impl Fn(&T) -> bool,
+    field_styles: &[FieldStyle],
+) -> anyhow::Result<()>
+where
+    T: Serialize,
+    F: Serialize,
+{
+    let is_table = matches!(format, OutputFormat::Tsv) && std::io::stdout().is_terminal();
+    let values = items
+        .iter()
+        .map(|item| {
+            serde_json::to_value(item).map(|value| match truncation.max_title_width {
+                Some(max_width) if matches!(format, OutputFormat::Tsv | OutputFormat::Compact) => {
+                    truncate_title(value, max_width)
+                }
+                _ => value,
+            })
+        })
+        .map(|value| match value {
+            Ok(value) if is_table => Ok(replace_with_symbols(value, field_styles)),
+            value => value,
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut output = match format {
+        OutputFormat::Compact => format_compact(
+            &values,
+            get_terminal_width(truncation.fit_to_terminal),
+            use_color(),
+        )?,
+        _ => self::format(&values, fields, format)?,
+    };
+
+    if is_table {
+        output = align_numeric_columns(&output);
+    }
+
+    if truncation.fit_to_terminal {
+        output = fit_to_terminal(output, format);
+    }
+
+    if matches!(format, OutputFormat::Tsv) && use_color() {
+        output = color_cells(&output, &get_field_names(fields), field_styles);
+        output = dim_rows(&output, items, is_dimmed);
+    }
+
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Whether the output on stdout may be styled, see [`ColorChoice`].
+pub fn use_color() -> bool {
+    get_output_settings().color
+}
+
+/// Decides whether to style the output. --color takes precedence over the
+/// environment. With `auto`, a non-empty NO_COLOR disables styles
+/// (https://no-color.org) even if CLICOLOR_FORCE is set. Otherwise, a
+/// CLICOLOR_FORCE other than `0` enables them for output that isn't a
+/// terminal.
+fn resolve_color(
+    choice: ColorChoice,
+    no_color: Option<&OsStr>,
+    clicolor_force: Option<&OsStr>,
+    is_terminal: bool,
+) -> bool {
+    let is_set = |value: Option<&OsStr>| value.is_some_and(|value| !value.is_empty());
+
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if is_set(no_color) => false,
+        ColorChoice::Auto => {
+            is_terminal || (is_set(clicolor_force) && clicolor_force != Some(OsStr::new("0")))
+        }
+    }
+}
+
+/// Gets the width of the terminal if `fit` is set and stdout is a terminal.
+fn get_terminal_width(fit: bool) -> Option<usize> {
+    if !fit || !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    crossterm::terminal::size()
+        .ok()
+        .map(|(width, _)| width as usize)
+}
+
+/// Truncates the `title` of a serialized item for --max-title-width.
+fn truncate_title(mut item: Value, max_width: usize) -> Value {
+    if let Some(Value::String(title)) = item.get_mut("title") {
+        *title = truncate_to_width(title, max_width).into_owned();
+    }
+
+    item
+}
+
+/// Formats the values of the fields of a single item tab-separated on one line
+/// without any decoration, e.g. for `pr view --field url` in a command
+/// substitution. Arrays are joined with commas and missing values are empty.
+pub fn format_field_values<T, F>(item: &T, fields: &[F]) -> anyhow::Result<String>
+where
+    T: Serialize,
+    F: Serialize,
+{
+    let value = serde_json::to_value(item)?;
+
+    Ok(get_field_names(fields)
+        .iter()
+        .map(|name| {
+            let field = get_value_by_path(&value, name)
+                .map(stringify_json_value_for_serialization)
+                .unwrap_or_default();
+
+            sanitize_field(&field).into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\t"))
+}
+
+/// Prints the values of the fields of a single item like
+/// [`format_field_values`] or copies them to the clipboard instead.
+pub fn print_field_values<T, F>(item: &T, fields: &[F], copy: bool) -> anyhow::Result<()>
+where
+    T: Serialize,
+    F: Serialize,
+{
+    let values = format_field_values(item, fields)?;
+
+    if copy {
+        copy_to_clipboard(&values)?;
+
+        eprintln!("Copied {values} to the clipboard");
+    } else {
+        println!("{values}");
+    }
+
+    Ok(())
+}
+
+/// Prints one id per line for `--ids-only`. Unlike `print_list`, it doesn't
+/// serialize the items.
+pub fn print_ids(ids: impl Iterator<Item = u32>) {
+    let mut stdout = std::io::stdout().lock();
+
+    for id in ids {
+        // Ignore errors like a closed pipe, e.g. when piping into `head`.
+        let _ = writeln!(stdout, "{id}");
+    }
+}
+
+/// Fails if the number of listed items violates the `--fail-if-any` or
+/// `--fail-if-none` assertion, which turns listings into CI checks.
+pub fn check_list_assertions(
+    count: usize,
+    fail_if_any: bool,
+    fail_if_none: bool,
+) -> anyhow::Result<()> {
+    if fail_if_any && count > 0 {
+        anyhow::bail!("Expected no matching items but found {count} (--fail-if-any)");
+    }
+
+    if fail_if_none && count == 0 {
+        anyhow::bail!("Found no matching items (--fail-if-none)");
+    }
+
+    Ok(())
+}
+
+/// Gets the page size of a listing. Without --per-page, the page size is the
+/// --limit up to the largest page size the forges allow, so that small limits
+/// need only one small page.
+pub fn get_per_page(per_page: Option<u32>, limit: Option<u32>, default: u32) -> u32 {
+    match (per_page, limit) {
+        (Some(per_page), _) => per_page,
+        (None, Some(limit)) => limit.clamp(1, MAX_PER_PAGE),
+        (None, None) => default,
+    }
+}
+
+/// Checks whether a listing fetches the page after the one that brought it to
+/// `item_count` items. With a --limit, pages are fetched until the limit is
+/// reached. Otherwise, only --all-pages fetches more than one page.
+pub fn should_fetch_next_page(
+    item_count: usize,
+    has_next_page: bool,
+    all_pages: bool,
+    limit: Option<u32>,
+) -> bool {
+    has_next_page
+        && match limit {
+            Some(limit) => item_count < limit as usize,
+            None => all_pages,
+        }
+}
+
+/// Checks whether the pages up to `page` contain fewer items than the
+/// --limit. The interactive TUI stops loading more pages once they don't.
+pub fn is_below_limit(page: u32, per_page: u32, limit: Option<u32>) -> bool {
+    limit.is_none_or(|limit| page.saturating_mul(per_page) < limit)
+}
+
+/// Replaces the values of the `field_styles` with their symbols.
+fn replace_with_symbols(mut value: Value, field_styles: &[FieldStyle]) -> Value {
+    for style in field_styles {
+        if let Some(field) = value.get_mut(style.field)
+            && let Some(symbol) = field.as_str().and_then(style.symbol)
+        {
+            *field = Value::from(symbol);
+        }
+    }
+
+    value
+}
+
+/// Colors the cells of the `field_styles` in TSV output. `field_names` are
+/// the names of the columns. Rows that don't have a cell for every column
+/// because a cell contains a line break are left as is.
+fn color_cells(output: &str, field_names: &[String], field_styles: &[FieldStyle]) -> String {
+    let colors: Vec<Option<&FieldStyle>> = field_names
+        .iter()
+        .map(|name| field_styles.iter().find(|colored| colored.field == name))
+        .collect();
+
+    if colors.iter().all(Option::is_none) {
+        return output.to_string();
+    }
+
+    output
+        .lines()
+        .map(|row| {
+            let cells: Vec<&str> = row.split('\t').collect();
+
+            if cells.len() != colors.len() {
+                return row.to_string();
+            }
+
+            cells
+                .iter()
+                .zip(&colors)
+                .map(|(cell, colored)| {
+                    match colored.and_then(|colored| (colored.color)(cell.trim())) {
+                        Some(color) => format!("{}{cell}{STYLE_RESET}", color.style()),
+                        None => cell.to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Dims the rows of the items for which `is_dimmed` returns true. Rows can't
+/// be matched with their items if a cell contains a line break, so nothing is
+/// dimmed then. Colored cells in a dimmed row stay dimmed after their color is
+/// reset.
+fn dim_rows<T>(output: &str, items: &[T], is_dimmed: impl Fn(&T) -> bool) -> String {
+    let rows: Vec<&str> = output.lines().collect();
+
+    if rows.len() != items.len() {
+        return output.to_string();
+    }
+
+    rows.iter()
+        .zip(items)
+        .map(|(row, item)| {
+            if is_dimmed(item) {
+                let row = row.replace(STYLE_RESET, &format!("{STYLE_RESET}{STYLE_DIM}"));
+
+                format!("{STYLE_DIM}{row}{STYLE_RESET}")
+            } else {
+                row.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Fits every row of TSV output into the width of the terminal by truncating
+/// its widest cells if stdout is a terminal. Other formats and output that is
+/// piped are returned as is.
+fn fit_to_terminal(output: String, format: &OutputFormat) -> String {
+    if !matches!(format, OutputFormat::Tsv) || !std::io::stdout().is_terminal() {
+        return output;
+    }
+
+    match crossterm::terminal::size() {
+        Ok((width, _)) => fit_tsv_to_width(&output, width as usize),
+        Err(_) => output,
+    }
+}
+
+/// Truncates the text to at most `max_width` terminal columns and ends it with
+/// an ellipsis if it was truncated. The text is cut between grapheme clusters,
+/// so wide characters and combining marks are never split.
+pub fn truncate_to_width(text: &str, max_width: usize) -> Cow<'_, str> {
+    if text.width() <= max_width {
+        return Cow::Borrowed(text);
+    }
+
+    let Some(budget) = max_width.checked_sub(ELLIPSIS.width()) else {
+        return Cow::Borrowed("");
+    };
+    let mut width = 0;
+    let mut end = 0;
+
+    for (index, grapheme) in text.grapheme_indices(true) {
+        width += grapheme.width();
+
+        if width > budget {
+            break;
+        }
+
+        end = index + grapheme.len();
+    }
+
+    Cow::Owned(format!("{}{ELLIPSIS}", &text[..end]))
+}
+
+/// Right-aligns the TSV columns whose cells are all numbers, e.g. counts, so
+/// that their digits line up. Cells may be empty since not every item has
+/// every field. Rows can't be split into the same columns if a cell contains
+/// a line break, so nothing is aligned then.
+fn align_numeric_columns(output: &str) -> String {
+    let rows: Vec<Vec<&str>> = output
+        .lines()
+        .map(|row| row.split('\t').collect())
+        .collect();
+    let column_count = rows.first().map_or(0, Vec::len);
+
+    if rows.iter().any(|row| row.len() != column_count) {
+        return output.to_string();
+    }
+
+    let widths: Vec<Option<usize>> = (0..column_count)
+        .map(|column| {
+            let cells = || rows.iter().map(|row| row[column]);
+            let is_numeric = cells().any(|cell| !cell.is_empty())
+                && cells().all(|cell| cell.bytes().all(|byte| byte.is_ascii_digit()));
+
+            is_numeric.then(|| cells().map(str::len).max().unwrap_or_default())
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| match width {
+                    Some(width) => format!("{cell:>width$}"),
+                    None => cell.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn fit_tsv_to_width(output: &str, max_width: usize) -> String {
+    output
+        .lines()
+        .map(|row| fit_row_to_width(row, max_width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn fit_row_to_width(row: &str, max_width: usize) -> String {
+    let original_cells: Vec<&str> = row.split('\t').collect();
+    let mut cells: Vec<Cow<str>> = original_cells
+        .iter()
+        .map(|&cell| Cow::Borrowed(cell))
+        .collect();
+
+    // Shrink the widest cell until the row fits. Because of the tab stops,
+    // that may not be enough, so continue with the next widest cell.
+    while get_row_width(&cells) > max_width {
+        let Some((index, width)) = cells
+            .iter()
+            .map(|cell| cell.width())
+            .enumerate()
+            .filter(|(_, width)| *width > MIN_CELL_WIDTH)
+            .max_by_key(|(_, width)| *width)
+        else {
+            break;
+        };
+
+        for new_width in (MIN_CELL_WIDTH..width).rev() {
+            cells[index] = truncate_to_width(original_cells[index], new_width);
+
+            if get_row_width(&cells) <= max_width {
+                break;
+            }
+        }
+    }
+
+    cells.join("\t")
+}
+
+/// Gets the number of terminal columns a row of tab-separated cells takes up.
+fn get_row_width(cells: &[Cow<str>]) -> usize {
+    cells.iter().enumerate().fold(0, |column, (index, cell)| {
+        let end = column + cell.width();
+
+        if index + 1 < cells.len() {
+            (end / TAB_WIDTH + 1) * TAB_WIDTH
+        } else {
+            end
+        }
+    })
+}
+
 fn stringify_json_value_for_serialization(value: &Value) -> String {
     match value {
         Value::Null => String::new(),
@@ -202,6 +1294,75 @@ fn stringify_json_value_for_serialization(value: &Value) -> String {
     }
 }
 
+/// Removes ANSI escape sequences and other control characters, so that text
+/// from the forge can't change the terminal or break rows apart. Line breaks
+/// and tabs become spaces.
+fn sanitize_field(field: &str) -> Cow<'_, str> {
+    if !field.chars().any(char::is_control) {
+        return Cow::Borrowed(field);
+    }
+
+    let mut sanitized = String::with_capacity(field.len());
+    let mut chars = field.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => skip_escape_sequence(&mut chars),
+            '\t' | '\n' | '\r' => sanitized.push(' '),
+            c if c.is_control() => {}
+            c => sanitized.push(c),
+        }
+    }
+
+    Cow::Owned(sanitized)
+}
+
+/// Skips the rest of an escape sequence after its ESC character.
+/// https://en.wikipedia.org/wiki/ANSI_escape_code#Fe_Escape_sequences
+fn skip_escape_sequence(chars: &mut Peekable<Chars>) {
+    match chars.next() {
+        // Control Sequence Introducer: parameter and intermediate bytes
+        // followed by a final byte, e.g. colors.
+        Some('[') => {
+            while chars.next_if(|c| ('\x20'..='\x3f').contains(c)).is_some() {}
+            chars.next_if(|c| ('\x40'..='\x7e').contains(c));
+        }
+        // Strings terminated by BEL or ST (ESC \\), e.g. hyperlinks or window
+        // titles.
+        Some(']' | 'P' | 'X' | '^' | '_') => {
+            while let Some(c) = chars.next() {
+                if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+        }
+        // Other sequences: intermediate bytes followed by a final byte.
+        Some(c) if ('\x20'..='\x2f').contains(&c) => {
+            while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+            chars.next_if(|c| ('\x30'..='\x7e').contains(c));
+        }
+        _ => {}
+    }
+}
+
+/// Wraps the text in single quotes, so that the shell doesn't expand anything
+/// in it, e.g. `$(...)`.
+pub fn quote_for_shell(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Gets a value of nested objects by a dot-separated path, e.g. `head.ref`.
+fn get_value_by_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| value.get(key))
+}
+
+/// Gets the key of the top-level object of a dot-separated field path.
+fn get_top_level_key(path: &str) -> &str {
+    path.split_once('.').map_or(path, |(key, _)| key)
+}
+
+/// Gets the serialized names of the fields. The name of a field in a nested
+/// object is its dot-separated path, e.g. `head.ref`.
 fn get_field_names<T: Serialize>(fields: &[T]) -> Vec<String> {
     fields
         .iter()
@@ -211,3 +1372,722 @@ fn get_field_names<T: Serialize>(fields: &[T]) -> Vec<String> {
         })
         .collect::<Vec<String>>()
 }
+
+/// Resolves the fields to output. If no fields were passed on the command
+/// line, the comma-separated `config_value` is used and the `defaults` as last
+/// resort. The `all` pseudo-field expands to every field in the order of their
+/// declaration.
+pub fn resolve_fields<F: ValueEnum + PartialEq>(
+    fields: Vec<F>,
+    config_value: Option<String>,
+    defaults: Vec<F>,
+) -> anyhow::Result<Vec<F>> {
+    let fields = match config_value {
+        Some(value) if fields.is_empty() => {
+            parse_fields(&value).context("Invalid fields in the configuration")?
+        }
+        _ => fields,
+    };
+    let fields = if fields.is_empty() { defaults } else { fields };
+
+    Ok(expand_all_fields(fields))
+}
+
+fn parse_fields<F: ValueEnum>(value: &str) -> anyhow::Result<Vec<F>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(parse_field)
+        .collect()
+}
+
+/// Parses the name of a field or one of its aliases in `FIELD_ALIASES`.
+pub fn parse_field<F: ValueEnum>(name: &str) -> anyhow::Result<F> {
+    let field_name = FIELD_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map_or(name, |(_, field_name)| field_name);
+
+    F::from_str(field_name, true).map_err(|_| {
+        let valid_names = F::value_variants()
+            .iter()
+            .filter_map(|f| f.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect::<Vec<_>>();
+
+        let aliases = get_field_aliases::<F>();
+        let mut message = format!(
+            "Unknown field '{name}'. Valid fields are: {}",
+            valid_names.join(", ")
+        );
+
+        if !aliases.is_empty() {
+            message.push_str(&format!(". Accepted aliases are: {}", aliases.join(", ")));
+        }
+
+        anyhow::anyhow!(message)
+    })
+}
+
+/// Gets the aliases of the fields of `F` as `alias (field)`.
+pub fn get_field_aliases<F: ValueEnum>() -> Vec<String> {
+    FIELD_ALIASES
+        .iter()
+        .filter(|(_, field_name)| F::from_str(field_name, false).is_ok())
+        .map(|(alias, field_name)| format!("{alias} ({field_name})"))
+        .collect()
+}
+
+/// Parses the values of the `--fields` and `--field` options with
+/// `parse_field`, so that the aliases are accepted as well. The help and the
+/// shell completions still only list the field names.
+#[derive(Clone)]
+pub struct FieldParser<F>(PhantomData<F>);
+
+impl<F> FieldParser<F> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: ValueEnum + Send + Sync + 'static> TypedValueParser for FieldParser<F> {
+    type Value = F;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<F, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        parse_field(value.trim())
+            .map_err(|e| clap::Error::raw(ErrorKind::InvalidValue, format!("{e}\n")).with_cmd(cmd))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            F::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+fn expand_all_fields<F: ValueEnum + PartialEq>(fields: Vec<F>) -> Vec<F> {
+    let mut expanded: Vec<F> = vec![];
+
+    for field in fields {
+        let new_fields = if is_all_fields(&field) {
+            F::value_variants()
+                .iter()
+                .filter(|f| !is_all_fields(*f))
+                .cloned()
+                .collect()
+        } else {
+            vec![field]
+        };
+
+        for field in new_fields {
+            if !expanded.contains(&field) {
+                expanded.push(field);
+            }
+        }
+    }
+
+    expanded
+}
+
+fn is_all_fields<F: ValueEnum>(field: &F) -> bool {
+    field
+        .to_possible_value()
+        .is_some_and(|v| v.get_name() == ALL_FIELDS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, ValueEnum)]
+    #[value(rename_all = "snake_case")]
+    enum TestField {
+        Id,
+        Title,
+        CreatedAt,
+        All,
+    }
+
+    #[test]
+    fn test_resolve_fields_prefers_cli_fields() {
+        let fields = resolve_fields(
+            vec![TestField::Title],
+            Some("id".to_string()),
+            vec![TestField::Id],
+        )
+        .unwrap();
+
+        assert_eq!(fields, [TestField::Title]);
+    }
+
+    #[test]
+    fn test_resolve_fields_from_config_and_defaults() {
+        assert_eq!(
+            resolve_fields::<TestField>(vec![], Some("created_at, id".to_string()), vec![])
+                .unwrap(),
+            [TestField::CreatedAt, TestField::Id]
+        );
+        assert_eq!(
+            resolve_fields(vec![], None, vec![TestField::Id]).unwrap(),
+            [TestField::Id]
+        );
+    }
+
+    #[test]
+    fn test_resolve_fields_expands_all() {
+        assert_eq!(
+            resolve_fields(vec![TestField::Title, TestField::All], None, vec![]).unwrap(),
+            [TestField::Title, TestField::Id, TestField::CreatedAt]
+        );
+        assert_eq!(
+            resolve_fields::<TestField>(vec![], Some("all".to_string()), vec![]).unwrap(),
+            [TestField::Id, TestField::Title, TestField::CreatedAt]
+        );
+    }
+
+    #[test]
+    fn test_resolve_fields_unknown_field_lists_valid_fields() {
+        let error =
+            resolve_fields::<TestField>(vec![], Some("id,tite".to_string()), vec![]).unwrap_err();
+        let message = format!("{error:#}");
+
+        assert!(message.contains("Invalid fields in the configuration"));
+        assert!(message.contains("Unknown field 'tite'"));
+        assert!(message.contains("Valid fields are: id, title, created_at, all"));
+    }
+
+    #[test]
+    fn test_resolve_fields_accepts_aliases() {
+        assert_eq!(
+            resolve_fields::<TestField>(vec![], Some("number,createdAt,Title".to_string()), vec![])
+                .unwrap(),
+            [TestField::Id, TestField::CreatedAt, TestField::Title]
+        );
+    }
+
+    #[test]
+    fn test_parse_field_unknown_field_lists_aliases() {
+        let error = parse_field::<TestField>("isDraft").unwrap_err().to_string();
+
+        assert!(error.contains("Unknown field 'isDraft'"));
+        assert!(error.contains("Valid fields are: id, title, created_at, all"));
+        assert!(
+            error.contains("Accepted aliases are: number (id), iid (id), createdAt (created_at)")
+        );
+        assert!(!error.contains("isDraft (draft)"));
+    }
+
+    #[test]
+    fn test_field_parser_accepts_aliases() {
+        let command = clap::Command::new("test").arg(
+            clap::Arg::new("fields")
+                .long("fields")
+                .value_delimiter(',')
+                .value_parser(FieldParser::<TestField>::new()),
+        );
+
+        let matches = command
+            .clone()
+            .try_get_matches_from(["test", "--fields", "number,title"])
+            .unwrap();
+        let fields = matches
+            .get_many::<TestField>("fields")
+            .unwrap()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(fields, [TestField::Id, TestField::Title]);
+        assert!(
+            command
+                .try_get_matches_from(["test", "--fields", "tite"])
+                .unwrap_err()
+                .to_string()
+                .contains("Unknown field 'tite'")
+        );
+    }
+
+    #[test]
+    fn test_format_timestamp_with_date_styles() {
+        let timestamp = Timestamp::parse("2024-03-01T12:00:00Z").unwrap();
+
+        assert_eq!(
+            format_timestamp_with(timestamp, &DateStyle::Rfc3339),
+            "2024-03-01T12:00:00Z"
+        );
+        assert_eq!(
+            format_timestamp_with(
+                timestamp,
+                &DateStyle::Format(DateFormat::parse("%d %B %Y, %H:%M").unwrap())
+            ),
+            "01 March 2024, 12:00"
+        );
+        assert!(format_timestamp_with(timestamp, &DateStyle::Relative).ends_with("w ago"));
+    }
+
+    #[test]
+    fn test_machine_formats_keep_rfc3339_timestamps() {
+        let items = [serde_json::json!({
+            "id": 1,
+            "created_at": Timestamp::parse("2024-03-01T12:00:00Z").unwrap(),
+        })];
+
+        for output_format in [OutputFormat::Tsv, OutputFormat::Csv, OutputFormat::Json] {
+            assert!(
+                format(&items, &["created_at"], &output_format)
+                    .unwrap()
+                    .contains("2024-03-01T12:00:00Z")
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_nested_fields() {
+        let items = [serde_json::json!({
+            "id": 1,
+            "head": { "ref": "feature", "sha": "abc123" },
+            "base": { "ref": "main" },
+        })];
+        let fields = ["id", "head.ref", "head.sha", "base.sha"];
+
+        assert_eq!(
+            format(&items, &fields, &OutputFormat::Tsv).unwrap(),
+            "1\tfeature\tabc123\t"
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(
+                &format(&items, &["head.ref"], &OutputFormat::Json).unwrap()
+            )
+            .unwrap(),
+            serde_json::json!([{ "head": { "ref": "feature", "sha": "abc123" } }])
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_field_order() {
+        let items = [serde_json::json!({
+            "id": 1,
+            "title": "Fix crash",
+            "url": "https://example.com/1",
+        })];
+        let fields = ["url", "id", "title"];
+        let json = format(&items, &fields, &OutputFormat::Json).unwrap();
+        let keys = serde_json::from_str::<Vec<Map<String, Value>>>(&json).unwrap()[0]
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(keys, fields);
+        assert_eq!(
+            format(&items, &fields, &OutputFormat::Csv).unwrap(),
+            "https://example.com/1,1,Fix crash"
+        );
+    }
+
+    #[test]
+    fn test_align_numeric_columns() {
+        assert_eq!(
+            align_numeric_columns("7\tFix crash\t3\t\n123\tDocs\t12\t5"),
+            "  7\tFix crash\t 3\t \n123\tDocs\t12\t5"
+        );
+        assert_eq!(
+            align_numeric_columns("1\t2\n30"),
+            "1\t2\n30",
+            "Rows with different numbers of cells can't be aligned"
+        );
+    }
+
+    #[test]
+    fn test_resolve_color_flag_takes_precedence() {
+        let set = Some(OsStr::new("1"));
+
+        assert!(resolve_color(ColorChoice::Always, set, None, false));
+        assert!(!resolve_color(ColorChoice::Never, None, set, true));
+    }
+
+    #[test]
+    fn test_resolve_color_auto_uses_env() {
+        let set = Some(OsStr::new("1"));
+        let empty = Some(OsStr::new(""));
+
+        assert!(resolve_color(ColorChoice::Auto, None, None, true));
+        assert!(!resolve_color(ColorChoice::Auto, None, None, false));
+        assert!(!resolve_color(ColorChoice::Auto, set, None, true));
+        assert!(resolve_color(ColorChoice::Auto, empty, None, true));
+        assert!(resolve_color(ColorChoice::Auto, None, set, false));
+        assert!(!resolve_color(
+            ColorChoice::Auto,
+            None,
+            Some(OsStr::new("0")),
+            false
+        ));
+        assert!(
+            !resolve_color(ColorChoice::Auto, set, set, true),
+            "NO_COLOR wins over CLICOLOR_FORCE"
+        );
+    }
+
+    #[test]
+    fn test_check_prompt() {
+        assert!(check_prompt(PromptMode::Auto, true, "Title?", "--title").unwrap());
+        assert!(!check_prompt(PromptMode::Auto, false, "Title?", "--title").unwrap());
+        assert!(check_prompt(PromptMode::Always, true, "Title?", "--title").unwrap());
+        assert!(check_prompt(PromptMode::Always, false, "Title?", "--title").is_err());
+
+        let error = check_prompt(PromptMode::Never, true, "Enter issue title", "--title")
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(
+            error,
+            "Can't ask \"Enter issue title\" with --prompt never. Pass --title instead"
+        );
+    }
+
+    #[test]
+    fn test_format_field_values() {
+        let item = serde_json::json!({
+            "id": 7,
+            "title": "Fix\tcrash",
+            "labels": ["bug", "tui"],
+            "head": { "ref": "fix-crash" },
+        });
+
+        assert_eq!(format_field_values(&item, &["id"]).unwrap(), "7");
+        assert_eq!(
+            format_field_values(&item, &["labels", "head.ref", "title"]).unwrap(),
+            "bug,tui\tfix-crash\tFix crash"
+        );
+        assert_eq!(
+            format_field_values(&item, &["merged_at", "id"]).unwrap(),
+            "\t7",
+            "Missing values are empty"
+        );
+    }
+
+    #[test]
+    fn test_format_compact_line() {
+        let item = serde_json::json!({
+            "id": 123,
+            "title": "Fix crash in TUI",
+            "labels": ["bug", "tui"],
+            "author": "alice",
+        });
+
+        assert_eq!(
+            format_compact_line(&item, None, false),
+            "#123 Fix crash in TUI (bug, tui) @alice"
+        );
+        assert_eq!(
+            format_compact_line(&item, None, true),
+            format!("#123 Fix crash in TUI {STYLE_DIM}(bug, tui){STYLE_RESET} @alice")
+        );
+        assert_eq!(
+            format_compact_line(&item, Some(36), false),
+            "#123 Fix crash in… (bug, tui) @alice"
+        );
+    }
+
+    #[test]
+    fn test_format_compact_line_leaves_out_missing_parts() {
+        let item = serde_json::json!({ "id": 7, "title": "Docs", "labels": [] });
+
+        assert_eq!(format_compact_line(&item, None, true), "#7 Docs");
+        assert_eq!(
+            format_compact_line(&item, Some(1), false),
+            "#7 Docs",
+            "The title keeps a minimum width"
+        );
+    }
+
+    #[test]
+    fn test_truncate_title() {
+        let item = serde_json::json!({ "id": 1, "title": "A rather long title" });
+
+        assert_eq!(
+            truncate_title(item.clone(), 10),
+            serde_json::json!({ "id": 1, "title": truncate_to_width("A rather long title", 10) })
+        );
+        assert_eq!(truncate_title(item.clone(), 100), item);
+    }
+
+    #[test]
+    fn test_format_delimited_sanitizes_fields() {
+        let items = [
+            serde_json::json!({ "id": 1, "title": "\x1b[31mred\x1b[0m $(rm -rf ~)" }),
+            serde_json::json!({
+                "id": 2,
+                "title": "\x1b]8;;https://example.com\x07link\x1b]8;;\x1b\\ and \x1b]0;title\x07",
+            }),
+            serde_json::json!({ "id": 3, "title": "multi\nline\r\nwith\ttab" }),
+            serde_json::json!({ "id": 4, "title": "bell\x07 back\x08space \x1b(Bcharset\x1b" }),
+        ];
+        let fields = ["id", "title"];
+
+        assert_eq!(
+            format_delimited(&items, &fields, b'\t', false).unwrap(),
+            "1\tred $(rm -rf ~)\n\
+             2\tlink and \n\
+             3\tmulti line  with tab\n\
+             4\tbell backspace charset"
+        );
+    }
+
+    #[test]
+    fn test_format_delimited_escapes_for_shell() {
+        let items = [
+            serde_json::json!({ "id": 1, "title": "it's $(rm -rf ~)", "labels": [] }),
+            serde_json::json!({ "id": 2, "title": "a, \"b\"\x1b[2J", "labels": ["x", "y"] }),
+        ];
+        let fields = ["id", "title", "labels"];
+
+        assert_eq!(
+            format_delimited(&items, &fields, b'\t', true).unwrap(),
+            "'1'\t'it'\\''s $(rm -rf ~)'\t''\n'2'\t'a, \"b\"'\t'x,y'"
+        );
+        assert_eq!(
+            format_delimited(&items, &fields, b',', false).unwrap(),
+            "1,it's $(rm -rf ~),\n2,\"a, \"\"b\"\"\",\"x,y\""
+        );
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("exactly 10", 10), "exactly 10");
+        assert_eq!(truncate_to_width("a longer title", 10), "a longer …");
+        assert_eq!(truncate_to_width("title", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_to_width_with_wide_characters() {
+        // CJK characters take up two columns
+        assert_eq!(truncate_to_width("漢字のタイトル", 8), "漢字の…");
+        assert_eq!(truncate_to_width("漢字のタイトル", 9), "漢字のタ…");
+
+        // As do emoji, even if they consist of several code points
+        assert_eq!(truncate_to_width("🚀🚀🚀", 4), "🚀…");
+        assert_eq!(truncate_to_width("👩‍💻 fix", 4), "👩‍💻 …");
+    }
+
+    #[test]
+    fn test_truncate_to_width_keeps_combining_marks() {
+        // "e" followed by a combining acute accent
+        let text = "cafe\u{301} au lait";
+
+        assert_eq!(truncate_to_width(text, 5), "cafe\u{301}…");
+        assert_eq!(truncate_to_width(text, 4), "caf…");
+    }
+
+    #[test]
+    fn test_fit_tsv_to_width() {
+        let output = "1\tA title that is way too long\thttps://example.org/1\n2\tShort\turl";
+
+        assert_eq!(
+            fit_tsv_to_width(output, 50),
+            "1\tA title that i…\thttps://example.org/1\n2\tShort\turl"
+        );
+        // The title can't shrink enough, so the URL is truncated as well
+        assert_eq!(
+            fit_tsv_to_width(output, 40),
+            "1\tA title…\thttps://example…\n2\tShort\turl"
+        );
+        assert_eq!(fit_tsv_to_width(output, 200), output);
+
+        // Cells aren't truncated below the minimum width
+        assert_eq!(fit_tsv_to_width("1\tA title\turl", 4), "1\tA title\turl");
+    }
+
+    #[test]
+    fn test_dim_rows() {
+        let output = "1\tbot\n2\thuman";
+
+        assert_eq!(
+            dim_rows(output, &[1, 2], |&id| id == 1),
+            "\x1b[2m1\tbot\x1b[0m\n2\thuman"
+        );
+        // A line break in a cell makes the rows ambiguous
+        assert_eq!(dim_rows(output, &[1], |_| true), output);
+        // A colored cell doesn't end the dimming of its row
+        assert_eq!(
+            dim_rows("1\t\x1b[32mok\x1b[0m\tbot", &[1], |_| true),
+            "\x1b[2m1\t\x1b[32mok\x1b[0m\x1b[2m\tbot\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_replace_with_symbols() {
+        let field_styles = [FieldStyle {
+            field: "ci",
+            symbol: |ci| (ci == "success").then_some("✓"),
+            color: |_| None,
+        }];
+
+        assert_eq!(
+            replace_with_symbols(serde_json::json!({"id": 1, "ci": "success"}), &field_styles),
+            serde_json::json!({"id": 1, "ci": "✓"})
+        );
+        assert_eq!(
+            replace_with_symbols(serde_json::json!({"id": 2, "ci": "failure"}), &field_styles),
+            serde_json::json!({"id": 2, "ci": "failure"})
+        );
+        // Fields that weren't fetched are missing
+        assert_eq!(
+            replace_with_symbols(serde_json::json!({"id": 3}), &field_styles),
+            serde_json::json!({"id": 3})
+        );
+    }
+
+    #[test]
+    fn test_color_cells() {
+        let field_names = [String::from("id"), String::from("status")];
+        let field_styles = [FieldStyle {
+            field: "status",
+            symbol: |_| None,
+            color: |status| (status == "ok").then_some(CellColor::Green),
+        }];
+
+        assert_eq!(
+            color_cells("1\tok\n2\tfailed", &field_names, &field_styles),
+            "1\t\x1b[32mok\x1b[0m\n2\tfailed"
+        );
+        // Rows that don't fit the columns are left as is
+        assert_eq!(
+            color_cells("1\tok\tmore\n2\tok", &field_names, &field_styles),
+            "1\tok\tmore\n2\t\x1b[32mok\x1b[0m"
+        );
+        assert_eq!(color_cells("1\tok", &field_names, &[]), "1\tok");
+    }
+
+    #[test]
+    fn test_check_list_assertions() {
+        assert!(check_list_assertions(0, false, false).is_ok());
+        assert!(check_list_assertions(3, false, false).is_ok());
+
+        assert!(check_list_assertions(0, true, false).is_ok());
+        assert!(
+            check_list_assertions(3, true, false)
+                .is_err_and(|e| e.to_string().contains("found 3 (--fail-if-any)"))
+        );
+
+        assert!(check_list_assertions(3, false, true).is_ok());
+        assert!(
+            check_list_assertions(0, false, true)
+                .is_err_and(|e| e.to_string().contains("--fail-if-none"))
+        );
+    }
+
+    #[test]
+    fn test_get_per_page() {
+        assert_eq!(get_per_page(None, None, 30), 30);
+        assert_eq!(get_per_page(Some(50), None, 30), 50);
+        assert_eq!(get_per_page(None, Some(10), 30), 10);
+        assert_eq!(get_per_page(None, Some(250), 30), 100);
+        assert_eq!(get_per_page(Some(20), Some(250), 30), 20);
+    }
+
+    /// Counts the pages a listing fetches if every page is full and the forge
+    /// has `available` items.
+    fn count_fetched_pages(available: usize, per_page: u32, limit: Option<u32>) -> u32 {
+        let mut item_count = 0;
+
+        for page in 1.. {
+            item_count = available.min(item_count + per_page as usize);
+
+            if !should_fetch_next_page(item_count, item_count < available, false, limit) {
+                return page;
+            }
+        }
+
+        unreachable!()
+    }
+
+    #[test]
+    fn test_should_fetch_next_page() {
+        assert_eq!(count_fetched_pages(1000, 100, Some(250)), 3);
+        assert_eq!(count_fetched_pages(1000, 100, Some(200)), 2);
+        assert_eq!(count_fetched_pages(120, 100, Some(250)), 2);
+        assert_eq!(count_fetched_pages(1000, 30, None), 1);
+
+        // --all-pages without a limit stops only at the last page
+        assert!(should_fetch_next_page(500, true, true, None));
+        assert!(!should_fetch_next_page(500, false, true, None));
+    }
+
+    #[test]
+    fn test_is_below_limit() {
+        assert!(is_below_limit(1, 20, None));
+        assert!(is_below_limit(2, 20, Some(50)));
+        assert!(!is_below_limit(3, 20, Some(50)));
+        assert!(!is_below_limit(u32::MAX, 20, Some(50)));
+    }
+
+    #[test]
+    fn test_select_by_number_or_name() {
+        let items = ["github".to_string(), "gitlab".to_string()];
+        let mut output = Vec::new();
+
+        let selection = select(&mut "2\n".as_bytes(), &mut output, "Forge?", &items).unwrap();
+
+        assert_eq!(selection, Some(1));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Forge?\n  1) github\n  2) gitlab\nSelect [1-2]: "
+        );
+
+        let selection = select(&mut "GitHub\n".as_bytes(), &mut Vec::new(), "", &items).unwrap();
+
+        assert_eq!(selection, Some(0));
+    }
+
+    #[test]
+    fn test_select_retries_invalid_selection() {
+        let items = ["github".to_string(), "gitlab".to_string()];
+        let mut output = Vec::new();
+
+        let selection = select(&mut "0\n3\nfoo\n1\n".as_bytes(), &mut output, "", &items).unwrap();
+
+        assert_eq!(selection, Some(0));
+        assert_eq!(
+            String::from_utf8(output)
+                .unwrap()
+                .matches("Invalid selection")
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_select_without_answer() {
+        let items = ["github".to_string()];
+
+        assert_eq!(
+            select(&mut "\n".as_bytes(), &mut Vec::new(), "", &items).unwrap(),
+            None
+        );
+        assert_eq!(
+            select(&mut "".as_bytes(), &mut Vec::new(), "", &items).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_confirm() {
+        assert!(confirm(&mut "y\n".as_bytes(), &mut Vec::new(), "", false).unwrap());
+        assert!(!confirm(&mut "No\n".as_bytes(), &mut Vec::new(), "", true).unwrap());
+        assert!(confirm(&mut "\n".as_bytes(), &mut Vec::new(), "", true).unwrap());
+        assert!(!confirm(&mut "".as_bytes(), &mut Vec::new(), "", false).unwrap());
+        assert!(confirm(&mut "maybe\nyes\n".as_bytes(), &mut Vec::new(), "", false).unwrap());
+    }
+}
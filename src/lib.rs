@@ -1,32 +1,127 @@
+mod audit;
 mod cli;
+mod date;
 mod git;
 mod io;
+mod logging;
+mod rate_limit;
+mod run_marker;
+mod storage;
+#[cfg(test)]
+mod test_env;
+mod throttle;
 mod tui;
+mod watch;
 
 use clap::Parser;
 
-use crate::cli::{Cli, ConfigCommand, GitForgeCommand, IssueCommand, PrCommand};
+use crate::cli::{
+    AuditCommand, BranchCommand, CacheCommand, Cli, Config, ConfigCommand, GitForgeCommand,
+    HookCommand, IssueCommand, IssueLabelCommand, PrCommand, RepoCommand,
+};
 
 pub fn run() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    match cli.subcommand {
+    logging::init(cli.verbose);
+    git::set_verbose(cli.verbose > 0 || cli.verbose_git);
+
+    // Loaded once for the global settings. If it fails, the settings count as
+    // unset, and the commands report the error when they load the config
+    let config = Config::load_from_disk();
+    let global_config = config.as_ref().ok();
+
+    rate_limit::set_enabled(cli.show_rate_limit || cli::get_show_rate_limit_setting(global_config));
+    cli::set_force_auth_host(cli.force_auth_host);
+    throttle::set_mutation_interval(cli::get_mutation_interval_setting(global_config));
+
+    if let Some(max_size) = cli::get_audit_log_setting(global_config)
+        && let Err(e) = audit::enable(max_size)
+    {
+        log::warn!("Failed to enable the audit log: {e:#}");
+    }
+
+    cli::set_refresh_identity(cli.refresh_identity);
+    io::init_output_settings(
+        cli.get_color_choice(),
+        cli.escape_shell,
+        cli::get_date_style_setting(global_config),
+    );
+    io::set_prompt_mode(
+        cli.prompt
+            .or_else(|| cli::get_prompt_setting(global_config))
+            .unwrap_or_default(),
+    );
+
+    let result = match cli.subcommand {
+        GitForgeCommand::Audit(args) => match args.subcommand {
+            AuditCommand::List(args) => cli::list_audit_log(args),
+        },
+        GitForgeCommand::Branch(args) => match args.subcommand {
+            BranchCommand::DeleteMerged(args) => cli::delete_merged_branches(args),
+            BranchCommand::List(args) => cli::list_branches(args),
+        },
         GitForgeCommand::Browse(args) => cli::browse_repository(args),
+        GitForgeCommand::Cache(args) => match args.subcommand {
+            CacheCommand::Clear(args) => cli::cache_clear(args),
+            CacheCommand::List => cli::cache_list(),
+            CacheCommand::Path => cli::cache_path(),
+        },
+        GitForgeCommand::Changelog(args) => cli::generate_changelog(args),
         GitForgeCommand::Completions(args) => cli::generate_completions(args),
         GitForgeCommand::Config(args) => match args.subcommand {
             ConfigCommand::Get(args) => cli::config_get(args),
             ConfigCommand::Set(args) => cli::config_set(args),
             ConfigCommand::Unset(args) => cli::config_unset(args),
             ConfigCommand::Edit => cli::config_edit(),
+            ConfigCommand::Key(args) => cli::config_key(args),
         },
+        GitForgeCommand::Context(args) => cli::print_context(args, config),
+        GitForgeCommand::Doctor(args) => cli::doctor(args),
+        GitForgeCommand::Hook(args) => match args.subcommand {
+            HookCommand::Disable(args) => cli::disable_hook(args),
+            HookCommand::Enable(args) => cli::enable_hook(args),
+            HookCommand::List(args) => cli::list_hooks(args),
+            HookCommand::Ping(args) => cli::ping_hook(args),
+        },
+        GitForgeCommand::Init(args) => cli::init(args),
         GitForgeCommand::Issue(args) => match args.subcommand {
             IssueCommand::List(args) => cli::list_issues(args),
             IssueCommand::Create(args) => cli::create_issue(args),
+            IssueCommand::Snapshot(args) => cli::snapshot_issues(args),
+            IssueCommand::Export(args) => cli::export_issues(args),
+            IssueCommand::Import(args) => cli::import_issues(args),
+            IssueCommand::Pin(args) => cli::pin_issue(args),
+            IssueCommand::Unpin(args) => cli::unpin_issue(args),
+            IssueCommand::Assign(args) => cli::assign_issue(args),
+            IssueCommand::Unassign(args) => cli::unassign_issue(args),
+            IssueCommand::Label(args) => match args.subcommand {
+                IssueLabelCommand::Add(args) => cli::add_issue_labels(args),
+                IssueLabelCommand::Remove(args) => cli::remove_issue_labels(args),
+                IssueLabelCommand::Set(args) => cli::set_issue_labels(args),
+            },
+            IssueCommand::View(args) => cli::view_issue(args),
         },
         GitForgeCommand::Pr(args) => match args.subcommand {
             PrCommand::Checkout(args) => cli::checkout_pr(args),
             PrCommand::Create(args) => cli::create_pr(args),
             PrCommand::List(args) => cli::list_prs(args),
+            PrCommand::Merge(args) => cli::merge_pr(args),
+            PrCommand::Reviewer(args) => cli::update_pr_reviewers(args),
+            PrCommand::View(args) => cli::view_pr(args),
         },
-    }
+        GitForgeCommand::Repo(args) => match args.subcommand {
+            RepoCommand::Edit(args) => cli::edit_repo(args),
+            RepoCommand::List(args) => cli::list_repos(args),
+            RepoCommand::View(args) => cli::view_repo(args),
+        },
+        #[cfg(feature = "self-update")]
+        GitForgeCommand::SelfUpdate(args) => cli::self_update(args),
+        GitForgeCommand::Version(args) => cli::print_version(args),
+    };
+
+    audit::flush();
+    rate_limit::print_summary();
+
+    result
 }
@@ -0,0 +1,168 @@
+//! Diagnostics via the `log` facade.
+//!
+//! Messages go to stderr by default. While the TUI is active, writing to
+//! stderr would break the drawing, so messages are appended to a log file
+//! instead (see `redirect_to_file`). The level is `warn` unless overridden by
+//! the `GIT_FORGE_LOG` environment variable (e.g. `GIT_FORGE_LOG=debug`) and
+//! raised once for each `--verbose` flag.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    str::FromStr,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::storage::{self, StorageCategory};
+
+const LOG_ENV_VAR: &str = "GIT_FORGE_LOG";
+const LOG_FILE_NAME: &str = "git-forge.log";
+
+static LOGGER: Logger = Logger {
+    to_file: AtomicBool::new(false),
+    file: Mutex::new(None),
+};
+
+struct Logger {
+    to_file: AtomicBool,
+    file: Mutex<Option<File>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        if !self.to_file.load(Ordering::Relaxed) {
+            eprintln!(
+                "{}: {}",
+                record.level().as_str().to_lowercase(),
+                record.args()
+            );
+
+            return;
+        }
+
+        let Ok(mut file) = self.file.lock() else {
+            return;
+        };
+
+        if file.is_none() {
+            *file = open_log_file().ok();
+        }
+
+        if let Some(file) = file.as_mut() {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+
+            // Logging must never fail the actual command
+            let _ = writeln!(
+                file,
+                "{timestamp} {} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock()
+            && let Some(file) = file.as_mut()
+        {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Redirects the log messages to the log file until the guard is dropped.
+pub struct FileRedirectGuard(());
+
+impl Drop for FileRedirectGuard {
+    fn drop(&mut self) {
+        LOGGER.flush();
+        LOGGER.to_file.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Sets up the logger. `verbosity` is the number of `--verbose` flags.
+pub fn init(verbosity: u8) {
+    let env_value = std::env::var(LOG_ENV_VAR).ok();
+
+    // Only fails if a logger was already set, e.g. in tests
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(get_level_filter(env_value.as_deref(), verbosity));
+    }
+}
+
+/// Writes log messages to the log file instead of stderr, e.g. while the TUI
+/// is drawn.
+pub fn redirect_to_file() -> FileRedirectGuard {
+    LOGGER.to_file.store(true, Ordering::Relaxed);
+
+    FileRedirectGuard(())
+}
+
+/// Returns the path of the log file.
+pub fn get_log_file_path() -> anyhow::Result<PathBuf> {
+    Ok(storage::get_category_dir(StorageCategory::Logs)?.join(LOG_FILE_NAME))
+}
+
+fn open_log_file() -> anyhow::Result<File> {
+    let path = get_log_file_path()?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    Ok(OpenOptions::new().create(true).append(true).open(path)?)
+}
+
+fn get_level_filter(env_value: Option<&str>, verbosity: u8) -> LevelFilter {
+    let base_level = env_value
+        .and_then(|value| LevelFilter::from_str(value.trim()).ok())
+        .unwrap_or(LevelFilter::Warn);
+
+    LevelFilter::iter()
+        .skip_while(|level| *level != base_level)
+        .nth(verbosity as usize)
+        .unwrap_or(LevelFilter::Trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_level_filter_defaults_to_warn() {
+        assert_eq!(get_level_filter(None, 0), LevelFilter::Warn);
+        assert_eq!(get_level_filter(Some("nonsense"), 0), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn test_get_level_filter_from_env() {
+        assert_eq!(get_level_filter(Some("debug"), 0), LevelFilter::Debug);
+        assert_eq!(get_level_filter(Some("OFF"), 0), LevelFilter::Off);
+    }
+
+    #[test]
+    fn test_get_level_filter_raised_by_verbosity() {
+        assert_eq!(get_level_filter(None, 1), LevelFilter::Info);
+        assert_eq!(get_level_filter(None, 2), LevelFilter::Debug);
+        assert_eq!(get_level_filter(Some("debug"), 5), LevelFilter::Trace);
+    }
+}
@@ -3,9 +3,15 @@ use std::process::ExitCode;
 fn main() -> ExitCode {
     match git_forge::run() {
         Ok(_) => ExitCode::SUCCESS,
-        Err(e) => {
-            eprintln!("{e:?}");
-            ExitCode::FAILURE
-        }
+        Err(e) => match e.downcast_ref::<clap::Error>() {
+            Some(clap_error) => {
+                let _ = clap_error.print();
+                ExitCode::from(clap_error.exit_code() as u8)
+            }
+            None => {
+                eprintln!("{e:?}");
+                ExitCode::FAILURE
+            }
+        },
     }
 }
@@ -0,0 +1,251 @@
+//! Reporting of the API rate limits that forges send with their responses.
+//!
+//! The shared response handling of the HTTP client records the rate limit
+//! headers of every response here: `x-ratelimit-*` on GitHub and Gitea/Forgejo
+//! and `ratelimit-*` on GitLab. With `--show-rate-limit`, the state of the last
+//! response is printed to stderr at the end of the run and shown in the info
+//! bar of the TUI.
+
+use std::sync::{
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use reqwest::{Url, header::HeaderMap};
+use time::OffsetDateTime;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LAST_RATE_LIMIT: Mutex<Option<RateLimit>> = Mutex::new(None);
+
+/// The rate limit state of a host as reported with its last response.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimit {
+    host: String,
+    /// `None` if the response didn't contain rate limit headers.
+    quota: Option<Quota>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Quota {
+    limit: u64,
+    remaining: u64,
+    /// Unix timestamp of when the quota is reset.
+    reset: Option<i64>,
+}
+
+impl RateLimit {
+    /// Formats the state for the end-of-run summary, e.g. "api.github.com rate
+    /// limit: 4,322/5,000 remaining, resets in 23 min".
+    fn format_summary(&self, now: i64) -> String {
+        let Some(quota) = &self.quota else {
+            return format!("{} didn't report a rate limit", self.host);
+        };
+        let mut summary = format!(
+            "{} rate limit: {}/{} remaining",
+            self.host,
+            format_thousands(quota.remaining),
+            format_thousands(quota.limit)
+        );
+
+        if let Some(reset) = quota.reset {
+            summary.push_str(&format!(
+                ", resets {}",
+                format_time_until(reset.saturating_sub(now))
+            ));
+        }
+
+        summary
+    }
+
+    /// Formats the remaining quota for the info bar of the TUI.
+    pub fn format_remaining(&self) -> Option<String> {
+        self.quota.as_ref().map(|quota| {
+            format!(
+                "Rate limit: {}/{}",
+                format_thousands(quota.remaining),
+                format_thousands(quota.limit)
+            )
+        })
+    }
+}
+
+/// Sets whether rate limits are recorded and reported.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Records the rate limit headers of a response if reporting is enabled.
+pub fn record(url: &Url, headers: &HeaderMap) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let rate_limit = RateLimit {
+        host: url.host_str().unwrap_or_default().to_string(),
+        quota: parse_quota(headers),
+    };
+
+    if let Ok(mut last) = LAST_RATE_LIMIT.lock() {
+        *last = Some(rate_limit);
+    }
+}
+
+/// Gets the recorded rate limit of the last response if reporting is enabled.
+pub fn get_last() -> Option<RateLimit> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    LAST_RATE_LIMIT.lock().ok()?.clone()
+}
+
+/// Prints the rate limit of the last response to stderr if reporting is
+/// enabled and the run hit the network.
+pub fn print_summary() {
+    if let Some(rate_limit) = get_last() {
+        eprintln!(
+            "{}",
+            rate_limit.format_summary(OffsetDateTime::now_utc().unix_timestamp())
+        );
+    }
+}
+
+fn parse_quota(headers: &HeaderMap) -> Option<Quota> {
+    let get_number = |name: &str| -> Option<u64> {
+        ["x-ratelimit-", "ratelimit-"].iter().find_map(|prefix| {
+            headers
+                .get(format!("{prefix}{name}"))?
+                .to_str()
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        })
+    };
+
+    Some(Quota {
+        limit: get_number("limit")?,
+        remaining: get_number("remaining")?,
+        reset: get_number("reset").and_then(|reset| i64::try_from(reset).ok()),
+    })
+}
+
+/// Formats a number with commas as thousands separators, e.g. 5,000.
+fn format_thousands(number: u64) -> String {
+    let digits = number.to_string();
+    let mut result = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+
+        result.push(digit);
+    }
+
+    result
+}
+
+fn format_time_until(seconds: i64) -> String {
+    match seconds {
+        ..=0 => String::from("now"),
+        1..60 => format!("in {seconds} s"),
+        60..3600 => format!("in {} min", seconds / 60),
+        _ => format!("in {} h {} min", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn create_headers(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    reqwest::header::HeaderName::from_static(name),
+                    HeaderValue::from_static(value),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_quota_from_github_and_gitlab_headers() {
+        let github = create_headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4322"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]);
+        let gitlab = create_headers(&[("ratelimit-limit", "2000"), ("ratelimit-remaining", "0")]);
+
+        assert_eq!(
+            parse_quota(&github),
+            Some(Quota {
+                limit: 5000,
+                remaining: 4322,
+                reset: Some(1_700_000_000),
+            })
+        );
+        assert_eq!(
+            parse_quota(&gitlab),
+            Some(Quota {
+                limit: 2000,
+                remaining: 0,
+                reset: None,
+            })
+        );
+        assert_eq!(
+            parse_quota(&create_headers(&[("x-ratelimit-limit", "60")])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let mut rate_limit = RateLimit {
+            host: String::from("api.github.com"),
+            quota: Some(Quota {
+                limit: 5000,
+                remaining: 4322,
+                reset: Some(1_000 + 23 * 60),
+            }),
+        };
+
+        assert_eq!(
+            rate_limit.format_summary(1_000),
+            "api.github.com rate limit: 4,322/5,000 remaining, resets in 23 min"
+        );
+        assert_eq!(
+            rate_limit.format_remaining().as_deref(),
+            Some("Rate limit: 4,322/5,000")
+        );
+
+        rate_limit.quota = None;
+
+        assert_eq!(
+            rate_limit.format_summary(1_000),
+            "api.github.com didn't report a rate limit"
+        );
+        assert_eq!(rate_limit.format_remaining(), None);
+    }
+
+    #[test]
+    fn test_format_thousands() {
+        assert_eq!(format_thousands(0), "0");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_thousands(1000), "1,000");
+        assert_eq!(format_thousands(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_time_until() {
+        assert_eq!(format_time_until(-5), "now");
+        assert_eq!(format_time_until(42), "in 42 s");
+        assert_eq!(format_time_until(3599), "in 59 min");
+        assert_eq!(format_time_until(3 * 3600 + 120), "in 3 h 2 min");
+    }
+}
@@ -0,0 +1,112 @@
+//! Markers of when incremental listings last succeeded, e.g. `issue list
+//! --since-last-run`.
+//!
+//! A marker is stored per repository and per set of filters in the state
+//! directory, so that listings with different filters don't advance each
+//! other's marker. It holds the time at which the last successful run started
+//! rather than ended, so that items created during a run aren't missed by the
+//! next one. Items created in the same second may be listed twice instead.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{
+    date::Timestamp,
+    git::GitRemoteData,
+    storage::{self, StorageCategory},
+};
+
+/// The marker of a listing.
+#[derive(Debug)]
+pub struct RunMarker {
+    path: PathBuf,
+}
+
+#[derive(Deserialize, Serialize)]
+struct MarkerFile {
+    last_run: Timestamp,
+}
+
+impl RunMarker {
+    /// Gets the marker of the listing `name` with the `filters` in the
+    /// repository of the remote. The filters shouldn't contain dates relative
+    /// to now since every run would get its own marker otherwise.
+    pub fn new(
+        remote: &GitRemoteData,
+        name: &str,
+        filters: &impl Serialize,
+    ) -> anyhow::Result<Self> {
+        let filter_hash = hash_filters(filters)?;
+        let path = storage::get_repo_file_path(
+            StorageCategory::Markers,
+            remote,
+            &format!("{name}-{filter_hash:016x}"),
+        )?;
+
+        Ok(Self { path })
+    }
+
+    /// Gets when the last successful run started. `None` before the first
+    /// one.
+    pub fn get_last_run(&self) -> anyhow::Result<Option<OffsetDateTime>> {
+        Ok(storage::read_json::<MarkerFile>(&self.path)?
+            .map(|marker| OffsetDateTime::from(marker.last_run)))
+    }
+
+    /// Remembers the start of a successful run.
+    pub fn set_last_run(&self, started_at: OffsetDateTime) -> anyhow::Result<()> {
+        // The forges don't filter by fractions of a second
+        let started_at = started_at.replace_nanosecond(0).unwrap_or(started_at);
+
+        storage::write_json(
+            &self.path,
+            &MarkerFile {
+                last_run: started_at.into(),
+            },
+        )
+    }
+}
+
+/// Hashes the JSON of the filters with FNV-1a, since the std hasher isn't
+/// stable across releases.
+fn hash_filters(filters: &impl Serialize) -> anyhow::Result<u64> {
+    let json = serde_json::to_string(filters)?;
+
+    Ok(json.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_filters_tells_filters_apart() {
+        let open = serde_json::json!({ "labels": ["bug"], "state": "open" });
+        let closed = serde_json::json!({ "labels": ["bug"], "state": "closed" });
+
+        assert_eq!(hash_filters(&open).unwrap(), hash_filters(&open).unwrap());
+        assert_ne!(hash_filters(&open).unwrap(), hash_filters(&closed).unwrap());
+    }
+
+    #[test]
+    fn test_run_marker_set_and_get_last_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = RunMarker {
+            path: dir.path().join("host/user/repo/issues-0.json"),
+        };
+        let started_at = Timestamp::parse("2024-03-01T12:00:00.750Z").unwrap();
+
+        assert_eq!(marker.get_last_run().unwrap(), None);
+
+        marker.set_last_run(started_at.into()).unwrap();
+
+        assert_eq!(
+            marker.get_last_run().unwrap().map(Timestamp::from),
+            Timestamp::parse("2024-03-01T12:00:00Z")
+        );
+    }
+}
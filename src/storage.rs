@@ -0,0 +1,292 @@
+//! On-disk storage for data that isn't configuration, e.g. issue snapshots.
+//!
+//! Everything git-forge writes besides the config goes through this module so
+//! that `git forge cache clear` can reliably remove it. The data is sorted into
+//! categories, each living in the matching base directory of the platform
+//! (`$XDG_CACHE_HOME`, `$XDG_STATE_HOME` and `$XDG_DATA_HOME` on Unix-likes,
+//! `%LOCALAPPDATA%` and `%APPDATA%` on Windows) namespaced by the app name.
+//! This uses the same strategy as the config file.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use clap::ValueEnum;
+use etcetera::{AppStrategy, AppStrategyArgs};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::git::GitRemoteData;
+
+const APP_NAME: &str = std::env!("CARGO_PKG_NAME");
+
+/// The kinds of data stored on disk.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum StorageCategory {
+    /// The audit log of changes, see the `audit-log` setting. `cache clear`
+    /// only deletes it with `--category audit`.
+    Audit,
+    /// Cached HTTP responses.
+    Http,
+    /// History of the interactive search.
+    History,
+    /// The authenticated user per host and token.
+    Identity,
+    /// Log files.
+    Logs,
+    /// When incremental listings last ran, e.g. `issue list --since-last-run`.
+    Markers,
+    /// Snapshots of issues for offline use.
+    Snapshots,
+}
+
+impl StorageCategory {
+    pub const ALL: [StorageCategory; 7] = [
+        StorageCategory::Audit,
+        StorageCategory::Http,
+        StorageCategory::History,
+        StorageCategory::Identity,
+        StorageCategory::Logs,
+        StorageCategory::Markers,
+        StorageCategory::Snapshots,
+    ];
+}
+
+impl std::fmt::Display for StorageCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageCategory::Audit => write!(f, "audit"),
+            StorageCategory::Http => write!(f, "http"),
+            StorageCategory::History => write!(f, "history"),
+            StorageCategory::Identity => write!(f, "identity"),
+            StorageCategory::Logs => write!(f, "logs"),
+            StorageCategory::Markers => write!(f, "markers"),
+            StorageCategory::Snapshots => write!(f, "snapshots"),
+        }
+    }
+}
+
+/// The app's base directories.
+#[derive(Debug)]
+pub struct BaseDirs {
+    pub cache: PathBuf,
+    pub data: PathBuf,
+    pub state: PathBuf,
+}
+
+impl BaseDirs {
+    /// Resolves the base directories from the environment.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let strategy = etcetera::choose_app_strategy(AppStrategyArgs {
+            top_level_domain: "rs".to_string(),
+            author: String::new(),
+            app_name: APP_NAME.to_string(),
+        })
+        .context("Failed to determine the home directory")?;
+        let data = strategy.data_dir();
+
+        Ok(Self {
+            cache: strategy.cache_dir(),
+            // Windows has no equivalent of a state dir
+            state: strategy.state_dir().unwrap_or_else(|| data.join("state")),
+            data,
+        })
+    }
+
+    /// Returns the directory of a category.
+    pub fn get_category_dir(&self, category: StorageCategory) -> PathBuf {
+        let base_dir = match category {
+            StorageCategory::Http => &self.cache,
+            StorageCategory::Audit
+            | StorageCategory::History
+            | StorageCategory::Identity
+            | StorageCategory::Logs
+            | StorageCategory::Markers => &self.state,
+            StorageCategory::Snapshots => &self.data,
+        };
+
+        base_dir.join(category.to_string())
+    }
+}
+
+/// Returns the directory of a category.
+pub fn get_category_dir(category: StorageCategory) -> anyhow::Result<PathBuf> {
+    Ok(BaseDirs::from_env()?.get_category_dir(category))
+}
+
+/// Returns the path of a snapshot file for the repository of the remote.
+pub fn get_snapshot_path(remote: &GitRemoteData, name: &str) -> anyhow::Result<PathBuf> {
+    get_repo_file_path(StorageCategory::Snapshots, remote, name)
+}
+
+/// Returns the path of a JSON file of a category for the repository of the
+/// remote.
+pub fn get_repo_file_path(
+    category: StorageCategory,
+    remote: &GitRemoteData,
+    name: &str,
+) -> anyhow::Result<PathBuf> {
+    let host = match remote.port {
+        Some(port) => format!("{}_{port}", remote.host),
+        None => remote.host.clone(),
+    };
+
+    Ok(get_category_dir(category)?
+        .join(host)
+        .join(&remote.path)
+        .join(format!("{name}.json")))
+}
+
+/// Returns the total size in bytes of all files in a directory. A missing
+/// directory has a size of 0.
+pub fn get_dir_size(dir: &Path) -> anyhow::Result<u64> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut size = 0;
+
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        size += if metadata.is_dir() {
+            get_dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+
+    Ok(size)
+}
+
+/// Removes a directory with all its contents. A missing directory is not an
+/// error.
+pub fn remove_dir(dir: &Path) -> anyhow::Result<()> {
+    match fs::remove_dir_all(dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {}", dir.display())),
+    }
+}
+
+/// Reads a JSON file. Returns `None` if the file doesn't exist.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<Option<T>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+        .map(Some)
+}
+
+/// Writes a value as JSON file and creates the parent directories if needed.
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    let content = serde_json::to_string_pretty(value)?;
+
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_base_dirs(root: &Path) -> BaseDirs {
+        BaseDirs {
+            cache: root.join("cache"),
+            data: root.join("data"),
+            state: root.join("state"),
+        }
+    }
+
+    #[test]
+    fn test_get_category_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let base_dirs = create_base_dirs(root.path());
+
+        assert_eq!(
+            base_dirs.get_category_dir(StorageCategory::Audit),
+            root.path().join("state/audit")
+        );
+        assert_eq!(
+            base_dirs.get_category_dir(StorageCategory::Http),
+            root.path().join("cache/http")
+        );
+        assert_eq!(
+            base_dirs.get_category_dir(StorageCategory::History),
+            root.path().join("state/history")
+        );
+        assert_eq!(
+            base_dirs.get_category_dir(StorageCategory::Identity),
+            root.path().join("state/identity")
+        );
+        assert_eq!(
+            base_dirs.get_category_dir(StorageCategory::Markers),
+            root.path().join("state/markers")
+        );
+        assert_eq!(
+            base_dirs.get_category_dir(StorageCategory::Snapshots),
+            root.path().join("data/snapshots")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_base_dirs_respect_xdg_env_vars() {
+        let root = tempfile::tempdir().unwrap();
+        let mut env = crate::test_env::EnvGuard::lock();
+
+        env.set("XDG_CACHE_HOME", root.path().join("cache"));
+        env.set("XDG_DATA_HOME", root.path().join("data"));
+        env.set("XDG_STATE_HOME", root.path().join("state"));
+
+        let base_dirs = BaseDirs::from_env().unwrap();
+
+        assert_eq!(base_dirs.cache, root.path().join("cache").join(APP_NAME));
+        assert_eq!(base_dirs.data, root.path().join("data").join(APP_NAME));
+        assert_eq!(base_dirs.state, root.path().join("state").join(APP_NAME));
+    }
+
+    #[test]
+    fn test_write_and_read_json() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("nested/dir/file.json");
+
+        write_json(&path, &vec![1, 2, 3]).unwrap();
+
+        assert_eq!(read_json::<Vec<u32>>(&path).unwrap(), Some(vec![1, 2, 3]));
+        assert_eq!(
+            read_json::<Vec<u32>>(&root.path().join("missing.json")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_dir_size_and_remove_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = create_base_dirs(root.path()).get_category_dir(StorageCategory::Snapshots);
+
+        assert_eq!(get_dir_size(&dir).unwrap(), 0);
+
+        fs::create_dir_all(dir.join("host/user/repo")).unwrap();
+        fs::write(dir.join("a.json"), "1234").unwrap();
+        fs::write(dir.join("host/user/repo/b.json"), "123456").unwrap();
+
+        assert_eq!(get_dir_size(&dir).unwrap(), 10);
+
+        remove_dir(&dir).unwrap();
+
+        assert!(!dir.exists());
+        assert!(remove_dir(&dir).is_ok());
+    }
+}
@@ -0,0 +1,77 @@
+//! Changing environment variables in tests.
+//!
+//! The tests run in threads of the same process, which share the environment.
+//! So tests that change variables take turns via a lock and restore the
+//! previous values when they are done.
+
+use std::{
+    ffi::{OsStr, OsString},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Holds the lock of the environment. Dropping it restores the variables that
+/// were set through it.
+pub struct EnvGuard {
+    previous_values: Vec<(&'static str, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvGuard {
+    /// Waits until no other test changes the environment. A test that panicked
+    /// while holding the lock restored the variables anyway, so a poisoned
+    /// lock is fine.
+    pub fn lock() -> Self {
+        Self {
+            previous_values: vec![],
+            _lock: ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+
+    /// Sets a variable until the guard is dropped.
+    pub fn set(&mut self, name: &'static str, value: impl AsRef<OsStr>) {
+        if !self.previous_values.iter().any(|(n, _)| *n == name) {
+            self.previous_values.push((name, std::env::var_os(name)));
+        }
+
+        // SAFETY: Every test that changes the environment holds the lock.
+        unsafe {
+            std::env::set_var(name, value);
+        }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (name, value) in self.previous_values.drain(..).rev() {
+            // SAFETY: The lock is still held.
+            unsafe {
+                match value {
+                    Some(value) => std::env::set_var(name, value),
+                    None => std::env::remove_var(name),
+                }
+            }
+        }
+    }
+}
+
+mod tests {
+    use super::*;
+
+    const NAME: &str = "GIT_FORGE_TEST_ENV_GUARD";
+
+    #[test]
+    fn test_dropping_the_guard_restores_the_variables() {
+        {
+            let mut env = EnvGuard::lock();
+
+            env.set(NAME, "a");
+            env.set(NAME, "b");
+
+            assert_eq!(std::env::var(NAME).as_deref(), Ok("b"));
+        }
+
+        assert_eq!(std::env::var_os(NAME), None);
+    }
+}
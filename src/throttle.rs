@@ -0,0 +1,368 @@
+//! Client-side throttling of the requests that change something on a forge.
+//!
+//! Bulk commands like `issue import` or `branch delete-merged` send many
+//! changing requests in a row, which trips the secondary rate limits of the
+//! forges, e.g. GitHub refuses them with a 403 about abuse. So the shared HTTP
+//! client keeps a minimum interval between changing requests
+//! (`http/mutation-interval-ms`) and retries a request once after waiting for
+//! the `Retry-After` of a 403 or 429. The waits are summed up, so that the
+//! bulk commands can show them with their progress.
+
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use reqwest::{StatusCode, header::HeaderMap};
+use time::{OffsetDateTime, format_description::well_known::Rfc2822};
+
+pub const DEFAULT_MUTATION_INTERVAL_MS: u64 = 500;
+/// Longer `Retry-After`s aren't waited for. The request fails instead, so that
+/// a command doesn't seem to hang.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+static THROTTLE: Throttle<SystemClock> = Throttle::new(SystemClock, DEFAULT_MUTATION_INTERVAL_MS);
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+pub struct Throttle<C> {
+    clock: C,
+    interval_ms: AtomicU64,
+    /// When the last changing request was sent.
+    last_mutation: Mutex<Option<Instant>>,
+    /// How long was waited since the waits were last taken.
+    waited: Mutex<Duration>,
+}
+
+impl<C: Clock> Throttle<C> {
+    pub const fn new(clock: C, interval_ms: u64) -> Self {
+        Self {
+            clock,
+            interval_ms: AtomicU64::new(interval_ms),
+            last_mutation: Mutex::new(None),
+            waited: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Sends a request with `send`. Changing requests wait for the minimum
+    /// interval since the previous one first. If `get_retry_after` finds a
+    /// delay in the response, the request is sent once more after waiting for
+    /// it.
+    pub fn send<R, E>(
+        &self,
+        is_mutation: bool,
+        mut send: impl FnMut() -> Result<R, E>,
+        get_retry_after: impl Fn(&R) -> Option<Duration>,
+    ) -> Result<R, E> {
+        if is_mutation {
+            self.pace();
+        }
+
+        let response = send()?;
+
+        let Some(retry_after) = get_retry_after(&response) else {
+            return Ok(response);
+        };
+
+        log::warn!(
+            "The forge asked to retry in {}, waiting...",
+            format_duration(retry_after)
+        );
+        self.wait(retry_after);
+
+        if is_mutation {
+            self.pace();
+        }
+
+        send()
+    }
+
+    fn pace(&self) {
+        let interval = Duration::from_millis(self.interval_ms.load(Ordering::Relaxed));
+        let Ok(mut last_mutation) = self.last_mutation.lock() else {
+            return;
+        };
+
+        if let Some(last) = *last_mutation {
+            let elapsed = self.clock.now().saturating_duration_since(last);
+
+            if elapsed < interval {
+                self.wait(interval - elapsed);
+            }
+        }
+
+        *last_mutation = Some(self.clock.now());
+    }
+
+    fn wait(&self, duration: Duration) {
+        self.clock.sleep(duration);
+
+        if let Ok(mut waited) = self.waited.lock() {
+            *waited += duration;
+        }
+    }
+
+    fn take_waited(&self) -> Duration {
+        self.waited
+            .lock()
+            .map(|mut waited| std::mem::take(&mut *waited))
+            .unwrap_or_default()
+    }
+}
+
+/// Sets the minimum interval between changing requests. 0 turns the pacing
+/// off.
+pub fn set_mutation_interval(interval_ms: u64) {
+    THROTTLE.interval_ms.store(interval_ms, Ordering::Relaxed);
+}
+
+/// Sends a request with the process-wide throttle, see [`Throttle::send`].
+pub fn send<R, E>(
+    is_mutation: bool,
+    send: impl FnMut() -> Result<R, E>,
+    get_retry_after: impl Fn(&R) -> Option<Duration>,
+) -> Result<R, E> {
+    THROTTLE.send(is_mutation, send, get_retry_after)
+}
+
+/// Formats the waits for the throttle since the last call for the progress of
+/// bulk commands, e.g. " (throttled for 1.5s)". It's empty if nothing was
+/// waited for.
+pub fn take_wait_note() -> String {
+    format_wait_note(THROTTLE.take_waited())
+}
+
+fn format_wait_note(waited: Duration) -> String {
+    if waited.is_zero() {
+        return String::new();
+    }
+
+    format!(" (throttled for {})", format_duration(waited))
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}s", duration.as_secs_f64())
+}
+
+/// Gets how long to wait before retrying a request that a forge refused with a
+/// 403 or 429 and a `Retry-After` header. It's either a number of seconds or an
+/// HTTP date. Delays above [`MAX_RETRY_AFTER`] are ignored.
+pub fn get_retry_after(status: StatusCode, headers: &HeaderMap, now: i64) -> Option<Duration> {
+    if !matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) {
+        return None;
+    }
+
+    let value = headers.get("retry-after")?.to_str().ok()?.trim();
+    let seconds = match value.parse::<u64>() {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+
+            u64::try_from(date.unix_timestamp().saturating_sub(now)).unwrap_or_default()
+        }
+    };
+    let retry_after = Duration::from_secs(seconds);
+
+    (retry_after <= MAX_RETRY_AFTER).then_some(retry_after)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[derive(Clone)]
+    struct FakeClock {
+        now: Rc<RefCell<Instant>>,
+    }
+
+    impl FakeClock {
+        fn new() -> Self {
+            Self {
+                now: Rc::new(RefCell::new(Instant::now())),
+            }
+        }
+
+        fn advance(&self, duration: Duration) {
+            *self.now.borrow_mut() += duration;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            *self.now.borrow()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.advance(duration);
+        }
+    }
+
+    /// Records when the requests were sent, relative to the start, and answers
+    /// with the given retry delays, one per request.
+    struct FakeTransport {
+        clock: FakeClock,
+        start: Instant,
+        sent_at: RefCell<Vec<Duration>>,
+        retry_afters: RefCell<Vec<Option<Duration>>>,
+    }
+
+    impl FakeTransport {
+        fn new(clock: &FakeClock, retry_afters: Vec<Option<Duration>>) -> Self {
+            Self {
+                clock: clock.clone(),
+                start: clock.now(),
+                sent_at: RefCell::new(vec![]),
+                retry_afters: RefCell::new(retry_afters),
+            }
+        }
+
+        fn send(&self) -> Result<Option<Duration>, ()> {
+            self.sent_at
+                .borrow_mut()
+                .push(self.clock.now() - self.start);
+
+            let mut retry_afters = self.retry_afters.borrow_mut();
+
+            Ok(if retry_afters.is_empty() {
+                None
+            } else {
+                retry_afters.remove(0)
+            })
+        }
+    }
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_mutations_keep_the_interval() {
+        let clock = FakeClock::new();
+        let throttle = Throttle::new(clock.clone(), 500);
+        let transport = FakeTransport::new(&clock, vec![]);
+
+        throttle.send(true, || transport.send(), |r| *r).unwrap();
+        clock.advance(millis(200));
+        throttle.send(true, || transport.send(), |r| *r).unwrap();
+        clock.advance(millis(800));
+        throttle.send(true, || transport.send(), |r| *r).unwrap();
+
+        assert_eq!(
+            *transport.sent_at.borrow(),
+            [millis(0), millis(500), millis(1300)]
+        );
+        assert_eq!(throttle.take_waited(), millis(300));
+        assert_eq!(throttle.take_waited(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_reads_arent_paced() {
+        let clock = FakeClock::new();
+        let throttle = Throttle::new(clock.clone(), 500);
+        let transport = FakeTransport::new(&clock, vec![]);
+
+        throttle.send(true, || transport.send(), |r| *r).unwrap();
+        throttle.send(false, || transport.send(), |r| *r).unwrap();
+        throttle.send(false, || transport.send(), |r| *r).unwrap();
+
+        assert_eq!(*transport.sent_at.borrow(), [millis(0); 3]);
+    }
+
+    #[test]
+    fn test_zero_interval_turns_pacing_off() {
+        let clock = FakeClock::new();
+        let throttle = Throttle::new(clock.clone(), 0);
+        let transport = FakeTransport::new(&clock, vec![]);
+
+        throttle.send(true, || transport.send(), |r| *r).unwrap();
+        throttle.send(true, || transport.send(), |r| *r).unwrap();
+
+        assert_eq!(*transport.sent_at.borrow(), [millis(0); 2]);
+    }
+
+    #[test]
+    fn test_retries_once_after_retry_after() {
+        let clock = FakeClock::new();
+        let throttle = Throttle::new(clock.clone(), 500);
+        let transport = FakeTransport::new(
+            &clock,
+            vec![Some(Duration::from_secs(2)), Some(Duration::from_secs(2))],
+        );
+
+        let response = throttle.send(true, || transport.send(), |r| *r).unwrap();
+
+        assert_eq!(*transport.sent_at.borrow(), [millis(0), millis(2000)]);
+        // The second refusal is returned, so that it fails with its status
+        assert_eq!(response, Some(Duration::from_secs(2)));
+        assert_eq!(throttle.take_waited(), millis(2000));
+    }
+
+    #[test]
+    fn test_get_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("30"));
+
+        assert_eq!(
+            get_retry_after(StatusCode::FORBIDDEN, &headers, 0),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            get_retry_after(StatusCode::TOO_MANY_REQUESTS, &headers, 0),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            get_retry_after(StatusCode::SERVICE_UNAVAILABLE, &headers, 0),
+            None
+        );
+        assert_eq!(
+            get_retry_after(StatusCode::FORBIDDEN, &HeaderMap::new(), 0),
+            None
+        );
+
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+
+        assert_eq!(
+            get_retry_after(StatusCode::TOO_MANY_REQUESTS, &headers, 1_445_412_470),
+            Some(Duration::from_secs(10))
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("3600"));
+
+        assert_eq!(get_retry_after(StatusCode::FORBIDDEN, &headers, 0), None);
+    }
+
+    #[test]
+    fn test_format_wait_note() {
+        assert_eq!(format_wait_note(Duration::ZERO), "");
+        assert_eq!(format_wait_note(millis(1500)), " (throttled for 1.5s)");
+    }
+}
@@ -14,7 +14,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::{self, Block, Borders, HighlightSpacing, Paragraph, Wrap},
 };
-use std::{collections::HashMap, io, panic, sync::Arc};
+use std::{cmp::Ordering, collections::HashMap, io, panic, sync::Arc};
 use std::{str::FromStr, thread};
 use std::{
     sync::mpsc::{self, Receiver},
@@ -23,20 +23,33 @@ use std::{
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+use crate::{date::Timestamp, io::truncate_to_width, logging, rate_limit};
+
 const COLOR_DIM: Color = Color::DarkGray;
 const COLOR_FOCUS: Color = Color::LightBlue;
+const COLOR_WARNING: Color = Color::Yellow;
 const MAX_HISTORY_SIZE: usize = 100;
+/// The bounds of the page size that is derived from the height of the list.
+const MIN_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+const MAX_PREVIOUS_RESULTS: usize = 10;
 const SELECTION_PREFIX: &str = "> ";
 
 /// Displays an interactive selection UI with search and filtering.
 ///
 /// The `fetch` function receives a page number and fetch options.
 /// Users can search with `@key=value` fetch options or plain text queries.
+/// The search bar hints at the keys and values of `option_specs` while the
+/// user types an option.
 ///
 /// # Errors
 ///
 /// Returns an error if the selection was cancelled or the fetch fails.
-pub fn select_item_with<T, F>(initial_options: FetchOptions, fetch: F) -> anyhow::Result<T>
+pub fn select_item_with<T, F>(
+    initial_options: FetchOptions,
+    option_specs: Vec<FetchOptionSpec>,
+    fetch: F,
+) -> anyhow::Result<T>
 where
     T: ListableItem,
     F: Fn(u32, &FetchOptions, FetchResult<T>) -> anyhow::Result<FetchResult<T>>
@@ -44,8 +57,10 @@ where
         + Sync
         + 'static,
 {
+    // Writing to stderr would break the drawing
+    let _log_guard = logging::redirect_to_file();
     let mut terminal = setup_tui()?;
-    let mut app = App::new(fetch, initial_options);
+    let mut app = App::new(fetch, initial_options).with_option_specs(option_specs);
 
     let selected_index = loop {
         if let Err(e) = terminal.draw(|frame| app.render(frame)) {
@@ -114,6 +129,119 @@ fn restore_tui() -> io::Result<()> {
 pub trait ListableItem: Clone + Send + 'static {
     /// Returns the display text for this item.
     fn get_display_text(&self) -> String;
+
+    /// Returns an identifier that is unique among the items of a listing. It's
+    /// used to keep the selection when the items are replaced.
+    fn get_id(&self) -> String;
+
+    /// Returns the value to sort the loaded items by with `s`. Keys without a
+    /// value are skipped when cycling through the keys.
+    fn get_sort_value(&self, _key: SortKey) -> Option<SortValue> {
+        None
+    }
+}
+
+/// What the loaded items can be sorted by in the TUI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortKey {
+    Id,
+    Updated,
+    Created,
+    Title,
+    Reactions,
+}
+
+impl SortKey {
+    const ALL: [SortKey; 5] = [
+        SortKey::Id,
+        SortKey::Updated,
+        SortKey::Created,
+        SortKey::Title,
+        SortKey::Reactions,
+    ];
+
+    /// Titles are sorted alphabetically, everything else newest or most first.
+    fn is_descending_by_default(self) -> bool {
+        self != SortKey::Title
+    }
+}
+
+impl std::fmt::Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Id => write!(f, "id"),
+            SortKey::Updated => write!(f, "updated"),
+            SortKey::Created => write!(f, "created"),
+            SortKey::Title => write!(f, "title"),
+            SortKey::Reactions => write!(f, "reactions"),
+        }
+    }
+}
+
+/// The value of an item for a [`SortKey`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SortValue {
+    Number(u64),
+    /// Compared case-insensitively.
+    Text(String),
+    Timestamp(Timestamp),
+}
+
+impl Ord for SortValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortValue::Number(a), SortValue::Number(b)) => a.cmp(b),
+            (SortValue::Text(a), SortValue::Text(b)) => a
+                .to_lowercase()
+                .cmp(&b.to_lowercase())
+                .then_with(|| a.cmp(b)),
+            (SortValue::Timestamp(a), SortValue::Timestamp(b)) => a.cmp(b),
+            // The items of a listing return the same kind of value for a key
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+impl PartialOrd for SortValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The client-side sort of the loaded items.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ItemSort {
+    key: SortKey,
+    descending: bool,
+}
+
+impl ItemSort {
+    fn new(key: SortKey) -> Self {
+        ItemSort {
+            key,
+            descending: key.is_descending_by_default(),
+        }
+    }
+
+    /// Compares two items. Items without a value for the key come last in
+    /// both directions.
+    fn compare<T: ListableItem>(&self, a: &T, b: &T) -> Ordering {
+        match (a.get_sort_value(self.key), b.get_sort_value(self.key)) {
+            (Some(a), Some(b)) if self.descending => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl std::fmt::Display for ItemSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arrow = if self.descending { "↓" } else { "↑" };
+
+        write!(f, "Sort: {} {arrow}", self.key)
+    }
 }
 
 /// Options to configure the fetch function.
@@ -159,6 +287,45 @@ impl FetchOptions {
     }
 }
 
+/// Describes a `@key=value` fetch option, so the search bar can list the keys
+/// and values while the user types.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FetchOptionSpec {
+    key: &'static str,
+    /// The allowed values. Empty if any value is allowed.
+    values: Vec<String>,
+}
+
+impl FetchOptionSpec {
+    /// An option that takes any value, e.g. a username.
+    pub fn new(key: &'static str) -> Self {
+        FetchOptionSpec {
+            key,
+            values: vec![],
+        }
+    }
+
+    /// An option that takes the values of a clap ValueEnum.
+    pub fn with_enum_values<T: ValueEnum>(key: &'static str) -> Self {
+        FetchOptionSpec {
+            key,
+            values: T::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value)
+                .map(|value| value.get_name().to_string())
+                .collect(),
+        }
+    }
+
+    /// An option that takes `true` or `false`.
+    pub fn flag(key: &'static str) -> Self {
+        FetchOptionSpec {
+            key,
+            values: vec![String::from("true"), String::from("false")],
+        }
+    }
+}
+
 /// Builds FetchOptions from key-value pairs.
 ///
 /// # Example
@@ -240,6 +407,8 @@ pub struct FetchResult<T> {
     items: Vec<T>,
     /// The page of the fetch
     page: u32,
+    /// The number of items to fetch per page
+    page_size: u32,
 }
 
 impl<T> FetchResult<T> {
@@ -249,9 +418,16 @@ impl<T> FetchResult<T> {
             items: vec![],
             more_items: true,
             page: 1,
+            page_size: MIN_PAGE_SIZE,
         }
     }
 
+    /// Gets the number of items to fetch per page. It fills the list's height
+    /// at the start of the search, so the first page fills the screen.
+    pub fn page_size(&self) -> u32 {
+        self.page_size
+    }
+
     pub fn with_items(mut self, items: Vec<T>) -> Self {
         self.items.extend(items);
 
@@ -275,6 +451,12 @@ impl<T> FetchResult<T> {
 
         self
     }
+
+    fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+
+        self
+    }
 }
 
 #[derive(Default)]
@@ -282,6 +464,9 @@ enum FetchStatus<T> {
     #[default]
     Idle,
     Fetching(Receiver<anyhow::Result<FetchResult<T>>>),
+    /// The last fetch was cancelled. Its thread keeps running until the
+    /// request finishes or times out, but the result is dropped.
+    Cancelled,
 }
 
 type FetchFn<T> =
@@ -327,6 +512,21 @@ impl<T: ListableItem> ItemFetcher<T> {
         matches!(self.status, FetchStatus::Fetching { .. })
     }
 
+    fn is_cancelled(&self) -> bool {
+        matches!(self.status, FetchStatus::Cancelled)
+    }
+
+    /// Cancels the in-flight fetch. Returns whether there was one.
+    fn cancel(&mut self) -> bool {
+        if !self.is_fetching() {
+            return false;
+        }
+
+        self.status = FetchStatus::Cancelled;
+
+        true
+    }
+
     fn poll_result(&mut self) -> Option<anyhow::Result<FetchResult<T>>> {
         if let FetchStatus::Fetching(rx) = &self.status
             && let Ok(result) = rx.try_recv()
@@ -347,13 +547,20 @@ impl<T: ListableItem> ItemFetcher<T> {
 #[derive(Default)]
 struct ListState<T> {
     items: Vec<T>,
+    /// The position of each item in the order of the forge. It's the last
+    /// tiebreak of the sort, so that earlier sorts don't affect it.
+    fetch_order: Vec<usize>,
+    /// The sort of the items. `None` keeps the order of the forge.
+    sort: Option<ItemSort>,
     state: widgets::ListState,
 }
 
-impl<T> ListState<T> {
+impl<T: ListableItem> ListState<T> {
     fn new() -> Self {
         Self {
             items: vec![],
+            fetch_order: vec![],
+            sort: None,
             state: widgets::ListState::default(),
         }
     }
@@ -370,20 +577,106 @@ impl<T> ListState<T> {
         &self.items
     }
 
+    /// Appends the items of the next page. The pages come in the order of the
+    /// forge, so they are merged into the loaded items under the active sort.
     fn append_items(&mut self, new_items: Vec<T>) {
+        self.fetch_order
+            .extend(self.items.len()..self.items.len() + new_items.len());
         self.items.extend(new_items);
+        self.apply_sort();
     }
 
+    /// Replaces the items and reselects the previously selected item if it's
+    /// part of the new items. Otherwise, the first item is selected.
     fn replace_items(&mut self, new_items: Vec<T>) {
+        let selected_id = self.selected_item().map(ListableItem::get_id);
+
+        self.fetch_order = (0..new_items.len()).collect();
         self.items = new_items;
-        self.state
-            .select(if self.items.is_empty() { None } else { Some(0) });
+        self.sort_items();
+        self.select_by_id(selected_id);
+    }
+
+    /// Restores items with their positions in the order of the forge and the
+    /// selection, e.g. of a previous search, as they were. They are sorted
+    /// again if the sort changed in the meantime.
+    fn restore_items(
+        &mut self,
+        items: Vec<T>,
+        fetch_order: Vec<usize>,
+        selected_index: Option<usize>,
+    ) {
+        self.items = items;
+        self.fetch_order = fetch_order;
+        self.state.select(selected_index);
+        self.apply_sort();
+    }
+
+    /// Sorts the items and keeps the selected item selected.
+    fn set_sort(&mut self, sort: ItemSort) {
+        self.sort = Some(sort);
+        self.apply_sort();
+    }
+
+    /// The sort keys that at least one of the items has a value for.
+    fn available_sort_keys(&self) -> Vec<SortKey> {
+        SortKey::ALL
+            .into_iter()
+            .filter(|&key| {
+                self.items
+                    .iter()
+                    .any(|item| item.get_sort_value(key).is_some())
+            })
+            .collect()
+    }
+
+    fn apply_sort(&mut self) {
+        if self.sort.is_none() {
+            return;
+        }
+
+        let selected_id = self.selected_item().map(ListableItem::get_id);
+
+        self.sort_items();
+        self.select_by_id(selected_id);
+    }
+
+    /// Sorts the items. Items with the same value keep the order of the forge.
+    fn sort_items(&mut self) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+        let mut entries: Vec<(usize, T)> = self
+            .fetch_order
+            .drain(..)
+            .zip(self.items.drain(..))
+            .collect();
+
+        entries.sort_by(|(a_position, a), (b_position, b)| {
+            sort.compare(a, b).then(a_position.cmp(b_position))
+        });
+        (self.fetch_order, self.items) = entries.into_iter().unzip();
+    }
+
+    /// Selects the item with the id, or the first item if it's gone.
+    fn select_by_id(&mut self, id: Option<String>) {
+        let index = id
+            .and_then(|id| self.items.iter().position(|item| item.get_id() == id))
+            .or(if self.items.is_empty() { None } else { Some(0) });
+
+        self.state.select(index);
     }
 
     fn selected_index(&self) -> Option<usize> {
         self.state.selected()
     }
 
+    fn selected_item(&self) -> Option<&T> {
+        self.state
+            .selected()
+            .and_then(|index| self.items.get(index))
+    }
+
     fn select_next(&mut self) {
         self.state.select_next();
     }
@@ -458,6 +751,16 @@ impl SearchState {
         !self.query.is_empty()
     }
 
+    /// Gets the part of the word at the cursor that is before the cursor.
+    fn word_before_cursor(&self) -> &str {
+        let byte_index = self.grapheme_index_to_byte_index(self.cursor_pos);
+
+        self.query[..byte_index]
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+    }
+
     fn display_width_up_to_cursor(&self) -> usize {
         let byte_index = self.grapheme_index_to_byte_index(self.cursor_pos);
 
@@ -657,6 +960,10 @@ struct PaginationState {
     /// moment. It actually needs to be set from the outside because it depends
     /// on the terminal height.
     per_page: u16,
+    /// The number of items fetched per page. It's set at the start of a search
+    /// and kept for its later pages since the pages would overlap otherwise,
+    /// e.g. after resizing the terminal. 0 if it isn't set yet.
+    page_size: u32,
     /// API has more items to fetch.
     has_next_page: bool,
 }
@@ -664,8 +971,17 @@ struct PaginationState {
 impl PaginationState {
     fn reset(&mut self) {
         self.current_page = 0;
+        self.page_size = 0;
         self.has_next_page = true;
     }
+
+    fn get_or_init_page_size(&mut self) -> u32 {
+        if self.page_size == 0 {
+            self.page_size = u32::from(self.per_page).clamp(MIN_PAGE_SIZE, MAX_PAGE_SIZE);
+        }
+
+        self.page_size
+    }
 }
 
 impl Default for PaginationState {
@@ -674,10 +990,22 @@ impl Default for PaginationState {
             has_next_page: true, // default to true for initial fetch
             current_page: Default::default(),
             per_page: Default::default(),
+            page_size: Default::default(),
         }
     }
 }
 
+/// The results of a previous search, which can be restored with Backspace.
+struct PreviousResults<T> {
+    items: Vec<T>,
+    fetch_order: Vec<usize>,
+    options: FetchOptions,
+    selected_index: Option<usize>,
+    current_page: u32,
+    page_size: u32,
+    has_next_page: bool,
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum Focus {
     List,
@@ -706,7 +1034,9 @@ struct App<T: ListableItem> {
     mode: Mode,
     item_fetcher: ItemFetcher<T>,
     list: ListState<T>,
+    option_specs: Vec<FetchOptionSpec>,
     pagination: PaginationState,
+    previous_results: Vec<PreviousResults<T>>,
     search: SearchState,
 }
 
@@ -728,11 +1058,19 @@ impl<T: ListableItem> App<T> {
             mode: Mode::default(),
             item_fetcher: ItemFetcher::new(fetch, initial_options),
             list: ListState::new(),
+            option_specs: vec![],
             pagination: PaginationState::default(),
+            previous_results: vec![],
             search,
         }
     }
 
+    fn with_option_specs(mut self, option_specs: Vec<FetchOptionSpec>) -> Self {
+        self.option_specs = option_specs;
+
+        self
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) -> UserAction {
         let KeyEvent {
             code,
@@ -779,7 +1117,11 @@ impl<T: ListableItem> App<T> {
                 None => true, // fetch on start of TUI
             };
 
-        if !self.item_fetcher.is_fetching() && self.pagination.has_next_page && reached_end_of_page
+        // Don't immediately restart a fetch that the user just cancelled
+        if !self.item_fetcher.is_fetching()
+            && !self.item_fetcher.is_cancelled()
+            && self.pagination.has_next_page
+            && reached_end_of_page
         {
             self.fetch_and_append_items(self.item_fetcher.options.clone());
         }
@@ -806,7 +1148,10 @@ impl<T: ListableItem> App<T> {
 
     fn fetch_and_append_items(&mut self, options: FetchOptions) {
         let page = self.pagination.current_page + 1;
-        let fetch_result = FetchResult::new().with_page(page).with_append_items(true);
+        let fetch_result = FetchResult::new()
+            .with_page(page)
+            .with_page_size(self.pagination.get_or_init_page_size())
+            .with_append_items(true);
 
         self.item_fetcher.fetch(options, page, fetch_result);
     }
@@ -818,24 +1163,116 @@ impl<T: ListableItem> App<T> {
         self.pagination.reset();
 
         let page = 1;
-        let fetch_result = FetchResult::new().with_page(page).with_append_items(false);
+        let fetch_result = FetchResult::new()
+            .with_page(page)
+            .with_page_size(self.pagination.get_or_init_page_size())
+            .with_append_items(false);
 
         self.item_fetcher.fetch(options, page, fetch_result);
     }
 
+    /// Remembers the current results so that they can be restored after a
+    /// new search.
+    fn save_results(&mut self) {
+        if self.previous_results.len() >= MAX_PREVIOUS_RESULTS {
+            self.previous_results.remove(0);
+        }
+
+        self.previous_results.push(PreviousResults {
+            items: self.list.items.clone(),
+            fetch_order: self.list.fetch_order.clone(),
+            options: self.item_fetcher.options.clone(),
+            selected_index: self.list.selected_index(),
+            current_page: self.pagination.current_page,
+            page_size: self.pagination.page_size,
+            has_next_page: self.pagination.has_next_page,
+        });
+    }
+
+    /// Restores the results of the previous search. An in-flight fetch is
+    /// dropped.
+    fn restore_previous_results(&mut self) {
+        let Some(previous) = self.previous_results.pop() else {
+            return;
+        };
+
+        self.item_fetcher.reset();
+        self.item_fetcher.options = previous.options;
+        self.list.restore_items(
+            previous.items,
+            previous.fetch_order,
+            previous.selected_index,
+        );
+        self.pagination.current_page = previous.current_page;
+        self.pagination.page_size = previous.page_size;
+        self.pagination.has_next_page = previous.has_next_page;
+    }
+
+    /// Sorts the loaded items by the next key that they have values for.
+    fn cycle_sort_key(&mut self) {
+        let keys = self.list.available_sort_keys();
+        let next_index = match self.list.sort {
+            Some(sort) => keys
+                .iter()
+                .position(|&key| key == sort.key)
+                .map_or(0, |index| index + 1),
+            None => 0,
+        };
+
+        if let Some(&key) = keys.get(next_index).or(keys.first()) {
+            self.list.set_sort(ItemSort::new(key));
+        }
+    }
+
+    /// Reverses the sort of the loaded items. Without a sort, the items are
+    /// sorted by the first key in the reverse of its default direction.
+    fn toggle_sort_direction(&mut self) {
+        let sort = match self.list.sort {
+            Some(sort) => Some(sort),
+            None => self
+                .list
+                .available_sort_keys()
+                .first()
+                .map(|&key| ItemSort::new(key)),
+        };
+
+        if let Some(sort) = sort {
+            self.list.set_sort(ItemSort {
+                descending: !sort.descending,
+                ..sort
+            });
+        }
+    }
+
     fn handle_key_event_list_widget(
         &mut self,
         code: KeyCode,
         modifiers: KeyModifiers,
     ) -> UserAction {
         match code {
-            KeyCode::Esc => UserAction::Quit,
+            KeyCode::Esc => {
+                if self.item_fetcher.cancel() {
+                    UserAction::None
+                } else {
+                    UserAction::Quit
+                }
+            }
             KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => UserAction::Quit,
             KeyCode::Char('?') => {
                 self.mode = Mode::Help(Focus::List);
 
                 UserAction::None
             }
+            KeyCode::Char('s') => {
+                self.cycle_sort_key();
+
+                UserAction::None
+            }
+            KeyCode::Char('S') => {
+                self.toggle_sort_direction();
+
+                UserAction::None
+            }
             KeyCode::Char(char) => {
                 self.mode = Mode::Normal(Focus::SearchBar);
 
@@ -848,6 +1285,11 @@ impl<T: ListableItem> App<T> {
 
                 UserAction::None
             }
+            KeyCode::Backspace => {
+                self.restore_previous_results();
+
+                UserAction::None
+            }
             KeyCode::Up => {
                 self.list.select_previous();
 
@@ -887,6 +1329,8 @@ impl<T: ListableItem> App<T> {
                 if self.search.has_query() {
                     self.search.clear();
 
+                    UserAction::None
+                } else if self.item_fetcher.cancel() {
                     UserAction::None
                 } else {
                     UserAction::Quit
@@ -995,6 +1439,7 @@ impl<T: ListableItem> App<T> {
                 self.search.clear();
                 self.mode = Mode::Normal(Focus::List);
 
+                self.save_results();
                 self.fetch_and_replace_items(fetch_options);
 
                 UserAction::None
@@ -1022,16 +1467,27 @@ impl<T: ListableItem> App<T> {
     }
 
     fn render_selection_ui(&mut self, frame: &mut Frame<'_>) {
+        let search_hint = if self.mode == Mode::Normal(Focus::SearchBar) {
+            get_search_hint(self.search.word_before_cursor(), &self.option_specs)
+        } else {
+            None
+        };
         let rects = Layout::vertical([
             Constraint::Min(3),
             Constraint::Length(3),
+            Constraint::Length(if search_hint.is_some() { 1 } else { 0 }),
             Constraint::Length(2),
         ])
         .split(frame.area());
 
         self.render_list(frame, rects[0]);
         self.render_search_bar(frame, rects[1]);
-        self.render_info_bar(frame, rects[2]);
+
+        if let Some(search_hint) = search_hint {
+            self.render_search_hint(frame, rects[2], search_hint);
+        }
+
+        self.render_info_bar(frame, rects[3]);
     }
 
     fn render_list(&mut self, frame: &mut Frame, area: Rect) {
@@ -1049,11 +1505,16 @@ impl<T: ListableItem> App<T> {
             ])
             .block(Block::new())
         } else {
+            let max_width = (area.width as usize).saturating_sub(SELECTION_PREFIX.width());
             let mut list_items: Vec<widgets::ListItem> = self
                 .list
                 .items()
                 .iter()
-                .map(|item| widgets::ListItem::new(item.get_display_text()))
+                .map(|item| {
+                    let text = item.get_display_text();
+
+                    widgets::ListItem::new(truncate_to_width(&text, max_width).into_owned())
+                })
                 .collect();
             let item_count = list_items.len();
             let max_item_count = area.height as usize;
@@ -1086,11 +1547,11 @@ impl<T: ListableItem> App<T> {
         } else {
             COLOR_DIM
         };
-        let search_box = Paragraph::new(Line::from(vec![
-            Span::styled(prefix, Style::new().fg(focus_color)),
-            Span::raw(&self.search.query),
-        ]))
-        .block(
+        let mut spans = vec![Span::styled(prefix, Style::new().fg(focus_color))];
+
+        spans.extend(style_search_query(&self.search.query, &self.option_specs));
+
+        let search_box = Paragraph::new(Line::from(spans)).block(
             Block::new()
                 .borders(Borders::TOP | Borders::BOTTOM)
                 .border_style(Style::new().fg(focus_color)),
@@ -1110,6 +1571,30 @@ impl<T: ListableItem> App<T> {
         }
     }
 
+    fn render_search_hint(&self, frame: &mut Frame, area: Rect, search_hint: SearchHint) {
+        let list_keys = || {
+            self.option_specs
+                .iter()
+                .map(|spec| format!("@{}=", spec.key))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let (text, color) = match search_hint {
+            SearchHint::Keys(keys) => (format!("  Keys: {}", keys.join(" ")), COLOR_DIM),
+            SearchHint::Values(spec) if spec.values.is_empty() => {
+                (format!("  @{}= takes any value", spec.key), COLOR_DIM)
+            }
+            SearchHint::Values(spec) => (format!("  Values: {}", spec.values.join(" ")), COLOR_DIM),
+            SearchHint::UnknownKey(key) => (
+                format!("  Unknown key @{key}. Keys: {}", list_keys()),
+                COLOR_WARNING,
+            ),
+        };
+        let text = truncate_to_width(&text, area.width as usize).into_owned();
+
+        frame.render_widget(Paragraph::new(text).style(Style::new().fg(color)), area);
+    }
+
     fn render_help(&self, frame: &mut Frame) {
         let areas =
             Layout::vertical([Constraint::Percentage(100), Constraint::Min(2)]).split(frame.area());
@@ -1119,7 +1604,12 @@ impl<T: ListableItem> App<T> {
             Line::from("  ↑/↓              Navigate items"),
             Line::from("  Tab              Focus the search bar"),
             Line::from("  Enter            Select current item"),
-            Line::from("  Esc              Abort selection"),
+            Line::from("  Backspace        Go back to the results of the previous search"),
+            Line::from("  s                Sort the loaded items by the next key"),
+            Line::from("  S                Reverse the sort of the loaded items"),
+            Line::from(
+                "  Esc              Cancel loading, if in progress, otherwise abort selection",
+            ),
             Line::from(""),
             Line::from("Search Bar").bold(),
             Line::from("  ↑/↓              Navigate search history"),
@@ -1127,13 +1617,15 @@ impl<T: ListableItem> App<T> {
             Line::from("  Alt+←/→          Delete words"),
             Line::from("  Tab              Focus the list"),
             Line::from("  Enter            Start search"),
-            Line::from("  Esc              Clear search, if it exists, otherwise abort selection"),
+            Line::from(
+                "  Esc              Clear search, if it exists, otherwise cancel loading or abort selection",
+            ),
             Line::from("  Ctrl+L           Clear search"),
             Line::from("  Ctrl+a/Home      Go to line start"),
             Line::from("  Ctrl+e/End       Go to line end"),
             Line::from("  <text>           Filter items with plain text query"),
             Line::from(
-                "  @<key>=<value>   Add fetch option. Check the subcommands help for possible options (flags), e.g., @state=open",
+                "  @<key>=<value>   Add fetch option. Typing @ lists the possible keys and their values, e.g., @state=open",
             ),
             Line::from(""),
             Line::from(
@@ -1157,6 +1649,8 @@ impl<T: ListableItem> App<T> {
         let options = self.item_fetcher.options.as_hash_map();
         let status_text = if self.item_fetcher.is_fetching() {
             String::from("  Loading items...")
+        } else if self.item_fetcher.is_cancelled() {
+            String::from("  Loading cancelled")
         } else if !options.is_empty() {
             let mut status = String::from("  Search:");
 
@@ -1176,7 +1670,17 @@ impl<T: ListableItem> App<T> {
             String::new()
         };
 
-        let nav_text = "?: Show Help";
+        let mut nav_text = String::new();
+
+        if let Some(sort) = self.list.sort {
+            nav_text.push_str(&format!("{sort}  "));
+        }
+
+        if let Some(remaining) = rate_limit::get_last().and_then(|r| r.format_remaining()) {
+            nav_text.push_str(&format!("{remaining}  "));
+        }
+
+        nav_text.push_str("?: Show Help");
 
         let areas = Layout::horizontal([
             Constraint::Min(status_text.len().saturating_add(5) as u16),
@@ -1223,6 +1727,68 @@ fn format_fetch_options(options: &FetchOptions) -> String {
     result
 }
 
+/// A hint for the `@key=value` option that is typed in the search bar.
+#[derive(Debug, PartialEq)]
+enum SearchHint {
+    /// The keys that start with the typed text, e.g. `@st`.
+    Keys(Vec<String>),
+    /// The values of a complete key, e.g. `@state=`.
+    Values(FetchOptionSpec),
+    UnknownKey(String),
+}
+
+/// Gets the hint for the word before the cursor if it's an `@key=value`
+/// option. Callers without option specs get no hints.
+fn get_search_hint(word: &str, option_specs: &[FetchOptionSpec]) -> Option<SearchHint> {
+    let option_str = word.strip_prefix('@')?;
+
+    if option_specs.is_empty() {
+        return None;
+    }
+
+    if let Some((key, _)) = option_str.split_once('=') {
+        return match option_specs.iter().find(|spec| spec.key == key) {
+            Some(spec) => Some(SearchHint::Values(spec.clone())),
+            None => Some(SearchHint::UnknownKey(key.to_string())),
+        };
+    }
+
+    let keys: Vec<String> = option_specs
+        .iter()
+        .filter(|spec| spec.key.starts_with(option_str))
+        .map(|spec| format!("@{}=", spec.key))
+        .collect();
+
+    if keys.is_empty() {
+        Some(SearchHint::UnknownKey(option_str.to_string()))
+    } else {
+        Some(SearchHint::Keys(keys))
+    }
+}
+
+/// Splits the query into spans and underlines the options with unknown keys.
+fn style_search_query<'a>(query: &'a str, option_specs: &[FetchOptionSpec]) -> Vec<Span<'a>> {
+    query
+        .split_inclusive(char::is_whitespace)
+        .flat_map(|piece| {
+            let word = piece.trim_end();
+
+            if matches!(
+                get_search_hint(word, option_specs),
+                Some(SearchHint::UnknownKey(_))
+            ) && word.contains('=')
+            {
+                vec![
+                    Span::styled(word, Style::new().fg(COLOR_WARNING).underlined()),
+                    Span::raw(&piece[word.len()..]),
+                ]
+            } else {
+                vec![Span::raw(piece)]
+            }
+        })
+        .collect()
+}
+
 fn parse_fetch_options(query: &str) -> FetchOptions {
     let mut options = FetchOptions::default();
     let mut remaining_text = String::new();
@@ -1253,3 +1819,353 @@ fn parse_fetch_options(query: &str) -> FetchOptions {
 
     options
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Modifier;
+
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TestItem(u32);
+
+    impl ListableItem for TestItem {
+        fn get_display_text(&self) -> String {
+            format!("item {}", self.0)
+        }
+
+        fn get_id(&self) -> String {
+            self.0.to_string()
+        }
+
+        /// Items in the same ten have the same number of reactions.
+        fn get_sort_value(&self, key: SortKey) -> Option<SortValue> {
+            match key {
+                SortKey::Id => Some(SortValue::Number(u64::from(self.0))),
+                SortKey::Title => Some(SortValue::Text(self.get_display_text())),
+                SortKey::Reactions => Some(SortValue::Number(u64::from(self.0 / 10))),
+                SortKey::Updated | SortKey::Created => None,
+            }
+        }
+    }
+
+    const FETCH_DURATION: Duration = Duration::from_millis(100);
+
+    fn app_with_slow_fetch() -> App<TestItem> {
+        App::new(
+            |_, _, result: FetchResult<TestItem>| {
+                thread::sleep(FETCH_DURATION);
+
+                Ok(result.with_items(vec![TestItem(1)]).with_more_items(true))
+            },
+            FetchOptions::default(),
+        )
+    }
+
+    fn press_esc(app: &mut App<TestItem>) -> UserAction {
+        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_esc_cancels_fetch_before_quitting() {
+        let mut app = app_with_slow_fetch();
+
+        app.fetch_and_replace_items(FetchOptions::default());
+
+        assert!(matches!(press_esc(&mut app), UserAction::None));
+        assert!(app.item_fetcher.is_cancelled());
+        assert!(matches!(press_esc(&mut app), UserAction::Quit));
+    }
+
+    #[test]
+    fn test_esc_in_search_bar_cancels_fetch_before_quitting() {
+        let mut app = app_with_slow_fetch();
+
+        app.fetch_and_replace_items(FetchOptions::default());
+        app.mode = Mode::Normal(Focus::SearchBar);
+
+        assert!(matches!(press_esc(&mut app), UserAction::None));
+        assert!(matches!(press_esc(&mut app), UserAction::Quit));
+    }
+
+    #[test]
+    fn test_cancelled_fetch_result_is_dropped() {
+        let mut app = app_with_slow_fetch();
+
+        app.fetch_and_replace_items(FetchOptions::default());
+        press_esc(&mut app);
+        thread::sleep(FETCH_DURATION * 2);
+        app.update().unwrap();
+
+        assert!(app.list.is_empty());
+        assert!(app.item_fetcher.is_cancelled());
+    }
+
+    /// Creates an app whose fetch returns the items with the ids given by the
+    /// `ids` fetch option, e.g. `@ids=1,2,3`.
+    fn app_with_fetch_by_ids() -> App<TestItem> {
+        App::new(
+            |_, options, result: FetchResult<TestItem>| {
+                let ids = options.parse_list::<u32>("ids").unwrap_or_default();
+
+                Ok(result
+                    .with_items(ids.into_iter().map(TestItem).collect())
+                    .with_more_items(false))
+            },
+            FetchOptions::default(),
+        )
+    }
+
+    fn search(app: &mut App<TestItem>, query: &str) {
+        app.mode = Mode::Normal(Focus::SearchBar);
+        app.search.query = query.to_string();
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        wait_for_fetch(app);
+    }
+
+    fn get_ids(app: &App<TestItem>) -> Vec<u32> {
+        app.list.items().iter().map(|item| item.0).collect()
+    }
+
+    fn wait_for_fetch(app: &mut App<TestItem>) {
+        while app.item_fetcher.is_fetching() {
+            thread::sleep(Duration::from_millis(5));
+            app.update().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_page_size_fills_the_list() {
+        // The fetched item is the page size that the fetch received
+        let mut app = App::new(
+            |_, _, result: FetchResult<TestItem>| {
+                let page_size = result.page_size();
+
+                Ok(result
+                    .with_items(vec![TestItem(page_size)])
+                    .with_more_items(true))
+            },
+            FetchOptions::default(),
+        );
+
+        for (height, expected) in [(45, 45), (12, 20), (200, 100)] {
+            app.pagination.per_page = height;
+            app.fetch_and_replace_items(FetchOptions::default());
+            wait_for_fetch(&mut app);
+
+            assert_eq!(get_ids(&app), [expected]);
+        }
+
+        // Later pages of the search keep the page size after a resize
+        app.pagination.per_page = 30;
+        app.fetch_and_append_items(FetchOptions::default());
+        wait_for_fetch(&mut app);
+
+        assert_eq!(get_ids(&app), [100, 100]);
+    }
+
+    fn option_specs() -> Vec<FetchOptionSpec> {
+        vec![
+            FetchOptionSpec::new("author"),
+            FetchOptionSpec::flag("draft"),
+            FetchOptionSpec::new("labels"),
+        ]
+    }
+
+    #[test]
+    fn test_get_search_hint() {
+        let specs = option_specs();
+
+        assert_eq!(
+            get_search_hint("@", &specs),
+            Some(SearchHint::Keys(vec![
+                String::from("@author="),
+                String::from("@draft="),
+                String::from("@labels=")
+            ]))
+        );
+        assert_eq!(
+            get_search_hint("@dr", &specs),
+            Some(SearchHint::Keys(vec![String::from("@draft=")]))
+        );
+        assert_eq!(
+            get_search_hint("@draft=t", &specs),
+            Some(SearchHint::Values(FetchOptionSpec::flag("draft")))
+        );
+        assert_eq!(
+            get_search_hint("@state=closed", &specs),
+            Some(SearchHint::UnknownKey(String::from("state")))
+        );
+        assert_eq!(get_search_hint("crash", &specs), None);
+        assert_eq!(get_search_hint("@draft=", &[]), None);
+    }
+
+    #[test]
+    fn test_style_search_query_underlines_unknown_keys() {
+        let spans = style_search_query("crash @state=closed @draft=true", &option_specs());
+        let underlined: Vec<&str> = spans
+            .iter()
+            .filter(|span| span.style.add_modifier.contains(Modifier::UNDERLINED))
+            .map(|span| span.content.as_ref())
+            .collect();
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+
+        assert_eq!(underlined, ["@state=closed"]);
+        assert_eq!(text, "crash @state=closed @draft=true");
+    }
+
+    #[test]
+    fn test_replace_items_keeps_selected_item() {
+        let mut list = ListState::new();
+
+        list.replace_items(vec![TestItem(1), TestItem(2), TestItem(3)]);
+        list.select_next();
+        list.select_next();
+        list.replace_items(vec![TestItem(4), TestItem(3), TestItem(1)]);
+
+        assert_eq!(list.selected_item(), Some(&TestItem(3)));
+
+        list.replace_items(vec![TestItem(5), TestItem(6)]);
+
+        assert_eq!(list.selected_item(), Some(&TestItem(5)));
+
+        list.replace_items(vec![]);
+
+        assert_eq!(list.selected_index(), None);
+    }
+
+    #[test]
+    fn test_search_keeps_selected_item() {
+        let mut app = app_with_fetch_by_ids();
+
+        search(&mut app, "@ids=1,2,3");
+        app.list.select_next();
+        search(&mut app, "@ids=2,4");
+
+        assert_eq!(get_ids(&app), [2, 4]);
+        assert_eq!(app.list.selected_item(), Some(&TestItem(2)));
+    }
+
+    #[test]
+    fn test_backspace_restores_previous_results() {
+        let mut app = app_with_fetch_by_ids();
+        let press_backspace = |app: &mut App<TestItem>| {
+            app.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE))
+        };
+
+        search(&mut app, "@ids=1,2,3");
+        app.list.select_next();
+        app.list.select_next();
+        search(&mut app, "@ids=4,5");
+        search(&mut app, "@ids=6");
+
+        press_backspace(&mut app);
+
+        assert_eq!(get_ids(&app), [4, 5]);
+        assert_eq!(app.item_fetcher.options.parse_str("ids"), Some("4,5"));
+
+        press_backspace(&mut app);
+
+        assert_eq!(get_ids(&app), [1, 2, 3]);
+        assert_eq!(app.list.selected_item(), Some(&TestItem(3)));
+        assert_eq!(app.item_fetcher.options.parse_str("ids"), Some("1,2,3"));
+
+        // The initial, empty results before the first search
+        press_backspace(&mut app);
+        press_backspace(&mut app);
+
+        assert!(app.list.is_empty());
+        assert!(app.previous_results.is_empty());
+    }
+
+    fn test_items(ids: &[u32]) -> Vec<TestItem> {
+        ids.iter().copied().map(TestItem).collect()
+    }
+
+    fn list_ids(list: &ListState<TestItem>) -> Vec<u32> {
+        list.items().iter().map(|item| item.0).collect()
+    }
+
+    #[test]
+    fn test_sort_is_stable_and_keeps_selected_item() {
+        let mut list = ListState::new();
+
+        list.replace_items(test_items(&[3, 12, 1, 15, 2]));
+        list.select_next();
+        list.select_next();
+        list.set_sort(ItemSort::new(SortKey::Reactions));
+
+        // Items with the same number of reactions keep the order of the forge
+        assert_eq!(list_ids(&list), [12, 15, 3, 1, 2]);
+        assert_eq!(list.selected_item(), Some(&TestItem(1)));
+
+        list.set_sort(ItemSort {
+            key: SortKey::Reactions,
+            descending: false,
+        });
+
+        assert_eq!(list_ids(&list), [3, 1, 2, 12, 15]);
+        assert_eq!(list.selected_item(), Some(&TestItem(1)));
+
+        list.set_sort(ItemSort::new(SortKey::Id));
+
+        assert_eq!(list_ids(&list), [15, 12, 3, 2, 1]);
+        assert_eq!(list.selected_item(), Some(&TestItem(1)));
+
+        // Ties fall back to the order of the forge, not to the previous sort
+        list.set_sort(ItemSort::new(SortKey::Reactions));
+
+        assert_eq!(list_ids(&list), [12, 15, 3, 1, 2]);
+    }
+
+    #[test]
+    fn test_sort_texts_case_insensitively() {
+        let text = |text: &str| SortValue::Text(text.to_string());
+
+        assert_eq!(text("b").cmp(&text("A")), Ordering::Greater);
+        assert_eq!(text("a").cmp(&text("B")), Ordering::Less);
+        assert_eq!(text("a").cmp(&text("a")), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_appended_items_are_merged_under_the_sort() {
+        let mut list = ListState::new();
+
+        list.replace_items(test_items(&[1, 5]));
+        list.set_sort(ItemSort::new(SortKey::Id));
+
+        assert_eq!(list.selected_item(), Some(&TestItem(1)));
+
+        list.append_items(test_items(&[3, 7]));
+
+        assert_eq!(list_ids(&list), [7, 5, 3, 1]);
+        assert_eq!(list.selected_item(), Some(&TestItem(1)));
+
+        // New searches are sorted as well
+        list.replace_items(test_items(&[2, 9, 4]));
+
+        assert_eq!(list_ids(&list), [9, 4, 2]);
+    }
+
+    #[test]
+    fn test_s_cycles_the_available_sort_keys() {
+        let mut app = app_with_fetch_by_ids();
+        let press = |app: &mut App<TestItem>, char| {
+            app.handle_key_event(KeyEvent::new(KeyCode::Char(char), KeyModifiers::NONE));
+
+            app.list.sort.map(|sort| sort.to_string())
+        };
+
+        search(&mut app, "@ids=2,11,3");
+
+        assert_eq!(press(&mut app, 's').as_deref(), Some("Sort: id ↓"));
+        assert_eq!(get_ids(&app), [11, 3, 2]);
+        assert_eq!(press(&mut app, 'S').as_deref(), Some("Sort: id ↑"));
+        assert_eq!(get_ids(&app), [2, 3, 11]);
+        // Updated and created are skipped since the items have no values
+        assert_eq!(press(&mut app, 's').as_deref(), Some("Sort: title ↑"));
+        assert_eq!(press(&mut app, 's').as_deref(), Some("Sort: reactions ↓"));
+        assert_eq!(press(&mut app, 's').as_deref(), Some("Sort: id ↓"));
+        assert!(app.mode == Mode::Normal(Focus::List));
+    }
+}
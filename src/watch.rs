@@ -0,0 +1,149 @@
+//! Periodically refreshed listings for the `--watch` flag.
+//!
+//! The listing is re-rendered with plain ANSI escape codes. Rows are diffed by
+//! id against the previous refresh: new rows and changed rows are highlighted,
+//! rows that disappeared (e.g. because they were closed) are shown crossed out
+//! once. Without colors (see `--color`), the rows aren't styled.
+
+use std::{thread, time::Duration};
+
+use crate::io;
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const STYLE_NEW: &str = "\x1b[32m";
+const STYLE_CHANGED: &str = "\x1b[33m";
+const STYLE_REMOVED: &str = "\x1b[9;31m";
+const STYLE_RESET: &str = "\x1b[0m";
+
+/// A single row of a watched listing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchedRow {
+    pub id: u32,
+    pub line: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum RowChange {
+    Unchanged,
+    New,
+    Changed,
+    Removed,
+}
+
+/// Fetches the rows every `interval` and re-renders them if anything changed.
+/// Only returns on error; the user is expected to quit with Ctrl-C.
+pub fn watch(
+    interval: Duration,
+    mut fetch_rows: impl FnMut() -> anyhow::Result<Vec<WatchedRow>>,
+) -> anyhow::Result<()> {
+    let mut previous_rows: Option<Vec<WatchedRow>> = None;
+
+    loop {
+        match fetch_rows() {
+            Ok(rows) if previous_rows.as_ref() != Some(&rows) => {
+                render(&rows, previous_rows.as_deref(), interval);
+                previous_rows = Some(rows);
+            }
+            Ok(_) => {}
+            // Keep watching through temporary network hiccups
+            Err(e) if previous_rows.is_some() => log::warn!("Failed to refresh: {e:#}"),
+            Err(e) => return Err(e),
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+fn render(rows: &[WatchedRow], previous_rows: Option<&[WatchedRow]>, interval: Duration) {
+    let mut output = String::from(CLEAR_SCREEN);
+
+    output.push_str(&format!(
+        "Every {}s. Press Ctrl-C to quit.\n\n",
+        interval.as_secs()
+    ));
+
+    for (change, row) in diff_rows(previous_rows.unwrap_or_default(), rows) {
+        let style = match change {
+            _ if !io::use_color() => None,
+            // Don't highlight everything on the first render
+            _ if previous_rows.is_none() => None,
+            RowChange::Unchanged => None,
+            RowChange::New => Some(STYLE_NEW),
+            RowChange::Changed => Some(STYLE_CHANGED),
+            RowChange::Removed => Some(STYLE_REMOVED),
+        };
+
+        match style {
+            Some(style) => output.push_str(&format!("{style}{}{STYLE_RESET}\n", row.line)),
+            None => output.push_str(&format!("{}\n", row.line)),
+        }
+    }
+
+    print!("{output}");
+}
+
+/// Compares the current rows with the previous rows by id. The current rows
+/// keep their order; removed rows are appended at the end.
+fn diff_rows<'a>(
+    previous_rows: &'a [WatchedRow],
+    rows: &'a [WatchedRow],
+) -> Vec<(RowChange, &'a WatchedRow)> {
+    let mut diff: Vec<(RowChange, &WatchedRow)> = rows
+        .iter()
+        .map(|row| {
+            let change = match previous_rows.iter().find(|prev| prev.id == row.id) {
+                None => RowChange::New,
+                Some(prev) if prev.line != row.line => RowChange::Changed,
+                Some(_) => RowChange::Unchanged,
+            };
+
+            (change, row)
+        })
+        .collect();
+
+    diff.extend(
+        previous_rows
+            .iter()
+            .filter(|prev| !rows.iter().any(|row| row.id == prev.id))
+            .map(|prev| (RowChange::Removed, prev)),
+    );
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: u32, line: &str) -> WatchedRow {
+        WatchedRow {
+            id,
+            line: line.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_rows_first_refresh() {
+        let rows = [row(1, "a"), row(2, "b")];
+        let changes: Vec<RowChange> = diff_rows(&[], &rows).into_iter().map(|d| d.0).collect();
+
+        assert_eq!(changes, [RowChange::New, RowChange::New]);
+    }
+
+    #[test]
+    fn test_diff_rows_detects_changes() {
+        let previous = [row(1, "a"), row(2, "b"), row(3, "c")];
+        let rows = [row(4, "d"), row(1, "a"), row(2, "b renamed")];
+        let diff = diff_rows(&previous, &rows);
+
+        assert_eq!(
+            diff.iter().map(|d| (&d.0, d.1.id)).collect::<Vec<_>>(),
+            [
+                (&RowChange::New, 4),
+                (&RowChange::Unchanged, 1),
+                (&RowChange::Changed, 2),
+                (&RowChange::Removed, 3),
+            ]
+        );
+    }
+}
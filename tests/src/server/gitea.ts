@@ -1,6 +1,7 @@
 import express, { Request, Response } from "express";
 
-import { GITEA_PORT } from "../utils.js";
+import { ARCHIVED_REPO, BRANCHES, GITEA_PORT, REPOS } from "../utils.js";
+import hooks from "./data/gitea/hooks.json";
 import issues from "./data/gitea/issue.json";
 import prs from "./data/gitea/pr.json";
 
@@ -34,10 +35,11 @@ interface PullRequest {
     html_url: string;
     created_at: string;
     updated_at: string;
-    head: { ref: string };
-    base: { ref: string };
+    head: { ref: string; sha: string };
+    base: { ref: string; sha: string };
     draft: boolean;
     merged: boolean;
+    milestone?: { title: string };
 }
 
 interface CreatePrRequest {
@@ -45,11 +47,32 @@ interface CreatePrRequest {
     head: string;
     base: string;
     body?: string;
+    draft?: boolean;
 }
 
 interface CreateIssueRequest {
     title: string;
     body?: string;
+    labels?: number[];
+}
+
+const repoLabels = [
+    { id: 1, name: "bug" },
+    { id: 2, name: "enhancement" },
+    { id: 3, name: "documentation" },
+];
+
+/**
+ * Gets the labels of a fixture issue with the ids of the repository's labels.
+ * The labels that the repository doesn't have get ids starting at 100.
+ */
+function getIssueLabels(number: number): { id: number; name: string }[] {
+    const issue = issues.find((i) => i.number === number);
+
+    return (issue?.labels ?? []).map(({ name }, index) => ({
+        id: repoLabels.find((l) => l.name === name)?.id ?? 100 + index,
+        name,
+    }));
 }
 
 export function createGiteaServer(): express.Express {
@@ -67,28 +90,30 @@ export function createGiteaServer(): express.Express {
                 labels,
                 created_by,
                 assigned_by,
+                milestones,
                 q,
                 page = "1",
                 limit = "30",
             } = req.query;
 
             let filtered: (Issue | PullRequest)[] = [];
+            // For PRs, we need to transform them to include pull_request field
+            const pulls = prs.map((pr) => ({
+                ...pr,
+                pull_request: {
+                    draft: pr.draft,
+                    merged: pr.merged,
+                },
+            }));
 
             // Filter by type (issues or pulls)
             if (type === "issues") {
                 filtered = [...issues];
             } else if (type === "pulls") {
-                // For PRs, we need to transform them to include pull_request field
-                filtered = prs.map((pr) => ({
-                    ...pr,
-                    pull_request: {
-                        draft: pr.draft,
-                        merged: pr.merged,
-                    },
-                }));
+                filtered = pulls;
             } else {
                 // If no type specified, combine both
-                filtered = [...issues, ...prs];
+                filtered = [...issues, ...pulls];
             }
 
             // Filter by state
@@ -127,6 +152,15 @@ export function createGiteaServer(): express.Express {
                 );
             }
 
+            // Filter by milestone
+            if (milestones) {
+                filtered = filtered.filter(
+                    (issue) =>
+                        "milestone" in issue &&
+                        issue.milestone?.title === milestones,
+                );
+            }
+
             // Filter by query (search in title)
             if (q && typeof q === "string") {
                 const searchTerm = q.toLowerCase();
@@ -170,6 +204,301 @@ export function createGiteaServer(): express.Express {
         },
     );
 
+    // Server version endpoint
+    app.get("/api/v1/version", (_req: Request, res: Response) => {
+        res.json({ version: "1.24.0" });
+    });
+
+    // Pull request reviews endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/pulls/:number/reviews",
+        (_req: Request, res: Response) => {
+            res.json([
+                {
+                    user: { login: "bob" },
+                    state: "REQUEST_CHANGES",
+                    dismissed: true,
+                    body: "Please add tests",
+                    submitted_at: "2024-05-01T12:00:00Z",
+                },
+                {
+                    user: { login: "bob" },
+                    state: "APPROVED",
+                    dismissed: false,
+                    body: "",
+                    submitted_at: "2024-05-02T09:00:00Z",
+                },
+                {
+                    user: { login: "carol" },
+                    state: "COMMENT",
+                    dismissed: false,
+                    body: "",
+                    submitted_at: "2024-05-02T10:00:00Z",
+                },
+            ]);
+        },
+    );
+
+    // Pinned issues endpoint. Lists the pinned PRs as well and isn't
+    // paginated.
+    app.get(
+        "/api/v1/repos/:owner/:repo/issues/pinned",
+        (_req: Request, res: Response) => {
+            res.json([
+                { ...issues[0], pin_order: 1 },
+                { ...issues[1], pin_order: 2 },
+                {
+                    ...prs[0],
+                    pull_request: { draft: prs[0].draft, merged: false },
+                    pin_order: 3,
+                },
+            ]);
+        },
+    );
+
+    // Issue comments endpoint (also used for pull request comments)
+    app.get(
+        "/api/v1/repos/:owner/:repo/issues/:number/comments",
+        (_req: Request, res: Response) => {
+            res.json([
+                {
+                    id: 1001,
+                    user: { login: "carol" },
+                    body: "Looks good now",
+                    created_at: "2024-05-01T15:00:00Z",
+                },
+            ]);
+        },
+    );
+
+    // Repository endpoint
+    app.get("/api/v1/repos/:owner/:repo", (req: Request, res: Response) => {
+        const { owner, repo } = req.params;
+
+        res.json({
+            full_name: `${owner}/${repo}`,
+            description: "A test repository",
+            html_url: `http://localhost:${GITEA_PORT}/${owner}/${repo}`,
+            default_branch: "main",
+            archived: repo === ARCHIVED_REPO,
+        });
+    });
+
+    // Organization and user repositories endpoint
+    app.get(
+        ["/api/v1/orgs/:owner/repos", "/api/v1/users/:owner/repos"],
+        (req: Request, res: Response) => {
+            const { owner } = req.params;
+
+            res.json(
+                REPOS.map((r) => ({
+                    full_name: `${owner}/${r.name}`,
+                    private: false,
+                    internal: false,
+                    stars_count: r.stars,
+                    default_branch: "main",
+                    updated_at: r.pushedAt,
+                    archived: r.archived,
+                    fork: r.fork,
+                    html_url: `http://localhost:${GITEA_PORT}/${owner}/${r.name}`,
+                    clone_url: `http://localhost:${GITEA_PORT}/${owner}/${r.name}.git`,
+                    ssh_url: `git@localhost:${owner}/${r.name}.git`,
+                })),
+            );
+        },
+    );
+
+    // Edit repository endpoint
+    app.patch("/api/v1/repos/:owner/:repo", (req: Request, res: Response) => {
+        const authHeader = req.headers.authorization;
+
+        if (!authHeader || !authHeader.startsWith("token ")) {
+            res.sendStatus(403);
+
+            return;
+        }
+
+        res.json(req.body);
+    });
+
+    // Repository languages endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/languages",
+        (_req: Request, res: Response) => {
+            res.json({ TypeScript: 1200, Rust: 48000 });
+        },
+    );
+
+    // Repository topics endpoints
+    app.get(
+        "/api/v1/repos/:owner/:repo/topics",
+        (_req: Request, res: Response) => {
+            res.json({ topics: ["cli", "git"] });
+        },
+    );
+    app.put(
+        "/api/v1/repos/:owner/:repo/topics",
+        (req: Request, res: Response) => {
+            const authHeader = req.headers.authorization;
+
+            if (!authHeader || !authHeader.startsWith("token ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            res.sendStatus(204);
+        },
+    );
+
+    // Webhooks endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/hooks",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            res.json(hooks);
+        },
+    );
+
+    // Edit webhook endpoint
+    app.patch(
+        "/api/v1/repos/:owner/:repo/hooks/:id",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const hook = hooks.find((h) => h.id === Number(req.params.id));
+
+            if (!hook) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            res.json({ ...hook, active: req.body.active });
+        },
+    );
+
+    // Test webhook endpoint
+    app.post(
+        "/api/v1/repos/:owner/:repo/hooks/:id/tests",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const hook = hooks.find((h) => h.id === Number(req.params.id));
+
+            if (!hook) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            res.sendStatus(204);
+        },
+    );
+
+    // Branches endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/branches",
+        (_req: Request, res: Response) => {
+            res.json(
+                BRANCHES.map((b) => ({
+                    name: b.name,
+                    commit: { id: b.sha, timestamp: b.date },
+                    protected: b.protected,
+                })),
+            );
+        },
+    );
+
+    // Compare endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/compare/:basehead",
+        (req: Request, res: Response) => {
+            const head = req.params.basehead.split("...")[1];
+            const branch = BRANCHES.find((b) => b.sha === head);
+
+            res.json({ total_commits: branch?.merged ? 0 : 2 });
+        },
+    );
+
+    // Branch endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/branches/:branch",
+        (req: Request, res: Response) => {
+            if (!BRANCHES.some((b) => b.name === req.params.branch)) {
+                res.status(404).json({ message: "branch not found" });
+
+                return;
+            }
+
+            res.json({ name: req.params.branch });
+        },
+    );
+
+    // Delete branch endpoint
+    app.delete(
+        "/api/v1/repos/:owner/:repo/branches/:branch",
+        (req: Request, res: Response) => {
+            const authHeader = req.headers.authorization;
+
+            if (!authHeader || !authHeader.startsWith("token ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            if (!BRANCHES.some((b) => b.name === req.params.branch)) {
+                res.status(404).json({ message: "branch not found" });
+
+                return;
+            }
+
+            res.sendStatus(204);
+        },
+    );
+
+    // Single pull request endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/pulls/:number",
+        (req: Request, res: Response) => {
+            const pr = prs.find(
+                (pr) => pr.number === Number(req.params.number),
+            );
+
+            res.json({
+                ...pr,
+                mergeable: true,
+                merged: false,
+                head: { ...pr?.head, sha: "abc123" },
+                user: { login: "alice" },
+                body: "Adds the feature\n\nFixes #2",
+                created_at: "2024-05-01T10:00:00Z",
+                requested_reviewers: [{ login: "alice" }],
+            });
+        },
+    );
+
+    // Combined commit status endpoint
+    app.get(
+        "/api/v1/repos/:owner/:repo/commits/:sha/status",
+        (_req: Request, res: Response) => {
+            res.json({ state: "failure", total_count: 2 });
+        },
+    );
+
     let prNumber = 0;
     let issueNumber = 0;
 
@@ -198,14 +527,14 @@ export function createGiteaServer(): express.Express {
                 number: prNumber,
                 title: body.title,
                 state: "open",
-                labels: [],
+                labels: repoLabels.filter((l) => body.labels?.includes(l.id)),
                 user: { login: "test-user" },
                 html_url: `http://localhost:${GITEA_PORT}/${owner}/${repo}/pulls/${prNumber}`,
                 created_at: new Date().toISOString(),
                 updated_at: new Date().toISOString(),
-                head: { ref: body.head },
-                base: { ref: body.base },
-                draft: body.title.startsWith("WIP:"),
+                head: { ref: body.head, sha: "abc123" },
+                base: { ref: body.base, sha: "def456" },
+                draft: body.draft || body.title.startsWith("WIP:"),
                 merged: false,
             };
 
@@ -215,6 +544,34 @@ export function createGiteaServer(): express.Express {
         },
     );
 
+    // List labels endpoint
+    app.get("/api/v1/repos/:owner/:repo/labels", (_req, res: Response) => {
+        res.json(repoLabels);
+    });
+
+    // Create label endpoint. The label is kept since issues and PRs are
+    // created with label ids.
+    app.post(
+        "/api/v1/repos/:owner/:repo/labels",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("token ")) {
+                res.sendStatus(401);
+
+                return;
+            }
+
+            const body = req.body as { name: string; color: string };
+            let label = repoLabels.find((l) => l.name === body.name);
+
+            if (!label) {
+                label = { id: repoLabels.length + 1, name: body.name };
+                repoLabels.push(label);
+            }
+
+            res.status(201).json({ ...label, color: body.color });
+        },
+    );
+
     // Create issue endpoint
     app.post(
         "/api/v1/repos/:owner/:repo/issues",
@@ -227,6 +584,14 @@ export function createGiteaServer(): express.Express {
                 return;
             }
 
+            if (req.params.repo === ARCHIVED_REPO) {
+                res.status(403).json({
+                    message: "repo is archived",
+                });
+
+                return;
+            }
+
             const { owner, repo } = req.params;
             const body = req.body as CreateIssueRequest;
 
@@ -255,5 +620,131 @@ export function createGiteaServer(): express.Express {
         },
     );
 
+    // Authenticated user endpoint
+    app.get("/api/v1/user", (req: Request, res: Response) => {
+        if (!req.headers.authorization?.startsWith("token ")) {
+            res.sendStatus(401);
+
+            return;
+        }
+
+        res.json({ id: 42, login: "test-user" });
+    });
+
+    // Single issue endpoint. Every issue is assigned to alice.
+    app.get(
+        "/api/v1/repos/:owner/:repo/issues/:number",
+        (req: Request, res: Response) => {
+            const { number } = req.params;
+            const issue = issues.find((i) => i.number === Number(number));
+
+            res.json({
+                ...issue,
+                body: issue ? `The description of #${number}` : undefined,
+                number: Number(number),
+                assignees: [{ login: "alice" }],
+            });
+        },
+    );
+
+    // Edit issue endpoint
+    app.patch(
+        "/api/v1/repos/:owner/:repo/issues/:number",
+        (req: Request, res: Response) => {
+            const { owner, repo, number } = req.params;
+            const { assignees } = req.body as { assignees?: string[] };
+
+            res.status(201).json({
+                number: Number(number),
+                title: "Edited issue",
+                state: "open",
+                labels: [],
+                user: { login: "test-user" },
+                assignee: assignees?.length ? { login: assignees[0] } : null,
+                assignees: assignees?.length
+                    ? assignees.map((login) => ({ login }))
+                    : null,
+                html_url: `http://localhost:${GITEA_PORT}/${owner}/${repo}/issues/${number}`,
+                created_at: new Date().toISOString(),
+                updated_at: new Date().toISOString(),
+                pull_request: null,
+            });
+        },
+    );
+
+    // Issue labels endpoints. Added labels are appended to the fixture's
+    // labels and replaced labels replace them.
+    app.get(
+        "/api/v1/repos/:owner/:repo/issues/:number/labels",
+        (req: Request, res: Response) => {
+            res.json(getIssueLabels(Number(req.params.number)));
+        },
+    );
+
+    for (const method of ["post", "put"] as const) {
+        app[method](
+            "/api/v1/repos/:owner/:repo/issues/:number/labels",
+            (req: Request, res: Response) => {
+                if (!req.headers.authorization?.startsWith("token ")) {
+                    res.sendStatus(401);
+
+                    return;
+                }
+
+                const number = Number(req.params.number);
+
+                if (!issues.some((i) => i.number === number)) {
+                    res.sendStatus(404);
+
+                    return;
+                }
+
+                const issueLabels = getIssueLabels(number);
+                const { labels } = req.body as { labels: number[] };
+                const known = [...issueLabels, ...repoLabels];
+                const ids =
+                    method === "post" ? issueLabels.map((l) => l.id) : [];
+
+                res.json(
+                    [...new Set([...ids, ...labels])].map((id) =>
+                        known.find((l) => l.id === id),
+                    ),
+                );
+            },
+        );
+    }
+
+    // Pin and unpin issue endpoints
+    for (const method of ["post", "delete"] as const) {
+        app[method](
+            "/api/v1/repos/:owner/:repo/issues/:number/pin",
+            (req: Request, res: Response) => {
+                if (!req.headers.authorization?.startsWith("token ")) {
+                    res.sendStatus(401);
+
+                    return;
+                }
+
+                res.sendStatus(204);
+            },
+        );
+    }
+
+    // Request and remove reviewers endpoints
+    for (const method of ["post", "delete"] as const) {
+        app[method](
+            "/api/v1/repos/:owner/:repo/pulls/:number/requested_reviewers",
+            (req: Request, res: Response) => {
+                if (!req.headers.authorization?.startsWith("token ")) {
+                    res.sendStatus(401);
+
+                    return;
+                }
+
+                res.sendStatus(method === "post" ? 201 : 204);
+            },
+        );
+    }
+
     return app;
 }
@@ -1,6 +1,7 @@
 import express, { Request, Response } from "express";
 
-import { GITHUB_PORT } from "../utils.js";
+import { ARCHIVED_REPO, BRANCHES, GITHUB_PORT, REPOS } from "../utils.js";
+import hooks from "./data/github/hooks.json";
 import issues from "./data/github/issue.json";
 import prs from "./data/github/pr.json";
 
@@ -16,10 +17,13 @@ interface Issue {
     number: number;
     title: string;
     state: string;
+    state_reason?: string;
     labels: Label[];
     user: User;
     assignee: User | null;
+    created_at: string;
     html_url: string;
+    pull_request?: object;
 }
 
 interface PullRequest {
@@ -31,10 +35,11 @@ interface PullRequest {
     html_url: string;
     created_at: string;
     updated_at: string;
-    head: { ref: string };
-    base: { ref: string };
+    head: { ref: string; sha: string };
+    base: { ref: string; sha: string };
     draft: boolean;
     merged_at: string | null;
+    milestone?: { title: string };
 }
 
 interface CreatePrRequest {
@@ -48,6 +53,7 @@ interface CreatePrRequest {
 interface CreateIssueRequest {
     title: string;
     body?: string;
+    labels?: string[];
 }
 
 export function createGitHubServer(): express.Express {
@@ -64,8 +70,20 @@ export function createGitHubServer(): express.Express {
             return;
         }
 
-        // Parse query string
-        const query = q.toString();
+        // Parse query string. The reason and milestone qualifiers may be
+        // quoted, so extract them before splitting the query into terms.
+        const reasonMatch = q.toString().match(/reason:("[^"]*"|\S+)/);
+        const reason =
+            reasonMatch ?
+                reasonMatch[1].replaceAll('"', "").replace(" ", "_")
+            :   null;
+        const milestoneMatch = q.toString().match(/milestone:("[^"]*"|\S+)/);
+        const milestone =
+            milestoneMatch ? milestoneMatch[1].replaceAll('"', "") : null;
+        const query = q
+            .toString()
+            .replace(/reason:("[^"]*"|\S+)/, "")
+            .replace(/milestone:("[^"]*"|\S+)/, "");
         const isIssue = query.includes("is:issue");
         const isPR = query.includes("is:pr");
         const isOpen = query.includes("is:open");
@@ -73,6 +91,7 @@ export function createGitHubServer(): express.Express {
         const isUnmerged = query.includes("is:unmerged");
         const isMerged = query.includes("is:merged");
         const isDraft = query.includes("draft:true");
+        const hasNoLabel = query.includes("no:label");
 
         // Extract search terms (words that are not qualifiers)
         const searchTerms = query
@@ -84,7 +103,9 @@ export function createGitHubServer(): express.Express {
                     !term.startsWith("author:") &&
                     !term.startsWith("assignee:") &&
                     !term.startsWith("label:") &&
+                    !term.startsWith("no:") &&
                     !term.startsWith("draft:") &&
+                    !term.startsWith("head:") &&
                     !term.startsWith("in:"),
             )
             .map((term) => term.toLowerCase());
@@ -96,18 +117,33 @@ export function createGitHubServer(): express.Express {
         const assigneeMatch = query.match(/assignee:(\S+)/);
         const assignee = assigneeMatch ? assigneeMatch[1] : null;
 
-        const labelMatches = query.match(/label:(\S+)/g);
+        const headMatch = query.match(/head:(\S+)/);
+        const head = headMatch ? headMatch[1] : null;
+
+        const labelMatches = query.match(/(?<!-)label:(\S+)/g);
         const labels =
             labelMatches ?
                 labelMatches.map((l) => l.replace("label:", ""))
             :   [];
 
+        const excludedLabelMatches = query.match(/-label:(\S+)/g);
+        const excludedLabels =
+            excludedLabelMatches ?
+                excludedLabelMatches.map((l) => l.replace("-label:", ""))
+            :   [];
+
         // Start with appropriate items
         let filtered: (Issue | PullRequest)[] = [];
         if (isIssue) {
             filtered = [...issues];
         } else if (isPR) {
             filtered = [...prs];
+        } else {
+            // Issues search results mark the PRs with a pull_request field
+            filtered = [
+                ...issues,
+                ...prs.map((pr) => ({ ...pr, pull_request: {} })),
+            ];
         }
 
         // Filter by state
@@ -129,11 +165,33 @@ export function createGitHubServer(): express.Express {
             filtered = filtered.filter((item) => item.state === "closed");
         }
 
+        // Filter by close reason
+        if (reason) {
+            filtered = filtered.filter(
+                (item) => "state_reason" in item && item.state_reason === reason,
+            );
+        }
+
+        // Filter by milestone
+        if (milestone) {
+            filtered = filtered.filter(
+                (item) =>
+                    "milestone" in item && item.milestone?.title === milestone,
+            );
+        }
+
         // Filter by author
         if (author) {
             filtered = filtered.filter((item) => item.user.login === author);
         }
 
+        // Filter by head branch
+        if (head) {
+            filtered = filtered.filter(
+                (item) => "head" in item && item.head.ref === head,
+            );
+        }
+
         // Filter by assignee
         if (assignee) {
             filtered = filtered.filter(
@@ -151,6 +209,17 @@ export function createGitHubServer(): express.Express {
             );
         }
 
+        if (excludedLabels.length > 0) {
+            filtered = filtered.filter(
+                (item) =>
+                    !item.labels.some((l) => excludedLabels.includes(l.name)),
+            );
+        }
+
+        if (hasNoLabel) {
+            filtered = filtered.filter((item) => item.labels.length === 0);
+        }
+
         // Filter by draft
         if (isDraft) {
             filtered = filtered.filter(
@@ -241,7 +310,7 @@ export function createGitHubServer(): express.Express {
     app.get(
         "/api/v3/repos/:owner/:repo/pulls",
         (req: Request, res: Response) => {
-            const { state, page = "1", per_page = "30" } = req.query;
+            const { state, head, page = "1", per_page = "30" } = req.query;
             let filtered: PullRequest[] = [...prs];
 
             // Filter by state
@@ -250,6 +319,12 @@ export function createGitHubServer(): express.Express {
                 filtered = filtered.filter((pr) => pr.state === s);
             }
 
+            // Filter by head branch given as `owner:branch`
+            if (head) {
+                const branch = head.toString().split(":")[1];
+                filtered = filtered.filter((pr) => pr.head.ref === branch);
+            }
+
             // Pagination
             const pageNum = Number.parseInt(page as string, 10);
             const perPage = Number.parseInt(per_page as string, 10);
@@ -261,6 +336,316 @@ export function createGitHubServer(): express.Express {
         },
     );
 
+    // Pull request reviews endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/pulls/:number/reviews",
+        (_req: Request, res: Response) => {
+            res.json([
+                {
+                    user: { login: "bob" },
+                    state: "CHANGES_REQUESTED",
+                    body: "Please add tests",
+                    submitted_at: "2024-05-01T12:00:00Z",
+                },
+                {
+                    user: { login: "bob" },
+                    state: "APPROVED",
+                    body: "",
+                    submitted_at: "2024-05-02T09:00:00Z",
+                },
+                {
+                    user: { login: "carol" },
+                    state: "COMMENTED",
+                    body: "",
+                    submitted_at: "2024-05-02T10:00:00Z",
+                },
+            ]);
+        },
+    );
+
+    // Issue comments endpoint (also used for pull request comments)
+    app.get(
+        "/api/v3/repos/:owner/:repo/issues/:number/comments",
+        (_req: Request, res: Response) => {
+            res.json([
+                {
+                    id: 1001,
+                    user: { login: "carol" },
+                    body: "Looks good now",
+                    created_at: "2024-05-01T15:00:00Z",
+                },
+            ]);
+        },
+    );
+
+    // Requested reviewers endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/pulls/:number/requested_reviewers",
+        (_req: Request, res: Response) => {
+            res.json({ users: [], teams: [] });
+        },
+    );
+
+    // Repository endpoint
+    app.get("/api/v3/repos/:owner/:repo", (req: Request, res: Response) => {
+        const { owner, repo } = req.params;
+
+        res.json({
+            full_name: `${owner}/${repo}`,
+            description: "A test repository",
+            html_url: `http://localhost:${GITHUB_PORT}/${owner}/${repo}`,
+            default_branch: "main",
+            topics: ["cli", "git"],
+            archived: repo === ARCHIVED_REPO,
+        });
+    });
+
+    // Organization and user repositories endpoint
+    app.get(
+        ["/api/v3/orgs/:owner/repos", "/api/v3/users/:owner/repos"],
+        (req: Request, res: Response) => {
+            const { owner } = req.params;
+
+            res.json(
+                REPOS.map((r) => ({
+                    full_name: `${owner}/${r.name}`,
+                    private: false,
+                    visibility: "public",
+                    stargazers_count: r.stars,
+                    default_branch: "main",
+                    pushed_at: r.pushedAt,
+                    archived: r.archived,
+                    fork: r.fork,
+                    html_url: `http://localhost:${GITHUB_PORT}/${owner}/${r.name}`,
+                    clone_url: `http://localhost:${GITHUB_PORT}/${owner}/${r.name}.git`,
+                    ssh_url: `git@localhost:${owner}/${r.name}.git`,
+                })),
+            );
+        },
+    );
+
+    // Edit repository endpoint
+    app.patch(
+        "/api/v3/repos/:owner/:repo",
+        (req: Request, res: Response) => {
+            const authHeader = req.headers.authorization;
+
+            if (!authHeader || !authHeader.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            res.json(req.body);
+        },
+    );
+
+    // Repository languages endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/languages",
+        (_req: Request, res: Response) => {
+            res.json({ TypeScript: 1200, Rust: 48000 });
+        },
+    );
+
+    // Replace repository topics endpoint
+    app.put(
+        "/api/v3/repos/:owner/:repo/topics",
+        (req: Request, res: Response) => {
+            const authHeader = req.headers.authorization;
+
+            if (!authHeader || !authHeader.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            res.json({ names: req.body.names });
+        },
+    );
+
+    // Webhooks endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/hooks",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            res.json(hooks);
+        },
+    );
+
+    // Edit webhook endpoint
+    app.patch(
+        "/api/v3/repos/:owner/:repo/hooks/:id",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const hook = hooks.find((h) => h.id === Number(req.params.id));
+
+            if (!hook) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            res.json({ ...hook, active: req.body.active });
+        },
+    );
+
+    // Ping webhook endpoint
+    app.post(
+        "/api/v3/repos/:owner/:repo/hooks/:id/pings",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const hook = hooks.find((h) => h.id === Number(req.params.id));
+
+            if (!hook) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            res.sendStatus(204);
+        },
+    );
+
+    // Branches endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/branches",
+        (_req: Request, res: Response) => {
+            res.json(
+                BRANCHES.map((b) => ({
+                    name: b.name,
+                    commit: { sha: b.sha },
+                    protected: b.protected,
+                })),
+            );
+        },
+    );
+
+    // Commit endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/commits/:sha",
+        (req: Request, res: Response) => {
+            const branch = BRANCHES.find((b) => b.sha === req.params.sha);
+
+            if (!branch) {
+                res.status(404).json({ message: "No commit found" });
+
+                return;
+            }
+
+            res.json({
+                sha: branch.sha,
+                commit: { committer: { date: branch.date } },
+            });
+        },
+    );
+
+    // Compare endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/compare/:basehead",
+        (req: Request, res: Response) => {
+            const head = req.params.basehead.split("...")[1];
+            const branch = BRANCHES.find((b) => b.sha === head);
+
+            res.json({ ahead_by: branch?.merged ? 0 : 2 });
+        },
+    );
+
+    // Branch endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/branches/:branch",
+        (req: Request, res: Response) => {
+            if (!BRANCHES.some((b) => b.name === req.params.branch)) {
+                res.status(404).json({ message: "Branch not found" });
+
+                return;
+            }
+
+            res.json({ name: req.params.branch });
+        },
+    );
+
+    // Delete branch endpoint. Branch names may contain slashes.
+    app.delete(
+        "/api/v3/repos/:owner/:repo/git/refs/heads/*branch",
+        (req: Request, res: Response) => {
+            const authHeader = req.headers.authorization;
+
+            if (!authHeader || !authHeader.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const branch = (req.params.branch as unknown as string[]).join(
+                "/",
+            );
+
+            if (!BRANCHES.some((b) => b.name === branch)) {
+                res.status(422).json({ message: "Reference does not exist" });
+
+                return;
+            }
+
+            res.sendStatus(204);
+        },
+    );
+
+    // Single pull request endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/pulls/:number",
+        (req: Request, res: Response) => {
+            const pr = prs.find(
+                (pr) => pr.number === Number(req.params.number),
+            );
+
+            res.json({
+                ...pr,
+                mergeable: true,
+                head: { ...pr?.head, sha: "abc123" },
+                user: { login: "alice" },
+                body: "Adds the feature\n\nFixes #2",
+                created_at: "2024-05-01T10:00:00Z",
+            });
+        },
+    );
+
+    // Check runs endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/commits/:sha/check-runs",
+        (_req: Request, res: Response) => {
+            res.json({
+                check_runs: [
+                    { status: "completed", conclusion: "success" },
+                    { status: "completed", conclusion: "failure" },
+                ],
+            });
+        },
+    );
+
+    // Combined commit status endpoint
+    app.get(
+        "/api/v3/repos/:owner/:repo/commits/:sha/status",
+        (_req: Request, res: Response) => {
+            res.json({ state: "pending", total_count: 0 });
+        },
+    );
+
     let prNumber = 1;
     let issueNumber = 1;
 
@@ -289,13 +674,13 @@ export function createGitHubServer(): express.Express {
                 number: prNumber,
                 title: body.title,
                 state: "open",
-                labels: [],
+                labels: (body.labels ?? []).map((name) => ({ name })),
                 user: { login: "test-user" },
                 html_url: `http://localhost:${GITHUB_PORT}/${owner}/${repo}/pull/${prNumber}`,
                 created_at: new Date().toISOString(),
                 updated_at: new Date().toISOString(),
-                head: { ref: body.head },
-                base: { ref: body.base },
+                head: { ref: body.head, sha: "abc123" },
+                base: { ref: body.base, sha: "def456" },
                 draft: body.draft || false,
                 merged_at: null,
             };
@@ -306,6 +691,27 @@ export function createGitHubServer(): express.Express {
         },
     );
 
+    // List labels endpoint
+    app.get("/api/v3/repos/:owner/:repo/labels", (_req, res: Response) => {
+        res.json([{ name: "bug" }, { name: "enhancement" }]);
+    });
+
+    // Create label endpoint
+    app.post(
+        "/api/v3/repos/:owner/:repo/labels",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const body = req.body as { name: string; color: string };
+
+            res.status(201).json({ name: body.name, color: body.color });
+        },
+    );
+
     // Create issue endpoint
     app.post(
         "/api/v3/repos/:owner/:repo/issues",
@@ -318,6 +724,14 @@ export function createGitHubServer(): express.Express {
                 return;
             }
 
+            if (req.params.repo === ARCHIVED_REPO) {
+                res.status(403).json({
+                    message: "Repository was archived so is read-only.",
+                });
+
+                return;
+            }
+
             const { owner, repo } = req.params;
             const body = req.body as CreateIssueRequest;
 
@@ -334,6 +748,7 @@ export function createGitHubServer(): express.Express {
                 labels: [],
                 user: { login: "test-user" },
                 assignee: null,
+                created_at: new Date().toISOString(),
                 html_url: `http://localhost:${GITHUB_PORT}/${owner}/${repo}/issues/${issueNumber}`,
             };
 
@@ -343,5 +758,198 @@ export function createGitHubServer(): express.Express {
         },
     );
 
+    // Authenticated user endpoint
+    app.get("/api/v3/user", (req: Request, res: Response) => {
+        if (!req.headers.authorization?.startsWith("Bearer ")) {
+            res.sendStatus(401);
+
+            return;
+        }
+
+        res.json({ id: 42, login: "test-user" });
+    });
+
+    // Add assignees endpoint
+    app.post(
+        "/api/v3/repos/:owner/:repo/issues/:number/assignees",
+        (req: Request, res: Response) => {
+            const { owner, repo, number } = req.params;
+            const { assignees } = req.body as { assignees: string[] };
+
+            res.status(201).json({
+                number: Number(number),
+                title: "Assigned issue",
+                state: "open",
+                labels: [],
+                user: { login: "test-user" },
+                assignee: { login: assignees[0] },
+                assignees: ["alice", ...assignees].map((login) => ({ login })),
+                created_at: new Date().toISOString(),
+                html_url: `http://localhost:${GITHUB_PORT}/${owner}/${repo}/issues/${number}`,
+            });
+        },
+    );
+
+    // Remove assignees endpoint. Every issue is assigned to alice.
+    app.delete(
+        "/api/v3/repos/:owner/:repo/issues/:number/assignees",
+        (req: Request, res: Response) => {
+            const { number } = req.params;
+            const { assignees } = req.body as { assignees: string[] };
+
+            res.json({
+                number: Number(number),
+                assignees: ["alice"]
+                    .filter((login) => !assignees.includes(login))
+                    .map((login) => ({ login })),
+            });
+        },
+    );
+
+    // Issue labels endpoints. Added labels are appended to the fixture's
+    // labels and replaced labels replace them.
+    app.get(
+        "/api/v3/repos/:owner/:repo/issues/:number/labels",
+        (req: Request, res: Response) => {
+            const issue = issues.find(
+                (i) => i.number === Number(req.params.number),
+            );
+
+            res.json(issue?.labels ?? []);
+        },
+    );
+
+    app.post(
+        "/api/v3/repos/:owner/:repo/issues/:number/labels",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const issue = issues.find(
+                (i) => i.number === Number(req.params.number),
+            );
+
+            if (!issue) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            const names = issue.labels.map((l) => l.name);
+            const { labels } = req.body as { labels: string[] };
+
+            res.json(
+                [...new Set([...names, ...labels])].map((name) => ({ name })),
+            );
+        },
+    );
+
+    app.put(
+        "/api/v3/repos/:owner/:repo/issues/:number/labels",
+        (req: Request, res: Response) => {
+            const { labels } = req.body as { labels: string[] };
+
+            res.json(labels.map((name) => ({ name })));
+        },
+    );
+
+    // Request and remove reviewers endpoints. Every PR has a review request
+    // for alice.
+    app.post(
+        "/api/v3/repos/:owner/:repo/pulls/:number/requested_reviewers",
+        (req: Request, res: Response) => {
+            const { number } = req.params;
+            const { reviewers } = req.body as { reviewers: string[] };
+
+            res.status(201).json({
+                number: Number(number),
+                requested_reviewers: ["alice", ...reviewers].map((login) => ({
+                    login,
+                })),
+            });
+        },
+    );
+
+    app.delete(
+        "/api/v3/repos/:owner/:repo/pulls/:number/requested_reviewers",
+        (req: Request, res: Response) => {
+            const { number } = req.params;
+            const { reviewers } = req.body as { reviewers: string[] };
+
+            res.json({
+                number: Number(number),
+                requested_reviewers: ["alice"]
+                    .filter((login) => !reviewers.includes(login))
+                    .map((login) => ({ login })),
+            });
+        },
+    );
+
+    // Get issue endpoint. Unknown issues only have the fields for pinning.
+    app.get(
+        "/api/v3/repos/:owner/:repo/issues/:number",
+        (req: Request, res: Response) => {
+            const { number } = req.params;
+            const issue = issues.find((i) => i.number === Number(number));
+
+            res.json({
+                ...issue,
+                body: issue ? `The description of #${number}` : undefined,
+                number: Number(number),
+                node_id: `I_${number}`,
+            });
+        },
+    );
+
+    // GraphQL endpoint. Only the pinning mutations and the review status
+    // query are supported.
+    app.post("/api/graphql", (req: Request, res: Response) => {
+        if (!req.headers.authorization?.startsWith("Bearer ")) {
+            res.sendStatus(401);
+
+            return;
+        }
+
+        const { query, variables } = req.body as {
+            query: string;
+            variables: { issueId: string };
+        };
+
+        if (query.includes("latestReviews")) {
+            // The latest reviews of the reviews endpoint
+            const aliases = [...query.matchAll(/(pr\d+): pullRequest/g)];
+            const prs = aliases.map(([, alias]) => [
+                alias,
+                {
+                    latestReviews: {
+                        nodes: [
+                            { state: "APPROVED", author: { login: "bob" } },
+                            { state: "COMMENTED", author: { login: "carol" } },
+                        ],
+                    },
+                    reviewRequests: { totalCount: 0 },
+                },
+            ]);
+
+            res.json({ data: { repository: Object.fromEntries(prs) } });
+
+            return;
+        }
+        const mutation = query.includes("unpinIssue") ? "unpinIssue" : "pinIssue";
+
+        if (variables.issueId === "I_404") {
+            res.json({
+                errors: [{ message: "Could not resolve to a node" }],
+            });
+
+            return;
+        }
+
+        res.json({ data: { [mutation]: { issue: { number: 1 } } } });
+    });
+
     return app;
 }
@@ -1,6 +1,7 @@
 import express, { Request, Response } from "express";
 
-import { GITLAB_PORT } from "../utils.js";
+import { ARCHIVED_REPO, BRANCHES, GITLAB_PORT, REPOS } from "../utils.js";
+import hooks from "./data/gitlab/hooks.json";
 import issues from "./data/gitlab/issue.json";
 import mrs from "./data/gitlab/mr.json";
 
@@ -15,6 +16,7 @@ interface Issue {
     labels: string[];
     author: Author;
     assignees: Author[];
+    created_at: string;
     web_url: string;
 }
 
@@ -29,7 +31,9 @@ interface MergeRequest {
     updated_at: string;
     source_branch: string;
     target_branch: string;
+    sha: string;
     draft: boolean;
+    milestone?: { title: string };
 }
 
 interface CreateMrRequest {
@@ -42,6 +46,26 @@ interface CreateMrRequest {
 interface CreateIssueRequest {
     title: string;
     description?: string;
+    labels?: string;
+}
+
+/** Ids of the users known to the mock server. */
+const userIds: Record<string, number> = {
+    alice: 1,
+    bob: 2,
+    "test-user": 42,
+};
+
+function getUser(id: number): { id: number; username: string } {
+    const username =
+        Object.keys(userIds).find((name) => userIds[name] === id) ?? "unknown";
+
+    return { id, username };
+}
+
+/** Whether the project id, i.e. the decoded project path, is archived. */
+function isArchived(projectId: string): boolean {
+    return projectId.split("/").at(-1) === ARCHIVED_REPO;
 }
 
 export function createGitLabServer(): express.Express {
@@ -70,8 +94,12 @@ export function createGitLabServer(): express.Express {
                 filtered = filtered.filter((issue) => issue.state === s);
             }
 
-            // Filter by labels
-            if (labels && typeof labels === "string") {
+            // Filter by labels. `None` lists the issues without labels.
+            if (labels === "None") {
+                filtered = filtered.filter(
+                    (issue) => issue.labels.length === 0,
+                );
+            } else if (labels && typeof labels === "string") {
                 const requestedLabels = labels.split(",");
 
                 filtered = filtered.filter((issue) =>
@@ -81,6 +109,20 @@ export function createGitLabServer(): express.Express {
                 );
             }
 
+            // Exclude labels
+            const excludedLabels = req.query["not[labels]"];
+
+            if (excludedLabels && typeof excludedLabels === "string") {
+                const requestedLabels = excludedLabels.split(",");
+
+                filtered = filtered.filter(
+                    (issue) =>
+                        !requestedLabels.some((label) =>
+                            issue.labels.includes(label),
+                        ),
+                );
+            }
+
             // Filter by assignee
             if (assignee_username) {
                 filtered = filtered.filter((issue) =>
@@ -124,8 +166,10 @@ export function createGitLabServer(): express.Express {
                 state,
                 labels,
                 author_username,
+                milestone,
                 wip,
                 search,
+                source_branch,
                 page = "1",
                 per_page = "30",
             } = req.query;
@@ -149,6 +193,20 @@ export function createGitLabServer(): express.Express {
                 );
             }
 
+            // Exclude labels
+            const excludedLabels = req.query["not[labels]"];
+
+            if (excludedLabels && typeof excludedLabels === "string") {
+                const requestedLabels = excludedLabels.split(",");
+
+                filtered = filtered.filter(
+                    (mr) =>
+                        !requestedLabels.some((label) =>
+                            mr.labels.includes(label),
+                        ),
+                );
+            }
+
             // Filter by author
             if (author_username) {
                 filtered = filtered.filter(
@@ -156,11 +214,25 @@ export function createGitLabServer(): express.Express {
                 );
             }
 
+            // Filter by milestone
+            if (milestone) {
+                filtered = filtered.filter(
+                    (mr) => mr.milestone?.title === milestone,
+                );
+            }
+
             // Filter by draft/wip
             if (wip === "yes") {
                 filtered = filtered.filter((mr) => mr.draft === true);
             }
 
+            // Filter by source branch
+            if (source_branch) {
+                filtered = filtered.filter(
+                    (mr) => mr.source_branch === source_branch,
+                );
+            }
+
             // Filter by search query
             if (search && typeof search === "string") {
                 const searchTerm = search.toLowerCase();
@@ -180,6 +252,257 @@ export function createGitLabServer(): express.Express {
         },
     );
 
+    // Project endpoint
+    app.get("/api/v4/projects/:projectId", (req: Request, res: Response) => {
+        const { projectId } = req.params;
+
+        res.json({
+            path_with_namespace: projectId,
+            description: "A test repository",
+            web_url: `http://localhost:${GITLAB_PORT}/${projectId}`,
+            default_branch: "main",
+            topics: ["cli", "git"],
+            archived: isArchived(projectId),
+        });
+    });
+
+    // Group and user projects endpoint
+    app.get(
+        ["/api/v4/groups/:owner/projects", "/api/v4/users/:owner/projects"],
+        (req: Request, res: Response) => {
+            const { owner } = req.params;
+            const archived = req.query.archived;
+            const filtered = REPOS.filter(
+                (r) =>
+                    archived === undefined || String(r.archived) === archived,
+            );
+
+            res.json(
+                filtered.map((r) => ({
+                    path_with_namespace: `${owner}/${r.name}`,
+                    visibility: "public",
+                    star_count: r.stars,
+                    default_branch: "main",
+                    last_activity_at: r.pushedAt,
+                    archived: r.archived,
+                    forked_from_project: r.fork ? { id: 1 } : undefined,
+                    web_url: `http://localhost:${GITLAB_PORT}/${owner}/${r.name}`,
+                    http_url_to_repo: `http://localhost:${GITLAB_PORT}/${owner}/${r.name}.git`,
+                    ssh_url_to_repo: `git@localhost:${owner}/${r.name}.git`,
+                })),
+            );
+        },
+    );
+
+    // Edit project endpoint
+    app.put("/api/v4/projects/:projectId", (req: Request, res: Response) => {
+        const authHeader = req.headers.authorization;
+
+        if (!authHeader || !authHeader.startsWith("Bearer ")) {
+            res.sendStatus(401);
+
+            return;
+        }
+
+        res.json(req.body);
+    });
+
+    // Project languages endpoint
+    app.get(
+        "/api/v4/projects/:projectId/languages",
+        (_req: Request, res: Response) => {
+            res.json({ TypeScript: 2.4, Rust: 97.6 });
+        },
+    );
+
+    // Webhooks endpoint
+    app.get(
+        "/api/v4/projects/:projectId/hooks",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            res.json(hooks);
+        },
+    );
+
+    // Single webhook endpoint
+    app.get(
+        "/api/v4/projects/:projectId/hooks/:id",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const hook = hooks.find((h) => h.id === Number(req.params.id));
+
+            if (!hook) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            res.json(hook);
+        },
+    );
+
+    // Test webhook endpoint
+    app.post(
+        "/api/v4/projects/:projectId/hooks/:id/test/:trigger",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(403);
+
+                return;
+            }
+
+            const hook = hooks.find((h) => h.id === Number(req.params.id));
+
+            if (!hook) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            if (!hook[req.params.trigger as keyof typeof hook]) {
+                res.sendStatus(422);
+
+                return;
+            }
+
+            res.status(201).json({ message: "201 Created" });
+        },
+    );
+
+    // Branches endpoint
+    app.get(
+        "/api/v4/projects/:projectId/repository/branches",
+        (_req: Request, res: Response) => {
+            res.json(
+                BRANCHES.map((b) => ({
+                    name: b.name,
+                    commit: { id: b.sha, committed_date: b.date },
+                    protected: b.protected,
+                    merged: b.merged,
+                    default: b.name === "main",
+                })),
+            );
+        },
+    );
+
+    // Branch endpoint
+    app.get(
+        "/api/v4/projects/:projectId/repository/branches/:branch",
+        (req: Request, res: Response) => {
+            if (!BRANCHES.some((b) => b.name === req.params.branch)) {
+                res.status(404).json({ message: "404 Branch Not Found" });
+
+                return;
+            }
+
+            res.json({ name: req.params.branch });
+        },
+    );
+
+    // Delete branch endpoint
+    app.delete(
+        "/api/v4/projects/:projectId/repository/branches/:branch",
+        (req: Request, res: Response) => {
+            const authHeader = req.headers.authorization;
+
+            if (!authHeader || !authHeader.startsWith("Bearer ")) {
+                res.sendStatus(401);
+
+                return;
+            }
+
+            if (!BRANCHES.some((b) => b.name === req.params.branch)) {
+                res.status(404).json({ message: "404 Branch Not Found" });
+
+                return;
+            }
+
+            res.sendStatus(204);
+        },
+    );
+
+    // Single merge request endpoint
+    app.get(
+        "/api/v4/projects/:projectId/merge_requests/:iid",
+        (req: Request, res: Response) => {
+            const mr = mrs.find((mr) => mr.iid === Number(req.params.iid));
+
+            res.json({
+                ...mr,
+                merge_status: "can_be_merged",
+                has_conflicts: false,
+                head_pipeline: { status: "failed" },
+                author: { username: "alice" },
+                description: "Adds the feature",
+                created_at: "2024-05-01T10:00:00Z",
+                reviewers: [{ id: 1, username: "alice" }],
+            });
+        },
+    );
+
+    // Issues closed by a merge request endpoint
+    app.get(
+        "/api/v4/projects/:projectId/merge_requests/:iid/closes_issues",
+        (_req: Request, res: Response) => {
+            res.json([{ iid: 2 }, { iid: 5 }]);
+        },
+    );
+
+    // Merge request notes endpoint
+    app.get(
+        "/api/v4/projects/:projectId/merge_requests/:iid/notes",
+        (_req: Request, res: Response) => {
+            res.json([
+                {
+                    id: 2001,
+                    author: { username: "bob" },
+                    body: "requested changes",
+                    created_at: "2024-05-01T12:00:00Z",
+                    system: true,
+                },
+                {
+                    id: 2002,
+                    author: { username: "carol" },
+                    body: "Looks good now",
+                    created_at: "2024-05-01T15:00:00Z",
+                    system: false,
+                },
+                {
+                    id: 2003,
+                    author: { username: "bob" },
+                    body: "added 1 commit",
+                    created_at: "2024-05-01T16:00:00Z",
+                    system: true,
+                },
+                {
+                    id: 2004,
+                    author: { username: "bob" },
+                    body: "approved this merge request",
+                    created_at: "2024-05-02T09:00:00Z",
+                    system: true,
+                },
+            ]);
+        },
+    );
+
+    // Merge request approvals endpoint
+    app.get(
+        "/api/v4/projects/:projectId/merge_requests/:iid/approvals",
+        (req: Request, res: Response) => {
+            res.json({ approvals_required: 2, approvals_left: 1 });
+        },
+    );
+
     let mrIid = 1;
     let issueIid = 1;
 
@@ -207,13 +530,14 @@ export function createGitLabServer(): express.Express {
                 iid: mrIid,
                 title: body.title,
                 state: "opened",
-                labels: [],
+                labels: body.labels ? body.labels.split(",") : [],
                 author: { username: "test-user" },
                 web_url: `http://localhost:${GITLAB_PORT}/user/repo/-/merge_requests/${mrIid}`,
                 created_at: new Date().toISOString(),
                 updated_at: new Date().toISOString(),
                 source_branch: body.source_branch,
                 target_branch: body.target_branch,
+                sha: "abc123",
                 draft: body.title.startsWith("Draft:"),
             };
 
@@ -223,6 +547,27 @@ export function createGitLabServer(): express.Express {
         },
     );
 
+    // List labels endpoint
+    app.get("/api/v4/projects/:projectId/labels", (_req, res: Response) => {
+        res.json([{ name: "bug" }, { name: "enhancement" }]);
+    });
+
+    // Create label endpoint
+    app.post(
+        "/api/v4/projects/:projectId/labels",
+        (req: Request, res: Response) => {
+            if (!req.headers.authorization?.startsWith("Bearer ")) {
+                res.sendStatus(401);
+
+                return;
+            }
+
+            const body = req.body as { name: string; color: string };
+
+            res.status(201).json({ name: body.name, color: body.color });
+        },
+    );
+
     // Create issue endpoint
     app.post(
         "/api/v4/projects/:projectId/issues",
@@ -235,6 +580,14 @@ export function createGitLabServer(): express.Express {
                 return;
             }
 
+            if (isArchived(req.params.projectId)) {
+                res.status(403).json({
+                    message: "403 Forbidden - Project is archived",
+                });
+
+                return;
+            }
+
             const body = req.body as CreateIssueRequest;
 
             if (!body.title) {
@@ -250,6 +603,7 @@ export function createGitLabServer(): express.Express {
                 labels: [],
                 author: { username: "test-user" },
                 assignees: [],
+                created_at: new Date().toISOString(),
                 web_url: `http://localhost:${GITLAB_PORT}/user/repo/-/issues/${issueIid}`,
             };
 
@@ -259,5 +613,135 @@ export function createGitLabServer(): express.Express {
         },
     );
 
+    // Authenticated user endpoint
+    app.get("/api/v4/user", (req: Request, res: Response) => {
+        if (!req.headers.authorization?.startsWith("Bearer ")) {
+            res.sendStatus(401);
+
+            return;
+        }
+
+        res.json({ id: 42, username: "test-user" });
+    });
+
+    // Users endpoint to look up the id of a username
+    app.get("/api/v4/users", (req: Request, res: Response) => {
+        const username = req.query.username as string;
+        const id = userIds[username];
+
+        res.json(id ? [{ id, username }] : []);
+    });
+
+    // Single issue endpoint. Every issue is assigned to alice.
+    app.get(
+        "/api/v4/projects/:projectId/issues/:iid",
+        (req: Request, res: Response) => {
+            const { iid } = req.params;
+            const issue = issues.find((i) => i.iid === Number(iid));
+
+            res.json({
+                ...issue,
+                description: issue ? `The description of #${iid}` : undefined,
+                iid: Number(iid),
+                assignees: [{ id: 1, username: "alice" }],
+            });
+        },
+    );
+
+    // Edit issue endpoint
+    app.put(
+        "/api/v4/projects/:projectId/issues/:iid",
+        (req: Request, res: Response) => {
+            const { iid } = req.params;
+            const body = req.body as {
+                assignee_ids?: number[];
+                add_labels?: string;
+                remove_labels?: string;
+                labels?: string;
+            };
+            const { assignee_ids, add_labels, remove_labels } = body;
+            const issue = issues.find((i) => i.iid === Number(iid));
+            const editsLabels =
+                add_labels !== undefined ||
+                remove_labels !== undefined ||
+                body.labels !== undefined;
+
+            if (!issue && editsLabels) {
+                res.sendStatus(404);
+
+                return;
+            }
+
+            const removed = remove_labels?.split(",") ?? [];
+            const currentLabels =
+                body.labels !== undefined
+                    ? body.labels.split(",").filter((label) => label !== "")
+                    : (issue?.labels ?? []);
+            const labels = [
+                ...new Set([
+                    ...currentLabels,
+                    ...(add_labels?.split(",") ?? []),
+                ]),
+            ].filter((label) => !removed.includes(label));
+
+            res.json({
+                iid: Number(iid),
+                title: "Edited issue",
+                state: "opened",
+                labels,
+                author: { username: "test-user" },
+                assignees: (assignee_ids ?? []).map(getUser),
+                created_at: new Date().toISOString(),
+                web_url: `http://localhost:${GITLAB_PORT}/user/repo/-/issues/${iid}`,
+            });
+        },
+    );
+
+    // Edit merge request endpoint
+    app.put(
+        "/api/v4/projects/:projectId/merge_requests/:iid",
+        (req: Request, res: Response) => {
+            const { iid } = req.params;
+            const { reviewer_ids } = req.body as { reviewer_ids?: number[] };
+
+            res.json({
+                iid: Number(iid),
+                reviewers: (reviewer_ids ?? []).map(getUser),
+            });
+        },
+    );
+
+    // Merge merge request endpoint. Pipelines never succeed right away, so
+    // merging once the pipeline succeeds keeps the MR open.
+    app.put(
+        "/api/v4/projects/:projectId/merge_requests/:iid/merge",
+        (req: Request, res: Response) => {
+            const { iid } = req.params;
+            const { merge_when_pipeline_succeeds } = req.body as {
+                merge_when_pipeline_succeeds?: boolean;
+            };
+
+            res.json({
+                iid: Number(iid),
+                state: merge_when_pipeline_succeeds ? "opened" : "merged",
+                merge_when_pipeline_succeeds: !!merge_when_pipeline_succeeds,
+            });
+        },
+    );
+
+    // Cancel merge when pipeline succeeds endpoint
+    app.post(
+        "/api/v4/projects/:projectId/merge_requests/:iid/cancel_merge_when_pipeline_succeeds",
+        (req: Request, res: Response) => {
+            const { iid } = req.params;
+
+            res.json({
+                iid: Number(iid),
+                state: "opened",
+                merge_when_pipeline_succeeds: false,
+            });
+        },
+    );
+
     return app;
 }
@@ -0,0 +1,105 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+])("Branch Delete-Merged Command ($forge)", ({ forge, token }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["branch", "delete-merged", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should print which branches would be deleted with --dry-run", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "delete-merged",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--dry-run",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe(
+            [
+                "keep\tdevelop\tprotected",
+                "keep\tfeature/login\topen PR #1",
+                "keep\tmain\tdefault branch",
+                "delete\tfix/auth-bug\tmerged into main",
+            ].join("\n"),
+        );
+        expect(result.stderr).toContain("Would delete 1 branches and keep 3");
+    });
+
+    it("Should refuse to delete without --yes outside a terminal", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "delete-merged",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Pass --yes");
+    });
+
+    it("Should delete the merged branches with --yes", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "delete-merged",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--stale",
+                "2024-01-01",
+                "--yes",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("deleted\tfix/auth-bug\tmerged into main");
+        expect(result.stderr).toContain("Deleted 1 branches and kept 3");
+    });
+});
@@ -0,0 +1,138 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitea" } as const,
+    { forge: "gitlab" } as const,
+])("Branch List Command ($forge)", ({ forge }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["branch", "list", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should list the branches with their protection and open PR", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "ls",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "name,protected,pr",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe(
+            [
+                "main\ttrue\t7",
+                "develop\ttrue\t9",
+                "feature/login\tfalse\t1",
+                "fix/auth-bug\tfalse\t",
+            ].join("\n"),
+        );
+    });
+
+    it("Should only list merged branches with --merged", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "ls",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--merged",
+                "--fields",
+                "name",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("main\nfix/auth-bug");
+    });
+
+    it("Should only list unmerged branches with --no-merged", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "ls",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--no-merged",
+                "--fields",
+                "name",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("develop\nfeature/login");
+    });
+
+    it("Should mark branches older than --stale as stale", () => {
+        const result = runGitForge({
+            args: [
+                "branch",
+                "ls",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--stale",
+                "2024-01-01",
+                "--fields",
+                "name,last_commit_at,stale",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const branches = JSON.parse(result.stdout);
+
+        expect(branches[0]).toEqual({
+            name: "main",
+            last_commit_at: "2024-05-01T12:00:00Z",
+            stale: false,
+        });
+        expect(
+            branches
+                .filter((b: { stale: boolean }) => b.stale)
+                .map((b: { name: string }) => b.name),
+        ).toEqual(["fix/auth-bug"]);
+    });
+});
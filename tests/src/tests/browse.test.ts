@@ -1,7 +1,12 @@
 import { execSync } from "node:child_process";
 import { afterEach, beforeEach, describe, expect, it } from "vitest";
 
-import { cleanupGitRepo, runGitForge, setupGitRepo } from "../utils.js";
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
 
 describe("Browse Command", () => {
     let tempDir: string;
@@ -117,6 +122,70 @@ describe("Browse Command", () => {
             );
         });
 
+        it("Should generate issue comment URL with --comment flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "github",
+                    "--api-url",
+                    getApiUrl("github"),
+                    "-i",
+                    "123",
+                    "--comment",
+                    "1",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3001/user/repo/issues/123#issuecomment-1001",
+            );
+        });
+
+        it("Should fail for a comment index out of range", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "github",
+                    "--api-url",
+                    getApiUrl("github"),
+                    "-i",
+                    "123",
+                    "--comment",
+                    "2",
+                ],
+                cwd: tempDir,
+                throwsError: true,
+            });
+
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain("Issue #123 has only 1 comment");
+        });
+
+        it("Should fail copying and printing the URL at once", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--copy",
+                    "--no-browser",
+                    "--api",
+                    "github",
+                    "-p",
+                    "42",
+                ],
+                cwd: tempDir,
+                throwsError: true,
+            });
+
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain("cannot be used with");
+        });
+
         it("Should generate commit URL with --commit flag", () => {
             const result = runGitForge({
                 args: [
@@ -159,6 +228,42 @@ describe("Browse Command", () => {
             );
         });
 
+        it("Should generate labels page URL with --labels flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "github",
+                    "--labels",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3001/user/repo/labels",
+            );
+        });
+
+        it("Should generate milestones page URL with --milestones flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "github",
+                    "--milestones",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3001/user/repo/milestones",
+            );
+        });
+
         it("Should generate releases page URL with --releases flag", () => {
             const result = runGitForge({
                 args: [
@@ -355,6 +460,42 @@ describe("Browse Command", () => {
             );
         });
 
+        it("Should generate labels page URL with --labels flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "gitlab",
+                    "--labels",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3002/user/repo/-/labels",
+            );
+        });
+
+        it("Should generate milestones page URL with --milestones flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "gitlab",
+                    "--milestones",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3002/user/repo/-/milestones",
+            );
+        });
+
         it("Should generate releases page URL with --releases flag", () => {
             const result = runGitForge({
                 args: [
@@ -536,6 +677,42 @@ describe("Browse Command", () => {
             );
         });
 
+        it("Should generate labels page URL with --labels flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "gitea",
+                    "--labels",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3003/user/repo/labels",
+            );
+        });
+
+        it("Should generate milestones page URL with --milestones flag", () => {
+            const result = runGitForge({
+                args: [
+                    "browse",
+                    "--no-browser",
+                    "--api",
+                    "gitea",
+                    "--milestones",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe(
+                "https://localhost:3003/user/repo/milestones",
+            );
+        });
+
         it("Should generate releases page URL with --releases flag", () => {
             const result = runGitForge({
                 args: [
@@ -0,0 +1,147 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitea" } as const,
+    { forge: "gitlab" } as const,
+])("Changelog Command ($forge)", ({ forge }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should group the merged PRs of a milestone by label", () => {
+        const result = runGitForge({
+            args: [
+                "changelog",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--milestone",
+                "v1.0",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const sections = result.stdout.split("\n\n### ");
+
+        expect(sections).toHaveLength(2);
+        expect(sections[0]).toMatch(
+            /^### Bug Fixes\n\n- Fix authentication bug \(\[#2\]\(.+\)\) by @bob$/,
+        );
+        expect(sections[1]).toMatch(
+            /^Other Changes\n\n- Security patch for auth \(\[#10\]/,
+        );
+        // Closed PRs that weren't merged aren't part of the changelog
+        expect(result.stdout).not.toContain("Fix memory leak");
+    });
+
+    it("Should output the sections as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "changelog",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--milestone",
+                "v1.0",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const sections = JSON.parse(result.stdout);
+
+        expect(sections.map((s: { title: string }) => s.title)).toEqual([
+            "Bug Fixes",
+            "Other Changes",
+        ]);
+        expect(sections[0].entries[0]).toMatchObject({
+            kind: "pr",
+            id: 2,
+            title: "Fix authentication bug",
+            labels: ["bug"],
+        });
+    });
+
+    it("Should use the configured sections", () => {
+        runGitForge({
+            args: [
+                "config",
+                "set",
+                "--scope",
+                "local",
+                "changelog/sections",
+                "security:Security,critical:Security,bug:Fixes",
+            ],
+            cwd: tempDir,
+        });
+
+        const result = runGitForge({
+            args: [
+                "changelog",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--milestone",
+                "v1.0",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const sections = JSON.parse(result.stdout);
+
+        expect(sections.map((s: { title: string }) => s.title)).toEqual([
+            "Security",
+            "Fixes",
+        ]);
+    });
+
+    it("Should fail for an unknown tag", () => {
+        const result = runGitForge({
+            args: [
+                "changelog",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--since-tag",
+                "v0.0.0-does-not-exist",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            "Failed to get the date of tag 'v0.0.0-does-not-exist'",
+        );
+    });
+});
@@ -0,0 +1,84 @@
+import { execSync } from "node:child_process";
+
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitlab" } as const,
+    { forge: "gitea" } as const,
+])("Context Command ($forge)", ({ forge }) => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should print the context as JSON without network requests", () => {
+        execSync("git checkout -b pr-7", { cwd: localRepoDir });
+        execSync("git config branch.pr-7.git-forge-pr 7", {
+            cwd: localRepoDir,
+        });
+
+        const result = runGitForge({
+            args: ["context", "--format", "json", "--api", forge],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout)).toEqual({
+            remote: "origin",
+            url: expect.stringContaining("/user/repo.git"),
+            host: "localhost",
+            path: "user/repo",
+            forge,
+            default_branch: null,
+            branch: "pr-7",
+            pr: 7,
+        });
+    });
+
+    it("Should print the context as text", () => {
+        const result = runGitForge({
+            args: ["context", "--api", forge],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toContain("path\tuser/repo\n");
+        expect(result.stdout).toContain(`forge\t${forge}\n`);
+        expect(result.stdout).toContain("pr\t\n");
+    });
+
+    it("Should fetch the default branch with --fresh", () => {
+        const result = runGitForge({
+            args: [
+                "context",
+                "--fresh",
+                "--format",
+                "json",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout).default_branch).toBe("main");
+    });
+});
@@ -113,6 +113,63 @@ describe.each([
             expect(issues[0]).toHaveProperty("title");
             expect(issues[0]).not.toHaveProperty("url");
         });
+
+        it("Should output JSON keys in the order of the fields", () => {
+            const result = runGitForge({
+                args: [
+                    "issue",
+                    "list",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--format",
+                    "json",
+                    "--fields",
+                    "url,id,title",
+                    "--per-page",
+                    "1",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+
+            const issues = JSON.parse(result.stdout);
+
+            expect(Object.keys(issues[0])).toEqual(["url", "id", "title"]);
+        });
+
+        it("Should output issues in the compact format", () => {
+            const result = runGitForge({
+                args: [
+                    "issue",
+                    "list",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--format",
+                    "compact",
+                    "--max-title-width",
+                    "10",
+                    "--per-page",
+                    "3",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+
+            const lines = result.stdout.trim().split("\n");
+
+            expect(lines).toHaveLength(3);
+
+            for (const line of lines) {
+                expect(line).toMatch(/^#\d+ /);
+                expect(line).not.toContain("\t");
+            }
+        });
     });
 
     describe("PR List Formats", () => {
@@ -202,5 +259,74 @@ describe.each([
             expect(prs[0]).toHaveProperty("draft");
             expect(prs[0]).not.toHaveProperty("url");
         });
+
+        it("Should nest the branches in the JSON format", () => {
+            const result = runGitForge({
+                args: [
+                    "pr",
+                    "list",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--format",
+                    "json",
+                    "--fields",
+                    "id,source,target",
+                    "--per-page",
+                    "1",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+
+            const prs = JSON.parse(result.stdout);
+
+            // Gitea lists pull requests via the issues endpoint
+            if (forge === "gitea") {
+                expect(prs[0]).not.toHaveProperty("head");
+                return;
+            }
+
+            expect(prs[0].head.ref).toBe("feature/login");
+            expect(prs[0].head.sha).toMatch(/^[0-9a-f]{40}$/);
+            expect(prs[0].base.ref).toBe("main");
+            expect(prs[0]).not.toHaveProperty("title");
+        });
+
+        it("Should output the branch SHAs in TSV format", () => {
+            const result = runGitForge({
+                args: [
+                    "pr",
+                    "list",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--format",
+                    "tsv",
+                    "--fields",
+                    "id,source,source_sha",
+                    "--per-page",
+                    "1",
+                ],
+                cwd: tempDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expectTsvFormat(result.stdout);
+
+            const rows = parseTSV(result.stdout, ["id", "source", "sha"]);
+
+            // Gitea lists pull requests via the issues endpoint
+            if (forge === "gitea") {
+                expect(rows[0].source).toBe("");
+                return;
+            }
+
+            expect(rows[0].source).toBe("feature/login");
+            expect(rows[0].sha).toMatch(/^[0-9a-f]{40}$/);
+        });
     });
 });
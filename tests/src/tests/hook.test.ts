@@ -0,0 +1,154 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+])("Hook Command ($forge)", ({ forge, token }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["hook", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should list the webhooks with redacted URLs", () => {
+        const result = runGitForge({
+            args: [
+                "hook",
+                "ls",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).not.toContain("s3cr3t");
+        expect(result.stdout).not.toContain("hunter2");
+
+        const hooks = JSON.parse(result.stdout);
+
+        expect(hooks).toHaveLength(2);
+        expect(hooks[0]).toMatchObject({
+            id: 101,
+            url: "https://ci.example.com/hooks/***?token=***&project=app",
+            active: true,
+        });
+        expect(hooks[0].events).toContain("push");
+        expect(hooks[1]).toMatchObject({
+            id: 102,
+            url: "https://***@chat.example.com/notify",
+            active: false,
+        });
+    });
+
+    it("Should fail to list the webhooks without a token", () => {
+        const result = runGitForge({
+            args: [
+                "hook",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+    });
+
+    it("Should ping a webhook", () => {
+        const result = runGitForge({
+            args: [
+                "hook",
+                "ping",
+                "101",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toContain("Sent a test delivery to webhook 101");
+    });
+
+    it("Should fail to ping an unknown webhook", () => {
+        const result = runGitForge({
+            args: [
+                "hook",
+                "ping",
+                "999",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("webhook 999");
+    });
+
+    it("Should disable a webhook", () => {
+        const result = runGitForge({
+            args: [
+                "hook",
+                "disable",
+                "101",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+            throwsError: forge === "gitlab",
+        });
+
+        if (forge === "gitlab") {
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain("only supported for");
+        } else {
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toContain("Webhook 101 is disabled");
+        }
+    });
+});
@@ -0,0 +1,213 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+])("Issue Assign Command ($forge)", ({ forge, token }) => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should assign the issue to the authenticated user", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "assign",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Assignees of issue #1: alice, test-user");
+    });
+
+    it("Should assign the issue to another user as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "assign",
+                "1",
+                "--to",
+                "bob",
+                "--format",
+                "json",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout)).toEqual({
+            id: 1,
+            assignees: ["alice", "bob"],
+        });
+    });
+
+    it("Should assign several users", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "assign",
+                "1",
+                "bob,test-user",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe(
+            "Assignees of issue #1: alice, bob, test-user",
+        );
+    });
+
+    it("Should assign the authenticated user with --me", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "assign",
+                "1",
+                "bob",
+                "--me",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe(
+            "Assignees of issue #1: alice, test-user, bob",
+        );
+    });
+
+    it("Should unassign the given users", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "unassign",
+                "1",
+                "alice",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Issue #1 has no assignees");
+    });
+
+    it("Should unassign a user", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "unassign",
+                "1",
+                "--from",
+                "alice",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Issue #1 has no assignees");
+    });
+
+    it("Should fail without authentication", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "assign",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(token);
+    });
+});
+
+describe("Issue Assign Command (gitlab only)", () => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge: "gitlab" });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should fail for unknown users", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "assign",
+                "1",
+                "ghost",
+                "--api",
+                "gitlab",
+                "--api-url",
+                getApiUrl("gitlab"),
+            ],
+            cwd: localRepoDir,
+            env: { GIT_FORGE_GITLAB_TOKEN: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("GitLab user 'ghost' not found");
+    });
+});
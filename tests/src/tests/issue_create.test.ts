@@ -1,8 +1,12 @@
+import { execSync } from "node:child_process";
+
 import { afterEach, beforeEach, describe, expect, it } from "vitest";
 
 import {
+    ARCHIVED_REPO,
     cleanupGitRepo,
     getApiUrl,
+    getRemoteUrl,
     runGitForge,
     setupGitRepo,
 } from "../utils.js";
@@ -87,6 +91,212 @@ describe.each([
         expect(result.exitCode).toBe(0);
     });
 
+    it("Should create issue with labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Issue with Labels",
+                "--labels",
+                "bug,enhancement",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+    });
+
+    it("Should fail when creating issue with an unknown label on Gitea", () => {
+        if (forge !== "gitea") {
+            return;
+        }
+
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Issue with Labels",
+                "--labels",
+                "does-not-exist",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Label 'does-not-exist' doesn't exist");
+    });
+
+    it("Should fail on labels that don't exist by default", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Issue with Labels",
+                "--labels",
+                "bug,does-not-exist",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            "Label 'does-not-exist' doesn't exist in user/repo. Pass " +
+                "--create-missing-labels to create it or " +
+                "--ignore-missing-labels to leave it out",
+        );
+    });
+
+    it("Should create missing labels with --create-missing-labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Issue with new Labels",
+                "--labels",
+                "Bug,needs-triage",
+                "--create-missing-labels",
+                "--format",
+                "json",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).toContain("Created label 'needs-triage'");
+        expect(JSON.parse(result.stdout).labels).toEqual([
+            "bug",
+            "needs-triage",
+        ]);
+    });
+
+    it("Should leave out missing labels with --ignore-missing-labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Issue without unknown Labels",
+                "--labels",
+                "bug,does-not-exist",
+                "--ignore-missing-labels",
+                "--format",
+                "json",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout).labels).toEqual(["bug"]);
+    });
+
+    it("Should print the created issue as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "JSON Issue",
+                "--format",
+                "json",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const issue = JSON.parse(result.stdout);
+
+        expect(issue).toMatchObject({ title: "JSON Issue", state: "open" });
+        expect(issue.id).toEqual(expect.any(Number));
+        expect(issue.url).toContain(`/issues/${issue.id}`);
+    });
+
+    it("Should assign the created issue with --post-action assign", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Assigned Issue",
+                "--post-action",
+                "assign",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).toMatch(/Assigned issue #\d+ to you/);
+    });
+
+    it("Should do nothing after creation with --post-action none", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "create",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Quiet Issue",
+                "--post-action",
+                "none",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).not.toContain("Opening issue in browser");
+    });
+
     it("Should fail when creating issue without authentication", () => {
         const result = runGitForge({
             args: [
@@ -127,4 +337,89 @@ describe.each([
         expect(result.exitCode).not.toBe(0);
         expect(result.stderr).toBeTruthy();
     });
+
+    it("Should name the missing --title with --prompt never", () => {
+        const result = runGitForge({
+            args: [
+                "--prompt",
+                "never",
+                "issue",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            'Can\'t ask "Enter issue title" with --prompt never. Pass --title instead',
+        );
+    });
+
+    describe("On an archived repository", () => {
+        beforeEach(() => {
+            const remoteUrl = getRemoteUrl(forge).replace(
+                "/repo.git",
+                `/${ARCHIVED_REPO}.git`,
+            );
+
+            execSync(`git remote set-url origin ${remoteUrl}`, {
+                cwd: localRepoDir,
+            });
+        });
+
+        it("Should stop before asking for the issue", () => {
+            const result = runGitForge({
+                args: [
+                    "issue",
+                    "create",
+                    "--no-browser",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--title",
+                    "Test Issue",
+                ],
+                cwd: localRepoDir,
+                env: { [token]: "test-token" },
+                throwsError: true,
+            });
+
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain(
+                "The repository is archived, so issues can't be created",
+            );
+            expect(result.stderr).not.toContain("Creating issue");
+        });
+
+        it("Should explain the refusal with --no-precheck", () => {
+            const result = runGitForge({
+                args: [
+                    "issue",
+                    "create",
+                    "--no-browser",
+                    "--no-precheck",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--title",
+                    "Test Issue",
+                ],
+                cwd: localRepoDir,
+                env: { [token]: "test-token" },
+                throwsError: true,
+            });
+
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain("Hint: The repository is archived");
+        });
+    });
 });
@@ -0,0 +1,120 @@
+import { writeFileSync } from "node:fs";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+])("Issue Export/Import Commands ($forge)", ({ forge, token }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should export issues of all states as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "export",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--all-pages",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const issues = JSON.parse(result.stdout);
+
+        expect(issues).toHaveLength(10);
+        expect(issues[0]).toHaveProperty("id");
+        expect(issues[0]).toHaveProperty("title");
+        expect(issues[0]).toHaveProperty("body");
+        expect(issues[0]).toHaveProperty("created_at");
+        expect(issues[0]).not.toHaveProperty("comments");
+    });
+
+    it("Should summarize the import with --dry-run", () => {
+        const exportResult = runGitForge({
+            args: [
+                "issue",
+                "export",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--state",
+                "closed",
+            ],
+            cwd: tempDir,
+        });
+        const exportFile = path.join(tempDir, "issues.json");
+
+        writeFileSync(exportFile, exportResult.stdout);
+
+        const result = runGitForge({
+            args: [
+                "issue",
+                "import",
+                exportFile,
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--dry-run",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        for (const line of result.stdout.split("\n")) {
+            expect(line).toMatch(/^create\t\d+\tclosed\t/);
+        }
+
+        expect(result.stderr).toContain("skip 0 already imported issues");
+    });
+
+    it("Should fail to import a file that isn't an export", () => {
+        const exportFile = path.join(tempDir, "issues.json");
+
+        writeFileSync(exportFile, "{}");
+
+        const result = runGitForge({
+            args: [
+                "issue",
+                "import",
+                exportFile,
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Failed to parse");
+    });
+});
@@ -0,0 +1,222 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+])("Issue Label Command ($forge)", ({ forge, token }) => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should add a label to the given issues", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "add",
+                "enhancement",
+                "--numbers",
+                "2,3",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe(
+            "2\tbug,critical,enhancement\n3\tdocumentation,enhancement",
+        );
+        expect(result.stderr).toContain("Labeled 2/2 issues");
+    });
+
+    it("Should remove a label from the issues read from stdin", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "remove",
+                "bug",
+                "--stdin",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            input: "2\n3\n",
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("2\tcritical\n3\tdocumentation");
+    });
+
+    it("Should print the issues matching --where with --dry-run", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "add",
+                "bug",
+                "--where",
+                "--labels enhancement,high-priority",
+                "--dry-run",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toMatch(/^#1\t/);
+        expect(result.stderr).toContain("Would change the labels of 1 issues");
+    });
+
+    it("Should label the issues matching --where with --yes", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "add",
+                "bug",
+                "--where",
+                "--labels enhancement,high-priority",
+                "--yes",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("1\tenhancement,high-priority,bug");
+    });
+
+    it("Should report the issues that failed", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "add",
+                "bug",
+                "--numbers",
+                "2,404",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stdout).toBe("2\tbug,critical");
+        expect(result.stderr).toContain(
+            "Failed to change the labels of issue #404",
+        );
+        expect(result.stderr).toContain(
+            "Failed to change the labels of 1 of 2 issues: #404",
+        );
+    });
+
+    it("Should add labels to a single issue", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "add",
+                "3",
+                "bug",
+                "enhancement",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("3\tdocumentation,bug,enhancement");
+    });
+
+    it("Should replace the labels of an issue as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "set",
+                "2",
+                "Enhancement",
+                "--format",
+                "json",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout)).toEqual([
+            { id: 2, labels: ["enhancement"] },
+        ]);
+    });
+
+    it("Should list the available labels for unknown labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "label",
+                "add",
+                "2",
+                "ux",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Label 'ux' doesn't exist in");
+        expect(result.stderr).toContain(
+            "Available labels: 'bug', 'enhancement'",
+        );
+    });
+});
@@ -137,6 +137,52 @@ describe.each([
         expect(rows[0]).toHaveProperty("title");
     });
 
+    it("Should list issues with all fields (--fields all)", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--format",
+                "json",
+                "--fields",
+                "all",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const issues = JSON.parse(result.stdout);
+
+        expect(Object.keys(issues[0]).sort()).toEqual(
+            ["author", "id", "labels", "state", "title", "url"].sort(),
+        );
+    });
+
+    it("Should fail with the valid fields when given an unknown field", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "tite",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("possible values");
+    });
+
     it("Should list issues filtered by labels (enhancement+high-priority)", () => {
         const result = runGitForge({
             args: [
@@ -157,6 +203,91 @@ describe.each([
         expect(parseTSV(result.stdout)).toHaveLength(1);
     });
 
+    it("Should list issues without the excluded labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--labels",
+                "enhancement,-high-priority",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+        expect(parseTSV(result.stdout)).toHaveLength(3);
+    });
+
+    it("Should list issues with only excluded labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--labels",
+                "-enhancement",
+                "--fields",
+                "id,labels",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const issues = JSON.parse(result.stdout);
+
+        expect(issues.length).toBeGreaterThan(0);
+        expect(
+            issues.some((issue: { labels: string[] }) =>
+                issue.labels.includes("enhancement"),
+            ),
+        ).toBe(false);
+    });
+
+    it("Should list only issues without labels", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--no-labels",
+                "--state",
+                "all",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        // Every issue of the mock data has labels
+        expect(JSON.parse(result.stdout)).toEqual([]);
+    });
+
+    it("Should conflict --no-labels with --labels", () => {
+        const result = runGitForge({
+            args: ["issue", "list", "--no-labels", "--labels", "bug"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("cannot be used with");
+    });
+
     it("Should list issues from page 2", () => {
         const result = runGitForge({
             args: [
@@ -318,6 +449,38 @@ describe.each([
         expect(parseTSV(result.stdout)).toHaveLength(3);
     });
 
+    it("Should list issues closed as not planned (--closed-as)", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--closed-as",
+                "not-planned",
+                "--fields",
+                "id,state,state_reason",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+            throwsError: forge !== "github",
+        });
+
+        if (forge !== "github") {
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain("only supported for GitHub");
+            return;
+        }
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout)).toEqual([
+            { id: 6, state: "closed", state_reason: "not_planned" },
+        ]);
+    });
+
     it("Should list issues with multiple options combined (auth, fields, labels)", () => {
         const result = runGitForge({
             args: [
@@ -399,4 +562,323 @@ describe.each([
         expect(result.exitCode).toBe(0);
         expect(parseTSV(result.stdout)).toHaveLength(0);
     });
+
+    it("Should list the pinned issues with --pinned", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--pinned",
+                "--author",
+                "bob",
+                "--fields",
+                "id",
+            ],
+            cwd: tempDir,
+            throwsError: forge !== "gitea",
+        });
+
+        if (forge === "gitea") {
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toBe("2");
+        } else {
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain(
+                "--pinned is only supported for Gitea/Forgejo",
+            );
+        }
+    });
+
+    it("Should list the PRs along with the issues with --include-prs", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--include-prs",
+                "--author",
+                "alice",
+                "--fields",
+                "id,type",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout.split("\n").sort()).toEqual([
+            "1\tissue",
+            "1\tpr",
+            "5\tissue",
+            "7\tpr",
+            "8\tissue",
+        ]);
+    });
+
+    it("Should skip remotes of unknown forges with --all-remotes", () => {
+        // The remote's host is localhost, so the forge can't be guessed
+        const result = runGitForge({
+            args: ["issue", "list", "--all-remotes"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            "None of the git remotes belongs to a known forge",
+        );
+    });
+
+    it("Should reject --all-remotes combined with --remote", () => {
+        const result = runGitForge({
+            args: ["issue", "list", "--all-remotes", "--remote", "origin"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("cannot be used with");
+    });
+
+    it("Should print the issues and fail with --fail-if-any", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fail-if-any",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).toBe(1);
+        expect(parseTSV(result.stdout).length).toBeGreaterThan(0);
+        expect(result.stderr).toContain("--fail-if-any");
+    });
+
+    it("Should fail with --fail-if-none if no issues match", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--query",
+                "nonexistentterm",
+                "--fail-if-none",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).toBe(1);
+        expect(result.stdout).toBe("");
+        expect(result.stderr).toContain("--fail-if-none");
+    });
+
+    it("Should succeed with --fail-if-none if issues match", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fail-if-none",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(parseTSV(result.stdout).length).toBeGreaterThan(0);
+    });
+
+    it("Should print only the ids of all pages with --ids-only", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--ids-only",
+                "--all-pages",
+                "--state",
+                "all",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const ids = result.stdout.split("\n");
+
+        expect(ids).toHaveLength(10);
+
+        for (const id of ids) {
+            expect(id).toMatch(/^\d+$/);
+        }
+    });
+
+    it("Should reject --ids-only combined with --fields", () => {
+        const result = runGitForge({
+            args: ["issue", "list", "--ids-only", "--fields", "title"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("cannot be used with");
+    });
+
+    it("Should reject a detached value of --since-last-run", () => {
+        const result = runGitForge({
+            args: ["issue", "list", "--since-last-run", "updated"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+    });
+
+    it("Should reject --dry-run without --since-last-run", () => {
+        const result = runGitForge({
+            args: ["issue", "list", "--dry-run"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("--since-last-run");
+    });
+
+    it("Should refuse to send the token to a host other than the remote's", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge).replace("localhost", "127.0.0.1"),
+                "--auth",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            `Refusing to send the token of ${token} to 127.0.0.1`,
+        );
+        expect(result.stderr).toContain("--force-auth-host");
+    });
+
+    it("Should send the token to hosts of auth/allowed-hosts", () => {
+        const apiUrl = new URL(getApiUrl(forge));
+
+        runGitForge({
+            args: [
+                "config",
+                "set",
+                "--scope",
+                "local",
+                "auth/allowed-hosts",
+                `127.0.0.1:${apiUrl.port}`,
+            ],
+            cwd: tempDir,
+        });
+
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge).replace("localhost", "127.0.0.1"),
+                "--auth",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+    });
+
+    it("Should send the token to any host with --force-auth-host", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge).replace("localhost", "127.0.0.1"),
+                "--auth",
+                "--force-auth-host",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+    });
+
+    it("Should use the forge and API URL of the environment variables", () => {
+        const result = runGitForge({
+            args: ["issue", "list"],
+            cwd: tempDir,
+            env: {
+                GIT_FORGE_API: forge,
+                GIT_FORGE_API_URL: getApiUrl(forge),
+            },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+    });
+
+    it("Should prefer --api and --api-url over the environment variables", () => {
+        const result = runGitForge({
+            args: ["issue", "list", "--api", forge, "--api-url", getApiUrl(forge)],
+            cwd: tempDir,
+            env: {
+                GIT_FORGE_API: forge === "github" ? "gitlab" : "github",
+                GIT_FORGE_API_URL: "https://localhost:1/api",
+            },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+    });
+
+    it("Should fail with an invalid GIT_FORGE_API", () => {
+        const result = runGitForge({
+            args: ["issue", "list"],
+            cwd: tempDir,
+            env: { GIT_FORGE_API: "gitbucket" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Invalid value for GIT_FORGE_API");
+    });
 });
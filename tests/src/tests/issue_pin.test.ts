@@ -0,0 +1,153 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+])("Issue Pin Command ($forge)", ({ forge, token }) => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should pin an issue", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "pin",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Issue #1 is pinned");
+    });
+
+    it("Should unpin an issue", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "unpin",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Issue #1 is no longer pinned");
+    });
+
+    it("Should fail without authentication", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "pin",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(token);
+    });
+});
+
+describe("Issue Pin Command (github only)", () => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge: "github" });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should report GraphQL errors", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "pin",
+                "404",
+                "--api",
+                "github",
+                "--api-url",
+                getApiUrl("github"),
+            ],
+            cwd: localRepoDir,
+            env: { GIT_FORGE_GITHUB_TOKEN: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Could not resolve to a node");
+    });
+});
+
+describe("Issue Pin Command (gitlab)", () => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge: "gitlab" });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should fail since GitLab doesn't support pinning", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "pin",
+                "1",
+                "--api",
+                "gitlab",
+                "--api-url",
+                getApiUrl("gitlab"),
+            ],
+            cwd: localRepoDir,
+            env: { GIT_FORGE_GITLAB_TOKEN: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("GitLab does not support pinning issues");
+    });
+});
@@ -0,0 +1,92 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitlab" } as const,
+    { forge: "gitea" } as const,
+])("Issue View Command ($forge)", ({ forge }) => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should render the issue", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "view",
+                "2",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toContain("# Fix crash on startup (#2)");
+        expect(result.stdout).toContain("State: open");
+        expect(result.stdout).toContain("Author: bob");
+        expect(result.stdout).toContain("Labels: bug, critical");
+        expect(result.stdout).toContain("/issues/2");
+        expect(result.stdout).toContain("The description of #2");
+    });
+
+    it("Should print the issue as JSON with the alias", () => {
+        const result = runGitForge({
+            args: [
+                "issue",
+                "v",
+                "2",
+                "--format",
+                "json",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const issues = JSON.parse(result.stdout);
+
+        expect(issues).toHaveLength(1);
+        expect(issues[0]).toMatchObject({
+            id: 2,
+            title: "Fix crash on startup",
+            body: "The description of #2",
+            state: "open",
+            author: "bob",
+            labels: ["bug", "critical"],
+        });
+    });
+
+    it("Should conflict --web with --format", () => {
+        const result = runGitForge({
+            args: ["issue", "view", "2", "--web", "--format", "json"],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("cannot be used with");
+    });
+});
@@ -0,0 +1,55 @@
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import { cleanupGitRepo, runGitForge, setupGitRepo } from "../utils.js";
+
+describe("Missing git executable", () => {
+    let tempDir: string;
+    let emptyPathDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: "github" });
+        emptyPathDir = mkdtempSync(path.join(tmpdir(), "git-forge-path-"));
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+
+        if (emptyPathDir) {
+            rmSync(emptyPathDir, { recursive: true, force: true });
+            emptyPathDir = "";
+        }
+    });
+
+    it("Should report a missing git with the usage exit code", () => {
+        const result = runGitForge({
+            args: ["issue", "list"],
+            cwd: tempDir,
+            env: { PATH: emptyPathDir },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).toBe(2);
+        expect(result.stderr).toBe(
+            "error: the `git` executable was not found in PATH; install git " +
+                "or, for the `config get/set/unset` commands, use --repo " +
+                "<HOST[:PORT]/OWNER/REPO>",
+        );
+    });
+
+    it("Should generate completions without git", () => {
+        const result = runGitForge({
+            args: ["completions", "bash"],
+            cwd: tempDir,
+            env: { PATH: emptyPathDir },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+});
@@ -4,6 +4,7 @@ import { afterEach, beforeEach, describe, expect, it } from "vitest";
 import {
     cleanupGitRepo,
     getApiUrl,
+    getRemoteUrl,
     runGitForge,
     setupGitRepoWithBareRemote,
 } from "../utils.js";
@@ -94,6 +95,51 @@ describe.each([
         expect(getCurrentCommit(localRepoDir)).toBe(prCommitSha);
     });
 
+    it("Should set the upstream of the branch to the PR ref", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "checkout",
+                prNumber,
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(
+            getGitConfig(localRepoDir, `branch.pr-${prNumber}.remote`),
+        ).toBe("origin");
+        expect(
+            getGitConfig(localRepoDir, `branch.pr-${prNumber}.merge`),
+        ).toBe(`refs/${remotePrRef}`);
+        expect(result.stderr).toContain("pushing can't update it");
+    });
+
+    it("Should not set the upstream of the branch with --no-track", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "checkout",
+                prNumber,
+                "--no-track",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(
+            getGitConfig(localRepoDir, `branch.pr-${prNumber}.merge`),
+        ).toBe("");
+    });
+
     it("Should checkout a PR using 'p' alias", () => {
         const result = runGitForge({
             args: [
@@ -173,6 +219,119 @@ describe.each([
         expect(result.exitCode).not.toBe(0);
     });
 
+    it("Should checkout a PR by its URL", () => {
+        const prUrl =
+            forge === "github" ? `https://localhost/user/repo/pull/${prNumber}`
+            : forge === "gitlab" ?
+                `https://localhost/user/repo/-/merge_requests/${prNumber}`
+            :   `https://localhost/user/repo/pulls/${prNumber}/files`;
+        const result = runGitForge({
+            args: [
+                "pr",
+                "checkout",
+                prUrl,
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(getCurrentBranch(localRepoDir)).toBe(`pr-${prNumber}`);
+        expect(getCurrentCommit(localRepoDir)).toBe(prCommitSha);
+    });
+
+    it("Should fail checking out a PR by the URL of another repository", () => {
+        execSync(`git remote set-url origin '${getRemoteUrl(forge)}'`, {
+            cwd: localRepoDir,
+            stdio: "ignore",
+        });
+
+        const result = runGitForge({
+            args: [
+                "pr",
+                "checkout",
+                "https://localhost/someone/else/pull/1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Use --remote");
+    });
+
+    it("Should fail checking out a PR by an invalid reference", () => {
+        const result = runGitForge({
+            args: ["pr", "checkout", "feature/login", "--api", forge],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("is neither a PR number nor the URL");
+    });
+
+    it("Should pick the open PR of a branch", () => {
+        // The mock server can't serve git, so only check the chosen PR
+        execSync(`git remote set-url origin '${getRemoteUrl(forge)}'`, {
+            cwd: localRepoDir,
+            stdio: "ignore",
+        });
+
+        const result = runGitForge({
+            args: [
+                "pr",
+                "checkout",
+                "--branch",
+                "feature/login",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+        const prRef =
+            forge === "gitlab" ? "merge-requests/1/head" : "pull/1/head";
+
+        expect(result.stderr).toContain(`Fetching ${prRef}:pr-1 from origin`);
+    });
+
+    it("Should fail when no open PR has the branch", () => {
+        execSync(`git remote set-url origin '${getRemoteUrl(forge)}'`, {
+            cwd: localRepoDir,
+            stdio: "ignore",
+        });
+
+        const result = runGitForge({
+            args: [
+                "pr",
+                "checkout",
+                "--branch",
+                "fix/auth-bug",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            "There is no open PR for the branch 'fix/auth-bug'",
+        );
+    });
+
     it("Should fail when PR ref doesn't exist", () => {
         const result = runGitForge({
             args: [
@@ -205,3 +364,14 @@ function getCurrentCommit(repoPath: string): string {
         encoding: "utf-8",
     }).trim();
 }
+
+function getGitConfig(repoPath: string, key: string): string {
+    try {
+        return execSync(`git config ${key}`, {
+            cwd: repoPath,
+            encoding: "utf-8",
+        }).trim();
+    } catch {
+        return "";
+    }
+}
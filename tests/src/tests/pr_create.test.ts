@@ -254,6 +254,95 @@ describe.each([
         expect(result.exitCode).toBe(0);
     });
 
+    it("Should print the created PR as JSON", () => {
+        switchBranchAndAddCommit("feature-branch", localRepoDir);
+
+        const result = runGitForge({
+            args: [
+                "pr",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "JSON PR",
+                "--format",
+                "json",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const pr = JSON.parse(result.stdout);
+
+        expect(pr).toMatchObject({
+            title: "JSON PR",
+            state: "open",
+            head: { ref: "feature-branch" },
+        });
+        expect(pr.id).toEqual(expect.any(Number));
+        expect(result.stderr).toContain("Pushing branch 'feature-branch'");
+    });
+
+    it("Should do nothing after creation with --post-action none", () => {
+        switchBranchAndAddCommit("feature-branch", localRepoDir);
+
+        const result = runGitForge({
+            args: [
+                "pr",
+                "create",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Quiet PR",
+                "--post-action",
+                "none",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).not.toContain("Opening PR in browser");
+    });
+
+    it("Should reject --remove-source-branch on forges other than GitLab", () => {
+        switchBranchAndAddCommit("feature-branch", localRepoDir);
+
+        const result = runGitForge({
+            args: [
+                "pr",
+                "create",
+                "--no-browser",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--title",
+                "Some Title",
+                "--remove-source-branch",
+                "--squash-on-merge",
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+            throwsError: forge !== "gitlab",
+        });
+
+        if (forge !== "gitlab") {
+            expect(result.exitCode).not.toBe(0);
+            expect(result.stderr).toContain("only supported for GitLab");
+            return;
+        }
+
+        expect(result.exitCode).toBe(0);
+    });
+
     it("Should fail when using both --fill and --editor", () => {
         switchBranchAndAddCommit("feature-branch", localRepoDir);
 
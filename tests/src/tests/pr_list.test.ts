@@ -150,6 +150,52 @@ describe.each([
         expect(rows[0]).toHaveProperty("url");
     });
 
+    it("Should accept gh field names as aliases", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "number,headRefName,isDraft",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const prs = JSON.parse(result.stdout);
+
+        expect(Object.keys(prs[0])).toEqual(["id", "head", "draft"]);
+        expect(prs[0].head).toHaveProperty("ref");
+    });
+
+    it("Should list the aliases for an unknown field", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "numbr",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("Unknown field 'numbr'");
+        expect(result.stderr).toContain("number (id)");
+    });
+
     it("Should list only draft pull requests", () => {
         const result = runGitForge({
             args: [
@@ -162,17 +208,11 @@ describe.each([
                 "--draft",
             ],
             cwd: tempDir,
-            throwsError: forge === "gitea",
         });
 
-        if (forge === "gitea") {
-            expect(result.exitCode).not.toBe(0);
-            expect(result.stderr).toContain("does not support");
-        } else {
-            expect(result.exitCode).toBe(0);
-            expectTsvFormat(result.stdout);
-            expect(parseTSV(result.stdout)).toHaveLength(2);
-        }
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+        expect(parseTSV(result.stdout)).toHaveLength(2);
     });
 
     it("Should list pull requests filtered by labels (enhancement+ui)", () => {
@@ -326,6 +366,29 @@ describe.each([
         expect(parseTSV(result.stdout)).toHaveLength(expectedLength);
     });
 
+    it("Should list pull requests filtered by milestone (v1.0)", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--milestone",
+                "v1.0",
+                "--state",
+                "all",
+                "--fields",
+                "id,milestone",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("2\tv1.0\n8\tv1.0\n10\tv1.0");
+    });
+
     it("Should list pull requests filtered by author (bob)", () => {
         const result = runGitForge({
             args: [
@@ -346,6 +409,48 @@ describe.each([
         expect(parseTSV(result.stdout)).toHaveLength(1);
     });
 
+    it("Should filter pull requests of configured bot authors", () => {
+        runGitForge({
+            args: [
+                "config",
+                "set",
+                "--scope",
+                "local",
+                "pr/list/bot-authors",
+                "bob",
+            ],
+            cwd: tempDir,
+        });
+
+        const botPrs = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--bot",
+            ],
+            cwd: tempDir,
+        });
+        const humanPrs = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--no-bot",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(parseTSV(botPrs.stdout)).toHaveLength(1);
+        expect(parseTSV(humanPrs.stdout)).toHaveLength(4);
+    });
+
     it("Should list pull requests with multiple options combined (auth, fields, labels)", () => {
         const result = runGitForge({
             args: [
@@ -376,6 +481,129 @@ describe.each([
         expect(rows[0]).toHaveProperty("draft");
     });
 
+    it("Should list approval fields only when requested", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "id,approvals_required,approvals_left",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const rows = parseTSV(result.stdout, [
+            "id",
+            "approvals_required",
+            "approvals_left",
+        ]);
+
+        expect(rows).toHaveLength(5);
+        expect(rows[0].approvals_required).toBe(forge === "gitlab" ? "2" : "");
+        expect(rows[0].approvals_left).toBe(forge === "gitlab" ? "1" : "");
+    });
+
+    it("Should list the review status when requested", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "id,review_status",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const rows = parseTSV(result.stdout, ["id", "review_status"]);
+
+        expect(rows).toHaveLength(5);
+        expect(rows[0].review_status).toBe(
+            forge === "gitlab" ? "review_required" : "approved",
+        );
+    });
+
+    it("Should list the review status with a token", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--auth",
+                "--fields",
+                "id,review_status",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const rows = parseTSV(result.stdout, ["id", "review_status"]);
+
+        expect(rows).toHaveLength(5);
+        expect(rows[0].review_status).toBe(
+            forge === "gitlab" ? "review_required" : "approved",
+        );
+    });
+
+    it("Should filter pull requests by review status", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--review",
+                "approved",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(parseTSV(result.stdout)).toHaveLength(forge === "gitlab" ? 0 : 5);
+    });
+
+    it("Should list mergeability and CI status when requested", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fields",
+                "id,mergeable,ci",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const rows = parseTSV(result.stdout, ["id", "mergeable", "ci"]);
+
+        expect(rows).toHaveLength(5);
+        expect(rows[0].mergeable).toBe("mergeable");
+        expect(rows[0].ci).toBe("failure");
+    });
+
     it("Should filter pull requests by query (Add)", () => {
         const result = runGitForge({
             args: [
@@ -422,4 +650,89 @@ describe.each([
         expect(result.exitCode).toBe(0);
         expect(parseTSV(result.stdout)).toHaveLength(0);
     });
+
+    it("Should print the prs and fail with --fail-if-any", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fail-if-any",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).toBe(1);
+        expect(parseTSV(result.stdout).length).toBeGreaterThan(0);
+        expect(result.stderr).toContain("--fail-if-any");
+    });
+
+    it("Should fail with --fail-if-none if no prs match", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--query",
+                "nonexistentterm",
+                "--fail-if-none",
+            ],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).toBe(1);
+        expect(result.stdout).toBe("");
+        expect(result.stderr).toContain("--fail-if-none");
+    });
+
+    it("Should succeed with --fail-if-none if prs match", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--fail-if-none",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(parseTSV(result.stdout).length).toBeGreaterThan(0);
+    });
+
+    it("Should print the same ids with --ids-only as with --fields id", () => {
+        const args = [
+            "pr",
+            "list",
+            "--api",
+            forge,
+            "--api-url",
+            getApiUrl(forge),
+            "--state",
+            "all",
+        ];
+        const idsOnlyResult = runGitForge({
+            args: [...args, "--ids-only", "--all-pages"],
+            cwd: tempDir,
+        });
+        const fieldsResult = runGitForge({
+            args: [...args, "--fields", "id"],
+            cwd: tempDir,
+        });
+
+        expect(idsOnlyResult.exitCode).toBe(0);
+        expect(idsOnlyResult.stdout).not.toBe("");
+        expect(idsOnlyResult.stdout).toBe(fieldsResult.stdout);
+    });
 });
@@ -0,0 +1,71 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe("PR Merge Command (gitlab)", () => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge: "gitlab" });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    function runMerge(...flags: string[]) {
+        return runGitForge({
+            args: [
+                "pr",
+                "merge",
+                "1",
+                ...flags,
+                "--api",
+                "gitlab",
+                "--api-url",
+                getApiUrl("gitlab"),
+            ],
+            cwd: localRepoDir,
+            env: { GIT_FORGE_GITLAB_TOKEN: "test-token" },
+        });
+    }
+
+    it("Should merge right away", () => {
+        const result = runMerge();
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).toContain("Merged PR #1");
+    });
+
+    it("Should report that the merge is only scheduled with --auto", () => {
+        const result = runMerge("--auto");
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).toContain(
+            "Scheduled PR #1 to be merged once its checks pass",
+        );
+    });
+
+    it("Should cancel the scheduled merge with --disable-auto", () => {
+        const result = runMerge("--disable-auto");
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).toContain(
+            "Cancelled the scheduled merge of PR #1",
+        );
+    });
+
+    it("Should reject --auto together with --disable-auto", () => {
+        const result = runMerge("--auto", "--disable-auto");
+
+        expect(result.exitCode).not.toBe(0);
+    });
+});
@@ -0,0 +1,132 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+])("PR Reviewer Command ($forge)", ({ forge, token }) => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should add a reviewer", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "reviewer",
+                "1",
+                "--add",
+                "bob",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Requested reviewers of PR #1: alice, bob");
+    });
+
+    it("Should remove a reviewer as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "reviewer",
+                "1",
+                "--remove",
+                "alice",
+                "--format",
+                "json",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+            ],
+            cwd: localRepoDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout)).toEqual({ id: 1, reviewers: [] });
+    });
+});
+
+describe("PR Reviewer Command (gitea)", () => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge: "gitea" });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should print the reviewers after the change", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "reviewer",
+                "1",
+                "--add",
+                "alice",
+                "--api",
+                "gitea",
+                "--api-url",
+                getApiUrl("gitea"),
+            ],
+            cwd: localRepoDir,
+            env: { GIT_FORGE_GITEA_TOKEN: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Requested reviewers of PR #1: alice");
+    });
+});
+
+describe("PR Reviewer Command (arguments)", () => {
+    let localRepoDir: string;
+
+    beforeEach(() => {
+        localRepoDir = setupGitRepo({ forge: "github" });
+    });
+
+    afterEach(() => {
+        if (localRepoDir) {
+            cleanupGitRepo(localRepoDir);
+            localRepoDir = "";
+        }
+    });
+
+    it("Should require --add or --remove", () => {
+        const result = runGitForge({
+            args: ["pr", "reviewer", "1"],
+            cwd: localRepoDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("--add <USERNAMES>");
+    });
+});
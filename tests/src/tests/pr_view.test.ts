@@ -0,0 +1,186 @@
+import { execSync } from "node:child_process";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+    setupGitRepoWithBareRemote,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitea" } as const,
+    { forge: "gitlab" } as const,
+])("PR View Command ($forge)", ({ forge }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["pr", "view", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should print only the description by default", () => {
+        const result = runGitForge({
+            args: ["pr", "view", "1", "--api", forge, "--api-url", getApiUrl(forge)],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toContain("alice opened the pull request");
+        expect(result.stdout).toContain("Adds the feature");
+        expect(result.stdout).not.toContain("Looks good now");
+    });
+
+    it("Should print the timeline with comments and reviews as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "view",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--comments",
+                "--reviews",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const events = JSON.parse(result.stdout);
+
+        expect(events.map((e: { kind: string }) => e.kind)).toEqual([
+            "description",
+            forge === "gitea" ? "dismissed" : "changes_requested",
+            "comment",
+            "approved",
+        ]);
+        expect(events[2]).toEqual({
+            kind: "comment",
+            author: "carol",
+            created_at: "2024-05-01T15:00:00Z",
+            body: "Looks good now",
+        });
+    });
+
+    it("Should list the closed issues with --links", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "view",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--links",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const output = JSON.parse(result.stdout);
+
+        expect(output.timeline[0].kind).toBe("description");
+        expect(output.links).toEqual({
+            closes: forge === "gitlab" ? [2, 5] : [2],
+        });
+    });
+
+    it("Should reject --links with a non-JSON format", () => {
+        const result = runGitForge({
+            args: ["pr", "view", "1", "--api", forge, "--links", "--format", "csv"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("--links can only be combined with --format json");
+    });
+
+    it("Should print single fields separated by tabs", () => {
+        const result = runGitForge({
+            args: [
+                "pr",
+                "view",
+                "1",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--field",
+                "title",
+                "--field",
+                "labels",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBe("Add login feature\tenhancement\n");
+    });
+
+    it("Should show how far the checked out PR branch diverged", () => {
+        const [localRepoDir, remoteRepoDir] = setupGitRepoWithBareRemote({
+            forge,
+        });
+
+        try {
+            execSync(
+                "git commit --allow-empty -m 'Upstream change' && " +
+                    "git push origin main && " +
+                    "git checkout -b feature/login HEAD~1 && " +
+                    "git commit --allow-empty -m 'Feature 1' && " +
+                    "git commit --allow-empty -m 'Feature 2'",
+                { cwd: localRepoDir, stdio: "ignore" },
+            );
+
+            const result = runGitForge({
+                args: [
+                    "pr",
+                    "view",
+                    "1",
+                    "--api",
+                    forge,
+                    "--api-url",
+                    getApiUrl(forge),
+                    "--no-fetch",
+                ],
+                cwd: localRepoDir,
+            });
+
+            expect(result.exitCode).toBe(0);
+            expect(result.stdout).toContain(
+                "Branch: 2 ahead, 1 behind origin/main — consider " +
+                    "`git rebase origin/main`",
+            );
+        } finally {
+            cleanupGitRepo(localRepoDir);
+            cleanupGitRepo(remoteRepoDir);
+        }
+    });
+});
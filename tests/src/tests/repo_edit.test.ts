@@ -0,0 +1,125 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github", token: "GIT_FORGE_GITHUB_TOKEN" } as const,
+    { forge: "gitea", token: "GIT_FORGE_GITEA_TOKEN" } as const,
+    { forge: "gitlab", token: "GIT_FORGE_GITLAB_TOKEN" } as const,
+])("Repo Edit Command ($forge)", ({ forge, token }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["repo", "edit", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should print the changes without saving them with --dry-run", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "edit",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--add-topic",
+                "rust",
+                "--remove-topic",
+                "git",
+                "--dry-run",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stderr).toContain("Dry run");
+        expect(result.stdout).toBe("- topics: cli, git\n+ topics: cli, rust");
+    });
+
+    it("Should edit the description and default branch", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "edit",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--description",
+                "A new description",
+                "--default-branch",
+                "develop",
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(JSON.parse(result.stdout)).toEqual({
+            description: {
+                old: "A test repository",
+                new: "A new description",
+            },
+            default_branch: { old: "main", new: "develop" },
+        });
+    });
+
+    it("Should fail if the new default branch doesn't exist", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "edit",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--default-branch",
+                "unknown",
+            ],
+            cwd: tempDir,
+            env: { [token]: "test-token" },
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain(
+            "The branch 'unknown' doesn't exist on the forge",
+        );
+    });
+
+    it("Should reject invalid topics", () => {
+        const result = runGitForge({
+            args: ["repo", "edit", "--api", forge, "--add-topic", "Not Valid"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("--add-topic");
+    });
+});
@@ -0,0 +1,144 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    ARCHIVED_REPO,
+    cleanupGitRepo,
+    expectTsvFormat,
+    getApiUrl,
+    parseTSV,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitea" } as const,
+    { forge: "gitlab" } as const,
+])("Repo List Command ($forge)", ({ forge }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["repo", "list", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should list the repositories of an organization", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "ls",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--org",
+                "acme",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expectTsvFormat(result.stdout);
+
+        const rows = parseTSV(result.stdout, [
+            "name",
+            "visibility",
+            "stars",
+            "default_branch",
+            "pushed_at",
+        ]);
+
+        expect(rows).toHaveLength(3);
+        expect(rows[0]).toMatchObject({
+            name: "acme/repo",
+            visibility: "public",
+            stars: "12",
+            default_branch: "main",
+        });
+    });
+
+    it("Should filter the repositories of a user", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--user",
+                "alice",
+                "--no-archived",
+                "--source",
+                "--format",
+                "json",
+                "--fields",
+                "name,archived,fork,clone_url",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const repos = JSON.parse(result.stdout);
+
+        expect(repos).toHaveLength(1);
+        expect(repos[0]).toMatchObject({
+            name: "alice/repo",
+            archived: false,
+            fork: false,
+        });
+        expect(repos[0].clone_url).toMatch(/alice\/repo\.git$/);
+    });
+
+    it("Should list only archived repositories with --archived", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "list",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--org",
+                "acme",
+                "--archived",
+                "--fields",
+                "name",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(parseTSV(result.stdout, ["name"])).toEqual([
+            { name: `acme/${ARCHIVED_REPO}` },
+        ]);
+    });
+
+    it("Should require --org or --user", () => {
+        const result = runGitForge({
+            args: ["repo", "list"],
+            cwd: tempDir,
+            throwsError: true,
+        });
+
+        expect(result.exitCode).not.toBe(0);
+        expect(result.stderr).toContain("--org");
+    });
+});
@@ -0,0 +1,77 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+
+import {
+    cleanupGitRepo,
+    getApiUrl,
+    runGitForge,
+    setupGitRepo,
+} from "../utils.js";
+
+describe.each([
+    { forge: "github" } as const,
+    { forge: "gitea" } as const,
+    { forge: "gitlab" } as const,
+])("Repo View Command ($forge)", ({ forge }) => {
+    let tempDir: string;
+
+    beforeEach(() => {
+        tempDir = setupGitRepo({ forge: forge });
+    });
+
+    afterEach(() => {
+        if (tempDir) {
+            cleanupGitRepo(tempDir);
+            tempDir = "";
+        }
+    });
+
+    it("Should display help", () => {
+        const result = runGitForge({
+            args: ["repo", "view", "--help"],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toBeTruthy();
+    });
+
+    it("Should print the metadata of the repository", () => {
+        const result = runGitForge({
+            args: ["repo", "view", "--api", forge, "--api-url", getApiUrl(forge)],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+        expect(result.stdout).toContain("user/repo");
+        expect(result.stdout).toContain("A test repository");
+        expect(result.stdout).toContain("Default branch: main");
+        expect(result.stdout).toContain("Topics:         cli, git");
+        expect(result.stdout).toContain("Languages:      Rust, TypeScript");
+        expect(result.stdout).not.toContain("Archived");
+    });
+
+    it("Should print the repository as JSON", () => {
+        const result = runGitForge({
+            args: [
+                "repo",
+                "view",
+                "--api",
+                forge,
+                "--api-url",
+                getApiUrl(forge),
+                "--format",
+                "json",
+            ],
+            cwd: tempDir,
+        });
+
+        expect(result.exitCode).toBe(0);
+
+        const repo = JSON.parse(result.stdout);
+
+        expect(repo.name).toBe("user/repo");
+        expect(repo.topics).toEqual(["cli", "git"]);
+        expect(repo.languages).toEqual(["Rust", "TypeScript"]);
+        expect(repo.archived).toBe(false);
+    });
+});
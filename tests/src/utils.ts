@@ -7,6 +7,70 @@ import { expect } from "vitest";
 export const GITHUB_PORT = 3001;
 export const GITLAB_PORT = 3002;
 export const GITEA_PORT = 3003;
+/** Name of a repository that the mock servers report as archived. */
+export const ARCHIVED_REPO = "archived";
+/**
+ * The branches that exist on the mock servers. `merged` tells whether the
+ * default branch `main` contains the branch.
+ */
+export const BRANCHES = [
+    {
+        name: "main",
+        sha: "a1b2c3d4",
+        date: "2024-05-01T12:00:00Z",
+        protected: true,
+        merged: true,
+    },
+    {
+        name: "develop",
+        sha: "b2c3d4e5",
+        date: "2024-04-20T09:30:00Z",
+        protected: true,
+        merged: false,
+    },
+    {
+        name: "feature/login",
+        sha: "c3d4e5f6",
+        date: "2024-03-01T15:00:00Z",
+        protected: false,
+        merged: false,
+    },
+    {
+        name: "fix/auth-bug",
+        sha: "d4e5f6a7",
+        date: "2023-06-01T08:00:00Z",
+        protected: false,
+        merged: true,
+    },
+];
+
+/**
+ * The repositories that the mock servers list for every organization and
+ * user.
+ */
+export const REPOS = [
+    {
+        name: "repo",
+        stars: 12,
+        archived: false,
+        fork: false,
+        pushedAt: "2024-05-01T12:00:00Z",
+    },
+    {
+        name: ARCHIVED_REPO,
+        stars: 3,
+        archived: true,
+        fork: false,
+        pushedAt: "2022-01-10T08:00:00Z",
+    },
+    {
+        name: "upstream-fork",
+        stars: 0,
+        archived: false,
+        fork: true,
+        pushedAt: "2024-02-15T10:00:00Z",
+    },
+];
 
 const BINARY_PATH = path.resolve(
     import.meta.dirname,
@@ -17,11 +81,14 @@ export function runGitForge({
     args,
     cwd,
     env = {},
+    input,
     throwsError = false,
 }: {
     args: string[];
     cwd: string;
     env?: Record<string, string>;
+    /** Written to the stdin of git-forge. */
+    input?: string;
     throwsError?: boolean;
 }): {
     stdout: string;
@@ -32,6 +99,7 @@ export function runGitForge({
         cwd,
         env,
         encoding: "utf-8",
+        input,
     });
     const stdout = result.stdout.trim();
     const stderr = result.stderr.trim();
@@ -193,8 +261,8 @@ export function expectTsvFormat(output: string): void {
 }
 
 export function getRemoteUrl(forge: ApiType) {
-    // The local express server runs on http, but git-forge only accepts https
-    // or ssh URLs for parsing
+    // The local express server runs on http, but the remote uses https so that
+    // the web URLs match those of real forges. The API is reached via --api-url
     return {
         github: `https://localhost:${GITHUB_PORT}/user/repo.git`,
         gitlab: `https://localhost:${GITLAB_PORT}/user/repo.git`,